@@ -18,6 +18,7 @@ use ibc_relayer::{
 use crate::{
     commands::CliCmd,
     components::{JsonTracing, PrettyTracing},
+    conclude::OutputFormat,
     entry::EntryPoint,
     tracing_handle::{spawn_reload_handler, ReloadHandle},
 };
@@ -48,8 +49,8 @@ pub struct CliApp {
     /// Application state.
     state: application::State<Self>,
 
-    /// Toggle json output on/off. Changed with the global config option `-j` / `--json`.
-    json_output: bool,
+    /// Format of the output. Changed with the global config options `--output` and `--json`.
+    output_format: OutputFormat,
 
     /// Enable the given debug sections.
     debug_sections: Vec<DebugSection>,
@@ -67,7 +68,7 @@ impl Default for CliApp {
         Self {
             config: CfgCell::default(),
             state: application::State::default(),
-            json_output: false,
+            output_format: OutputFormat::default(),
             debug_sections: Vec::default(),
             config_path: None,
         }
@@ -75,9 +76,9 @@ impl Default for CliApp {
 }
 
 impl CliApp {
-    /// Whether or not JSON output is enabled
-    pub fn json_output(&self) -> bool {
-        self.json_output
+    /// Returns the format of the output
+    pub fn output_format(&self) -> OutputFormat {
+        self.output_format
     }
 
     /// Returns the enabled debug sections
@@ -188,8 +189,8 @@ impl Application for CliApp {
             .expect("invalid config")
             .unwrap_or_default();
 
-        // Update the `json_output` flag used by `conclude::Output`
-        self.json_output = command.json;
+        // Update the `output_format` used by `conclude::Output`
+        self.output_format = command.output_format();
 
         // Update the `debug_sections` flag
         self.debug_sections = command.debug.iter().copied().map(Into::into).collect();
@@ -204,7 +205,7 @@ impl Application for CliApp {
             .as_ref()
             .is_some_and(|cmd| matches!(cmd, CliCmd::Start(_)));
 
-        if command.json {
+        if self.output_format == OutputFormat::Json {
             // Enable JSON by using the crate-level `Tracing`
             let tracing = JsonTracing::new(config.global, &self.debug_sections)?;
             Ok(vec![Box::new(terminal), Box::new(tracing)])
@@ -143,33 +143,79 @@ where
         max_gas: Some(400000),
         gas_adjustment: None,
         gas_multiplier: Some(GasMultiplier::new(1.1).unwrap()),
+        gas_price_decimals: None,
         dynamic_gas_price,
         fee_granter: None,
         max_msg_num: MaxMsgNum::default(),
         max_tx_size: MaxTxSize::default(),
         max_grpc_decoding_size: default::max_grpc_decoding_size(),
         query_packets_chunk_size: default::query_packets_chunk_size(),
+        max_concurrent_tx_confirmations: default::max_concurrent_tx_confirmations(),
         clock_drift: default::clock_drift(),
         max_block_time: default::max_block_time(),
         trusting_period: None,
         client_refresh_rate: default::client_refresh_rate(),
+        trusting_period_source: Default::default(),
+        timeout_timestamp_unit: Default::default(),
+        grpc_height_header: Default::default(),
+        timeout_batch: None,
+        successor_chain_id: None,
+        max_concurrent_client_updates: None,
+        max_parallel_header_builds: None,
+        max_concurrent_client_state_queries: default::max_concurrent_client_state_queries(),
+        detect_misbehaviour: None,
+        multisig: None,
+        tx_confirmation_strategy: Default::default(),
         ccv_consumer_chain: false,
         memo_prefix: Memo::default(),
         memo_overwrite: None,
+        memo_overrides: BTreeMap::new(),
+        max_memo_bytes: None,
         proof_specs: Default::default(),
         trust_threshold: TrustThreshold::default(),
         gas_price: GasPrice {
             price: avg_gas_price,
             denom: asset.base.to_owned(),
-        },
+        }
+        .into(),
         packet_filter: packet_filter.unwrap_or_default(),
         address_type: AddressType::default(),
+        signer_encoding: Default::default(),
         sequential_batch_tx: false,
         extension_options: Vec::new(),
         compat_mode: None,
         clear_interval: None,
         excluded_sequences: ExcludedSequences::new(BTreeMap::new()),
         allow_ccq: true,
+        gas_estimation_sampling: Default::default(),
+        tx_priority: Default::default(),
+        retry_strategy: Default::default(),
+        shadow_gas_strategy: None,
+        reference_gas_price: None,
+        txfees_gas_price: None,
+        block_gas_usage: None,
+        acquire_fee_denom_command: Vec::new(),
+        extra_fee_denoms: Vec::new(),
+        min_tx_interval: None,
+        receipt_recheck_delay: None,
+        daily_fee_budget: None,
+        sticky_session: None,
+        relayer_attribution_memo: None,
+        max_requests_per_second: None,
+        recv_signer_whitelist_query: None,
+        denom_key_names: Default::default(),
+        signing_accounts: Vec::new(),
+        skip_empty_memo_channels: Vec::new(),
+        pruned_height_handling: Default::default(),
+        account_query: None,
+        min_confirmation_blocks: None,
+        batch_failure_mode: Default::default(),
+        proof_height_strategy: Default::default(),
+        packet_commitment_source: Default::default(),
+        local_trust_threshold: None,
+        channel_overrides: Default::default(),
+        canaries: Default::default(),
+        packet_data_encodings: Default::default(),
     }))
 }
 
@@ -16,13 +16,14 @@ mod start;
 mod tx;
 mod update;
 mod upgrade;
+mod util;
 mod version;
 
 use self::{
     clear::ClearCmds, completions::CompletionsCmd, config::ConfigCmd, create::CreateCmds,
     evidence::EvidenceCmd, fee::FeeCmd, health::HealthCheckCmd, keys::KeysCmd, listen::ListenCmd,
     logs::LogsCmd, misbehaviour::MisbehaviourCmd, query::QueryCmd, start::StartCmd, tx::TxCmd,
-    update::UpdateCmds, upgrade::UpgradeCmds, version::VersionCmd,
+    update::UpdateCmds, upgrade::UpgradeCmds, util::UtilCmd, version::VersionCmd,
 };
 
 use core::time::Duration;
@@ -103,6 +104,10 @@ pub enum CliCmd {
     /// Performs a health check of all chains in the config
     HealthCheck(HealthCheckCmd),
 
+    /// Utilities which do not interact with the chains
+    #[clap(subcommand)]
+    Util(UtilCmd),
+
     /// Generate auto-complete scripts for different shells.
     #[clap(display_order = 1000)]
     Completions(CompletionsCmd),
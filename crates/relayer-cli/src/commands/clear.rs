@@ -9,7 +9,7 @@ use ibc_relayer::chain::handle::{BaseChainHandle, ChainHandle};
 use ibc_relayer::chain::requests::{IncludeProof, QueryChannelRequest, QueryHeight};
 use ibc_relayer::config::Config;
 use ibc_relayer::link::error::LinkError;
-use ibc_relayer::link::{Link, LinkParameters};
+use ibc_relayer::link::{Link, LinkParameters, SequenceRange};
 use ibc_relayer::util::seq_range::parse_seq_range;
 use ibc_relayer_types::core::ics04_channel::packet::Sequence;
 use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
@@ -71,6 +71,24 @@ pub struct ClearPacketsCmd {
     )]
     packet_sequences: Vec<RangeInclusive<Sequence>>,
 
+    #[clap(
+        long = "seq-start",
+        value_name = "SEQUENCE",
+        conflicts_with = "packet-sequences",
+        help = "Only clear the pending packets whose sequence is greater than or equal \
+                to this one, on both chains"
+    )]
+    seq_start: Option<Sequence>,
+
+    #[clap(
+        long = "seq-end",
+        value_name = "SEQUENCE",
+        conflicts_with = "packet-sequences",
+        help = "Only clear the pending packets whose sequence is lower than or equal \
+                to this one, on both chains"
+    )]
+    seq_end: Option<Sequence>,
+
     #[clap(
         long = "key-name",
         help = "Use the given signing key for the specified chain (default: `key_name` config)"
@@ -88,6 +106,14 @@ pub struct ClearPacketsCmd {
         help = "Number of packets to fetch at once from the chain (default: `query_packets_chunk_size` config)"
     )]
     query_packets_chunk_size: Option<usize>,
+
+    #[clap(
+        long = "stop-on-completion",
+        conflicts_with_all = &["packet-sequences", "seq-start", "seq-end"],
+        help = "Keep clearing until all the packets pending on the channel are relayed and \
+                acknowledged, and fail if some are still pending after the last attempt"
+    )]
+    stop_on_completion: bool,
 }
 
 impl Override<Config> for ClearPacketsCmd {
@@ -116,6 +142,12 @@ impl Runnable for ClearPacketsCmd {
     fn run(&self) {
         let config = app_config();
 
+        // The sequences of the packets to clear in both directions, all of them by default
+        let sequence_range = match SequenceRange::new(self.seq_start, self.seq_end) {
+            Ok(sequence_range) => sequence_range,
+            Err(e) => Output::error(e).exit(),
+        };
+
         let chains = match spawn_chain_counterparty::<BaseChainHandle>(
             &config,
             &self.chain_id,
@@ -186,6 +218,9 @@ impl Runnable for ClearPacketsCmd {
             max_memo_size: config.mode.packets.ics20_max_memo_size,
             max_receiver_size: config.mode.packets.ics20_max_receiver_size,
             exclude_src_sequences,
+            max_clear_duration: None,
+            verification: config.mode.packets.channel_verification(),
+            clear_order: config.mode.packets.clear_order,
         };
 
         let counterparty_channel_id = match channel.counterparty().channel_id() {
@@ -205,6 +240,9 @@ impl Runnable for ClearPacketsCmd {
             max_memo_size: config.mode.packets.ics20_max_memo_size,
             max_receiver_size: config.mode.packets.ics20_max_receiver_size,
             exclude_src_sequences: exclude_dst_sequences,
+            max_clear_duration: None,
+            verification: config.mode.packets.channel_verification(),
+            clear_order: config.mode.packets.clear_order,
         };
 
         let fwd_link = match Link::new_from_opts(
@@ -224,28 +262,43 @@ impl Runnable for ClearPacketsCmd {
             Err(e) => Output::error(e).exit(),
         };
 
+        if self.stop_on_completion {
+            match fwd_link.clear_packets_until_complete(&rev_link) {
+                Ok(ev_list) => Output::success(ev_list).exit(),
+                Err(e) => Output::error(e).exit(),
+            }
+        }
+
         let mut ev_list = vec![];
 
+        let sequence_filter = sequence_filter(sequence_range);
+
+        let fwd_sequences = if self.packet_sequences.is_empty() {
+            sequence_filter.clone()
+        } else {
+            self.packet_sequences.clone()
+        };
+
         // Schedule RecvPacket messages for pending packets in both directions or,
         // if packet sequences are provided, only on the specified chain.
         // This may produce pending acks which will be processed in the next phase.
         run_and_collect_events("forward recv and timeout", &mut ev_list, || {
-            fwd_link.relay_recv_packet_and_timeout_messages(self.packet_sequences.clone())
+            fwd_link.relay_recv_packet_and_timeout_messages(fwd_sequences.clone())
         });
         if self.packet_sequences.is_empty() {
             run_and_collect_events("reverse recv and timeout", &mut ev_list, || {
-                rev_link.relay_recv_packet_and_timeout_messages(vec![])
+                rev_link.relay_recv_packet_and_timeout_messages(sequence_filter.clone())
             });
         }
 
         // Schedule AckPacket messages in both directions or, if packet sequences are provided,
         // only on the specified chain.
         run_and_collect_events("reverse ack", &mut ev_list, || {
-            rev_link.relay_ack_packet_messages(self.packet_sequences.clone())
+            rev_link.relay_ack_packet_messages(fwd_sequences)
         });
         if self.packet_sequences.is_empty() {
             run_and_collect_events("forward ack", &mut ev_list, || {
-                fwd_link.relay_ack_packet_messages(vec![])
+                fwd_link.relay_ack_packet_messages(sequence_filter)
             });
         }
 
@@ -253,6 +306,16 @@ impl Runnable for ClearPacketsCmd {
     }
 }
 
+/// Turns the range of sequences given with `--seq-start` and `--seq-end` into a filter
+/// of the sequences to clear, which is empty if all the sequences are to be cleared.
+fn sequence_filter(range: SequenceRange) -> Vec<RangeInclusive<Sequence>> {
+    if range.is_all() {
+        return vec![];
+    }
+
+    vec![range.start.unwrap_or(Sequence::MIN)..=range.end.unwrap_or(Sequence::MAX)]
+}
+
 fn run_and_collect_events<F>(desc: &str, ev_list: &mut Vec<IbcEvent>, f: F)
 where
     F: FnOnce() -> Result<Vec<IbcEvent>, LinkError>,
@@ -265,11 +328,12 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::ClearPacketsCmd;
+    use super::{sequence_filter, ClearPacketsCmd};
 
     use std::str::FromStr;
 
     use abscissa_core::clap::Parser;
+    use ibc_relayer::link::SequenceRange;
     use ibc_relayer_types::core::ics04_channel::packet::Sequence;
     use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
 
@@ -281,9 +345,12 @@ mod tests {
                 port_id: PortId::from_str("port_id").unwrap(),
                 channel_id: ChannelId::from_str("channel-07").unwrap(),
                 packet_sequences: vec![],
+                seq_start: None,
+                seq_end: None,
                 key_name: None,
                 counterparty_key_name: None,
-                query_packets_chunk_size: None
+                query_packets_chunk_size: None,
+                stop_on_completion: false,
             },
             ClearPacketsCmd::parse_from([
                 "test",
@@ -305,9 +372,12 @@ mod tests {
                 port_id: PortId::from_str("port_id").unwrap(),
                 channel_id: ChannelId::from_str("channel-07").unwrap(),
                 packet_sequences: vec![],
+                seq_start: None,
+                seq_end: None,
                 key_name: None,
                 counterparty_key_name: None,
-                query_packets_chunk_size: None
+                query_packets_chunk_size: None,
+                stop_on_completion: false,
             },
             ClearPacketsCmd::parse_from([
                 "test",
@@ -332,9 +402,12 @@ mod tests {
                     Sequence::from(1)..=Sequence::from(1),
                     Sequence::from(10)..=Sequence::from(20)
                 ],
+                seq_start: None,
+                seq_end: None,
                 key_name: Some("key_name".to_owned()),
                 counterparty_key_name: None,
-                query_packets_chunk_size: None
+                query_packets_chunk_size: None,
+                stop_on_completion: false,
             },
             ClearPacketsCmd::parse_from([
                 "test",
@@ -352,6 +425,98 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_clear_packets_sequence_range() {
+        assert_eq!(
+            ClearPacketsCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                port_id: PortId::from_str("port_id").unwrap(),
+                channel_id: ChannelId::from_str("channel-07").unwrap(),
+                packet_sequences: vec![],
+                seq_start: Some(Sequence::from(5)),
+                seq_end: Some(Sequence::from(10)),
+                key_name: None,
+                counterparty_key_name: None,
+                query_packets_chunk_size: None,
+                stop_on_completion: false,
+            },
+            ClearPacketsCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--port",
+                "port_id",
+                "--channel",
+                "channel-07",
+                "--seq-start",
+                "5",
+                "--seq-end",
+                "10"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_clear_packets_inverted_sequence_range() {
+        let cmd = ClearPacketsCmd::parse_from([
+            "test",
+            "--chain",
+            "chain_id",
+            "--port",
+            "port_id",
+            "--channel",
+            "channel-07",
+            "--seq-start",
+            "10",
+            "--seq-end",
+            "5",
+        ]);
+
+        // The command fails instead of clearing no packet
+        assert!(SequenceRange::new(cmd.seq_start, cmd.seq_end).is_err());
+    }
+
+    #[test]
+    fn test_clear_packets_sequence_range_with_sequences() {
+        assert!(ClearPacketsCmd::try_parse_from([
+            "test",
+            "--chain",
+            "chain_id",
+            "--port",
+            "port_id",
+            "--channel",
+            "channel-07",
+            "--packet-sequences",
+            "1,10..20",
+            "--seq-start",
+            "5"
+        ])
+        .is_err())
+    }
+
+    #[test]
+    fn test_clear_packets_sequence_filter() {
+        let range = |start: Option<u64>, end: Option<u64>| {
+            sequence_filter(
+                SequenceRange::new(start.map(Sequence::from), end.map(Sequence::from)).unwrap(),
+            )
+        };
+
+        assert!(range(None, None).is_empty());
+        assert_eq!(
+            range(Some(5), Some(10)),
+            vec![Sequence::from(5)..=Sequence::from(10)]
+        );
+        assert_eq!(
+            range(Some(5), None),
+            vec![Sequence::from(5)..=Sequence::MAX]
+        );
+        assert_eq!(
+            range(None, Some(10)),
+            vec![Sequence::MIN..=Sequence::from(10)]
+        );
+    }
+
     #[test]
     fn test_clear_packets_key_name() {
         assert_eq!(
@@ -360,9 +525,12 @@ mod tests {
                 port_id: PortId::from_str("port_id").unwrap(),
                 channel_id: ChannelId::from_str("channel-07").unwrap(),
                 packet_sequences: vec![],
+                seq_start: None,
+                seq_end: None,
                 key_name: Some("key_name".to_owned()),
                 counterparty_key_name: None,
-                query_packets_chunk_size: None
+                query_packets_chunk_size: None,
+                stop_on_completion: false,
             },
             ClearPacketsCmd::parse_from([
                 "test",
@@ -386,9 +554,12 @@ mod tests {
                 port_id: PortId::from_str("port_id").unwrap(),
                 channel_id: ChannelId::from_str("channel-07").unwrap(),
                 packet_sequences: vec![],
+                seq_start: None,
+                seq_end: None,
                 key_name: None,
                 counterparty_key_name: Some("counterparty_key_name".to_owned()),
-                query_packets_chunk_size: None
+                query_packets_chunk_size: None,
+                stop_on_completion: false,
             },
             ClearPacketsCmd::parse_from([
                 "test",
@@ -412,9 +583,12 @@ mod tests {
                 port_id: PortId::from_str("port_id").unwrap(),
                 channel_id: ChannelId::from_str("channel-07").unwrap(),
                 packet_sequences: vec![],
+                seq_start: None,
+                seq_end: None,
                 key_name: None,
                 counterparty_key_name: Some("counterparty_key_name".to_owned()),
                 query_packets_chunk_size: Some(100),
+                stop_on_completion: false,
             },
             ClearPacketsCmd::parse_from([
                 "test",
@@ -432,6 +606,51 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_clear_packets_stop_on_completion() {
+        assert_eq!(
+            ClearPacketsCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                port_id: PortId::from_str("port_id").unwrap(),
+                channel_id: ChannelId::from_str("channel-07").unwrap(),
+                packet_sequences: vec![],
+                seq_start: None,
+                seq_end: None,
+                key_name: None,
+                counterparty_key_name: None,
+                query_packets_chunk_size: None,
+                stop_on_completion: true,
+            },
+            ClearPacketsCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--port",
+                "port_id",
+                "--channel",
+                "channel-07",
+                "--stop-on-completion"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_clear_packets_stop_on_completion_with_sequences() {
+        assert!(ClearPacketsCmd::try_parse_from([
+            "test",
+            "--chain",
+            "chain_id",
+            "--port",
+            "port_id",
+            "--channel",
+            "channel-07",
+            "--packet-sequences",
+            "1,10..20",
+            "--stop-on-completion"
+        ])
+        .is_err())
+    }
+
     #[test]
     fn test_clear_packets_no_chan() {
         assert!(ClearPacketsCmd::try_parse_from([
@@ -11,7 +11,7 @@ use tokio::runtime::Runtime as TokioRuntime;
 use tendermint::block::Height as TendermintHeight;
 use tendermint::evidence::{DuplicateVoteEvidence, LightClientAttackEvidence};
 use tendermint::validator;
-use tendermint_rpc::{Client, Paging};
+use tendermint_rpc::Paging;
 
 use ibc_relayer::chain::cosmos::CosmosSdkChain;
 use ibc_relayer::chain::endpoint::ChainEndpoint;
@@ -6,7 +6,7 @@ use eyre::eyre;
 use ibc_relayer::{
     chain::handle::ChainHandle,
     config::Config,
-    transfer::{build_transfer_messages, send_messages, TransferOptions},
+    transfer::{build_transfer_messages, check_memo_size, send_messages, TransferOptions},
 };
 use ibc_relayer_types::{
     applications::{
@@ -173,13 +173,19 @@ impl FeeTransferCmd {
             )
         })?;
 
-        config.find_chain(&self.dst_chain_id).ok_or_else(|| {
+        let dst_chain_config = config.find_chain(&self.dst_chain_id).ok_or_else(|| {
             eyre!(
                 "missing configuration for destination chain '{}'",
                 self.dst_chain_id
             )
         })?;
 
+        check_memo_size(
+            self.memo.as_deref(),
+            dst_chain_config.max_memo_bytes(),
+            &self.dst_chain_id,
+        )?;
+
         let denom = self.denom.clone();
 
         let number_msgs = self.number_msgs.unwrap_or(1);
@@ -1,6 +1,5 @@
 use abscissa_core::clap::Parser;
 
-use ibc_relayer::chain::endpoint::HealthCheck::*;
 use ibc_relayer::chain::handle::ChainHandle;
 
 use crate::cli_utils::spawn_chain_runtime;
@@ -23,12 +22,7 @@ impl Runnable for HealthCheckCmd {
                 spawn_chain_runtime(&config, ch.id()).unwrap_or_else(exit_with_unrecoverable_error);
 
             match chain.health_check() {
-                Ok(Healthy) => info!("chain is healthy"),
-                Ok(Unhealthy(_)) => {
-                    // No need to print the error here as it's already printed in `Chain::health_check`
-                    // TODO(romac): Move the printing code here and in the supervisor/registry
-                    warn!("chain is not healthy")
-                }
+                Ok(report) => report.log(),
                 Err(e) => error!("failed to perform health check, reason: {}", e.detail()),
             }
         }
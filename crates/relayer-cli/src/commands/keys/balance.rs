@@ -9,7 +9,7 @@ use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 
 use crate::application::app_config;
 use crate::cli_utils::spawn_chain_runtime;
-use crate::conclude::{exit_with_unrecoverable_error, json, Output};
+use crate::conclude::{exit_with_unrecoverable_error, structured, Output};
 
 /// The data structure that represents the arguments when invoking the `keys balance` CLI command.
 ///
@@ -71,7 +71,7 @@ impl Runnable for KeyBalanceCmd {
 
 fn get_balance(chain: impl ChainHandle, key_name: Option<String>, denom: Option<String>) {
     match chain.query_balance(key_name.clone(), denom) {
-        Ok(balance) if json() => Output::success(balance).exit(),
+        Ok(balance) if structured() => Output::success(balance).exit(),
         Ok(balance) => {
             // Retrieve the key name string to output.
             let key_name = key_name.unwrap_or_else(|| {
@@ -96,7 +96,7 @@ fn get_balance(chain: impl ChainHandle, key_name: Option<String>, denom: Option<
 
 fn get_balances(chain: impl ChainHandle, key_name: Option<String>) {
     match chain.query_all_balances(key_name.clone()) {
-        Ok(balances) if json() => Output::success(balances).exit(),
+        Ok(balances) if structured() => Output::success(balances).exit(),
         Ok(balances) => {
             // Retrieve the key name string to output.
             let key_name = key_name.unwrap_or_else(|| {
@@ -5,7 +5,7 @@ use abscissa_core::clap::Parser;
 use abscissa_core::{Command, Runnable};
 
 use crate::conclude::Output;
-use crate::{application::app_config, conclude::json};
+use crate::{application::app_config, conclude::structured};
 use ibc_relayer::config::{ChainConfig, Config};
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 
@@ -43,7 +43,7 @@ impl Runnable for KeysListCmd {
         };
 
         match opts.chain_config.list_keys() {
-            Ok(keys) if json() => {
+            Ok(keys) if structured() => {
                 let keys = keys.into_iter().collect::<HashMap<_, _>>();
                 Output::success(keys).exit()
             }
@@ -14,10 +14,11 @@ use tokio::runtime::Runtime as TokioRuntime;
 use tracing::{error, info, instrument};
 
 use ibc_relayer::chain::cosmos::fetch_compat_mode;
+use ibc_relayer::chain::cosmos::request_rate::{PacedRpcClient, RequestRateLimiter};
 use ibc_relayer::chain::handle::Subscription;
 use ibc_relayer::config::{ChainConfig, EventSourceMode};
 use ibc_relayer::error::Error;
-use ibc_relayer::event::source::EventSource;
+use ibc_relayer::event::source::{EventSource, ReconnectBackoff};
 use ibc_relayer::HERMES_VERSION;
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use ibc_relayer_types::events::IbcEvent;
@@ -150,11 +151,19 @@ fn subscribe(
     match chain_config {
         ChainConfig::CosmosSdk(config) | ChainConfig::Namada(config) => {
             let (event_source, monitor_tx) = match &config.event_source {
-                EventSourceMode::Push { url, batch_delay } => EventSource::websocket(
+                EventSourceMode::Push {
+                    url,
+                    batch_delay,
+                    queries,
+                    reconnect_backoff,
+                    max_reconnect_backoff,
+                } => EventSource::websocket(
                     chain_config.id().clone(),
                     url.clone(),
                     compat_mode,
                     *batch_delay,
+                    queries.iter().cloned().map(Into::into).collect(),
+                    ReconnectBackoff::new(*reconnect_backoff, *max_reconnect_backoff),
                     rt,
                 ),
                 EventSourceMode::Pull {
@@ -167,11 +176,17 @@ fn subscribe(
                         .map_err(|e| Error::rpc(config.rpc_addr.clone(), e))?;
                     rpc_client.set_compat_mode(compat_mode);
 
+                    let request_limiter = config
+                        .max_requests_per_second
+                        .map(|rps| Arc::new(RequestRateLimiter::new(rps)));
+
                     EventSource::rpc(
                         chain_config.id().clone(),
-                        rpc_client,
+                        PacedRpcClient::new(rpc_client, request_limiter),
                         *interval,
                         *max_retries,
+                        // Only the relayer resumes after the last block it processed
+                        None,
                         rt,
                     )
                 }
@@ -184,11 +199,19 @@ fn subscribe(
         }
         ChainConfig::Penumbra(config) => {
             let (event_source, monitor_tx) = match &config.event_source {
-                EventSourceMode::Push { url, batch_delay } => EventSource::websocket(
+                EventSourceMode::Push {
+                    url,
+                    batch_delay,
+                    queries,
+                    reconnect_backoff,
+                    max_reconnect_backoff,
+                } => EventSource::websocket(
                     chain_config.id().clone(),
                     url.clone(),
                     compat_mode,
                     *batch_delay,
+                    queries.iter().cloned().map(Into::into).collect(),
+                    ReconnectBackoff::new(*reconnect_backoff, *max_reconnect_backoff),
                     rt,
                 ),
                 EventSourceMode::Pull {
@@ -196,9 +219,11 @@ fn subscribe(
                     max_retries,
                 } => EventSource::rpc(
                     chain_config.id().clone(),
-                    HttpClient::new(config.rpc_addr.clone())?,
+                    PacedRpcClient::new(HttpClient::new(config.rpc_addr.clone())?, None),
                     *interval,
                     *max_retries,
+                    // Only the relayer resumes after the last block it processed
+                    None,
                     rt,
                 ),
             }?;
@@ -225,9 +250,9 @@ fn detect_compatibility_mode(
         .build()?;
 
     let compat_mode = match config {
-        ChainConfig::CosmosSdk(config) | ChainConfig::Namada(config) => {
-            rt.block_on(fetch_compat_mode(&client, config))?
-        }
+        ChainConfig::CosmosSdk(config) | ChainConfig::Namada(config) => rt.block_on(
+            fetch_compat_mode(&PacedRpcClient::new(client, None), config),
+        )?,
         ChainConfig::Penumbra(config) => {
             let status = rt.block_on(client.status())?;
             penumbra::util::compat_mode_from_version(&config.compat_mode, status.node_info.version)?
@@ -10,12 +10,14 @@ use crate::commands::query::packet::QueryPacketCmds;
 
 mod channel;
 mod channel_client;
+mod channel_competition;
 mod channel_ends;
 mod channels;
 mod client;
 mod clients;
 mod connection;
 mod connections;
+mod fees_spent;
 mod packet;
 mod transfer;
 mod tx;
@@ -52,6 +54,9 @@ pub enum QueryCmd {
     #[clap(subcommand)]
     Tx(tx::QueryTxCmd),
 
+    /// Query the fees spent by the relayer on its most recent transactions
+    FeesSpent(fees_spent::QueryFeesSpentCmd),
+
     /// Query information about token transfers
     #[clap(subcommand)]
     Transfer(transfer::TransferCmd),
@@ -89,6 +94,9 @@ pub enum QueryChannelCmds {
     /// Query channel's client state
     Client(QueryChannelClientCmd),
 
+    /// Query the relayers which recently received packets on a channel, and their share of them
+    Competition(channel_competition::QueryChannelCompetitionCmd),
+
     /// Query channel end
     End(channel::QueryChannelEndCmd),
 
@@ -0,0 +1,162 @@
+use std::sync::Arc;
+
+use abscissa_core::clap::Parser;
+
+use ibc_relayer::chain::cosmos::competition::{recv_packet_signers, relayer_shares};
+use ibc_relayer::chain::cosmos::query::tx::query_recv_packet_txs;
+use ibc_relayer::chain::cosmos::CosmosSdkChain;
+use ibc_relayer::chain::endpoint::ChainEndpoint;
+use ibc_relayer::config::ChainConfig;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId};
+
+use crate::conclude::Output;
+use crate::prelude::*;
+
+/// Query the relayers which recently received packets on a channel, and their share
+/// of the packets received, to find out whether other relayers compete on the channel
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct QueryChannelCompetitionCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain receiving the packets"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "channel",
+        visible_alias = "chan",
+        required = true,
+        value_name = "CHANNEL_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the channel on which the packets are received"
+    )]
+    channel_id: ChannelId,
+
+    #[clap(
+        long = "lookback",
+        value_name = "BLOCKS",
+        help = "Number of most recent blocks to inspect the transactions of",
+        default_value = "1000"
+    )]
+    lookback: u64,
+}
+
+// cargo run --bin hermes -- query channel competition --chain ibc-1 --channel channel-0
+impl Runnable for QueryChannelCompetitionCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let chain_config = config
+            .find_chain(&self.chain_id)
+            .cloned()
+            .unwrap_or_else(|| {
+                Output::error(format!(
+                    "chain `{}` not found in configuration",
+                    self.chain_id
+                ))
+                .exit()
+            });
+
+        if !matches!(chain_config, ChainConfig::CosmosSdk(_)) {
+            Output::error(format!(
+                "chain `{}` is not a Cosmos SDK chain",
+                self.chain_id
+            ))
+            .exit();
+        }
+
+        let rt = Arc::new(
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap(),
+        );
+
+        let res = CosmosSdkChain::bootstrap(chain_config, rt.clone()).and_then(|chain| {
+            let latest_height = chain.query_chain_latest_height()?;
+            let min_height = latest_height
+                .revision_height()
+                .saturating_sub(self.lookback)
+                .max(1);
+
+            let txs = rt.block_on(query_recv_packet_txs(
+                &chain.rpc_client,
+                &chain.config().rpc_addr,
+                &self.channel_id,
+                min_height,
+            ))?;
+
+            // Only the transactions which were successfully executed received packets
+            let signers = txs
+                .iter()
+                .filter(|tx| tx.tx_result.code.is_ok())
+                .map(|tx| recv_packet_signers(&tx.tx, self.channel_id.as_str()))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok(relayer_shares(signers.into_iter().flatten()))
+        });
+
+        match res {
+            Ok(shares) => Output::success(shares).exit(),
+            Err(e) => Output::error(e).exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QueryChannelCompetitionCmd;
+
+    use std::str::FromStr;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId};
+
+    #[test]
+    fn test_query_channel_competition() {
+        assert_eq!(
+            QueryChannelCompetitionCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                channel_id: ChannelId::from_str("channel-07").unwrap(),
+                lookback: 1000,
+            },
+            QueryChannelCompetitionCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--channel",
+                "channel-07"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_query_channel_competition_lookback() {
+        assert_eq!(
+            QueryChannelCompetitionCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                channel_id: ChannelId::from_str("channel-07").unwrap(),
+                lookback: 50,
+            },
+            QueryChannelCompetitionCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--chan",
+                "channel-07",
+                "--lookback",
+                "50"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_query_channel_competition_no_channel() {
+        assert!(
+            QueryChannelCompetitionCmd::try_parse_from(["test", "--chain", "chain_id"]).is_err()
+        )
+    }
+}
@@ -0,0 +1,150 @@
+use std::sync::Arc;
+
+use abscissa_core::clap::Parser;
+
+use ibc_relayer::chain::cosmos::fees_spent::{fees_spent_from_txs, total_fees_spent};
+use ibc_relayer::chain::cosmos::query::tx::query_txs_by_sender;
+use ibc_relayer::chain::cosmos::CosmosSdkChain;
+use ibc_relayer::chain::endpoint::ChainEndpoint;
+use ibc_relayer::config::ChainConfig;
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use crate::conclude::Output;
+use crate::prelude::*;
+
+/// Query the fees spent by the relayer on its most recent transactions
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct QueryFeesSpentCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain to query"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "key-name",
+        value_name = "KEY_NAME",
+        help = "Use the given signing key name to look up the relayer transactions (default: `key_name` config)"
+    )]
+    key_name: Option<String>,
+
+    #[clap(
+        long = "limit",
+        value_name = "LIMIT",
+        help = "Number of most recent relayer transactions to sum up the fees of (default: 100, max: 100)",
+        default_value = "100",
+        value_parser = clap::value_parser!(u8).range(1..=100)
+    )]
+    limit: u8,
+}
+
+// cargo run --bin hermes -- query fees-spent --chain ibc-0
+impl Runnable for QueryFeesSpentCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let mut chain_config = config
+            .find_chain(&self.chain_id)
+            .cloned()
+            .unwrap_or_else(|| {
+                Output::error(format!(
+                    "chain `{}` not found in configuration",
+                    self.chain_id
+                ))
+                .exit()
+            });
+
+        if !matches!(chain_config, ChainConfig::CosmosSdk(_)) {
+            Output::error(format!(
+                "chain `{}` is not a Cosmos SDK chain",
+                self.chain_id
+            ))
+            .exit();
+        }
+
+        if let Some(ref key_name) = self.key_name {
+            chain_config.set_key_name(key_name.to_string());
+        }
+
+        let rt = Arc::new(
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap(),
+        );
+
+        let res = CosmosSdkChain::bootstrap(chain_config, rt.clone()).and_then(|chain| {
+            let signer = chain.get_signer()?;
+
+            let txs = rt.block_on(query_txs_by_sender(
+                &chain.rpc_client,
+                &chain.config().rpc_addr,
+                &signer,
+                self.limit,
+            ))?;
+
+            Ok(total_fees_spent(fees_spent_from_txs(&txs)))
+        });
+
+        match res {
+            Ok(fees_spent) => Output::success(fees_spent).exit(),
+            Err(e) => Output::error(e).exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QueryFeesSpentCmd;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+    #[test]
+    fn test_query_fees_spent() {
+        assert_eq!(
+            QueryFeesSpentCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                key_name: None,
+                limit: 100,
+            },
+            QueryFeesSpentCmd::parse_from(["test", "--chain", "chain_id"])
+        )
+    }
+
+    #[test]
+    fn test_query_fees_spent_limit_key_name() {
+        assert_eq!(
+            QueryFeesSpentCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                key_name: Some("relayer".to_owned()),
+                limit: 10,
+            },
+            QueryFeesSpentCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--key-name",
+                "relayer",
+                "--limit",
+                "10"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_query_fees_spent_limit_too_high() {
+        assert!(QueryFeesSpentCmd::try_parse_from([
+            "test", "--chain", "chain_id", "--limit", "101"
+        ])
+        .is_err())
+    }
+
+    #[test]
+    fn test_query_fees_spent_no_chain() {
+        assert!(QueryFeesSpentCmd::try_parse_from(["test"]).is_err())
+    }
+}
@@ -8,6 +8,7 @@ mod commitments;
 mod pending;
 mod pending_acks;
 mod pending_sends;
+mod relay_plan;
 mod util;
 
 #[derive(Command, Debug, Parser, Runnable)]
@@ -32,4 +33,7 @@ pub enum QueryPacketCmds {
 
     /// Output a summary of pending packets in both directions
     Pending(pending::QueryPendingPacketsCmd),
+
+    /// Output the messages which would be submitted to relay a packet, without submitting them
+    RelayPlan(relay_plan::QueryPacketRelayPlanCmd),
 }
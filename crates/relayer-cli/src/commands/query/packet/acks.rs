@@ -67,10 +67,10 @@ impl QueryPacketAcknowledgementsCmd {
 // cargo run --bin hermes -- query packet acknowledgements --chain ibc-0 --port transfer --connection ibconexfer --height 3
 impl Runnable for QueryPacketAcknowledgementsCmd {
     fn run(&self) {
-        use crate::conclude::json;
+        use crate::conclude::structured;
 
         match self.execute() {
-            Ok(p) if json() => Output::success(p).exit(),
+            Ok(p) if structured() => Output::success(p).exit(),
             Ok(Some(p)) => Output::success(p.collated()).exit(),
             Ok(None) => Output::error("No acknowledgments found").exit(),
             Err(e) => Output::error(e).exit(),
@@ -82,10 +82,10 @@ impl QueryPacketCommitmentsCmd {
 // cargo run --bin hermes -- query packet commitments --chain ibc-0 --port transfer --channel ibconexfer --height 3
 impl Runnable for QueryPacketCommitmentsCmd {
     fn run(&self) {
-        use crate::conclude::json;
+        use crate::conclude::structured;
 
         match self.execute() {
-            Ok(p) if json() => Output::success(p).exit(),
+            Ok(p) if structured() => Output::success(p).exit(),
             Ok(p) => Output::success(p.collated()).exit(),
             Err(e) => Output::error(e).exit(),
         }
@@ -166,10 +166,10 @@ impl QueryPendingPacketsCmd {
 
 impl Runnable for QueryPendingPacketsCmd {
     fn run(&self) {
-        use crate::conclude::json;
+        use crate::conclude::structured;
 
         match self.execute() {
-            Ok(summary) if json() => Output::success(summary).exit(),
+            Ok(summary) if structured() => Output::success(summary).exit(),
             Ok(summary) => Output::success_msg(summary.collate().to_string()).exit(),
             Err(e) => Output::error(e).exit(),
         }
@@ -79,10 +79,10 @@ impl QueryPendingAcksCmd {
 
 impl Runnable for QueryPendingAcksCmd {
     fn run(&self) {
-        use crate::conclude::json;
+        use crate::conclude::structured;
 
         match self.execute() {
-            Ok(seqs) if json() => Output::success(seqs).exit(),
+            Ok(seqs) if structured() => Output::success(seqs).exit(),
             Ok(seqs) => Output::success(seqs.into_iter().collated().collect::<Vec<_>>()).exit(),
             Err(e) => Output::error(e).exit(),
         }
@@ -77,10 +77,10 @@ impl QueryPendingSendsCmd {
 
 impl Runnable for QueryPendingSendsCmd {
     fn run(&self) {
-        use crate::conclude::json;
+        use crate::conclude::structured;
 
         match self.execute() {
-            Ok(seqs) if json() => Output::success(seqs).exit(),
+            Ok(seqs) if structured() => Output::success(seqs).exit(),
             Ok(seqs) => Output::success(seqs.into_iter().collated().collect::<Vec<_>>()).exit(),
             Err(e) => Output::error(e).exit(),
         }
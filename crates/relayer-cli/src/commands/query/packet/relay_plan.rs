@@ -0,0 +1,154 @@
+use abscissa_core::clap::Parser;
+
+use ibc_relayer::chain::handle::BaseChainHandle;
+use ibc_relayer::link::relay_plan::RelayPlan;
+use ibc_relayer::link::{Link, LinkParameters};
+use ibc_relayer_types::core::ics04_channel::packet::Sequence;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+
+use crate::cli_utils::spawn_chain_counterparty;
+use crate::conclude::Output;
+use crate::error::Error;
+use crate::prelude::*;
+
+/// Prints the messages Hermes would submit in order to relay a packet sent
+/// on the given chain, including the client update they depend on, without
+/// submitting them.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct QueryPacketRelayPlanCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain the packet was sent from"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "port",
+        required = true,
+        value_name = "PORT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the port the packet was sent from"
+    )]
+    port_id: PortId,
+
+    #[clap(
+        long = "channel",
+        visible_alias = "chan",
+        required = true,
+        value_name = "CHANNEL_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the channel the packet was sent on"
+    )]
+    channel_id: ChannelId,
+
+    #[clap(
+        long = "sequence",
+        visible_alias = "seq",
+        required = true,
+        value_name = "SEQUENCE",
+        help_heading = "REQUIRED",
+        help = "Sequence of the packet to relay"
+    )]
+    sequence: Sequence,
+}
+
+impl QueryPacketRelayPlanCmd {
+    fn execute(&self) -> Result<Option<RelayPlan>, Error> {
+        let config = app_config();
+
+        let (chains, _) = spawn_chain_counterparty::<BaseChainHandle>(
+            &config,
+            &self.chain_id,
+            &self.port_id,
+            &self.channel_id,
+        )?;
+
+        let opts = LinkParameters {
+            src_port_id: self.port_id.clone(),
+            src_channel_id: self.channel_id.clone(),
+            max_memo_size: config.mode.packets.ics20_max_memo_size,
+            max_receiver_size: config.mode.packets.ics20_max_receiver_size,
+
+            // Packets are only excluded when clearing
+            exclude_src_sequences: vec![],
+            max_clear_duration: None,
+            verification: config.mode.packets.channel_verification(),
+            clear_order: config.mode.packets.clear_order,
+        };
+
+        let link =
+            Link::new_from_opts(chains.src, chains.dst, opts, false, false).map_err(Error::link)?;
+
+        link.a_to_b.relay_plan(self.sequence).map_err(Error::link)
+    }
+}
+
+impl Runnable for QueryPacketRelayPlanCmd {
+    fn run(&self) {
+        use crate::conclude::structured;
+
+        match self.execute() {
+            Ok(plan) if structured() => Output::success(plan).exit(),
+            Ok(Some(plan)) => Output::success_msg(plan).exit(),
+            Ok(None) => Output::success_msg(format!(
+                "nothing to relay for packet {} on {}/{}",
+                self.sequence, self.port_id, self.channel_id
+            ))
+            .exit(),
+            Err(e) => Output::error(e).exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QueryPacketRelayPlanCmd;
+
+    use std::str::FromStr;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::{
+        ics04_channel::packet::Sequence,
+        ics24_host::identifier::{ChainId, ChannelId, PortId},
+    };
+
+    #[test]
+    fn test_query_packet_relay_plan() {
+        assert_eq!(
+            QueryPacketRelayPlanCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                port_id: PortId::from_str("port_id").unwrap(),
+                channel_id: ChannelId::from_str("channel-07").unwrap(),
+                sequence: Sequence::from(42),
+            },
+            QueryPacketRelayPlanCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--port",
+                "port_id",
+                "--chan",
+                "channel-07",
+                "--seq",
+                "42"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_query_packet_relay_plan_no_seq() {
+        assert!(QueryPacketRelayPlanCmd::try_parse_from([
+            "test",
+            "--chain",
+            "chain_id",
+            "--port",
+            "port_id",
+            "--channel",
+            "channel-07"
+        ])
+        .is_err())
+    }
+}
@@ -6,7 +6,7 @@ use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 
 use crate::application::app_config;
 use crate::cli_utils::spawn_chain_runtime;
-use crate::conclude::{exit_with_unrecoverable_error, json, Output};
+use crate::conclude::{exit_with_unrecoverable_error, structured, Output};
 
 /// The data structure that represents the arguments when invoking the `query transfer denom-trace` CLI command.
 ///
@@ -42,7 +42,7 @@ impl Runnable for DenomTraceCmd {
             .unwrap_or_else(exit_with_unrecoverable_error);
 
         match chain.query_denom_trace(self.hash.clone()) {
-            Ok(denom_trace) if json() => Output::success(denom_trace).exit(),
+            Ok(denom_trace) if structured() => Output::success(denom_trace).exit(),
             Ok(denom_trace) => Output::success_msg(format!(
                 "base_denom: {}\n path: {}",
                 denom_trace.base_denom, denom_trace.path
@@ -2,12 +2,14 @@ use ibc_relayer::supervisor::SupervisorOptions;
 use ibc_relayer::util::debug_section::DebugSection;
 use std::error::Error;
 use std::io;
+use std::path::{Path, PathBuf};
 
 use abscissa_core::clap::Parser;
 use crossbeam_channel::Sender;
+use itertools::Itertools;
 
 use ibc_relayer::chain::handle::{CachingChainHandle, ChainHandle};
-use ibc_relayer::config::Config;
+use ibc_relayer::config::{Config, Diagnostic};
 use ibc_relayer::registry::SharedRegistry;
 use ibc_relayer::rest;
 use ibc_relayer::supervisor::{cmd::SupervisorCmd, spawn_supervisor, SupervisorHandle};
@@ -65,8 +67,10 @@ impl Runnable for StartCmd {
             });
 
         match crate::config::config_path() {
-            Some(_) => {
-                register_signals(supervisor_handle.sender.clone()).unwrap_or_else(|e| {
+            Some(config_path) => {
+                let tx_cmd = supervisor_handle.sender.clone();
+
+                register_signals(config_path, tx_cmd).unwrap_or_else(|e| {
                     warn!("failed to install signal handler: {}", e);
                 });
             }
@@ -82,13 +86,13 @@ impl Runnable for StartCmd {
 }
 
 /// Register the SIGHUP and SIGUSR1 signals, and notify the supervisor.
-/// - [DEPRECATED] SIGHUP: Trigger a reload of the configuration.
+/// - SIGHUP: Reload the configuration from the given file.
 /// - SIGUSR1: Ask the supervisor to dump its state and print it to the console.
-fn register_signals(tx_cmd: Sender<SupervisorCmd>) -> Result<(), io::Error> {
+fn register_signals(config_path: PathBuf, tx_cmd: Sender<SupervisorCmd>) -> Result<(), io::Error> {
     use signal_hook::{consts::signal::*, iterator::Signals};
 
     let sigs = vec![
-        SIGHUP,  // Reload of configuration (disabled)
+        SIGHUP,  // Reload of configuration
         SIGUSR1, // Dump state
     ];
 
@@ -97,10 +101,32 @@ fn register_signals(tx_cmd: Sender<SupervisorCmd>) -> Result<(), io::Error> {
     std::thread::spawn(move || {
         for signal in &mut signals {
             match signal {
-                SIGHUP => warn!(
-                    "configuration reloading via SIGHUP has been disabled, \
-                     the signal handler will be removed in the future"
-                ),
+                SIGHUP => {
+                    info!("reloading configuration (triggered by SIGHUP)");
+
+                    let config = match load_config(&config_path) {
+                        Ok(config) => config,
+                        Err(e) => {
+                            error!("keeping the current configuration: {e}");
+                            continue;
+                        }
+                    };
+
+                    let (tx, rx) = crossbeam_channel::bounded(1);
+                    tx_cmd
+                        .try_send(SupervisorCmd::ReloadConfig(Box::new(config), tx))
+                        .unwrap();
+
+                    std::thread::spawn(move || {
+                        if let Ok(stopped) = rx.recv() {
+                            info!(
+                                "reloaded configuration, stopped {} workers: {}",
+                                stopped.len(),
+                                stopped.iter().map(|object| object.short_name()).join(", ")
+                            );
+                        }
+                    });
+                }
                 SIGUSR1 => {
                     info!("dumping state (triggered by SIGUSR1)");
 
@@ -131,6 +157,24 @@ fn register_signals(tx_cmd: Sender<SupervisorCmd>) -> Result<(), io::Error> {
     Ok(())
 }
 
+/// Load and validate the configuration at the given path.
+fn load_config(config_path: &Path) -> Result<Config, String> {
+    let config = ibc_relayer::config::load(config_path)
+        .map_err(|e| format!("failed to load {}: {e}", config_path.display()))?;
+
+    match config.validate_config() {
+        Ok(()) => Ok(config),
+        Err(Diagnostic::Warning(e)) => {
+            warn!("relayer may be misconfigured: {e}");
+            Ok(config)
+        }
+        Err(Diagnostic::Error(e)) => Err(format!(
+            "invalid configuration {}: {e}",
+            config_path.display()
+        )),
+    }
+}
+
 fn spawn_rest_server(config: &Config) -> Option<rest::Receiver> {
     use ibc_relayer::util::spawn_blocking;
 
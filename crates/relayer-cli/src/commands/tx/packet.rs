@@ -90,6 +90,9 @@ impl Runnable for TxPacketRecvCmd {
 
             // Packets are only excluded when clearing
             exclude_src_sequences: vec![],
+            max_clear_duration: None,
+            verification: config.mode.packets.channel_verification(),
+            clear_order: config.mode.packets.clear_order,
         };
 
         let link = match Link::new_from_opts(chains.src, chains.dst, opts, false, false) {
@@ -191,6 +194,9 @@ impl Runnable for TxPacketAckCmd {
 
             // Packets are only excluded when clearing
             exclude_src_sequences: vec![],
+            max_clear_duration: None,
+            verification: config.mode.packets.channel_verification(),
+            clear_order: config.mode.packets.clear_order,
         };
 
         let link = match Link::new_from_opts(chains.src, chains.dst, opts, false, false) {
@@ -8,7 +8,7 @@ use ibc_relayer::{
     chain::handle::ChainHandle,
     config::Config,
     event::IbcEventWithHeight,
-    transfer::{build_and_send_transfer_messages, TransferOptions},
+    transfer::{build_and_send_transfer_messages, check_memo_size, TransferOptions},
 };
 use ibc_relayer_types::{
     applications::transfer::Amount,
@@ -147,13 +147,19 @@ impl TxIcs20MsgTransferCmd {
             )
         })?;
 
-        config.find_chain(&self.dst_chain_id).ok_or_else(|| {
+        let dst_chain_config = config.find_chain(&self.dst_chain_id).ok_or_else(|| {
             eyre!(
                 "missing configuration for destination chain '{}'",
                 self.dst_chain_id
             )
         })?;
 
+        check_memo_size(
+            self.memo.as_deref(),
+            dst_chain_config.max_memo_bytes(),
+            &self.dst_chain_id,
+        )?;
+
         let denom = self.denom.clone();
 
         let number_msgs = self.number_msgs.unwrap_or(1);
@@ -216,6 +222,8 @@ mod tests {
         core::ics24_host::identifier::{ChainId, ChannelId, PortId},
     };
 
+    use ibc_relayer::config::ChainConfig;
+
     use super::TxIcs20MsgTransferCmd;
 
     use abscissa_core::clap::Parser;
@@ -611,4 +619,50 @@ mod tests {
         ])
         .is_err())
     }
+
+    #[test]
+    fn test_ft_transfer_oversized_memo_rejected() {
+        let mut config = ibc_relayer::config::load(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../relayer/tests/config/fixtures/relayer_conf_example.toml"
+        ))
+        .unwrap();
+
+        match config
+            .find_chain_mut(&ChainId::from_string("chain_B"))
+            .unwrap()
+        {
+            ChainConfig::CosmosSdk(chain_config) | ChainConfig::Namada(chain_config) => {
+                chain_config.max_memo_bytes = Some(16)
+            }
+            ChainConfig::Penumbra(_) => unreachable!(),
+        }
+
+        let transfer = |memo: &str| {
+            TxIcs20MsgTransferCmd::try_parse_from([
+                "test",
+                "--dst-chain",
+                "chain_B",
+                "--src-chain",
+                "chain_A",
+                "--src-port",
+                "transfer",
+                "--src-channel",
+                "channel-0",
+                "--amount",
+                "42",
+                "--memo",
+                memo,
+            ])
+            .unwrap()
+        };
+
+        let err = transfer("a memo which is too large")
+            .validate_options(&config)
+            .unwrap_err();
+        assert!(err.to_string().contains("exceeds the maximum of 16 bytes"));
+
+        let opts = transfer("a small memo").validate_options(&config).unwrap();
+        assert_eq!(opts.memo.as_deref(), Some("a small memo"));
+    }
 }
@@ -0,0 +1,12 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::Command;
+use abscissa_core::Runnable;
+
+pub mod verify_header;
+
+/// `util` subcommand
+#[derive(Command, Debug, Parser, Runnable)]
+pub enum UtilCmd {
+    /// Verify a header against a trusted header, without connecting to the chain
+    VerifyHeader(verify_header::VerifyHeaderCmd),
+}
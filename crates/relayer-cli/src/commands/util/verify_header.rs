@@ -0,0 +1,217 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use tendermint::Time;
+use tendermint_light_client_verifier::options::Options;
+use tendermint_light_client_verifier::types::{LightBlock, TrustThreshold};
+use tendermint_light_client_verifier::Verdict;
+
+use ibc_relayer::light_client::tendermint::verify_update_header;
+
+use crate::commands::tx::client::parse_trust_threshold;
+use crate::conclude::Output;
+use crate::error::Error;
+
+/// Verify a target header against a trusted header, the same way
+/// a Tendermint client would when processing a `MsgUpdateClient`.
+///
+/// Both headers are light blocks encoded as JSON, as returned for instance
+/// by the `/commit` and `/validators` endpoints of a CometBFT node.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct VerifyHeaderCmd {
+    #[clap(
+        long = "trusted",
+        required = true,
+        value_name = "TRUSTED_FILE",
+        help_heading = "REQUIRED",
+        help = "Path to the JSON file containing the trusted light block"
+    )]
+    trusted: PathBuf,
+
+    #[clap(
+        long = "target",
+        required = true,
+        value_name = "TARGET_FILE",
+        help_heading = "REQUIRED",
+        help = "Path to the JSON file containing the light block to verify"
+    )]
+    target: PathBuf,
+
+    #[clap(
+        long = "trust-threshold",
+        required = true,
+        value_name = "TRUST_THRESHOLD",
+        help_heading = "REQUIRED",
+        help = "Fraction of the trusted validator set voting power which must have signed the target header",
+        parse(try_from_str = parse_trust_threshold)
+    )]
+    trust_threshold: TrustThreshold,
+
+    /// How long the validator set of the trusted header is trusted for.
+    #[clap(
+        long = "trusting-period",
+        value_name = "TRUSTING_PERIOD",
+        default_value = "14days"
+    )]
+    trusting_period: humantime::Duration,
+
+    /// The maximum allowed clock drift between the verifier and the chain.
+    #[clap(long = "clock-drift", value_name = "CLOCK_DRIFT", default_value = "5s")]
+    clock_drift: humantime::Duration,
+
+    /// The RFC 3339 time at which to perform the verification.
+    /// Defaults to the time of the target header.
+    #[clap(long = "now", value_name = "NOW")]
+    now: Option<Time>,
+}
+
+impl Runnable for VerifyHeaderCmd {
+    fn run(&self) {
+        let (trusted, target) = match read_light_block(&self.trusted)
+            .and_then(|trusted| Ok((trusted, read_light_block(&self.target)?)))
+        {
+            Ok(blocks) => blocks,
+            Err(e) => Output::error(e).exit(),
+        };
+
+        let options = Options {
+            trust_threshold: self.trust_threshold,
+            trusting_period: self.trusting_period.into(),
+            clock_drift: self.clock_drift.into(),
+        };
+
+        let now = self.now.unwrap_or(target.signed_header.header.time);
+
+        match verdict_to_result(verify_update_header(&trusted, &target, &options, now)) {
+            Ok(()) => {
+                Output::success_msg(format!("header at height {} is valid", target.height())).exit()
+            }
+            Err(reason) => Output::error(format!(
+                "header verification failed at height {}: {reason}",
+                target.height()
+            ))
+            .exit(),
+        }
+    }
+}
+
+fn read_light_block(path: &Path) -> Result<LightBlock, Error> {
+    let content = fs::read_to_string(path).map_err(|e| {
+        Error::cli_arg(format!(
+            "failed to read light block from `{}`: {e}",
+            path.display()
+        ))
+    })?;
+
+    serde_json::from_str(&content).map_err(|e| {
+        Error::cli_arg(format!(
+            "failed to parse light block from `{}`: {e}",
+            path.display()
+        ))
+    })
+}
+
+/// Turns the verdict of the verifier into the reason why the header was rejected, if any.
+fn verdict_to_result(verdict: Verdict) -> Result<(), String> {
+    match verdict {
+        Verdict::Success => Ok(()),
+        Verdict::NotEnoughTrust(tally) => Err(format!("not enough trust: {tally}")),
+        Verdict::Invalid(detail) => Err(format!("invalid header: {detail}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{verdict_to_result, VerifyHeaderCmd};
+
+    use std::path::PathBuf;
+    use std::str::FromStr;
+
+    use abscissa_core::clap::Parser;
+    use humantime::Duration;
+    use tendermint::Time;
+    use tendermint_light_client_verifier::operations::VotingPowerTally;
+    use tendermint_light_client_verifier::types::TrustThreshold;
+    use tendermint_light_client_verifier::Verdict;
+
+    #[test]
+    fn test_verify_header_required_only() {
+        assert_eq!(
+            VerifyHeaderCmd {
+                trusted: PathBuf::from("trusted.json"),
+                target: PathBuf::from("target.json"),
+                trust_threshold: TrustThreshold::new(2, 3).unwrap(),
+                trusting_period: Duration::from_str("14days").unwrap(),
+                clock_drift: Duration::from_str("5s").unwrap(),
+                now: None,
+            },
+            VerifyHeaderCmd::parse_from([
+                "test",
+                "--trusted",
+                "trusted.json",
+                "--target",
+                "target.json",
+                "--trust-threshold",
+                "2/3"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_verify_header_all_options() {
+        assert_eq!(
+            VerifyHeaderCmd {
+                trusted: PathBuf::from("trusted.json"),
+                target: PathBuf::from("target.json"),
+                trust_threshold: TrustThreshold::new(1, 3).unwrap(),
+                trusting_period: Duration::from_str("1day").unwrap(),
+                clock_drift: Duration::from_str("10s").unwrap(),
+                now: Some(Time::from_str("2024-01-01T00:00:00Z").unwrap()),
+            },
+            VerifyHeaderCmd::parse_from([
+                "test",
+                "--trusted",
+                "trusted.json",
+                "--target",
+                "target.json",
+                "--trust-threshold",
+                "1/3",
+                "--trusting-period",
+                "1day",
+                "--clock-drift",
+                "10s",
+                "--now",
+                "2024-01-01T00:00:00Z"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_verify_header_no_trust_threshold() {
+        assert!(VerifyHeaderCmd::try_parse_from([
+            "test",
+            "--trusted",
+            "trusted.json",
+            "--target",
+            "target.json"
+        ])
+        .is_err())
+    }
+
+    #[test]
+    fn test_verdict_reasons() {
+        assert_eq!(verdict_to_result(Verdict::Success), Ok(()));
+
+        let tally = VotingPowerTally {
+            total: 100,
+            tallied: 10,
+            trust_threshold: TrustThreshold::ONE_THIRD,
+        };
+
+        let reason = verdict_to_result(Verdict::NotEnoughTrust(tally)).unwrap_err();
+        assert!(reason.starts_with("not enough trust"), "{reason}");
+        assert!(reason.contains("tallied=10"), "{reason}");
+    }
+}
@@ -2,6 +2,10 @@
 //! from a CLI command. The main use-case for this module is to provide a consistent output for
 //! queries and transactions.
 //!
+//! The output is rendered in the [`OutputFormat`] selected with the global `--output` flag.
+//! In the structured formats (JSON and YAML), the output is an envelope with a `status`
+//! and a `result` field, the latter holding the serialized result of the command.
+//!
 //! The examples below rely on crate-private methods (for this reason, doctests are ignored).
 //! They are intended for contributors to crate `relayer-cli`, and _not_ for users of this binary.
 //!
@@ -55,6 +59,7 @@
 //! Output::success(h).with_result(end).exit();
 //! ```
 
+use clap::ValueEnum;
 use console::style;
 use core::fmt;
 
@@ -70,15 +75,7 @@ pub fn exit_with(out: Output) -> ! {
     let status = out.status;
 
     // Handle the output message
-    if json() {
-        println!("{}", serde_json::to_string(&out.into_json()).unwrap());
-    } else {
-        let status = match out.status {
-            Status::Success => style("SUCCESS").green(),
-            Status::Error => style("ERROR").red(),
-        };
-        println!("{} {}", status, out.result);
-    }
+    println!("{}", out.render(output_format()));
 
     // The return code
     if status == Status::Error {
@@ -88,11 +85,38 @@ pub fn exit_with(out: Output) -> ! {
     }
 }
 
-/// Returns true if the application global json flag `--json` is enabled.
-/// Returns false otherwise.
-pub fn json() -> bool {
+/// The format in which the output of a command is rendered.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    Yaml,
+}
+
+impl OutputFormat {
+    /// Returns true if the format outputs the result as structured data.
+    pub fn is_structured(&self) -> bool {
+        !matches!(self, Self::Text)
+    }
+}
+
+/// Returns the output format selected with the application global flag `--output`.
+pub fn output_format() -> OutputFormat {
     let a = app_reader();
-    a.json_output()
+    a.output_format()
+}
+
+/// Returns true if the application global json flag `--json` is enabled,
+/// or if the JSON output format is selected. Returns false otherwise.
+pub fn json() -> bool {
+    output_format() == OutputFormat::Json
+}
+
+/// Returns true if the result is output as structured data, ie. in JSON or YAML.
+/// Returns false otherwise.
+pub fn structured() -> bool {
+    output_format().is_structured()
 }
 
 /// Exits the program. Useful when a type produces an error which can no longer be propagated, and
@@ -173,7 +197,7 @@ impl Output {
     where
         R: Serialize + fmt::Debug + 'static,
     {
-        if json() {
+        if structured() {
             self.result = Result::Json(serialize_result(result));
         } else {
             self.result = Result::Value(Box::new(result));
@@ -213,6 +237,21 @@ impl Output {
         exit_with(self);
     }
 
+    /// Renders this output in the given format
+    pub fn render(self, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::Text => {
+                let status = match self.status {
+                    Status::Success => style("SUCCESS").green(),
+                    Status::Error => style("ERROR").red(),
+                };
+                format!("{} {}", status, self.result)
+            }
+            OutputFormat::Json => serde_json::to_string(&self.into_json()).unwrap(),
+            OutputFormat::Yaml => serde_yaml::to_string(&self.into_json()).unwrap(),
+        }
+    }
+
     /// Convert this output value to a JSON value
     pub fn into_json(self) -> serde_json::Value {
         let mut map = serde_json::Map::new();
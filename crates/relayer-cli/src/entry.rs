@@ -10,6 +10,7 @@ use ibc_relayer::config::Config;
 use ibc_relayer::util::debug_section::DebugSection;
 
 use crate::commands::CliCmd;
+use crate::conclude::OutputFormat;
 
 #[derive(Copy, Clone, Debug, ValueEnum)]
 pub enum CliDebugSection {
@@ -37,9 +38,18 @@ pub struct EntryPoint {
     pub config: Option<PathBuf>,
 
     /// Toggle JSON output mode one verbosity setting
-    #[clap(long = "json", help = "Enable JSON output")]
+    #[clap(long = "json", help = "Enable JSON output, same as `--output json`")]
     pub json: bool,
 
+    /// Format of the output of the command
+    #[clap(
+        long = "output",
+        value_enum,
+        conflicts_with = "json",
+        help = "Output format of the result of the command [default: text]"
+    )]
+    pub output: Option<OutputFormat>,
+
     /// Enable the given debug sections, separated by commas.
     #[clap(
         long = "debug",
@@ -57,6 +67,17 @@ pub struct EntryPoint {
     pub command: Option<CliCmd>,
 }
 
+impl EntryPoint {
+    /// Returns the output format selected with `--output`, or with `--json`.
+    pub fn output_format(&self) -> OutputFormat {
+        match self.output {
+            Some(format) => format,
+            None if self.json => OutputFormat::Json,
+            None => OutputFormat::Text,
+        }
+    }
+}
+
 impl Runnable for EntryPoint {
     fn run(&self) {
         match &self.command {
@@ -49,3 +49,69 @@ fn example_configuration_is_valid() {
     cmd.stdout().expect_regex("configuration is valid");
     cmd.wait().unwrap().expect_success();
 }
+
+/// Query commands output their error in the `{status, result}` envelope with `--output json`.
+///
+/// No chain is running during this test, so every query fails. The output of queries which
+/// succeed is tested by the `output_format` integration test.
+#[test]
+fn query_commands_output_json_envelope() {
+    let queries: &[&[&str]] = &[
+        &["query", "clients", "--host-chain", "ibc-0"],
+        &["query", "channels", "--chain", "ibc-0"],
+        &[
+            "query",
+            "connection",
+            "end",
+            "--chain",
+            "ibc-0",
+            "--connection",
+            "connection-0",
+        ],
+        &[
+            "query",
+            "packet",
+            "pending",
+            "--chain",
+            "ibc-0",
+            "--port",
+            "transfer",
+            "--channel",
+            "channel-0",
+        ],
+    ];
+
+    for query in queries {
+        let mut runner = RUNNER.clone();
+        let mut cmd = runner
+            .capture_stdout()
+            .args(["--config", "../../config.toml", "--output", "json"])
+            .args(query.iter())
+            .run();
+
+        let mut stdout = String::new();
+        std::io::Read::read_to_string(&mut **cmd.stdout(), &mut stdout).unwrap();
+        cmd.wait().unwrap().expect_code(1);
+
+        // The output is preceded by the logs, which are also emitted as JSON
+        let output = stdout.lines().last().unwrap_or_default();
+
+        let envelope: serde_json::Value = serde_json::from_str(output)
+            .unwrap_or_else(|e| panic!("invalid JSON output of {query:?}: {e}: {output}"));
+
+        let envelope = envelope.as_object().unwrap();
+        assert_eq!(
+            envelope.len(),
+            2,
+            "unexpected output of {query:?}: {output}"
+        );
+        assert_eq!(
+            envelope["status"], "error",
+            "unexpected status of {query:?}: {output}"
+        );
+        assert!(
+            envelope["result"].as_str().is_some_and(|e| !e.is_empty()),
+            "expected an error message in the output of {query:?}: {output}"
+        );
+    }
+}
@@ -7,6 +7,7 @@ use crossbeam_channel as channel;
 use ibc_relayer::supervisor::dump_state::SupervisorState;
 use ibc_relayer::{
     config::ChainConfig,
+    link::SequenceRange,
     rest::{
         request::{reply_channel, ReplySender, Request, VersionInfo},
         RestApiError,
@@ -64,13 +65,30 @@ pub fn supervisor_state(
     submit_request(sender, |reply_to| Request::State { reply_to })
 }
 
-/// Submit a request to clear all packets for the chain with the
-/// specified `chain_id`.
+/// Submit a request to clear the packets whose sequence is within `sequences`
+/// for the chain with the specified `chain_id`, or for all the chains if `None`.
 pub fn trigger_clear_packets(
     sender: &channel::Sender<Request>,
     chain_id: Option<ChainId>,
+    sequences: SequenceRange,
 ) -> Result<(), RestApiError> {
     submit_request(sender, |reply_to| Request::ClearPackets {
+        chain_id,
+        sequences,
+        reply_to,
+    })
+}
+
+/// Submit a request to drop the operational data buffered by the workers
+/// of the chain with the specified `chain_id`, or of all the chains if `None`.
+///
+/// The transactions which were already broadcast are not recalled,
+/// only the buffers of the workers are cleared.
+pub fn trigger_clear_pending_operational_data(
+    sender: &channel::Sender<Request>,
+    chain_id: Option<ChainId>,
+) -> Result<(), RestApiError> {
+    submit_request(sender, |reply_to| Request::ClearPendingOperationalData {
         chain_id,
         reply_to,
     })
@@ -10,14 +10,18 @@ use axum::{
     Extension, Json, Router, Server,
 };
 use crossbeam_channel as channel;
-use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use ibc_relayer_types::core::{ics04_channel::packet::Sequence, ics24_host::identifier::ChainId};
 use serde::{Deserialize, Serialize};
 use tokio::task::JoinHandle;
 
-use ibc_relayer::rest::{request::Request, RestApiError};
+use ibc_relayer::{
+    link::SequenceRange,
+    rest::{request::Request, RestApiError},
+};
 
 use crate::handle::{
     all_chain_ids, assemble_version_info, chain_config, supervisor_state, trigger_clear_packets,
+    trigger_clear_pending_operational_data,
 };
 
 pub type BoxError = Box<dyn Error + Send + Sync>;
@@ -76,11 +80,32 @@ struct ClearPacketParams {
     chain: Option<ChainId>,
 }
 
+#[derive(Debug, Deserialize)]
+struct ClearPacketsParams {
+    chain: Option<ChainId>,
+    seq_start: Option<u64>,
+    seq_end: Option<u64>,
+}
+
 async fn clear_packets(
+    Extension(sender): Extension<Sender>,
+    Query(params): Query<ClearPacketsParams>,
+) -> impl IntoResponse {
+    let result = SequenceRange::new(
+        params.seq_start.map(Sequence::from),
+        params.seq_end.map(Sequence::from),
+    )
+    .map_err(|e| RestApiError::InvalidSequenceRange(e.to_string()))
+    .and_then(|sequences| trigger_clear_packets(&sender, params.chain, sequences));
+
+    Json(JsonResult::from(result))
+}
+
+async fn clear_pending_data(
     Extension(sender): Extension<Sender>,
     Query(params): Query<ClearPacketParams>,
 ) -> impl IntoResponse {
-    let result = trigger_clear_packets(&sender, params.chain);
+    let result = trigger_clear_pending_operational_data(&sender, params.chain);
     Json(JsonResult::from(result))
 }
 
@@ -93,6 +118,7 @@ async fn run(addr: SocketAddr, sender: Sender) {
         .route("/chain/:id", get(get_chain))
         .route("/state", get(get_state))
         .route("/clear_packets", post(clear_packets))
+        .route("/clear_pending_data", post(clear_pending_data))
         .layer(Extension(sender));
 
     Server::bind(&addr)
@@ -146,3 +146,31 @@ async fn state() {
     })
     .await;
 }
+
+#[tokio::test]
+async fn clear_packets_with_inverted_sequence_range() {
+    let (tx, rx) = crossbeam_channel::unbounded();
+
+    let handle = spawn(("127.0.0.1", 19105), tx).unwrap();
+
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let response: JsonResult<(), serde_json::Value> = reqwest::Client::new()
+        .post("http://127.0.0.1:19105/clear_packets?seq_start=10&seq_end=5")
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+
+    match response {
+        JsonResult::Error(e) => assert_eq!(e["name"], "InvalidSequenceRange"),
+        JsonResult::Success(()) => panic!("clearing an inverted sequence range succeeded"),
+    }
+
+    // No packets are cleared
+    assert!(rx.try_recv().is_err());
+
+    drop(handle);
+}
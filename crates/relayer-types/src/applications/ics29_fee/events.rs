@@ -131,7 +131,7 @@ impl From<DistributeFeePacket> for abci::Event {
     fn from(event: DistributeFeePacket) -> Self {
         let attributes = vec![
             ("receiver", event.receiver.to_string()).into(),
-            ("fee", event.fee.denom.as_str()).into(),
+            ("fee", event.fee.to_string()).into(),
             ("distribution_type", event.distribution_type.to_string()).into(),
         ];
 
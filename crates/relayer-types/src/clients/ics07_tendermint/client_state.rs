@@ -28,6 +28,7 @@ pub const TENDERMINT_CLIENT_STATE_TYPE_URL: &str = "/ibc.lightclients.tendermint
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ClientState {
     pub chain_id: ChainId,
+    #[serde(serialize_with = "crate::core::ics02_client::trust_threshold::serialize_with_decimal")]
     pub trust_threshold: TrustThreshold,
     pub trusting_period: Duration,
     pub unbonding_period: Duration,
@@ -73,8 +73,85 @@ impl TrustThreshold {
     pub fn denominator(&self) -> u64 {
         *self.0.denom()
     }
+
+    /// The decimal value of this trust threshold, as the `f64` nearest to the fraction,
+    /// which is accurate to 15 significant decimal digits. For instance, the decimal
+    /// value of [`TrustThreshold::TWO_THIRDS`] is `0.6666666666666666`.
+    ///
+    /// The denominator of a trust threshold cannot be zero, as [`TrustThreshold::new`]
+    /// rejects it, except for [`TrustThreshold::CLIENT_STATE_RESET`], whose decimal
+    /// value is `0`.
+    pub fn as_decimal(&self) -> f64 {
+        if self.denominator() == 0 {
+            return 0.0;
+        }
+
+        self.numerator() as f64 / self.denominator() as f64
+    }
+
+    /// Parses a trust threshold written as a decimal number, eg. `0.75`, into the
+    /// nearest fraction whose denominator divides `10^MAX_DECIMALS`.
+    ///
+    /// Unlike [`TrustThreshold::new`], the resulting trust threshold must be in the
+    /// range `[1/3, 1)`, as a decimal number is only accepted in the configuration.
+    fn from_decimal_str(s: &str) -> Result<Self, String> {
+        let invalid = || format!("invalid trust threshold, must be a fraction or a decimal: {s}");
+
+        let (integer, decimals) = s.split_once('.').unwrap_or((s, ""));
+
+        let is_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+
+        if (integer.is_empty() && decimals.is_empty())
+            || !is_digits(integer)
+            || !is_digits(decimals)
+        {
+            return Err(invalid());
+        }
+
+        let integer: u64 = if integer.is_empty() {
+            0
+        } else {
+            integer.parse().map_err(|_| invalid())?
+        };
+
+        let denominator = 10u64.pow(MAX_DECIMALS);
+
+        // Keep `MAX_DECIMALS` decimals, rounding to the nearest on the next one
+        let kept = decimals.get(..MAX_DECIMALS as usize).unwrap_or(decimals);
+        let mut fraction: u64 = format!("{kept:0<width$}", width = MAX_DECIMALS as usize)
+            .parse()
+            .map_err(|_| invalid())?;
+
+        if decimals
+            .as_bytes()
+            .get(MAX_DECIMALS as usize)
+            .is_some_and(|digit| *digit >= b'5')
+        {
+            fraction += 1;
+        }
+
+        let numerator = integer
+            .checked_mul(denominator)
+            .and_then(|numerator| numerator.checked_add(fraction))
+            .ok_or_else(invalid)?;
+
+        let threshold = Ratio::new(numerator, denominator);
+
+        if threshold < Self::ONE_THIRD.0 || threshold >= Ratio::from_integer(1) {
+            return Err(format!(
+                "invalid trust threshold {s}, must be greater than or equal to 1/3 and \
+                less than 1 (write '1/3' for exactly one third)"
+            ));
+        }
+
+        Self::new(*threshold.numer(), *threshold.denom()).map_err(|e| e.to_string())
+    }
 }
 
+/// The maximum number of decimals of a trust threshold written as a decimal number,
+/// beyond which it is rounded to the nearest.
+const MAX_DECIMALS: u32 = 6;
+
 /// Conversion from Tendermint domain type into IBC domain type.
 impl From<TrustThresholdFraction> for TrustThreshold {
     fn from(t: TrustThresholdFraction) -> Self {
@@ -125,6 +202,10 @@ impl FromStr for TrustThreshold {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if !s.contains('/') {
+            return Self::from_decimal_str(s);
+        }
+
         let parts: Vec<&str> = s.split('/').collect();
 
         if parts.len() != 2 {
@@ -155,6 +236,26 @@ impl Serialize for TrustThreshold {
     }
 }
 
+/// Serializes a trust threshold along with its decimal value, see [`TrustThreshold::as_decimal`],
+/// eg. `{ "numerator": 2, "denominator": 3, "decimal": 0.6666666666666666 }`.
+///
+/// The decimal value is ignored when deserializing the trust threshold back.
+pub fn serialize_with_decimal<S>(
+    trust_threshold: &TrustThreshold,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    use serde::ser::SerializeStruct;
+
+    let mut s = serializer.serialize_struct("TrustThreshold", 3)?;
+    s.serialize_field("numerator", &trust_threshold.numerator())?;
+    s.serialize_field("denominator", &trust_threshold.denominator())?;
+    s.serialize_field("decimal", &trust_threshold.as_decimal())?;
+    s.end()
+}
+
 impl<'de> Deserialize<'de> for TrustThreshold {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -175,7 +276,7 @@ impl<'de> Deserialize<'de> for TrustThreshold {
 
             fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
                 formatter.write_str(
-                    "string (eg. '1/3') or map `{ numerator = <int>, denominator = <int> }`",
+                    "string (eg. '1/3' or '0.75'), decimal (eg. 0.75) or map `{ numerator = <int>, denominator = <int> }`",
                 )
             }
 
@@ -183,7 +284,14 @@ impl<'de> Deserialize<'de> for TrustThreshold {
             where
                 E: de::Error,
             {
-                Ok(FromStr::from_str(value).unwrap())
+                FromStr::from_str(value).map_err(de::Error::custom)
+            }
+
+            fn visit_f64<E>(self, value: f64) -> Result<TrustThreshold, E>
+            where
+                E: de::Error,
+            {
+                FromStr::from_str(&value.to_string()).map_err(de::Error::custom)
             }
 
             fn visit_map<M>(self, map: M) -> Result<TrustThreshold, M::Error>
@@ -248,3 +356,115 @@ where
 
     deserializer.deserialize_any(StringOrInt)
 }
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+
+    use super::{serialize_with_decimal, TrustThreshold};
+
+    fn parse(value: &str) -> Result<TrustThreshold, String> {
+        serde_json::from_str(value).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn deserialize_fraction() {
+        let expected = TrustThreshold::new(2, 3).unwrap();
+
+        assert_eq!(parse(r#""2/3""#).unwrap(), expected);
+        assert_eq!(
+            parse(r#"{ "numerator": "2", "denominator": "3" }"#).unwrap(),
+            expected
+        );
+        assert_eq!(
+            parse(r#"{ "numerator": 2, "denominator": 3 }"#).unwrap(),
+            expected
+        );
+        assert!(parse(r#""4/3""#).is_err());
+    }
+
+    #[test]
+    fn deserialize_decimal() {
+        assert_eq!(
+            parse(r#""0.75""#).unwrap(),
+            TrustThreshold::new(3, 4).unwrap()
+        );
+        assert_eq!(parse("0.75").unwrap(), TrustThreshold::new(3, 4).unwrap());
+        assert_eq!(
+            parse(r#"".5""#).unwrap(),
+            TrustThreshold::new(1, 2).unwrap()
+        );
+        assert_eq!(
+            parse(r#""0.666667""#).unwrap(),
+            TrustThreshold::new(666_667, 1_000_000).unwrap()
+        );
+
+        // Rounded to the nearest fraction of denominator 10^6
+        assert_eq!(
+            parse(r#""0.66666666""#).unwrap(),
+            TrustThreshold::new(666_667, 1_000_000).unwrap()
+        );
+    }
+
+    #[test]
+    fn deserialize_decimal_out_of_range() {
+        // Strictly less than 1/3
+        assert!(parse(r#""0.333""#).is_err());
+        assert!(parse(r#""0.3333333""#).is_err());
+        assert!(parse(r#""0""#).is_err());
+
+        assert!(parse(r#""1""#).is_err());
+        assert!(parse(r#""1.0""#).is_err());
+        assert!(parse("1.5").is_err());
+
+        assert!(parse(r#""0.5.1""#).is_err());
+        assert!(parse(r#""-0.5""#).is_err());
+        assert!(parse(r#"".""#).is_err());
+    }
+
+    #[test]
+    fn decimal_value() {
+        let threshold = TrustThreshold::new(13, 23).unwrap();
+        assert_eq!(threshold.as_decimal(), 13.0 / 23.0);
+        assert_eq!(threshold.as_decimal().to_string(), "0.5652173913043478");
+
+        assert_eq!(TrustThreshold::TWO_THIRDS.as_decimal(), 2.0 / 3.0);
+        assert_eq!(
+            TrustThreshold::TWO_THIRDS.as_decimal().to_string(),
+            "0.6666666666666666"
+        );
+
+        assert_eq!(TrustThreshold::CLIENT_STATE_RESET.as_decimal(), 0.0);
+        assert!(TrustThreshold::new(0, 0).is_err());
+    }
+
+    #[test]
+    fn serialize_decimal_value() {
+        #[derive(Serialize)]
+        struct Query {
+            #[serde(serialize_with = "serialize_with_decimal")]
+            trust_threshold: TrustThreshold,
+        }
+
+        let query = Query {
+            trust_threshold: TrustThreshold::TWO_THIRDS,
+        };
+
+        let json = serde_json::to_value(&query).unwrap();
+
+        assert_eq!(
+            json["trust_threshold"],
+            serde_json::json!({
+                "numerator": 2,
+                "denominator": 3,
+                "decimal": 0.6666666666666666,
+            })
+        );
+
+        // The decimal value is ignored when deserializing
+        assert_eq!(
+            parse(&json["trust_threshold"].to_string()).unwrap(),
+            TrustThreshold::TWO_THIRDS
+        );
+    }
+}
@@ -226,6 +226,21 @@ impl Timeout {
         }
     }
 
+    /// Whether the counterparty reached the timeout at the given height and time,
+    /// ie. whether either of them is at or past the corresponding timeout.
+    pub fn has_elapsed(&self, height: Height, timestamp: Timestamp) -> bool {
+        let (timeout_height, timeout_timestamp) = self.clone().into_tuple();
+
+        let height_elapsed = timeout_height.is_some_and(|timeout_height| height >= timeout_height);
+
+        let timestamp_elapsed = timeout_timestamp.is_some_and(|timeout_timestamp| {
+            timestamp != Timestamp::none()
+                && timestamp.nanoseconds() >= timeout_timestamp.nanoseconds()
+        });
+
+        height_elapsed || timestamp_elapsed
+    }
+
     pub fn into_tuple(self) -> (Option<Height>, Option<Timestamp>) {
         match self {
             Timeout::Height(height) => (Some(height), None),
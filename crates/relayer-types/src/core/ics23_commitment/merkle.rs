@@ -50,6 +50,15 @@ impl From<MerkleProof> for RawMerkleProof {
 }
 
 impl MerkleProof {
+    /// The value which this proof proves to be stored under the key in the
+    /// store of the chain, ie. by its first proof, if it is a membership proof.
+    pub fn proven_value(&self) -> Option<&[u8]> {
+        match self.proofs.first()?.proof.as_ref()? {
+            Proof::Exist(existence_proof) => Some(&existence_proof.value),
+            _ => None,
+        }
+    }
+
     pub fn verify_membership(
         &self,
         specs: &ProofSpecs,
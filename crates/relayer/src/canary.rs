@@ -0,0 +1,135 @@
+//! Canary packets, ie. transfers of a dust amount from the relayer account to its
+//! account on the counterparty chain, which are periodically sent on a channel to
+//! check that its path relays packets end-to-end.
+
+use core::time::Duration;
+use std::thread;
+use std::time::Instant;
+
+use flex_error::define_error;
+use tracing::debug;
+
+use ibc_relayer_types::core::ics04_channel::packet::Sequence;
+use ibc_relayer_types::core::ics24_host::identifier::ChannelId;
+use ibc_relayer_types::events::IbcEvent;
+
+use crate::chain::handle::ChainHandle;
+use crate::chain::requests::{IncludeProof, QueryHeight, QueryPacketCommitmentRequest};
+use crate::config::CanaryConfig;
+use crate::error::Error;
+use crate::transfer::{build_and_send_transfer_messages, TransferError, TransferOptions};
+
+/// Interval at which the commitment of a canary packet is queried
+/// to find out whether the packet was relayed.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The canary packets time out after this many times the threshold, so that
+/// they are never cleared by a timeout before the threshold has elapsed.
+const TIMEOUT_FACTOR: u32 = 10;
+
+define_error! {
+    CanaryError {
+        Transfer
+            [ TransferError ]
+            |_| { "failed to send canary packet" },
+
+        Query
+            [ Error ]
+            |_| { "failed to query the commitment of the canary packet" },
+
+        MissingSendPacket
+            |_| { "no SendPacket event found for the canary packet" },
+    }
+}
+
+/// The outcome of a canary packet.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CanaryReport {
+    pub sequence: Sequence,
+
+    /// Whether the packet was received and acknowledged within the threshold.
+    pub success: bool,
+
+    /// Time from the commit of the transfer until the packet was acknowledged,
+    /// or until the threshold elapsed if it was not.
+    pub latency: Duration,
+}
+
+/// Sends a canary packet on the given channel of the source chain, and waits
+/// until the packet is acknowledged, or until the threshold of the canary elapses.
+///
+/// The packet is considered acknowledged once its commitment is deleted from
+/// the source chain, which requires the packet to be relayed in both directions.
+pub fn send_canary<SrcChain: ChainHandle, DstChain: ChainHandle>(
+    src_chain: &SrcChain,
+    dst_chain: &DstChain,
+    channel_id: &ChannelId,
+    config: &CanaryConfig,
+) -> Result<CanaryReport, CanaryError> {
+    let opts = TransferOptions {
+        src_port_id: config.port.clone(),
+        src_channel_id: channel_id.clone(),
+        amount: config.amount.into(),
+        denom: config.denom.clone(),
+        receiver: None,
+        timeout_height_offset: 0,
+        timeout_duration: config.threshold * TIMEOUT_FACTOR,
+        number_msgs: 1,
+        memo: None,
+    };
+
+    let events = build_and_send_transfer_messages(src_chain, dst_chain, &opts)
+        .map_err(CanaryError::transfer)?;
+
+    let sent_at = Instant::now();
+
+    let sequence = events
+        .iter()
+        .find_map(|event| match &event.event {
+            IbcEvent::SendPacket(send_packet) => Some(send_packet.packet.sequence),
+            _ => None,
+        })
+        .ok_or_else(CanaryError::missing_send_packet)?;
+
+    debug!(
+        "sent canary packet {} on {}/{} of chain {}",
+        sequence,
+        config.port,
+        channel_id,
+        src_chain.id()
+    );
+
+    loop {
+        let (commitment, _) = src_chain
+            .query_packet_commitment(
+                QueryPacketCommitmentRequest {
+                    port_id: config.port.clone(),
+                    channel_id: channel_id.clone(),
+                    sequence,
+                    height: QueryHeight::Latest,
+                },
+                IncludeProof::No,
+            )
+            .map_err(CanaryError::query)?;
+
+        let latency = sent_at.elapsed();
+
+        if commitment.is_empty() {
+            return Ok(CanaryReport {
+                sequence,
+                success: true,
+                latency,
+            });
+        }
+
+        if latency >= config.threshold {
+            return Ok(CanaryReport {
+                sequence,
+                success: false,
+                latency,
+            });
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
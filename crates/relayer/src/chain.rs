@@ -4,9 +4,11 @@ pub mod cosmos;
 pub mod counterparty;
 pub mod endpoint;
 pub mod handle;
+pub mod health;
 pub mod namada;
 pub mod penumbra;
 pub mod requests;
 pub mod runtime;
+pub mod signing_accounts;
 pub mod tracking;
 pub mod version;
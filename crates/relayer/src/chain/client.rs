@@ -1,6 +1,7 @@
 //! Data structures and logic to set up IBC client's parameters.
 
 use crate::chain::client_settings::Settings;
+use crate::client_state::AnyClientState;
 use crate::config::ChainConfig;
 use crate::foreign_client::CreateOptions;
 
@@ -30,4 +31,15 @@ impl ClientSettings {
             dst_chain_config,
         ))
     }
+
+    /// The settings which the given client state was built with.
+    pub fn from_client_state(client_state: &AnyClientState) -> Self {
+        match client_state {
+            AnyClientState::Tendermint(state) => ClientSettings::Tendermint(Settings {
+                max_clock_drift: state.max_clock_drift,
+                trusting_period: Some(state.trusting_period),
+                trust_threshold: state.trust_threshold,
+            }),
+        }
+    }
 }
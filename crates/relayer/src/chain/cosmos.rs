@@ -9,12 +9,13 @@ use itertools::Itertools;
 use num_bigint::BigInt;
 use prost::Message;
 use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::thread;
 use tokio::runtime::Runtime as TokioRuntime;
 use tonic::codegen::http::Uri;
-use tonic::metadata::AsciiMetadataValue;
-use tracing::{debug, error, instrument, trace, warn};
+use tracing::{debug, instrument, trace, warn};
 
+use ibc_proto::cosmos::auth::v1beta1::query_client::QueryClient as AuthQueryClient;
 use ibc_proto::cosmos::base::node::v1beta1::ConfigResponse;
 use ibc_proto::cosmos::staking::v1beta1::{Params as StakingParams, QueryParamsResponse};
 use ibc_proto::ibc::apps::fee::v1::{
@@ -66,7 +67,7 @@ use tendermint_light_client::verifier::types::LightBlock as TmLightBlock;
 use tendermint_rpc::client::CompatMode;
 use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
 use tendermint_rpc::endpoint::status;
-use tendermint_rpc::{Client, HttpClient, Order};
+use tendermint_rpc::Order;
 
 use crate::account::Balance;
 use crate::chain::client::ClientSettings;
@@ -74,66 +75,91 @@ use crate::chain::cosmos::batch::{
     send_batched_messages_and_wait_check_tx, send_batched_messages_and_wait_commit,
     sequential_send_batched_messages_and_wait_commit,
 };
-use crate::chain::cosmos::encode::key_pair_to_signer;
 use crate::chain::cosmos::fee::maybe_register_counterparty_payee;
-use crate::chain::cosmos::gas::{calculate_fee, mul_ceil};
-use crate::chain::cosmos::query::account::get_or_fetch_account;
+use crate::chain::cosmos::fee_denom::{ensure_fee_denom_balance, select_gas_price};
+use crate::chain::cosmos::gas::{calculate_fee, max_fee_amount, mul_ceil};
+use crate::chain::cosmos::query::account::{get_or_fetch_account, query_account};
 use crate::chain::cosmos::query::balance::{query_all_balances, query_balance};
 use crate::chain::cosmos::query::connection::query_connection_params;
 use crate::chain::cosmos::query::consensus_state::query_consensus_state_heights;
 use crate::chain::cosmos::query::custom::cross_chain_query_via_rpc;
 use crate::chain::cosmos::query::denom_trace::query_denom_trace;
 use crate::chain::cosmos::query::fee::query_incentivized_packet;
+use crate::chain::cosmos::query::node_config::query_node_config;
+use crate::chain::cosmos::query::packet::{query_sequences_in_pages, UNRECEIVED_PACKETS_PAGE_SIZE};
+use crate::chain::cosmos::query::relayer_whitelist::{
+    query_relayer_whitelist, recv_signer_whitelist_warning,
+};
 use crate::chain::cosmos::query::status::query_status;
 use crate::chain::cosmos::query::tx::{
     filter_matching_event, query_packets_from_block, query_packets_from_txs, query_txs,
 };
-use crate::chain::cosmos::query::{abci_query, fetch_version_specs, packet_query, QueryResponse};
+use crate::chain::cosmos::query::{
+    abci_query, fetch_version_specs, insert_height_header, packet_query, QueryResponse,
+};
+use crate::chain::cosmos::request_rate::{PacedRpcClient, RequestRateLimiter};
+use crate::chain::cosmos::sticky_session::{
+    build_rpc_client, create_pinned_grpc_client, Session, StickySession,
+};
+use crate::chain::cosmos::tx::encode_signer;
 use crate::chain::cosmos::types::account::Account;
 use crate::chain::cosmos::types::config::TxConfig;
 use crate::chain::cosmos::types::gas::{
     default_gas_from_config, gas_multiplier_from_config, max_gas_from_config,
 };
-use crate::chain::endpoint::{ChainEndpoint, ChainStatus, HealthCheck};
+use crate::chain::endpoint::{ChainEndpoint, ChainStatus};
 use crate::chain::handle::Subscription;
+use crate::chain::health::{HealthCheckKind, HealthReport};
 use crate::chain::requests::*;
 use crate::chain::tracking::TrackedMsgs;
 use crate::chain::version::Specs;
 use crate::client_state::{AnyClientState, IdentifiedAnyClientState};
 use crate::config::Error as ConfigError;
-use crate::config::{parse_gas_prices, ChainConfig, GasPrice};
+use crate::config::{parse_gas_prices, ChainConfig, GasPrice, GasPrices};
 use crate::consensus_state::AnyConsensusState;
 use crate::denom::DenomTrace;
 use crate::error::Error;
-use crate::event::source::{EventSource, TxEventSourceCmd};
+use crate::event::source::{rpc, EventSource, ReconnectBackoff, TxEventSourceCmd};
 use crate::event::IbcEventWithHeight;
 use crate::keyring::{KeyRing, Secp256k1KeyPair, SigningKeyPair};
 use crate::light_client::tendermint::LightClient as TmLightClient;
 use crate::light_client::{LightClient, Verified};
 use crate::misbehaviour::MisbehaviourEvidence;
 use crate::util::collate::CollatedIterExt;
-use crate::util::create_grpc_client;
 use crate::util::pretty::{
     PrettyIdentifiedChannel, PrettyIdentifiedClientState, PrettyIdentifiedConnection,
 };
-use crate::HERMES_VERSION;
 
 use self::gas::dynamic_gas_price;
-use self::types::gas::GasConfig;
 
+pub mod base_fee_history;
 pub mod batch;
+pub mod block_gas;
+pub mod chain_id_drift;
 pub mod client;
 pub mod compatibility;
+pub mod competition;
 pub mod config;
 pub mod eip_base_fee;
 pub mod encode;
 pub mod estimate;
 pub mod fee;
+pub mod fee_budget;
+pub mod fee_denom;
+pub mod fees_spent;
 pub mod gas;
+pub mod price_oracle;
 pub mod query;
+pub mod request_rate;
 pub mod retry;
 pub mod simulate;
+pub mod sticky_session;
+#[cfg(feature = "submit-delay")]
+pub mod submit_delay;
+pub mod timeout;
 pub mod tx;
+pub mod tx_interval;
+pub mod txfees;
 pub mod types;
 pub mod version;
 pub mod wait;
@@ -157,7 +183,7 @@ pub const BLOCK_MAX_BYTES_MAX_FRACTION: f64 = 0.9;
 pub struct CosmosSdkChain {
     config: config::CosmosSdkConfig,
     tx_config: TxConfig,
-    pub rpc_client: HttpClient,
+    pub rpc_client: PacedRpcClient,
     compat_mode: CompatMode,
     grpc_addr: Uri,
     light_client: TmLightClient,
@@ -167,7 +193,17 @@ pub struct CosmosSdkChain {
     /// A cached copy of the account information
     account: Option<Account>,
 
+    /// Cached copies of the account information of the keys
+    /// used instead of the configured key, indexed by key name
+    key_accounts: HashMap<String, Option<Account>>,
+
     tx_monitor_cmd: Option<TxEventSourceCmd>,
+
+    /// The session pinning the queries to a backend node of a load balancer, if any
+    sticky_session: Option<StickySession>,
+
+    /// The pacing of the requests made to the chain, if limited
+    request_limiter: Option<Arc<RequestRateLimiter>>,
 }
 
 impl CosmosSdkChain {
@@ -181,10 +217,147 @@ impl CosmosSdkChain {
         self.config.max_tx_size.into()
     }
 
+    /// The session pinning the gRPC queries to a backend node of the load balancer, if any.
+    fn grpc_session(&self) -> Option<&Arc<Session>> {
+        self.sticky_session
+            .as_ref()
+            .map(StickySession::grpc_session)
+    }
+
+    /// Builds anew the RPC clients of the chain, so that they send the current session
+    /// of the load balancer along with every request.
+    fn rebuild_pinned_clients(&mut self) -> Result<(), Error> {
+        let session = self.sticky_session.as_ref().map(StickySession::rpc_session);
+
+        let mut rpc_client = PacedRpcClient::new(
+            build_rpc_client(&self.config.rpc_addr, self.config.rpc_timeout, session)?,
+            self.request_limiter.clone(),
+        );
+        rpc_client.set_compat_mode(self.compat_mode);
+
+        self.light_client = TmLightClient::from_cosmos_sdk_config(
+            &self.config,
+            self.light_client.peer_id(),
+            session,
+            self.request_limiter.clone(),
+        )?;
+        self.rpc_client = rpc_client;
+
+        Ok(())
+    }
+
     fn key(&self) -> Result<Secp256k1KeyPair, Error> {
-        self.keybase()
-            .get_key(&self.config.key_name)
-            .map_err(Error::key_base)
+        self.named_key(&self.config.key_name)
+    }
+
+    fn named_key(&self, key_name: &str) -> Result<Secp256k1KeyPair, Error> {
+        self.keybase().get_key(key_name).map_err(Error::key_base)
+    }
+
+    /// The account which the given key signs transactions as, ie. the multisig
+    /// account configured for this chain if the key is one of its members.
+    fn key_account(&self, key_pair: &Secp256k1KeyPair) -> Result<String, Error> {
+        match &self.tx_config.multisig {
+            Some(multisig) if multisig.key().is_member(&key_pair.public_key) => multisig
+                .key()
+                .account(&self.config.account_prefix)
+                .map_err(Error::key_base),
+            _ => Ok(key_pair.account()),
+        }
+    }
+
+    /// The configuration of the next transaction signed by the given account, paying its
+    /// fees at the first of the configured gas prices for which the balance of the account
+    /// covers the maximum fee, if several gas prices are configured. Returns `None` if the
+    /// configuration of the chain applies as is, ie. if a single gas price is configured,
+    /// or if the fees are paid by a fee granter.
+    ///
+    /// If an acquire fee denom command is configured, it is run when the balance of the fee
+    /// denomination does not cover the maximum fee, and the transaction fails if it still does
+    /// not cover it afterwards. If none of the balances covers the maximum fee, the preferred
+    /// gas price is kept, for the command to acquire its denomination.
+    async fn fee_denom_tx_config(&self, key_account: &str) -> Result<Option<TxConfig>, Error> {
+        let gas_config = &self.tx_config.gas_config;
+        let balances = &self.tx_config.fee_denom_balances;
+        let acquire_command = &self.config.acquire_fee_denom_command;
+        let has_fallbacks = self.config.gas_price.has_fallbacks();
+
+        if (!has_fallbacks && acquire_command.is_empty()) || !gas_config.fee_granter.is_empty() {
+            return Ok(None);
+        }
+
+        let candidates = self
+            .tx_config
+            .fee_denom_candidates
+            .max_fees(|gas_config| async move {
+                max_fee_amount(
+                    &gas_config,
+                    &self.config.rpc_addr,
+                    self.request_limiter.as_ref(),
+                )
+                .await
+            })
+            .await;
+
+        let selected = if has_fallbacks {
+            select_gas_price(
+                &self.config.id,
+                key_account,
+                &candidates,
+                balances,
+                |denom| self.query_fee_denom_balance(key_account, denom),
+            )
+            .await
+        } else {
+            Ok(&candidates[0])
+        };
+
+        let (gas_price, max_fee) = match selected {
+            Ok(selected) => selected,
+            Err(_) if !acquire_command.is_empty() => &candidates[0],
+            Err(e) => return Err(e),
+        };
+
+        if !acquire_command.is_empty() {
+            ensure_fee_denom_balance(
+                &self.config.id,
+                key_account,
+                &gas_price.denom,
+                *max_fee,
+                acquire_command,
+                balances,
+                || self.query_fee_denom_balance(key_account, &gas_price.denom),
+            )
+            .await?;
+        }
+
+        balances.spend(key_account, &gas_price.denom, *max_fee);
+
+        Ok(has_fallbacks.then(|| TxConfig {
+            gas_config: self
+                .tx_config
+                .fee_denom_candidates
+                .gas_config(gas_price)
+                .unwrap_or(gas_config)
+                .clone(),
+            ..self.tx_config.clone()
+        }))
+    }
+
+    /// The spendable balance of the given account in the given fee denomination.
+    async fn query_fee_denom_balance(&self, key_account: &str, denom: &str) -> Result<u128, Error> {
+        let balance = query_balance(
+            &self.grpc_addr,
+            self.grpc_session(),
+            self.request_limiter.as_ref(),
+            key_account,
+            denom,
+        )
+        .await?;
+
+        // The amount may be a decimal, only its integer part is spendable
+        let amount = balance.amount.split('.').next().unwrap_or_default();
+        amount.parse().map_err(Error::parse_int)
     }
 
     /// Fetches the trusting period as a `Duration` from the chain config.
@@ -303,7 +476,11 @@ impl CosmosSdkChain {
         // Query Connection Params with gRPC endpoint to retrieve the `max_expected_time_per_block` value and verify the
         // configured `max_block_time`.
         // If it is not found, the verification for the configured `max_block_time` is skipped.
-        match self.block_on(query_connection_params(&self.grpc_addr)) {
+        match self.block_on(query_connection_params(
+            &self.grpc_addr,
+            self.grpc_session(),
+            self.request_limiter.as_ref(),
+        )) {
             Ok(params) => {
                 debug!(
                     "queried `max_expected_time_per_block`: `{}ns`",
@@ -343,11 +520,19 @@ impl CosmosSdkChain {
         use crate::config::EventSourceMode as Mode;
 
         let (event_source, monitor_tx) = match &self.config.event_source {
-            Mode::Push { url, batch_delay } => EventSource::websocket(
+            Mode::Push {
+                url,
+                batch_delay,
+                queries,
+                reconnect_backoff,
+                max_reconnect_backoff,
+            } => EventSource::websocket(
                 self.config.id.clone(),
                 url.clone(),
                 self.compat_mode,
                 *batch_delay,
+                queries.iter().cloned().map(Into::into).collect(),
+                ReconnectBackoff::new(*reconnect_backoff, *max_reconnect_backoff),
                 self.rt.clone(),
             ),
             Mode::Pull {
@@ -358,6 +543,7 @@ impl CosmosSdkChain {
                 self.rpc_client.clone(),
                 *interval,
                 *max_retries,
+                rpc::last_processed_height_path(&self.config.id),
                 self.rt.clone(),
             ),
         }
@@ -379,8 +565,10 @@ impl CosmosSdkChain {
         );
         crate::telemetry!(query, self.id(), "query_ccv_consumer_chain_params");
 
-        let mut client = self.block_on(create_grpc_client(
+        let mut client = self.block_on(create_pinned_grpc_client(
             &self.grpc_addr,
+            self.grpc_session(),
+            self.request_limiter.as_ref(),
             ibc_proto::interchain_security::ccv::consumer::v1::query_client::QueryClient::new,
         ))?;
 
@@ -450,18 +638,8 @@ impl CosmosSdkChain {
         );
         crate::telemetry!(query, self.id(), "query_config_params");
 
-        let query_response = self.block_on(abci_query(
-            &self.rpc_client,
-            &self.config().rpc_addr,
-            "/cosmos.base.node.v1beta1.Service/Config".to_owned(),
-            "".to_owned(),
-            QueryHeight::Latest.into(),
-            false,
-        ))?;
         let config_response =
-            ConfigResponse::decode(query_response.value.as_ref()).map_err(|e| {
-                Error::protobuf_decode("cosmos.base.node.v1beta1.Service/Config".to_owned(), e)
-            })?;
+            self.block_on(query_node_config(&self.rpc_client, &self.config().rpc_addr))?;
 
         Ok(Some(config_response))
     }
@@ -483,12 +661,11 @@ impl CosmosSdkChain {
     }
 
     pub fn dynamic_gas_price(&self) -> GasPrice {
-        let gas_config = GasConfig::from(self.config());
-
-        self.rt.block_on(dynamic_gas_price(
-            &gas_config,
+        self.block_on(dynamic_gas_price(
+            &self.tx_config.gas_config,
             &self.config.id,
             &self.config.rpc_addr,
+            self.request_limiter.as_ref(),
         ))
     }
 
@@ -669,7 +846,7 @@ impl CosmosSdkChain {
         );
         crate::telemetry!(query, self.id(), "query_latest_height");
 
-        let status = self.rt.block_on(query_status(
+        let status = self.block_on(query_status(
             self.id(),
             &self.rpc_client,
             &self.config.rpc_addr,
@@ -700,22 +877,39 @@ impl CosmosSdkChain {
 
         let proto_msgs = tracked_msgs.msgs;
 
-        let key_pair = self.key()?;
-        let key_account = key_pair.account();
+        let key_pair = match &tracked_msgs.key_name {
+            Some(key_name) => self.named_key(key_name)?,
+            None => self.key()?,
+        };
+        let key_account = self.key_account(&key_pair)?;
 
-        let account =
-            get_or_fetch_account(&self.grpc_addr, &key_account, &mut self.account).await?;
+        let tx_config = self.fee_denom_tx_config(&key_account).await?;
+        let tx_config = tx_config.as_ref().unwrap_or(&self.tx_config);
 
-        let memo_prefix = if let Some(memo_overwrite) = &self.config.memo_overwrite {
-            memo_overwrite.clone()
-        } else {
-            self.config.memo_prefix.clone()
+        let cached_account = match tracked_msgs.key_name {
+            Some(key_name) => self.key_accounts.entry(key_name).or_default(),
+            None => &mut self.account,
         };
 
+        let account = get_or_fetch_account(
+            &self.grpc_addr,
+            tx_config.grpc_session.as_ref(),
+            tx_config.request_limiter.as_ref(),
+            &key_account,
+            self.config.account_query,
+            cached_account,
+        )
+        .await?;
+
+        let memo_prefix = self
+            .config
+            .tx_memo(tracked_msgs.counterparty_chain_id.as_ref())
+            .clone();
+
         if self.config.sequential_batch_tx {
             sequential_send_batched_messages_and_wait_commit(
                 &self.rpc_client,
-                &self.tx_config,
+                tx_config,
                 &key_pair,
                 account,
                 &memo_prefix,
@@ -725,7 +919,7 @@ impl CosmosSdkChain {
         } else {
             send_batched_messages_and_wait_commit(
                 &self.rpc_client,
-                &self.tx_config,
+                tx_config,
                 &key_pair,
                 account,
                 &memo_prefix,
@@ -757,21 +951,38 @@ impl CosmosSdkChain {
 
         let proto_msgs = tracked_msgs.msgs;
 
-        let key_pair = self.key()?;
-        let key_account = key_pair.account();
+        let key_pair = match &tracked_msgs.key_name {
+            Some(key_name) => self.named_key(key_name)?,
+            None => self.key()?,
+        };
+        let key_account = self.key_account(&key_pair)?;
 
-        let account =
-            get_or_fetch_account(&self.grpc_addr, &key_account, &mut self.account).await?;
+        let tx_config = self.fee_denom_tx_config(&key_account).await?;
+        let tx_config = tx_config.as_ref().unwrap_or(&self.tx_config);
 
-        let memo_prefix = if let Some(memo_overwrite) = &self.config.memo_overwrite {
-            memo_overwrite.clone()
-        } else {
-            self.config.memo_prefix.clone()
+        let cached_account = match tracked_msgs.key_name {
+            Some(key_name) => self.key_accounts.entry(key_name).or_default(),
+            None => &mut self.account,
         };
 
+        let account = get_or_fetch_account(
+            &self.grpc_addr,
+            tx_config.grpc_session.as_ref(),
+            tx_config.request_limiter.as_ref(),
+            &key_account,
+            self.config.account_query,
+            cached_account,
+        )
+        .await?;
+
+        let memo_prefix = self
+            .config
+            .tx_memo(tracked_msgs.counterparty_chain_id.as_ref())
+            .clone();
+
         send_batched_messages_and_wait_check_tx(
             &self.rpc_client,
-            &self.tx_config,
+            tx_config,
             &key_pair,
             account,
             &memo_prefix,
@@ -916,16 +1127,48 @@ impl ChainEndpoint for CosmosSdkChain {
             return Err(Error::config(ConfigError::wrong_type()));
         };
 
-        let mut rpc_client = HttpClient::builder(config.rpc_addr.clone().try_into().unwrap())
-            .user_agent(format!("hermes/{}", HERMES_VERSION))
-            .build()
-            .map_err(|e| Error::rpc(config.rpc_addr.clone(), e))?;
+        let request_limiter = config
+            .max_requests_per_second
+            .map(|rps| Arc::new(RequestRateLimiter::new(rps)));
+
+        let sticky_session = config.sticky_session.clone().map(|session| {
+            StickySession::new(
+                session,
+                config.rpc_addr.clone(),
+                config.rpc_timeout,
+                request_limiter.clone(),
+            )
+        });
+
+        if let Some(session) = &sticky_session {
+            rt.block_on(session.start_operation())?;
+
+            if session.rpc_session().header().is_none() {
+                warn!(
+                    chain = %config.id,
+                    session = ?config.sticky_session,
+                    "the load balancer did not return a sticky session, \
+                    the queries will not be pinned to a backend node"
+                );
+            }
+        }
+
+        let rpc_session = sticky_session.as_ref().map(StickySession::rpc_session);
+        let mut rpc_client = PacedRpcClient::new(
+            build_rpc_client(&config.rpc_addr, config.rpc_timeout, rpc_session)?,
+            request_limiter.clone(),
+        );
 
         let compat_mode = rt.block_on(fetch_compat_mode(&rpc_client, &config))?;
         rpc_client.set_compat_mode(compat_mode);
 
         let node_info = rt.block_on(fetch_node_info(&rpc_client, &config))?;
-        let light_client = TmLightClient::from_cosmos_sdk_config(&config, node_info.id)?;
+        let light_client = TmLightClient::from_cosmos_sdk_config(
+            &config,
+            node_info.id,
+            rpc_session,
+            request_limiter.clone(),
+        )?;
 
         // Initialize key store and load key
         let keybase = KeyRing::new_secp256k1(
@@ -939,9 +1182,13 @@ impl ChainEndpoint for CosmosSdkChain {
         let grpc_addr = Uri::from_str(&config.grpc_addr.to_string())
             .map_err(|e| Error::invalid_uri(config.grpc_addr.to_string(), e))?;
 
-        let tx_config = TxConfig::try_from(&config)?;
-
-        // Retrieve the version specification of this chain
+        let tx_config = TxConfig {
+            grpc_session: sticky_session
+                .as_ref()
+                .map(|session| session.grpc_session().clone()),
+            request_limiter: request_limiter.clone(),
+            ..TxConfig::try_from(&config)?
+        };
 
         let chain = Self {
             config,
@@ -953,12 +1200,37 @@ impl ChainEndpoint for CosmosSdkChain {
             keybase,
             tx_config,
             account: None,
+            key_accounts: HashMap::new(),
             tx_monitor_cmd: None,
+            sticky_session,
+            request_limiter,
         };
 
         Ok(chain)
     }
 
+    fn start_operation(&mut self) {
+        let Some(session) = &self.sticky_session else {
+            return;
+        };
+
+        match self.block_on(session.start_operation()) {
+            Ok(false) => {}
+            Ok(true) => {
+                if let Err(e) = self.rebuild_pinned_clients() {
+                    warn!(
+                        chain = %self.id(),
+                        "failed to pin the queries to the new session of the load balancer: {e}"
+                    );
+                }
+            }
+            Err(e) => warn!(
+                chain = %self.id(),
+                "failed to check the sticky session with the load balancer: {e}"
+            ),
+        }
+    }
+
     fn shutdown(self) -> Result<(), Error> {
         if let Some(monitor_tx) = self.tx_monitor_cmd {
             monitor_tx.shutdown().map_err(Error::event_source)?;
@@ -999,35 +1271,26 @@ impl ChainEndpoint for CosmosSdkChain {
         Ok(subscription)
     }
 
-    /// Does multiple RPC calls to the full node, to check for
-    /// reachability and some basic APIs are available.
-    ///
-    /// Currently this checks that:
-    ///     - the node responds OK to `/health` RPC call;
-    ///     - the node has transaction indexing enabled;
-    ///     - the SDK & IBC versions are supported;
+    /// Does multiple RPC and gRPC calls to the full node, to check for
+    /// reachability and some basic APIs are available, and validates
+    /// the configuration of the chain against its parameters.
     ///
-    /// Emits a log warning in case anything is amiss.
-    /// Exits early if any health check fails, without doing any
-    /// further checks.
-    fn health_check(&mut self) -> Result<HealthCheck, Error> {
-        if let Err(e) = do_health_check(self) {
-            warn!("health check failed for chain '{}'", self.id());
-            warn!("reason: {}", e.detail());
-            warn!("some Hermes features may not work in this mode!");
-
-            return Ok(HealthCheck::Unhealthy(Box::new(e)));
-        }
+    /// See [`do_health_check`] for the checks performed. The outcome of
+    /// each check is recorded in the returned report, for the caller to render.
+    fn health_check(&mut self) -> Result<HealthReport, Error> {
+        let mut report = HealthReport::new(self.id().clone());
 
-        if let Err(e) = self.validate_params() {
-            warn!("found potential misconfiguration for chain '{}'", self.id());
-            warn!("reason: {}", e.detail());
-            warn!("some Hermes features may not work in this mode!");
+        do_health_check(self, &mut report);
 
-            return Ok(HealthCheck::Unhealthy(Box::new(e)));
+        match self.validate_params() {
+            Ok(()) => report.pass(
+                HealthCheckKind::Params,
+                "configuration is consistent with the parameters of the chain",
+            ),
+            Err(e) => report.fail(HealthCheckKind::Params, e.detail().to_string()),
         }
 
-        Ok(HealthCheck::Healthy)
+        Ok(report)
     }
 
     /// Fetch a header from the chain at the given height and verify it.
@@ -1103,9 +1366,7 @@ impl ChainEndpoint for CosmosSdkChain {
         // Get the key from key seed file
         let key_pair = self.key()?;
 
-        let signer = key_pair_to_signer(&key_pair)?;
-
-        Ok(signer)
+        encode_signer(&self.key_account(&key_pair)?, self.config.signer_encoding)
     }
 
     /// Get the chain configuration
@@ -1135,10 +1396,16 @@ impl ChainEndpoint for CosmosSdkChain {
             Some(key_name) => self.keybase().get_key(key_name).map_err(Error::key_base)?,
             None => self.key()?,
         };
-        let account = key.account();
+        let account = self.key_account(&key)?;
 
-        let denom = denom.unwrap_or(&self.config.gas_price.denom);
-        let balance = self.block_on(query_balance(&self.grpc_addr, &account, denom))?;
+        let denom = denom.unwrap_or(&self.config.gas_price.preferred().denom);
+        let balance = self.block_on(query_balance(
+            &self.grpc_addr,
+            self.grpc_session(),
+            self.request_limiter.as_ref(),
+            &account,
+            denom,
+        ))?;
 
         Ok(balance)
     }
@@ -1156,15 +1423,25 @@ impl ChainEndpoint for CosmosSdkChain {
             Some(key_name) => self.keybase().get_key(key_name).map_err(Error::key_base)?,
             None => self.key()?,
         };
-        let account = key.account();
+        let account = self.key_account(&key)?;
 
-        let balance = self.block_on(query_all_balances(&self.grpc_addr, &account))?;
+        let balance = self.block_on(query_all_balances(
+            &self.grpc_addr,
+            self.grpc_session(),
+            self.request_limiter.as_ref(),
+            &account,
+        ))?;
 
         Ok(balance)
     }
 
     fn query_denom_trace(&self, hash: String) -> Result<DenomTrace, Error> {
-        let denom_trace = self.block_on(query_denom_trace(&self.grpc_addr, &hash))?;
+        let denom_trace = self.block_on(query_denom_trace(
+            &self.grpc_addr,
+            self.grpc_session(),
+            self.request_limiter.as_ref(),
+            &hash,
+        ))?;
 
         Ok(denom_trace)
     }
@@ -1215,6 +1492,26 @@ impl ChainEndpoint for CosmosSdkChain {
         Ok(ChainStatus { height, timestamp })
     }
 
+    fn query_earliest_height(&self) -> Result<ICSHeight, Error> {
+        crate::time!(
+            "query_earliest_height",
+            {
+                "src_chain": self.config().id.to_string(),
+            }
+        );
+        crate::telemetry!(query, self.id(), "query_earliest_height");
+
+        let status = self
+            .block_on(self.rpc_client.status())
+            .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
+
+        ICSHeight::new(
+            ChainId::chain_version(status.node_info.network.as_str()),
+            u64::from(status.sync_info.earliest_block_height),
+        )
+        .map_err(|_| Error::invalid_height_no_source())
+    }
+
     /// Performs a `QueryClientStatesRequest` gRPC query to fetch all the client states
     /// associated with the chain.
     fn query_clients(
@@ -1229,8 +1526,10 @@ impl ChainEndpoint for CosmosSdkChain {
         );
         crate::telemetry!(query, self.id(), "query_clients");
 
-        let mut client = self.block_on(create_grpc_client(
+        let mut client = self.block_on(create_pinned_grpc_client(
             &self.grpc_addr,
+            self.grpc_session(),
+            self.request_limiter.as_ref(),
             ibc_proto::ibc::core::client::v1::query_client::QueryClient::new,
         ))?;
 
@@ -1358,6 +1657,9 @@ impl ChainEndpoint for CosmosSdkChain {
         self.block_on(query_consensus_state_heights(
             self.id(),
             &self.grpc_addr,
+            self.grpc_session(),
+            self.request_limiter.as_ref(),
+            self.config.max_grpc_decoding_size.get_bytes() as usize,
             request,
         ))
     }
@@ -1417,8 +1719,10 @@ impl ChainEndpoint for CosmosSdkChain {
         );
         crate::telemetry!(query, self.id(), "query_client_connections");
 
-        let mut client = self.block_on(create_grpc_client(
+        let mut client = self.block_on(create_pinned_grpc_client(
             &self.grpc_addr,
+            self.grpc_session(),
+            self.request_limiter.as_ref(),
             ibc_proto::ibc::core::connection::v1::query_client::QueryClient::new,
         ))?;
 
@@ -1460,8 +1764,10 @@ impl ChainEndpoint for CosmosSdkChain {
         );
         crate::telemetry!(query, self.id(), "query_connections");
 
-        let mut client = self.block_on(create_grpc_client(
+        let mut client = self.block_on(create_pinned_grpc_client(
             &self.grpc_addr,
+            self.grpc_session(),
+            self.request_limiter.as_ref(),
             ibc_proto::ibc::core::connection::v1::query_client::QueryClient::new,
         ))?;
 
@@ -1515,9 +1821,13 @@ impl ChainEndpoint for CosmosSdkChain {
             use ibc_proto::ibc::core::connection::v1 as connection;
             use tonic::IntoRequest;
 
-            let mut client =
-                create_grpc_client(&chain.grpc_addr, connection::query_client::QueryClient::new)
-                    .await?;
+            let mut client = create_pinned_grpc_client(
+                &chain.grpc_addr,
+                chain.grpc_session(),
+                chain.request_limiter.as_ref(),
+                connection::query_client::QueryClient::new,
+            )
+            .await?;
 
             client = client.max_decoding_message_size(
                 chain.config().max_grpc_decoding_size.get_bytes() as usize,
@@ -1528,11 +1838,7 @@ impl ChainEndpoint for CosmosSdkChain {
             }
             .into_request();
 
-            let height_param = AsciiMetadataValue::try_from(height_query)?;
-
-            request
-                .metadata_mut()
-                .insert("x-cosmos-block-height", height_param);
+            insert_height_header(&mut request, height_query, &chain.config.grpc_height_header)?;
 
             let response = client.connection(request).await.map_err(|e| {
                 if e.code() == tonic::Code::NotFound {
@@ -1595,8 +1901,10 @@ impl ChainEndpoint for CosmosSdkChain {
         );
         crate::telemetry!(query, self.id(), "query_connection_channels");
 
-        let mut client = self.block_on(create_grpc_client(
+        let mut client = self.block_on(create_pinned_grpc_client(
             &self.grpc_addr,
+            self.grpc_session(),
+            self.request_limiter.as_ref(),
             ibc_proto::ibc::core::channel::v1::query_client::QueryClient::new,
         ))?;
 
@@ -1664,8 +1972,10 @@ impl ChainEndpoint for CosmosSdkChain {
         );
         crate::telemetry!(query, self.id(), "query_channels");
 
-        let mut client = self.block_on(create_grpc_client(
+        let mut client = self.block_on(create_pinned_grpc_client(
             &self.grpc_addr,
+            self.grpc_session(),
+            self.request_limiter.as_ref(),
             ibc_proto::ibc::core::channel::v1::query_client::QueryClient::new,
         ))?;
 
@@ -1785,8 +2095,10 @@ impl ChainEndpoint for CosmosSdkChain {
         );
         crate::telemetry!(query, self.id(), "query_channel_client_state");
 
-        let mut client = self.block_on(create_grpc_client(
+        let mut client = self.block_on(create_pinned_grpc_client(
             &self.grpc_addr,
+            self.grpc_session(),
+            self.request_limiter.as_ref(),
             ibc_proto::ibc::core::channel::v1::query_client::QueryClient::new,
         ))?;
 
@@ -1853,8 +2165,10 @@ impl ChainEndpoint for CosmosSdkChain {
         crate::telemetry!(query, self.id(), "query_packet_commitments");
 
         let mut client = self
-            .block_on(create_grpc_client(
+            .block_on(create_pinned_grpc_client(
                 &self.grpc_addr,
+                self.grpc_session(),
+                self.request_limiter.as_ref(),
                 ibc_proto::ibc::core::channel::v1::query_client::QueryClient::new,
             ))
             .map(|client| {
@@ -1863,8 +2177,6 @@ impl ChainEndpoint for CosmosSdkChain {
                 )
             })?;
 
-        let height_param = AsciiMetadataValue::try_from(request.query_height)?;
-
         if request.pagination.is_enabled() {
             let mut results = Vec::new();
             let mut page_key = Vec::new();
@@ -1892,9 +2204,11 @@ impl ChainEndpoint for CosmosSdkChain {
                 // TODO: This should either be configurable or inferred from the pagination
                 tonic_request.set_timeout(Duration::from_secs(10));
 
-                tonic_request
-                    .metadata_mut()
-                    .insert("x-cosmos-block-height", height_param.clone());
+                insert_height_header(
+                    &mut tonic_request,
+                    request.query_height,
+                    &self.config.grpc_height_header,
+                )?;
 
                 let response = self.rt.block_on(async {
                     client
@@ -1954,9 +2268,11 @@ impl ChainEndpoint for CosmosSdkChain {
         } else {
             let mut tonic_request = tonic::Request::new(request.clone().into());
 
-            tonic_request
-                .metadata_mut()
-                .insert("x-cosmos-block-height", height_param);
+            insert_height_header(
+                &mut tonic_request,
+                request.query_height,
+                &self.config.grpc_height_header,
+            )?;
 
             let response = self
                 .block_on(client.packet_commitments(tonic_request))
@@ -2024,27 +2340,43 @@ impl ChainEndpoint for CosmosSdkChain {
         );
         crate::telemetry!(query, self.id(), "query_unreceived_packets");
 
-        let mut client = self.block_on(create_grpc_client(
+        let mut client = self.block_on(create_pinned_grpc_client(
             &self.grpc_addr,
+            self.grpc_session(),
+            self.request_limiter.as_ref(),
             ibc_proto::ibc::core::channel::v1::query_client::QueryClient::new,
         ))?;
 
         client = client
             .max_decoding_message_size(self.config().max_grpc_decoding_size.get_bytes() as usize);
 
-        let request = tonic::Request::new(request.into());
+        let QueryUnreceivedPacketsRequest {
+            port_id,
+            channel_id,
+            packet_commitment_sequences,
+        } = request;
+
+        query_sequences_in_pages(
+            &packet_commitment_sequences,
+            UNRECEIVED_PACKETS_PAGE_SIZE,
+            |page| {
+                let request = tonic::Request::new(
+                    QueryUnreceivedPacketsRequest {
+                        port_id: port_id.clone(),
+                        channel_id: channel_id.clone(),
+                        packet_commitment_sequences: page.to_vec(),
+                    }
+                    .into(),
+                );
 
-        let mut response = self
-            .block_on(client.unreceived_packets(request))
-            .map_err(|e| Error::grpc_status(e, "query_unreceived_packets".to_owned()))?
-            .into_inner();
+                let response = self
+                    .block_on(client.unreceived_packets(request))
+                    .map_err(|e| Error::grpc_status(e, "query_unreceived_packets".to_owned()))?
+                    .into_inner();
 
-        response.sequences.sort_unstable();
-        Ok(response
-            .sequences
-            .into_iter()
-            .map(|seq| seq.into())
-            .collect())
+                Ok(response.sequences.into_iter().map(Sequence::from).collect())
+            },
+        )
     }
 
     fn query_packet_acknowledgement(
@@ -2097,8 +2429,10 @@ impl ChainEndpoint for CosmosSdkChain {
         }
 
         let mut client = self
-            .block_on(create_grpc_client(
+            .block_on(create_pinned_grpc_client(
                 &self.grpc_addr,
+                self.grpc_session(),
+                self.request_limiter.as_ref(),
                 ibc_proto::ibc::core::channel::v1::query_client::QueryClient::new,
             ))
             .map(|client| {
@@ -2215,8 +2549,10 @@ impl ChainEndpoint for CosmosSdkChain {
         );
         crate::telemetry!(query, self.id(), "query_unreceived_acknowledgements");
 
-        let mut client = self.block_on(create_grpc_client(
+        let mut client = self.block_on(create_pinned_grpc_client(
             &self.grpc_addr,
+            self.grpc_session(),
+            self.request_limiter.as_ref(),
             ibc_proto::ibc::core::channel::v1::query_client::QueryClient::new,
         ))?;
 
@@ -2497,11 +2833,7 @@ impl ChainEndpoint for CosmosSdkChain {
         let address = self.get_signer()?;
         let key_pair = self.key()?;
 
-        let memo_prefix = if let Some(memo_overwrite) = &self.config.memo_overwrite {
-            memo_overwrite.clone()
-        } else {
-            self.config.memo_prefix.clone()
-        };
+        let memo_prefix = self.config.tx_memo(None).clone();
 
         self.rt.block_on(maybe_register_counterparty_payee(
             &self.rpc_client,
@@ -2539,8 +2871,12 @@ impl ChainEndpoint for CosmosSdkChain {
         &self,
         request: QueryIncentivizedPacketRequest,
     ) -> Result<QueryIncentivizedPacketResponse, Error> {
-        let incentivized_response =
-            self.block_on(query_incentivized_packet(&self.grpc_addr, request))?;
+        let incentivized_response = self.block_on(query_incentivized_packet(
+            &self.grpc_addr,
+            self.grpc_session(),
+            self.request_limiter.as_ref(),
+            request,
+        ))?;
         Ok(incentivized_response)
     }
 
@@ -2556,8 +2892,10 @@ impl ChainEndpoint for CosmosSdkChain {
         );
         crate::telemetry!(query, self.id(), "query_consumer_chains");
 
-        let mut client = self.block_on(create_grpc_client(
+        let mut client = self.block_on(create_pinned_grpc_client(
             &self.grpc_addr,
+            self.grpc_session(),
+            self.request_limiter.as_ref(),
             ibc_proto::interchain_security::ccv::provider::v1::query_client::QueryClient::new,
         ))?;
 
@@ -2657,7 +2995,12 @@ impl ChainEndpoint for CosmosSdkChain {
             .map_err(|e| Error::invalid_uri(self.config.grpc_addr.to_string(), e))?;
 
         let mut client = self
-            .block_on(create_grpc_client(&grpc_addr, QueryClient::new))?
+            .block_on(create_pinned_grpc_client(
+                &grpc_addr,
+                self.grpc_session(),
+                self.request_limiter.as_ref(),
+                QueryClient::new,
+            ))?
             .max_decoding_message_size(self.config.max_grpc_decoding_size.get_bytes() as usize);
 
         let request = tonic::Request::new(QueryConsumerIdFromClientIdRequest {
@@ -2671,6 +3014,17 @@ impl ChainEndpoint for CosmosSdkChain {
         let consumer_id = response.into_inner().consumer_id;
         Ok(ConsumerId::new(consumer_id))
     }
+
+    #[cfg(feature = "submit-delay")]
+    fn set_submit_delay(&mut self, delay: Option<Duration>) -> Result<(), Error> {
+        self.tx_config.submit_delay.set(delay);
+        Ok(())
+    }
+
+    #[cfg(feature = "submit-delay")]
+    fn query_delayed_submissions(&self) -> Result<u64, Error> {
+        Ok(self.tx_config.submit_delay.delayed_submissions())
+    }
 }
 
 fn sort_events_by_sequence(events: &mut [IbcEventWithHeight]) {
@@ -2684,7 +3038,7 @@ fn sort_events_by_sequence(events: &mut [IbcEventWithHeight]) {
 }
 
 async fn fetch_node_info(
-    rpc_client: &HttpClient,
+    rpc_client: &PacedRpcClient,
     config: &config::CosmosSdkConfig,
 ) -> Result<node::Info, Error> {
     crate::time!("fetch_node_info",
@@ -2710,19 +3064,23 @@ fn client_id_suffix(client_id: &ClientId) -> Option<u64> {
         .and_then(|e| e.parse::<u64>().ok())
 }
 
-/// Performs a health check on a Cosmos chain.
+/// Performs a health check on a Cosmos chain, recording the outcome of each check in the report.
 ///
 /// This health check checks on the following in this order:
-/// 1. Checks on the self-reported health endpoint.
-/// 2. Checks that the staking module maintains some historical entries such
+/// 1. Checks on the self-reported health endpoint, stopping there if the node is unreachable.
+/// 2. Checks that transaction indexing is enabled, unless the inclusion of transactions
+///    can be confirmed without it, following the `tx_confirmation_strategy` of the chain.
+/// 3. Checks that the chain identifier matches the network name.
+/// 4. Checks that the underlying SDK and ibc-go versions are compatible.
+/// 5. Checks that each of the gas prices of the `gas_price` parameter in Hermes is >=
+///    the `min_gas_price` advertised by the node Hermes is connected to.
+/// 6. Checks that the staking module maintains some historical entries such
 ///    that local header information is stored in the IBC state and thus
 ///    client proofs that are part of the connection handshake can be verified.
-/// 3. Checks that transaction indexing is enabled.
-/// 4. Checks that the chain identifier matches the network name.
-/// 5. Checks that the underlying SDK and ibc-go versions are compatible.
-/// 6. Checks that the `gas_price` parameter in Hermes is >= the `min_gas_price`
-///    advertised by the node Hermes is connected to.
-fn do_health_check(chain: &CosmosSdkChain) -> Result<(), Error> {
+/// 7. Checks that the gRPC endpoint is reachable, and that the account of the relayer exists.
+/// 8. Checks that the relayer address is part of the chain's relayer whitelist,
+///    if a `recv_signer_whitelist_query` is configured for the chain.
+fn do_health_check(chain: &CosmosSdkChain, report: &mut HealthReport) {
     let chain_id = chain.id();
     let grpc_address = chain.grpc_addr.to_string();
     let rpc_address = chain.config.rpc_addr.to_string();
@@ -2738,93 +3096,285 @@ fn do_health_check(chain: &CosmosSdkChain) -> Result<(), Error> {
         }
     }
 
-    chain.block_on(chain.rpc_client.health()).map_err(|e| {
-        Error::health_check_json_rpc(
-            chain_id.clone(),
-            rpc_address.clone(),
-            "/health".to_string(),
-            e,
-        )
-    })?;
+    let status = chain
+        .block_on(chain.rpc_client.health())
+        .map_err(|e| {
+            Error::health_check_json_rpc(
+                chain_id.clone(),
+                rpc_address.clone(),
+                "/health".to_string(),
+                e,
+            )
+        })
+        .and_then(|()| chain.chain_status());
+
+    // All the other checks query the node, so they are pointless if it is unreachable
+    let status = match status {
+        Ok(status) => {
+            report.pass(
+                HealthCheckKind::RpcReachable,
+                format!("RPC endpoint {rpc_address} is reachable"),
+            );
+            status
+        }
+        Err(e) => {
+            report.fail(HealthCheckKind::RpcReachable, e.detail().to_string());
+            return;
+        }
+    };
 
-    let status = chain.chain_status()?;
+    // Without transaction indexing, the inclusion of transactions is confirmed
+    // by searching the new blocks, unless the chain is configured otherwise
+    let tx_indexing_disabled = status.node_info.other.tx_index != TxIndexStatus::On;
 
-    if status.node_info.other.tx_index != TxIndexStatus::On {
-        return Err(Error::tx_indexing_disabled(chain_id.clone()));
+    if tx_indexing_disabled {
+        chain.tx_config.tx_confirmation.switch_to_block_search();
     }
 
-    if status.node_info.network.as_str() != chain_id.as_str() {
-        // Log the error, continue optimistically
-        error!(
-            "/status endpoint from chain '{}' reports network identifier to be '{}'. \
-            This is usually a sign of misconfiguration, please check your config.toml",
-            chain_id, status.node_info.network
+    if tx_indexing_disabled && !chain.tx_config.tx_confirmation.searches_blocks() {
+        report.fail(
+            HealthCheckKind::TxIndexing,
+            Error::tx_indexing_disabled(chain_id.clone())
+                .detail()
+                .to_string(),
+        );
+    } else {
+        report.pass(
+            HealthCheckKind::TxIndexing,
+            "the inclusion of transactions can be confirmed",
         );
     }
 
-    let relayer_gas_price = &chain.config.gas_price;
-    let node_min_gas_prices_result = chain.min_gas_price()?;
+    let network = status.node_info.network.as_str();
 
-    match node_min_gas_prices_result {
-        Some(node_min_gas_prices) if !node_min_gas_prices.is_empty() => {
-            let mut found_matching_denom = false;
+    if let Err(e) =
+        check_successor_chain_id(chain_id, chain.config.successor_chain_id.as_ref(), network)
+    {
+        report.fail(HealthCheckKind::ChainId, e.detail().to_string());
+    } else if network != chain_id.as_str() {
+        report.warn(
+            HealthCheckKind::ChainId,
+            format!(
+                "/status endpoint reports network identifier to be '{network}'. \
+                This is usually a sign of misconfiguration, please check your config.toml"
+            ),
+        );
+    } else {
+        report.pass(
+            HealthCheckKind::ChainId,
+            format!("node reports network identifier '{network}'"),
+        );
+    }
 
-            for price in node_min_gas_prices {
-                match relayer_gas_price.partial_cmp(&price) {
-                    Some(Ordering::Less) => return Err(Error::gas_price_too_low(chain_id.clone())),
-                    Some(_) => {
-                        found_matching_denom = true;
-                        break;
-                    }
-                    None => continue,
-                }
-            }
+    let version_specs = chain.block_on(fetch_version_specs(
+        &chain.config.id,
+        &chain.rpc_client,
+        &chain.config.rpc_addr,
+    ));
 
-            if !found_matching_denom {
-                warn!(
-                    "chain '{}' does not provide a minimum gas price for denomination '{}'.\
-                    This is usually a sign of misconfiguration, please check your chain configuration",
-                    chain_id, relayer_gas_price.denom
-                );
+    match version_specs {
+        Ok(version_specs) => match compatibility::run_diagnostic(&version_specs) {
+            Ok(()) => report.pass(
+                HealthCheckKind::VersionCompatible,
+                "SDK and ibc-go versions are supported",
+            ),
+            Err(diagnostic) => report.fail(
+                HealthCheckKind::VersionCompatible,
+                Error::compat_check_failed(
+                    chain_id.clone(),
+                    grpc_address.clone(),
+                    diagnostic.to_string(),
+                )
+                .detail()
+                .to_string(),
+            ),
+        },
+        Err(e) => report.fail(HealthCheckKind::VersionCompatible, e.detail().to_string()),
+    }
+
+    check_min_gas_prices(report, &chain.config.gas_price, chain.min_gas_price());
+
+    match chain.historical_entries() {
+        Ok(0) => report.fail(
+            HealthCheckKind::HistoricalEntries,
+            Error::no_historical_entries(chain_id.clone())
+                .detail()
+                .to_string(),
+        ),
+        Ok(entries) => report.pass(
+            HealthCheckKind::HistoricalEntries,
+            format!("staking module keeps {entries} historical entries"),
+        ),
+        Err(e) => report.fail(HealthCheckKind::HistoricalEntries, e.detail().to_string()),
+    }
+
+    check_account(chain, report);
+
+    if let Some(path) = &chain.config.recv_signer_whitelist_query {
+        check_recv_signer_whitelist(chain, path, report);
+    }
+}
+
+/// Checks that each gas price of the relayer is at least the minimum gas price of the node
+/// in the same denomination. A gas price which is too low, or which cannot be checked,
+/// is only reported as a warning since the node may not be the one the chain enforces.
+fn check_min_gas_prices(
+    report: &mut HealthReport,
+    relayer_gas_prices: &GasPrices,
+    node_min_gas_prices: Result<Option<Vec<GasPrice>>, Error>,
+) {
+    for relayer_gas_price in relayer_gas_prices.iter() {
+        check_min_gas_price(report, relayer_gas_price, &node_min_gas_prices);
+    }
+}
+
+fn check_min_gas_price(
+    report: &mut HealthReport,
+    relayer_gas_price: &GasPrice,
+    node_min_gas_prices: &Result<Option<Vec<GasPrice>>, Error>,
+) {
+    match node_min_gas_prices {
+        Ok(Some(node_min_gas_prices)) if !node_min_gas_prices.is_empty() => {
+            let node_min_gas_price = node_min_gas_prices
+                .iter()
+                .find(|price| relayer_gas_price.partial_cmp(price).is_some());
+
+            match node_min_gas_price {
+                Some(price) if relayer_gas_price < price => report.warn(
+                    HealthCheckKind::MinGasPrice,
+                    format!(
+                        "gas price {relayer_gas_price} is lower than the minimum gas price {price} \
+                        set by the node operator, transactions may be rejected"
+                    ),
+                ),
+                Some(price) => report.pass(
+                    HealthCheckKind::MinGasPrice,
+                    format!(
+                        "gas price {relayer_gas_price} is at least the minimum gas price {price}"
+                    ),
+                ),
+                None => report.warn(
+                    HealthCheckKind::MinGasPrice,
+                    format!(
+                        "node does not provide a minimum gas price for denomination '{}'. \
+                        This is usually a sign of misconfiguration, please check your chain configuration",
+                        relayer_gas_price.denom
+                    ),
+                ),
             }
         }
 
-        Some(_) => warn!(
-            "chain '{}' does not provide a minimum gas price for denomination '{}'. \
-            This is usually a sign of misconfiguration, please check your chain configuration",
-            chain_id, relayer_gas_price.denom
+        Ok(Some(_)) => report.warn(
+            HealthCheckKind::MinGasPrice,
+            format!(
+                "node does not provide a minimum gas price for denomination '{}'. \
+                This is usually a sign of misconfiguration, please check your chain configuration",
+                relayer_gas_price.denom
+            ),
         ),
 
-        None => warn!(
-            "chain '{}' does not implement the `cosmos.base.node.v1beta1.Service/Params` endpoint. \
+        Ok(None) => report.warn(
+            HealthCheckKind::MinGasPrice,
+            "node does not implement the `cosmos.base.node.v1beta1.Service/Params` endpoint. \
             It is impossible to check whether the chain's minimum-gas-prices matches the ones specified in config",
-            chain_id,
+        ),
+
+        Err(e) => report.warn(
+            HealthCheckKind::MinGasPrice,
+            format!(
+                "failed to query the minimum gas price of the node: {}",
+                e.detail()
+            ),
         ),
     }
+}
 
-    let version_specs = chain.block_on(fetch_version_specs(
-        &chain.config.id,
-        &chain.rpc_client,
-        &chain.config.rpc_addr,
-    ))?;
+/// Checks that the gRPC endpoint of the node is reachable, and that the account
+/// of the relayer exists on the chain, without which it cannot submit transactions.
+fn check_account(chain: &CosmosSdkChain, report: &mut HealthReport) {
+    let grpc_address = &chain.grpc_addr;
+
+    if let Err(e) = chain.block_on(create_pinned_grpc_client(
+        grpc_address,
+        chain.grpc_session(),
+        chain.request_limiter.as_ref(),
+        AuthQueryClient::new,
+    )) {
+        report.fail(HealthCheckKind::GrpcReachable, e.detail().to_string());
+        return;
+    }
+
+    report.pass(
+        HealthCheckKind::GrpcReachable,
+        format!("gRPC endpoint {grpc_address} is reachable"),
+    );
+
+    let account = chain
+        .key()
+        .and_then(|key_pair| chain.key_account(&key_pair));
+
+    let account = account.and_then(|account| {
+        chain
+            .block_on(query_account(
+                grpc_address,
+                chain.grpc_session(),
+                chain.request_limiter.as_ref(),
+                &account,
+                chain.config.account_query,
+            ))
+            .map(|_| account)
+    });
 
-    if let Err(diagnostic) = compatibility::run_diagnostic(&version_specs) {
-        return Err(Error::compat_check_failed(
-            chain_id.clone(),
-            grpc_address,
-            diagnostic.to_string(),
-        ));
+    match account {
+        Ok(account) => report.pass(
+            HealthCheckKind::AccountExists,
+            format!("account {account} of the relayer exists"),
+        ),
+        Err(e) => report.fail(HealthCheckKind::AccountExists, e.detail().to_string()),
     }
+}
 
-    if chain.historical_entries()? == 0 {
-        return Err(Error::no_historical_entries(chain_id.clone()));
+/// Queries the relayer whitelist of a permissioned chain and warns if the
+/// address of the configured key is not part of it.
+fn check_recv_signer_whitelist(chain: &CosmosSdkChain, path: &str, report: &mut HealthReport) {
+    let whitelist = chain.block_on(query_relayer_whitelist(
+        &chain.rpc_client,
+        &chain.config.rpc_addr,
+        path,
+    ));
+
+    let whitelist = match whitelist {
+        Ok(Some(whitelist)) => whitelist,
+        Ok(None) => {
+            report.warn(
+                HealthCheckKind::RelayerWhitelist,
+                format!(
+                    "unexpected response for the relayer whitelist query at '{path}'. \
+                    It is impossible to check whether the relayer address is whitelisted"
+                ),
+            );
+            return;
+        }
+        Err(e) => {
+            report.fail(HealthCheckKind::RelayerWhitelist, e.detail().to_string());
+            return;
+        }
+    };
+
+    match chain.get_signer() {
+        Ok(signer) => match recv_signer_whitelist_warning(signer.as_ref(), &whitelist) {
+            Some(warning) => report.warn(HealthCheckKind::RelayerWhitelist, warning),
+            None => report.pass(
+                HealthCheckKind::RelayerWhitelist,
+                format!("relayer address {signer} is whitelisted"),
+            ),
+        },
+        Err(e) => report.fail(HealthCheckKind::RelayerWhitelist, e.detail().to_string()),
     }
-
-    Ok(())
 }
 
 pub async fn fetch_compat_mode(
-    client: &HttpClient,
+    client: &PacedRpcClient,
     config: &CosmosSdkConfig,
 ) -> Result<CompatMode, Error> {
     use crate::util::compat_mode::compat_mode_from_node_version;
@@ -2857,10 +3407,28 @@ pub async fn fetch_compat_mode(
     Ok(compat_mode)
 }
 
+/// Fails if the node of the chain reports its configured successor chain id,
+/// ie. if the chain has hard-forked to that chain id.
+fn check_successor_chain_id(
+    chain_id: &ChainId,
+    successor: Option<&ChainId>,
+    network: &str,
+) -> Result<(), Error> {
+    match successor {
+        Some(successor) if successor.as_str() == network && successor != chain_id => {
+            Err(Error::chain_id_changed(chain_id.clone(), successor.clone()))
+        }
+        _ => Ok(()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::calculate_fee;
-    use crate::config::GasPrice;
+    use super::{calculate_fee, check_min_gas_prices, check_successor_chain_id};
+    use crate::chain::health::{HealthCheckKind, HealthReport, HealthStatus};
+    use crate::config::{GasPrice, GasPrices};
+    use crate::error::ErrorDetail;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 
     #[test]
     fn mul_ceil() {
@@ -2898,4 +3466,63 @@ mod tests {
         let fee = calculate_fee(gas_amount, &gas_price);
         assert_eq!(&fee.amount, "90000000000000000000000000");
     }
+
+    #[test]
+    fn chain_id_change_to_successor_is_detected() {
+        let chain_id = ChainId::from_string("gaia-1");
+        let successor = ChainId::from_string("gaia-2");
+
+        // Before the hard fork
+        assert!(check_successor_chain_id(&chain_id, Some(&successor), "gaia-1").is_ok());
+
+        // After the hard fork, the recreation of the clients of the chain is required
+        let error = check_successor_chain_id(&chain_id, Some(&successor), "gaia-2").unwrap_err();
+
+        match error.detail() {
+            ErrorDetail::ChainIdChanged(e) => {
+                assert_eq!(e.chain_id, chain_id);
+                assert_eq!(e.successor, successor);
+            }
+            _ => panic!("unexpected error: {error}"),
+        }
+        assert!(error.to_string().contains("recreate the clients"));
+
+        // Without a successor, another chain id is not reported as a change
+        assert!(check_successor_chain_id(&chain_id, None, "gaia-2").is_ok());
+    }
+
+    #[test]
+    fn gas_price_below_node_minimum_is_a_warning() {
+        let gas_price = |price: f64| GasPrice::new(price, "stake".to_string());
+        let node_min_gas_prices = || Ok(Some(vec![gas_price(0.01), gas_price(0.025)]));
+
+        let mut report = HealthReport::new(ChainId::from_string("ibc-0"));
+        check_min_gas_prices(&mut report, &gas_price(0.001).into(), node_min_gas_prices());
+
+        let entry = report.get(HealthCheckKind::MinGasPrice).unwrap();
+        assert_eq!(entry.status, HealthStatus::Warn);
+        assert!(entry.message.contains("lower than the minimum gas price"));
+
+        // A misconfigured gas price does not make the chain unhealthy
+        assert!(report.is_healthy());
+
+        let mut report = HealthReport::new(ChainId::from_string("ibc-0"));
+        check_min_gas_prices(&mut report, &gas_price(0.01).into(), node_min_gas_prices());
+
+        let entry = report.get(HealthCheckKind::MinGasPrice).unwrap();
+        assert_eq!(entry.status, HealthStatus::Pass);
+
+        // Every fallback gas price is checked as well
+        let gas_prices = GasPrices::new(vec![
+            gas_price(0.01),
+            GasPrice::new(0.1, "uosmo".to_string()),
+        ])
+        .unwrap();
+
+        let mut report = HealthReport::new(ChainId::from_string("ibc-0"));
+        check_min_gas_prices(&mut report, &gas_prices, node_min_gas_prices());
+
+        assert_eq!(report.status(), HealthStatus::Warn);
+        assert!(report.is_healthy());
+    }
 }
@@ -0,0 +1,113 @@
+//! Smoothing of the base fees queried for the dynamic gas price, so that a spike of
+//! the base fee in a single block does not make the relayer overpay for its transactions.
+//!
+//! The last base fees queried on a chain in each fee denomination are kept in a rolling
+//! window, and the dynamic gas price is derived from their exponentially-weighted moving average,
+//! in which the most recent base fees weigh the most. The history is owned by the
+//! gas configuration of the chain, and is therefore dropped when the relayer
+//! connects to the chain anew.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+/// The base fees last queried on a chain in each fee denomination,
+/// from the oldest to the most recent.
+#[derive(Debug, Default)]
+pub struct BaseFeeHistory {
+    base_fees: Mutex<HashMap<String, VecDeque<f64>>>,
+}
+
+impl BaseFeeHistory {
+    /// Records the base fee just queried on the chain in the given denomination, and returns
+    /// the average of the last `window` base fees queried in that denomination, including this one.
+    pub fn smoothed_base_fee(&self, denom: &str, base_fee: f64, window: usize) -> f64 {
+        let window = window.max(1);
+        let mut history = self.base_fees.lock().unwrap_or_else(|e| e.into_inner());
+        let base_fees = history.entry(denom.to_string()).or_default();
+
+        base_fees.push_back(base_fee);
+        while base_fees.len() > window {
+            base_fees.pop_front();
+        }
+
+        average(base_fees, window)
+    }
+}
+
+/// The exponentially-weighted moving average of the base fees, with the
+/// usual smoothing factor of `2 / (window + 1)` for a window of that size.
+fn average(base_fees: &VecDeque<f64>, window: usize) -> f64 {
+    let alpha = 2.0 / (window as f64 + 1.0);
+
+    let mut base_fees = base_fees.iter();
+    let first = base_fees.next().copied().unwrap_or_default();
+
+    base_fees.fold(first, |average, base_fee| {
+        alpha * base_fee + (1.0 - alpha) * average
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spike_is_smoothed() {
+        let history = BaseFeeHistory::default();
+
+        for _ in 0..4 {
+            assert!((history.smoothed_base_fee("uatom", 0.01, 5) - 0.01).abs() < 1e-12);
+        }
+
+        // A single-block spike barely moves the average
+        let spiked = history.smoothed_base_fee("uatom", 1.0, 5);
+        assert!(spiked > 0.01 && spiked < 0.5, "{spiked}");
+
+        // And is forgotten once it leaves the window
+        for _ in 0..5 {
+            history.smoothed_base_fee("uatom", 0.01, 5);
+        }
+        assert!((history.smoothed_base_fee("uatom", 0.01, 5) - 0.01).abs() < 1e-12);
+    }
+
+    #[test]
+    fn average_stays_within_the_queried_base_fees() {
+        let history = BaseFeeHistory::default();
+        let base_fees = [0.02, 0.5, 0.03, 0.04, 0.9, 0.02, 0.03];
+
+        let smoothed: Vec<f64> = base_fees
+            .iter()
+            .map(|base_fee| history.smoothed_base_fee("uatom", *base_fee, 3))
+            .collect();
+
+        let spread = |values: &[f64]| {
+            let max = values.iter().copied().fold(f64::MIN, f64::max);
+            let min = values.iter().copied().fold(f64::MAX, f64::min);
+            max - min
+        };
+
+        assert!(spread(&smoothed) < spread(&base_fees));
+    }
+
+    #[test]
+    fn window_of_one_is_not_smoothed() {
+        let history = BaseFeeHistory::default();
+
+        assert_eq!(history.smoothed_base_fee("uatom", 0.1, 1), 0.1);
+        assert_eq!(history.smoothed_base_fee("uatom", 0.7, 1), 0.7);
+        assert_eq!(history.smoothed_base_fee("uatom", 0.3, 0), 0.3);
+    }
+
+    #[test]
+    fn base_fees_are_averaged_per_denomination() {
+        let history = BaseFeeHistory::default();
+
+        for _ in 0..3 {
+            history.smoothed_base_fee("uatom", 0.01, 3);
+        }
+
+        // The base fees in another denomination do not skew the average
+        assert_eq!(history.smoothed_base_fee("uosmo", 0.5, 3), 0.5);
+        assert!((history.smoothed_base_fee("uatom", 0.01, 3) - 0.01).abs() < 1e-12);
+    }
+}
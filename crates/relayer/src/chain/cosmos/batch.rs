@@ -1,22 +1,26 @@
+use alloc::collections::VecDeque;
 use core::mem;
 
 use ibc_proto::google::protobuf::Any;
+use ibc_relayer_types::core::ics02_client::msgs::update_client::TYPE_URL as UPDATE_CLIENT_TYPE_URL;
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use ibc_relayer_types::events::IbcEvent;
 use ibc_relayer_types::Height;
 use prost::Message;
 use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
-use tendermint_rpc::HttpClient;
-use tracing::debug;
+use tracing::{debug, error, warn};
 
 use crate::chain::cosmos::encode::encoded_tx_metrics;
 use crate::chain::cosmos::gas::gas_amount_to_fee;
-use crate::chain::cosmos::retry::send_tx_with_account_sequence_retry;
+use crate::chain::cosmos::request_rate::PacedRpcClient;
+use crate::chain::cosmos::retry::{is_insufficient_fee, send_tx_with_account_sequence_retry};
+use crate::chain::cosmos::tx::relay_tx_memo;
 use crate::chain::cosmos::types::account::Account;
 use crate::chain::cosmos::types::config::TxConfig;
 use crate::chain::cosmos::types::tx::{TxStatus, TxSyncResult};
-use crate::chain::cosmos::wait::wait_for_block_commits;
+use crate::chain::cosmos::wait::{wait_for_block_commits, BlockSearch};
 use crate::config::types::Memo;
+use crate::config::BatchFailureMode;
 use crate::error::Error;
 use crate::event::IbcEventWithHeight;
 use crate::keyring::Secp256k1KeyPair;
@@ -29,7 +33,7 @@ use crate::keyring::Secp256k1KeyPair;
    priority mempool is enabled.
 */
 pub async fn send_batched_messages_and_wait_commit(
-    rpc_client: &HttpClient,
+    rpc_client: &PacedRpcClient,
     config: &TxConfig,
     key_pair: &Secp256k1KeyPair,
     account: &mut Account,
@@ -40,6 +44,9 @@ pub async fn send_batched_messages_and_wait_commit(
         return Ok(Vec::new());
     }
 
+    let block_search =
+        BlockSearch::start(rpc_client, &config.rpc_address, &config.tx_confirmation).await;
+
     let mut tx_sync_results =
         send_messages_as_batches(rpc_client, config, key_pair, account, tx_memo, messages).await?;
 
@@ -48,6 +55,9 @@ pub async fn send_batched_messages_and_wait_commit(
         rpc_client,
         &config.rpc_address,
         &config.rpc_timeout,
+        config.max_concurrent_tx_confirmations,
+        &config.tx_confirmation,
+        block_search,
         &mut tx_sync_results,
     )
     .await?;
@@ -66,7 +76,7 @@ pub async fn send_batched_messages_and_wait_commit(
    are committed in the wrong order due to interference from priority mempool.
 */
 pub async fn sequential_send_batched_messages_and_wait_commit(
-    rpc_client: &HttpClient,
+    rpc_client: &PacedRpcClient,
     config: &TxConfig,
     key_pair: &Secp256k1KeyPair,
     account: &mut Account,
@@ -91,7 +101,7 @@ pub async fn sequential_send_batched_messages_and_wait_commit(
 }
 
 pub async fn send_batched_messages_and_wait_check_tx(
-    rpc_client: &HttpClient,
+    rpc_client: &PacedRpcClient,
     config: &TxConfig,
     key_pair: &Secp256k1KeyPair,
     account: &mut Account,
@@ -102,24 +112,24 @@ pub async fn send_batched_messages_and_wait_check_tx(
         return Ok(Vec::new());
     }
 
+    sample_block_gas_usage(rpc_client, config).await;
+
     let batches = batch_messages(config, key_pair, account, tx_memo, messages).await?;
 
     let mut responses = Vec::new();
 
     for batch in batches {
-        let response = send_tx_with_account_sequence_retry(
-            rpc_client, config, key_pair, account, tx_memo, &batch,
-        )
-        .await?;
+        let batch_responses =
+            send_batch(rpc_client, config, key_pair, account, tx_memo, batch).await?;
 
-        responses.push(response);
+        responses.extend(batch_responses.into_iter().map(|(_, response)| response));
     }
 
     Ok(responses)
 }
 
 async fn send_messages_as_batches(
-    rpc_client: &HttpClient,
+    rpc_client: &PacedRpcClient,
     config: &TxConfig,
     key_pair: &Secp256k1KeyPair,
     account: &mut Account,
@@ -132,6 +142,8 @@ async fn send_messages_as_batches(
 
     let message_count = messages.len();
 
+    sample_block_gas_usage(rpc_client, config).await;
+
     let batches = batch_messages(config, key_pair, account, tx_memo, messages).await?;
 
     debug!(
@@ -144,23 +156,22 @@ async fn send_messages_as_batches(
     let mut tx_sync_results = Vec::new();
 
     for batch in batches {
-        let message_count = batch.len();
-
-        let response = send_tx_with_account_sequence_retry(
-            rpc_client, config, key_pair, account, tx_memo, &batch,
-        )
-        .await?;
+        let batch_responses =
+            send_batch(rpc_client, config, key_pair, account, tx_memo, batch).await?;
 
-        let tx_sync_result = response_to_tx_sync_result(&config.chain_id, message_count, response);
+        for (message_count, response) in batch_responses {
+            let tx_sync_result =
+                response_to_tx_sync_result(&config.chain_id, message_count, response);
 
-        tx_sync_results.push(tx_sync_result);
+            tx_sync_results.push(tx_sync_result);
+        }
     }
 
     Ok(tx_sync_results)
 }
 
 async fn sequential_send_messages_as_batches(
-    rpc_client: &HttpClient,
+    rpc_client: &PacedRpcClient,
     config: &TxConfig,
     key_pair: &Secp256k1KeyPair,
     account: &mut Account,
@@ -173,6 +184,8 @@ async fn sequential_send_messages_as_batches(
 
     let message_count = messages.len();
 
+    sample_block_gas_usage(rpc_client, config).await;
+
     let batches = batch_messages(config, key_pair, account, tx_memo, messages).await?;
 
     debug!(
@@ -185,22 +198,27 @@ async fn sequential_send_messages_as_batches(
     let mut tx_sync_results = Vec::new();
 
     for batch in batches {
-        let message_count = batch.len();
+        let block_search =
+            BlockSearch::start(rpc_client, &config.rpc_address, &config.tx_confirmation).await;
 
-        let response = send_tx_with_account_sequence_retry(
-            rpc_client, config, key_pair, account, tx_memo, &batch,
-        )
-        .await?;
+        let batch_responses =
+            send_batch(rpc_client, config, key_pair, account, tx_memo, batch).await?;
 
-        let tx_sync_result = response_to_tx_sync_result(&config.chain_id, message_count, response);
+        for (message_count, response) in batch_responses {
+            let tx_sync_result =
+                response_to_tx_sync_result(&config.chain_id, message_count, response);
 
-        tx_sync_results.push(tx_sync_result);
+            tx_sync_results.push(tx_sync_result);
+        }
 
         wait_for_block_commits(
             &config.chain_id,
             rpc_client,
             &config.rpc_address,
             &config.rpc_timeout,
+            config.max_concurrent_tx_confirmations,
+            &config.tx_confirmation,
+            block_search,
             &mut tx_sync_results,
         )
         .await?;
@@ -209,6 +227,130 @@ async fn sequential_send_messages_as_batches(
     Ok(tx_sync_results)
 }
 
+/// Sends the given batch of messages as a single transaction, and returns the response
+/// of the transaction along with its number of messages.
+///
+/// In best-effort mode, a batch whose transaction is rejected is bisected, and its halves
+/// are sent separately, until the messages which are rejected on their own are isolated.
+/// The responses of all the transactions sent are then returned, the ones rejecting the
+/// isolated messages included, except for the messages which could not be sent at all.
+async fn send_batch(
+    rpc_client: &PacedRpcClient,
+    config: &TxConfig,
+    key_pair: &Secp256k1KeyPair,
+    account: &mut Account,
+    tx_memo: &Memo,
+    batch: Vec<Any>,
+) -> Result<Vec<(usize, Response)>, Error> {
+    if config.batch_failure_mode == BatchFailureMode::Abort {
+        let message_count = batch.len();
+
+        let response = send_tx_with_account_sequence_retry(
+            rpc_client, config, key_pair, account, tx_memo, &batch,
+        )
+        .await?;
+
+        return Ok(vec![(message_count, response)]);
+    }
+
+    let mut bisection = BatchBisection::new(batch);
+    let mut responses = Vec::new();
+    let mut last_error = None;
+
+    while let Some(batch) = bisection.next_batch() {
+        let message_count = batch.len();
+
+        match send_tx_with_account_sequence_retry(
+            rpc_client, config, key_pair, account, tx_memo, &batch,
+        )
+        .await
+        {
+            Ok(response) if !is_rejected_batch(&response) => {
+                responses.push((message_count, response));
+            }
+            Ok(response) => match bisection.reject(batch) {
+                Some(message) => {
+                    warn!(
+                        chain = %config.chain_id,
+                        type_url = %message.type_url,
+                        log = %response.log,
+                        "message was rejected on its own, submitting the rest of its batch without it"
+                    );
+
+                    responses.push((1, response));
+                }
+                None => {
+                    debug!(
+                        chain = %config.chain_id,
+                        message_count,
+                        "batch was rejected, bisecting it to isolate the rejected messages"
+                    );
+                }
+            },
+            Err(e) => {
+                if let Some(message) = bisection.reject(batch) {
+                    error!(
+                        chain = %config.chain_id,
+                        type_url = %message.type_url,
+                        error = %e,
+                        "failed to send message on its own, submitting the rest of its batch without it"
+                    );
+                }
+
+                last_error = Some(e);
+            }
+        }
+    }
+
+    // None of the messages could be sent, most likely not because of the messages themselves
+    match last_error {
+        Some(e) if responses.is_empty() => Err(e),
+        _ => Ok(responses),
+    }
+}
+
+/// Whether the given response of `broadcast_tx_sync` rejects the tx because of its
+/// messages, as opposed to its fee or to the packets being relayed already.
+fn is_rejected_batch(response: &Response) -> bool {
+    response.code.is_err()
+        && !response.log.contains("packet messages are redundant")
+        && !is_insufficient_fee(response)
+}
+
+/// The parts of a batch of messages left to send in best-effort mode, where each part
+/// which is rejected is split in two halves, sent next in the original order, until the
+/// messages which are rejected on their own are isolated.
+struct BatchBisection<T> {
+    pending: VecDeque<Vec<T>>,
+}
+
+impl<T> BatchBisection<T> {
+    fn new(batch: Vec<T>) -> Self {
+        Self {
+            pending: VecDeque::from([batch]),
+        }
+    }
+
+    fn next_batch(&mut self) -> Option<Vec<T>> {
+        self.pending.pop_front()
+    }
+
+    /// Splits the given rejected batch in two halves to send next,
+    /// or returns its message if it is the only one in the batch.
+    fn reject(&mut self, mut batch: Vec<T>) -> Option<T> {
+        if batch.len() <= 1 {
+            return batch.pop();
+        }
+
+        let second_half = batch.split_off(batch.len() / 2);
+
+        self.pending.push_front(second_half);
+        self.pending.push_front(batch);
+
+        None
+    }
+}
+
 pub fn response_to_tx_sync_result(
     chain_id: &ChainId,
     message_count: usize,
@@ -238,6 +380,23 @@ pub fn response_to_tx_sync_result(
     }
 }
 
+/// Samples the gas used by the latest block of the chain, if the batches are sized
+/// after the gas used by the recent blocks.
+async fn sample_block_gas_usage(rpc_client: &PacedRpcClient, config: &TxConfig) {
+    if let Some(block_gas_usage) = &config.block_gas_usage {
+        if let Err(e) = block_gas_usage
+            .sample(rpc_client, &config.rpc_address)
+            .await
+        {
+            warn!(
+                chain = %config.chain_id,
+                "failed to sample the gas used by the latest block, \
+                sizing the batches after the previous samples: {e}"
+            );
+        }
+    }
+}
+
 async fn batch_messages(
     config: &TxConfig,
     key_pair: &Secp256k1KeyPair,
@@ -245,7 +404,12 @@ async fn batch_messages(
     tx_memo: &Memo,
     messages: Vec<Any>,
 ) -> Result<Vec<Vec<Any>>, Error> {
-    let max_message_count = config.max_msg_num.to_usize();
+    let max_message_count = match &config.block_gas_usage {
+        Some(block_gas_usage) => {
+            block_gas_usage.max_msg_num(config.max_msg_num.to_usize(), config.gas_config.max_gas)
+        }
+        None => config.max_msg_num.to_usize(),
+    };
     let max_tx_size = config.max_tx_size.into();
 
     let mut batches = vec![];
@@ -258,10 +422,14 @@ async fn batch_messages(
         config.gas_config.max_gas,
         &config.chain_id,
         &config.rpc_address,
+        config.request_limiter.as_ref(),
     )
     .await;
 
-    let tx_metrics = encoded_tx_metrics(config, key_pair, account, tx_memo, &[], &max_fee)?;
+    // The batches relaying packets carry the attribution memo of the relayer, if any
+    let tx_memo = relay_tx_memo(config, tx_memo, &messages);
+
+    let tx_metrics = encoded_tx_metrics(config, key_pair, account, &tx_memo, &[], &max_fee)?;
     let tx_envelope_len = tx_metrics.envelope_len;
     let empty_body_len = tx_metrics.body_bytes_len;
 
@@ -276,7 +444,7 @@ async fn batch_messages(
 
     let mut current_count = 0;
     let mut current_len = empty_body_len;
-    let mut current_batch = vec![];
+    let mut current_batch: Vec<Any> = vec![];
 
     for message in messages {
         let message_len = message.encoded_len();
@@ -285,7 +453,14 @@ async fn batch_messages(
         // field tag (small varint) and the length delimiter.
         let tagged_len = 1 + prost::length_delimiter_len(message_len) + message_len;
 
-        if current_count >= max_message_count
+        // A client update is only useful together with the messages whose proofs
+        // it allows to verify, so never leave it alone in a batch because of the
+        // message count limit. The size limit still applies.
+        let only_client_updates = current_batch
+            .iter()
+            .all(|msg| msg.type_url == UPDATE_CLIENT_TYPE_URL);
+
+        if (current_count >= max_message_count && !only_client_updates)
             || tx_len(tx_envelope_len, current_len + tagged_len) > max_tx_size
         {
             let insert_batch = mem::take(&mut current_batch);
@@ -312,40 +487,80 @@ async fn batch_messages(
     Ok(batches)
 }
 
+/// The configs shared by the tests of the relayer, loaded from its test fixtures.
+#[cfg(test)]
+pub(crate) mod test_fixtures {
+    use crate::chain::cosmos::config::CosmosSdkConfig;
+    use crate::chain::cosmos::types::config::TxConfig;
+    use crate::config::{self, ChainConfig, Config};
+
+    /// Loads the relayer config of the given file in `tests/config/fixtures`.
+    pub fn fixture_config(file_name: &str) -> Config {
+        let path = format!(
+            "{}/tests/config/fixtures/{file_name}",
+            env!("CARGO_MANIFEST_DIR")
+        );
+        config::load(path).expect("could not parse config")
+    }
+
+    /// The example relayer config, with the Cosmos SDK chains `chain_A` and `chain_B`.
+    pub fn example_config() -> Config {
+        fixture_config("relayer_conf_example.toml")
+    }
+
+    pub fn cosmos_config(config: &ChainConfig) -> &CosmosSdkConfig {
+        match config {
+            ChainConfig::CosmosSdk(config) => config,
+            _ => panic!("should be a cosmos sdk chain config"),
+        }
+    }
+
+    pub fn cosmos_config_mut(config: &mut ChainConfig) -> &mut CosmosSdkConfig {
+        match config {
+            ChainConfig::CosmosSdk(config) => config,
+            _ => panic!("should be a cosmos sdk chain config"),
+        }
+    }
+
+    pub fn tx_config(config: &ChainConfig) -> TxConfig {
+        TxConfig::try_from(cosmos_config(config)).expect("could not obtain tx config")
+    }
+
+    /// The tx config of `chain_A` of the example config.
+    pub fn example_tx_config() -> TxConfig {
+        tx_config(&example_config().chains[0])
+    }
+}
+
 // Clippy on 1.70 yields false positives
 #[allow(clippy::redundant_clone)]
 #[cfg(test)]
 mod tests {
-    use super::batch_messages;
+    use super::test_fixtures::{cosmos_config, example_config, tx_config};
+    use super::{batch_messages, BatchBisection, UPDATE_CLIENT_TYPE_URL};
+    use crate::chain::cosmos::block_gas::BlockGasUsageSampler;
     use crate::chain::cosmos::encode::sign_and_encode_tx;
     use crate::chain::cosmos::gas::gas_amount_to_fee;
     use crate::chain::cosmos::types::account::{
         Account, AccountAddress, AccountNumber, AccountSequence,
     };
     use crate::chain::cosmos::types::config::TxConfig;
-    use crate::config;
     use crate::config::types::{MaxMsgNum, MaxTxSize, Memo};
     use crate::keyring::{self, KeyRing, Secp256k1KeyPair, SigningKeyPair};
     use ibc_proto::google::protobuf::Any;
     use ibc_relayer_types::core::ics24_host::identifier::ChainId;
     use std::fs;
+    use std::sync::Arc;
+    use std::time::Duration;
 
     const COSMOS_HD_PATH: &str = "m/44'/118'/0'/0/0";
 
     fn test_fixture() -> (TxConfig, Secp256k1KeyPair, Account) {
-        let path = concat!(
-            env!("CARGO_MANIFEST_DIR"),
-            "/tests/config/fixtures/relayer_conf_example.toml"
-        );
-        let config = config::load(path).expect("could not parse config");
+        let config = example_config();
         let chain_id = ChainId::from_string("chain_A");
+        let chain_config = config.find_chain(&chain_id).unwrap();
 
-        #[allow(irrefutable_let_patterns)]
-        let config::ChainConfig::CosmosSdk(chain_config) = config.find_chain(&chain_id).unwrap() else {
-            panic!("should be a cosmos sdk chain config");
-        };
-
-        let tx_config = TxConfig::try_from(chain_config).expect("could not obtain tx config");
+        let tx_config = tx_config(chain_config);
 
         let path = concat!(
             env!("CARGO_MANIFEST_DIR"),
@@ -356,7 +571,7 @@ mod tests {
             keyring::Store::Memory,
             "cosmos",
             &chain_id,
-            &chain_config.key_store_folder,
+            &cosmos_config(chain_config).key_store_folder,
         )
         .unwrap();
         let hd_path = COSMOS_HD_PATH.parse().unwrap();
@@ -379,6 +594,7 @@ mod tests {
             config.gas_config.max_gas,
             &config.chain_id,
             &config.rpc_address,
+            config.request_limiter.as_ref(),
         )
         .await;
         let mut messages = vec![Any {
@@ -467,6 +683,7 @@ mod tests {
             config.gas_config.max_gas,
             &config.chain_id,
             &config.rpc_address,
+            config.request_limiter.as_ref(),
         )
         .await;
         let tx_bytes =
@@ -546,6 +763,63 @@ mod tests {
         assert_eq!(batches[0].len(), 5);
     }
 
+    #[tokio::test]
+    async fn batches_are_smaller_when_recent_blocks_use_more_gas() {
+        let (config, key_pair, account) = test_fixture();
+
+        let messages = vec![
+            Any {
+                type_url: "/example.Foo".into(),
+                value: vec![0; 6],
+            };
+            12
+        ];
+
+        let mut limited_config = config;
+        limited_config.max_msg_num = MaxMsgNum::new(10).unwrap();
+        limited_config.max_tx_size = MaxTxSize::default();
+
+        let max_gas = limited_config.gas_config.max_gas;
+        let max_block_gas = 4 * max_gas;
+        let block_gas_usage = Arc::new(BlockGasUsageSampler::new(2, 0.5, Duration::ZERO));
+        limited_config.block_gas_usage = Some(block_gas_usage.clone());
+
+        let batch_sizes = |batches: Vec<Vec<Any>>| batches.iter().map(Vec::len).collect::<Vec<_>>();
+
+        // The recent blocks leave enough gas available for a batch of `max_msg_num` messages
+        block_gas_usage.record(1, 0, Some(max_block_gas));
+
+        let batches = batch_messages(
+            &limited_config,
+            &key_pair,
+            &account,
+            &Memo::new("").unwrap(),
+            messages.clone(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(batch_sizes(batches), vec![10, 2]);
+
+        // The recent blocks use most of their gas, leaving only half of `max_gas`
+        // to a batch after the headroom
+        block_gas_usage.record(2, 3 * max_gas, Some(max_block_gas));
+        block_gas_usage.record(3, 3 * max_gas, Some(max_block_gas));
+        assert_eq!(block_gas_usage.gas_target(), Some(max_gas / 2));
+
+        let batches = batch_messages(
+            &limited_config,
+            &key_pair,
+            &account,
+            &Memo::new("").unwrap(),
+            messages,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(batch_sizes(batches), vec![5, 5, 2]);
+    }
+
     #[tokio::test]
     async fn test_batches_are_structured_appropriately_per_max_tx_size() {
         const MAX_TX_SIZE: usize = 198;
@@ -598,6 +872,7 @@ mod tests {
             config.gas_config.max_gas,
             &config.chain_id,
             &config.rpc_address,
+            config.request_limiter.as_ref(),
         )
         .await;
 
@@ -626,6 +901,59 @@ mod tests {
         assert_eq!(batches[0].len(), 5);
     }
 
+    #[tokio::test]
+    async fn client_update_is_batched_with_packet_msgs() {
+        let (config, key_pair, account) = test_fixture();
+
+        let update = Any {
+            type_url: UPDATE_CLIENT_TYPE_URL.into(),
+            value: vec![0; 8],
+        };
+        let recv = |n| Any {
+            type_url: "/ibc.core.channel.v1.MsgRecvPacket".into(),
+            value: vec![0; n],
+        };
+        let messages = vec![update.clone(), recv(1), recv(2), recv(3)];
+        let memo = Memo::new("").unwrap();
+
+        // For a small batch, the client update and the packet messages share a single tx
+        let mut limited_config = config;
+        limited_config.max_msg_num = MaxMsgNum::default();
+        limited_config.max_tx_size = MaxTxSize::default();
+
+        let batches = batch_messages(
+            &limited_config,
+            &key_pair,
+            &account,
+            &memo,
+            messages.clone(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0], messages);
+
+        // When the batch needs to be split, the client update
+        // is kept together with at least the first packet message
+        limited_config.max_msg_num = MaxMsgNum::new(1).unwrap();
+
+        let batches = batch_messages(
+            &limited_config,
+            &key_pair,
+            &account,
+            &memo,
+            messages.clone(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(batches.len(), 3);
+        assert_eq!(batches[0], vec![update, recv(1)]);
+        assert_eq!(batches[1], vec![recv(2)]);
+        assert_eq!(batches[2], vec![recv(3)]);
+    }
+
     #[tokio::test]
     #[should_panic(expected = "`max_msg_num` must be greater than or equal to 1, found 0")]
     async fn test_max_msg_num_of_zero_panics() {
@@ -640,4 +968,32 @@ mod tests {
         )
         .await;
     }
+
+    #[test]
+    fn best_effort_bisection_isolates_poison_message() {
+        let batch: Vec<u32> = (1..=8).collect();
+        let poison = 6;
+
+        let mut bisection = BatchBisection::new(batch);
+        let mut sent = vec![];
+        let mut isolated = vec![];
+        let mut attempts = 0;
+
+        while let Some(batch) = bisection.next_batch() {
+            attempts += 1;
+
+            if batch.contains(&poison) {
+                isolated.extend(bisection.reject(batch));
+            } else {
+                sent.extend(batch);
+            }
+        }
+
+        // All the other messages are relayed, in their original order
+        assert_eq!(sent, vec![1, 2, 3, 4, 5, 7, 8]);
+        assert_eq!(isolated, vec![poison]);
+
+        // The poison message is isolated in a logarithmic number of attempts
+        assert_eq!(attempts, 7);
+    }
 }
@@ -0,0 +1,313 @@
+//! Sampling of the gas used by the recent blocks of a chain, from which the gas that a
+//! batch of messages may need is derived, so that the batches fit comfortably in the gas
+//! left available in the next blocks.
+
+use alloc::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tendermint_rpc::{Client, Url};
+
+use crate::chain::cosmos::request_rate::PacedRpcClient;
+use crate::config::BlockGasUsageConfig;
+use crate::error::Error;
+
+/// How long the maximum gas of a block is used for before it is queried again.
+const MAX_BLOCK_GAS_REFRESH_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+#[derive(Debug, Default)]
+struct Samples {
+    /// The maximum gas of a block, `None` if unlimited.
+    max_block_gas: Option<u64>,
+
+    /// When the maximum gas of a block was last queried.
+    max_block_gas_queried_at: Option<Instant>,
+
+    /// When a block was last sampled.
+    sampled_at: Option<Instant>,
+
+    /// The height and gas used by each block sampled, the most recent last.
+    gas_used: VecDeque<(u64, u64)>,
+}
+
+/// Keeps track of the gas used by the last `sample_blocks` blocks sampled, to derive
+/// the gas target of a batch of messages from the gas left available in a block.
+#[derive(Debug)]
+pub struct BlockGasUsageSampler {
+    sample_blocks: usize,
+    headroom: f64,
+    sample_interval: Duration,
+    samples: Mutex<Samples>,
+}
+
+impl BlockGasUsageSampler {
+    pub fn new(sample_blocks: usize, headroom: f64, sample_interval: Duration) -> Self {
+        Self {
+            sample_blocks,
+            headroom,
+            sample_interval,
+            samples: Mutex::new(Samples::default()),
+        }
+    }
+
+    /// Creates a sampler which samples a block at most once per `max_block_time`.
+    pub fn from_config(config: &BlockGasUsageConfig, max_block_time: Duration) -> Self {
+        Self::new(config.sample_blocks, config.headroom, max_block_time)
+    }
+
+    /// Records the gas used by the block at the given height, unless it was sampled already.
+    pub fn record(&self, height: u64, gas_used: u64, max_block_gas: Option<u64>) {
+        let mut samples = self.samples.lock().expect("poisoned lock");
+
+        samples.max_block_gas = max_block_gas;
+
+        if samples
+            .gas_used
+            .back()
+            .is_some_and(|(last_height, _)| *last_height >= height)
+        {
+            return;
+        }
+
+        samples.gas_used.push_back((height, gas_used));
+
+        while samples.gas_used.len() > self.sample_blocks {
+            samples.gas_used.pop_front();
+        }
+    }
+
+    /// Samples the gas used by the latest block of the chain, unless a block was sampled
+    /// less than `sample_interval` ago. The maximum gas of a block is only queried again
+    /// once it is older than [`MAX_BLOCK_GAS_REFRESH_INTERVAL`].
+    pub async fn sample(
+        &self,
+        rpc_client: &PacedRpcClient<impl Client + Sync>,
+        rpc_address: &Url,
+    ) -> Result<(), Error> {
+        let (max_block_gas, max_block_gas_due) = {
+            let samples = self.samples.lock().expect("poisoned lock");
+
+            let due =
+                |at: Option<Instant>, interval| at.map_or(true, |at| at.elapsed() >= interval);
+
+            if !due(samples.sampled_at, self.sample_interval) {
+                return Ok(());
+            }
+
+            (
+                samples.max_block_gas,
+                due(
+                    samples.max_block_gas_queried_at,
+                    MAX_BLOCK_GAS_REFRESH_INTERVAL,
+                ),
+            )
+        };
+
+        let block_results = rpc_client
+            .latest_block_results()
+            .await
+            .map_err(|e| Error::rpc(rpc_address.clone(), e))?;
+
+        let max_block_gas = if max_block_gas_due {
+            let consensus_params = rpc_client
+                .consensus_params(block_results.height)
+                .await
+                .map_err(|e| Error::rpc(rpc_address.clone(), e))?;
+
+            self.samples
+                .lock()
+                .expect("poisoned lock")
+                .max_block_gas_queried_at = Some(Instant::now());
+
+            // A negative maximum gas means that the gas of a block is unlimited
+            u64::try_from(consensus_params.consensus_params.block.max_gas).ok()
+        } else {
+            max_block_gas
+        };
+
+        let gas_used = block_results
+            .txs_results
+            .unwrap_or_default()
+            .iter()
+            .map(|result| u64::try_from(result.gas_used).unwrap_or(0))
+            .sum();
+
+        self.samples.lock().expect("poisoned lock").sampled_at = Some(Instant::now());
+        self.record(block_results.height.value(), gas_used, max_block_gas);
+
+        Ok(())
+    }
+
+    /// Returns the gas that a batch may need, ie. `headroom` times the gas left available
+    /// in a block after the average gas used by the blocks sampled, or `None` if no block
+    /// was sampled yet or the gas of a block is unlimited.
+    pub fn gas_target(&self) -> Option<u64> {
+        let samples = self.samples.lock().expect("poisoned lock");
+
+        let max_block_gas = samples.max_block_gas?;
+
+        if samples.gas_used.is_empty() {
+            return None;
+        }
+
+        let total_gas_used: u64 = samples.gas_used.iter().map(|(_, gas_used)| gas_used).sum();
+        let average_gas_used = total_gas_used / samples.gas_used.len() as u64;
+        let available_gas = max_block_gas.saturating_sub(average_gas_used);
+
+        Some((available_gas as f64 * self.headroom) as u64)
+    }
+
+    /// Returns how many messages at most a batch may contain, given that a batch of
+    /// `max_msg_num` messages needs up to `max_gas`, so that it does not need more
+    /// than the gas target. A batch always contains at least one message.
+    pub fn max_msg_num(&self, max_msg_num: usize, max_gas: u64) -> usize {
+        match self.gas_target() {
+            Some(gas_target) if gas_target < max_gas => {
+                let scaled = max_msg_num as u128 * u128::from(gas_target) / u128::from(max_gas);
+                usize::try_from(scaled).unwrap_or(max_msg_num).max(1)
+            }
+            _ => max_msg_num,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use tendermint_rpc::{
+        Error as RpcError, Method, MockClient, MockRequestMatcher, Request, Response,
+    };
+
+    /// A node whose blocks use 4M gas out of 10M, which counts the
+    /// `block_results` and `consensus_params` requests it receives.
+    #[derive(Default)]
+    struct GasUsageMatcher {
+        block_results: AtomicU32,
+        consensus_params: AtomicU32,
+    }
+
+    impl MockRequestMatcher for &GasUsageMatcher {
+        fn response_for<R, S>(&self, request: R) -> Option<Result<R::Response, RpcError>>
+        where
+            R: Request<S>,
+            S: tendermint_rpc::dialect::Dialect,
+        {
+            let result = match request.method() {
+                Method::BlockResults => {
+                    let height = self.block_results.fetch_add(1, Ordering::SeqCst) + 1;
+
+                    format!(
+                        r#"{{
+                            "app_hash": null,
+                            "consensus_param_updates": null,
+                            "finalize_block_events": null,
+                            "height": "{height}",
+                            "txs_results": [{{ "code": 0, "gas_used": "4000000" }}],
+                            "validator_updates": null
+                        }}"#
+                    )
+                }
+                Method::ConsensusParams => {
+                    self.consensus_params.fetch_add(1, Ordering::SeqCst);
+
+                    r#"{
+                        "block_height": "1",
+                        "consensus_params": {
+                            "abci": { "vote_extensions_enable_height": "0" },
+                            "block": { "max_bytes": "22020096", "max_gas": "10000000" },
+                            "evidence": {
+                                "max_age_duration": "172800000000000",
+                                "max_age_num_blocks": "100000",
+                                "max_bytes": "1048576"
+                            },
+                            "validator": { "pub_key_types": ["ed25519"] },
+                            "version": { "app": "0" }
+                        }
+                    }"#
+                    .to_string()
+                }
+                _ => return None,
+            };
+
+            let response = format!(r#"{{ "jsonrpc": "2.0", "id": "", "result": {result} }}"#);
+
+            Some(R::Response::from_string(response))
+        }
+    }
+
+    #[tokio::test]
+    async fn sampling_is_limited_to_one_block_per_interval() {
+        let rpc_address: Url = "http://127.0.0.1:26657".parse().unwrap();
+        let matcher = GasUsageMatcher::default();
+        let (client, _driver) = MockClient::new(&matcher);
+        let client = PacedRpcClient::new(client, None);
+
+        let sampler = BlockGasUsageSampler::new(10, 0.5, Duration::from_millis(200));
+        let requests = || {
+            (
+                matcher.block_results.load(Ordering::SeqCst),
+                matcher.consensus_params.load(Ordering::SeqCst),
+            )
+        };
+
+        sampler.sample(&client, &rpc_address).await.unwrap();
+        assert_eq!(requests(), (1, 1));
+        assert_eq!(sampler.gas_target(), Some(3_000_000));
+
+        // The batches sent before the next block is expected do not query the node
+        for _ in 0..5 {
+            sampler.sample(&client, &rpc_address).await.unwrap();
+        }
+        assert_eq!(requests(), (1, 1));
+
+        // The next block is sampled, with the maximum gas of a block already known
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        sampler.sample(&client, &rpc_address).await.unwrap();
+        assert_eq!(requests(), (2, 1));
+        assert_eq!(sampler.gas_target(), Some(3_000_000));
+    }
+
+    #[test]
+    fn gas_target_is_headroom_of_available_gas() {
+        let sampler = BlockGasUsageSampler::new(2, 0.5, Duration::ZERO);
+        assert_eq!(sampler.gas_target(), None);
+
+        sampler.record(1, 2_000_000, Some(10_000_000));
+        sampler.record(2, 4_000_000, Some(10_000_000));
+        assert_eq!(sampler.gas_target(), Some(3_500_000));
+
+        // A block already sampled is not sampled again
+        sampler.record(2, 4_000_000, Some(10_000_000));
+        assert_eq!(sampler.gas_target(), Some(3_500_000));
+
+        // Only the last `sample_blocks` blocks are averaged
+        sampler.record(3, 8_000_000, Some(10_000_000));
+        assert_eq!(sampler.gas_target(), Some(2_000_000));
+
+        // Without a block gas limit, there is no target
+        sampler.record(4, 8_000_000, None);
+        assert_eq!(sampler.gas_target(), None);
+    }
+
+    #[test]
+    fn max_msg_num_scales_with_gas_target() {
+        let sampler = BlockGasUsageSampler::new(10, 0.5, Duration::ZERO);
+        assert_eq!(sampler.max_msg_num(30, 3_000_000), 30);
+
+        // The target is above the maximum gas of a transaction
+        sampler.record(1, 0, Some(10_000_000));
+        assert_eq!(sampler.max_msg_num(30, 3_000_000), 30);
+
+        // The target is a third of the maximum gas of a transaction
+        sampler.record(2, 16_000_000, Some(10_000_000));
+        assert_eq!(sampler.gas_target(), Some(1_000_000));
+        assert_eq!(sampler.max_msg_num(30, 3_000_000), 10);
+
+        // The blocks are full
+        sampler.record(3, 20_000_000, Some(10_000_000));
+        assert_eq!(sampler.max_msg_num(30, 3_000_000), 1);
+    }
+}
@@ -0,0 +1,300 @@
+//! Detection of the drift of the chain id of a chain from its configured `id`, eg. after
+//! an unexpected upgrade of the chain. The signatures of the transactions then no longer
+//! verify, as they commit to the configured chain id, and every transaction is rejected.
+//!
+//! Once a transaction is rejected for a failed signature verification, the chain id
+//! reported by the node is queried, and if it differs from the configured one, the
+//! submission of transactions to the chain is halted until the relayer connects to
+//! the chain anew, instead of retrying transactions which cannot succeed.
+
+use std::sync::Mutex;
+
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use tendermint::abci::Code;
+use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
+use tendermint_rpc::Url;
+use tracing::error;
+
+use crate::chain::cosmos::request_rate::PacedRpcClient;
+use crate::error::Error;
+
+// The error "unauthorized" is defined as the error code 4 of the `sdk` codespace in cosmos-sdk,
+// which the ante handler returns when the signature verification of a tx fails:
+// https://github.com/cosmos/cosmos-sdk/blob/v0.47.0/x/auth/ante/sigverify.go#L303-L308
+const UNAUTHORIZED_ERR: u32 = 4;
+const SDK_CODESPACE: &str = "sdk";
+
+/// Whether the given response of `broadcast_tx_sync` indicates that the signature of the
+/// tx failed to verify, possibly because it was signed for another chain id than the
+/// one of the chain.
+pub fn is_signature_verification_failure(response: &Response) -> bool {
+    response.codespace == SDK_CODESPACE
+        && response.code == Code::from(UNAUTHORIZED_ERR)
+        && response.log.contains("chain-id")
+}
+
+/// Whether the submission of transactions to a chain is halted on a drift of its chain id,
+/// for as long as the relayer is connected to the chain.
+#[derive(Debug, Default)]
+pub struct ChainIdDrift {
+    /// The chain id reported by the node, once the submission of transactions is halted
+    halted: Mutex<Option<String>>,
+}
+
+impl ChainIdDrift {
+    /// Queries the chain id reported by the node after a tx failed its signature
+    /// verification, and halts the submission of transactions to the chain if it differs
+    /// from the configured chain id, in which case the error reporting the mismatch is
+    /// returned.
+    pub async fn check(
+        &self,
+        rpc_client: &PacedRpcClient,
+        rpc_address: &Url,
+        chain_id: &ChainId,
+    ) -> Result<(), Error> {
+        let status = rpc_client
+            .status()
+            .await
+            .map_err(|e| Error::rpc(rpc_address.clone(), e))?;
+
+        let network = status.node_info.network.as_str();
+
+        if network == chain_id.as_str() {
+            return Ok(());
+        }
+
+        error!(
+            chain = %chain_id,
+            network,
+            "node reports another chain id than the configured one, \
+            halting the submission of transactions to the chain"
+        );
+
+        *self.halted.lock().unwrap_or_else(|e| e.into_inner()) = Some(network.to_string());
+
+        Err(Error::chain_id_mismatch(
+            chain_id.clone(),
+            network.to_string(),
+        ))
+    }
+
+    /// Fails if the submission of transactions to the given chain was halted.
+    pub fn ensure_not_halted(&self, chain_id: &ChainId) -> Result<(), Error> {
+        let halted = self.halted.lock().unwrap_or_else(|e| e.into_inner());
+
+        match halted.as_ref() {
+            Some(network) => Err(Error::chain_id_mismatch(chain_id.clone(), network.clone())),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::mpsc;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use ibc_proto::google::protobuf::Any;
+    use ibc_relayer_types::core::ics02_client::msgs::update_client;
+    use tendermint_rpc::HttpClient;
+
+    use crate::chain::cosmos::batch::test_fixtures::example_tx_config;
+    use crate::chain::cosmos::gas::{BatchShape, GasEstimateSampler};
+    use crate::chain::cosmos::retry::send_tx_with_account_sequence_retry;
+    use crate::chain::cosmos::types::account::{
+        Account, AccountAddress, AccountNumber, AccountSequence,
+    };
+    use crate::chain::cosmos::types::config::TxConfig;
+    use crate::config::gas_sampling::GasEstimationSampling;
+    use crate::config::types::Memo;
+    use crate::config::AddressType;
+    use crate::error::ErrorDetail;
+    use crate::keyring::{Secp256k1KeyPair, SigningKeyPair};
+    use crate::util::mock_http::{spawn_mock_http_server, MockResponse};
+
+    const HASH: &str = "E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855";
+
+    fn response(codespace: &str, code: u32, log: &str) -> Response {
+        Response {
+            codespace: codespace.to_string(),
+            code: Code::from(code),
+            data: Default::default(),
+            log: log.to_string(),
+            hash: Default::default(),
+        }
+    }
+
+    #[test]
+    fn detects_signature_verification_failures() {
+        let mismatch = response(
+            "sdk",
+            4,
+            "signature verification failed; please verify account number (12), \
+            sequence (3) and chain-id (gaia-1): unauthorized",
+        );
+        assert!(is_signature_verification_failure(&mismatch));
+
+        assert!(!is_signature_verification_failure(&response(
+            "sdk",
+            32,
+            "account sequence mismatch, expected 4, got 3: incorrect account sequence",
+        )));
+        assert!(!is_signature_verification_failure(&response(
+            "channel", 4, "chain-id"
+        )));
+    }
+
+    /// A node of the chain `network`, rejecting every tx for a failed signature verification,
+    /// which forwards the method of each request it receives.
+    fn drifted_node(network: &'static str) -> (Url, mpsc::Receiver<String>) {
+        let (address, requests) = spawn_mock_http_server(move |request| {
+            let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+
+            let result = match body["method"].as_str().unwrap() {
+                "broadcast_tx_sync" => r#"{
+                    "code": 4,
+                    "codespace": "sdk",
+                    "data": "",
+                    "hash": "57018296EE0919C9D351F2FFEA82A8D28DE223724D79965FC8D00A7477ED48BC",
+                    "log": "signature verification failed; verify chain-id (chain_A): unauthorized"
+                }"#
+                .to_string(),
+                "status" => format!(
+                    r#"{{
+                        "node_info": {{
+                            "channels": "40202122233038606100",
+                            "id": "cf4a66aa29e5123abfdfbdf485da6788bb8e46d1",
+                            "listen_addr": "tcp://0.0.0.0:26656",
+                            "moniker": "node",
+                            "network": "{network}",
+                            "other": {{ "rpc_address": "tcp://0.0.0.0:26657", "tx_index": "on" }},
+                            "protocol_version": {{ "app": "1", "block": "11", "p2p": "8" }},
+                            "version": "0.38.0"
+                        }},
+                        "sync_info": {{
+                            "catching_up": false,
+                            "earliest_app_hash": "0000000000000000",
+                            "earliest_block_hash": "{HASH}",
+                            "earliest_block_height": "1",
+                            "earliest_block_time": "2023-05-17T14:12:48.347696215Z",
+                            "latest_app_hash": "0600000000000000",
+                            "latest_block_hash": "{HASH}",
+                            "latest_block_height": "100",
+                            "latest_block_time": "2023-05-17T14:14:48.530153458Z"
+                        }},
+                        "validator_info": {{
+                            "address": "2DD9F44FD9067555C322243C3C913BA7B51D2BE0",
+                            "pub_key": {{
+                                "type": "tendermint/PubKeyEd25519",
+                                "value": "bNNlGls5R25wC3Sd8720F/3+7IZBhXcD22MNFtPk/v0="
+                            }},
+                            "voting_power": "10"
+                        }}
+                    }}"#
+                ),
+                method => panic!("unexpected request: {method}"),
+            };
+
+            MockResponse::json(format!(
+                r#"{{ "jsonrpc": "2.0", "id": {}, "result": {result} }}"#,
+                body["id"]
+            ))
+        });
+
+        let methods = mpsc::channel();
+        std::thread::spawn(move || {
+            for request in requests {
+                let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+                let _ = methods.0.send(body["method"].as_str().unwrap().to_string());
+            }
+        });
+
+        (format!("http://{address}").parse().unwrap(), methods.1)
+    }
+
+    fn messages() -> Vec<Any> {
+        vec![Any {
+            type_url: update_client::TYPE_URL.to_string(),
+            value: vec![],
+        }]
+    }
+
+    /// The tx config of `chain_A` of the example config, connected to the given node, which
+    /// estimates the gas of the test messages without simulating them.
+    fn tx_config(rpc_address: &Url) -> TxConfig {
+        let mut tx_config = example_tx_config();
+        tx_config.rpc_address = rpc_address.clone();
+
+        let sampler = GasEstimateSampler::new(GasEstimationSampling::enabled(100).unwrap());
+        sampler.record_simulation(BatchShape::of(&messages()), 100_000);
+        tx_config.gas_config.gas_sampler = Arc::new(sampler);
+
+        tx_config
+    }
+
+    /// Sends the test messages, asserting that it fails with the drift of the chain id.
+    async fn send_tx_to_drifted_chain(rpc_client: &PacedRpcClient, config: &TxConfig) {
+        let key_pair = Secp256k1KeyPair::from_mnemonic(
+            "abandon abandon abandon abandon abandon abandon \
+            abandon abandon abandon abandon abandon about",
+            &"m/44'/118'/0'/0/0".parse().unwrap(),
+            &AddressType::Cosmos,
+            "cosmos",
+        )
+        .unwrap();
+        let mut account = Account {
+            address: AccountAddress::new(key_pair.account()),
+            number: AccountNumber::new(1),
+            sequence: AccountSequence::new(0),
+        };
+
+        let error = send_tx_with_account_sequence_retry(
+            rpc_client,
+            config,
+            &key_pair,
+            &mut account,
+            &Memo::new("hermes").unwrap(),
+            &messages(),
+        )
+        .await
+        .unwrap_err();
+
+        match error.detail() {
+            ErrorDetail::ChainIdMismatch(e) => {
+                assert_eq!(e.chain_id, ChainId::from_string("chain_A"));
+                assert_eq!(e.network, "chain_A-2");
+            }
+            _ => panic!("unexpected error: {error}"),
+        }
+        assert!(error
+            .to_string()
+            .contains("set the `id` of the chain to 'chain_A-2'"));
+    }
+
+    #[tokio::test]
+    async fn txs_are_halted_on_a_drifted_chain_id_until_connecting_anew() {
+        let (rpc_address, requests) = drifted_node("chain_A-2");
+        let rpc_client = PacedRpcClient::new(HttpClient::new(rpc_address.clone()).unwrap(), None);
+        let config = tx_config(&rpc_address);
+
+        // The rejected tx makes the relayer check the chain id reported by the node
+        send_tx_to_drifted_chain(&rpc_client, &config).await;
+        assert_eq!(requests.recv().unwrap(), "broadcast_tx_sync");
+        assert_eq!(requests.recv().unwrap(), "status");
+
+        // Every later tx fails right away with the mismatch, without reaching the node
+        send_tx_to_drifted_chain(&rpc_client, &config).await;
+        send_tx_to_drifted_chain(&rpc_client, &config.clone()).await;
+        assert!(requests.recv_timeout(Duration::from_millis(200)).is_err());
+
+        // Connecting anew to the chain, eg. after fixing its configuration, submits the txs
+        // again, until the next rejection
+        let reconnected = tx_config(&rpc_address);
+        send_tx_to_drifted_chain(&rpc_client, &reconnected).await;
+        assert_eq!(requests.recv().unwrap(), "broadcast_tx_sync");
+        assert_eq!(requests.recv().unwrap(), "status");
+    }
+}
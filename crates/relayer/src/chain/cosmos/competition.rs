@@ -0,0 +1,148 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+use ibc_proto::cosmos::tx::v1beta1::{TxBody, TxRaw};
+use ibc_proto::ibc::core::channel::v1::MsgRecvPacket;
+use ibc_relayer_types::core::ics04_channel::msgs::recv_packet;
+use prost::Message;
+use serde::Serialize;
+
+use crate::error::Error;
+
+/// The share of the packets received on a channel which were relayed by a single relayer.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct RelayerShare {
+    /// The address of the relayer, ie. the signer of its `MsgRecvPacket` messages.
+    pub relayer: String,
+    /// The number of packets received on the channel by the relayer.
+    pub packets: u64,
+    /// The fraction of all the packets received on the channel relayed by the relayer.
+    pub share: f64,
+}
+
+/// Decodes the given raw transaction and returns the signer of each of its
+/// `MsgRecvPacket` messages receiving a packet on the given channel.
+pub fn recv_packet_signers(tx_bytes: &[u8], channel_id: &str) -> Result<Vec<String>, Error> {
+    let tx_raw =
+        TxRaw::decode(tx_bytes).map_err(|e| Error::protobuf_decode("TxRaw".to_string(), e))?;
+
+    let body = TxBody::decode(tx_raw.body_bytes.as_slice())
+        .map_err(|e| Error::protobuf_decode("TxBody".to_string(), e))?;
+
+    let signers = body
+        .messages
+        .iter()
+        .filter(|message| message.type_url == recv_packet::TYPE_URL)
+        .filter_map(|message| MsgRecvPacket::decode(message.value.as_slice()).ok())
+        .filter(|msg| {
+            msg.packet
+                .as_ref()
+                .is_some_and(|packet| packet.destination_channel == channel_id)
+        })
+        .map(|msg| msg.signer)
+        .collect();
+
+    Ok(signers)
+}
+
+/// Counts the packets received by each of the given signers of `MsgRecvPacket` messages,
+/// and returns the share of each relayer, from the most to the least active one.
+pub fn relayer_shares(signers: impl IntoIterator<Item = String>) -> Vec<RelayerShare> {
+    let mut counts: BTreeMap<String, u64> = BTreeMap::new();
+
+    for signer in signers {
+        *counts.entry(signer).or_default() += 1;
+    }
+
+    let total: u64 = counts.values().sum();
+
+    let mut shares: Vec<RelayerShare> = counts
+        .into_iter()
+        .map(|(relayer, packets)| RelayerShare {
+            relayer,
+            packets,
+            share: packets as f64 / total as f64,
+        })
+        .collect();
+
+    shares.sort_by_key(|share| Reverse(share.packets));
+
+    shares
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_proto::google::protobuf::Any;
+    use ibc_proto::ibc::core::channel::v1::Packet;
+
+    fn recv_packet_msg(destination_channel: &str, signer: &str) -> Any {
+        let msg = MsgRecvPacket {
+            packet: Some(Packet {
+                sequence: 1,
+                source_port: "transfer".to_string(),
+                source_channel: "channel-99".to_string(),
+                destination_port: "transfer".to_string(),
+                destination_channel: destination_channel.to_string(),
+                ..Default::default()
+            }),
+            signer: signer.to_string(),
+            ..Default::default()
+        };
+
+        Any {
+            type_url: recv_packet::TYPE_URL.to_string(),
+            value: msg.encode_to_vec(),
+        }
+    }
+
+    fn tx_bytes(messages: Vec<Any>) -> Vec<u8> {
+        let body = TxBody {
+            messages,
+            ..Default::default()
+        };
+
+        TxRaw {
+            body_bytes: body.encode_to_vec(),
+            ..Default::default()
+        }
+        .encode_to_vec()
+    }
+
+    #[test]
+    fn channel_relayed_by_two_relayers() {
+        let txs = [
+            tx_bytes(vec![
+                recv_packet_msg("channel-0", "cosmos1alice"),
+                recv_packet_msg("channel-0", "cosmos1alice"),
+                recv_packet_msg("channel-1", "cosmos1carol"),
+            ]),
+            tx_bytes(vec![recv_packet_msg("channel-0", "cosmos1bob")]),
+            tx_bytes(vec![recv_packet_msg("channel-0", "cosmos1alice")]),
+        ];
+
+        let signers = txs
+            .iter()
+            .map(|tx| recv_packet_signers(tx, "channel-0"))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        let shares = relayer_shares(signers.into_iter().flatten());
+
+        assert_eq!(shares.len(), 2);
+
+        assert_eq!(shares[0].relayer, "cosmos1alice");
+        assert_eq!(shares[0].packets, 3);
+        assert!((shares[0].share - 0.75).abs() < f64::EPSILON);
+
+        assert_eq!(shares[1].relayer, "cosmos1bob");
+        assert_eq!(shares[1].packets, 1);
+        assert!((shares[1].share - 0.25).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn no_competition_without_packets() {
+        assert!(relayer_shares(vec![]).is_empty());
+    }
+}
@@ -1,4 +1,6 @@
+use core::num::{NonZeroU32, NonZeroUsize};
 use core::time::Duration;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 use byte_unit::Byte;
@@ -6,15 +8,24 @@ use serde_derive::{Deserialize, Serialize};
 use tendermint_rpc::Url;
 
 use ibc_relayer_types::core::ics23_commitment::specs::ProofSpecs;
-use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
 
 use crate::chain::cosmos::config::error::Error as ConfigError;
 use crate::config::compat_mode::CompatMode;
 use crate::config::dynamic_gas::DynamicGasPrice;
 use crate::config::gas_multiplier::GasMultiplier;
+use crate::config::gas_sampling::GasEstimationSampling;
+use crate::config::retry_strategy::RetryStrategy;
+use crate::config::shadow_gas::ShadowGasStrategy;
+use crate::config::tx_priority::TxPriority;
 use crate::config::types::{MaxMsgNum, MaxTxSize, Memo, TrustThreshold};
 use crate::config::{
-    self, AddressType, EventSourceMode, ExtensionOption, GasPrice, GenesisRestart, PacketFilter,
+    self, AccountQuery, AddressType, BatchFailureMode, BlockGasUsageConfig, CanaryConfig,
+    ChannelOverrides, EventSourceMode, ExtensionOption, GasPrices, GenesisRestart,
+    GrpcHeightHeader, MultisigConfig, PacketCommitmentSource, PacketDataEncoding, PacketFilter,
+    ProofHeightStrategy, PrunedHeightHandling, ReferenceGasPriceConfig, SignerEncoding,
+    StickySessionConfig, TimeoutBatchConfig, TimestampUnit, TrustingPeriodSource,
+    TxConfirmationStrategy, TxFeesGasPriceConfig,
 };
 use crate::config::{default, RefreshRate};
 use crate::keyring::Store;
@@ -63,6 +74,11 @@ pub struct CosmosSdkConfig {
     pub gas_adjustment: Option<f64>,
     pub gas_multiplier: Option<GasMultiplier>,
 
+    /// Number of decimals of the gas price which this chain accepts, eg. 0 for
+    /// chains which require an integer gas price. The gas price is rounded up to it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_price_decimals: Option<u32>,
+
     pub fee_granter: Option<String>,
 
     #[serde(default)]
@@ -78,6 +94,10 @@ pub struct CosmosSdkConfig {
     #[serde(default = "default::query_packets_chunk_size")]
     pub query_packets_chunk_size: usize,
 
+    /// How many submitted transactions to query at once when waiting for them to be committed
+    #[serde(default = "default::max_concurrent_tx_confirmations")]
+    pub max_concurrent_tx_confirmations: usize,
+
     /// A correction parameter that helps deal with clocks that are only approximately synchronized
     /// between the source and destination chains for a client.
     /// This parameter is used when deciding to accept or reject a new header
@@ -100,6 +120,36 @@ pub struct CosmosSdkConfig {
     #[serde(default = "default::client_refresh_rate")]
     pub client_refresh_rate: RefreshRate,
 
+    /// The maximum number of client updates submitted to this chain at once
+    /// by the refreshes of the clients it hosts. Unlimited if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_client_updates: Option<NonZeroUsize>,
+
+    /// The maximum number of headers of this chain built at once when updating
+    /// a client referencing it to several heights. Sequential if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_parallel_header_builds: Option<NonZeroUsize>,
+
+    /// The maximum number of client states queried at once when listing the clients
+    /// hosted by this chain, on chains whose client states are queried one at a time.
+    #[serde(default = "default::max_concurrent_client_state_queries")]
+    pub max_concurrent_client_state_queries: NonZeroUsize,
+
+    /// Whether to detect the misbehaviour of the chains referenced by the clients hosted
+    /// on this chain, overriding the global `mode.clients.misbehaviour` setting if set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detect_misbehaviour: Option<bool>,
+
+    /// Whether the refreshes of the clients referencing this chain are scheduled
+    /// against the configured `trusting_period` or the one of their client state.
+    #[serde(default)]
+    pub trusting_period_source: TrustingPeriodSource,
+
+    /// The unit in which this chain interprets the timeout timestamps
+    /// of the packets it receives.
+    #[serde(default)]
+    pub timeout_timestamp_unit: TimestampUnit,
+
     /// CCV consumer chain
     #[serde(default = "default::ccv_consumer_chain")]
     pub ccv_consumer_chain: bool,
@@ -110,6 +160,18 @@ pub struct CosmosSdkConfig {
     #[serde(default)]
     pub memo_overwrite: Option<Memo>,
 
+    /// Memo prefixes of the transactions relaying on the channels to the given
+    /// counterparty chains, used instead of the `memo_prefix`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub memo_overrides: BTreeMap<ChainId, Memo>,
+
+    /// Structured memo attributing the relayed packets to the relayer, for the chains
+    /// crediting the relayers of the packets they receive from the memo of the relay
+    /// transactions. Used in place of the memo of the transactions relaying packets
+    /// to this chain, and subject to the same length limit as `memo_prefix`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relayer_attribution_memo: Option<Memo>,
+
     // This is an undocumented and hidden config to make the relayer wait for
     // DeliverTX before sending the next transaction when sending messages in
     // multiple batches. We will instruct relayer operators to turn this on
@@ -118,6 +180,50 @@ pub struct CosmosSdkConfig {
     #[serde(default)]
     pub sequential_batch_tx: bool,
 
+    /// ABCI query path exposing the relayer whitelist of a permissioned chain.
+    /// When set, the health check warns if the configured key is not whitelisted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recv_signer_whitelist_query: Option<String>,
+
+    /// Channels of this chain on which ICS-20 transfers with an empty memo are not
+    /// relayed, for applications which reject such transfers.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skip_empty_memo_channels: Vec<ChannelId>,
+
+    /// Maximum size in bytes of the memo of the ICS-20 transfers which this chain
+    /// accepts, enforced by the transfer commands before submitting a transfer to it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_memo_bytes: Option<usize>,
+
+    /// How to relay packets whose proofs must be built at a height
+    /// which the node of this chain has already pruned.
+    #[serde(default)]
+    pub pruned_height_handling: PrunedHeightHandling,
+
+    /// How the account of the relayer is extracted from the response of
+    /// the `Account` query. Detected from the type of the account if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_query: Option<AccountQuery>,
+
+    /// Number of blocks which must be built on top of the block of a packet
+    /// event of this chain before the packet is relayed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_confirmation_blocks: Option<u64>,
+
+    /// How to handle a batch of messages whose transaction is rejected
+    /// because of one of its messages.
+    #[serde(default)]
+    pub batch_failure_mode: BatchFailureMode,
+
+    /// How to select the height at which the proofs of the packet
+    /// messages built from the events of this chain are built.
+    #[serde(default)]
+    pub proof_height_strategy: ProofHeightStrategy,
+
+    /// Where the commitments of the packets sent by this chain are obtained from.
+    #[serde(default)]
+    pub packet_commitment_source: PacketCommitmentSource,
+
     // Note: These last few need to be last otherwise we run into `ValueAfterTable` error when serializing to TOML.
     //       That's because these are all tables and have to come last when serializing.
     #[serde(
@@ -133,7 +239,14 @@ pub struct CosmosSdkConfig {
     #[serde(default)]
     pub trust_threshold: TrustThreshold,
 
-    pub gas_price: GasPrice,
+    /// Trust threshold used to verify the headers of this chain locally,
+    /// if stricter than the trust threshold of the client.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local_trust_threshold: Option<TrustThreshold>,
+
+    /// The gas price, or the list of gas prices in order of preference of their
+    /// denominations, at which the relayer pays the fees of its transactions.
+    pub gas_price: GasPrices,
 
     #[serde(default)]
     pub packet_filter: PacketFilter,
@@ -141,23 +254,179 @@ pub struct CosmosSdkConfig {
     #[serde(default)]
     pub dynamic_gas_price: DynamicGasPrice,
 
+    #[serde(default)]
+    pub gas_estimation_sampling: GasEstimationSampling,
+
+    #[serde(default)]
+    pub tx_priority: TxPriority,
+
+    /// An alternative gas strategy evaluated alongside the active one for every
+    /// transaction, whose difference with the active one is recorded to telemetry.
+    #[serde(default)]
+    pub shadow_gas_strategy: Option<ShadowGasStrategy>,
+
+    /// How to retry the submission of a transaction which failed with a transient error.
+    #[serde(default)]
+    pub retry_strategy: RetryStrategy,
+
+    /// Scale the gas price so that its value in a reference denomination stays the same.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference_gas_price: Option<ReferenceGasPriceConfig>,
+
+    /// Derive the gas price in the denomination of `gas_price` from its spot price in
+    /// the base denomination of the Osmosis `txfees` module.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub txfees_gas_price: Option<TxFeesGasPriceConfig>,
+
+    /// Size the batches of messages after the gas used by the recent blocks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_gas_usage: Option<BlockGasUsageConfig>,
+
+    /// The command run when the balance of the fee denomination of the relayer account
+    /// does not cover the maximum fee of a transaction, to acquire the fee denomination,
+    /// eg. by swapping other tokens, with the fee denomination and the address of the
+    /// account appended to its arguments.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub acquire_fee_denom_command: Vec<String>,
+
+    /// Other denominations than the one of the gas price in which the relayer account
+    /// pays fees, whose balances are reported to telemetry along with the one of the
+    /// denomination of the gas price.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_fee_denoms: Vec<String>,
+
+    /// The minimum time between two transactions broadcast from the same account,
+    /// for chains which reject the transactions of an account that arrive too close
+    /// together.
+    #[serde(
+        default,
+        with = "humantime_serde",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub min_tx_interval: Option<Duration>,
+
+    /// Delay after which the receipts of the packets are checked again on this chain
+    /// right before submitting their `RecvPacket` messages, for chains which index the
+    /// receipt of a packet received by another relayer late. Disabled if unset.
+    #[serde(
+        default,
+        with = "humantime_serde",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub receipt_recheck_delay: Option<Duration>,
+
+    /// The maximum amount of fees, in the preferred denomination of `gas_price`, spent on this
+    /// chain per day, in UTC. The fees paid in the other denominations of `gas_price` count
+    /// against it at the rate of their gas prices. Once spent, only client updates are
+    /// submitted until the next day.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily_fee_budget: Option<u64>,
+
+    /// Pin the queries to the same backend node when the endpoints of the chain are
+    /// behind a load balancer with sticky sessions, so that consecutive queries are not
+    /// served by nodes at slightly different heights.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sticky_session: Option<StickySessionConfig>,
+
+    /// The maximum number of RPC requests and gRPC queries made to the chain per second,
+    /// for nodes whose provider enforces such a limit. Unlimited if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_requests_per_second: Option<NonZeroU32>,
+
     #[serde(default)]
     pub address_type: AddressType,
+
+    /// The encoding of the address of the relayer in the `signer` field
+    /// of the messages built for this chain.
+    #[serde(default)]
+    pub signer_encoding: SignerEncoding,
+
     #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
     pub extension_options: Vec<ExtensionOption>,
     pub compat_mode: Option<CompatMode>,
+
+    /// The chain id which the chain takes after a hard fork, which Hermes
+    /// reports the required reconfiguration of the chain for once its node does.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub successor_chain_id: Option<ChainId>,
+
+    /// How the inclusion of the transactions sent to this chain is confirmed.
+    #[serde(default)]
+    pub tx_confirmation_strategy: TxConfirmationStrategy,
+
+    /// The gRPC header through which the height of historical queries is passed to the node.
+    #[serde(default)]
+    pub grpc_height_header: GrpcHeightHeader,
+
+    /// Limits of the batches in which the timeouts of packets are submitted to this chain,
+    /// separately from the other packet messages. If unset, timeouts are not batched separately.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_batch: Option<TimeoutBatchConfig>,
     pub clear_interval: Option<u64>,
     #[serde(default)]
     pub excluded_sequences: ExcludedSequences,
 
+    /// Names of the keys used to sign `MsgRecvPacket` messages of ICS-20 transfers,
+    /// indexed by the denom of the transfer as it appears in the packet data.
+    /// Transfers of any other denom are signed by the key given by `key_name`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub denom_key_names: BTreeMap<String, String>,
+
+    /// Names of the keys which sign in turn the transactions relayed to this chain,
+    /// instead of the key given by `key_name`, each with its own account sequence.
+    /// The transfers of the denoms in `denom_key_names` are still signed by their key.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub signing_accounts: Vec<String>,
+
+    /// The multisig account which the relayer signs transactions as, of which
+    /// the key given by `key_name` is one of the members.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multisig: Option<MultisigConfig>,
+
+    /// Overrides of `min_confirmation_blocks` and `local_trust_threshold`
+    /// for the packets sent on the given channels of this chain.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub channel_overrides: BTreeMap<ChannelId, ChannelOverrides>,
+
+    /// Encodings of the data of the packets sent from the given ports of this chain,
+    /// for the ports whose packet data is not JSON-encoded.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub packet_data_encodings: BTreeMap<PortId, PacketDataEncoding>,
+
+    /// Canary packets periodically sent on the given channels of this chain,
+    /// to check that their paths relay packets end-to-end.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub canaries: BTreeMap<ChannelId, CanaryConfig>,
+
     #[serde(default = "default::allow_ccq")]
     pub allow_ccq: bool,
 }
 
 impl CosmosSdkConfig {
+    /// The memo of the transactions submitted to this chain, relaying on a channel to the
+    /// given counterparty chain, if any: the `memo_overwrite` if set, otherwise the memo
+    /// prefix overridden for the counterparty chain, or the `memo_prefix`.
+    pub fn tx_memo(&self, counterparty_chain_id: Option<&ChainId>) -> &Memo {
+        let memo_override =
+            counterparty_chain_id.and_then(|chain_id| self.memo_overrides.get(chain_id));
+
+        self.memo_overwrite
+            .as_ref()
+            .or(memo_override)
+            .unwrap_or(&self.memo_prefix)
+    }
+
     pub fn validate(&self) -> Result<(), Diagnostic<ConfigError>> {
         validate_trust_threshold(&self.id, self.trust_threshold)?;
         validate_gas_settings(&self.id, self.gas_adjustment)?;
+        validate_reference_gas_price(&self.id, self.reference_gas_price.as_ref())?;
+        validate_txfees_gas_price(
+            &self.id,
+            self.txfees_gas_price.as_ref(),
+            self.dynamic_gas_price.enabled,
+        )?;
+        validate_block_gas_usage(&self.id, self.block_gas_usage.as_ref())?;
+        validate_sticky_session(&self.id, self.sticky_session.as_ref())?;
         Ok(())
     }
 }
@@ -215,6 +484,97 @@ fn validate_gas_settings(
 
     Ok(())
 }
+
+fn validate_reference_gas_price(
+    id: &ChainId,
+    reference_gas_price: Option<&ReferenceGasPriceConfig>,
+) -> Result<(), Diagnostic<ConfigError>> {
+    match reference_gas_price {
+        Some(config) if !(config.reference_price.is_finite() && config.reference_price > 0.0) => {
+            Err(Diagnostic::Error(ConfigError::invalid_reference_price(
+                config.reference_price,
+                id.clone(),
+            )))
+        }
+        Some(config) if !(config.max_scale.is_finite() && config.max_scale >= 1.0) => {
+            Err(Diagnostic::Error(
+                ConfigError::invalid_reference_price_max_scale(config.max_scale, id.clone()),
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn validate_txfees_gas_price(
+    id: &ChainId,
+    txfees_gas_price: Option<&TxFeesGasPriceConfig>,
+    dynamic_gas_price_enabled: bool,
+) -> Result<(), Diagnostic<ConfigError>> {
+    let Some(config) = txfees_gas_price else {
+        return Ok(());
+    };
+
+    let reason = if !(config.base_gas_price.is_finite() && config.base_gas_price > 0.0) {
+        format!(
+            "`base_gas_price = {}` must be a positive number",
+            config.base_gas_price
+        )
+    } else if dynamic_gas_price_enabled {
+        "it cannot be used together with `dynamic_gas_price`".to_string()
+    } else {
+        return Ok(());
+    };
+
+    Err(Diagnostic::Error(ConfigError::invalid_tx_fees_gas_price(
+        id.clone(),
+        reason,
+    )))
+}
+
+fn validate_block_gas_usage(
+    id: &ChainId,
+    block_gas_usage: Option<&BlockGasUsageConfig>,
+) -> Result<(), Diagnostic<ConfigError>> {
+    let Some(config) = block_gas_usage else {
+        return Ok(());
+    };
+
+    if config.sample_blocks == 0 {
+        return Err(Diagnostic::Error(ConfigError::invalid_block_gas_usage(
+            id.clone(),
+            "`sample_blocks` must be at least 1".to_string(),
+        )));
+    }
+
+    if !(config.headroom > 0.0 && config.headroom <= 1.0) {
+        return Err(Diagnostic::Error(ConfigError::invalid_block_gas_usage(
+            id.clone(),
+            format!("`headroom = {}` must be in (0, 1]", config.headroom),
+        )));
+    }
+
+    Ok(())
+}
+
+fn validate_sticky_session(
+    id: &ChainId,
+    sticky_session: Option<&StickySessionConfig>,
+) -> Result<(), Diagnostic<ConfigError>> {
+    let name = match sticky_session {
+        Some(StickySessionConfig::Cookie(name) | StickySessionConfig::Header(name)) => name,
+        None => return Ok(()),
+    };
+
+    if http::HeaderName::from_bytes(name.as_bytes()).is_err() {
+        return Err(Diagnostic::Error(ConfigError::invalid_sticky_session(
+            id.clone(),
+            name.clone(),
+        )));
+    }
+
+    Ok(())
+}
+
 #[derive(Clone, Debug)]
 pub enum Diagnostic<E> {
     Warning(E),
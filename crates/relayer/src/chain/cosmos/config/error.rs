@@ -31,6 +31,68 @@ define_error! {
             )
         },
 
+        InvalidReferencePrice
+        {
+            reference_price: f64,
+            chain_id: ChainId,
+        }
+        |e| {
+            format!(
+                "config file specifies an invalid `reference_price = {0}` in `reference_gas_price` \
+                for the chain '{1}', it must be a positive number",
+                e.reference_price, e.chain_id
+            )
+        },
+
+        InvalidReferencePriceMaxScale
+        {
+            max_scale: f64,
+            chain_id: ChainId,
+        }
+        |e| {
+            format!(
+                "config file specifies an invalid `max_scale = {0}` in `reference_gas_price` \
+                for the chain '{1}', it must be a number greater than or equal to 1",
+                e.max_scale, e.chain_id
+            )
+        },
+
+        InvalidTxFeesGasPrice
+        {
+            chain_id: ChainId,
+            reason: String,
+        }
+        |e| {
+            format!(
+                "config file specifies an invalid `txfees_gas_price` for the chain '{0}': {1}",
+                e.chain_id, e.reason
+            )
+        },
+
+        InvalidBlockGasUsage
+        {
+            chain_id: ChainId,
+            reason: String,
+        }
+        |e| {
+            format!(
+                "config file specifies invalid `block_gas_usage` settings for the chain '{0}': {1}",
+                e.chain_id, e.reason
+            )
+        },
+
+        InvalidStickySession
+        {
+            chain_id: ChainId,
+            name: String,
+        }
+        |e| {
+            format!(
+                "config file specifies an invalid `sticky_session` for the chain '{0}': `{1}` is not a valid header or cookie name",
+                e.chain_id, e.name
+            )
+        },
+
         ExpectedExcludedSequencesArray
         |_| { "expected excluded_sequences to be an array of values" },
 
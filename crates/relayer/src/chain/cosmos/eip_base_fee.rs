@@ -1,6 +1,7 @@
 use core::fmt;
 use std::ops::Div;
 use std::str::FromStr;
+use std::sync::Arc;
 
 use serde::Deserialize;
 use subtle_encoding::base64;
@@ -10,10 +11,12 @@ use tracing::{debug, trace};
 use ibc_proto::cosmos::base::v1beta1::{DecCoin, DecProto};
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 
+use crate::chain::cosmos::request_rate::RequestRateLimiter;
 use crate::error::Error;
 
 pub async fn query_eip_base_fee(
     rpc_address: &Url,
+    limiter: Option<&Arc<RequestRateLimiter>>,
     gas_price_denom: &str,
     chain_id: &ChainId,
 ) -> Result<f64, Error> {
@@ -35,6 +38,10 @@ pub async fn query_eip_base_fee(
         )
     };
 
+    if let Some(limiter) = limiter {
+        limiter.wait().await;
+    }
+
     let response = reqwest::get(&url).await.map_err(Error::http_request)?;
 
     if !response.status().is_success() {
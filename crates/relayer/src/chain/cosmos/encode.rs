@@ -1,5 +1,6 @@
 use bech32::{ToBase32, Variant};
 use core::str::FromStr;
+use ibc_proto::cosmos::crypto::multisig::v1beta1::MultiSignature;
 use ibc_proto::cosmos::tx::v1beta1::mode_info::{Single, Sum};
 use ibc_proto::cosmos::tx::v1beta1::{AuthInfo, Fee, ModeInfo, SignDoc, SignerInfo, TxBody, TxRaw};
 use ibc_proto::google::protobuf::Any;
@@ -15,7 +16,7 @@ use crate::chain::cosmos::types::tx::SignedTx;
 use crate::config::types::Memo;
 use crate::config::AddressType;
 use crate::error::Error;
-use crate::keyring::{Secp256k1KeyPair, SigningKeyPair};
+use crate::keyring::{MultisigSigner, Secp256k1KeyPair, SigningKeyPair};
 
 pub fn sign_and_encode_tx(
     config: &TxConfig,
@@ -52,7 +53,7 @@ pub fn encoded_tx_metrics(
     messages: &[Any],
     fee: &Fee,
 ) -> Result<EncodedTxMetrics, Error> {
-    let signed_tx = sign_tx(config, key_pair, account, tx_memo, messages, fee)?;
+    let signed_tx = sign_tx_for_simulation(config, key_pair, account, tx_memo, messages, fee)?;
 
     let tx_raw = TxRaw {
         body_bytes: signed_tx.body_bytes,
@@ -74,6 +75,9 @@ pub fn encoded_tx_metrics(
     })
 }
 
+/// Signs a transaction with the given key, or as the multisig account configured
+/// for the chain if the key is one of its members, in which case the signatures of
+/// the other members are obtained from the partial signature source of the account.
 pub fn sign_tx(
     config: &TxConfig,
     key_pair: &Secp256k1KeyPair,
@@ -81,6 +85,53 @@ pub fn sign_tx(
     tx_memo: &Memo,
     messages: &[Any],
     fee: &Fee,
+) -> Result<SignedTx, Error> {
+    match multisig_signers(config, key_pair) {
+        Some((multisig, signers)) => sign_multisig_tx(
+            config, multisig, &signers, key_pair, account, tx_memo, messages, fee, true,
+        ),
+        None => sign_single_tx(config, key_pair, account, tx_memo, messages, fee),
+    }
+}
+
+/// Signs a transaction like [`sign_tx`], but without obtaining the signatures of the
+/// other members of a multisig account, whose signatures are left blank, for the
+/// simulation of the transaction or the estimation of its size.
+pub fn sign_tx_for_simulation(
+    config: &TxConfig,
+    key_pair: &Secp256k1KeyPair,
+    account: &Account,
+    tx_memo: &Memo,
+    messages: &[Any],
+    fee: &Fee,
+) -> Result<SignedTx, Error> {
+    match multisig_signers(config, key_pair) {
+        Some((multisig, signers)) => sign_multisig_tx(
+            config, multisig, &signers, key_pair, account, tx_memo, messages, fee, false,
+        ),
+        None => sign_single_tx(config, key_pair, account, tx_memo, messages, fee),
+    }
+}
+
+/// Returns the multisig account configured for the chain and the members signing
+/// its transactions, if the given key is one of its members.
+fn multisig_signers<'a>(
+    config: &'a TxConfig,
+    key_pair: &Secp256k1KeyPair,
+) -> Option<(&'a MultisigSigner, Vec<usize>)> {
+    let multisig = config.multisig.as_ref()?;
+    let signers = multisig.key().signers(&key_pair.public_key)?;
+
+    Some((multisig, signers))
+}
+
+fn sign_single_tx(
+    config: &TxConfig,
+    key_pair: &Secp256k1KeyPair,
+    account: &Account,
+    tx_memo: &Memo,
+    messages: &[Any],
+    fee: &Fee,
 ) -> Result<SignedTx, Error> {
     let key_bytes = encode_key_bytes(key_pair)?;
 
@@ -108,6 +159,61 @@ pub fn sign_tx(
     })
 }
 
+#[allow(clippy::too_many_arguments)]
+fn sign_multisig_tx(
+    config: &TxConfig,
+    multisig: &MultisigSigner,
+    signers: &[usize],
+    key_pair: &Secp256k1KeyPair,
+    account: &Account,
+    tx_memo: &Memo,
+    messages: &[Any],
+    fee: &Fee,
+    cosign: bool,
+) -> Result<SignedTx, Error> {
+    let signer = SignerInfo {
+        public_key: Some(multisig.key().to_any()),
+        mode_info: Some(multisig.key().mode_info(signers)),
+        sequence: account.sequence.to_u64(),
+    };
+
+    let (body, body_bytes) =
+        tx_body_and_bytes(messages, tx_memo, config.extension_options.clone())?;
+
+    let (auth_info, auth_info_bytes) = auth_info_and_bytes(signer, fee.clone())?;
+
+    let sign_bytes = sign_doc_bytes(
+        &config.chain_id,
+        account.number,
+        auth_info_bytes.clone(),
+        body_bytes.clone(),
+    );
+
+    let signatures = signers
+        .iter()
+        .map(|&index| {
+            let public_key = &multisig.key().public_keys()[index];
+
+            if *public_key == key_pair.public_key {
+                key_pair.sign(&sign_bytes)
+            } else if cosign {
+                multisig.partial_signature(public_key, &sign_bytes)
+            } else {
+                Ok(vec![0; 64])
+            }
+        })
+        .collect::<Result<_, _>>()
+        .map_err(Error::key_base)?;
+
+    Ok(SignedTx {
+        body,
+        body_bytes,
+        auth_info,
+        auth_info_bytes,
+        signatures: vec![MultiSignature { signatures }.encode_to_vec()],
+    })
+}
+
 fn encode_key_bytes(key_pair: &Secp256k1KeyPair) -> Result<Vec<u8>, Error> {
     let mut pk_buf = Vec::new();
 
@@ -124,6 +230,20 @@ fn encode_sign_doc(
     auth_info_bytes: Vec<u8>,
     body_bytes: Vec<u8>,
 ) -> Result<Vec<u8>, Error> {
+    let signdoc_buf = sign_doc_bytes(chain_id, account_number, auth_info_bytes, body_bytes);
+
+    let signed = key_pair.sign(&signdoc_buf).map_err(Error::key_base)?;
+
+    Ok(signed)
+}
+
+/// A protobuf serialization of a SignDoc
+fn sign_doc_bytes(
+    chain_id: &ChainId,
+    account_number: AccountNumber,
+    auth_info_bytes: Vec<u8>,
+    body_bytes: Vec<u8>,
+) -> Vec<u8> {
     let sign_doc = SignDoc {
         body_bytes,
         auth_info_bytes,
@@ -131,13 +251,7 @@ fn encode_sign_doc(
         account_number: account_number.to_u64(),
     };
 
-    // A protobuf serialization of a SignDoc
-    let mut signdoc_buf = Vec::new();
-    Message::encode(&sign_doc, &mut signdoc_buf).unwrap();
-
-    let signed = key_pair.sign(&signdoc_buf).map_err(Error::key_base)?;
-
-    Ok(signed)
+    sign_doc.encode_to_vec()
 }
 
 fn encode_signer_info(
@@ -234,3 +348,143 @@ pub fn key_pair_to_signer(key_pair: &Secp256k1KeyPair) -> Result<Signer, Error>
 
     Ok(signer)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use ibc_proto::cosmos::tx::v1beta1::ModeInfo;
+    use secp256k1::PublicKey;
+
+    use crate::chain::cosmos::batch::test_fixtures::example_tx_config;
+    use crate::chain::cosmos::types::account::AccountAddress;
+    use crate::keyring::errors::Error as KeyringError;
+    use crate::keyring::{MultisigKey, PartialSignatureSource};
+
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon \
+        abandon abandon abandon abandon abandon about";
+
+    /// Signs with the keys of the other members held in memory.
+    #[derive(Debug)]
+    struct MemberKeys(Vec<Secp256k1KeyPair>);
+
+    impl PartialSignatureSource for MemberKeys {
+        fn partial_signature(
+            &self,
+            public_key: &PublicKey,
+            sign_bytes: &[u8],
+        ) -> Result<Vec<u8>, KeyringError> {
+            let key_pair = self
+                .0
+                .iter()
+                .find(|key_pair| key_pair.public_key == *public_key)
+                .ok_or_else(KeyringError::key_not_found)?;
+
+            key_pair.sign(sign_bytes)
+        }
+    }
+
+    fn member_key(index: u32) -> Secp256k1KeyPair {
+        let hd_path = format!("m/44'/118'/0'/0/{index}").parse().unwrap();
+
+        Secp256k1KeyPair::from_mnemonic(MNEMONIC, &hd_path, &AddressType::Cosmos, "cosmos").unwrap()
+    }
+
+    fn multisig_parts(signed_tx: &SignedTx) -> (&ModeInfo, &[u8]) {
+        let signer_info = &signed_tx.auth_info.signer_infos[0];
+
+        (
+            signer_info.mode_info.as_ref().unwrap(),
+            &signed_tx.signatures[0],
+        )
+    }
+
+    #[test]
+    fn multisig_signature_of_relay_tx_verifies_against_threshold() {
+        let members = (0..3).map(member_key).collect::<Vec<_>>();
+
+        let key =
+            MultisigKey::new(2, members.iter().map(|member| member.public_key).collect()).unwrap();
+
+        let config = TxConfig {
+            multisig: Some(MultisigSigner::new(
+                key.clone(),
+                Arc::new(MemberKeys(members.clone())),
+            )),
+            ..example_tx_config()
+        };
+
+        let account = Account {
+            address: AccountAddress::new(key.account("cosmos").unwrap()),
+            number: AccountNumber::new(7),
+            sequence: AccountSequence::new(3),
+        };
+
+        let messages = [Any {
+            type_url: "/ibc.core.channel.v1.MsgRecvPacket".to_string(),
+            value: vec![1; 100],
+        }];
+
+        let memo = Memo::new("relay").unwrap();
+        let fee = config.gas_config.max_fee.clone();
+
+        // The relayer holds the key of the second member
+        let relayer_key = &members[1];
+
+        let signed_tx = sign_tx(&config, relayer_key, &account, &memo, &messages, &fee).unwrap();
+
+        let signer_info = &signed_tx.auth_info.signer_infos[0];
+        assert_eq!(signer_info.public_key, Some(key.to_any()));
+        assert_eq!(signer_info.sequence, 3);
+
+        let sign_bytes = sign_doc_bytes(
+            &config.chain_id,
+            account.number,
+            signed_tx.auth_info_bytes.clone(),
+            signed_tx.body_bytes.clone(),
+        );
+
+        let (mode_info, signature) = multisig_parts(&signed_tx);
+        assert!(key.verify(&sign_bytes, mode_info, signature));
+
+        // The signatures are bound to the transaction
+        let other_sign_bytes = sign_doc_bytes(
+            &config.chain_id,
+            AccountNumber::new(8),
+            signed_tx.auth_info_bytes.clone(),
+            signed_tx.body_bytes.clone(),
+        );
+        assert!(!key.verify(&other_sign_bytes, mode_info, signature));
+
+        // The signature of the relayer alone does not reach the threshold
+        let signature = MultiSignature {
+            signatures: vec![relayer_key.sign(&sign_bytes).unwrap()],
+        }
+        .encode_to_vec();
+        assert!(!key.verify(&sign_bytes, &key.mode_info(&[1]), &signature));
+
+        // The transactions signed for simulation leave the signatures of the other members blank
+        let simulated_tx =
+            sign_tx_for_simulation(&config, relayer_key, &account, &memo, &messages, &fee).unwrap();
+
+        assert_eq!(simulated_tx.auth_info_bytes, signed_tx.auth_info_bytes);
+        assert_eq!(
+            simulated_tx.signatures[0].len(),
+            signed_tx.signatures[0].len()
+        );
+
+        let (mode_info, signature) = multisig_parts(&simulated_tx);
+        assert!(!key.verify(&sign_bytes, mode_info, signature));
+
+        // Keys which are not members of the account sign on their own
+        let other_key = member_key(3);
+        let single_tx = sign_tx(&config, &other_key, &account, &memo, &messages, &fee).unwrap();
+
+        assert_ne!(
+            single_tx.auth_info.signer_infos[0].public_key,
+            Some(key.to_any())
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use ibc_proto::cosmos::tx::v1beta1::{Fee, Tx};
 use ibc_proto::google::protobuf::Any;
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
@@ -5,9 +7,11 @@ use tendermint_rpc::Url;
 use tonic::codegen::http::Uri;
 use tracing::{debug, error, span, warn, Level};
 
-use crate::chain::cosmos::encode::sign_tx;
-use crate::chain::cosmos::gas::gas_amount_to_fee;
+use crate::chain::cosmos::encode::sign_tx_for_simulation;
+use crate::chain::cosmos::gas::{cap_dynamic_fee, gas_amount_to_fee, BatchShape};
+use crate::chain::cosmos::request_rate::RequestRateLimiter;
 use crate::chain::cosmos::simulate::send_tx_simulate;
+use crate::chain::cosmos::sticky_session::Session;
 use crate::chain::cosmos::types::account::Account;
 use crate::chain::cosmos::types::config::TxConfig;
 use crate::chain::cosmos::types::gas::GasConfig;
@@ -39,12 +43,31 @@ pub async fn estimate_tx_fees(
 ) -> Result<(Fee, EstimatedGas), Error> {
     let gas_config = &config.gas_config;
 
+    let batch_shape = BatchShape::of(messages);
+
+    if let Some(gas_amount) = gas_config.gas_sampler.reuse_estimate(&batch_shape) {
+        debug!("reusing sampled gas estimate for tx: {gas_amount}");
+
+        let fee = gas_amount_to_fee(
+            gas_config,
+            gas_amount,
+            &config.chain_id,
+            &config.rpc_address,
+            config.request_limiter.as_ref(),
+        )
+        .await;
+
+        let fee = cap_dynamic_fee(gas_config, &config.chain_id, fee)?;
+
+        return Ok((fee, EstimatedGas::Simulated(gas_amount)));
+    }
+
     debug!(
         "max fee, for use in tx simulation: {}",
         PrettyFee(&gas_config.max_fee)
     );
 
-    let signed_tx = sign_tx(
+    let signed_tx = sign_tx_for_simulation(
         config,
         key_pair,
         account,
@@ -62,6 +85,8 @@ pub async fn estimate_tx_fees(
     let estimated_fee_and_gas = estimate_fee_with_tx(
         gas_config,
         &config.grpc_address,
+        config.grpc_session.as_ref(),
+        config.request_limiter.as_ref(),
         &config.rpc_address,
         &config.chain_id,
         tx,
@@ -69,12 +94,20 @@ pub async fn estimate_tx_fees(
     )
     .await?;
 
+    if let EstimatedGas::Simulated(gas_amount) = estimated_fee_and_gas.1 {
+        gas_config
+            .gas_sampler
+            .record_simulation(batch_shape, gas_amount);
+    }
+
     Ok(estimated_fee_and_gas)
 }
 
 async fn estimate_fee_with_tx(
     gas_config: &GasConfig,
     grpc_address: &Uri,
+    grpc_session: Option<&Arc<Session>>,
+    request_limiter: Option<&Arc<RequestRateLimiter>>,
     rpc_address: &Url,
     chain_id: &ChainId,
     tx: Tx,
@@ -88,7 +121,15 @@ async fn estimate_fee_with_tx(
             }
 
         );
-        estimate_gas_with_tx(gas_config, grpc_address, tx, account).await
+        estimate_gas_with_tx(
+            gas_config,
+            grpc_address,
+            grpc_session,
+            request_limiter,
+            tx,
+            account,
+        )
+        .await
     }?;
 
     let estimated_gas_amount = estimated_gas.get_amount();
@@ -106,8 +147,16 @@ async fn estimate_fee_with_tx(
         ));
     }
 
-    let adjusted_fee =
-        gas_amount_to_fee(gas_config, estimated_gas_amount, chain_id, rpc_address).await;
+    let adjusted_fee = gas_amount_to_fee(
+        gas_config,
+        estimated_gas_amount,
+        chain_id,
+        rpc_address,
+        request_limiter,
+    )
+    .await;
+
+    let adjusted_fee = cap_dynamic_fee(gas_config, chain_id, adjusted_fee)?;
 
     debug!(
         id = %chain_id,
@@ -130,10 +179,12 @@ async fn estimate_fee_with_tx(
 async fn estimate_gas_with_tx(
     gas_config: &GasConfig,
     grpc_address: &Uri,
+    grpc_session: Option<&Arc<Session>>,
+    grpc_limiter: Option<&Arc<RequestRateLimiter>>,
     tx: Tx,
     _account: &Account,
 ) -> Result<EstimatedGas, Error> {
-    let simulated_gas = send_tx_simulate(grpc_address, tx)
+    let simulated_gas = send_tx_simulate(grpc_address, grpc_session, grpc_limiter, tx)
         .await
         .map(|sr| sr.gas_info);
 
@@ -220,3 +271,215 @@ fn get_error_text(e: &Error) -> String {
         detail => detail.to_string(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use ibc_proto::cosmos::base::abci::v1beta1::GasInfo;
+    use ibc_proto::cosmos::tx::v1beta1::service_server::{Service, ServiceServer};
+    use ibc_proto::cosmos::tx::v1beta1::{
+        BroadcastTxRequest, BroadcastTxResponse, GetBlockWithTxsRequest, GetBlockWithTxsResponse,
+        GetTxRequest, GetTxResponse, GetTxsEventRequest, GetTxsEventResponse, SimulateRequest,
+        SimulateResponse, TxDecodeAminoRequest, TxDecodeAminoResponse, TxDecodeRequest,
+        TxDecodeResponse, TxEncodeAminoRequest, TxEncodeAminoResponse, TxEncodeRequest,
+        TxEncodeResponse,
+    };
+    use ibc_relayer_types::core::ics02_client::msgs::update_client;
+    use ibc_relayer_types::core::ics04_channel::msgs::recv_packet;
+    use tonic::{Request, Response, Status};
+
+    use crate::chain::cosmos::batch::test_fixtures::example_tx_config;
+    use crate::chain::cosmos::gas::GasEstimateSampler;
+    use crate::chain::cosmos::types::account::{AccountAddress, AccountNumber, AccountSequence};
+    use crate::config::gas_sampling::GasEstimationSampling;
+    use crate::config::AddressType;
+    use crate::keyring::SigningKeyPair;
+    use crate::util::mock_http::spawn_mock_grpc_server;
+
+    /// A node simulating every tx at 1000 more gas than the previous one, from 100 000 gas.
+    #[derive(Clone, Default)]
+    struct SimulatingNode {
+        simulations: Arc<AtomicU64>,
+    }
+
+    #[tonic::async_trait]
+    impl Service for SimulatingNode {
+        async fn simulate(
+            &self,
+            _request: Request<SimulateRequest>,
+        ) -> Result<Response<SimulateResponse>, Status> {
+            let simulation = self.simulations.fetch_add(1, Ordering::SeqCst);
+
+            Ok(Response::new(SimulateResponse {
+                gas_info: Some(GasInfo {
+                    gas_wanted: 0,
+                    gas_used: 100_000 + 1000 * simulation,
+                }),
+                result: None,
+            }))
+        }
+
+        async fn get_tx(
+            &self,
+            _request: Request<GetTxRequest>,
+        ) -> Result<Response<GetTxResponse>, Status> {
+            Err(Status::unimplemented("get_tx"))
+        }
+
+        async fn broadcast_tx(
+            &self,
+            _request: Request<BroadcastTxRequest>,
+        ) -> Result<Response<BroadcastTxResponse>, Status> {
+            Err(Status::unimplemented("broadcast_tx"))
+        }
+
+        async fn get_txs_event(
+            &self,
+            _request: Request<GetTxsEventRequest>,
+        ) -> Result<Response<GetTxsEventResponse>, Status> {
+            Err(Status::unimplemented("get_txs_event"))
+        }
+
+        async fn get_block_with_txs(
+            &self,
+            _request: Request<GetBlockWithTxsRequest>,
+        ) -> Result<Response<GetBlockWithTxsResponse>, Status> {
+            Err(Status::unimplemented("get_block_with_txs"))
+        }
+
+        async fn tx_decode(
+            &self,
+            _request: Request<TxDecodeRequest>,
+        ) -> Result<Response<TxDecodeResponse>, Status> {
+            Err(Status::unimplemented("tx_decode"))
+        }
+
+        async fn tx_encode(
+            &self,
+            _request: Request<TxEncodeRequest>,
+        ) -> Result<Response<TxEncodeResponse>, Status> {
+            Err(Status::unimplemented("tx_encode"))
+        }
+
+        async fn tx_encode_amino(
+            &self,
+            _request: Request<TxEncodeAminoRequest>,
+        ) -> Result<Response<TxEncodeAminoResponse>, Status> {
+            Err(Status::unimplemented("tx_encode_amino"))
+        }
+
+        async fn tx_decode_amino(
+            &self,
+            _request: Request<TxDecodeAminoRequest>,
+        ) -> Result<Response<TxDecodeAminoResponse>, Status> {
+            Err(Status::unimplemented("tx_decode_amino"))
+        }
+    }
+
+    impl SimulatingNode {
+        /// Serves the node until the test exits, returning its gRPC address.
+        async fn spawn(&self) -> Uri {
+            let address = spawn_mock_grpc_server(ServiceServer::new(self.clone())).await;
+
+            format!("http://{address}").parse().unwrap()
+        }
+
+        fn simulations(&self) -> u64 {
+            self.simulations.load(Ordering::SeqCst)
+        }
+    }
+
+    /// The tx config of `chain_A` of the example config, connected to the given node,
+    /// with the given gas estimation sampling.
+    fn tx_config(grpc_address: Uri, sampling: GasEstimationSampling) -> TxConfig {
+        let mut tx_config = example_tx_config();
+        tx_config.grpc_address = grpc_address;
+        tx_config.gas_config.gas_sampler = Arc::new(GasEstimateSampler::new(sampling));
+
+        tx_config
+    }
+
+    fn messages(type_urls: &[&str]) -> Vec<Any> {
+        type_urls
+            .iter()
+            .map(|type_url| Any {
+                type_url: type_url.to_string(),
+                value: vec![],
+            })
+            .collect()
+    }
+
+    /// Estimates the fees of a tx carrying the given messages, returning its estimated gas.
+    async fn estimate(config: &TxConfig, messages: &[Any]) -> u64 {
+        let key_pair = Secp256k1KeyPair::from_mnemonic(
+            "abandon abandon abandon abandon abandon abandon \
+            abandon abandon abandon abandon abandon about",
+            &"m/44'/118'/0'/0/0".parse().unwrap(),
+            &AddressType::Cosmos,
+            "cosmos",
+        )
+        .unwrap();
+        let account = Account {
+            address: AccountAddress::new(key_pair.account()),
+            number: AccountNumber::new(1),
+            sequence: AccountSequence::new(0),
+        };
+
+        let (_, estimated_gas) = estimate_tx_fees(
+            config,
+            &key_pair,
+            &account,
+            &Memo::new("hermes").unwrap(),
+            messages,
+        )
+        .await
+        .unwrap();
+
+        match estimated_gas {
+            EstimatedGas::Simulated(gas_amount) => gas_amount,
+            EstimatedGas::Default(_) => panic!("the tx should have been simulated"),
+        }
+    }
+
+    #[tokio::test]
+    async fn sampling_simulates_every_nth_tx_of_the_same_shape() {
+        let node = SimulatingNode::default();
+        let sampling = GasEstimationSampling::enabled(3).unwrap();
+        let config = tx_config(node.spawn().await, sampling);
+        let recv = messages(&[recv_packet::TYPE_URL]);
+
+        let mut estimates = Vec::new();
+        for _ in 0..7 {
+            estimates.push(estimate(&config, &recv).await);
+        }
+
+        // The 1st, 4th and 7th txs are simulated, the others reuse the last simulation
+        assert_eq!(node.simulations(), 3);
+        assert_eq!(
+            estimates,
+            [100_000, 100_000, 100_000, 101_000, 101_000, 101_000, 102_000]
+        );
+
+        // A change in the shape of the batch always triggers a new simulation
+        let update_and_recv = messages(&[update_client::TYPE_URL, recv_packet::TYPE_URL]);
+        assert_eq!(estimate(&config, &update_and_recv).await, 103_000);
+        assert_eq!(estimate(&config, &recv).await, 104_000);
+        assert_eq!(node.simulations(), 5);
+    }
+
+    #[tokio::test]
+    async fn every_tx_is_simulated_without_sampling() {
+        let node = SimulatingNode::default();
+        let config = tx_config(node.spawn().await, GasEstimationSampling::disabled());
+        let recv = messages(&[recv_packet::TYPE_URL]);
+
+        for _ in 0..3 {
+            estimate(&config, &recv).await;
+        }
+
+        assert_eq!(node.simulations(), 3);
+    }
+}
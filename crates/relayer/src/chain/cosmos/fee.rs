@@ -1,21 +1,21 @@
 use ibc_relayer_types::applications::ics29_fee::msgs::register_payee::build_register_counterparty_payee_message;
 use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, PortId};
 use ibc_relayer_types::signer::Signer;
-use tendermint_rpc::HttpClient;
 
 use crate::chain::cosmos::query::account::get_or_fetch_account;
 use crate::chain::cosmos::query::fee::query_counterparty_payee;
+use crate::chain::cosmos::request_rate::PacedRpcClient;
 use crate::chain::cosmos::retry::send_tx_with_account_sequence_retry;
 use crate::chain::cosmos::types::account::Account;
 use crate::chain::cosmos::types::config::TxConfig;
-use crate::chain::cosmos::wait::wait_tx_succeed;
+use crate::chain::cosmos::wait::{wait_tx_succeed, BlockSearch};
 use crate::config::types::Memo;
 use crate::error::Error;
-use crate::keyring::{Secp256k1KeyPair, SigningKeyPair};
+use crate::keyring::Secp256k1KeyPair;
 
 // FIXME: monster function, refactor
 pub async fn maybe_register_counterparty_payee(
-    rpc_client: &HttpClient,
+    rpc_client: &PacedRpcClient,
     tx_config: &TxConfig,
     key_pair: &Secp256k1KeyPair,
     m_account: &mut Option<Account>,
@@ -25,11 +25,24 @@ pub async fn maybe_register_counterparty_payee(
     address: &Signer,
     counterparty_payee: &Signer,
 ) -> Result<(), Error> {
-    let key_account = key_pair.account();
-    let account = get_or_fetch_account(&tx_config.grpc_address, &key_account, m_account).await?;
+    let account = get_or_fetch_account(
+        &tx_config.grpc_address,
+        tx_config.grpc_session.as_ref(),
+        tx_config.request_limiter.as_ref(),
+        address.as_ref(),
+        tx_config.account_query,
+        m_account,
+    )
+    .await?;
 
-    let current_counterparty_payee =
-        query_counterparty_payee(&tx_config.grpc_address, channel_id, address).await?;
+    let current_counterparty_payee = query_counterparty_payee(
+        &tx_config.grpc_address,
+        tx_config.grpc_session.as_ref(),
+        tx_config.request_limiter.as_ref(),
+        channel_id,
+        address,
+    )
+    .await?;
 
     match &current_counterparty_payee {
         Some(current_counterparty_payee)
@@ -46,6 +59,13 @@ pub async fn maybe_register_counterparty_payee(
             )
             .map_err(Error::ics29)?;
 
+            let block_search = BlockSearch::start(
+                rpc_client,
+                &tx_config.rpc_address,
+                &tx_config.tx_confirmation,
+            )
+            .await;
+
             let response = send_tx_with_account_sequence_retry(
                 rpc_client,
                 tx_config,
@@ -60,6 +80,8 @@ pub async fn maybe_register_counterparty_payee(
                 rpc_client,
                 &tx_config.rpc_address,
                 &tx_config.rpc_timeout,
+                &tx_config.tx_confirmation,
+                block_search,
                 &response.hash,
             )
             .await?;
@@ -0,0 +1,535 @@
+//! Daily budget of the fees spent by the relayer on a chain. Once the fees of the
+//! transactions submitted on the day would exceed the budget, the relayer stops submitting
+//! transactions to the chain until the next day, in UTC, except for the client updates
+//! which keep the clients hosted by the chain from expiring.
+//!
+//! The budget is expressed in the preferred denomination of the fees of the chain. The fees
+//! paid in the other configured denominations count against it at the rate of their gas prices.
+//!
+//! The fees spent on the day before the relayer started are loaded from the most recent
+//! transactions of each of its accounts, the first time the account submits a transaction.
+
+use std::collections::HashSet;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ibc_proto::cosmos::tx::v1beta1::Fee;
+use ibc_proto::google::protobuf::Any;
+use ibc_relayer_types::core::ics02_client::error::Error as ClientError;
+use ibc_relayer_types::core::ics02_client::msgs::{misbehaviour, update_client};
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use ibc_relayer_types::signer::Signer;
+use tendermint_rpc::Url;
+use tracing::{debug, warn};
+
+use crate::chain::cosmos::fees_spent::fees_spent_from_txs;
+use crate::chain::cosmos::query::tx::query_txs_by_sender;
+use crate::chain::cosmos::request_rate::PacedRpcClient;
+use crate::config::GasPrices;
+use crate::error::Error;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// The number of most recent transactions of an account whose fees are loaded.
+const MAX_LOADED_TXS: u8 = 100;
+
+#[derive(Clone, Debug, Default)]
+struct DailySpend {
+    day: u64,
+    amount: u128,
+    /// The accounts whose fees spent on the day before the relayer started were loaded
+    loaded_accounts: HashSet<String>,
+}
+
+impl DailySpend {
+    fn add(&mut self, day: u64, amount: u128) {
+        if self.day != day {
+            self.day = day;
+            self.amount = 0;
+        }
+
+        self.amount = self.amount.saturating_add(amount);
+    }
+
+    fn on(&self, day: u64) -> u128 {
+        if self.day == day {
+            self.amount
+        } else {
+            0
+        }
+    }
+}
+
+/// The number of days elapsed since the Unix epoch, which changes at midnight UTC.
+fn today() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+        / SECONDS_PER_DAY
+}
+
+/// The maximum amount of fees, in the preferred denomination of the fees of a chain, spent
+/// on the chain per day, along with the fees spent on the chain today.
+#[derive(Debug)]
+pub struct DailyFeeBudget {
+    chain_id: ChainId,
+    denom: String,
+    budget: u128,
+    /// The rates at which the fees paid in each of the other configured denominations
+    /// are converted to the denomination of the budget
+    fallback_rates: Vec<(String, f64)>,
+    spend: Mutex<DailySpend>,
+}
+
+impl DailyFeeBudget {
+    pub fn new(chain_id: ChainId, gas_prices: &GasPrices, budget: u128) -> Self {
+        let preferred = gas_prices.preferred();
+
+        Self {
+            chain_id,
+            denom: preferred.denom.clone(),
+            budget,
+            fallback_rates: gas_prices
+                .iter()
+                .skip(1)
+                .map(|gas_price| (gas_price.denom.clone(), preferred.price / gas_price.price))
+                .collect(),
+            spend: Mutex::new(DailySpend::default()),
+        }
+    }
+
+    /// Loads the fees of the transactions sent today from the given account, among its
+    /// most recent ones, unless they were loaded already. The fees of the transactions
+    /// submitted afterwards are recorded with [`DailyFeeBudget::record`].
+    pub async fn load_account(
+        &self,
+        rpc_client: &PacedRpcClient,
+        rpc_address: &Url,
+        account: &str,
+    ) -> Result<(), Error> {
+        self.load_account_on(today(), rpc_client, rpc_address, account)
+            .await
+    }
+
+    async fn load_account_on(
+        &self,
+        day: u64,
+        rpc_client: &PacedRpcClient,
+        rpc_address: &Url,
+        account: &str,
+    ) -> Result<(), Error> {
+        if self.lock().loaded_accounts.contains(account) {
+            return Ok(());
+        }
+
+        let amount = query_fees_spent_on(day, rpc_client, rpc_address, account, |denom, amount| {
+            self.amount_in_budget_denom(denom, amount)
+        })
+        .await?;
+
+        debug!(
+            chain = %self.chain_id,
+            %account,
+            "loaded the fees of {amount}{} spent today by the account",
+            self.denom
+        );
+
+        let mut spend = self.lock();
+        if spend.loaded_accounts.insert(account.to_string()) {
+            spend.add(day, amount);
+        }
+
+        Ok(())
+    }
+
+    /// Fails if the fee of the transaction carrying the given messages would exceed
+    /// the budget of the day, unless the messages are urgent.
+    pub fn check(&self, messages: &[Any], fee: &Fee) -> Result<(), Error> {
+        self.check_on(today(), messages, fee)
+    }
+
+    fn check_on(&self, day: u64, messages: &[Any], fee: &Fee) -> Result<(), Error> {
+        let spent = self.lock().on(day);
+        let amount = self.amount_of(fee);
+
+        if spent.saturating_add(amount) <= self.budget {
+            return Ok(());
+        }
+
+        if is_urgent(messages) {
+            warn!(
+                chain = %self.chain_id,
+                "daily fee budget of {}{} is exhausted, submitting client updates anyway",
+                self.budget,
+                self.denom
+            );
+
+            return Ok(());
+        }
+
+        Err(Error::daily_fee_budget_exhausted(
+            self.chain_id.clone(),
+            self.denom.clone(),
+            self.budget,
+            spent,
+            amount,
+        ))
+    }
+
+    /// Records the fee of a transaction accepted by the chain.
+    pub fn record(&self, fee: &Fee) {
+        self.record_on(today(), fee)
+    }
+
+    fn record_on(&self, day: u64, fee: &Fee) {
+        let amount = self.amount_of(fee);
+        self.lock().add(day, amount);
+    }
+
+    /// The amount of the given fee in the denomination of the budget.
+    fn amount_of(&self, fee: &Fee) -> u128 {
+        fee.amount
+            .iter()
+            .filter_map(|coin| {
+                let amount = coin.amount.parse::<u128>().ok()?;
+                Some(self.amount_in_budget_denom(&coin.denom, amount))
+            })
+            .sum()
+    }
+
+    /// The given amount of fees in the given denomination, converted to the denomination
+    /// of the budget if it is one of the other configured denominations, or zero otherwise.
+    fn amount_in_budget_denom(&self, denom: &str, amount: u128) -> u128 {
+        if denom == self.denom {
+            return amount;
+        }
+
+        self.fallback_rates
+            .iter()
+            .find(|(fallback, _)| fallback == denom)
+            .map_or(0, |(_, rate)| (amount as f64 * rate).ceil() as u128)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, DailySpend> {
+        self.spend.lock().expect("poisoned lock")
+    }
+}
+
+/// Sums up the fees of the transactions sent on the given day from the given account,
+/// among its most recent ones, each converted with `amount_of` from its denomination.
+async fn query_fees_spent_on(
+    day: u64,
+    rpc_client: &PacedRpcClient,
+    rpc_address: &Url,
+    account: &str,
+    amount_of: impl Fn(&str, u128) -> u128,
+) -> Result<u128, Error> {
+    let sender: Signer = account
+        .parse()
+        .map_err(|e| Error::ics02(ClientError::signer(e)))?;
+
+    let txs = query_txs_by_sender(rpc_client, rpc_address, &sender, MAX_LOADED_TXS).await?;
+
+    let day_start = i64::try_from(day * SECONDS_PER_DAY).unwrap_or(i64::MAX);
+    let mut amount = 0u128;
+    let mut last_block = None;
+
+    // The transactions are the most recent first
+    for tx in txs {
+        let block_time = match last_block {
+            Some((height, time)) if height == tx.height => time,
+            _ => {
+                let header = rpc_client
+                    .header(tx.height)
+                    .await
+                    .map_err(|e| Error::rpc(rpc_address.clone(), e))?
+                    .header;
+
+                header.time.unix_timestamp()
+            }
+        };
+        last_block = Some((tx.height, block_time));
+
+        if block_time < day_start {
+            break;
+        }
+
+        amount += fees_spent_from_txs([&tx])
+            .iter()
+            .map(|fee_spent| amount_of(&fee_spent.denom, fee_spent.amount))
+            .sum::<u128>();
+    }
+
+    Ok(amount)
+}
+
+/// Whether the messages only update or freeze clients, which must go through
+/// regardless of the budget to prevent the clients from expiring.
+fn is_urgent(messages: &[Any]) -> bool {
+    !messages.is_empty()
+        && messages.iter().all(|msg| {
+            msg.type_url == update_client::TYPE_URL || msg.type_url == misbehaviour::TYPE_URL
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_proto::cosmos::base::v1beta1::Coin;
+    use ibc_proto::cosmos::tx::v1beta1::{AuthInfo, TxBody, TxRaw};
+    use ibc_relayer_types::core::ics04_channel::msgs::recv_packet;
+    use prost::Message;
+    use subtle_encoding::base64;
+    use tendermint_rpc::HttpClient;
+
+    use crate::chain::cosmos::fee_denom::{select_gas_price, FeeDenomBalances};
+    use crate::chain::cosmos::gas::calculate_fee;
+    use crate::config::GasPrice;
+    use crate::error::ErrorDetail;
+    use crate::util::mock_http::{spawn_mock_http_server, MockResponse};
+
+    const DAY: u64 = 19_000;
+    const HASH: &str = "E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855";
+
+    fn msg(type_url: &str) -> Any {
+        Any {
+            type_url: type_url.to_string(),
+            value: vec![],
+        }
+    }
+
+    fn fee(denom: &str, amount: u64) -> Fee {
+        Fee {
+            amount: vec![Coin {
+                denom: denom.to_string(),
+                amount: amount.to_string(),
+            }],
+            gas_limit: 200_000,
+            ..Fee::default()
+        }
+    }
+
+    /// Gas prices in `stake`, and in `uosmo` at twice the price.
+    fn gas_prices() -> GasPrices {
+        GasPrices::new(vec![
+            GasPrice::new(0.1, "stake".to_string()),
+            GasPrice::new(0.2, "uosmo".to_string()),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn relays_halt_once_budget_is_spent_and_resume_the_next_day() {
+        let budget = DailyFeeBudget::new(ChainId::from_string("budget-0"), &gas_prices(), 100);
+
+        let packets = [msg(update_client::TYPE_URL), msg(recv_packet::TYPE_URL)];
+        let client_update = [msg(update_client::TYPE_URL)];
+
+        budget.record_on(DAY, &fee("stake", 60));
+        assert!(budget.check_on(DAY, &packets, &fee("stake", 40)).is_ok());
+
+        // The fee of the tx would exceed the budget
+        assert!(budget.check_on(DAY, &packets, &fee("stake", 41)).is_err());
+
+        // Fees spent in a denomination which is not configured do not count against the budget
+        budget.record_on(DAY, &fee("uatom", 1000));
+        assert!(budget.check_on(DAY, &packets, &fee("stake", 40)).is_ok());
+
+        budget.record_on(DAY, &fee("stake", 40));
+        assert!(budget.check_on(DAY, &packets, &fee("stake", 1)).is_err());
+
+        // Client updates still go through to prevent the clients from expiring
+        assert!(budget
+            .check_on(DAY, &client_update, &fee("stake", 1))
+            .is_ok());
+
+        // The budget is replenished at the rollover
+        assert!(budget
+            .check_on(DAY + 1, &packets, &fee("stake", 100))
+            .is_ok());
+
+        budget.record_on(DAY + 1, &fee("stake", 10));
+        assert_eq!(budget.lock().on(DAY + 1), 10);
+        assert!(budget
+            .check_on(DAY + 1, &packets, &fee("stake", 91))
+            .is_err());
+    }
+
+    fn tx(fee_amount: u64) -> String {
+        let body = TxBody {
+            messages: vec![msg(recv_packet::TYPE_URL)],
+            ..Default::default()
+        };
+
+        let auth_info = AuthInfo {
+            fee: Some(fee("stake", fee_amount)),
+            ..Default::default()
+        };
+
+        let tx_raw = TxRaw {
+            body_bytes: body.encode_to_vec(),
+            auth_info_bytes: auth_info.encode_to_vec(),
+            signatures: vec![],
+        };
+
+        String::from_utf8(base64::encode(tx_raw.encode_to_vec())).unwrap()
+    }
+
+    /// A node on which the account sent a tx at each of the given heights, with the given
+    /// fee, in the block whose time is at the given number of seconds since the Unix epoch.
+    fn node(txs: &'static [(u32, u64, u64)]) -> Url {
+        let (address, _requests) = spawn_mock_http_server(move |request| {
+            let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+
+            let result = match body["method"].as_str().unwrap() {
+                "tx_search" => {
+                    let txs: Vec<String> = txs
+                        .iter()
+                        .map(|(height, fee, _)| {
+                            format!(
+                                r#"{{
+                                    "hash": "{HASH}",
+                                    "height": "{height}",
+                                    "index": 0,
+                                    "tx_result": {{
+                                        "code": 0, "codespace": "", "data": null, "events": [],
+                                        "gas_used": "0", "gas_wanted": "0", "info": "", "log": ""
+                                    }},
+                                    "tx": "{}"
+                                }}"#,
+                                tx(*fee)
+                            )
+                        })
+                        .collect();
+
+                    format!(
+                        r#"{{ "txs": [{}], "total_count": "{}" }}"#,
+                        txs.join(","),
+                        txs.len()
+                    )
+                }
+                "header" => {
+                    let height: u32 = body["params"]["height"].as_str().unwrap().parse().unwrap();
+                    let (_, _, time) = txs.iter().find(|(h, _, _)| *h == height).unwrap();
+                    let time = tendermint::Time::from_unix_timestamp(*time as i64, 0).unwrap();
+
+                    format!(
+                        r#"{{ "header": {{
+                            "app_hash": "0000000000000000",
+                            "chain_id": "budget-0",
+                            "consensus_hash": "{HASH}",
+                            "data_hash": "{HASH}",
+                            "evidence_hash": "{HASH}",
+                            "height": "{height}",
+                            "last_block_id": {{
+                                "hash": "{HASH}",
+                                "parts": {{ "hash": "{HASH}", "total": 1 }}
+                            }},
+                            "last_commit_hash": "{HASH}",
+                            "last_results_hash": "{HASH}",
+                            "next_validators_hash": "{HASH}",
+                            "proposer_address": "2DD9F44FD9067555C322243C3C913BA7B51D2BE0",
+                            "time": "{}",
+                            "validators_hash": "{HASH}",
+                            "version": {{ "app": "1", "block": "11" }}
+                        }} }}"#,
+                        time.to_rfc3339()
+                    )
+                }
+                method => panic!("unexpected request: {method}"),
+            };
+
+            MockResponse::json(format!(
+                r#"{{ "jsonrpc": "2.0", "id": {}, "result": {result} }}"#,
+                body["id"]
+            ))
+        });
+
+        format!("http://{address}").parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn fees_spent_today_before_a_restart_count_against_the_budget() {
+        const TODAY: u64 = DAY * SECONDS_PER_DAY;
+
+        // The account spent 60 and 30 today, most recent first, and 1000 yesterday
+        let rpc_address = node(&[
+            (12, 60, TODAY + 120),
+            (11, 30, TODAY + 60),
+            (10, 1000, TODAY - 60),
+        ]);
+        let rpc_client = PacedRpcClient::new(HttpClient::new(rpc_address.clone()).unwrap(), None);
+
+        let budget = DailyFeeBudget::new(ChainId::from_string("budget-0"), &gas_prices(), 100);
+        let packets = [msg(recv_packet::TYPE_URL)];
+
+        budget
+            .load_account_on(DAY, &rpc_client, &rpc_address, "cosmos1relayer")
+            .await
+            .unwrap();
+
+        assert_eq!(budget.lock().on(DAY), 90);
+        assert!(budget.check_on(DAY, &packets, &fee("stake", 10)).is_ok());
+        assert!(budget.check_on(DAY, &packets, &fee("stake", 11)).is_err());
+
+        // The fees of an account are only loaded once, the later ones being recorded
+        budget.record_on(DAY, &fee("stake", 10));
+        budget
+            .load_account_on(DAY, &rpc_client, &rpc_address, "cosmos1relayer")
+            .await
+            .unwrap();
+
+        assert_eq!(budget.lock().on(DAY), 100);
+    }
+
+    #[tokio::test]
+    async fn fees_paid_in_a_fallback_denom_count_against_the_budget() {
+        let chain_id = ChainId::from_string("budget-0");
+        let budget = DailyFeeBudget::new(chain_id.clone(), &gas_prices(), 100);
+        let packets = [msg(recv_packet::TYPE_URL)];
+
+        // The account has no `stake` left, so the fees are paid in `uosmo`
+        let candidates: Vec<_> = gas_prices()
+            .iter()
+            .map(|gas_price| (gas_price.clone(), 100))
+            .collect();
+
+        let (gas_price, _) = select_gas_price(
+            &chain_id,
+            "cosmos1relayer",
+            &candidates,
+            &FeeDenomBalances::default(),
+            |denom| async move { Ok(if denom == "uosmo" { 1000 } else { 0 }) },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(gas_price.denom, "uosmo");
+
+        let fee_of = |gas: u64| Fee {
+            amount: vec![calculate_fee(gas, gas_price)],
+            gas_limit: gas,
+            ..Fee::default()
+        };
+
+        // 500 gas cost 100uosmo, ie. 50stake
+        budget.record_on(DAY, &fee_of(500));
+        assert_eq!(budget.lock().on(DAY), 50);
+        assert!(budget.check_on(DAY, &packets, &fee_of(500)).is_ok());
+
+        // The relay halts once the fees paid in `uosmo` spent the budget
+        budget.record_on(DAY, &fee_of(500));
+        match budget
+            .check_on(DAY, &packets, &fee_of(5))
+            .unwrap_err()
+            .detail()
+        {
+            ErrorDetail::DailyFeeBudgetExhausted(e) => {
+                assert_eq!((e.spent, e.fee), (100, 1));
+            }
+            e => panic!("unexpected error: {e}"),
+        }
+    }
+}
@@ -0,0 +1,592 @@
+//! Preflight check of the balance of the fee denomination of the relayer account, which
+//! runs a configured command to acquire the fee denomination, eg. by swapping other tokens,
+//! when the balance does not cover the maximum fee of a transaction, and selection of
+//! the gas price to pay the fees at among the ones configured, by the balance of their
+//! denomination.
+//!
+//! The balances are cached per account, and debited by the maximum fee of each transaction,
+//! so that they are only queried again once they no longer cover the maximum fee. The maximum
+//! fees of the configured gas prices are computed again at most once per block.
+
+use core::future::Future;
+use core::time::Duration;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use tracing::{debug, info, warn};
+
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use crate::chain::cosmos::types::gas::GasConfig;
+use crate::config::GasPrice;
+use crate::error::Error;
+use crate::util::command::run_command_async;
+
+/// How long the acquire fee denom command may run before it is killed.
+const ACQUIRE_FEE_DENOM_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The balances of the fee denominations of the relayer accounts on a chain, as last queried
+/// and since debited by the maximum fee of each transaction.
+#[derive(Debug, Default)]
+pub struct FeeDenomBalances {
+    balances: Mutex<HashMap<(String, String), u128>>,
+}
+
+impl FeeDenomBalances {
+    /// The balance of the given account in the given denomination, as cached if it covers
+    /// the `required` amount, and otherwise as queried anew with `query_balance`.
+    pub async fn balance<Fut>(
+        &self,
+        address: &str,
+        denom: &str,
+        required: u128,
+        query_balance: impl FnOnce() -> Fut,
+    ) -> Result<u128, Error>
+    where
+        Fut: Future<Output = Result<u128, Error>>,
+    {
+        let cached = self
+            .lock()
+            .get(&(address.to_string(), denom.to_string()))
+            .copied();
+
+        match cached {
+            Some(balance) if balance >= required => Ok(balance),
+            _ => self.refresh(address, denom, query_balance).await,
+        }
+    }
+
+    /// Queries the balance of the given account in the given denomination anew.
+    pub async fn refresh<Fut>(
+        &self,
+        address: &str,
+        denom: &str,
+        query_balance: impl FnOnce() -> Fut,
+    ) -> Result<u128, Error>
+    where
+        Fut: Future<Output = Result<u128, Error>>,
+    {
+        let balance = query_balance().await?;
+
+        self.lock()
+            .insert((address.to_string(), denom.to_string()), balance);
+
+        Ok(balance)
+    }
+
+    /// Debits the cached balance of the given account in the given denomination by the
+    /// maximum fee of a transaction about to be submitted.
+    pub fn spend(&self, address: &str, denom: &str, max_fee: u128) {
+        if let Some(balance) = self
+            .lock()
+            .get_mut(&(address.to_string(), denom.to_string()))
+        {
+            *balance = balance.saturating_sub(max_fee);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<(String, String), u128>> {
+        self.balances.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// The gas configurations paying the fees at each of the gas prices configured for a chain,
+/// in order of preference, along with the maximum fee of a transaction paid at each of them,
+/// as last computed.
+#[derive(Debug)]
+pub struct FeeDenomCandidates {
+    gas_configs: Vec<GasConfig>,
+    refresh_interval: Duration,
+    max_fees: Mutex<Option<(Instant, Vec<u128>)>>,
+}
+
+impl FeeDenomCandidates {
+    /// The candidates paying the fees at the given gas prices, whose maximum fees are computed
+    /// again at most once per `refresh_interval`, ie. the maximum block time of the chain.
+    pub fn new<'a>(
+        gas_config: &GasConfig,
+        gas_prices: impl IntoIterator<Item = &'a GasPrice>,
+        refresh_interval: Duration,
+    ) -> Self {
+        Self {
+            gas_configs: gas_prices
+                .into_iter()
+                .map(|gas_price| gas_config.with_gas_price(gas_price.clone()))
+                .collect(),
+            refresh_interval,
+            max_fees: Mutex::new(None),
+        }
+    }
+
+    /// The gas configuration paying the fees at the given gas price, if it is a candidate.
+    pub fn gas_config(&self, gas_price: &GasPrice) -> Option<&GasConfig> {
+        self.gas_configs
+            .iter()
+            .find(|gas_config| &gas_config.gas_price == gas_price)
+    }
+
+    /// The gas prices of the candidates along with the maximum fee of a transaction paid
+    /// at each of them, as computed with `max_fee` at most `refresh_interval` ago.
+    pub async fn max_fees<Fut>(&self, max_fee: impl Fn(GasConfig) -> Fut) -> Vec<(GasPrice, u128)>
+    where
+        Fut: Future<Output = u128>,
+    {
+        let cached = self
+            .lock()
+            .as_ref()
+            .filter(|(computed_at, _)| computed_at.elapsed() < self.refresh_interval)
+            .map(|(_, max_fees)| max_fees.clone());
+
+        let max_fees = match cached {
+            Some(max_fees) => max_fees,
+            None => {
+                let mut max_fees = Vec::with_capacity(self.gas_configs.len());
+                for gas_config in &self.gas_configs {
+                    max_fees.push(max_fee(gas_config.clone()).await);
+                }
+
+                *self.lock() = Some((Instant::now(), max_fees.clone()));
+                max_fees
+            }
+        };
+
+        self.gas_configs
+            .iter()
+            .map(|gas_config| gas_config.gas_price.clone())
+            .zip(max_fees)
+            .collect()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<(Instant, Vec<u128>)>> {
+        self.max_fees.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Checks that the balance of the fee denomination of the account with the given address,
+/// cached in `balances` or obtained from `query_balance`, covers the `required` amount, and
+/// otherwise runs the given acquire fee denom command, with the denomination and the address
+/// appended to its arguments, before querying it again.
+///
+/// Fails if the balance still does not cover the required amount, so that the messages
+/// are not submitted and are retried later on, instead of failing for lack of funds.
+pub async fn ensure_fee_denom_balance<F, Fut>(
+    chain_id: &ChainId,
+    address: &str,
+    denom: &str,
+    required: u128,
+    acquire_command: &[String],
+    balances: &FeeDenomBalances,
+    query_balance: F,
+) -> Result<(), Error>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<u128, Error>>,
+{
+    let balance = balances
+        .balance(address, denom, required, &query_balance)
+        .await?;
+
+    if balance >= required {
+        return Ok(());
+    }
+
+    warn!(
+        chain = %chain_id,
+        %address,
+        %denom,
+        balance,
+        required,
+        "balance of the fee denomination does not cover the maximum fee, \
+        running the acquire fee denom command"
+    );
+
+    let acquired = run_command_async(
+        acquire_command.to_vec(),
+        vec![denom.to_string(), address.to_string()],
+        None,
+        ACQUIRE_FEE_DENOM_TIMEOUT,
+    )
+    .await;
+
+    if let Err(e) = acquired {
+        warn!(chain = %chain_id, "failed to acquire the fee denomination: {e}");
+    }
+
+    let balance = balances.refresh(address, denom, query_balance).await?;
+
+    if balance < required {
+        return Err(Error::insufficient_fee_denom_balance(
+            chain_id.clone(),
+            address.to_string(),
+            denom.to_string(),
+            balance,
+            required,
+        ));
+    }
+
+    info!(chain = %chain_id, %denom, balance, "acquired the fee denomination");
+
+    Ok(())
+}
+
+/// Selects the first of the given gas prices, in order of preference, for which the
+/// balance of its denomination of the account with the given address, cached in `balances`
+/// or obtained from `query_balance`, covers the maximum fee of a transaction paid at that
+/// gas price.
+///
+/// Falls back to the preferred gas price if a balance cannot be queried, and fails with
+/// an error naming all the checked denominations if none of the balances covers the
+/// maximum fee.
+pub async fn select_gas_price<'a, F, Fut>(
+    chain_id: &ChainId,
+    address: &str,
+    candidates: &'a [(GasPrice, u128)],
+    balances: &FeeDenomBalances,
+    query_balance: F,
+) -> Result<&'a (GasPrice, u128), Error>
+where
+    F: Fn(&'a str) -> Fut,
+    Fut: Future<Output = Result<u128, Error>>,
+{
+    for candidate @ (gas_price, required) in candidates {
+        let denom = &gas_price.denom;
+
+        let balance = match balances
+            .balance(address, denom, *required, || query_balance(denom))
+            .await
+        {
+            Ok(balance) => balance,
+            Err(e) => {
+                warn!(
+                    chain = %chain_id,
+                    %address,
+                    %denom,
+                    "failed to query the balance of the fee denomination, \
+                    paying the fees in the preferred one: {e}"
+                );
+
+                return Ok(&candidates[0]);
+            }
+        };
+
+        if balance >= *required {
+            return Ok(candidate);
+        }
+
+        debug!(
+            chain = %chain_id,
+            %address,
+            denom = %gas_price.denom,
+            balance,
+            required,
+            "balance of the fee denomination does not cover the maximum fee, trying the next one"
+        );
+    }
+
+    Err(Error::insufficient_fee_denoms_balance(
+        chain_id.clone(),
+        address.to_string(),
+        candidates
+            .iter()
+            .map(|(gas_price, _)| gas_price.denom.clone())
+            .collect(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+    use std::sync::Mutex;
+
+    use super::*;
+
+    use crate::chain::cosmos::batch::test_fixtures::example_tx_config;
+    use crate::error::ErrorDetail;
+
+    /// An account whose balance is kept in a file, and an acquire fee denom command
+    /// which records the denominations it is run for in another file and credits the
+    /// account with a given amount.
+    struct Account {
+        balance_path: PathBuf,
+        acquired_path: PathBuf,
+    }
+
+    impl Account {
+        fn new(name: &str, balance: u128) -> Self {
+            let path = |file: &str| {
+                std::env::temp_dir().join(format!(
+                    "hermes-fee-denom-{}-{name}-{file}",
+                    std::process::id()
+                ))
+            };
+
+            let account = Self {
+                balance_path: path("balance"),
+                acquired_path: path("acquired"),
+            };
+
+            fs::write(&account.balance_path, balance.to_string()).unwrap();
+            let _ = fs::remove_file(&account.acquired_path);
+
+            account
+        }
+
+        fn acquire_command(&self, credit: u128) -> Vec<String> {
+            let script = format!(
+                r#"test "$2" = cosmos1relayer && echo "$1" >> {acquired} \
+                && echo $(( $(cat {balance}) + {credit} )) > {balance}"#,
+                acquired = self.acquired_path.display(),
+                balance = self.balance_path.display(),
+            );
+
+            ["sh", "-c", &script, "sh"].map(String::from).to_vec()
+        }
+
+        fn balance(&self) -> u128 {
+            fs::read_to_string(&self.balance_path)
+                .unwrap()
+                .trim()
+                .parse()
+                .unwrap()
+        }
+
+        fn acquired(&self) -> Vec<String> {
+            fs::read_to_string(&self.acquired_path)
+                .unwrap_or_default()
+                .lines()
+                .map(String::from)
+                .collect()
+        }
+
+        async fn ensure(&self, credit: u128) -> Result<(), Error> {
+            ensure_fee_denom_balance(
+                &ChainId::from_string("ibc-0"),
+                "cosmos1relayer",
+                "uosmo",
+                1000,
+                &self.acquire_command(credit),
+                &FeeDenomBalances::default(),
+                || async { Ok(self.balance()) },
+            )
+            .await
+        }
+    }
+
+    impl Drop for Account {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.balance_path);
+            let _ = fs::remove_file(&self.acquired_path);
+        }
+    }
+
+    #[tokio::test]
+    async fn command_is_not_run_when_balance_covers_fee() {
+        let account = Account::new("covered", 1000);
+
+        account.ensure(5000).await.unwrap();
+
+        assert!(account.acquired().is_empty());
+        assert_eq!(account.balance(), 1000);
+    }
+
+    #[tokio::test]
+    async fn command_is_run_when_fee_denom_is_missing() {
+        let account = Account::new("missing", 0);
+
+        account.ensure(5000).await.unwrap();
+
+        assert_eq!(account.acquired(), vec!["uosmo".to_string()]);
+        assert_eq!(account.balance(), 5000);
+    }
+
+    #[tokio::test]
+    async fn messages_are_deferred_when_command_does_not_acquire_fee_denom() {
+        let account = Account::new("deferred", 0);
+
+        let result = account.ensure(0).await;
+
+        // The command is run before giving up on submitting the messages
+        assert_eq!(account.acquired(), vec!["uosmo".to_string()]);
+        match result.unwrap_err().detail() {
+            ErrorDetail::InsufficientFeeDenomBalance(e) => assert_eq!(e.balance, 0),
+            e => panic!("unexpected error: {e}"),
+        }
+    }
+
+    /// Gas prices in `uatom` then `uosmo`, with a maximum fee of 1000 in either.
+    fn gas_prices() -> Vec<(GasPrice, u128)> {
+        vec![
+            (GasPrice::new(0.01, "uatom".to_string()), 1000),
+            (GasPrice::new(0.01, "uosmo".to_string()), 1000),
+        ]
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_next_fee_denom_when_first_is_empty() {
+        let candidates = gas_prices();
+        let queried = Mutex::new(Vec::new());
+
+        let (gas_price, _) = select_gas_price(
+            &ChainId::from_string("ibc-0"),
+            "cosmos1relayer",
+            &candidates,
+            &FeeDenomBalances::default(),
+            |denom| {
+                queried.lock().unwrap().push(denom.to_string());
+                async move { Ok(if denom == "uosmo" { 5000 } else { 0 }) }
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(gas_price.denom, "uosmo");
+        assert_eq!(*queried.lock().unwrap(), vec!["uatom", "uosmo"]);
+    }
+
+    #[tokio::test]
+    async fn preferred_fee_denom_is_selected_when_balance_covers_fee() {
+        let candidates = gas_prices();
+
+        let (gas_price, _) = select_gas_price(
+            &ChainId::from_string("ibc-0"),
+            "cosmos1relayer",
+            &candidates,
+            &FeeDenomBalances::default(),
+            |_| async { Ok(1000) },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(gas_price.denom, "uatom");
+    }
+
+    #[tokio::test]
+    async fn error_names_all_checked_fee_denoms() {
+        let candidates = gas_prices();
+
+        let error = select_gas_price(
+            &ChainId::from_string("ibc-0"),
+            "cosmos1relayer",
+            &candidates,
+            &FeeDenomBalances::default(),
+            |_| async { Ok(999) },
+        )
+        .await
+        .unwrap_err();
+
+        match error.detail() {
+            ErrorDetail::InsufficientFeeDenomsBalance(e) => {
+                assert_eq!(e.denoms, vec!["uatom", "uosmo"]);
+            }
+            _ => panic!("unexpected error: {error}"),
+        }
+        assert!(error.to_string().contains("[uatom, uosmo]"));
+    }
+
+    #[tokio::test]
+    async fn preferred_fee_denom_is_selected_when_a_balance_cannot_be_queried() {
+        let candidates = gas_prices();
+
+        let (gas_price, _) = select_gas_price(
+            &ChainId::from_string("ibc-0"),
+            "cosmos1relayer",
+            &candidates,
+            &FeeDenomBalances::default(),
+            |denom| async move {
+                match denom {
+                    "uatom" => Ok(0),
+                    _ => Err(Error::query("balance".to_string())),
+                }
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(gas_price.denom, "uatom");
+    }
+
+    #[tokio::test]
+    async fn balances_are_queried_again_once_they_no_longer_cover_the_fee() {
+        let candidates = gas_prices();
+        let balances = FeeDenomBalances::default();
+        let account = Account::new("cached", 2500);
+        let queried = Mutex::new(0);
+
+        let query_balance = |_| {
+            *queried.lock().unwrap() += 1;
+            async { Ok(account.balance()) }
+        };
+
+        // Each transaction spends the maximum fee of 1000
+        let mut selected = vec![];
+        for _ in 0..3 {
+            let (gas_price, _) = select_gas_price(
+                &ChainId::from_string("ibc-0"),
+                "cosmos1relayer",
+                &candidates,
+                &balances,
+                query_balance,
+            )
+            .await
+            .unwrap();
+
+            // The balance is not queried again before submitting the transaction
+            ensure_fee_denom_balance(
+                &ChainId::from_string("ibc-0"),
+                "cosmos1relayer",
+                &gas_price.denom,
+                1000,
+                &account.acquire_command(0),
+                &balances,
+                || query_balance(&gas_price.denom),
+            )
+            .await
+            .unwrap();
+
+            balances.spend("cosmos1relayer", &gas_price.denom, 1000);
+            selected.push((gas_price.denom.clone(), *queried.lock().unwrap()));
+        }
+
+        // The balance is only queried again once the cached one no longer covers the fee,
+        // the account spending less than the maximum fee of each transaction
+        assert_eq!(
+            selected,
+            [("uatom", 1), ("uatom", 1), ("uatom", 2)]
+                .map(|(denom, queries)| (denom.to_string(), queries))
+        );
+        assert!(account.acquired().is_empty());
+    }
+
+    #[tokio::test]
+    async fn max_fees_are_computed_at_most_once_per_refresh_interval() {
+        let gas_config = example_tx_config().gas_config;
+        let prices: Vec<_> = gas_prices().into_iter().map(|(price, _)| price).collect();
+        let computed = Mutex::new(Vec::new());
+
+        let max_fee = |gas_config: GasConfig| {
+            computed
+                .lock()
+                .unwrap()
+                .push(gas_config.gas_price.denom.clone());
+            async { 1000 }
+        };
+
+        let cached = FeeDenomCandidates::new(&gas_config, &prices, Duration::from_secs(60));
+        assert_eq!(cached.max_fees(max_fee).await, gas_prices());
+        assert_eq!(cached.max_fees(max_fee).await, gas_prices());
+        assert_eq!(*computed.lock().unwrap(), vec!["uatom", "uosmo"]);
+        assert_eq!(cached.gas_config(&prices[1]).unwrap().gas_price, prices[1]);
+
+        computed.lock().unwrap().clear();
+
+        let expired = FeeDenomCandidates::new(&gas_config, &prices, Duration::ZERO);
+        expired.max_fees(max_fee).await;
+        expired.max_fees(max_fee).await;
+        assert_eq!(
+            *computed.lock().unwrap(),
+            vec!["uatom", "uosmo", "uatom", "uosmo"]
+        );
+    }
+}
@@ -0,0 +1,368 @@
+use std::collections::BTreeMap;
+
+use ibc_proto::cosmos::tx::v1beta1::{AuthInfo, Fee, TxBody, TxRaw};
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::core::channel::v1::{
+    MsgAcknowledgement, MsgRecvPacket, MsgTimeout, MsgTimeoutOnClose, Packet,
+};
+use ibc_relayer_types::core::ics04_channel::msgs::{
+    acknowledgement, recv_packet, timeout, timeout_on_close,
+};
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use itertools::Itertools;
+use prost::Message;
+use serde::Serialize;
+use tendermint_rpc::endpoint::tx::Response as TxResponse;
+use tracing::{debug, warn};
+
+use crate::error::Error;
+
+/// Label used for the messages which cannot be attributed to a single channel.
+pub const NO_CHANNEL: &str = "none";
+
+/// The share of a transaction fee spent on a single message of the transaction.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct FeeSpent {
+    /// The channel, on the chain which paid the fee, the message was relayed on.
+    pub channel: String,
+    /// The type URL of the message.
+    pub msg_type: String,
+    pub denom: String,
+    pub amount: u128,
+}
+
+/// Records the fees spent by a confirmed transaction in the
+/// `relay_fees_spent` telemetry counter.
+pub fn record_fees_spent(chain_id: &ChainId, response: &TxResponse) {
+    let fees_spent = match fees_spent_from_tx(&response.tx) {
+        Ok(fees_spent) => fees_spent,
+        Err(e) => {
+            debug!(
+                "failed to decode fees spent by tx {} on chain {chain_id}: {e}",
+                response.hash
+            );
+            return;
+        }
+    };
+
+    for fee_spent in fees_spent {
+        crate::telemetry!(
+            fees_spent,
+            chain_id,
+            &fee_spent.channel,
+            &fee_spent.msg_type,
+            &fee_spent.denom,
+            u64::try_from(fee_spent.amount).unwrap_or(u64::MAX),
+        );
+    }
+}
+
+/// Decodes the given raw transaction and splits its fee over its messages.
+pub fn fees_spent_from_tx(tx_bytes: &[u8]) -> Result<Vec<FeeSpent>, Error> {
+    let tx_raw =
+        TxRaw::decode(tx_bytes).map_err(|e| Error::protobuf_decode("TxRaw".to_string(), e))?;
+
+    let body = TxBody::decode(tx_raw.body_bytes.as_slice())
+        .map_err(|e| Error::protobuf_decode("TxBody".to_string(), e))?;
+
+    let auth_info = AuthInfo::decode(tx_raw.auth_info_bytes.as_slice())
+        .map_err(|e| Error::protobuf_decode("AuthInfo".to_string(), e))?;
+
+    Ok(split_fee(
+        &auth_info.fee.unwrap_or_default(),
+        &body.messages,
+    ))
+}
+
+/// Decodes the fees spent by the given transactions, skipping the ones which cannot be
+/// decoded, eg. the transactions of another kind sent from the same account.
+pub fn fees_spent_from_txs<'a>(txs: impl IntoIterator<Item = &'a TxResponse>) -> Vec<FeeSpent> {
+    txs.into_iter()
+        .flat_map(|tx| {
+            fees_spent_from_tx(&tx.tx).unwrap_or_else(|e| {
+                warn!("skipping the fees spent by tx {}: {e}", tx.hash);
+                vec![]
+            })
+        })
+        .collect()
+}
+
+/// Splits the given fee evenly over the given messages, for each denom of the fee.
+/// The remainder of the division is attributed to the first message.
+///
+/// Packet messages are attributed to the channel, on the chain which paid the fee,
+/// of their packet. Any other message, eg. a client update, is attributed to the
+/// channel of the packet messages of the transaction if they all share the same
+/// channel, and to [`NO_CHANNEL`] otherwise.
+pub fn split_fee(fee: &Fee, messages: &[Any]) -> Vec<FeeSpent> {
+    if messages.is_empty() {
+        return vec![];
+    }
+
+    let channels: Vec<Option<String>> = messages.iter().map(packet_msg_channel).collect();
+
+    let tx_channel = channels
+        .iter()
+        .flatten()
+        .unique()
+        .exactly_one()
+        .map_or_else(|_| NO_CHANNEL.to_string(), Clone::clone);
+
+    let count = messages.len() as u128;
+
+    let mut fees_spent = Vec::with_capacity(messages.len() * fee.amount.len());
+
+    for coin in &fee.amount {
+        let amount = match coin.amount.parse::<u128>() {
+            Ok(amount) => amount,
+            Err(e) => {
+                debug!("ignoring fee amount {} {}: {e}", coin.amount, coin.denom);
+                continue;
+            }
+        };
+
+        let share = amount / count;
+        let remainder = amount % count;
+
+        for (i, (message, channel)) in messages.iter().zip(&channels).enumerate() {
+            fees_spent.push(FeeSpent {
+                channel: channel.clone().unwrap_or_else(|| tx_channel.clone()),
+                msg_type: message.type_url.clone(),
+                denom: coin.denom.clone(),
+                amount: if i == 0 { share + remainder } else { share },
+            });
+        }
+    }
+
+    fees_spent
+}
+
+/// Sums up the given fees per channel, message type and denom.
+pub fn total_fees_spent(fees_spent: impl IntoIterator<Item = FeeSpent>) -> Vec<FeeSpent> {
+    let mut totals: BTreeMap<(String, String, String), u128> = BTreeMap::new();
+
+    for fee_spent in fees_spent {
+        *totals
+            .entry((fee_spent.channel, fee_spent.msg_type, fee_spent.denom))
+            .or_default() += fee_spent.amount;
+    }
+
+    totals
+        .into_iter()
+        .map(|((channel, msg_type, denom), amount)| FeeSpent {
+            channel,
+            msg_type,
+            denom,
+            amount,
+        })
+        .collect()
+}
+
+/// The channel, on the chain the message is submitted to, of the packet carried by the message.
+fn packet_msg_channel(message: &Any) -> Option<String> {
+    fn packet_of<M: Message + Default>(
+        message: &Any,
+        packet: impl FnOnce(M) -> Option<Packet>,
+    ) -> Option<Packet> {
+        M::decode(message.value.as_slice()).ok().and_then(packet)
+    }
+
+    match message.type_url.as_str() {
+        recv_packet::TYPE_URL => {
+            packet_of(message, |m: MsgRecvPacket| m.packet).map(|p| p.destination_channel)
+        }
+        acknowledgement::TYPE_URL => {
+            packet_of(message, |m: MsgAcknowledgement| m.packet).map(|p| p.source_channel)
+        }
+        timeout::TYPE_URL => packet_of(message, |m: MsgTimeout| m.packet).map(|p| p.source_channel),
+        timeout_on_close::TYPE_URL => {
+            packet_of(message, |m: MsgTimeoutOnClose| m.packet).map(|p| p.source_channel)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_proto::cosmos::base::v1beta1::Coin;
+
+    const UPDATE_CLIENT_TYPE_URL: &str = "/ibc.core.client.v1.MsgUpdateClient";
+
+    fn packet(source_channel: &str, destination_channel: &str) -> Packet {
+        Packet {
+            sequence: 1,
+            source_port: "transfer".to_string(),
+            source_channel: source_channel.to_string(),
+            destination_port: "transfer".to_string(),
+            destination_channel: destination_channel.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn recv_packet_msg(destination_channel: &str) -> Any {
+        let msg = MsgRecvPacket {
+            packet: Some(packet("channel-99", destination_channel)),
+            ..Default::default()
+        };
+
+        Any {
+            type_url: recv_packet::TYPE_URL.to_string(),
+            value: msg.encode_to_vec(),
+        }
+    }
+
+    fn ack_msg(source_channel: &str) -> Any {
+        let msg = MsgAcknowledgement {
+            packet: Some(packet(source_channel, "channel-99")),
+            ..Default::default()
+        };
+
+        Any {
+            type_url: acknowledgement::TYPE_URL.to_string(),
+            value: msg.encode_to_vec(),
+        }
+    }
+
+    fn update_client_msg() -> Any {
+        Any {
+            type_url: UPDATE_CLIENT_TYPE_URL.to_string(),
+            value: vec![],
+        }
+    }
+
+    fn fee(amount: &str) -> Fee {
+        Fee {
+            amount: vec![Coin {
+                denom: "stake".to_string(),
+                amount: amount.to_string(),
+            }],
+            gas_limit: 300000,
+            ..Default::default()
+        }
+    }
+
+    fn fee_spent(channel: &str, msg_type: &str, amount: u128) -> FeeSpent {
+        FeeSpent {
+            channel: channel.to_string(),
+            msg_type: msg_type.to_string(),
+            denom: "stake".to_string(),
+            amount,
+        }
+    }
+
+    #[test]
+    fn fee_is_split_over_the_messages_of_the_tx() {
+        let messages = vec![
+            update_client_msg(),
+            recv_packet_msg("channel-0"),
+            recv_packet_msg("channel-0"),
+        ];
+
+        let fees_spent = split_fee(&fee("1000"), &messages);
+
+        assert_eq!(
+            fees_spent,
+            vec![
+                fee_spent("channel-0", UPDATE_CLIENT_TYPE_URL, 334),
+                fee_spent("channel-0", recv_packet::TYPE_URL, 333),
+                fee_spent("channel-0", recv_packet::TYPE_URL, 333),
+            ]
+        );
+
+        let total: u128 = fees_spent.iter().map(|f| f.amount).sum();
+        assert_eq!(total, 1000);
+    }
+
+    #[test]
+    fn client_updates_of_multi_channel_txs_have_no_channel() {
+        let messages = vec![
+            update_client_msg(),
+            ack_msg("channel-1"),
+            recv_packet_msg("channel-2"),
+        ];
+
+        assert_eq!(
+            split_fee(&fee("30"), &messages),
+            vec![
+                fee_spent(NO_CHANNEL, UPDATE_CLIENT_TYPE_URL, 10),
+                fee_spent("channel-1", acknowledgement::TYPE_URL, 10),
+                fee_spent("channel-2", recv_packet::TYPE_URL, 10),
+            ]
+        );
+    }
+
+    #[test]
+    fn fees_spent_are_summed_up_per_channel_and_msg_type() {
+        let fees_spent = split_fee(&fee("100"), &[ack_msg("channel-1"), ack_msg("channel-1")])
+            .into_iter()
+            .chain(split_fee(&fee("31"), &[recv_packet_msg("channel-1")]))
+            .chain(split_fee(&fee("7"), &[ack_msg("channel-1")]));
+
+        assert_eq!(
+            total_fees_spent(fees_spent),
+            vec![
+                fee_spent("channel-1", acknowledgement::TYPE_URL, 107),
+                fee_spent("channel-1", recv_packet::TYPE_URL, 31),
+            ]
+        );
+    }
+
+    fn tx_raw(messages: Vec<Any>, fee: Fee) -> Vec<u8> {
+        let body = TxBody {
+            messages,
+            ..Default::default()
+        };
+
+        let auth_info = AuthInfo {
+            fee: Some(fee),
+            ..Default::default()
+        };
+
+        TxRaw {
+            body_bytes: body.encode_to_vec(),
+            auth_info_bytes: auth_info.encode_to_vec(),
+            signatures: vec![],
+        }
+        .encode_to_vec()
+    }
+
+    #[test]
+    fn undecodable_txs_are_skipped() {
+        let tx_response = |tx: Vec<u8>| TxResponse {
+            hash: Default::default(),
+            height: 10_u32.into(),
+            index: 0,
+            tx_result: Default::default(),
+            tx,
+            proof: None,
+        };
+
+        let txs = [
+            tx_response(tx_raw(vec![ack_msg("channel-1")], fee("10"))),
+            tx_response(b"not a tx".to_vec()),
+            tx_response(tx_raw(vec![ack_msg("channel-1")], fee("5"))),
+        ];
+
+        assert_eq!(
+            total_fees_spent(fees_spent_from_txs(&txs)),
+            vec![fee_spent("channel-1", acknowledgement::TYPE_URL, 15)]
+        );
+    }
+
+    #[test]
+    fn fees_spent_are_decoded_from_the_broadcast_tx() {
+        let tx_raw = tx_raw(vec![update_client_msg(), ack_msg("channel-3")], fee("5001"));
+
+        let fees_spent = fees_spent_from_tx(&tx_raw).unwrap();
+
+        assert_eq!(
+            fees_spent,
+            vec![
+                fee_spent("channel-3", UPDATE_CLIENT_TYPE_URL, 2501),
+                fee_spent("channel-3", acknowledgement::TYPE_URL, 2500),
+            ]
+        );
+    }
+}
@@ -1,14 +1,23 @@
 use core::cmp::min;
+use core::str::FromStr;
+use std::sync::{Arc, Mutex};
+
 use ibc_proto::cosmos::base::v1beta1::Coin;
 use ibc_proto::cosmos::tx::v1beta1::Fee;
+use ibc_proto::google::protobuf::Any;
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use num_bigint::BigInt;
 use num_rational::BigRational;
 use tendermint_rpc::Url;
-use tracing::warn;
+use tracing::{debug, warn};
 
+use crate::chain::cosmos::request_rate::RequestRateLimiter;
 use crate::chain::cosmos::types::gas::GasConfig;
+use crate::config::dynamic_gas::DynamicGasPrice;
+use crate::config::gas_sampling::GasEstimationSampling;
+use crate::config::shadow_gas::ShadowGasStrategy;
 use crate::config::GasPrice;
+use crate::error::Error;
 use crate::telemetry;
 
 use super::eip_base_fee::query_eip_base_fee;
@@ -18,7 +27,110 @@ pub async fn gas_amount_to_fee(
     gas_amount: u64,
     chain_id: &ChainId,
     rpc_address: &Url,
+    limiter: Option<&Arc<RequestRateLimiter>>,
 ) -> Fee {
+    let base_fee = if uses_eip_base_fee(config) {
+        Some(query_eip_base_fee(rpc_address, limiter, &config.gas_price.denom, chain_id).await)
+    } else {
+        None
+    };
+
+    // The base fee queried for the active strategy is reused by the shadow strategy
+    let queried_base_fee = match &base_fee {
+        Some(Ok(base_fee)) => Some((config.gas_price.denom.as_str(), *base_fee)),
+        _ => None,
+    };
+
+    let gas_price = match (&config.txfees_gas_price, base_fee) {
+        (Some(txfees), _) => {
+            txfees
+                .gas_price(rpc_address, limiter, &config.gas_price)
+                .await
+        }
+        (None, Some(base_fee)) => dynamic_gas_price_at_base_fee(config, chain_id, base_fee),
+        (None, None) => config.gas_price.clone(),
+    };
+    let gas_price = scaled_gas_price(config, gas_price).await;
+    let fee = fee_at_gas_price(config, gas_amount, gas_price);
+
+    if let Some(strategy) = &config.shadow_gas_strategy {
+        let shadow_config = shadow_gas_config(config, strategy);
+        let shadow_gas_price = shadow_gas_price(
+            &shadow_config,
+            chain_id,
+            rpc_address,
+            limiter,
+            queried_base_fee,
+        )
+        .await;
+        let shadow_gas_price = scaled_gas_price(&shadow_config, shadow_gas_price).await;
+        let shadow_fee = fee_at_gas_price(&shadow_config, gas_amount, shadow_gas_price);
+
+        record_shadow_gas_delta(chain_id, &fee, &shadow_fee);
+    }
+
+    fee
+}
+
+/// The largest fee at which a transaction may be paid, in the denomination of the gas price:
+/// at the maximum gas, and at the gas price derived from the `txfees` module or at the maximum
+/// dynamic gas price if configured, scaled to the reference price of its denomination and
+/// raised for the priority of the transactions if configured.
+pub async fn max_fee_amount(
+    config: &GasConfig,
+    rpc_address: &Url,
+    limiter: Option<&Arc<RequestRateLimiter>>,
+) -> u128 {
+    let gas_price = match &config.txfees_gas_price {
+        Some(txfees) => {
+            txfees
+                .gas_price(rpc_address, limiter, &config.gas_price)
+                .await
+        }
+        None if config.dynamic_gas_price.enabled => GasPrice::new(
+            config.dynamic_gas_price.max.max(config.gas_price.price),
+            config.gas_price.denom.clone(),
+        ),
+        None => config.gas_price.clone(),
+    };
+    let gas_price = scaled_gas_price(config, gas_price).await;
+
+    // The priority of the transactions does not raise the gas price past the maximum
+    // dynamic gas price, which it is already at
+    let gas_price = if config.dynamic_gas_price.enabled {
+        gas_price
+    } else {
+        prioritized_gas_price(config, gas_price)
+    };
+    let gas_price = round_up_gas_price(gas_price, config.gas_price_decimals);
+
+    let amount = calculate_fee(config.max_gas, &gas_price)
+        .amount
+        .parse::<u128>()
+        .unwrap_or(u128::MAX);
+
+    match config.dynamic_gas_price.max_fee {
+        Some(max_fee) if config.dynamic_gas_price.enabled => max_fee
+            .to_string()
+            .parse::<u128>()
+            .map_or(amount, |max_fee| amount.min(max_fee)),
+        _ => amount,
+    }
+}
+
+/// Scales the given gas price to the reference price of its denomination, if configured.
+async fn scaled_gas_price(config: &GasConfig, gas_price: GasPrice) -> GasPrice {
+    match &config.reference_gas_price {
+        Some(reference_gas_price) => reference_gas_price.scale(gas_price).await,
+        None => gas_price,
+    }
+}
+
+/// The fee of a transaction needing the given amount of gas, before adjustment,
+/// at the given gas price, either static, dynamic or derived from the spot price
+/// of its denomination in the `txfees` module, and already scaled to the reference
+/// price of its denomination if configured.
+fn fee_at_gas_price(config: &GasConfig, gas_amount: u64, gas_price: GasPrice) -> Fee {
     let adjusted_gas_limit = adjust_estimated_gas(AdjustGas {
         gas_multiplier: config.gas_multiplier,
         max_gas: config.max_gas,
@@ -26,8 +138,11 @@ pub async fn gas_amount_to_fee(
     });
 
     // The fee in coins based on gas amount
-    let dynamic_gas_price = dynamic_gas_price(config, chain_id, rpc_address).await;
-    let amount = calculate_fee(adjusted_gas_limit, &dynamic_gas_price);
+    let gas_price = round_up_gas_price(
+        prioritized_gas_price(config, gas_price),
+        config.gas_price_decimals,
+    );
+    let amount = calculate_fee(adjusted_gas_limit, &gas_price);
 
     Fee {
         amount: vec![amount],
@@ -37,57 +152,336 @@ pub async fn gas_amount_to_fee(
     }
 }
 
+/// Returns a copy of the given gas configuration with the settings of the given
+/// shadow gas strategy, to evaluate the fee it would use for the same transactions.
+pub fn shadow_gas_config(config: &GasConfig, strategy: &ShadowGasStrategy) -> GasConfig {
+    let mut shadow = config.clone();
+
+    if let Some(gas_multiplier) = strategy.gas_multiplier {
+        shadow.gas_multiplier = gas_multiplier.to_f64();
+    }
+    if let Some(gas_price) = &strategy.gas_price {
+        shadow.gas_price = gas_price.clone();
+    }
+    if let Some(dynamic_gas_price) = strategy.dynamic_gas_price {
+        shadow.dynamic_gas_price = dynamic_gas_price;
+    }
+    if let Some(tx_priority) = strategy.tx_priority {
+        shadow.tx_priority = tx_priority;
+    }
+    shadow.shadow_gas_strategy = None;
+
+    shadow
+}
+
+/// Whether the gas price follows the EIP base fee of the chain, queried with each fee.
+fn uses_eip_base_fee(config: &GasConfig) -> bool {
+    config.txfees_gas_price.is_none() && config.dynamic_gas_price.enabled
+}
+
+/// The gas price of the shadow gas strategy, computed the same way as [`dynamic_gas_price`]
+/// but without recording it to telemetry, which only reflects the active strategy.
+///
+/// The base fee queried for the active strategy in the given denomination, if any,
+/// is used rather than querying it again.
+async fn shadow_gas_price(
+    config: &GasConfig,
+    chain_id: &ChainId,
+    rpc_address: &Url,
+    limiter: Option<&Arc<RequestRateLimiter>>,
+    queried_base_fee: Option<(&str, f64)>,
+) -> GasPrice {
+    if let Some(txfees) = &config.txfees_gas_price {
+        return txfees
+            .gas_price(rpc_address, limiter, &config.gas_price)
+            .await;
+    }
+
+    if !config.dynamic_gas_price.enabled {
+        return config.gas_price.clone();
+    }
+
+    let base_fee = match queried_base_fee {
+        Some((denom, base_fee)) if denom == config.gas_price.denom => Ok(base_fee),
+        _ => query_eip_base_fee(rpc_address, limiter, &config.gas_price.denom, chain_id).await,
+    };
+
+    match base_fee {
+        Ok(base_fee) => GasPrice::new(
+            (base_fee * config.dynamic_gas_price.multiplier).min(config.dynamic_gas_price.max),
+            config.gas_price.denom.clone(),
+        ),
+        Err(_) => config.gas_price.clone(),
+    }
+}
+
+/// The difference between the gas limit and fee of a transaction under the
+/// shadow gas strategy and those under the active gas strategy.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShadowGasDelta {
+    pub gas_limit: i128,
+    pub fee_amount: i128,
+}
+
+impl ShadowGasDelta {
+    /// Returns the difference between the given fees, or `None` if they are
+    /// not paid in the same denomination, and therefore cannot be compared.
+    pub fn between(active: &Fee, shadow: &Fee) -> Option<Self> {
+        let (active_amount, shadow_amount) = match (active.amount.first(), shadow.amount.first()) {
+            (Some(active), Some(shadow)) if active.denom == shadow.denom => (
+                active.amount.parse::<i128>().ok()?,
+                shadow.amount.parse::<i128>().ok()?,
+            ),
+            _ => return None,
+        };
+
+        Some(Self {
+            gas_limit: i128::from(shadow.gas_limit) - i128::from(active.gas_limit),
+            fee_amount: shadow_amount - active_amount,
+        })
+    }
+}
+
+fn record_shadow_gas_delta(chain_id: &ChainId, active: &Fee, shadow: &Fee) {
+    let Some(delta) = ShadowGasDelta::between(active, shadow) else {
+        warn!(
+            %chain_id,
+            "cannot compare the fee of the shadow gas strategy with the active one, \
+            as they are not paid in the same denomination"
+        );
+        return;
+    };
+
+    debug!(
+        %chain_id,
+        gas_limit_delta = %delta.gas_limit,
+        fee_delta = %delta.fee_amount,
+        "evaluated shadow gas strategy"
+    );
+
+    telemetry!(
+        shadow_gas_delta,
+        chain_id,
+        delta.gas_limit as f64,
+        delta.fee_amount as f64
+    );
+}
+
 pub async fn dynamic_gas_price(
     config: &GasConfig,
     chain_id: &ChainId,
     rpc_address: &Url,
+    limiter: Option<&Arc<RequestRateLimiter>>,
 ) -> GasPrice {
     if config.dynamic_gas_price.enabled {
-        let dynamic_gas_price = query_eip_base_fee(rpc_address, &config.gas_price.denom, chain_id)
-            .await
-            .map(|base_fee| base_fee * config.dynamic_gas_price.multiplier)
-            .map(|new_price| GasPrice {
-                price: new_price,
-                denom: config.gas_price.denom.clone(),
-            });
-
-        let dynamic_gas_price = match dynamic_gas_price {
-            Ok(dynamic_gas_price) => {
-                telemetry!(
-                    dynamic_gas_queried_success_fees,
-                    chain_id,
-                    dynamic_gas_price.price
-                );
-
-                dynamic_gas_price
-            }
-            Err(e) => {
-                warn!("failed to query EIP base fee, will fallback to configured `gas_price`: {e}");
-                config.gas_price.clone()
+        let base_fee =
+            query_eip_base_fee(rpc_address, limiter, &config.gas_price.denom, chain_id).await;
+
+        dynamic_gas_price_at_base_fee(config, chain_id, base_fee)
+    } else {
+        config.gas_price.clone()
+    }
+}
+
+/// The dynamic gas price at the given result of the query of the EIP base fee, falling
+/// back to the configured `gas_price` if the query failed, and recorded to telemetry.
+fn dynamic_gas_price_at_base_fee(
+    config: &GasConfig,
+    chain_id: &ChainId,
+    base_fee: Result<f64, Error>,
+) -> GasPrice {
+    let dynamic_gas_price = base_fee
+        .map(|base_fee| match config.dynamic_gas_price.smoothing_window {
+            Some(window) => {
+                config
+                    .base_fee_history
+                    .smoothed_base_fee(&config.gas_price.denom, base_fee, window)
             }
+            None => base_fee,
+        })
+        .map(|base_fee| base_fee * config.dynamic_gas_price.multiplier)
+        .map(|new_price| GasPrice {
+            price: new_price,
+            denom: config.gas_price.denom.clone(),
+        });
+
+    let dynamic_gas_price = match dynamic_gas_price {
+        Ok(dynamic_gas_price) => {
+            telemetry!(
+                dynamic_gas_queried_success_fees,
+                chain_id,
+                dynamic_gas_price.price
+            );
+
+            dynamic_gas_price
+        }
+        Err(e) => {
+            warn!("failed to query EIP base fee, will fallback to configured `gas_price`: {e}");
+            config.gas_price.clone()
+        }
+    };
+
+    {
+        telemetry!(dynamic_gas_queried_fees, chain_id, dynamic_gas_price.price);
+        let _ = chain_id;
+    }
+
+    if dynamic_gas_price.price > config.dynamic_gas_price.max {
+        warn!(
+            "queried EIP gas price is higher than configured max gas price, \
+            will fallback to configured `max`. Queried: {}, maximum: {}",
+            dynamic_gas_price.price, config.dynamic_gas_price.max
+        );
+
+        return GasPrice::new(config.dynamic_gas_price.max, dynamic_gas_price.denom);
+    }
+
+    telemetry!(dynamic_gas_paid_fees, chain_id, dynamic_gas_price.price);
+
+    dynamic_gas_price
+}
+
+/// Clamps every coin of the fee of a transaction to the `max_fee` of the dynamic gas price,
+/// if enabled.
+///
+/// The fee is computed at the queried base fee raised by the `multiplier` of the dynamic
+/// gas price, and by the `tip_multiplier` of `tx_priority` if enabled. If a clamped coin
+/// is lower than the fee at the base fee itself, the chain would most likely reject the
+/// transaction, which fails so that it is skipped rather than submitted.
+pub fn cap_dynamic_fee(config: &GasConfig, chain_id: &ChainId, mut fee: Fee) -> Result<Fee, Error> {
+    let max_fee = match config.dynamic_gas_price.max_fee {
+        Some(max_fee) if config.dynamic_gas_price.enabled => max_fee,
+        _ => return Ok(fee),
+    };
+
+    let Ok(max_amount) = BigInt::from_str(&max_fee.to_string()) else {
+        return Ok(fee);
+    };
+
+    let mut raise = config.dynamic_gas_price.multiplier;
+    if config.tx_priority.enabled {
+        raise *= config.tx_priority.tip_multiplier;
+    }
+
+    // Check every coin before clamping any, so that the fee is left as is on error
+    let mut capped = Vec::new();
+    for (index, coin) in fee.amount.iter().enumerate() {
+        let Ok(amount) = BigInt::from_str(&coin.amount) else {
+            continue;
         };
 
-        {
-            telemetry!(dynamic_gas_queried_fees, chain_id, dynamic_gas_price.price);
-            let _ = chain_id;
+        if amount <= max_amount {
+            continue;
         }
 
-        if dynamic_gas_price.price > config.dynamic_gas_price.max {
-            warn!(
-                "queried EIP gas price is higher than configured max gas price, \
-                will fallback to configured `max`. Queried: {}, maximum: {}",
-                dynamic_gas_price.price, config.dynamic_gas_price.max
-            );
+        let min_amount = BigRational::from_float(raise)
+            .map(|raise| {
+                (BigRational::from_integer(amount.clone()) / raise)
+                    .ceil()
+                    .to_integer()
+            })
+            .unwrap_or_else(|| amount.clone());
 
-            return GasPrice::new(config.dynamic_gas_price.max, dynamic_gas_price.denom);
+        if max_amount < min_amount {
+            return Err(Error::dynamic_fee_cap_too_low(
+                chain_id.clone(),
+                max_fee.to_string(),
+                min_amount.to_string(),
+            ));
         }
 
-        telemetry!(dynamic_gas_paid_fees, chain_id, dynamic_gas_price.price);
+        capped.push((index, amount));
+    }
 
-        dynamic_gas_price
-    } else {
-        config.gas_price.clone()
+    for (index, amount) in capped {
+        let coin = &mut fee.amount[index];
+
+        warn!(
+            %chain_id,
+            fee = %amount,
+            %max_fee,
+            denom = %coin.denom,
+            "fee at the dynamic gas price is higher than the configured `max_fee`, \
+            will fallback to the configured `max_fee`"
+        );
+
+        telemetry!(dynamic_gas_capped_fees, chain_id);
+
+        coin.amount = max_fee.to_string();
+    }
+
+    Ok(fee)
+}
+
+/// Raises the given gas price by the configured `tx_priority` tip multiplier.
+///
+/// With dynamic gas price enabled, the raised price does not exceed the configured
+/// maximum dynamic gas price, but is never lower than the given gas price.
+pub fn prioritized_gas_price(config: &GasConfig, gas_price: GasPrice) -> GasPrice {
+    if !config.tx_priority.enabled {
+        return gas_price;
+    }
+
+    let mut price = gas_price.price * config.tx_priority.tip_multiplier;
+
+    if config.dynamic_gas_price.enabled && price > config.dynamic_gas_price.max {
+        warn!(
+            "prioritized gas price is higher than configured max gas price, \
+            will fallback to configured `max`. Prioritized: {}, maximum: {}",
+            price, config.dynamic_gas_price.max
+        );
+
+        price = config.dynamic_gas_price.max.max(gas_price.price);
+    }
+
+    GasPrice::new(price, gas_price.denom)
+}
+
+/// Returns a copy of the given gas configuration with its gas price raised to
+/// the minimum gas price accepted by the node for the same denomination,
+/// or `None` if the node does not require a higher price than the configured one.
+///
+/// Dynamic gas price is disabled in the returned configuration, so that the
+/// transaction is submitted at the node's minimum gas price, raised by the
+/// `tx_priority` tip, if enabled.
+pub fn raise_to_min_gas_price(
+    config: &GasConfig,
+    min_gas_prices: &[GasPrice],
+) -> Option<GasConfig> {
+    let min_gas_price = min_gas_prices
+        .iter()
+        .find(|price| price.denom == config.gas_price.denom)?;
+
+    if min_gas_price.price <= config.gas_price.price {
+        return None;
     }
+
+    let mut raised = config.clone();
+    raised.gas_price = min_gas_price.clone();
+    raised.dynamic_gas_price = DynamicGasPrice::disabled();
+
+    Some(raised)
+}
+
+/// Rounds the given gas price up to the given number of decimals, if any,
+/// for the chains which only accept gas prices of a limited precision.
+pub fn round_up_gas_price(gas_price: GasPrice, decimals: Option<u32>) -> GasPrice {
+    let Some(decimals) = decimals else {
+        return gas_price;
+    };
+
+    let scale = 10_f64.powi(decimals as i32);
+    let scaled = gas_price.price * scale;
+
+    // Prices which already have the required precision, but which are
+    // not exactly represented as floats, must not be rounded up.
+    let rounded = if (scaled - scaled.round()).abs() < 1e-9 {
+        scaled.round()
+    } else {
+        scaled.ceil()
+    };
+
+    GasPrice::new(rounded / scale, gas_price.denom)
 }
 
 pub fn calculate_fee(adjusted_gas_amount: u64, gas_price: &GasPrice) -> Coin {
@@ -161,9 +555,533 @@ pub fn adjust_estimated_gas(
     min(gas, max_gas)
 }
 
+/// The shape of a batch of messages, ie. the type URLs of its messages, in order.
+/// A gas estimate is only reused for batches with the same shape.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BatchShape(Vec<String>);
+
+impl BatchShape {
+    pub fn of(messages: &[Any]) -> Self {
+        Self(messages.iter().map(|msg| msg.type_url.clone()).collect())
+    }
+}
+
+#[derive(Debug)]
+struct SampledEstimate {
+    shape: BatchShape,
+    gas_amount: u64,
+    reused: u64,
+}
+
+/// Keeps track of the last simulated gas estimate, in order to only simulate every
+/// `interval`-th transaction when [`GasEstimationSampling`] is enabled.
+///
+/// The last estimate is reused, before applying the gas multiplier, for transactions
+/// of the same shape in between two simulations. A change in the shape of the batch
+/// always triggers a new simulation.
+#[derive(Debug)]
+pub struct GasEstimateSampler {
+    sampling: GasEstimationSampling,
+    last: Mutex<Option<SampledEstimate>>,
+}
+
+impl GasEstimateSampler {
+    pub fn new(sampling: GasEstimationSampling) -> Self {
+        Self {
+            sampling,
+            last: Mutex::new(None),
+        }
+    }
+
+    /// Returns the gas estimate to reuse for a batch of the given shape,
+    /// or `None` if the batch must be simulated.
+    pub fn reuse_estimate(&self, shape: &BatchShape) -> Option<u64> {
+        if !self.sampling.enabled {
+            return None;
+        }
+
+        let mut last = self.last.lock().unwrap_or_else(|e| e.into_inner());
+
+        match last.as_mut() {
+            Some(estimate)
+                if &estimate.shape == shape && estimate.reused + 1 < self.sampling.interval =>
+            {
+                estimate.reused += 1;
+                Some(estimate.gas_amount)
+            }
+            _ => None,
+        }
+    }
+
+    /// Records the result of simulating a batch of the given shape.
+    pub fn record_simulation(&self, shape: BatchShape, gas_amount: u64) {
+        if !self.sampling.enabled {
+            return;
+        }
+
+        let mut last = self.last.lock().unwrap_or_else(|e| e.into_inner());
+
+        *last = Some(SampledEstimate {
+            shape,
+            gas_amount,
+            reused: 0,
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{adjust_estimated_gas, AdjustGas};
+    use std::str::FromStr;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use ibc_proto::cosmos::base::v1beta1::DecCoin;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+    use subtle_encoding::base64;
+    use tendermint_rpc::Url;
+
+    use super::{
+        adjust_estimated_gas, calculate_fee, cap_dynamic_fee, fee_at_gas_price, gas_amount_to_fee,
+        max_fee_amount, prioritized_gas_price, raise_to_min_gas_price, round_up_gas_price,
+        shadow_gas_config, AdjustGas, GasEstimateSampler, ShadowGasDelta,
+    };
+    use crate::chain::cosmos::eip_base_fee::GasPriceResponse;
+    use crate::chain::cosmos::types::gas::GasConfig;
+    use crate::config::dynamic_gas::DynamicGasPrice;
+    use crate::config::gas_multiplier::GasMultiplier;
+    use crate::config::gas_sampling::GasEstimationSampling;
+    use crate::config::shadow_gas::ShadowGasStrategy;
+    use crate::config::tx_priority::TxPriority;
+    use crate::config::GasPrice;
+    use crate::util::mock_http::{spawn_mock_http_server, MockResponse};
+    use ibc_proto::cosmos::base::v1beta1::Coin;
+    use ibc_proto::cosmos::tx::v1beta1::Fee;
+    use ibc_relayer_types::applications::transfer::Amount;
+
+    fn gas_config(dynamic_gas_price: DynamicGasPrice, tx_priority: TxPriority) -> GasConfig {
+        GasConfig {
+            default_gas: 400_000,
+            max_gas: 400_000,
+            gas_multiplier: 1.1,
+            gas_price: GasPrice::new(0.025, "stake".to_owned()),
+            gas_price_decimals: None,
+            max_fee: Fee::default(),
+            fee_granter: String::new(),
+            dynamic_gas_price,
+            gas_sampler: Arc::new(GasEstimateSampler::new(GasEstimationSampling::disabled())),
+            tx_priority,
+            shadow_gas_strategy: None,
+            reference_gas_price: None,
+            txfees_gas_price: None,
+            base_fee_history: Default::default(),
+        }
+    }
+
+    #[test]
+    fn shadow_gas_delta_is_difference_between_strategies() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let chain_id = ChainId::from_string("shadow-gas");
+        let rpc_address = Url::from_str("http://127.0.0.1:26657").unwrap();
+
+        let mut config = gas_config(DynamicGasPrice::disabled(), TxPriority::disabled());
+        config.gas_price = GasPrice::new(0.25, "stake".to_owned());
+        config.shadow_gas_strategy = Some(ShadowGasStrategy {
+            gas_multiplier: Some(GasMultiplier::new(1.3).unwrap()),
+            tx_priority: Some(TxPriority::enabled(2.0).unwrap()),
+            ..Default::default()
+        });
+
+        // The transaction is submitted with the fee of the active strategy
+        let fee = rt.block_on(gas_amount_to_fee(
+            &config,
+            200_000,
+            &chain_id,
+            &rpc_address,
+            None,
+        ));
+        assert_eq!(fee.gas_limit, 220_000);
+        assert_eq!(fee.amount[0].amount, "55000");
+
+        // The shadow strategy uses its own multiplier and tip on the same tx
+        let shadow_config =
+            shadow_gas_config(&config, config.shadow_gas_strategy.as_ref().unwrap());
+        let shadow_fee = fee_at_gas_price(&shadow_config, 200_000, shadow_config.gas_price.clone());
+        assert_eq!(shadow_fee.gas_limit, 260_000);
+        assert_eq!(shadow_fee.amount[0].amount, "130000");
+
+        assert_eq!(
+            ShadowGasDelta::between(&fee, &shadow_fee),
+            Some(ShadowGasDelta {
+                gas_limit: 40_000,
+                fee_amount: 75_000,
+            })
+        );
+
+        // A shadow strategy paying less yields a negative delta
+        assert_eq!(
+            ShadowGasDelta::between(&shadow_fee, &fee),
+            Some(ShadowGasDelta {
+                gas_limit: -40_000,
+                fee_amount: -75_000,
+            })
+        );
+
+        // Fees in different denominations cannot be compared
+        let mut other_denom = shadow_fee.clone();
+        other_denom.amount[0].denom = "uatom".to_owned();
+        assert_eq!(ShadowGasDelta::between(&fee, &other_denom), None);
+    }
+
+    /// A feemarket node answering the queries of the base fee with the given base fees,
+    /// in units of 10^-18, one after the other.
+    fn spawn_mock_feemarket_node(base_fees: &[&str]) -> Url {
+        let values: Vec<String> = base_fees
+            .iter()
+            .map(|base_fee| {
+                let response = GasPriceResponse {
+                    price: Some(DecCoin {
+                        denom: "stake".to_owned(),
+                        amount: base_fee.to_string(),
+                    }),
+                };
+                String::from_utf8(base64::encode(prost::Message::encode_to_vec(&response))).unwrap()
+            })
+            .collect();
+        let queried = AtomicUsize::new(0);
+
+        let (address, _requests) = spawn_mock_http_server(move |_| {
+            let value = &values[queried.fetch_add(1, Ordering::SeqCst).min(values.len() - 1)];
+            let response = format!(r#"{{"response":{{"code":0,"value":"{value}"}}}}"#);
+            MockResponse::json(format!(
+                r#"{{"jsonrpc":"2.0","id":-1,"result":{response}}}"#
+            ))
+        });
+
+        format!("http://{address}/").parse().unwrap()
+    }
+
+    #[test]
+    fn base_fee_spike_is_smoothed_across_the_txs_of_the_chain() {
+        const LOW: &str = "250000000000000000";
+        const SPIKE: &str = "4000000000000000000";
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let chain_id = ChainId::from_string("feemarket");
+        let rpc_address = spawn_mock_feemarket_node(&[LOW, LOW, LOW, LOW, SPIKE, LOW]);
+
+        let smoothed_gas_config = || {
+            let dynamic_gas_price =
+                DynamicGasPrice::unsafe_new(true, 1.0, 10.0).with_smoothing_window(3);
+            let mut config = gas_config(dynamic_gas_price, TxPriority::disabled());
+            config.gas_multiplier = 1.0;
+            config
+        };
+
+        let fee_amount = |config: &GasConfig| -> u64 {
+            let fee = rt.block_on(gas_amount_to_fee(
+                config,
+                100_000,
+                &chain_id,
+                &rpc_address,
+                None,
+            ));
+            fee.amount[0].amount.parse().unwrap()
+        };
+
+        // The fees of the tx at a base fee of 0.25 and 4.0
+        let (low_fee, spike_fee) = (25_000, 400_000);
+
+        let config = smoothed_gas_config();
+        for _ in 0..4 {
+            assert_eq!(fee_amount(&config), low_fee);
+        }
+
+        // The spike of the base fee is only partly passed on to the fee of the next tx
+        let spiked = fee_amount(&config);
+        assert_eq!(spiked, 212_500);
+
+        // And is still accounted for by the tx after it
+        let after_spike = fee_amount(&config);
+        assert_eq!(after_spike, 118_750);
+        assert!(low_fee < after_spike && after_spike < spiked && spiked < spike_fee);
+
+        // Connecting anew to the chain drops the base fees queried so far
+        let reconnected = smoothed_gas_config();
+        assert_eq!(fee_amount(&reconnected), low_fee);
+    }
+
+    #[test]
+    fn priority_raises_fee_tip() {
+        let gas_price = GasPrice::new(0.025, "stake".to_owned());
+
+        let baseline = gas_config(DynamicGasPrice::disabled(), TxPriority::disabled());
+        let prioritized = gas_config(
+            DynamicGasPrice::disabled(),
+            TxPriority::enabled(1.5).unwrap(),
+        );
+
+        let baseline_price = prioritized_gas_price(&baseline, gas_price.clone());
+        let prioritized_price = prioritized_gas_price(&prioritized, gas_price.clone());
+
+        assert_eq!(baseline_price, gas_price);
+        assert!(prioritized_price.price > baseline_price.price);
+
+        let baseline_fee = calculate_fee(200_000, &baseline_price);
+        let prioritized_fee = calculate_fee(200_000, &prioritized_price);
+
+        let baseline_amount: u64 = baseline_fee.amount.parse().unwrap();
+        let prioritized_amount: u64 = prioritized_fee.amount.parse().unwrap();
+
+        assert!(prioritized_amount > baseline_amount);
+    }
+
+    #[test]
+    fn priority_with_dynamic_gas_price_is_bounded_by_max() {
+        let dynamic_gas_price = DynamicGasPrice::unsafe_new(true, 1.1, 0.03);
+        let config = gas_config(dynamic_gas_price, TxPriority::enabled(2.0).unwrap());
+
+        // The dynamic gas price queried from the chain
+        let queried_price = GasPrice::new(0.02, "stake".to_owned());
+        let prioritized_price = prioritized_gas_price(&config, queried_price.clone());
+
+        assert!(prioritized_price.price > queried_price.price);
+        assert_eq!(prioritized_price.price, 0.03);
+    }
+
+    fn fee(amount: u64) -> Fee {
+        Fee {
+            amount: vec![Coin {
+                denom: "stake".to_owned(),
+                amount: amount.to_string(),
+            }],
+            gas_limit: 200_000,
+            ..Fee::default()
+        }
+    }
+
+    #[test]
+    fn dynamic_fee_is_clamped_to_max_fee() {
+        let chain_id = ChainId::from_string("max-fee");
+        let dynamic_gas_price =
+            DynamicGasPrice::unsafe_new(true, 2.0, 0.6).with_max_fee(Amount::from(8000u64));
+        let config = gas_config(dynamic_gas_price, TxPriority::disabled());
+
+        // Fees up to the cap are left untouched
+        assert_eq!(
+            cap_dynamic_fee(&config, &chain_id, fee(8000)).unwrap(),
+            fee(8000)
+        );
+        assert_eq!(
+            cap_dynamic_fee(&config, &chain_id, fee(5000)).unwrap(),
+            fee(5000)
+        );
+
+        // Higher fees are clamped to the cap, above the fee of 7500 at the base fee
+        assert_eq!(
+            cap_dynamic_fee(&config, &chain_id, fee(15000)).unwrap(),
+            fee(8000)
+        );
+
+        // Clamping the fee below the one at the base fee of 10000 skips the tx
+        assert!(cap_dynamic_fee(&config, &chain_id, fee(20000)).is_err());
+
+        // The tip is not required by the chain either
+        let config = gas_config(dynamic_gas_price, TxPriority::enabled(2.0).unwrap());
+        assert_eq!(
+            cap_dynamic_fee(&config, &chain_id, fee(30000)).unwrap(),
+            fee(8000)
+        );
+    }
+
+    fn fee_in(coins: &[(&str, u64)]) -> Fee {
+        Fee {
+            amount: coins
+                .iter()
+                .map(|(denom, amount)| Coin {
+                    denom: denom.to_string(),
+                    amount: amount.to_string(),
+                })
+                .collect(),
+            gas_limit: 200_000,
+            ..Fee::default()
+        }
+    }
+
+    #[test]
+    fn every_coin_of_the_fee_is_clamped_to_max_fee() {
+        let chain_id = ChainId::from_string("max-fee");
+        let dynamic_gas_price =
+            DynamicGasPrice::unsafe_new(true, 2.0, 0.6).with_max_fee(Amount::from(8000u64));
+        let config = gas_config(dynamic_gas_price, TxPriority::disabled());
+
+        assert_eq!(
+            cap_dynamic_fee(
+                &config,
+                &chain_id,
+                fee_in(&[("stake", 5000), ("uatom", 15000)])
+            )
+            .unwrap(),
+            fee_in(&[("stake", 5000), ("uatom", 8000)])
+        );
+
+        // Any coin clamped below its fee at the base fee skips the tx
+        assert!(cap_dynamic_fee(
+            &config,
+            &chain_id,
+            fee_in(&[("stake", 15000), ("uatom", 20000)])
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn max_fee_of_fallback_fee_denom_is_at_the_rate_of_the_gas_prices() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let rpc_address = Url::from_str("http://127.0.0.1:26657").unwrap();
+        let max_fee_amount =
+            |config: &GasConfig| rt.block_on(max_fee_amount(config, &rpc_address, None));
+
+        // The fallback gas price of 0.25 uatom is 10 times the one of 0.025 stake
+        let fallback = GasPrice::new(0.25, "uatom".to_owned());
+
+        // At the maximum dynamic gas price of 0.6 stake, or of 6 uatom
+        let config = gas_config(
+            DynamicGasPrice::unsafe_new(true, 2.0, 0.6),
+            TxPriority::disabled(),
+        );
+        assert_eq!(max_fee_amount(&config), 240_000);
+        assert_eq!(
+            max_fee_amount(&config.with_gas_price(fallback.clone())),
+            2_400_000
+        );
+
+        // Capped at the maximum fee of 8000 stake, or of 80000 uatom
+        let config = gas_config(
+            DynamicGasPrice::unsafe_new(true, 2.0, 0.6).with_max_fee(Amount::from(8000u64)),
+            TxPriority::disabled(),
+        );
+        assert_eq!(max_fee_amount(&config), 8000);
+        assert_eq!(max_fee_amount(&config.with_gas_price(fallback)), 80_000);
+    }
+
+    #[test]
+    fn fee_is_not_clamped_without_dynamic_gas_price() {
+        let chain_id = ChainId::from_string("max-fee");
+        let dynamic_gas_price =
+            DynamicGasPrice::unsafe_new(false, 2.0, 0.6).with_max_fee(Amount::from(8000u64));
+
+        let config = gas_config(dynamic_gas_price, TxPriority::disabled());
+        assert_eq!(
+            cap_dynamic_fee(&config, &chain_id, fee(20000)).unwrap(),
+            fee(20000)
+        );
+
+        let config = gas_config(
+            DynamicGasPrice::unsafe_new(true, 2.0, 0.6),
+            TxPriority::disabled(),
+        );
+        assert_eq!(
+            cap_dynamic_fee(&config, &chain_id, fee(20000)).unwrap(),
+            fee(20000)
+        );
+    }
+
+    #[test]
+    fn fee_is_rounded_up_for_integer_gas_price_chain() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let chain_id = ChainId::from_string("integer-gas-price");
+        let rpc_address = Url::from_str("http://127.0.0.1:26657").unwrap();
+
+        let mut config = gas_config(DynamicGasPrice::disabled(), TxPriority::disabled());
+        config.gas_price_decimals = Some(0);
+
+        let fee = rt.block_on(gas_amount_to_fee(
+            &config,
+            100_000,
+            &chain_id,
+            &rpc_address,
+            None,
+        ));
+        let amount: u64 = fee.amount[0].amount.parse().unwrap();
+
+        // The decimal price of 0.025 is rounded up to the integer price of 1,
+        // so that the fee pays an integer price for each unit of gas...
+        assert_eq!(amount, fee.gas_limit);
+
+        // ...which is at least the minimum fee derived from the decimal price
+        assert!(node_accepts(
+            &fee.amount[0],
+            fee.gas_limit,
+            &config.gas_price
+        ));
+
+        // Prices with the required precision are not rounded up
+        let price = GasPrice::new(0.03, "stake".to_owned());
+        assert_eq!(round_up_gas_price(price.clone(), Some(2)), price);
+        assert_eq!(
+            round_up_gas_price(GasPrice::new(0.025, "stake".to_owned()), Some(2)).price,
+            0.03
+        );
+        assert_eq!(round_up_gas_price(price.clone(), None), price);
+    }
+
+    /// Whether a node with the given minimum gas price accepts the fee of a tx
+    fn node_accepts(fee: &Coin, gas: u64, min: &GasPrice) -> bool {
+        let required: u64 = calculate_fee(gas, min).amount.parse().unwrap();
+        let paid: u64 = fee.amount.parse().unwrap();
+
+        fee.denom == min.denom && paid >= required
+    }
+
+    #[test]
+    fn resubmit_succeeds_after_min_gas_price_increase() {
+        let config = gas_config(DynamicGasPrice::disabled(), TxPriority::disabled());
+        let gas = 200_000;
+
+        let fee = calculate_fee(gas, &config.gas_price);
+        assert!(node_accepts(&fee, gas, &config.gas_price));
+
+        // The minimum gas price is doubled by governance
+        let node_min_prices = vec![
+            GasPrice::new(0.1, "uatom".to_owned()),
+            GasPrice::new(0.05, "stake".to_owned()),
+        ];
+
+        assert!(!node_accepts(&fee, gas, &node_min_prices[1]));
+
+        let raised = raise_to_min_gas_price(&config, &node_min_prices).unwrap();
+        assert_eq!(raised.gas_price, node_min_prices[1]);
+
+        let price = prioritized_gas_price(&raised, raised.gas_price.clone());
+        let fee = calculate_fee(gas, &price);
+        assert!(node_accepts(&fee, gas, &node_min_prices[1]));
+    }
+
+    #[test]
+    fn min_gas_price_adjustment_disables_dynamic_gas_price() {
+        let dynamic_gas_price = DynamicGasPrice::unsafe_new(true, 1.1, 0.03);
+        let config = gas_config(dynamic_gas_price, TxPriority::disabled());
+
+        let node_min_prices = vec![GasPrice::new(0.05, "stake".to_owned())];
+        let raised = raise_to_min_gas_price(&config, &node_min_prices).unwrap();
+
+        assert!(!raised.dynamic_gas_price.enabled);
+        assert_eq!(raised.gas_price.price, 0.05);
+    }
+
+    #[test]
+    fn no_min_gas_price_adjustment_without_higher_price() {
+        let config = gas_config(DynamicGasPrice::disabled(), TxPriority::disabled());
+
+        // Same price as the configured one
+        let same = vec![GasPrice::new(0.025, "stake".to_owned())];
+        assert!(raise_to_min_gas_price(&config, &same).is_none());
+
+        // Higher price, but in another denomination
+        let other_denom = vec![GasPrice::new(0.05, "uatom".to_owned())];
+        assert!(raise_to_min_gas_price(&config, &other_denom).is_none());
+
+        assert!(raise_to_min_gas_price(&config, &[]).is_none());
+    }
 
     #[test]
     fn adjust_zero_gas() {
@@ -0,0 +1,249 @@
+//! Scaling of the gas price by the price of the fee denomination, obtained by running the
+//! configured price command, so that the value of the gas price paid in a reference
+//! denomination, eg. USD, stays the same when the value of the fee denomination changes.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use flex_error::define_error;
+use tracing::warn;
+
+use crate::config::{GasPrice, GasPrices, ReferenceGasPriceConfig};
+use crate::util::command::{run_command_async, CommandError};
+use crate::util::refresh_cache::RefreshCache;
+
+/// How long the price command may run before it is killed.
+const PRICE_COMMAND_TIMEOUT: Duration = Duration::from_secs(10);
+
+define_error! {
+    PriceOracleError {
+        Command
+            [ CommandError ]
+            |_| { "price command failed" },
+
+        InvalidPrice
+            { price: String }
+            |e| { format!("invalid price `{}`, expected a positive decimal number", e.price) },
+    }
+}
+
+/// Scales the gas price by the ratio between the reference price of the fee denomination
+/// and its current price, which is obtained by running the price command at most once
+/// every `refresh_interval` for each denomination.
+#[derive(Debug)]
+pub struct ReferenceGasPrice {
+    reference_price: f64,
+    /// The reference prices of the fallback fee denominations, derived from the one of the
+    /// preferred denomination at the rate of their gas prices
+    fallback_reference_prices: HashMap<String, f64>,
+    max_scale: f64,
+    price_command: Vec<String>,
+    current: RefreshCache<String, f64>,
+}
+
+impl ReferenceGasPrice {
+    pub fn from_config(config: &ReferenceGasPriceConfig, gas_prices: &GasPrices) -> Self {
+        let preferred = gas_prices.preferred();
+
+        let fallback_reference_prices = gas_prices
+            .iter()
+            .skip(1)
+            .map(|gas_price| {
+                let reference_price = config.reference_price * preferred.price / gas_price.price;
+                (gas_price.denom.clone(), reference_price)
+            })
+            .collect();
+
+        Self {
+            reference_price: config.reference_price,
+            fallback_reference_prices,
+            max_scale: config.max_scale,
+            price_command: config.price_command.clone(),
+            current: RefreshCache::new(config.refresh_interval),
+        }
+    }
+
+    /// Runs the price command to obtain the current price of the given denomination.
+    async fn query_price(&self, denom: &str) -> Result<f64, PriceOracleError> {
+        let output = run_command_async(
+            self.price_command.clone(),
+            vec![denom.to_string()],
+            None,
+            PRICE_COMMAND_TIMEOUT,
+        )
+        .await
+        .map_err(PriceOracleError::command)?;
+
+        let price = String::from_utf8_lossy(&output).trim().to_string();
+
+        match price.parse::<f64>() {
+            Ok(value) if value.is_finite() && value > 0.0 => Ok(value),
+            _ => Err(PriceOracleError::invalid_price(price)),
+        }
+    }
+
+    /// Returns the current price of the given denomination, from the price command if the
+    /// last price obtained is older than the refresh interval, or `None` if the command
+    /// failed and no price was obtained before.
+    async fn current_price(&self, denom: &str) -> Option<f64> {
+        let refresh = async {
+            self.query_price(denom).await.inspect_err(|e| {
+                warn!("failed to obtain the price of `{denom}`, will use the last known price: {e}")
+            })
+        };
+
+        self.current.get(denom.to_string(), refresh).await
+    }
+
+    /// Scales the given gas price by the ratio between the reference price of its
+    /// denomination and its current price, clamped so that the gas price is scaled
+    /// up or down by a factor of `max_scale` at most. The gas price is returned as is
+    /// if the current price of its denomination is unknown.
+    pub async fn scale(&self, gas_price: GasPrice) -> GasPrice {
+        let reference_price = self
+            .fallback_reference_prices
+            .get(&gas_price.denom)
+            .copied()
+            .unwrap_or(self.reference_price);
+
+        match self.current_price(&gas_price.denom).await {
+            Some(price) => {
+                let scale = (reference_price / price).clamp(1.0 / self.max_scale, self.max_scale);
+
+                GasPrice::new(gas_price.price * scale, gas_price.denom)
+            }
+            None => gas_price,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+    use std::path::PathBuf;
+
+    /// A price feed which prints the price written to a file by the test, and fails
+    /// when the file is missing.
+    struct PriceFeed {
+        path: PathBuf,
+    }
+
+    impl PriceFeed {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir()
+                .join(format!("hermes-price-feed-{}-{name}", std::process::id()));
+            let _ = fs::remove_file(&path);
+
+            Self { path }
+        }
+
+        fn set(&self, price: Option<f64>) {
+            match price {
+                Some(price) => fs::write(&self.path, price.to_string()).unwrap(),
+                None => fs::remove_file(&self.path).unwrap(),
+            }
+        }
+
+        fn reference_gas_price(&self, refresh_interval: Duration) -> ReferenceGasPrice {
+            let script = format!(r#"test "$1" = uatom && cat {}"#, self.path.display());
+
+            ReferenceGasPrice::from_config(
+                &ReferenceGasPriceConfig {
+                    reference_price: 10.0,
+                    price_command: ["sh", "-c", &script, "sh"].map(String::from).to_vec(),
+                    refresh_interval,
+                    max_scale: 4.0,
+                },
+                &gas_price().into(),
+            )
+        }
+    }
+
+    impl Drop for PriceFeed {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+
+    fn gas_price() -> GasPrice {
+        GasPrice::new(0.5, "uatom".to_string())
+    }
+
+    #[tokio::test]
+    async fn effective_gas_price_scales_with_price_feed() {
+        let feed = PriceFeed::new("scales");
+        let reference = feed.reference_gas_price(Duration::ZERO);
+
+        // At the reference price, the gas price is paid as is
+        feed.set(Some(10.0));
+        assert_eq!(reference.scale(gas_price()).await.price, 0.5);
+
+        // When the fee denom is worth twice as much, half as much of it is paid
+        feed.set(Some(20.0));
+        assert_eq!(reference.scale(gas_price()).await.price, 0.25);
+
+        // When it is worth half as much, twice as much of it is paid
+        feed.set(Some(5.0));
+        assert_eq!(reference.scale(gas_price()).await.price, 1.0);
+
+        // When the feed fails, the last known price is used
+        feed.set(None);
+        assert_eq!(reference.scale(gas_price()).await.price, 1.0);
+
+        // Without any known price, the gas price is paid as is
+        let reference = feed.reference_gas_price(Duration::ZERO);
+        assert_eq!(reference.scale(gas_price()).await, gas_price());
+    }
+
+    #[tokio::test]
+    async fn scale_is_clamped_to_max_scale() {
+        let feed = PriceFeed::new("clamped");
+        let reference = feed.reference_gas_price(Duration::ZERO);
+
+        // A price a thousand times lower than the reference one scales the gas price by 4
+        feed.set(Some(0.01));
+        assert_eq!(reference.scale(gas_price()).await.price, 2.0);
+
+        // A price a thousand times higher scales it by 1/4
+        feed.set(Some(10_000.0));
+        assert_eq!(reference.scale(gas_price()).await.price, 0.125);
+    }
+
+    #[tokio::test]
+    async fn price_feed_is_queried_once_per_refresh_interval() {
+        let feed = PriceFeed::new("refresh");
+        let reference = feed.reference_gas_price(Duration::from_secs(3600));
+
+        feed.set(Some(20.0));
+        assert_eq!(reference.scale(gas_price()).await.price, 0.25);
+
+        // The price obtained last is used until the refresh interval elapses
+        feed.set(Some(5.0));
+        assert_eq!(reference.scale(gas_price()).await.price, 0.25);
+    }
+
+    #[tokio::test]
+    async fn fallback_fee_denom_is_scaled_to_its_own_reference_price() {
+        // 10 uosmo are paid instead of 1 uatom, so the reference price of uosmo is 1
+        let uosmo = || GasPrice::new(5.0, "uosmo".to_string());
+        let gas_prices = GasPrices::new(vec![gas_price(), uosmo()]).unwrap();
+        let script = r#"case "$1" in uatom) echo 10 ;; uosmo) echo 2 ;; esac"#;
+
+        let reference = ReferenceGasPrice::from_config(
+            &ReferenceGasPriceConfig {
+                reference_price: 10.0,
+                price_command: ["sh", "-c", script, "sh"].map(String::from).to_vec(),
+                refresh_interval: Duration::ZERO,
+                max_scale: 4.0,
+            },
+            &gas_prices,
+        );
+
+        assert_eq!(reference.scale(gas_price()).await.price, 0.5);
+
+        // uosmo is worth twice its reference price, so half as much of it is paid
+        assert_eq!(reference.scale(uosmo()).await.price, 2.5);
+    }
+}
@@ -1,3 +1,5 @@
+use core::time::Duration;
+
 use ibc_proto::cosmos::base::tendermint::v1beta1::GetNodeInfoResponse;
 use ibc_relayer_types::core::ics04_channel::packet::Sequence;
 use ibc_relayer_types::core::ics23_commitment::merkle::{
@@ -7,11 +9,19 @@ use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use prost::Message;
 use tendermint::block::Height;
 use tendermint_rpc::query::Query;
-use tendermint_rpc::{Client, HttpClient, Url};
+use tendermint_rpc::{Client, Url};
+use tokio::time::sleep;
+use tonic::metadata::{AsciiMetadataKey, AsciiMetadataValue};
+use tracing::warn;
 
+use crate::chain::cosmos::request_rate::PacedRpcClient;
 use crate::chain::cosmos::version::Specs;
 use crate::chain::requests::QueryHeight;
-use crate::chain::requests::{QueryClientEventRequest, QueryPacketEventDataRequest, QueryTxHash};
+use crate::chain::requests::{
+    QueryClientEventRequest, QueryPacketEventDataRequest, QuerySendPacketsRequest,
+    QuerySenderTxsRequest, QueryTxHash,
+};
+use crate::config::{GrpcHeightHeader, HeightHeaderFormat};
 use crate::error::Error;
 
 pub mod account;
@@ -21,6 +31,9 @@ pub mod consensus_state;
 pub mod custom;
 pub mod denom_trace;
 pub mod fee;
+pub mod node_config;
+pub mod packet;
+pub mod relayer_whitelist;
 pub mod status;
 pub mod tx;
 
@@ -74,9 +87,72 @@ pub fn tx_hash_query(request: &QueryTxHash) -> Query {
     Query::eq("tx.hash", request.0.to_string())
 }
 
+pub fn sender_txs_query(request: &QuerySenderTxsRequest) -> Query {
+    Query::eq("message.sender", request.sender.to_string())
+        .and_gte("tx.height", request.min_height.revision_height())
+}
+
+pub fn send_packets_query(request: &QuerySendPacketsRequest) -> Query {
+    Query::eq(
+        "send_packet.packet_src_channel",
+        request.channel_id.to_string(),
+    )
+    .and_eq("send_packet.packet_src_port", request.port_id.to_string())
+    .and_gte("tx.height", request.min_height.revision_height())
+}
+
+/// Maximum number of attempts at an ABCI query for which a proof was requested,
+/// when the node keeps responding without one.
+const MAX_EMPTY_PROOF_ATTEMPTS: u32 = 3;
+
+/// Delay between two attempts at an ABCI query which returned no proof.
+const EMPTY_PROOF_RETRY_DELAY: Duration = Duration::from_millis(300);
+
 /// Perform a generic `abci_query`, and return the corresponding deserialized response data.
+///
+/// If a proof is requested but the node responds without one, the query is retried
+/// a few times before failing with [`Error::empty_response_proof`], since a response
+/// without a proof cannot be used to build a verifiable message.
 pub async fn abci_query(
-    rpc_client: &HttpClient,
+    rpc_client: &PacedRpcClient<impl Client + Sync>,
+    rpc_address: &Url,
+    path: String,
+    data: String,
+    height: Height,
+    prove: bool,
+) -> Result<QueryResponse, Error> {
+    let mut attempt = 1;
+
+    loop {
+        let result = abci_query_once(
+            rpc_client,
+            rpc_address,
+            path.clone(),
+            data.clone(),
+            height,
+            prove,
+        )
+        .await;
+
+        match result {
+            Err(e) if e.is_empty_response_proof_error() && attempt < MAX_EMPTY_PROOF_ATTEMPTS => {
+                warn!(
+                    %path,
+                    %height,
+                    attempt,
+                    "node returned no proof for a query requiring one, retrying"
+                );
+
+                sleep(EMPTY_PROOF_RETRY_DELAY).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+async fn abci_query_once(
+    rpc_client: &PacedRpcClient<impl Client + Sync>,
     rpc_address: &Url,
     path: String,
     data: String,
@@ -100,7 +176,12 @@ pub async fn abci_query(
         return Err(Error::abci_query(response));
     }
 
-    if prove && response.proof.is_none() {
+    let proof_is_missing = match &response.proof {
+        Some(proof) => proof.ops.is_empty(),
+        None => true,
+    };
+
+    if prove && proof_is_missing {
         // Fail due to empty proof
         return Err(Error::empty_response_proof());
     }
@@ -120,10 +201,42 @@ pub async fn abci_query(
     Ok(response)
 }
 
+/// Passes the height at which a gRPC query is made to the node, in the header
+/// and the format configured for the chain.
+pub fn insert_height_header<T>(
+    request: &mut tonic::Request<T>,
+    height: QueryHeight,
+    header: &GrpcHeightHeader,
+) -> Result<(), Error> {
+    let name = AsciiMetadataKey::from_bytes(header.name.as_bytes())
+        .map_err(Error::invalid_metadata_key)?;
+
+    let value: AsciiMetadataValue = height_header_value(height, header.format)
+        .parse()
+        .map_err(Error::invalid_metadata)?;
+
+    request.metadata_mut().insert(name, value);
+
+    Ok(())
+}
+
+fn height_header_value(height: QueryHeight, format: HeightHeaderFormat) -> String {
+    match (height, format) {
+        // A height of zero stands for the latest height
+        (QueryHeight::Latest, _) => "0".to_string(),
+        (QueryHeight::Specific(height), HeightHeaderFormat::Height) => {
+            height.revision_height().to_string()
+        }
+        (QueryHeight::Specific(height), HeightHeaderFormat::Revision) => {
+            format!("{}-{}", height.revision_number(), height.revision_height())
+        }
+    }
+}
+
 /// Queries the chain to obtain the version information.
 pub async fn fetch_version_specs(
     chain_id: &ChainId,
-    rpc_client: &HttpClient,
+    rpc_client: &PacedRpcClient,
     rpc_addr: &Url,
 ) -> Result<Specs, Error> {
     let query_response = abci_query(
@@ -156,3 +269,128 @@ pub async fn fetch_version_specs(
         .try_into()
         .map_err(|e| Error::fetch_version_parsing(chain_id.clone(), rpc_addr.to_string(), e))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::sync::atomic::{AtomicU32, Ordering};
+
+    use tendermint_rpc::{Error as RpcError, MockClient, MockRequestMatcher, Request, Response};
+
+    /// Responds to `abci_query` requests with a value but without a proof
+    /// for the first `missing_proofs` requests, and with a proof afterwards.
+    struct MissingProofMatcher {
+        missing_proofs: u32,
+        requests: AtomicU32,
+    }
+
+    impl MissingProofMatcher {
+        fn new(missing_proofs: u32) -> Self {
+            Self {
+                missing_proofs,
+                requests: AtomicU32::new(0),
+            }
+        }
+
+        fn requests(&self) -> u32 {
+            self.requests.load(Ordering::SeqCst)
+        }
+    }
+
+    impl MockRequestMatcher for &MissingProofMatcher {
+        fn response_for<R, S>(&self, _request: R) -> Option<Result<R::Response, RpcError>>
+        where
+            R: Request<S>,
+            S: tendermint_rpc::dialect::Dialect,
+        {
+            let request = self.requests.fetch_add(1, Ordering::SeqCst);
+
+            let proof = if request < self.missing_proofs {
+                "null"
+            } else {
+                r#"{ "ops": [{ "type": "ics23:iavl", "key": "a2V5", "data": "" }] }"#
+            };
+
+            let response = format!(
+                r#"{{
+                    "jsonrpc": "2.0",
+                    "id": "",
+                    "result": {{
+                        "response": {{
+                            "code": 0,
+                            "codespace": "",
+                            "height": "10",
+                            "index": "0",
+                            "info": "",
+                            "key": "a2V5",
+                            "log": "",
+                            "proofOps": {proof},
+                            "value": "dmFsdWU="
+                        }}
+                    }}
+                }}"#
+            );
+
+            Some(R::Response::from_string(response))
+        }
+    }
+
+    #[test]
+    fn height_header_follows_configured_format() {
+        let height = QueryHeight::Specific(ibc_relayer_types::Height::new(1, 2).unwrap());
+
+        let header = GrpcHeightHeader {
+            name: "x-block-height".to_string(),
+            format: HeightHeaderFormat::Revision,
+        };
+
+        let mut request = tonic::Request::new(());
+        insert_height_header(&mut request, height, &header).unwrap();
+        assert_eq!(request.metadata().get("x-block-height").unwrap(), "1-2");
+        assert!(request.metadata().get("x-cosmos-block-height").is_none());
+
+        let mut request = tonic::Request::new(());
+        insert_height_header(&mut request, height, &GrpcHeightHeader::default()).unwrap();
+        assert_eq!(
+            request.metadata().get("x-cosmos-block-height").unwrap(),
+            "2"
+        );
+    }
+
+    async fn query_with_proof(matcher: &MissingProofMatcher) -> Result<QueryResponse, Error> {
+        let (client, _driver) = MockClient::new(matcher);
+        let client = PacedRpcClient::new(client, None);
+
+        abci_query(
+            &client,
+            &"http://127.0.0.1:26657".parse().unwrap(),
+            "store/ibc/key".to_owned(),
+            "key".to_owned(),
+            Height::from(10_u32),
+            true,
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn missing_proof_is_retried() {
+        let matcher = MissingProofMatcher::new(MAX_EMPTY_PROOF_ATTEMPTS - 1);
+
+        let response = query_with_proof(&matcher).await.unwrap();
+
+        assert_eq!(matcher.requests(), MAX_EMPTY_PROOF_ATTEMPTS);
+        assert_eq!(response.value, b"value");
+        assert!(response.proof.is_some());
+    }
+
+    #[tokio::test]
+    async fn missing_proof_fails_after_max_attempts() {
+        let matcher = MissingProofMatcher::new(u32::MAX);
+
+        let error = query_with_proof(&matcher).await.unwrap_err();
+
+        assert!(error.is_empty_response_proof_error());
+        assert_eq!(matcher.requests(), MAX_EMPTY_PROOF_ATTEMPTS);
+    }
+}
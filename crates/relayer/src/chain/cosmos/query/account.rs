@@ -1,13 +1,17 @@
 use http::uri::Uri;
 use ibc_proto::cosmos::auth::v1beta1::query_client::QueryClient;
 use ibc_proto::cosmos::auth::v1beta1::{BaseAccount, QueryAccountRequest};
+use ibc_proto::google::protobuf::Any;
 use prost::Message;
+use std::sync::Arc;
 use tracing::info;
 
+use crate::chain::cosmos::request_rate::RequestRateLimiter;
+use crate::chain::cosmos::sticky_session::{create_pinned_grpc_client, Session};
 use crate::chain::cosmos::types::account::Account;
 use crate::config::default::max_grpc_decoding_size;
+use crate::config::AccountQuery;
 use crate::error::Error;
-use crate::util::create_grpc_client;
 
 /// EthAccount defines an Ethermint account.
 /// TODO: remove when/if a canonical `EthAccount`
@@ -22,18 +26,41 @@ pub struct EthAccount {
     pub code_hash: ::prost::alloc::vec::Vec<u8>,
 }
 
+/// Any account type which wraps a `BaseAccount`, or another wrapper of it,
+/// as its first field, such as the vesting accounts of the Cosmos SDK.
+#[derive(Clone, PartialEq, ::prost::Message)]
+struct WrappedAccount {
+    #[prost(bytes = "vec", tag = "1")]
+    inner: ::prost::alloc::vec::Vec<u8>,
+}
+
+const BASE_ACCOUNT_TYPE_URL: &str = "/cosmos.auth.v1beta1.BaseAccount";
+
+/// Maximum number of wrappers around the `BaseAccount` of a wrapped account.
+const MAX_ACCOUNT_WRAPPERS: usize = 4;
+
 /// Get a `&mut Account` from an `&mut Option<Account>` if it is `Some(Account)`.
 /// Otherwise query for the account information, update the `Option` to `Some`,
 /// and return the underlying `&mut` reference.
 pub async fn get_or_fetch_account<'a>(
     grpc_address: &'a Uri,
+    grpc_session: Option<&'a Arc<Session>>,
+    grpc_limiter: Option<&'a Arc<RequestRateLimiter>>,
     account_address: &'a str,
+    account_query: Option<AccountQuery>,
     m_account: &'a mut Option<Account>,
 ) -> Result<&'a mut Account, Error> {
     match m_account {
         Some(account) => Ok(account),
         None => {
-            let account = query_account(grpc_address, account_address).await?;
+            let account = query_account(
+                grpc_address,
+                grpc_session,
+                grpc_limiter,
+                account_address,
+                account_query,
+            )
+            .await?;
             *m_account = Some(account.into());
 
             Ok(m_account
@@ -47,10 +74,20 @@ pub async fn get_or_fetch_account<'a>(
 /// account and updating the `&mut` reference.
 pub async fn refresh_account(
     grpc_address: &Uri,
+    grpc_session: Option<&Arc<Session>>,
+    grpc_limiter: Option<&Arc<RequestRateLimiter>>,
     account_address: &str,
+    account_query: Option<AccountQuery>,
     m_account: &'_ mut Account,
 ) -> Result<(), Error> {
-    let account = query_account(grpc_address, account_address).await?;
+    let account = query_account(
+        grpc_address,
+        grpc_session,
+        grpc_limiter,
+        account_address,
+        account_query,
+    )
+    .await?;
 
     info!(
         old = %m_account.sequence,
@@ -66,9 +103,14 @@ pub async fn refresh_account(
 /// Uses the GRPC client to retrieve the account sequence
 pub async fn query_account(
     grpc_address: &Uri,
+    grpc_session: Option<&Arc<Session>>,
+    grpc_limiter: Option<&Arc<RequestRateLimiter>>,
     account_address: &str,
+    account_query: Option<AccountQuery>,
 ) -> Result<BaseAccount, Error> {
-    let mut client = create_grpc_client(grpc_address, QueryClient::new).await?;
+    let mut client =
+        create_pinned_grpc_client(grpc_address, grpc_session, grpc_limiter, QueryClient::new)
+            .await?;
 
     client = client.max_decoding_message_size(max_grpc_decoding_size().get_bytes() as usize);
 
@@ -88,15 +130,154 @@ pub async fn query_account(
         None => return Err(Error::empty_query_account(account_address.to_string())),
     };
 
-    if resp_account.type_url == "/cosmos.auth.v1beta1.BaseAccount" {
-        Ok(BaseAccount::decode(resp_account.value.as_slice())
-            .map_err(|e| Error::protobuf_decode("BaseAccount".to_string(), e))?)
-    } else if resp_account.type_url.ends_with(".EthAccount") {
-        Ok(EthAccount::decode(resp_account.value.as_slice())
-            .map_err(|e| Error::protobuf_decode("EthAccount".to_string(), e))?
-            .base_account
-            .ok_or_else(Error::empty_base_account)?)
-    } else {
-        Err(Error::unknown_account_type(resp_account.type_url))
+    decode_account(&resp_account, account_address, account_query)
+}
+
+/// Extracts the `BaseAccount` of the given address from the response of the `Account` query,
+/// as specified by `account_query`, or based on the type of the account if unspecified.
+fn decode_account(
+    account: &Any,
+    account_address: &str,
+    account_query: Option<AccountQuery>,
+) -> Result<BaseAccount, Error> {
+    match account_query {
+        Some(AccountQuery::Auth) => decode_base_account(account),
+        Some(AccountQuery::Base) => unwrap_base_account(account, account_address),
+        None if account.type_url == BASE_ACCOUNT_TYPE_URL => decode_base_account(account),
+        None if account.type_url.ends_with(".EthAccount") => {
+            Ok(EthAccount::decode(account.value.as_slice())
+                .map_err(|e| Error::protobuf_decode("EthAccount".to_string(), e))?
+                .base_account
+                .ok_or_else(Error::empty_base_account)?)
+        }
+        None => unwrap_base_account(account, account_address)
+            .map_err(|_| Error::unknown_account_type(account.type_url.clone())),
+    }
+}
+
+fn decode_base_account(account: &Any) -> Result<BaseAccount, Error> {
+    if account.type_url != BASE_ACCOUNT_TYPE_URL {
+        return Err(Error::unknown_account_type(account.type_url.clone()));
+    }
+
+    BaseAccount::decode(account.value.as_slice())
+        .map_err(|e| Error::protobuf_decode("BaseAccount".to_string(), e))
+}
+
+/// Looks for the `BaseAccount` of the given address in the first field of
+/// the account, descending through the wrappers around it.
+fn unwrap_base_account(account: &Any, account_address: &str) -> Result<BaseAccount, Error> {
+    let mut value = account.value.clone();
+
+    for _ in 0..=MAX_ACCOUNT_WRAPPERS {
+        match BaseAccount::decode(value.as_slice()) {
+            Ok(base_account) if base_account.address == account_address => return Ok(base_account),
+            _ => {
+                value = WrappedAccount::decode(value.as_slice())
+                    .map_err(|e| Error::protobuf_decode(account.type_url.clone(), e))?
+                    .inner;
+            }
+        }
+    }
+
+    Err(Error::missing_base_account(
+        account.type_url.clone(),
+        account_address.to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::chain::cosmos::types::account::{AccountNumber, AccountSequence};
+
+    const ADDRESS: &str = "cosmos1hy3c9gr8hsxyj0y6kn4hjmjpdsw4pjr0u8ysxf";
+
+    /// Mirrors `cosmos.vesting.v1beta1.BaseVestingAccount`
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    struct BaseVestingAccount {
+        #[prost(message, optional, tag = "1")]
+        base_account: Option<BaseAccount>,
+        #[prost(int64, tag = "5")]
+        end_time: i64,
+    }
+
+    /// Mirrors `cosmos.vesting.v1beta1.ContinuousVestingAccount`
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    struct ContinuousVestingAccount {
+        #[prost(message, optional, tag = "1")]
+        base_vesting_account: Option<BaseVestingAccount>,
+        #[prost(int64, tag = "2")]
+        start_time: i64,
+    }
+
+    fn base_account() -> BaseAccount {
+        BaseAccount {
+            address: ADDRESS.to_string(),
+            pub_key: None,
+            account_number: 42,
+            sequence: 7,
+        }
+    }
+
+    fn vesting_account() -> Any {
+        let account = ContinuousVestingAccount {
+            base_vesting_account: Some(BaseVestingAccount {
+                base_account: Some(base_account()),
+                end_time: 1_700_000_000,
+            }),
+            start_time: 1_600_000_000,
+        };
+
+        Any {
+            type_url: "/cosmos.vesting.v1beta1.ContinuousVestingAccount".to_string(),
+            value: account.encode_to_vec(),
+        }
+    }
+
+    #[test]
+    fn wrapped_base_account_is_unpacked() {
+        let account: Account =
+            decode_account(&vesting_account(), ADDRESS, Some(AccountQuery::Base))
+                .unwrap()
+                .into();
+
+        assert_eq!(account.number, AccountNumber::new(42));
+        assert_eq!(account.sequence, AccountSequence::new(7));
+    }
+
+    #[test]
+    fn wrapped_base_account_is_detected() {
+        let account = decode_account(&vesting_account(), ADDRESS, None).unwrap();
+
+        assert_eq!(account, base_account());
+    }
+
+    #[test]
+    fn wrapped_base_account_is_rejected_by_auth_query() {
+        assert!(decode_account(&vesting_account(), ADDRESS, Some(AccountQuery::Auth)).is_err());
+    }
+
+    #[test]
+    fn plain_base_account_is_decoded_by_all_queries() {
+        let account = Any {
+            type_url: BASE_ACCOUNT_TYPE_URL.to_string(),
+            value: base_account().encode_to_vec(),
+        };
+
+        for account_query in [None, Some(AccountQuery::Auth), Some(AccountQuery::Base)] {
+            assert_eq!(
+                decode_account(&account, ADDRESS, account_query).unwrap(),
+                base_account()
+            );
+        }
+    }
+
+    #[test]
+    fn account_of_another_address_is_not_found() {
+        let other = "cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu";
+
+        assert!(decode_account(&vesting_account(), other, Some(AccountQuery::Base)).is_err());
     }
 }
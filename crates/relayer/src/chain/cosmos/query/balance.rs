@@ -1,21 +1,27 @@
 use http::uri::Uri;
+use std::sync::Arc;
 
 use ibc_proto::cosmos::bank::v1beta1::{
     query_client::QueryClient, QueryAllBalancesRequest, QueryBalanceRequest,
 };
 
 use crate::account::Balance;
+use crate::chain::cosmos::request_rate::RequestRateLimiter;
+use crate::chain::cosmos::sticky_session::{create_pinned_grpc_client, Session};
 use crate::config::default::max_grpc_decoding_size;
 use crate::error::Error;
-use crate::util::create_grpc_client;
 
 /// Uses the GRPC client to retrieve the account balance for a specific denom
 pub async fn query_balance(
     grpc_address: &Uri,
+    grpc_session: Option<&Arc<Session>>,
+    grpc_limiter: Option<&Arc<RequestRateLimiter>>,
     account_address: &str,
     denom: &str,
 ) -> Result<Balance, Error> {
-    let mut client = create_grpc_client(grpc_address, QueryClient::new).await?;
+    let mut client =
+        create_pinned_grpc_client(grpc_address, grpc_session, grpc_limiter, QueryClient::new)
+            .await?;
 
     client = client.max_decoding_message_size(max_grpc_decoding_size().get_bytes() as usize);
 
@@ -44,9 +50,13 @@ pub async fn query_balance(
 /// Uses the GRPC client to retrieve the account balance for all denom
 pub async fn query_all_balances(
     grpc_address: &Uri,
+    grpc_session: Option<&Arc<Session>>,
+    grpc_limiter: Option<&Arc<RequestRateLimiter>>,
     account_address: &str,
 ) -> Result<Vec<Balance>, Error> {
-    let mut client = create_grpc_client(grpc_address, QueryClient::new).await?;
+    let mut client =
+        create_pinned_grpc_client(grpc_address, grpc_session, grpc_limiter, QueryClient::new)
+            .await?;
 
     client = client.max_decoding_message_size(max_grpc_decoding_size().get_bytes() as usize);
 
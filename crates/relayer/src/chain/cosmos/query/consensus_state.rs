@@ -1,20 +1,24 @@
 use http::Uri;
+use std::sync::Arc;
 use tracing::{debug, warn};
 
 use ibc_relayer_types::{core::ics24_host::identifier::ChainId, Height};
 
+use crate::chain::cosmos::request_rate::RequestRateLimiter;
+use crate::chain::cosmos::sticky_session::{create_pinned_grpc_client, Session};
 use crate::chain::requests::{QueryConsensusStateHeightsRequest, QueryConsensusStatesRequest};
-use crate::config::default::max_grpc_decoding_size;
 use crate::consensus_state::AnyConsensusStateWithHeight;
 use crate::error::Error;
-use crate::util::create_grpc_client;
 use crate::util::pretty::{PrettyConsensusStateWithHeight, PrettyHeight};
 
 /// Performs a `QueryConsensusStateHeightsRequest` gRPC query to fetch all the consensus state
-/// heights associated with a given client.
+/// heights associated with a given client, decoding responses of up to `max_decoding_size` bytes.
 pub async fn query_consensus_state_heights(
     chain_id: &ChainId,
     grpc_addr: &Uri,
+    grpc_session: Option<&Arc<Session>>,
+    grpc_limiter: Option<&Arc<RequestRateLimiter>>,
+    max_decoding_size: usize,
     request: QueryConsensusStateHeightsRequest,
 ) -> Result<Vec<Height>, Error> {
     crate::time!(
@@ -37,13 +41,15 @@ pub async fn query_consensus_state_heights(
             .contains("unknown method ConsensusStateHeights")
     }
 
-    let mut client = create_grpc_client(
+    let mut client = create_pinned_grpc_client(
         grpc_addr,
+        grpc_session,
+        grpc_limiter,
         ibc_proto::ibc::core::client::v1::query_client::QueryClient::new,
     )
     .await?;
 
-    client = client.max_decoding_message_size(max_grpc_decoding_size().get_bytes() as usize);
+    client = client.max_decoding_message_size(max_decoding_size);
 
     let grpc_request = tonic::Request::new(request.clone().into());
     let grpc_response = client.consensus_state_heights(grpc_request).await;
@@ -55,6 +61,9 @@ pub async fn query_consensus_state_heights(
             let states = query_consensus_states(
                 chain_id,
                 grpc_addr,
+                grpc_session,
+                grpc_limiter,
+                max_decoding_size,
                 QueryConsensusStatesRequest {
                     client_id: request.client_id,
                     pagination: request.pagination,
@@ -92,10 +101,13 @@ pub async fn query_consensus_state_heights(
 }
 
 /// Performs a `QueryConsensusStatesRequest` gRPC query to fetch all the consensus states
-/// associated with a given client.
+/// associated with a given client, decoding responses of up to `max_decoding_size` bytes.
 pub async fn query_consensus_states(
     chain_id: &ChainId,
     grpc_addr: &Uri,
+    grpc_session: Option<&Arc<Session>>,
+    grpc_limiter: Option<&Arc<RequestRateLimiter>>,
+    max_decoding_size: usize,
     request: QueryConsensusStatesRequest,
 ) -> Result<Vec<AnyConsensusStateWithHeight>, Error> {
     crate::telemetry!(query, chain_id, "query_consensus_states");
@@ -107,13 +119,15 @@ pub async fn query_consensus_states(
         }
     );
 
-    let mut client = create_grpc_client(
+    let mut client = create_pinned_grpc_client(
         grpc_addr,
+        grpc_session,
+        grpc_limiter,
         ibc_proto::ibc::core::client::v1::query_client::QueryClient::new,
     )
     .await?;
 
-    client = client.max_decoding_message_size(max_grpc_decoding_size().get_bytes() as usize);
+    client = client.max_decoding_message_size(max_decoding_size);
 
     let response = client
         .consensus_states(tonic::Request::new(request.into()))
@@ -141,3 +155,163 @@ pub async fn query_consensus_states(
 
     Ok(consensus_states)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use byte_unit::Byte;
+    use ibc_proto::ibc::core::client::v1::query_server::{Query, QueryServer};
+    use ibc_proto::ibc::core::client::v1::{
+        Height as RawHeight, QueryClientParamsRequest, QueryClientParamsResponse,
+        QueryClientStateRequest, QueryClientStateResponse, QueryClientStatesRequest,
+        QueryClientStatesResponse, QueryClientStatusRequest, QueryClientStatusResponse,
+        QueryConsensusStateHeightsRequest as RawQueryConsensusStateHeightsRequest,
+        QueryConsensusStateHeightsResponse, QueryConsensusStateRequest,
+        QueryConsensusStateResponse, QueryConsensusStatesRequest as RawQueryConsensusStatesRequest,
+        QueryConsensusStatesResponse, QueryUpgradedClientStateRequest,
+        QueryUpgradedClientStateResponse, QueryUpgradedConsensusStateRequest,
+        QueryUpgradedConsensusStateResponse,
+    };
+    use tonic::{Code, Request, Response, Status};
+
+    use crate::chain::requests::PageRequest;
+    use crate::config::default::max_grpc_decoding_size;
+    use crate::error::ErrorDetail;
+    use crate::util::mock_http::spawn_mock_grpc_server;
+
+    /// Enough consensus state heights for their response to exceed the 4 MiB
+    /// that tonic decodes by default.
+    const HEIGHTS: u64 = 700_000;
+
+    /// A node whose client has a consensus state at each of the given number of heights.
+    #[derive(Clone)]
+    struct ClientNode {
+        heights: u64,
+    }
+
+    #[tonic::async_trait]
+    impl Query for ClientNode {
+        async fn client_state(
+            &self,
+            _request: Request<QueryClientStateRequest>,
+        ) -> Result<Response<QueryClientStateResponse>, Status> {
+            Err(Status::unimplemented("client_state"))
+        }
+
+        async fn client_states(
+            &self,
+            _request: Request<QueryClientStatesRequest>,
+        ) -> Result<Response<QueryClientStatesResponse>, Status> {
+            Err(Status::unimplemented("client_states"))
+        }
+
+        async fn consensus_state(
+            &self,
+            _request: Request<QueryConsensusStateRequest>,
+        ) -> Result<Response<QueryConsensusStateResponse>, Status> {
+            Err(Status::unimplemented("consensus_state"))
+        }
+
+        async fn consensus_states(
+            &self,
+            _request: Request<RawQueryConsensusStatesRequest>,
+        ) -> Result<Response<QueryConsensusStatesResponse>, Status> {
+            Err(Status::unimplemented("consensus_states"))
+        }
+
+        async fn consensus_state_heights(
+            &self,
+            _request: Request<RawQueryConsensusStateHeightsRequest>,
+        ) -> Result<Response<QueryConsensusStateHeightsResponse>, Status> {
+            let consensus_state_heights = (1..=self.heights)
+                .rev()
+                .map(|revision_height| RawHeight {
+                    revision_number: 1,
+                    revision_height,
+                })
+                .collect();
+
+            Ok(Response::new(QueryConsensusStateHeightsResponse {
+                consensus_state_heights,
+                pagination: None,
+            }))
+        }
+
+        async fn client_status(
+            &self,
+            _request: Request<QueryClientStatusRequest>,
+        ) -> Result<Response<QueryClientStatusResponse>, Status> {
+            Err(Status::unimplemented("client_status"))
+        }
+
+        async fn client_params(
+            &self,
+            _request: Request<QueryClientParamsRequest>,
+        ) -> Result<Response<QueryClientParamsResponse>, Status> {
+            Err(Status::unimplemented("client_params"))
+        }
+
+        async fn upgraded_client_state(
+            &self,
+            _request: Request<QueryUpgradedClientStateRequest>,
+        ) -> Result<Response<QueryUpgradedClientStateResponse>, Status> {
+            Err(Status::unimplemented("upgraded_client_state"))
+        }
+
+        async fn upgraded_consensus_state(
+            &self,
+            _request: Request<QueryUpgradedConsensusStateRequest>,
+        ) -> Result<Response<QueryUpgradedConsensusStateResponse>, Status> {
+            Err(Status::unimplemented("upgraded_consensus_state"))
+        }
+    }
+
+    impl ClientNode {
+        /// Serves the node until the test exits, returning its gRPC address.
+        async fn spawn(&self) -> Uri {
+            let address = spawn_mock_grpc_server(QueryServer::new(self.clone())).await;
+
+            format!("http://{address}").parse().unwrap()
+        }
+    }
+
+    async fn query_heights(grpc_addr: &Uri, max_decoding_size: Byte) -> Result<Vec<Height>, Error> {
+        query_consensus_state_heights(
+            &ChainId::from_string("chain_A"),
+            grpc_addr,
+            None,
+            None,
+            max_decoding_size.get_bytes() as usize,
+            QueryConsensusStateHeightsRequest {
+                client_id: "07-tendermint-0".parse().unwrap(),
+                pagination: Some(PageRequest::all()),
+            },
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn consensus_state_heights_are_decoded_up_to_the_configured_size() {
+        let grpc_addr = ClientNode { heights: HEIGHTS }.spawn().await;
+
+        // The default 32 MiB of the config lifts the 4 MiB limit of tonic
+        let heights = query_heights(&grpc_addr, max_grpc_decoding_size())
+            .await
+            .unwrap();
+
+        assert_eq!(heights.len() as u64, HEIGHTS);
+        assert_eq!(heights[0], Height::new(1, 1).unwrap());
+        assert_eq!(heights.last(), Some(&Height::new(1, HEIGHTS).unwrap()));
+
+        // A lower configured size rejects the same response
+        let error = query_heights(&grpc_addr, Byte::from_bytes(4 * 1024 * 1024))
+            .await
+            .unwrap_err();
+
+        match error.detail() {
+            ErrorDetail::GrpcStatus(e) => assert_eq!(e.status.code(), Code::OutOfRange),
+            e => panic!("expected a gRPC status, got: {e}"),
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::chain::cosmos::request_rate::PacedRpcClient;
 use crate::chain::requests::CrossChainQueryRequest;
 use crate::error::Error;
 
@@ -5,10 +6,8 @@ use ibc_relayer_types::applications::ics31_icq::{
     error::Error as CrossChainQueryError, response::CrossChainQueryResponse,
 };
 
-use tendermint_rpc::{Client, HttpClient};
-
 pub async fn cross_chain_query_via_rpc(
-    client: &HttpClient,
+    client: &PacedRpcClient,
     cross_chain_query_request: CrossChainQueryRequest,
 ) -> Result<CrossChainQueryResponse, Error> {
     let hex_decoded_request = hex::decode(cross_chain_query_request.request.to_lowercase())
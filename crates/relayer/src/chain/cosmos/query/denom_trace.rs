@@ -1,17 +1,26 @@
 use http::uri::Uri;
+use std::sync::Arc;
 
 use ibc_proto::ibc::applications::transfer::v1::{
     query_client::QueryClient, QueryDenomTraceRequest,
 };
 
+use crate::chain::cosmos::request_rate::RequestRateLimiter;
+use crate::chain::cosmos::sticky_session::{create_pinned_grpc_client, Session};
 use crate::config::default::max_grpc_decoding_size;
 use crate::denom::DenomTrace;
 use crate::error::Error;
-use crate::util::create_grpc_client;
 
 // Uses the GRPC client to retrieve the denom trace for a specific hash
-pub async fn query_denom_trace(grpc_address: &Uri, hash: &str) -> Result<DenomTrace, Error> {
-    let mut client = create_grpc_client(grpc_address, QueryClient::new).await?;
+pub async fn query_denom_trace(
+    grpc_address: &Uri,
+    grpc_session: Option<&Arc<Session>>,
+    grpc_limiter: Option<&Arc<RequestRateLimiter>>,
+    hash: &str,
+) -> Result<DenomTrace, Error> {
+    let mut client =
+        create_pinned_grpc_client(grpc_address, grpc_session, grpc_limiter, QueryClient::new)
+            .await?;
 
     client = client.max_decoding_message_size(max_grpc_decoding_size().get_bytes() as usize);
 
@@ -9,18 +9,24 @@ use ibc_proto::ibc::apps::fee::v1::{
 use ibc_relayer_types::applications::ics29_fee::packet_fee::IdentifiedPacketFees;
 use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, PortId};
 use ibc_relayer_types::signer::Signer;
+use std::sync::Arc;
 use tonic::Code;
 
+use crate::chain::cosmos::request_rate::RequestRateLimiter;
+use crate::chain::cosmos::sticky_session::{create_pinned_grpc_client, Session};
 use crate::config::default::max_grpc_decoding_size;
 use crate::error::Error;
-use crate::util::create_grpc_client;
 
 pub async fn query_counterparty_payee(
     grpc_address: &Uri,
+    grpc_session: Option<&Arc<Session>>,
+    grpc_limiter: Option<&Arc<RequestRateLimiter>>,
     channel_id: &ChannelId,
     address: &Signer,
 ) -> Result<Option<String>, Error> {
-    let mut client = create_grpc_client(grpc_address, QueryClient::new).await?;
+    let mut client =
+        create_pinned_grpc_client(grpc_address, grpc_session, grpc_limiter, QueryClient::new)
+            .await?;
 
     client = client.max_decoding_message_size(max_grpc_decoding_size().get_bytes() as usize);
 
@@ -49,10 +55,14 @@ pub async fn query_counterparty_payee(
 
 pub async fn query_incentivized_packets(
     grpc_address: &Uri,
+    grpc_session: Option<&Arc<Session>>,
+    grpc_limiter: Option<&Arc<RequestRateLimiter>>,
     channel_id: &ChannelId,
     port_id: &PortId,
 ) -> Result<Vec<IdentifiedPacketFees>, Error> {
-    let mut client = create_grpc_client(grpc_address, QueryClient::new).await?;
+    let mut client =
+        create_pinned_grpc_client(grpc_address, grpc_session, grpc_limiter, QueryClient::new)
+            .await?;
 
     client = client.max_decoding_message_size(max_grpc_decoding_size().get_bytes() as usize);
 
@@ -82,9 +92,13 @@ pub async fn query_incentivized_packets(
 /// Query the incentivized packet for a specific packet at a specific height.
 pub async fn query_incentivized_packet(
     grpc_address: &Uri,
+    grpc_session: Option<&Arc<Session>>,
+    grpc_limiter: Option<&Arc<RequestRateLimiter>>,
     request: QueryIncentivizedPacketRequest,
 ) -> Result<QueryIncentivizedPacketResponse, Error> {
-    let mut client = create_grpc_client(grpc_address, QueryClient::new).await?;
+    let mut client =
+        create_pinned_grpc_client(grpc_address, grpc_session, grpc_limiter, QueryClient::new)
+            .await?;
 
     client = client.max_decoding_message_size(max_grpc_decoding_size().get_bytes() as usize);
 
@@ -0,0 +1,39 @@
+use ibc_proto::cosmos::base::node::v1beta1::ConfigResponse;
+use prost::Message;
+use tendermint_rpc::Url;
+
+use crate::chain::cosmos::query::abci_query;
+use crate::chain::cosmos::request_rate::PacedRpcClient;
+use crate::chain::requests::QueryHeight;
+use crate::config::{parse_gas_prices, GasPrice};
+use crate::error::Error;
+
+/// Query the configuration of the full node, via an ABCI query.
+pub async fn query_node_config(
+    rpc_client: &PacedRpcClient,
+    rpc_address: &Url,
+) -> Result<ConfigResponse, Error> {
+    let query_response = abci_query(
+        rpc_client,
+        rpc_address,
+        "/cosmos.base.node.v1beta1.Service/Config".to_owned(),
+        "".to_owned(),
+        QueryHeight::Latest.into(),
+        false,
+    )
+    .await?;
+
+    ConfigResponse::decode(query_response.value.as_ref()).map_err(|e| {
+        Error::protobuf_decode("cosmos.base.node.v1beta1.Service/Config".to_owned(), e)
+    })
+}
+
+/// Query the minimum gas prices that the full node accepts.
+pub async fn query_min_gas_prices(
+    rpc_client: &PacedRpcClient,
+    rpc_address: &Url,
+) -> Result<Vec<GasPrice>, Error> {
+    let config = query_node_config(rpc_client, rpc_address).await?;
+
+    Ok(parse_gas_prices(config.minimum_gas_price))
+}
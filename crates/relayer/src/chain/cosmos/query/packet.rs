@@ -0,0 +1,78 @@
+use ibc_relayer_types::core::ics04_channel::packet::Sequence;
+
+use crate::error::Error;
+
+/// The maximum number of packet commitment sequences sent in one `UnreceivedPackets` query.
+///
+/// The query does not support pagination, so on busy channels the sequences are split
+/// into pages to keep both the request and the response within the gRPC message size limit.
+pub const UNRECEIVED_PACKETS_PAGE_SIZE: usize = 1000;
+
+/// Queries the given sequences in pages of at most `page_size` sequences, and returns
+/// the concatenation of the sequences returned for each page, in ascending order.
+pub fn query_sequences_in_pages<F>(
+    sequences: &[Sequence],
+    page_size: usize,
+    mut query_page: F,
+) -> Result<Vec<Sequence>, Error>
+where
+    F: FnMut(&[Sequence]) -> Result<Vec<Sequence>, Error>,
+{
+    let mut result = Vec::new();
+
+    for page in sequences.chunks(page_size.max(1)) {
+        result.extend(query_page(page)?);
+    }
+
+    result.sort_unstable();
+    result.dedup();
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unreceived_packets_are_queried_in_pages() {
+        // A busy channel, on which every third packet was received
+        let commitments: Vec<Sequence> = (1..=2500).map(Sequence::from).collect();
+        let is_unreceived = |seq: &Sequence| u64::from(*seq) % 3 != 0;
+
+        let mut pages = 0;
+        let unreceived = query_sequences_in_pages(&commitments, 1000, |page| {
+            // The node rejects the queries of too many sequences at once
+            assert!(page.len() <= 1000, "page of {} sequences", page.len());
+            pages += 1;
+
+            // The node answers the sequences of a page in no particular order
+            Ok(page.iter().rev().copied().filter(is_unreceived).collect())
+        })
+        .unwrap();
+
+        assert_eq!(pages, 3);
+
+        let expected: Vec<Sequence> = commitments.into_iter().filter(is_unreceived).collect();
+        assert_eq!(unreceived, expected);
+    }
+
+    #[test]
+    fn failed_page_fails_query() {
+        let commitments: Vec<Sequence> = (1..=10).map(Sequence::from).collect();
+
+        let mut pages = 0;
+        let result = query_sequences_in_pages(&commitments, 4, |page| {
+            pages += 1;
+
+            if pages == 2 {
+                Err(Error::query("message too large".to_owned()))
+            } else {
+                Ok(page.to_vec())
+            }
+        });
+
+        assert!(result.is_err());
+        assert_eq!(pages, 2);
+    }
+}
@@ -0,0 +1,102 @@
+use serde_json::Value;
+use tendermint_rpc::Url;
+
+use crate::chain::cosmos::query::abci_query;
+use crate::chain::cosmos::request_rate::PacedRpcClient;
+use crate::chain::requests::QueryHeight;
+use crate::error::Error;
+
+/// Queries the list of relayer addresses allowed to submit packets to a
+/// permissioned chain, using the ABCI query path configured for that chain.
+///
+/// Returns `Ok(None)` if the response could not be interpreted as a whitelist.
+pub async fn query_relayer_whitelist(
+    rpc_client: &PacedRpcClient,
+    rpc_address: &Url,
+    path: &str,
+) -> Result<Option<Vec<String>>, Error> {
+    let response = abci_query(
+        rpc_client,
+        rpc_address,
+        path.to_owned(),
+        "".to_owned(),
+        QueryHeight::Latest.into(),
+        false,
+    )
+    .await?;
+
+    Ok(parse_relayer_whitelist(&response.value))
+}
+
+/// Parses the value returned by a relayer whitelist query.
+///
+/// The value is expected to be either a JSON array of addresses, or a legacy
+/// params subspace response of the form `{ "value": "[..]" }` whose value
+/// holds such an array.
+pub fn parse_relayer_whitelist(value: &[u8]) -> Option<Vec<String>> {
+    fn addresses(value: Value) -> Option<Vec<String>> {
+        match value {
+            Value::Array(entries) => entries
+                .into_iter()
+                .map(|entry| entry.as_str().map(str::to_owned))
+                .collect(),
+            Value::Object(mut fields) => match fields.remove("value")? {
+                Value::String(inner) => addresses(serde_json::from_str(&inner).ok()?),
+                inner => addresses(inner),
+            },
+            _ => None,
+        }
+    }
+
+    addresses(serde_json::from_slice(value).ok()?)
+}
+
+/// Returns a warning message if the given signer is not part of the whitelist.
+pub fn recv_signer_whitelist_warning(signer: &str, whitelist: &[String]) -> Option<String> {
+    if whitelist.iter().any(|address| address == signer) {
+        return None;
+    }
+
+    Some(format!(
+        "relayer address '{signer}' is not part of the chain's relayer whitelist, \
+        packets submitted by this relayer will be rejected by the chain"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_relayer_whitelist, recv_signer_whitelist_warning};
+
+    #[test]
+    fn parse_whitelist_array() {
+        let whitelist = parse_relayer_whitelist(br#"["cosmos1alice", "cosmos1bob"]"#);
+
+        assert_eq!(
+            whitelist,
+            Some(vec!["cosmos1alice".to_owned(), "cosmos1bob".to_owned()])
+        );
+    }
+
+    #[test]
+    fn parse_whitelist_params_subspace() {
+        let whitelist = parse_relayer_whitelist(
+            br#"{"subspace":"relayer","key":"Whitelist","value":"[\"cosmos1alice\"]"}"#,
+        );
+
+        assert_eq!(whitelist, Some(vec!["cosmos1alice".to_owned()]));
+    }
+
+    #[test]
+    fn parse_whitelist_malformed() {
+        assert_eq!(parse_relayer_whitelist(b"not json"), None);
+        assert_eq!(parse_relayer_whitelist(br#"[1, 2]"#), None);
+    }
+
+    #[test]
+    fn warn_when_signer_not_whitelisted() {
+        let whitelist = vec!["cosmos1alice".to_owned(), "cosmos1bob".to_owned()];
+
+        assert!(recv_signer_whitelist_warning("cosmos1carol", &whitelist).is_some());
+        assert!(recv_signer_whitelist_warning("cosmos1bob", &whitelist).is_none());
+    }
+}
@@ -1,15 +1,20 @@
 use ibc_relayer_types::core::ics02_client::height::Height;
 use ibc_relayer_types::core::ics04_channel::packet::{Packet, Sequence};
-use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId};
 use ibc_relayer_types::events::IbcEvent;
+use ibc_relayer_types::signer::Signer;
 use ibc_relayer_types::Height as ICSHeight;
 use tendermint::abci::Event;
 use tendermint::Hash as TxHash;
 use tendermint_rpc::endpoint::tx::Response as TxResponse;
-use tendermint_rpc::{Client, HttpClient, Order, Url};
+use tendermint_rpc::query::Query;
+use tendermint_rpc::{Order, Url};
 use tracing::warn;
 
-use crate::chain::cosmos::query::{header_query, packet_query, tx_hash_query};
+use crate::chain::cosmos::query::{
+    header_query, packet_query, send_packets_query, sender_txs_query, tx_hash_query,
+};
+use crate::chain::cosmos::request_rate::PacedRpcClient;
 use crate::chain::cosmos::types::events;
 use crate::chain::requests::{
     QueryClientEventRequest, QueryHeight, QueryPacketEventDataRequest, QueryTxHash, QueryTxRequest,
@@ -17,12 +22,22 @@ use crate::chain::requests::{
 use crate::error::Error;
 use crate::event::{ibc_event_try_from_abci_event, IbcEventWithHeight};
 
+/// Number of transactions fetched per page when querying the transactions of a sender,
+/// or the transactions sending or receiving packets on a channel.
+const SENDER_TXS_PER_PAGE: u8 = 100;
+
+/// Maximum number of pages fetched when querying the transactions of a sender,
+/// or the transactions sending or receiving packets on a channel.
+const MAX_SENDER_TXS_PAGES: u32 = 10;
+
 /// This function queries transactions for events matching certain criteria.
 /// 1. Client Update request - returns a vector with at most one update client event
 /// 2. Transaction event request - returns all IBC events resulted from a Tx execution
+/// 3. Sender request - returns all IBC events resulted from the Tx-es of a sender
+/// 4. Send packets request - returns the `SendPacket` events of the packets sent on a channel
 pub async fn query_txs(
     chain_id: &ChainId,
-    rpc_client: &HttpClient,
+    rpc_client: &PacedRpcClient,
     rpc_address: &Url,
     request: QueryTxRequest,
 ) -> Result<Vec<IbcEventWithHeight>, Error> {
@@ -99,9 +114,96 @@ pub async fn query_txs(
                 Ok(all_ibc_events_from_tx_search_response(chain_id, tx))
             }
         }
+
+        QueryTxRequest::Sender(request) => {
+            crate::time!(
+                "query_txs: sender transactions",
+                {
+                    "src_chain": chain_id,
+                }
+            );
+
+            paginated_tx_events(
+                chain_id,
+                rpc_client,
+                rpc_address,
+                sender_txs_query(&request),
+            )
+            .await
+        }
+
+        QueryTxRequest::SendPackets(request) => {
+            crate::time!(
+                "query_txs: send packet transactions",
+                {
+                    "src_chain": chain_id,
+                }
+            );
+
+            let events = paginated_tx_events(
+                chain_id,
+                rpc_client,
+                rpc_address,
+                send_packets_query(&request),
+            )
+            .await?;
+
+            // A transaction may send packets on other channels as well
+            Ok(events
+                .into_iter()
+                .filter(|event_with_height| match &event_with_height.event {
+                    IbcEvent::SendPacket(event) => {
+                        event.packet.source_channel == request.channel_id
+                            && event.packet.source_port == request.port_id
+                    }
+                    _ => false,
+                })
+                .collect())
+        }
     }
 }
 
+/// Returns all the IBC events of the transactions matching the given query, in ascending
+/// order of height. At most `MAX_SENDER_TXS_PAGES` pages of transactions are fetched.
+async fn paginated_tx_events(
+    chain_id: &ChainId,
+    rpc_client: &PacedRpcClient,
+    rpc_address: &Url,
+    query: Query,
+) -> Result<Vec<IbcEventWithHeight>, Error> {
+    let mut events = vec![];
+    let mut fetched = 0;
+
+    for page in 1..=MAX_SENDER_TXS_PAGES {
+        let response = rpc_client
+            .tx_search(
+                query.clone(),
+                false,
+                page,
+                SENDER_TXS_PER_PAGE,
+                Order::Ascending,
+            )
+            .await
+            .map_err(|e| Error::rpc(rpc_address.clone(), e))?;
+
+        let page_len = response.txs.len();
+        fetched += page_len;
+
+        events.extend(
+            response
+                .txs
+                .into_iter()
+                .flat_map(|tx| all_ibc_events_from_tx_search_response(chain_id, tx)),
+        );
+
+        if page_len < usize::from(SENDER_TXS_PER_PAGE) || fetched >= response.total_count as usize {
+            break;
+        }
+    }
+
+    Ok(events)
+}
+
 /// This function queries transactions for packet events matching certain criteria.
 ///
 /// It returns at most one packet event for each sequence specified in the request.
@@ -114,7 +216,7 @@ pub async fn query_txs(
 ///    packets ever sent.
 pub async fn query_packets_from_txs(
     chain_id: &ChainId,
-    rpc_client: &HttpClient,
+    rpc_client: &PacedRpcClient,
     rpc_address: &Url,
     request: &QueryPacketEventDataRequest,
 ) -> Result<Vec<IbcEventWithHeight>, Error> {
@@ -177,7 +279,7 @@ pub async fn query_packets_from_txs(
 /// It returns at most one packet event for each sequence specified in the request.
 pub async fn query_packets_from_block(
     chain_id: &ChainId,
-    rpc_client: &HttpClient,
+    rpc_client: &PacedRpcClient,
     rpc_address: &Url,
     request: &QueryPacketEventDataRequest,
 ) -> Result<Vec<IbcEventWithHeight>, Error> {
@@ -358,7 +460,7 @@ pub fn filter_matching_event(
 }
 
 pub async fn query_tx_response(
-    rpc_client: &HttpClient,
+    rpc_client: &PacedRpcClient,
     rpc_address: &Url,
     tx_hash: &TxHash,
 ) -> Result<Option<TxResponse>, Error> {
@@ -376,6 +478,66 @@ pub async fn query_tx_response(
     Ok(response.txs.into_iter().next())
 }
 
+/// Queries the most recent transactions including a message sent by the given account,
+/// returning at most `limit` of them, most recent first.
+pub async fn query_txs_by_sender(
+    rpc_client: &PacedRpcClient,
+    rpc_address: &Url,
+    sender: &Signer,
+    limit: u8,
+) -> Result<Vec<TxResponse>, Error> {
+    let response = rpc_client
+        .tx_search(
+            Query::eq("message.sender", sender.to_string()),
+            false,
+            1,
+            limit,
+            Order::Descending,
+        )
+        .await
+        .map_err(|e| Error::rpc(rpc_address.clone(), e))?;
+
+    Ok(response.txs)
+}
+
+/// Queries the transactions which received packets on the given channel, in the blocks
+/// from `min_height` on, most recent first. At most `MAX_SENDER_TXS_PAGES` pages of
+/// transactions are fetched.
+pub async fn query_recv_packet_txs(
+    rpc_client: &PacedRpcClient,
+    rpc_address: &Url,
+    channel_id: &ChannelId,
+    min_height: u64,
+) -> Result<Vec<TxResponse>, Error> {
+    let query = Query::eq("recv_packet.packet_dst_channel", channel_id.to_string())
+        .and_gte("tx.height", min_height);
+
+    let mut txs = vec![];
+
+    for page in 1..=MAX_SENDER_TXS_PAGES {
+        let response = rpc_client
+            .tx_search(
+                query.clone(),
+                false,
+                page,
+                SENDER_TXS_PER_PAGE,
+                Order::Descending,
+            )
+            .await
+            .map_err(|e| Error::rpc(rpc_address.clone(), e))?;
+
+        let page_len = response.txs.len();
+        txs.extend(response.txs);
+
+        if page_len < usize::from(SENDER_TXS_PER_PAGE) || txs.len() >= response.total_count as usize
+        {
+            break;
+        }
+    }
+
+    Ok(txs)
+}
+
 pub fn all_ibc_events_from_tx_search_response(
     chain_id: &ChainId,
     response: TxResponse,
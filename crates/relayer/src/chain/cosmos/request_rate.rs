@@ -0,0 +1,404 @@
+//! Pacing of the RPC requests and gRPC queries made to a chain, for nodes whose
+//! provider enforces a limit on the number of requests per second across all
+//! the types of queries.
+//!
+//! The requests are paced by a token bucket holding a single token, refilled at
+//! the configured rate, so that a burst of requests is spread evenly over time
+//! instead of hitting the provider all at once.
+//!
+//! The limiter is owned by the chain, which hands it over to the clients making
+//! the requests: the [`PacedRpcClient`] for the RPC requests, the gRPC channels
+//! for the gRPC queries, the light client and the event source. Each request
+//! takes its own token, so that a query made of several requests, eg. a paginated
+//! query or the broadcast of a tx followed by the polling of its result, is paced
+//! request by request.
+
+use core::num::NonZeroU32;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tendermint::abci::response::Info;
+use tendermint::block::Height as BlockHeight;
+use tendermint::evidence::Evidence;
+use tendermint::Hash;
+use tendermint_rpc::client::CompatMode;
+use tendermint_rpc::endpoint::validators::DEFAULT_VALIDATORS_PER_PAGE;
+use tendermint_rpc::endpoint::{
+    abci_query::AbciQuery, block, block_results, block_search, broadcast::tx_sync, commit,
+    consensus_params, evidence, header, status, tx, tx_search, validators,
+};
+use tendermint_rpc::query::Query;
+use tendermint_rpc::{Client, Error, HttpClient, Order, Paging};
+use tracing::trace;
+
+/// Paces the requests made to a chain to at most `max_requests_per_second`.
+#[derive(Debug)]
+pub struct RequestRateLimiter {
+    max_requests_per_second: NonZeroU32,
+
+    /// The time at which the next request may be made.
+    next_request: Mutex<Option<Instant>>,
+}
+
+impl RequestRateLimiter {
+    pub fn new(max_requests_per_second: NonZeroU32) -> Self {
+        Self {
+            max_requests_per_second,
+            next_request: Mutex::new(None),
+        }
+    }
+
+    /// The time between two consecutive requests.
+    fn interval(&self) -> Duration {
+        Duration::from_secs(1) / self.max_requests_per_second.get()
+    }
+
+    /// Reserves the earliest time, not before `now`, at which the next request may be made.
+    fn reserve(&self, now: Instant) -> Instant {
+        let mut next_request = self.next_request.lock().expect("poisoned lock");
+
+        let at = next_request.map_or(now, |next| next.max(now));
+        *next_request = Some(at + self.interval());
+
+        at
+    }
+
+    /// Reserves the next request, returning how long to wait before making it.
+    fn delay(&self) -> Duration {
+        let now = Instant::now();
+        let delay = self.reserve(now).saturating_duration_since(now);
+
+        if !delay.is_zero() {
+            trace!(
+                ?delay,
+                "delaying request to stay under the requests per second limit"
+            );
+        }
+
+        delay
+    }
+
+    /// Waits until the next request may be made.
+    pub async fn wait(&self) {
+        let delay = self.delay();
+
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Blocks the current thread until the next request may be made,
+    /// for the clients making their requests synchronously.
+    pub fn wait_blocking(&self) {
+        let delay = self.delay();
+
+        if !delay.is_zero() {
+            std::thread::sleep(delay);
+        }
+    }
+}
+
+/// An RPC client whose requests are paced by the limiter of the chain, if any.
+#[derive(Clone, Debug)]
+pub struct PacedRpcClient<C = HttpClient> {
+    inner: C,
+    limiter: Option<Arc<RequestRateLimiter>>,
+}
+
+impl<C> PacedRpcClient<C> {
+    pub fn new(inner: C, limiter: Option<Arc<RequestRateLimiter>>) -> Self {
+        Self { inner, limiter }
+    }
+
+    async fn pace(&self) {
+        if let Some(limiter) = &self.limiter {
+            limiter.wait().await;
+        }
+    }
+}
+
+impl PacedRpcClient<HttpClient> {
+    pub fn set_compat_mode(&mut self, compat: CompatMode) {
+        self.inner.set_compat_mode(compat);
+    }
+}
+
+impl<C: Client + Sync> PacedRpcClient<C> {
+    pub async fn abci_info(&self) -> Result<Info, Error> {
+        self.pace().await;
+        self.inner.abci_info().await
+    }
+
+    pub async fn abci_query(
+        &self,
+        path: Option<String>,
+        data: Vec<u8>,
+        height: Option<BlockHeight>,
+        prove: bool,
+    ) -> Result<AbciQuery, Error> {
+        self.pace().await;
+        self.inner.abci_query(path, data, height, prove).await
+    }
+
+    pub async fn block(
+        &self,
+        height: impl Into<BlockHeight> + Send,
+    ) -> Result<block::Response, Error> {
+        self.pace().await;
+        self.inner.block(height).await
+    }
+
+    pub async fn latest_block(&self) -> Result<block::Response, Error> {
+        self.pace().await;
+        self.inner.latest_block().await
+    }
+
+    pub async fn block_results(
+        &self,
+        height: impl Into<BlockHeight> + Send,
+    ) -> Result<block_results::Response, Error> {
+        self.pace().await;
+        self.inner.block_results(height).await
+    }
+
+    pub async fn latest_block_results(&self) -> Result<block_results::Response, Error> {
+        self.pace().await;
+        self.inner.latest_block_results().await
+    }
+
+    pub async fn block_search(
+        &self,
+        query: Query,
+        page: u32,
+        per_page: u8,
+        order: Order,
+    ) -> Result<block_search::Response, Error> {
+        self.pace().await;
+        self.inner.block_search(query, page, per_page, order).await
+    }
+
+    pub async fn broadcast_tx_sync(&self, tx: Vec<u8>) -> Result<tx_sync::Response, Error> {
+        self.pace().await;
+        self.inner.broadcast_tx_sync(tx).await
+    }
+
+    pub async fn commit(
+        &self,
+        height: impl Into<BlockHeight> + Send,
+    ) -> Result<commit::Response, Error> {
+        self.pace().await;
+        self.inner.commit(height).await
+    }
+
+    pub async fn consensus_params(
+        &self,
+        height: impl Into<BlockHeight> + Send,
+    ) -> Result<consensus_params::Response, Error> {
+        self.pace().await;
+        self.inner.consensus_params(height).await
+    }
+
+    pub async fn header(
+        &self,
+        height: impl Into<BlockHeight> + Send,
+    ) -> Result<header::Response, Error> {
+        self.pace().await;
+        self.inner.header(height).await
+    }
+
+    pub async fn broadcast_evidence(
+        &self,
+        evidence: Evidence,
+    ) -> Result<evidence::Response, Error> {
+        self.pace().await;
+        self.inner.broadcast_evidence(evidence).await
+    }
+
+    pub async fn health(&self) -> Result<(), Error> {
+        self.pace().await;
+        self.inner.health().await
+    }
+
+    pub async fn status(&self) -> Result<status::Response, Error> {
+        self.pace().await;
+        self.inner.status().await
+    }
+
+    pub async fn tx(&self, hash: Hash, prove: bool) -> Result<tx::Response, Error> {
+        self.pace().await;
+        self.inner.tx(hash, prove).await
+    }
+
+    pub async fn tx_search(
+        &self,
+        query: Query,
+        prove: bool,
+        page: u32,
+        per_page: u8,
+        order: Order,
+    ) -> Result<tx_search::Response, Error> {
+        self.pace().await;
+        self.inner
+            .tx_search(query, prove, page, per_page, order)
+            .await
+    }
+
+    /// Fetches all the pages of the validators with [`Paging::All`],
+    /// pacing the request of every page.
+    pub async fn validators(
+        &self,
+        height: impl Into<BlockHeight> + Send,
+        paging: Paging,
+    ) -> Result<validators::Response, Error> {
+        let height = height.into();
+
+        if !matches!(paging, Paging::All) {
+            self.pace().await;
+            return self.inner.validators(height, paging).await;
+        }
+
+        let mut validators = Vec::new();
+        let mut page_number = 1_usize;
+
+        loop {
+            let paging = Paging::Specific {
+                page_number: page_number.into(),
+                per_page: DEFAULT_VALIDATORS_PER_PAGE.into(),
+            };
+
+            self.pace().await;
+            let response = self.inner.validators(height, paging).await?;
+            validators.extend(response.validators);
+
+            if validators.len() as i32 == response.total {
+                return Ok(validators::Response::new(
+                    response.block_height,
+                    validators,
+                    response.total,
+                ));
+            }
+
+            page_number += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_relayer_types::core::ics24_host::identifier::ChannelId;
+    use tendermint_rpc::Url;
+
+    use crate::chain::cosmos::query::tx::query_recv_packet_txs;
+    use crate::util::mock_http::{spawn_mock_http_server, MockResponse};
+
+    fn limiter(max_requests_per_second: u32) -> RequestRateLimiter {
+        RequestRateLimiter::new(NonZeroU32::new(max_requests_per_second).unwrap())
+    }
+
+    #[test]
+    fn burst_is_paced_under_the_limit() {
+        let limiter = limiter(5);
+        let now = Instant::now();
+
+        let reservations: Vec<Instant> = (0..20).map(|_| limiter.reserve(now)).collect();
+
+        // The requests are spread evenly, the first one going out right away
+        assert_eq!(reservations[0], now);
+        for pair in reservations.windows(2) {
+            assert_eq!(pair[1] - pair[0], Duration::from_millis(200));
+        }
+
+        // No window of one second holds more requests than the limit
+        for start in &reservations {
+            let in_window = reservations
+                .iter()
+                .filter(|at| *at >= start && **at < *start + Duration::from_secs(1))
+                .count();
+
+            assert!(in_window <= 5, "{in_window} requests in one second");
+        }
+    }
+
+    #[test]
+    fn idle_limiter_does_not_delay() {
+        let limiter = limiter(2);
+        let now = Instant::now();
+
+        assert_eq!(limiter.reserve(now), now);
+        assert_eq!(limiter.reserve(now), now + Duration::from_millis(500));
+
+        // Once the pending requests went out, the next one is not delayed
+        let later = now + Duration::from_secs(10);
+        assert_eq!(limiter.reserve(later), later);
+    }
+
+    /// A node holding `total` txs, answering every `tx_search` with a full page of 100 txs,
+    /// which records the time at which each request is received.
+    fn paginated_node(total: usize) -> (Url, Arc<Mutex<Vec<Instant>>>) {
+        let received = Arc::new(Mutex::new(Vec::new()));
+
+        let (address, _requests) = spawn_mock_http_server({
+            let received = received.clone();
+
+            move |request| {
+                received.lock().unwrap().push(Instant::now());
+
+                let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+                let tx = r#"{
+                    "hash": "E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855",
+                    "height": "10",
+                    "index": 0,
+                    "tx_result": {
+                        "code": 0, "codespace": "", "data": null, "events": [],
+                        "gas_used": "0", "gas_wanted": "0", "info": "", "log": ""
+                    },
+                    "tx": ""
+                }"#;
+                let txs = vec![tx; 100].join(",");
+
+                MockResponse::json(format!(
+                    r#"{{ "jsonrpc": "2.0", "id": {}, "result": {{
+                        "txs": [{txs}], "total_count": "{total}"
+                    }} }}"#,
+                    body["id"]
+                ))
+            }
+        });
+
+        (format!("http://{address}").parse().unwrap(), received)
+    }
+
+    #[tokio::test]
+    async fn paginated_queries_sharing_the_limiter_of_the_chain_are_paced() {
+        let (rpc_address, received) = paginated_node(300);
+        let limiter = Arc::new(limiter(10));
+
+        // The clients of the chain, eg. of its runtime and of a CLI command, share its limiter
+        let rpc_client = PacedRpcClient::new(
+            HttpClient::new(rpc_address.clone()).unwrap(),
+            Some(limiter.clone()),
+        );
+        let other_client = rpc_client.clone();
+        let channel_id = ChannelId::new(0);
+
+        let (txs, other_txs) = tokio::join!(
+            query_recv_packet_txs(&rpc_client, &rpc_address, &channel_id, 1),
+            query_recv_packet_txs(&other_client, &rpc_address, &channel_id, 1),
+        );
+        assert_eq!(txs.unwrap().len(), 300);
+        assert_eq!(other_txs.unwrap().len(), 300);
+
+        // Each query fetched its three pages, the six requests being spread over half
+        // a second instead of reaching the node at once
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 6);
+
+        let elapsed = *received.last().unwrap() - received[0];
+        assert!(
+            elapsed >= Duration::from_millis(480),
+            "6 requests received in {elapsed:?}"
+        );
+    }
+}
@@ -1,14 +1,14 @@
-use core::time::Duration;
-use std::thread;
-
 use tracing::{debug, error, info, instrument, warn};
 
 use ibc_proto::google::protobuf::Any;
 use tendermint::abci::Code;
 use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
-use tendermint_rpc::HttpClient;
 
+use crate::chain::cosmos::chain_id_drift::is_signature_verification_failure;
+use crate::chain::cosmos::gas::raise_to_min_gas_price;
 use crate::chain::cosmos::query::account::refresh_account;
+use crate::chain::cosmos::query::node_config::query_min_gas_prices;
+use crate::chain::cosmos::request_rate::PacedRpcClient;
 use crate::chain::cosmos::tx::estimate_fee_and_send_tx;
 use crate::chain::cosmos::types::account::Account;
 use crate::chain::cosmos::types::config::TxConfig;
@@ -18,13 +18,15 @@ use crate::keyring::{Secp256k1KeyPair, SigningKeyPair};
 use crate::sdk_error::sdk_error_from_tx_sync_error_code;
 use crate::{telemetry, time};
 
-// Delay in milliseconds before retrying in the case of account sequence mismatch.
-const ACCOUNT_SEQUENCE_RETRY_DELAY: u64 = 300;
-
 // The error "incorrect account sequence" is defined as the unique error code 32 in cosmos-sdk:
 // https://github.com/cosmos/cosmos-sdk/blob/v0.44.0/types/errors/errors.go#L115-L117
 const INCORRECT_ACCOUNT_SEQUENCE_ERR: u32 = 32;
 
+// The error "insufficient fee" is defined as the error code 13 of the `sdk` codespace in cosmos-sdk:
+// https://github.com/cosmos/cosmos-sdk/blob/v0.47.0/types/errors/errors.go#L56-L57
+const INSUFFICIENT_FEE_ERR: u32 = 13;
+const SDK_CODESPACE: &str = "sdk";
+
 /// Try to `send_tx` and retry on account sequence error with re-cached account s.n.
 /// An account sequence error can occur if the account sequence that
 /// the relayer caches becomes outdated.
@@ -35,7 +37,8 @@ const INCORRECT_ACCOUNT_SEQUENCE_ERR: u32 = 32;
 ///     the `broadcast_tx_sync` step.
 ///
 /// We treat both cases by re-fetching the account sequence number
-/// from the full node and retrying once with the new account s.n.
+/// from the full node and retrying with the new account s.n., after
+/// the delays and up to the number of attempts of the chain's `retry_strategy`.
 #[instrument(
     name = "send_tx_with_account_sequence_retry",
     level = "error",
@@ -46,7 +49,7 @@ const INCORRECT_ACCOUNT_SEQUENCE_ERR: u32 = 32;
     ),
 )]
 pub async fn send_tx_with_account_sequence_retry(
-    rpc_client: &HttpClient,
+    rpc_client: &PacedRpcClient,
     config: &TxConfig,
     key_pair: &Secp256k1KeyPair,
     account: &mut Account,
@@ -62,10 +65,19 @@ pub async fn send_tx_with_account_sequence_retry(
 
     let _message_count = messages.len() as u64;
 
-    let response = do_send_tx_with_account_sequence_retry(
+    let response = match do_send_tx_with_account_sequence_retry(
         rpc_client, config, key_pair, account, tx_memo, messages,
     )
-    .await;
+    .await
+    {
+        Ok(response) if is_insufficient_fee(&response) => {
+            retry_send_tx_with_min_gas_price(
+                rpc_client, config, key_pair, account, tx_memo, messages, response,
+            )
+            .await
+        }
+        response => response,
+    };
 
     if response.is_ok() {
         telemetry!(messages_submitted, &config.chain_id, _message_count);
@@ -75,7 +87,7 @@ pub async fn send_tx_with_account_sequence_retry(
 }
 
 async fn do_send_tx_with_account_sequence_retry(
-    rpc_client: &HttpClient,
+    rpc_client: &PacedRpcClient,
     config: &TxConfig,
     key_pair: &Secp256k1KeyPair,
     account: &mut Account,
@@ -90,11 +102,17 @@ async fn do_send_tx_with_account_sequence_retry(
             warn!(
                 error = %e,
                 "failed to estimate gas because of a mismatched account sequence number, \
-                refreshing account sequence number and retrying once",
+                refreshing account sequence number and retrying",
             );
 
             refresh_account_and_retry_send_tx_with_account_sequence(
-                rpc_client, config, key_pair, account, tx_memo, messages,
+                rpc_client,
+                config,
+                key_pair,
+                account,
+                tx_memo,
+                messages,
+                e.to_string(),
             )
             .await
         }
@@ -107,7 +125,7 @@ async fn do_send_tx_with_account_sequence_retry(
             warn!(
                 ?response,
                 "failed to broadcast tx because of a mismatched account sequence number, \
-                refreshing account sequence number and retrying once"
+                refreshing account sequence number and retrying"
             );
 
             telemetry!(
@@ -118,7 +136,13 @@ async fn do_send_tx_with_account_sequence_retry(
             );
 
             refresh_account_and_retry_send_tx_with_account_sequence(
-                rpc_client, config, key_pair, account, tx_memo, messages,
+                rpc_client,
+                config,
+                key_pair,
+                account,
+                tx_memo,
+                messages,
+                response.log.clone(),
             )
             .await
         }
@@ -160,6 +184,50 @@ async fn do_send_tx_with_account_sequence_retry(
                     Ok(response)
                 }
 
+                // The fee was rejected by the node, most likely because its minimum gas price
+                // was raised, retried by the caller at the node's minimum gas price.
+                Code::Err(code) if is_insufficient_fee(&response) => {
+                    warn!(
+                        ?response,
+                        ?code,
+                        "failed to broadcast tx because of an insufficient fee"
+                    );
+
+                    telemetry!(
+                        broadcast_errors,
+                        &account.address.to_string(),
+                        code.into(),
+                        &response.log
+                    );
+
+                    Ok(response)
+                }
+
+                // The signature of the tx failed to verify, which is the case of every tx if the
+                // chain id of the chain drifted from the configured one, eg. after an unexpected
+                // upgrade. The submission of txs to the chain is then halted instead of retried.
+                Code::Err(code) if is_signature_verification_failure(&response) => {
+                    telemetry!(
+                        broadcast_errors,
+                        &account.address.to_string(),
+                        code.into(),
+                        &response.log
+                    );
+
+                    config
+                        .chain_id_drift
+                        .check(rpc_client, &config.rpc_address, &config.chain_id)
+                        .await?;
+
+                    error!(
+                        ?response,
+                        ?code,
+                        "failed to broadcast tx because of a failed signature verification"
+                    );
+
+                    Ok(response)
+                }
+
                 // Gas estimation succeeded, but broadcast_tx_sync failed with unrecoverable error.
                 Code::Err(code) => {
                     // Do not increase the account s.n. since CheckTx step of broadcast_tx_sync has failed.
@@ -191,25 +259,128 @@ async fn do_send_tx_with_account_sequence_retry(
     }
 }
 
+/// After each of the delays of the `retry_strategy` of the chain, refresh the account
+/// sequence number and resubmit the tx, as long as the submission fails because of
+/// a mismatched account sequence number.
+///
+/// Fails with the number of attempts and the last error once they are exhausted.
 async fn refresh_account_and_retry_send_tx_with_account_sequence(
-    rpc_client: &HttpClient,
+    rpc_client: &PacedRpcClient,
     config: &TxConfig,
     key_pair: &Secp256k1KeyPair,
     account: &mut Account,
     tx_memo: &Memo,
     messages: &[Any],
+    mut last_error: String,
 ) -> Result<Response, Error> {
     let key_account = key_pair.account();
-    // Re-fetch the account sequence number
-    refresh_account(&config.grpc_address, &key_account, account).await?;
+    let max_attempts = config.retry_strategy.max_attempts.get();
+
+    for (attempt, delay) in (2..).zip(config.retry_strategy.delays()) {
+        // Retry after delay
+        tokio::time::sleep(delay).await;
+
+        // Re-fetch the account sequence number, once the pending txs had time to be committed
+        refresh_account(
+            &config.grpc_address,
+            config.grpc_session.as_ref(),
+            config.request_limiter.as_ref(),
+            &key_account,
+            config.account_query,
+            account,
+        )
+        .await?;
+
+        debug!(attempt, max_attempts, ?delay, "retrying to send tx");
+
+        match estimate_fee_and_send_tx(rpc_client, config, key_pair, account, tx_memo, messages)
+            .await
+        {
+            Err(e) if mismatch_account_sequence_number_error_requires_refresh(&e) => {
+                warn!(
+                    error = %e,
+                    attempt,
+                    "retried tx failed with a mismatched account sequence number"
+                );
+
+                last_error = e.to_string();
+            }
+            Ok((response, _)) if response.code == Code::from(INCORRECT_ACCOUNT_SEQUENCE_ERR) => {
+                warn!(
+                    ?response,
+                    attempt, "retried tx failed with a mismatched account sequence number"
+                );
+
+                last_error = response.log;
+            }
+            result => return result.map(|(response, _)| response),
+        }
+    }
 
-    // Retry after delay
-    thread::sleep(Duration::from_millis(ACCOUNT_SEQUENCE_RETRY_DELAY));
+    Err(Error::tx_retries_exhausted(max_attempts, last_error))
+}
 
-    let (estimate_result, _) =
-        estimate_fee_and_send_tx(rpc_client, config, key_pair, account, tx_memo, messages).await?;
+/// Re-query the minimum gas price of the node after a tx was rejected because
+/// of an insufficient fee, and resubmit the tx once at that price if it is
+/// higher than the configured gas price.
+///
+/// The adjusted gas price only applies to the resubmitted tx, subsequent txs
+/// are still submitted with the configured gas price.
+async fn retry_send_tx_with_min_gas_price(
+    rpc_client: &PacedRpcClient,
+    config: &TxConfig,
+    key_pair: &Secp256k1KeyPair,
+    account: &mut Account,
+    tx_memo: &Memo,
+    messages: &[Any],
+    response: Response,
+) -> Result<Response, Error> {
+    let min_gas_prices = match query_min_gas_prices(rpc_client, &config.rpc_address).await {
+        Ok(min_gas_prices) => min_gas_prices,
+        Err(e) => {
+            error!(
+                error = %e,
+                "failed to query the minimum gas price of the node, not retrying"
+            );
+
+            return Ok(response);
+        }
+    };
+
+    let Some(gas_config) = raise_to_min_gas_price(&config.gas_config, &min_gas_prices) else {
+        error!(
+            ?min_gas_prices,
+            gas_price = ?config.gas_config.gas_price,
+            "node does not require a higher gas price than the configured one, not retrying"
+        );
+
+        return Ok(response);
+    };
+
+    warn!(
+        gas_price.old = %config.gas_config.gas_price.price,
+        gas_price.new = %gas_config.gas_price.price,
+        denom = %gas_config.gas_price.denom,
+        "minimum gas price of the node is higher than the configured `gas_price`, \
+        retrying once at the node's minimum gas price; \
+        consider updating `gas_price` in the configuration"
+    );
+
+    let config = TxConfig {
+        gas_config,
+        ..config.clone()
+    };
+
+    do_send_tx_with_account_sequence_retry(
+        rpc_client, &config, key_pair, account, tx_memo, messages,
+    )
+    .await
+}
 
-    Ok(estimate_result)
+/// Whether the given response of `broadcast_tx_sync` indicates that the tx
+/// was rejected because its fee is lower than the node's minimum gas price.
+pub(crate) fn is_insufficient_fee(response: &Response) -> bool {
+    response.codespace == SDK_CODESPACE && response.code == Code::from(INSUFFICIENT_FEE_ERR)
 }
 
 /// Determine whether the given error yielded by `tx_simulate`
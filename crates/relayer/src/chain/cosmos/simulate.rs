@@ -1,12 +1,20 @@
+use std::sync::Arc;
+
 use ibc_proto::cosmos::tx::v1beta1::service_client::ServiceClient;
 use ibc_proto::cosmos::tx::v1beta1::{SimulateRequest, SimulateResponse, Tx};
 use tonic::codegen::http::Uri;
 
+use crate::chain::cosmos::request_rate::RequestRateLimiter;
+use crate::chain::cosmos::sticky_session::{create_pinned_grpc_client, Session};
 use crate::config::default::max_grpc_decoding_size;
 use crate::error::Error;
-use crate::util::create_grpc_client;
 
-pub async fn send_tx_simulate(grpc_address: &Uri, tx: Tx) -> Result<SimulateResponse, Error> {
+pub async fn send_tx_simulate(
+    grpc_address: &Uri,
+    grpc_session: Option<&Arc<Session>>,
+    grpc_limiter: Option<&Arc<RequestRateLimiter>>,
+    tx: Tx,
+) -> Result<SimulateResponse, Error> {
     let mut tx_bytes = vec![];
     prost::Message::encode(&tx, &mut tx_bytes)
         .map_err(|e| Error::protobuf_encode(String::from("Transaction"), e))?;
@@ -16,7 +24,9 @@ pub async fn send_tx_simulate(grpc_address: &Uri, tx: Tx) -> Result<SimulateResp
         ..Default::default()
     };
 
-    let mut client = create_grpc_client(grpc_address, ServiceClient::new).await?;
+    let mut client =
+        create_pinned_grpc_client(grpc_address, grpc_session, grpc_limiter, ServiceClient::new)
+            .await?;
 
     client = client.max_decoding_message_size(max_grpc_decoding_size().get_bytes() as usize);
 
@@ -0,0 +1,521 @@
+//! Pinning of the queries to a chain to the same backend node, when its endpoints are
+//! behind a load balancer with sticky sessions, so that consecutive queries, eg. of a
+//! proof and of the header at its height, are not served by nodes at different heights.
+//!
+//! The RPC and gRPC endpoints are pinned through separate sessions, as they are usually
+//! served by different load balancers. The RPC session is checked with the load balancer
+//! at the start of every operation of the chain runtime, so that the relayer follows the
+//! load balancer when it moves the session to another node, eg. when the node it was
+//! pinned to goes down. The gRPC session is captured anew from every gRPC response.
+
+use core::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use http::Uri;
+use tendermint_rpc::{HttpClient, Url};
+use tonic::body::BoxBody;
+use tonic::codegen::{BoxFuture, Service};
+use tonic::transport::Channel;
+use tracing::debug;
+
+use crate::chain::cosmos::request_rate::RequestRateLimiter;
+use crate::config::StickySessionConfig;
+use crate::error::Error;
+use crate::util::create_grpc_client;
+use crate::HERMES_VERSION;
+
+/// A session of a load balancer, captured from its responses.
+#[derive(Debug)]
+pub struct Session {
+    config: StickySessionConfig,
+
+    /// The name and value of the request header carrying the session, once captured.
+    pinned: Mutex<Option<(String, String)>>,
+}
+
+impl Session {
+    pub fn new(config: StickySessionConfig) -> Self {
+        Self {
+            config,
+            pinned: Mutex::new(None),
+        }
+    }
+
+    /// Captures the session from the given headers of a response of the load balancer,
+    /// returning whether they carried one.
+    pub fn capture<'a>(&self, headers: impl IntoIterator<Item = (&'a str, &'a [u8])>) -> bool {
+        let session = headers
+            .into_iter()
+            .filter_map(|(name, value)| Some((name, std::str::from_utf8(value).ok()?)))
+            .find_map(|(name, value)| match &self.config {
+                StickySessionConfig::Cookie(cookie) if name.eq_ignore_ascii_case("set-cookie") => {
+                    let pair = value.split(';').next()?.trim();
+                    let (cookie_name, _) = pair.split_once('=')?;
+
+                    (cookie_name.trim() == cookie).then(|| ("cookie".to_string(), pair.to_string()))
+                }
+                StickySessionConfig::Header(header) if name.eq_ignore_ascii_case(header) => {
+                    Some((header.to_lowercase(), value.to_string()))
+                }
+                _ => None,
+            });
+
+        match session {
+            Some(session) => {
+                *self.pinned.lock().expect("poisoned lock") = Some(session);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the name and value of the request header carrying the session,
+    /// or `None` if no session was captured.
+    pub fn header(&self) -> Option<(String, String)> {
+        self.pinned.lock().expect("poisoned lock").clone()
+    }
+}
+
+/// The sessions pinning the queries to a chain to backend nodes of its load balancers.
+#[derive(Debug)]
+pub struct StickySession {
+    rpc_address: Url,
+    rpc_timeout: Duration,
+    rpc: Session,
+    grpc: Arc<Session>,
+
+    /// The pacing of the requests made to the chain, if limited
+    limiter: Option<Arc<RequestRateLimiter>>,
+}
+
+impl StickySession {
+    pub fn new(
+        config: StickySessionConfig,
+        rpc_address: Url,
+        rpc_timeout: Duration,
+        limiter: Option<Arc<RequestRateLimiter>>,
+    ) -> Self {
+        Self {
+            rpc_address,
+            rpc_timeout,
+            rpc: Session::new(config.clone()),
+            grpc: Arc::new(Session::new(config)),
+            limiter,
+        }
+    }
+
+    /// The session of the RPC endpoint.
+    pub fn rpc_session(&self) -> &Session {
+        &self.rpc
+    }
+
+    /// The session of the gRPC endpoint.
+    pub fn grpc_session(&self) -> &Arc<Session> {
+        &self.grpc
+    }
+
+    /// Checks the RPC session with the load balancer at the start of an operation, by
+    /// requesting the health of the node it is pinned to. The load balancer keeps the
+    /// session if that node is still up, and assigns a new one otherwise.
+    ///
+    /// Returns whether the session changed, in which case the RPC clients sending
+    /// the session must be built anew.
+    pub async fn start_operation(&self) -> Result<bool, Error> {
+        let current = self.rpc.header();
+
+        let url = format!(
+            "{}/health",
+            self.rpc_address.to_string().trim_end_matches('/')
+        );
+
+        let mut request = reqwest::Client::new().get(&url).timeout(self.rpc_timeout);
+        if let Some((name, value)) = &current {
+            request = request.header(name.as_str(), value.as_str());
+        }
+
+        if let Some(limiter) = &self.limiter {
+            limiter.wait().await;
+        }
+
+        let response = request.send().await.map_err(Error::http_request)?;
+
+        self.rpc.capture(
+            response
+                .headers()
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.as_bytes())),
+        );
+
+        let changed = self.rpc.header() != current;
+        if changed {
+            debug!(
+                rpc_address = %self.rpc_address,
+                "pinned the queries to a backend node of the load balancer"
+            );
+        }
+
+        Ok(changed)
+    }
+}
+
+/// Builds an RPC client for the given address, which sends the given session,
+/// if any, along with every request.
+pub fn build_rpc_client(
+    rpc_address: &Url,
+    rpc_timeout: Duration,
+    session: Option<&Session>,
+) -> Result<HttpClient, Error> {
+    let mut builder = HttpClient::builder(rpc_address.clone().try_into().unwrap())
+        .user_agent(format!("hermes/{}", HERMES_VERSION));
+
+    if let Some(session) = session {
+        let mut headers = reqwest::header::HeaderMap::new();
+
+        if let Some((name, value)) = session.header() {
+            if let (Ok(name), Ok(value)) = (
+                reqwest::header::HeaderName::from_bytes(name.as_bytes()),
+                reqwest::header::HeaderValue::from_str(&value),
+            ) {
+                headers.insert(name, value);
+            }
+        }
+
+        let client = reqwest::Client::builder()
+            .user_agent(format!("hermes/{}", HERMES_VERSION))
+            .timeout(rpc_timeout)
+            .default_headers(headers)
+            .build()
+            .map_err(Error::http_request)?;
+
+        builder = builder.client(client);
+    }
+
+    builder
+        .build()
+        .map_err(|e| Error::rpc(rpc_address.clone(), e))
+}
+
+/// A gRPC channel which sends the session, if any, along with every request,
+/// and captures it anew from every response.
+///
+/// The requests are paced by the limiter of the chain, if any.
+#[derive(Clone, Debug)]
+pub struct SessionChannel<S = Channel> {
+    inner: S,
+    session: Option<Arc<Session>>,
+    limiter: Option<Arc<RequestRateLimiter>>,
+}
+
+impl<S> Service<http::Request<BoxBody>> for SessionChannel<S>
+where
+    S: Service<http::Request<BoxBody>, Response = http::Response<BoxBody>>,
+    S: Clone + Send + 'static,
+    S::Future: Send + 'static,
+    S::Error: Send + 'static,
+{
+    type Response = http::Response<BoxBody>;
+    type Error = S::Error;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: http::Request<BoxBody>) -> Self::Future {
+        let session = self.session.clone();
+
+        if let Some((name, value)) = session.as_ref().and_then(|session| session.header()) {
+            if let (Ok(name), Ok(value)) = (
+                http::HeaderName::from_bytes(name.as_bytes()),
+                http::HeaderValue::from_str(&value),
+            ) {
+                request.headers_mut().insert(name, value);
+            }
+        }
+
+        // The request is only handed over to the channel once the limiter allows it,
+        // using the channel made ready by `poll_ready`
+        let clone = self.inner.clone();
+        let mut inner = core::mem::replace(&mut self.inner, clone);
+        let limiter = self.limiter.clone();
+
+        Box::pin(async move {
+            if let Some(limiter) = limiter {
+                limiter.wait().await;
+            }
+
+            let response = inner.call(request).await?;
+
+            if let Some(session) = session {
+                session.capture(
+                    response
+                        .headers()
+                        .iter()
+                        .map(|(name, value)| (name.as_str(), value.as_bytes())),
+                );
+            }
+
+            Ok(response)
+        })
+    }
+}
+
+/// Creates a gRPC client for the given address, whose queries are pinned
+/// to a backend node of the load balancer through the given session, if any,
+/// and paced by the given limiter of the chain, if any.
+pub async fn create_pinned_grpc_client<T>(
+    grpc_addr: &Uri,
+    session: Option<&Arc<Session>>,
+    limiter: Option<&Arc<RequestRateLimiter>>,
+    client_constructor: impl FnOnce(SessionChannel) -> T,
+) -> Result<T, Error> {
+    create_grpc_client(grpc_addr, |inner| {
+        client_constructor(SessionChannel {
+            inner,
+            session: session.cloned(),
+            limiter: limiter.cloned(),
+        })
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::Infallible;
+    use core::num::NonZeroU32;
+    use std::sync::mpsc;
+    use std::time::Instant;
+
+    use tendermint_rpc::Client;
+
+    use super::*;
+    use crate::util::mock_http::{spawn_mock_http_server, MockRequest, MockResponse};
+
+    fn cookie_session() -> StickySessionConfig {
+        StickySessionConfig::Cookie("SERVERID".to_string())
+    }
+
+    /// A load balancer which pins the requests to the given backend node,
+    /// assigning the session of that node to the requests without it.
+    fn spawn_mock_load_balancer(
+        backend: Arc<Mutex<&'static str>>,
+    ) -> (Url, mpsc::Receiver<MockRequest>) {
+        let (address, requests) = spawn_mock_http_server(move |request| {
+            let session = format!("SERVERID={}", backend.lock().unwrap());
+            let response = MockResponse::json(r#"{"jsonrpc":"2.0","id":"","result":{}}"#);
+
+            if request.header("cookie") == Some(session.as_str()) {
+                response
+            } else {
+                response.with_header("Set-Cookie", &format!("{session}; Path=/; HttpOnly"))
+            }
+        });
+
+        (format!("http://{address}").parse().unwrap(), requests)
+    }
+
+    #[test]
+    fn session_is_captured_from_cookie_or_header() {
+        let headers: [(&str, &[u8]); 3] = [
+            ("set-cookie", b"other=1; Path=/"),
+            ("set-cookie", b"SERVERID=node-2; Path=/"),
+            ("x-backend", b"node-3"),
+        ];
+
+        let session = Session::new(cookie_session());
+        assert!(session.capture(headers));
+        assert_eq!(
+            session.header(),
+            Some(("cookie".to_string(), "SERVERID=node-2".to_string()))
+        );
+
+        let session = Session::new(StickySessionConfig::Header("X-Backend".to_string()));
+        assert!(session.capture(headers));
+        assert_eq!(
+            session.header(),
+            Some(("x-backend".to_string(), "node-3".to_string()))
+        );
+
+        let session = Session::new(StickySessionConfig::Cookie("AWSALB".to_string()));
+        assert!(!session.capture(headers));
+        assert_eq!(session.header(), None);
+    }
+
+    #[tokio::test]
+    async fn rpc_session_follows_the_load_balancer_across_operations() {
+        let backend = Arc::new(Mutex::new("node-1"));
+        let (rpc_address, requests) = spawn_mock_load_balancer(backend.clone());
+
+        let session = StickySession::new(
+            cookie_session(),
+            rpc_address.clone(),
+            Duration::from_secs(10),
+            None,
+        );
+
+        // The first operation does not carry a session, the load balancer assigns one
+        assert!(session.start_operation().await.unwrap());
+        assert_eq!(requests.recv().unwrap().header("cookie"), None);
+
+        let rpc_client = build_rpc_client(
+            &rpc_address,
+            Duration::from_secs(10),
+            Some(session.rpc_session()),
+        )
+        .unwrap();
+
+        // The responses of the mock are not valid, only the requests matter
+        let _ = rpc_client.abci_info().await;
+        assert_eq!(
+            requests.recv().unwrap().header("cookie"),
+            Some("SERVERID=node-1")
+        );
+
+        // The next operation keeps the session while the node is up
+        assert!(!session.start_operation().await.unwrap());
+        assert_eq!(
+            requests.recv().unwrap().header("cookie"),
+            Some("SERVERID=node-1")
+        );
+
+        // When the node goes down, the load balancer moves the session to another node,
+        // which the next operation follows
+        *backend.lock().unwrap() = "node-2";
+
+        assert!(session.start_operation().await.unwrap());
+        assert_eq!(
+            requests.recv().unwrap().header("cookie"),
+            Some("SERVERID=node-1")
+        );
+
+        let rpc_client = build_rpc_client(
+            &rpc_address,
+            Duration::from_secs(10),
+            Some(session.rpc_session()),
+        )
+        .unwrap();
+
+        let _ = rpc_client.abci_info().await;
+        assert_eq!(
+            requests.recv().unwrap().header("cookie"),
+            Some("SERVERID=node-2")
+        );
+
+        // The RPC session is not sent to the gRPC endpoint
+        assert_eq!(session.grpc_session().header(), None);
+    }
+
+    /// A gRPC backend which assigns the session of the given node to every response,
+    /// and records the session carried by each request and the time it was received.
+    #[derive(Clone, Default)]
+    struct MockGrpcBackend {
+        node: Arc<Mutex<&'static str>>,
+        received: Arc<Mutex<Vec<Option<String>>>>,
+        received_at: Arc<Mutex<Vec<Instant>>>,
+    }
+
+    impl Service<http::Request<BoxBody>> for MockGrpcBackend {
+        type Response = http::Response<BoxBody>;
+        type Error = Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, request: http::Request<BoxBody>) -> Self::Future {
+            let cookie = request
+                .headers()
+                .get("cookie")
+                .map(|value| value.to_str().unwrap().to_string());
+            self.received.lock().unwrap().push(cookie);
+            self.received_at.lock().unwrap().push(Instant::now());
+
+            let response = http::Response::builder()
+                .header(
+                    "set-cookie",
+                    format!("SERVERID={}; Path=/", self.node.lock().unwrap()),
+                )
+                .body(tonic::body::empty_body())
+                .unwrap();
+
+            Box::pin(async move { Ok(response) })
+        }
+    }
+
+    #[tokio::test]
+    async fn grpc_session_is_refreshed_from_every_response() {
+        let backend = MockGrpcBackend {
+            node: Arc::new(Mutex::new("node-1")),
+            ..Default::default()
+        };
+
+        let session = StickySession::new(
+            cookie_session(),
+            "http://127.0.0.1:26657".parse().unwrap(),
+            Duration::from_secs(10),
+            None,
+        );
+
+        let mut channel = SessionChannel {
+            inner: backend.clone(),
+            session: Some(session.grpc_session().clone()),
+            limiter: None,
+        };
+
+        let query = || http::Request::new(tonic::body::empty_body());
+
+        channel.call(query()).await.unwrap();
+        channel.call(query()).await.unwrap();
+
+        *backend.node.lock().unwrap() = "node-2";
+        channel.call(query()).await.unwrap();
+        channel.call(query()).await.unwrap();
+
+        assert_eq!(
+            *backend.received.lock().unwrap(),
+            [
+                None,
+                Some("SERVERID=node-1".to_string()),
+                Some("SERVERID=node-1".to_string()),
+                Some("SERVERID=node-2".to_string()),
+            ]
+        );
+
+        // The gRPC session is not sent to the RPC endpoint
+        assert_eq!(session.rpc_session().header(), None);
+    }
+
+    #[tokio::test]
+    async fn grpc_queries_sharing_the_limiter_of_the_chain_are_paced() {
+        let backend = MockGrpcBackend::default();
+        let limiter = RequestRateLimiter::new(NonZeroU32::new(10).unwrap());
+
+        // Every gRPC client of the chain sends its queries through a channel sharing its limiter
+        let channel = SessionChannel {
+            inner: backend.clone(),
+            session: None,
+            limiter: Some(Arc::new(limiter)),
+        };
+
+        let query = |mut channel: SessionChannel<MockGrpcBackend>| async move {
+            for _ in 0..3 {
+                channel
+                    .call(http::Request::new(tonic::body::empty_body()))
+                    .await
+                    .unwrap();
+            }
+        };
+        tokio::join!(query(channel.clone()), query(channel));
+
+        let received_at = backend.received_at.lock().unwrap();
+        assert_eq!(received_at.len(), 6);
+
+        let elapsed = *received_at.last().unwrap() - received_at[0];
+        assert!(
+            elapsed >= Duration::from_millis(480),
+            "6 queries received in {elapsed:?}"
+        );
+    }
+}
@@ -0,0 +1,183 @@
+//! Delays of the broadcast of the transactions submitted to a chain, so that tests can
+//! reproduce the races between the relayer and other agents deterministically, eg. by
+//! submitting a transaction from the account of the relayer while it waits to broadcast
+//! its own one.
+//!
+//! Only available with the `submit-delay` feature, which is meant for tests only.
+
+use core::time::Duration;
+use std::sync::Mutex;
+
+use tracing::warn;
+
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+/// The delay of the broadcast of the transactions submitted to a chain, held in the
+/// [`TxConfig`](super::types::config::TxConfig) of the chain and set through its
+/// [`ChainHandle`](crate::chain::handle::ChainHandle).
+#[derive(Debug, Default)]
+pub struct SubmitDelay {
+    state: Mutex<SubmitDelayState>,
+}
+
+#[derive(Debug, Default)]
+struct SubmitDelayState {
+    delay: Option<Duration>,
+    delayed: u64,
+}
+
+impl SubmitDelay {
+    /// Delay the broadcast of every transaction subsequently submitted by the given
+    /// amount, or stop delaying them if `None`.
+    ///
+    /// The broadcasts which are already being delayed still wait for their whole delay.
+    pub fn set(&self, delay: Option<Duration>) {
+        self.state.lock().expect("poisoned lock").delay = delay;
+    }
+
+    /// The number of broadcasts of transactions which were delayed so far,
+    /// counting those which are still being delayed.
+    pub fn delayed_submissions(&self) -> u64 {
+        self.state.lock().expect("poisoned lock").delayed
+    }
+
+    /// Wait for the delay set, if any, before broadcasting a transaction to the given chain.
+    pub(crate) async fn wait(&self, chain_id: &ChainId) {
+        let delay = {
+            let mut state = self.state.lock().expect("poisoned lock");
+
+            let Some(delay) = state.delay else {
+                return;
+            };
+
+            state.delayed += 1;
+            delay
+        };
+
+        warn!(%chain_id, ?delay, "delaying the broadcast of the tx, as set for testing");
+
+        tokio::time::sleep(delay).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+    use std::time::Instant;
+
+    use ibc_proto::google::protobuf::Any;
+    use ibc_relayer_types::core::ics02_client::msgs::update_client;
+    use tendermint_rpc::{HttpClient, Url};
+
+    use crate::chain::cosmos::batch::test_fixtures::{self, example_config};
+    use crate::chain::cosmos::gas::{BatchShape, GasEstimateSampler};
+    use crate::chain::cosmos::request_rate::PacedRpcClient;
+    use crate::chain::cosmos::tx::estimate_fee_and_send_tx;
+    use crate::chain::cosmos::types::account::{
+        Account, AccountAddress, AccountNumber, AccountSequence,
+    };
+    use crate::chain::cosmos::types::config::TxConfig;
+    use crate::config::gas_sampling::GasEstimationSampling;
+    use crate::config::types::Memo;
+    use crate::config::AddressType;
+    use crate::keyring::{Secp256k1KeyPair, SigningKeyPair};
+    use crate::util::mock_http::{spawn_mock_http_server, MockResponse};
+
+    const DELAY: Duration = Duration::from_millis(300);
+
+    /// A node accepting every tx broadcast to it.
+    fn node() -> Url {
+        let (address, _requests) = spawn_mock_http_server(|request| {
+            let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+            assert_eq!(body["method"], "broadcast_tx_sync");
+
+            MockResponse::json(format!(
+                r#"{{ "jsonrpc": "2.0", "id": {}, "result": {{
+                    "code": 0, "data": "", "log": "", "codespace": "",
+                    "hash": "57018296EE0919C9D351F2FFEA82A8D28DE223724D79965FC8D00A7477ED48BC"
+                }} }}"#,
+                body["id"]
+            ))
+        });
+
+        format!("http://{address}").parse().unwrap()
+    }
+
+    fn messages() -> Vec<Any> {
+        vec![Any {
+            type_url: update_client::TYPE_URL.to_string(),
+            value: vec![],
+        }]
+    }
+
+    /// The tx config of the given chain of the example config, connected to the given node,
+    /// which estimates the gas of the test messages without simulating them.
+    fn tx_config(chain: usize, rpc_address: &Url) -> TxConfig {
+        let mut tx_config = test_fixtures::tx_config(&example_config().chains[chain]);
+        tx_config.rpc_address = rpc_address.clone();
+
+        let sampler = GasEstimateSampler::new(GasEstimationSampling::enabled(100).unwrap());
+        sampler.record_simulation(BatchShape::of(&messages()), 100_000);
+        tx_config.gas_config.gas_sampler = Arc::new(sampler);
+
+        tx_config
+    }
+
+    /// Sends the test messages, returning how long it took.
+    async fn send_tx(config: &TxConfig) -> Duration {
+        let rpc_client =
+            PacedRpcClient::new(HttpClient::new(config.rpc_address.clone()).unwrap(), None);
+        let key_pair = Secp256k1KeyPair::from_mnemonic(
+            "abandon abandon abandon abandon abandon abandon \
+            abandon abandon abandon abandon abandon about",
+            &"m/44'/118'/0'/0/0".parse().unwrap(),
+            &AddressType::Cosmos,
+            "cosmos",
+        )
+        .unwrap();
+        let account = Account {
+            address: AccountAddress::new(key_pair.account()),
+            number: AccountNumber::new(1),
+            sequence: AccountSequence::new(0),
+        };
+
+        let start = Instant::now();
+
+        let (response, _) = estimate_fee_and_send_tx(
+            &rpc_client,
+            config,
+            &key_pair,
+            &account,
+            &Memo::new("hermes").unwrap(),
+            &messages(),
+        )
+        .await
+        .unwrap();
+        assert!(response.code.is_ok());
+
+        start.elapsed()
+    }
+
+    #[tokio::test]
+    async fn only_delays_the_broadcasts_to_the_chain_while_set() {
+        let rpc_address = node();
+        let (config_a, config_b) = (tx_config(0, &rpc_address), tx_config(1, &rpc_address));
+
+        config_a.submit_delay.set(Some(DELAY));
+
+        // The delay applies to the txs of the chain, whatever the tx config they are sent with
+        assert!(send_tx(&config_a.clone()).await >= DELAY);
+        assert_eq!(config_a.submit_delay.delayed_submissions(), 1);
+
+        // But not to the txs of other chains
+        assert!(send_tx(&config_b).await < DELAY);
+        assert_eq!(config_b.submit_delay.delayed_submissions(), 0);
+
+        config_a.submit_delay.set(None);
+
+        assert!(send_tx(&config_a).await < DELAY);
+        assert_eq!(config_a.submit_delay.delayed_submissions(), 1);
+    }
+}
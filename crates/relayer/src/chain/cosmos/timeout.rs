@@ -0,0 +1,44 @@
+//! Encoding of the timeout timestamps of packets in the unit
+//! in which a chain interprets them, see [`TimestampUnit`].
+
+use ibc_relayer_types::timestamp::Timestamp;
+
+use crate::config::TimestampUnit;
+
+/// Encodes the given timestamp in the given unit, for the chains which interpret
+/// the timeout timestamps of packets in another unit than nanoseconds.
+///
+/// The timestamp is rounded down to the unit, and stays unset if it is not set.
+pub fn encode_timeout_timestamp(timestamp: Timestamp, unit: TimestampUnit) -> Timestamp {
+    let encoded = timestamp.nanoseconds() / unit.nanoseconds();
+
+    Timestamp::from_nanoseconds(encoded).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timeout_timestamp_is_encoded_in_unit() {
+        let timestamp = Timestamp::from_nanoseconds(1_700_000_000_123_456_789).unwrap();
+
+        let cases = [
+            (TimestampUnit::Nanoseconds, 1_700_000_000_123_456_789),
+            (TimestampUnit::Microseconds, 1_700_000_000_123_456),
+            (TimestampUnit::Milliseconds, 1_700_000_000_123),
+            (TimestampUnit::Seconds, 1_700_000_000),
+        ];
+
+        for (unit, expected) in cases {
+            assert_eq!(
+                encode_timeout_timestamp(timestamp, unit).nanoseconds(),
+                expected
+            );
+            assert_eq!(
+                encode_timeout_timestamp(Timestamp::none(), unit),
+                Timestamp::none()
+            );
+        }
+    }
+}
@@ -1,17 +1,26 @@
+use bech32::FromBase32;
 use ibc_proto::cosmos::tx::v1beta1::Fee;
 use ibc_proto::google::protobuf::Any;
+use ibc_relayer_types::core::ics02_client::error::Error as ClientError;
+use ibc_relayer_types::core::ics04_channel::msgs::{
+    acknowledgement, recv_packet, timeout, timeout_on_close,
+};
 use ibc_relayer_types::events::IbcEvent;
+use ibc_relayer_types::signer::Signer;
 use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
-use tendermint_rpc::{Client, HttpClient, Url};
+use tendermint_rpc::Url;
+use tracing::warn;
 
 use crate::chain::cosmos::encode::sign_and_encode_tx;
 use crate::chain::cosmos::estimate::estimate_tx_fees;
 use crate::chain::cosmos::query::account::query_account;
 use crate::chain::cosmos::query::tx::all_ibc_events_from_tx_search_response;
+use crate::chain::cosmos::request_rate::PacedRpcClient;
 use crate::chain::cosmos::types::account::Account;
 use crate::chain::cosmos::types::config::TxConfig;
-use crate::chain::cosmos::wait::wait_tx_succeed;
+use crate::chain::cosmos::wait::{wait_tx_succeed, BlockSearch};
 use crate::config::types::Memo;
+use crate::config::SignerEncoding;
 use crate::error::Error;
 use crate::event::IbcEventWithHeight;
 use crate::keyring::{Secp256k1KeyPair, SigningKeyPair};
@@ -20,26 +29,52 @@ use super::batch::send_batched_messages_and_wait_commit;
 use super::estimate::EstimatedGas;
 
 pub async fn estimate_fee_and_send_tx(
-    rpc_client: &HttpClient,
+    rpc_client: &PacedRpcClient,
     config: &TxConfig,
     key_pair: &Secp256k1KeyPair,
     account: &Account,
     tx_memo: &Memo,
     messages: &[Any],
 ) -> Result<(Response, EstimatedGas), Error> {
+    config.chain_id_drift.ensure_not_halted(&config.chain_id)?;
+
+    let tx_memo = relay_tx_memo(config, tx_memo, messages);
+
     let (fee, estimated_gas) =
-        estimate_tx_fees(config, key_pair, account, tx_memo, messages).await?;
+        estimate_tx_fees(config, key_pair, account, &tx_memo, messages).await?;
+
+    if let Some(daily_fee_budget) = &config.daily_fee_budget {
+        let loaded = daily_fee_budget
+            .load_account(rpc_client, &config.rpc_address, &key_pair.account())
+            .await;
+
+        if let Err(e) = loaded {
+            warn!(
+                chain = %config.chain_id,
+                "failed to load the fees spent today by the account, will retry: {e}"
+            );
+        }
+
+        daily_fee_budget.check(messages, &fee)?;
+    }
 
     let tx_result = send_tx_with_fee(
-        rpc_client, config, key_pair, account, tx_memo, messages, &fee,
+        rpc_client, config, key_pair, account, &tx_memo, messages, &fee,
     )
     .await?;
 
+    // The fee is paid once the chain includes the tx, which it accepted in its mempool
+    if let Some(daily_fee_budget) = &config.daily_fee_budget {
+        if tx_result.code.is_ok() {
+            daily_fee_budget.record(&fee);
+        }
+    }
+
     Ok((tx_result, estimated_gas))
 }
 
 async fn send_tx_with_fee(
-    rpc_client: &HttpClient,
+    rpc_client: &PacedRpcClient,
     config: &TxConfig,
     key_pair: &Secp256k1KeyPair,
     account: &Account,
@@ -49,14 +84,59 @@ async fn send_tx_with_fee(
 ) -> Result<Response, Error> {
     let tx_bytes = sign_and_encode_tx(config, key_pair, account, tx_memo, messages, fee)?;
 
+    if let Some(min_tx_interval) = &config.min_tx_interval {
+        min_tx_interval.wait(&key_pair.account()).await;
+    }
+
+    #[cfg(feature = "submit-delay")]
+    config.submit_delay.wait(&config.chain_id).await;
+
     let response = broadcast_tx_sync(rpc_client, &config.rpc_address, tx_bytes).await?;
 
     Ok(response)
 }
 
+/// The memo of a transaction carrying the given messages: the attribution memo of the
+/// relayer, if one is configured for the chain and the transaction relays packets, so
+/// that the chain credits the relayer with the packets, and the given memo otherwise.
+pub fn relay_tx_memo(config: &TxConfig, tx_memo: &Memo, messages: &[Any]) -> Memo {
+    match &config.attribution_memo {
+        Some(attribution_memo) if messages.iter().any(is_packet_msg) => attribution_memo.clone(),
+        _ => tx_memo.clone(),
+    }
+}
+
+fn is_packet_msg(message: &Any) -> bool {
+    [
+        recv_packet::TYPE_URL,
+        acknowledgement::TYPE_URL,
+        timeout::TYPE_URL,
+        timeout_on_close::TYPE_URL,
+    ]
+    .contains(&message.type_url.as_str())
+}
+
+/// Encodes the bech32 address of an account as the `signer` of the
+/// messages built for a chain, with the signer encoding of that chain.
+pub fn encode_signer(account: &str, encoding: SignerEncoding) -> Result<Signer, Error> {
+    let signer = match encoding {
+        SignerEncoding::Bech32 => account.to_string(),
+        SignerEncoding::Hex => {
+            let (_, data, _) = bech32::decode(account).map_err(Error::bech32_encoding)?;
+            let address = Vec::<u8>::from_base32(&data).map_err(Error::bech32_encoding)?;
+
+            format!("0x{}", hex::encode(address))
+        }
+    };
+
+    signer
+        .parse()
+        .map_err(|e| Error::ics02(ClientError::signer(e)))
+}
+
 /// Perform a `broadcast_tx_sync`, and return the corresponding deserialized response data.
 pub async fn broadcast_tx_sync(
-    rpc_client: &HttpClient,
+    rpc_client: &PacedRpcClient,
     rpc_address: &Url,
     data: Vec<u8>,
 ) -> Result<Response, Error> {
@@ -81,15 +161,24 @@ pub async fn broadcast_tx_sync(
    error event.
 */
 pub async fn simple_send_tx(
-    rpc_client: &HttpClient,
+    rpc_client: &PacedRpcClient,
     config: &TxConfig,
     key_pair: &Secp256k1KeyPair,
     messages: Vec<Any>,
 ) -> Result<Vec<IbcEventWithHeight>, Error> {
     let key_account = key_pair.account();
-    let account = query_account(&config.grpc_address, &key_account)
-        .await?
-        .into();
+    let account = query_account(
+        &config.grpc_address,
+        config.grpc_session.as_ref(),
+        config.request_limiter.as_ref(),
+        &key_account,
+        config.account_query,
+    )
+    .await?
+    .into();
+
+    let block_search =
+        BlockSearch::start(rpc_client, &config.rpc_address, &config.tx_confirmation).await;
 
     let (response, _) = estimate_fee_and_send_tx(
         rpc_client,
@@ -109,6 +198,8 @@ pub async fn simple_send_tx(
         rpc_client,
         &config.rpc_address,
         &config.rpc_timeout,
+        &config.tx_confirmation,
+        block_search,
         &response.hash,
     )
     .await?;
@@ -119,15 +210,21 @@ pub async fn simple_send_tx(
 }
 
 pub async fn batched_send_tx(
-    rpc_client: &HttpClient,
+    rpc_client: &PacedRpcClient,
     config: &TxConfig,
     key_pair: &Secp256k1KeyPair,
     messages: Vec<Any>,
 ) -> Result<Vec<IbcEventWithHeight>, Error> {
     let key_account = key_pair.account();
-    let mut account = query_account(&config.grpc_address, &key_account)
-        .await?
-        .into();
+    let mut account = query_account(
+        &config.grpc_address,
+        config.grpc_session.as_ref(),
+        config.request_limiter.as_ref(),
+        &key_account,
+        config.account_query,
+    )
+    .await?
+    .into();
 
     let events = send_batched_messages_and_wait_commit(
         rpc_client,
@@ -147,3 +244,197 @@ pub async fn batched_send_tx(
 
     Ok(events)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_proto::ibc::core::channel::v1::MsgRecvPacket as RawMsgRecvPacket;
+    use ibc_relayer_types::core::ics02_client::msgs::update_client;
+    use ibc_relayer_types::core::ics04_channel::msgs::recv_packet::MsgRecvPacket;
+    use ibc_relayer_types::core::ics04_channel::packet::Packet;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+    use ibc_relayer_types::proofs::Proofs;
+    use ibc_relayer_types::Height;
+    use prost::Message;
+
+    use crate::chain::cosmos::batch::test_fixtures::{
+        cosmos_config, example_tx_config, fixture_config, tx_config,
+    };
+    use crate::chain::cosmos::encode::sign_tx;
+    use crate::chain::cosmos::types::account::{AccountAddress, AccountNumber, AccountSequence};
+    use crate::config::{self, AddressType};
+
+    const ACCOUNT: &str = "evmos1qqqsyqcyq5rqwzqfqqqsyqcyq5rqwzqf5ve6zy";
+
+    fn recv_packet(signer: Signer) -> RawMsgRecvPacket {
+        let proofs = Proofs::new(
+            vec![0].try_into().unwrap(),
+            None,
+            None,
+            None,
+            None,
+            Height::new(0, 10).unwrap(),
+        )
+        .unwrap();
+
+        let packet = Packet {
+            sequence: 1.into(),
+            data: b"data".to_vec(),
+            ..Packet::default()
+        };
+
+        MsgRecvPacket::new(packet, proofs, signer).into()
+    }
+
+    #[test]
+    fn bech32_signer_is_the_account() {
+        let signer = encode_signer(ACCOUNT, SignerEncoding::Bech32).unwrap();
+
+        assert_eq!(recv_packet(signer).signer, ACCOUNT);
+    }
+
+    #[test]
+    fn hex_signer_encodes_the_address_of_the_account() {
+        let signer = encode_signer(ACCOUNT, SignerEncoding::Hex).unwrap();
+        let msg = recv_packet(signer);
+
+        assert_eq!(msg.signer, "0x0001020304050607080900010203040506070809");
+
+        // The hex signer decodes to the same address as the bech32 account
+        let (_, data, _) = bech32::decode(ACCOUNT).unwrap();
+        let address = Vec::<u8>::from_base32(&data).unwrap();
+        assert_eq!(hex::decode(&msg.signer[2..]).unwrap(), address);
+
+        // The message with the hex signer is valid
+        let decoded = MsgRecvPacket::try_from(msg.clone()).unwrap();
+        assert_eq!(decoded.signer.as_ref(), msg.signer);
+    }
+
+    #[test]
+    fn hex_signer_requires_a_bech32_account() {
+        assert!(encode_signer("not-an-account", SignerEncoding::Hex).is_err());
+    }
+
+    #[test]
+    fn recv_tx_carries_the_attribution_memo() {
+        let attribution = r#"{"relayer":"moonbridge"}"#;
+
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+        let config_toml = std::fs::read_to_string(path).unwrap();
+        let with_attribution_memo = |memo: &str| {
+            let config_toml = config_toml.replacen(
+                "[[chains]]\n",
+                &format!("[[chains]]\nrelayer_attribution_memo = '{memo}'\n"),
+                1,
+            );
+            toml::from_str::<config::Config>(&config_toml)
+        };
+
+        // The attribution memo is limited in length like the other memos
+        let too_long = format!(r#"{{"relayer":"{}"}}"#, "a".repeat(50));
+        assert!(with_attribution_memo(&too_long).is_err());
+
+        let config = with_attribution_memo(attribution).expect("could not parse config");
+        let config = tx_config(&config.chains[0]);
+
+        let key_pair = Secp256k1KeyPair::from_mnemonic(
+            "abandon abandon abandon abandon abandon abandon \
+            abandon abandon abandon abandon abandon about",
+            &"m/44'/118'/0'/0/0".parse().unwrap(),
+            &AddressType::Cosmos,
+            "cosmos",
+        )
+        .unwrap();
+
+        let account = Account {
+            address: AccountAddress::new(key_pair.account()),
+            number: AccountNumber::new(1),
+            sequence: AccountSequence::new(0),
+        };
+
+        let signer = encode_signer(&key_pair.account(), SignerEncoding::Bech32).unwrap();
+        let recv = Any {
+            type_url: recv_packet::TYPE_URL.to_string(),
+            value: recv_packet(signer).encode_to_vec(),
+        };
+        let update_client = Any {
+            type_url: update_client::TYPE_URL.to_string(),
+            value: vec![],
+        };
+
+        let memo = Memo::new("hermes").unwrap();
+        let fee = config.gas_config.max_fee.clone();
+
+        // The relay transaction is signed with the attribution memo
+        let messages = [update_client.clone(), recv];
+        let tx_memo = relay_tx_memo(&config, &memo, &messages);
+        let signed_tx = sign_tx(&config, &key_pair, &account, &tx_memo, &messages, &fee).unwrap();
+
+        assert_eq!(signed_tx.body.memo, attribution);
+
+        // The transactions which do not relay packets keep the configured memo
+        assert_eq!(relay_tx_memo(&config, &memo, &[update_client]), memo);
+
+        // As do the relay transactions of the chains without an attribution memo
+        assert_eq!(relay_tx_memo(&example_tx_config(), &memo, &messages), memo);
+    }
+
+    #[test]
+    fn tx_memo_is_overridden_per_counterparty_chain() {
+        let config = fixture_config("relayer_conf_example_memo_overrides.toml");
+        let chain_config = cosmos_config(&config.chains[0]);
+        let tx_config = tx_config(&config.chains[0]);
+
+        let key_pair = Secp256k1KeyPair::from_mnemonic(
+            "abandon abandon abandon abandon abandon abandon \
+            abandon abandon abandon abandon abandon about",
+            &"m/44'/118'/0'/0/0".parse().unwrap(),
+            &AddressType::Cosmos,
+            "cosmos",
+        )
+        .unwrap();
+
+        let account = Account {
+            address: AccountAddress::new(key_pair.account()),
+            number: AccountNumber::new(1),
+            sequence: AccountSequence::new(0),
+        };
+
+        let signer = encode_signer(&key_pair.account(), SignerEncoding::Bech32).unwrap();
+        let messages = [Any {
+            type_url: recv_packet::TYPE_URL.to_string(),
+            value: recv_packet(signer).encode_to_vec(),
+        }];
+        let fee = tx_config.gas_config.max_fee.clone();
+
+        // The memo of the relay transaction signed for messages relaying on a channel
+        // to the given counterparty chain
+        let signed_memo = |counterparty_chain_id: Option<&str>| {
+            let counterparty_chain_id = counterparty_chain_id.map(ChainId::from_string);
+            let memo_prefix = chain_config.tx_memo(counterparty_chain_id.as_ref());
+            let tx_memo = relay_tx_memo(&tx_config, memo_prefix, &messages);
+
+            sign_tx(&tx_config, &key_pair, &account, &tx_memo, &messages, &fee)
+                .unwrap()
+                .body
+                .memo
+        };
+
+        assert_eq!(signed_memo(Some("osmosis-1")), "relayed-to-osmosis");
+        assert_eq!(signed_memo(Some("neutron-1")), "relayed-to-neutron");
+
+        // The other transactions keep the memo prefix of the chain
+        assert_eq!(signed_memo(Some("juno-1")), "hermes");
+        assert_eq!(signed_memo(None), "hermes");
+
+        // The overrides are subject to the length limit of the memos
+        let too_long = std::fs::read_to_string(path)
+            .unwrap()
+            .replace("relayed-to-osmosis", &"x".repeat(51));
+        assert!(toml::from_str::<config::Config>(&too_long).is_err());
+    }
+}
@@ -0,0 +1,108 @@
+//! Spacing of the transactions broadcast from the same account, for chains which reject
+//! the transactions of an account that arrive too close together.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tracing::debug;
+
+/// Spaces the transactions broadcast from the same account by at least `min_interval`.
+#[derive(Debug)]
+pub struct TxIntervalLimiter {
+    min_interval: Duration,
+
+    /// The time at which the last transaction of each account was, or will be, broadcast.
+    last_broadcast: Mutex<HashMap<String, Instant>>,
+}
+
+impl TxIntervalLimiter {
+    pub fn new(min_interval: Duration) -> Self {
+        Self {
+            min_interval,
+            last_broadcast: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Reserves the earliest time, not before `now`, at which the given account
+    /// may broadcast its next transaction.
+    fn reserve(&self, account: &str, now: Instant) -> Instant {
+        let mut last_broadcast = self.last_broadcast.lock().expect("poisoned lock");
+
+        let at = match last_broadcast.get(account) {
+            Some(last) => now.max(*last + self.min_interval),
+            None => now,
+        };
+
+        last_broadcast.insert(account.to_string(), at);
+
+        at
+    }
+
+    /// Waits until the given account may broadcast its next transaction.
+    pub async fn wait(&self, account: &str) {
+        let now = Instant::now();
+        let at = self.reserve(account, now);
+
+        if at > now {
+            let delay = at - now;
+            debug!(%account, ?delay, "delaying transaction to honor the minimum interval between transactions");
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    #[test]
+    fn reservations_are_spaced_per_account() {
+        let limiter = TxIntervalLimiter::new(Duration::from_secs(5));
+        let now = Instant::now();
+
+        assert_eq!(limiter.reserve("alice", now), now);
+        assert_eq!(limiter.reserve("alice", now), now + Duration::from_secs(5));
+        assert_eq!(
+            limiter.reserve("alice", now + Duration::from_secs(1)),
+            now + Duration::from_secs(10)
+        );
+
+        // Another account is not delayed by the transactions of the first one
+        assert_eq!(limiter.reserve("bob", now), now);
+
+        // Once the interval elapsed, a transaction is not delayed
+        let later = now + Duration::from_secs(60);
+        assert_eq!(limiter.reserve("alice", later), later);
+    }
+
+    #[tokio::test]
+    async fn rapid_transactions_are_spaced_by_min_interval() {
+        let min_interval = Duration::from_millis(200);
+        let limiter = Arc::new(TxIntervalLimiter::new(min_interval));
+
+        let tasks = (0..3).map(|_| {
+            let limiter = limiter.clone();
+            tokio::spawn(async move {
+                limiter.wait("alice").await;
+                Instant::now()
+            })
+        });
+
+        let mut broadcast_at = Vec::new();
+        for task in tasks {
+            broadcast_at.push(task.await.unwrap());
+        }
+        broadcast_at.sort();
+
+        for pair in broadcast_at.windows(2) {
+            assert!(
+                pair[1] - pair[0] >= min_interval - Duration::from_millis(10),
+                "transactions were broadcast {:?} apart",
+                pair[1] - pair[0]
+            );
+        }
+    }
+}
@@ -0,0 +1,302 @@
+//! Derivation of the gas price in a fee denomination from its spot price in the base
+//! denomination of the Osmosis `txfees` module, which converts the fees paid in other
+//! denominations than the base one at that rate.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Deserialize;
+use subtle_encoding::base64;
+use tendermint_rpc::Url;
+use tracing::{debug, warn};
+
+use crate::chain::cosmos::request_rate::RequestRateLimiter;
+use crate::config::{GasPrice, TxFeesGasPriceConfig};
+use crate::error::Error;
+use crate::util::refresh_cache::RefreshCache;
+
+const DENOM_SPOT_PRICE_PATH: &str = "/osmosis.txfees.v1beta1.Query/DenomSpotPrice";
+
+/// The number of decimals of a `LegacyDec`.
+const DEC_PRECISION: i32 = 18;
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryDenomSpotPriceRequest {
+    #[prost(string, tag = "1")]
+    pub denom: String,
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryDenomSpotPriceResponse {
+    #[prost(uint64, tag = "1")]
+    pub pool_id: u64,
+    #[prost(string, tag = "2")]
+    pub spot_price: String,
+}
+
+/// Queries the spot price of the given fee denomination, in the base denomination
+/// of the `txfees` module, ie. the amount of the base denomination which one unit
+/// of the fee denomination is worth.
+pub async fn query_denom_spot_price(
+    rpc_address: &Url,
+    limiter: Option<&Arc<RequestRateLimiter>>,
+    denom: &str,
+) -> Result<f64, Error> {
+    debug!("Querying the txfees spot price of `{denom}` from {rpc_address}");
+
+    let request = QueryDenomSpotPriceRequest {
+        denom: denom.to_string(),
+    };
+    let data = hex::encode(prost::Message::encode_to_vec(&request));
+
+    let url = format!("{rpc_address}abci_query?path=\"{DENOM_SPOT_PRICE_PATH}\"&data=0x{data}");
+
+    if let Some(limiter) = limiter {
+        limiter.wait().await;
+    }
+
+    let response = reqwest::get(&url).await.map_err(Error::http_request)?;
+
+    if !response.status().is_success() {
+        return Err(Error::http_response(response.status()));
+    }
+
+    #[derive(Deserialize)]
+    struct AbciQueryHTTPResult {
+        result: AbciQueryResult,
+    }
+
+    #[derive(Deserialize)]
+    struct AbciQueryResult {
+        response: AbciQueryResponse,
+    }
+
+    #[derive(Deserialize)]
+    struct AbciQueryResponse {
+        #[serde(default)]
+        code: u32,
+        #[serde(default)]
+        log: String,
+        value: Option<String>,
+    }
+
+    let result: AbciQueryHTTPResult = response.json().await.map_err(Error::http_response_body)?;
+    let response = result.result.response;
+
+    match response.value {
+        Some(value) if response.code == 0 => decode_spot_price(&value),
+        _ => Err(Error::query(format!(
+            "txfees spot price of `{denom}`, code {}: {}",
+            response.code, response.log
+        ))),
+    }
+}
+
+/// Decodes the base64-encoded `QueryDenomSpotPriceResponse` of the `txfees` module.
+fn decode_spot_price(encoded: &str) -> Result<f64, Error> {
+    let decoded = base64::decode(encoded).map_err(Error::base64_decode)?;
+
+    let response: QueryDenomSpotPriceResponse =
+        prost::Message::decode(decoded.as_ref()).map_err(|e| {
+            Error::protobuf_decode(
+                "osmosis.txfees.v1beta1.QueryDenomSpotPriceResponse".to_string(),
+                e,
+            )
+        })?;
+
+    parse_dec(&response.spot_price)
+}
+
+/// Parses the `LegacyDec` spot price, which is encoded in one of two ways:
+///
+/// - in protobuf, as its underlying integer, ie. the decimal number scaled by 10^18,
+///   eg. `1500000000000000000` for 1.5, which is how the `txfees` module returns it;
+/// - in text, as a decimal number with all its 18 decimals, eg. `1.500000000000000000`,
+///   which is how it appears in JSON, eg. when the query is relayed by a gateway.
+///
+/// A `LegacyDec` in text always has a decimal point, so a string without one is
+/// always the protobuf encoding, and is scaled down by 10^18.
+fn parse_dec(dec: &str) -> Result<f64, Error> {
+    let value = dec.parse::<f64>().map_err(Error::parse_float)?;
+
+    if dec.contains('.') {
+        Ok(value)
+    } else {
+        Ok(value / 10_f64.powi(DEC_PRECISION))
+    }
+}
+
+/// The gas price in the fee denomination worth the given gas price in the base
+/// denomination, at the given spot price of the fee denomination.
+pub fn derive_gas_price(base_gas_price: f64, spot_price: f64) -> f64 {
+    base_gas_price / spot_price
+}
+
+/// Derives the gas price in a fee denomination from its spot price in the base
+/// denomination of the `txfees` module, which is queried at most once every
+/// `refresh_interval` for each fee denomination.
+#[derive(Debug)]
+pub struct TxFeesGasPrice {
+    base_gas_price: f64,
+    current: RefreshCache<String, f64>,
+}
+
+impl TxFeesGasPrice {
+    pub fn new(base_gas_price: f64, refresh_interval: Duration) -> Self {
+        Self {
+            base_gas_price,
+            current: RefreshCache::new(refresh_interval),
+        }
+    }
+
+    pub fn from_config(config: &TxFeesGasPriceConfig) -> Self {
+        Self::new(config.base_gas_price, config.refresh_interval)
+    }
+
+    /// Returns the current spot price of the given denomination, from the `txfees` module
+    /// if the last spot price queried is older than the refresh interval, or `None` if the
+    /// query failed and no spot price was queried before.
+    async fn current_spot_price(
+        &self,
+        rpc_address: &Url,
+        limiter: Option<&Arc<RequestRateLimiter>>,
+        denom: &str,
+    ) -> Option<f64> {
+        let refresh = async {
+            match query_denom_spot_price(rpc_address, limiter, denom).await {
+                Ok(spot_price) if spot_price.is_finite() && spot_price > 0.0 => Ok(spot_price),
+                Ok(spot_price) => {
+                    warn!(
+                        "invalid txfees spot price `{spot_price}` of `{denom}`, \
+                        will use the last known spot price"
+                    );
+                    Err(())
+                }
+                Err(e) => {
+                    warn!(
+                        "failed to query the txfees spot price of `{denom}`, \
+                        will use the last known spot price: {e}"
+                    );
+                    Err(())
+                }
+            }
+        };
+
+        self.current.get(denom.to_string(), refresh).await
+    }
+
+    /// Returns the gas price in the denomination of the given gas price, derived from the
+    /// base gas price and the spot price of the denomination. The given gas price is
+    /// returned as is if the spot price of its denomination is unknown.
+    pub async fn gas_price(
+        &self,
+        rpc_address: &Url,
+        limiter: Option<&Arc<RequestRateLimiter>>,
+        gas_price: &GasPrice,
+    ) -> GasPrice {
+        match self
+            .current_spot_price(rpc_address, limiter, &gas_price.denom)
+            .await
+        {
+            Some(spot_price) => GasPrice::new(
+                derive_gas_price(self.base_gas_price, spot_price),
+                gas_price.denom.clone(),
+            ),
+            None => gas_price.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+
+    use super::*;
+    use crate::util::mock_http::{spawn_mock_http_server, MockRequest, MockResponse};
+
+    const FEE_DENOM: &str = "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2";
+
+    /// A node answering the `DenomSpotPrice` queries of the `txfees` module with the
+    /// given spot price, encoded as a `LegacyDec`.
+    fn spawn_mock_txfees_node(spot_price: &str) -> (Url, mpsc::Receiver<MockRequest>) {
+        let response = QueryDenomSpotPriceResponse {
+            pool_id: 1,
+            spot_price: spot_price.to_string(),
+        };
+        let value =
+            String::from_utf8(base64::encode(prost::Message::encode_to_vec(&response))).unwrap();
+
+        let (address, requests) = spawn_mock_http_server(move |_| {
+            let response = format!(r#"{{"response":{{"code":0,"value":"{value}"}}}}"#);
+            MockResponse::json(format!(
+                r#"{{"jsonrpc":"2.0","id":-1,"result":{response}}}"#
+            ))
+        });
+
+        (format!("http://{address}/").parse().unwrap(), requests)
+    }
+
+    #[test]
+    fn spot_price_is_parsed_from_both_legacy_dec_encodings() {
+        // Protobuf encoding, scaled by 10^18
+        assert_eq!(parse_dec("1500000000000000000").unwrap(), 1.5);
+        assert_eq!(parse_dec("250000000000000000").unwrap(), 0.25);
+        assert_eq!(parse_dec("8").unwrap(), 8e-18);
+
+        // Text encoding, as a decimal number
+        assert_eq!(parse_dec("1.500000000000000000").unwrap(), 1.5);
+        assert_eq!(parse_dec("0.250000000000000000").unwrap(), 0.25);
+        assert_eq!(parse_dec("8.000000000000000000").unwrap(), 8.0);
+
+        assert!(parse_dec("not a dec").is_err());
+    }
+
+    #[tokio::test]
+    async fn gas_price_is_derived_from_txfees_spot_price() {
+        // One unit of the fee denom is worth 8 units of the base denom,
+        // whichever encoding the node returns its spot price in
+        for spot_price in ["8000000000000000000", "8.000000000000000000"] {
+            let (rpc_address, queries) = spawn_mock_txfees_node(spot_price);
+
+            let txfees = TxFeesGasPrice::new(0.04, Duration::from_secs(3600));
+            let configured = GasPrice::new(1.0, FEE_DENOM.to_string());
+
+            let gas_price = txfees.gas_price(&rpc_address, None, &configured).await;
+            assert_eq!(gas_price, GasPrice::new(0.005, FEE_DENOM.to_string()));
+
+            // The query carries the fee denom
+            let request = queries.recv().unwrap();
+            let data = hex::encode(prost::Message::encode_to_vec(&QueryDenomSpotPriceRequest {
+                denom: FEE_DENOM.to_string(),
+            }));
+            assert!(request.line.contains("DenomSpotPrice"), "{}", request.line);
+            assert!(request.line.contains(&data), "{}", request.line);
+
+            // The spot price queried last is used until the refresh interval elapses
+            let gas_price = txfees.gas_price(&rpc_address, None, &configured).await;
+            assert_eq!(gas_price.price, 0.005);
+            assert!(queries.try_recv().is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn configured_gas_price_is_used_without_spot_price() {
+        // The node does not answer the query with a spot price
+        let (address, _queries) = spawn_mock_http_server(|_| {
+            MockResponse::json(
+                r#"{"jsonrpc":"2.0","id":-1,"error":{"code":-32603,"message":"unknown query path"}}"#,
+            )
+        });
+        let rpc_address = format!("http://{address}/").parse().unwrap();
+
+        let txfees = TxFeesGasPrice::new(0.04, Duration::ZERO);
+        let configured = GasPrice::new(1.0, FEE_DENOM.to_string());
+
+        assert_eq!(
+            txfees.gas_price(&rpc_address, None, &configured).await,
+            configured
+        );
+    }
+}
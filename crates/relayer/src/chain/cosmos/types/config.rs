@@ -3,13 +3,26 @@ use core::time::Duration;
 use http::Uri;
 use ibc_proto::google::protobuf::Any;
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use std::sync::Arc;
 use tendermint_rpc::Url;
 
+use crate::chain::cosmos::block_gas::BlockGasUsageSampler;
+use crate::chain::cosmos::chain_id_drift::ChainIdDrift;
 use crate::chain::cosmos::config::CosmosSdkConfig;
+use crate::chain::cosmos::fee_budget::DailyFeeBudget;
+use crate::chain::cosmos::fee_denom::{FeeDenomBalances, FeeDenomCandidates};
+use crate::chain::cosmos::request_rate::RequestRateLimiter;
+use crate::chain::cosmos::sticky_session::Session;
+#[cfg(feature = "submit-delay")]
+use crate::chain::cosmos::submit_delay::SubmitDelay;
+use crate::chain::cosmos::tx_interval::TxIntervalLimiter;
 use crate::chain::cosmos::types::gas::GasConfig;
-use crate::config::types::{MaxMsgNum, MaxTxSize};
-use crate::config::AddressType;
+use crate::chain::cosmos::wait::TxConfirmation;
+use crate::config::retry_strategy::RetryStrategy;
+use crate::config::types::{MaxMsgNum, MaxTxSize, Memo};
+use crate::config::{AccountQuery, AddressType, BatchFailureMode};
 use crate::error::Error;
+use crate::keyring::MultisigSigner;
 
 #[derive(Debug, Clone)]
 pub struct TxConfig {
@@ -17,11 +30,28 @@ pub struct TxConfig {
     pub gas_config: GasConfig,
     pub rpc_address: Url,
     pub grpc_address: Uri,
+    pub grpc_session: Option<Arc<Session>>,
+    pub request_limiter: Option<Arc<RequestRateLimiter>>,
     pub rpc_timeout: Duration,
+    pub max_concurrent_tx_confirmations: usize,
     pub address_type: AddressType,
+    pub account_query: Option<AccountQuery>,
     pub max_msg_num: MaxMsgNum,
     pub max_tx_size: MaxTxSize,
+    pub block_gas_usage: Option<Arc<BlockGasUsageSampler>>,
+    pub min_tx_interval: Option<Arc<TxIntervalLimiter>>,
+    pub daily_fee_budget: Option<Arc<DailyFeeBudget>>,
+    pub chain_id_drift: Arc<ChainIdDrift>,
+    pub fee_denom_balances: Arc<FeeDenomBalances>,
+    pub fee_denom_candidates: Arc<FeeDenomCandidates>,
+    pub batch_failure_mode: BatchFailureMode,
     pub extension_options: Vec<Any>,
+    pub multisig: Option<MultisigSigner>,
+    pub tx_confirmation: TxConfirmation,
+    pub retry_strategy: RetryStrategy,
+    pub attribution_memo: Option<Memo>,
+    #[cfg(feature = "submit-delay")]
+    pub submit_delay: Arc<SubmitDelay>,
 }
 
 impl<'a> TryFrom<&'a CosmosSdkConfig> for TxConfig {
@@ -39,16 +69,60 @@ impl<'a> TryFrom<&'a CosmosSdkConfig> for TxConfig {
             .map(|opt| opt.to_any())
             .collect::<Result<_, _>>()?;
 
+        let multisig = config
+            .multisig
+            .as_ref()
+            .map(MultisigSigner::from_config)
+            .transpose()
+            .map_err(Error::key_base)?;
+
         Ok(Self {
             chain_id: config.id.clone(),
             gas_config,
             rpc_address: config.rpc_addr.clone(),
             grpc_address,
+            grpc_session: None,
+            request_limiter: None,
             rpc_timeout: config.rpc_timeout,
+            max_concurrent_tx_confirmations: config.max_concurrent_tx_confirmations,
             address_type: config.address_type.clone(),
+            account_query: config.account_query,
             max_msg_num: config.max_msg_num,
             max_tx_size: config.max_tx_size,
+            block_gas_usage: config.block_gas_usage.as_ref().map(|block_gas_usage| {
+                Arc::new(BlockGasUsageSampler::from_config(
+                    block_gas_usage,
+                    config.max_block_time,
+                ))
+            }),
+            min_tx_interval: config
+                .min_tx_interval
+                .map(|interval| Arc::new(TxIntervalLimiter::new(interval))),
+            daily_fee_budget: config.daily_fee_budget.map(|budget| {
+                Arc::new(DailyFeeBudget::new(
+                    config.id.clone(),
+                    &config.gas_price,
+                    budget.into(),
+                ))
+            }),
+            chain_id_drift: Arc::new(ChainIdDrift::default()),
+            fee_denom_balances: Arc::new(FeeDenomBalances::default()),
+            fee_denom_candidates: Arc::new(FeeDenomCandidates::new(
+                &gas_config,
+                config.gas_price.iter(),
+                config.max_block_time,
+            )),
+            batch_failure_mode: config.batch_failure_mode,
             extension_options,
+            multisig,
+            tx_confirmation: TxConfirmation::new(
+                config.id.clone(),
+                config.tx_confirmation_strategy,
+            ),
+            retry_strategy: config.retry_strategy,
+            attribution_memo: config.relayer_attribution_memo.clone(),
+            #[cfg(feature = "submit-delay")]
+            submit_delay: Arc::new(SubmitDelay::default()),
         })
     }
 }
@@ -1,8 +1,17 @@
+use std::sync::Arc;
+
 use ibc_proto::cosmos::tx::v1beta1::Fee;
+use ibc_relayer_types::applications::transfer::Amount;
 
+use crate::chain::cosmos::base_fee_history::BaseFeeHistory;
 use crate::chain::cosmos::calculate_fee;
 use crate::chain::cosmos::config::CosmosSdkConfig;
+use crate::chain::cosmos::gas::{round_up_gas_price, GasEstimateSampler};
+use crate::chain::cosmos::price_oracle::ReferenceGasPrice;
+use crate::chain::cosmos::txfees::TxFeesGasPrice;
 use crate::config::dynamic_gas::DynamicGasPrice;
+use crate::config::shadow_gas::ShadowGasStrategy;
+use crate::config::tx_priority::TxPriority;
 use crate::config::GasPrice;
 
 /// Default gas limit when submitting a transaction.
@@ -16,9 +25,16 @@ pub struct GasConfig {
     pub max_gas: u64,
     pub gas_multiplier: f64,
     pub gas_price: GasPrice,
+    pub gas_price_decimals: Option<u32>,
     pub max_fee: Fee,
     pub fee_granter: String,
     pub dynamic_gas_price: DynamicGasPrice,
+    pub gas_sampler: Arc<GasEstimateSampler>,
+    pub tx_priority: TxPriority,
+    pub shadow_gas_strategy: Option<ShadowGasStrategy>,
+    pub reference_gas_price: Option<Arc<ReferenceGasPrice>>,
+    pub txfees_gas_price: Option<Arc<TxFeesGasPrice>>,
+    pub base_fee_history: Arc<BaseFeeHistory>,
 }
 
 impl<'a> From<&'a CosmosSdkConfig> for GasConfig {
@@ -27,10 +43,60 @@ impl<'a> From<&'a CosmosSdkConfig> for GasConfig {
             default_gas: default_gas_from_config(config),
             max_gas: max_gas_from_config(config),
             gas_multiplier: gas_multiplier_from_config(config),
-            gas_price: config.gas_price.clone(),
+            gas_price: config.gas_price.preferred().clone(),
+            gas_price_decimals: config.gas_price_decimals,
             max_fee: max_fee_from_config(config),
             fee_granter: fee_granter_from_config(config),
             dynamic_gas_price: config.dynamic_gas_price,
+            gas_sampler: Arc::new(GasEstimateSampler::new(config.gas_estimation_sampling)),
+            tx_priority: config.tx_priority,
+            shadow_gas_strategy: config.shadow_gas_strategy.clone(),
+            reference_gas_price: config.reference_gas_price.as_ref().map(|reference| {
+                Arc::new(ReferenceGasPrice::from_config(reference, &config.gas_price))
+            }),
+            txfees_gas_price: config
+                .txfees_gas_price
+                .as_ref()
+                .map(|config| Arc::new(TxFeesGasPrice::from_config(config))),
+            base_fee_history: Arc::new(BaseFeeHistory::default()),
+        }
+    }
+}
+
+impl GasConfig {
+    /// This gas configuration, paying the fees at the given gas price instead,
+    /// with the maximum fee of a transaction in the denomination of that price.
+    ///
+    /// The maximum gas price and maximum fee of the dynamic gas price are converted
+    /// to the denomination of the given gas price, at the rate of the two gas prices.
+    pub fn with_gas_price(&self, gas_price: GasPrice) -> Self {
+        let max_fee_in_coins = calculate_fee(
+            self.max_gas,
+            &round_up_gas_price(gas_price.clone(), self.gas_price_decimals),
+        );
+
+        let mut dynamic_gas_price = self.dynamic_gas_price;
+        let rate = gas_price.price / self.gas_price.price;
+
+        if gas_price.denom != self.gas_price.denom && rate.is_finite() && rate > 0.0 {
+            dynamic_gas_price.max *= rate;
+            dynamic_gas_price.max_fee =
+                dynamic_gas_price
+                    .max_fee
+                    .map(|max_fee| match max_fee.to_string().parse::<f64>() {
+                        Ok(max_fee) => Amount::from((max_fee * rate).ceil() as u128),
+                        Err(_) => max_fee,
+                    });
+        }
+
+        Self {
+            gas_price,
+            max_fee: Fee {
+                amount: vec![max_fee_in_coins],
+                ..self.max_fee.clone()
+            },
+            dynamic_gas_price,
+            ..self.clone()
         }
     }
 }
@@ -71,7 +137,11 @@ fn max_fee_from_config(config: &CosmosSdkConfig) -> Fee {
     let max_gas = max_gas_from_config(config);
 
     // The maximum fee the relayer pays for a transaction
-    let max_fee_in_coins = calculate_fee(max_gas, &config.gas_price);
+    let gas_price = round_up_gas_price(
+        config.gas_price.preferred().clone(),
+        config.gas_price_decimals,
+    );
+    let max_fee_in_coins = calculate_fee(max_gas, &gas_price);
 
     let fee_granter = fee_granter_from_config(config);
 
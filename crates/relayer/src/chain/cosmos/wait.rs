@@ -1,32 +1,218 @@
+use core::future::{self as future, Future};
+use core::sync::atomic::{AtomicBool, Ordering};
 use core::time::Duration;
+use futures::stream::{self, StreamExt};
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use ibc_relayer_types::events::IbcEvent;
 use ibc_relayer_types::Height;
 use itertools::Itertools;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
 use std::thread;
 use std::time::Instant;
+use tendermint::block::Height as BlockHeight;
 use tendermint::Hash as TxHash;
 use tendermint_rpc::endpoint::tx::Response as TxResponse;
-use tendermint_rpc::{HttpClient, Url};
+use tendermint_rpc::{Client, Url};
 use tokio::time::sleep;
-use tracing::{debug, debug_span, trace};
+use tracing::{debug, debug_span, trace, warn};
 
+use crate::chain::cosmos::fees_spent::record_fees_spent;
 use crate::chain::cosmos::query::tx::query_tx_response;
+use crate::chain::cosmos::request_rate::PacedRpcClient;
 use crate::chain::cosmos::types::events::from_tx_response_event;
 use crate::chain::cosmos::types::tx::{TxStatus, TxSyncResult};
+use crate::config::TxConfirmationStrategy;
 use crate::error::Error;
 use crate::event::IbcEventWithHeight;
 
 const WAIT_BACKOFF: Duration = Duration::from_millis(300);
 
+/// How the inclusion of the transactions sent to a chain is confirmed, following the
+/// [`TxConfirmationStrategy`] of the chain. Shared by all the transactions sent to the
+/// chain, so that the switch to the search of blocks applies to all of them at once.
+#[derive(Clone, Debug)]
+pub struct TxConfirmation {
+    chain_id: ChainId,
+    strategy: TxConfirmationStrategy,
+    tx_indexing_disabled: Arc<AtomicBool>,
+}
+
+impl TxConfirmation {
+    pub fn new(chain_id: ChainId, strategy: TxConfirmationStrategy) -> Self {
+        Self {
+            chain_id,
+            strategy,
+            tx_indexing_disabled: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Whether the transactions are searched in the new blocks of the chain,
+    /// rather than by their hash.
+    pub fn searches_blocks(&self) -> bool {
+        match self.strategy {
+            TxConfirmationStrategy::Auto => self.tx_indexing_disabled.load(Ordering::Relaxed),
+            TxConfirmationStrategy::TxSearch => false,
+            TxConfirmationStrategy::BlockSearch => true,
+        }
+    }
+
+    /// Whether the transactions may be searched in the new blocks of the chain,
+    /// either right away or after a switch to the search of blocks.
+    fn may_search_blocks(&self) -> bool {
+        self.strategy != TxConfirmationStrategy::TxSearch
+    }
+
+    /// Switches to the search of blocks with the `auto` strategy, once the transaction
+    /// indexing of the node is known to be disabled. Does nothing with the other strategies.
+    pub fn switch_to_block_search(&self) {
+        if self.strategy != TxConfirmationStrategy::Auto {
+            return;
+        }
+
+        if !self.tx_indexing_disabled.swap(true, Ordering::Relaxed) {
+            warn!(
+                chain = %self.chain_id,
+                "transaction indexing is disabled on the node, \
+                confirming the inclusion of transactions by searching the new blocks instead; \
+                consider enabling transaction indexing on the node or setting \
+                `tx_confirmation_strategy = 'block_search'` for the chain"
+            );
+        }
+    }
+
+    /// Switches to the search of blocks if the given error of a search of transactions
+    /// by hash reports that the transaction indexing is disabled. Returns whether the
+    /// transactions are searched in the new blocks from now on.
+    fn switch_on_error(&self, error: &Error) -> bool {
+        if !error.is_tx_indexing_disabled_error() {
+            return false;
+        }
+
+        self.switch_to_block_search();
+        self.searches_blocks()
+    }
+}
+
+/// Searches the blocks of a chain for transactions, for the nodes whose transaction
+/// indexing is disabled.
+#[derive(Debug, Default)]
+pub struct BlockSearch {
+    next_height: Option<BlockHeight>,
+}
+
+impl BlockSearch {
+    /// Starts a search from the latest block of the chain. Must be called before the
+    /// transactions are broadcast, so that the blocks committed in the meantime are
+    /// searched as well. Nothing is queried if the transactions are only searched by hash,
+    /// and the search starts from the latest block at the first search if the query fails.
+    pub async fn start(
+        rpc_client: &PacedRpcClient<impl Client + Sync>,
+        rpc_address: &Url,
+        confirmation: &TxConfirmation,
+    ) -> Self {
+        if !confirmation.may_search_blocks() {
+            return Self::default();
+        }
+
+        match latest_block_height(rpc_client, rpc_address).await {
+            Ok(height) => Self {
+                next_height: Some(height),
+            },
+            Err(e) => {
+                debug!("failed to query the latest height before broadcasting the txs: {e}");
+                Self::default()
+            }
+        }
+    }
+
+    /// Searches the blocks committed since the last search for the given transactions,
+    /// and returns the responses of the transactions found.
+    async fn search(
+        &mut self,
+        rpc_client: &PacedRpcClient<impl Client + Sync>,
+        rpc_address: &Url,
+        tx_hashes: &[TxHash],
+    ) -> Result<Vec<TxResponse>, Error> {
+        let rpc_error = |e| Error::rpc(rpc_address.clone(), e);
+
+        let latest_height = latest_block_height(rpc_client, rpc_address).await?;
+
+        let mut height = self.next_height.unwrap_or(latest_height);
+        let mut responses = Vec::new();
+
+        while height <= latest_height {
+            let block = rpc_client.block(height).await.map_err(rpc_error)?.block;
+
+            let found = block
+                .data
+                .into_iter()
+                .enumerate()
+                .filter_map(|(index, tx)| {
+                    let hash = TxHash::Sha256(Sha256::digest(&tx).into());
+                    tx_hashes.contains(&hash).then_some((index, hash, tx))
+                })
+                .collect::<Vec<_>>();
+
+            if !found.is_empty() {
+                let tx_results = rpc_client
+                    .block_results(height)
+                    .await
+                    .map_err(rpc_error)?
+                    .txs_results
+                    .unwrap_or_default();
+
+                for (index, hash, tx) in found {
+                    if let Some(tx_result) = tx_results.get(index) {
+                        responses.push(TxResponse {
+                            hash,
+                            height,
+                            index: index as u32,
+                            tx_result: tx_result.clone(),
+                            tx,
+                            proof: None,
+                        });
+                    }
+                }
+            }
+
+            height = height.increment();
+            self.next_height = Some(height);
+        }
+
+        Ok(responses)
+    }
+}
+
+async fn latest_block_height(
+    rpc_client: &PacedRpcClient<impl Client + Sync>,
+    rpc_address: &Url,
+) -> Result<BlockHeight, Error> {
+    let status = rpc_client
+        .status()
+        .await
+        .map_err(|e| Error::rpc(rpc_address.clone(), e))?;
+
+    Ok(status.sync_info.latest_block_height)
+}
+
 /// Given a vector of `TxSyncResult` elements,
 /// each including a transaction response hash for one or more messages, periodically queries the chain
 /// with the transaction hashes to get the list of IbcEvents included in those transactions.
+///
+/// Up to `max_concurrent_queries` transactions are queried concurrently.
+///
+/// The transactions are searched by hash, or in the new blocks of the chain from
+/// the given [`BlockSearch`], following the given [`TxConfirmation`].
+#[allow(clippy::too_many_arguments)]
 pub async fn wait_for_block_commits(
     chain_id: &ChainId,
-    rpc_client: &HttpClient,
+    rpc_client: &PacedRpcClient,
     rpc_address: &Url,
     rpc_timeout: &Duration,
+    max_concurrent_queries: usize,
+    confirmation: &TxConfirmation,
+    mut block_search: BlockSearch,
     tx_sync_results: &mut [TxSyncResult],
 ) -> Result<(), Error> {
     if all_tx_results_found(tx_sync_results) {
@@ -67,30 +253,115 @@ pub async fn wait_for_block_commits(
         } else {
             thread::sleep(WAIT_BACKOFF);
 
-            for tx_sync_result in tx_sync_results.iter_mut() {
-                let res =
-                    update_tx_sync_result(chain_id, rpc_client, rpc_address, tx_sync_result).await;
-                if let Err(e) = res {
-                    debug!("update_tx_sync_result failed: {e}");
+            let block_search = &mut block_search;
+
+            confirm_tx_sync_results(
+                chain_id,
+                confirmation,
+                max_concurrent_queries,
+                tx_sync_results,
+                |hash| async move { query_tx_response(rpc_client, rpc_address, &hash).await },
+                move |hashes| async move {
+                    block_search.search(rpc_client, rpc_address, &hashes).await
+                },
+            )
+            .await;
+        }
+    }
+}
+
+/// Queries the responses of all the pending transactions, by hash with `query`, or in
+/// the new blocks of the chain with `search_blocks`, following the given [`TxConfirmation`].
+async fn confirm_tx_sync_results<Query, QueryFut, Search, SearchFut>(
+    chain_id: &ChainId,
+    confirmation: &TxConfirmation,
+    max_concurrent_queries: usize,
+    tx_sync_results: &mut [TxSyncResult],
+    query: Query,
+    search_blocks: Search,
+) where
+    Query: Fn(TxHash) -> QueryFut,
+    QueryFut: Future<Output = Result<Option<TxResponse>, Error>>,
+    Search: FnOnce(Vec<TxHash>) -> SearchFut,
+    SearchFut: Future<Output = Result<Vec<TxResponse>, Error>>,
+{
+    if !confirmation.searches_blocks() {
+        update_tx_sync_results(chain_id, max_concurrent_queries, tx_sync_results, |hash| {
+            let response = query(hash);
+
+            async move {
+                let response = response.await;
+
+                if let Err(e) = &response {
+                    confirmation.switch_on_error(e);
                 }
+
+                response
             }
+        })
+        .await;
+
+        return;
+    }
+
+    let pending = tx_sync_results
+        .iter()
+        .filter(|result| matches!(result.status, TxStatus::Pending { .. }))
+        .map(|result| result.response.hash)
+        .collect();
+
+    match search_blocks(pending).await {
+        Ok(responses) => {
+            update_tx_sync_results(chain_id, max_concurrent_queries, tx_sync_results, |hash| {
+                future::ready(Ok(responses
+                    .iter()
+                    .find(|response| response.hash == hash)
+                    .cloned()))
+            })
+            .await
         }
+        Err(e) => debug!("searching the new blocks for the pending txs failed: {e}"),
     }
 }
 
-async fn update_tx_sync_result(
+/// Queries the responses of all the pending transactions, with at most
+/// `max_concurrent_queries` queries in flight at any given time.
+async fn update_tx_sync_results<Query, Fut>(
+    chain_id: &ChainId,
+    max_concurrent_queries: usize,
+    tx_sync_results: &mut [TxSyncResult],
+    query: Query,
+) where
+    Query: Fn(TxHash) -> Fut,
+    Fut: Future<Output = Result<Option<TxResponse>, Error>>,
+{
+    stream::iter(tx_sync_results.iter_mut())
+        .for_each_concurrent(max_concurrent_queries.max(1), |tx_sync_result| async {
+            let res = update_tx_sync_result(chain_id, tx_sync_result, &query).await;
+            if let Err(e) = res {
+                debug!("update_tx_sync_result failed: {e}");
+            }
+        })
+        .await;
+}
+
+async fn update_tx_sync_result<Query, Fut>(
     chain_id: &ChainId,
-    rpc_client: &HttpClient,
-    rpc_address: &Url,
     tx_sync_result: &mut TxSyncResult,
-) -> Result<(), Error> {
+    query: &Query,
+) -> Result<(), Error>
+where
+    Query: Fn(TxHash) -> Fut,
+    Fut: Future<Output = Result<Option<TxResponse>, Error>>,
+{
     if let TxStatus::Pending { message_count } = tx_sync_result.status {
-        let response =
-            query_tx_response(rpc_client, rpc_address, &tx_sync_result.response.hash).await?;
+        let response = query(tx_sync_result.response.hash).await?;
 
         if let Some(response) = response {
             tx_sync_result.status = TxStatus::ReceivedResponse;
 
+            record_fees_spent(chain_id, &response);
+
             let height = Height::new(chain_id.version(), u64::from(response.height)).unwrap();
             if response.tx_result.code.is_err() {
                 tx_sync_result.events = vec![
@@ -124,12 +395,22 @@ pub fn all_tx_results_found(tx_sync_results: &[TxSyncResult]) -> bool {
 }
 
 pub async fn wait_tx_succeed(
-    rpc_client: &HttpClient,
+    rpc_client: &PacedRpcClient,
     rpc_address: &Url,
     timeout: &Duration,
+    confirmation: &TxConfirmation,
+    block_search: BlockSearch,
     tx_hash: &TxHash,
 ) -> Result<TxResponse, Error> {
-    let response = wait_tx_hash(rpc_client, rpc_address, timeout, tx_hash).await?;
+    let response = wait_tx_hash(
+        rpc_client,
+        rpc_address,
+        timeout,
+        confirmation,
+        block_search,
+        tx_hash,
+    )
+    .await?;
 
     let response_code = response.tx_result.code;
     if response_code.is_err() {
@@ -140,15 +421,27 @@ pub async fn wait_tx_succeed(
 }
 
 pub async fn wait_tx_hash(
-    rpc_client: &HttpClient,
+    rpc_client: &PacedRpcClient,
     rpc_address: &Url,
     timeout: &Duration,
+    confirmation: &TxConfirmation,
+    mut block_search: BlockSearch,
     tx_hash: &TxHash,
 ) -> Result<TxResponse, Error> {
     let start_time = Instant::now();
 
     loop {
-        let response = query_tx_response(rpc_client, rpc_address, tx_hash).await?;
+        let response = if confirmation.searches_blocks() {
+            block_search
+                .search(rpc_client, rpc_address, &[*tx_hash])
+                .await?
+                .pop()
+        } else {
+            match query_tx_response(rpc_client, rpc_address, tx_hash).await {
+                Err(e) if confirmation.switch_on_error(&e) => None,
+                response => response?,
+            }
+        };
 
         match response {
             None => {
@@ -165,3 +458,354 @@ pub async fn wait_tx_hash(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::sync::atomic::AtomicUsize;
+
+    use core::sync::atomic::AtomicU32;
+    use std::sync::Mutex;
+
+    use subtle_encoding::base64;
+    use tendermint::abci::types::ExecTxResult;
+    use tendermint_rpc::endpoint::broadcast::tx_sync::Response as TxSyncResponse;
+    use tendermint_rpc::error::Error as RpcError;
+    use tendermint_rpc::response_error::{Code, ResponseError};
+    use tendermint_rpc::{Method, MockClient, MockRequestMatcher, Request, Response};
+
+    const TX: &[u8] = b"tx";
+    const HASH: &str = "E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855";
+    const TX_HEIGHT: u32 = 11;
+
+    /// A node with transaction indexing disabled, which includes the `TX` transaction
+    /// in the block at `TX_HEIGHT`, and whose latest height can be moved forward.
+    struct BlocksMatcher {
+        latest_height: AtomicU32,
+        requested_blocks: Mutex<Vec<u32>>,
+    }
+
+    impl BlocksMatcher {
+        fn new(latest_height: u32) -> Self {
+            Self {
+                latest_height: AtomicU32::new(latest_height),
+                requested_blocks: Mutex::new(vec![]),
+            }
+        }
+
+        fn status(&self) -> String {
+            let latest_height = self.latest_height.load(Ordering::SeqCst);
+
+            format!(
+                r#"{{
+                    "node_info": {{
+                        "channels": "40202122233038606100",
+                        "id": "cf4a66aa29e5123abfdfbdf485da6788bb8e46d1",
+                        "listen_addr": "tcp://0.0.0.0:26656",
+                        "moniker": "node",
+                        "network": "ibc-0",
+                        "other": {{ "rpc_address": "tcp://0.0.0.0:26657", "tx_index": "off" }},
+                        "protocol_version": {{ "app": "1", "block": "11", "p2p": "8" }},
+                        "version": "0.38.0"
+                    }},
+                    "sync_info": {{
+                        "catching_up": false,
+                        "earliest_app_hash": "0000000000000000",
+                        "earliest_block_hash": "{HASH}",
+                        "earliest_block_height": "1",
+                        "earliest_block_time": "2023-05-17T14:12:48.347696215Z",
+                        "latest_app_hash": "0600000000000000",
+                        "latest_block_hash": "{HASH}",
+                        "latest_block_height": "{latest_height}",
+                        "latest_block_time": "2023-05-17T14:14:48.530153458Z"
+                    }},
+                    "validator_info": {{
+                        "address": "2DD9F44FD9067555C322243C3C913BA7B51D2BE0",
+                        "pub_key": {{
+                            "type": "tendermint/PubKeyEd25519",
+                            "value": "bNNlGls5R25wC3Sd8720F/3+7IZBhXcD22MNFtPk/v0="
+                        }},
+                        "voting_power": "10"
+                    }}
+                }}"#
+            )
+        }
+
+        fn block(&self, height: u32) -> String {
+            self.requested_blocks.lock().unwrap().push(height);
+
+            let txs = if height == TX_HEIGHT {
+                format!(r#""{}""#, String::from_utf8(base64::encode(TX)).unwrap())
+            } else {
+                String::new()
+            };
+
+            format!(
+                r#"{{
+                    "block": {{
+                        "data": {{ "txs": [{txs}] }},
+                        "evidence": {{ "evidence": [] }},
+                        "header": {{
+                            "app_hash": "0000000000000000",
+                            "chain_id": "ibc-0",
+                            "consensus_hash": "{HASH}",
+                            "data_hash": "{HASH}",
+                            "evidence_hash": "{HASH}",
+                            "height": "{height}",
+                            "last_block_id": {{
+                                "hash": "{HASH}",
+                                "parts": {{
+                                    "hash": "{HASH}",
+                                    "total": 1
+                                }}
+                            }},
+                            "last_commit_hash": "{HASH}",
+                            "last_results_hash": "{HASH}",
+                            "next_validators_hash": "{HASH}",
+                            "proposer_address": "2DD9F44FD9067555C322243C3C913BA7B51D2BE0",
+                            "time": "2023-05-17T14:12:53.088875124Z",
+                            "validators_hash": "{HASH}",
+                            "version": {{ "app": "1", "block": "11" }}
+                        }},
+                        "last_commit": null
+                    }},
+                    "block_id": {{
+                        "hash": "{HASH}",
+                        "parts": {{
+                            "hash": "{HASH}",
+                            "total": 1
+                        }}
+                    }}
+                }}"#
+            )
+        }
+
+        fn block_results(&self, height: u32) -> String {
+            format!(
+                r#"{{
+                    "app_hash": null,
+                    "consensus_param_updates": null,
+                    "finalize_block_events": null,
+                    "height": "{height}",
+                    "txs_results": [{{
+                        "code": 0,
+                        "codespace": "",
+                        "data": null,
+                        "events": [],
+                        "gas_used": "0",
+                        "gas_wanted": "0",
+                        "info": "",
+                        "log": ""
+                    }}],
+                    "validator_updates": null
+                }}"#
+            )
+        }
+    }
+
+    impl MockRequestMatcher for &BlocksMatcher {
+        fn response_for<R, S>(&self, request: R) -> Option<Result<R::Response, RpcError>>
+        where
+            R: Request<S>,
+            S: tendermint_rpc::dialect::Dialect,
+        {
+            let method = request.method();
+
+            let height = || {
+                let request: serde_json::Value = serde_json::from_str(&request.into_json()).ok()?;
+                request["params"]["height"].as_str()?.parse().ok()
+            };
+
+            let result = match method {
+                Method::Status => self.status(),
+                Method::Block => self.block(height()?),
+                Method::BlockResults => self.block_results(height()?),
+                _ => return None,
+            };
+
+            let response = format!(r#"{{ "jsonrpc": "2.0", "id": "", "result": {result} }}"#);
+
+            Some(R::Response::from_string(response))
+        }
+    }
+
+    fn pending_tx(n: u8) -> TxSyncResult {
+        TxSyncResult {
+            response: TxSyncResponse {
+                codespace: String::new(),
+                code: Default::default(),
+                data: Default::default(),
+                log: String::new(),
+                hash: TxHash::Sha256([n; 32]),
+            },
+            events: vec![],
+            status: TxStatus::Pending { message_count: 1 },
+        }
+    }
+
+    fn tx_response(hash: TxHash) -> TxResponse {
+        TxResponse {
+            hash,
+            height: 10_u32.into(),
+            index: 0,
+            tx_result: ExecTxResult::default(),
+            tx: vec![],
+            proof: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn confirmation_switches_to_block_search_when_tx_indexing_is_disabled() {
+        let chain_id = ChainId::from_string("ibc-0");
+        let confirmation = TxConfirmation::new(chain_id.clone(), TxConfirmationStrategy::Auto);
+        let mut tx_sync_results: Vec<_> = (0..2).map(pending_tx).collect();
+
+        let tx_searches = AtomicUsize::new(0);
+        let block_searches = AtomicUsize::new(0);
+
+        // A node with transaction indexing disabled, which includes the txs in its next block
+        let query = |_hash| {
+            tx_searches.fetch_add(1, Ordering::SeqCst);
+
+            async {
+                Err(Error::rpc(
+                    "http://127.0.0.1:26657".parse().unwrap(),
+                    RpcError::response(ResponseError::new(
+                        Code::InternalError,
+                        Some("transaction indexing is disabled".to_string()),
+                    )),
+                ))
+            }
+        };
+
+        let search_blocks = |hashes: Vec<TxHash>| {
+            block_searches.fetch_add(1, Ordering::SeqCst);
+
+            async move { Ok(hashes.into_iter().map(tx_response).collect()) }
+        };
+
+        assert!(!confirmation.searches_blocks());
+
+        confirm_tx_sync_results(
+            &chain_id,
+            &confirmation,
+            2,
+            &mut tx_sync_results,
+            query,
+            search_blocks,
+        )
+        .await;
+
+        // The txs cannot be searched by hash, so the confirmation switches to the search of blocks
+        assert!(confirmation.searches_blocks());
+        assert!(!all_tx_results_found(&tx_sync_results));
+        assert_eq!(tx_searches.load(Ordering::SeqCst), 2);
+        assert_eq!(block_searches.load(Ordering::SeqCst), 0);
+
+        confirm_tx_sync_results(
+            &chain_id,
+            &confirmation,
+            2,
+            &mut tx_sync_results,
+            query,
+            search_blocks,
+        )
+        .await;
+
+        // The inclusion of the txs is confirmed by the search of blocks
+        assert!(all_tx_results_found(&tx_sync_results));
+        assert_eq!(tx_searches.load(Ordering::SeqCst), 2);
+        assert_eq!(block_searches.load(Ordering::SeqCst), 1);
+
+        // The switch applies to all the txs sent to the chain, and is not undone
+        assert!(confirmation.clone().searches_blocks());
+
+        // Chains configured to search txs by hash do not switch
+        let tx_search = TxConfirmation::new(chain_id, TxConfirmationStrategy::TxSearch);
+        tx_search.switch_to_block_search();
+        assert!(!tx_search.searches_blocks());
+    }
+
+    #[tokio::test]
+    async fn block_search_finds_txs_committed_since_its_start() {
+        let rpc_address: Url = "http://127.0.0.1:26657".parse().unwrap();
+        let confirmation = TxConfirmation::new(
+            ChainId::from_string("ibc-0"),
+            TxConfirmationStrategy::BlockSearch,
+        );
+
+        let matcher = BlocksMatcher::new(TX_HEIGHT - 1);
+        let (client, _driver) = MockClient::new(&matcher);
+        let client = PacedRpcClient::new(client, None);
+
+        // The search starts before the tx is broadcast, and the tx is committed right
+        // away, in a block which is no longer the latest one at the first search
+        let mut block_search = BlockSearch::start(&client, &rpc_address, &confirmation).await;
+        matcher.latest_height.store(TX_HEIGHT + 1, Ordering::SeqCst);
+
+        let tx_hash = TxHash::Sha256(Sha256::digest(TX).into());
+        let responses = block_search
+            .search(&client, &rpc_address, &[tx_hash])
+            .await
+            .unwrap();
+
+        assert_eq!(responses.len(), 1);
+        assert_eq!(responses[0].hash, tx_hash);
+        assert_eq!(responses[0].height, TX_HEIGHT.into());
+        assert_eq!(
+            *matcher.requested_blocks.lock().unwrap(),
+            vec![TX_HEIGHT - 1, TX_HEIGHT, TX_HEIGHT + 1]
+        );
+
+        // The blocks already searched are not searched again
+        let responses = block_search
+            .search(&client, &rpc_address, &[tx_hash])
+            .await
+            .unwrap();
+
+        assert!(responses.is_empty());
+        assert_eq!(matcher.requested_blocks.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn pending_txs_are_confirmed_concurrently() {
+        const TX_COUNT: u8 = 8;
+
+        let chain_id = ChainId::from_string("ibc-0");
+
+        for max_concurrent_queries in [1, 3, TX_COUNT as usize] {
+            let mut tx_sync_results: Vec<_> = (0..TX_COUNT).map(pending_tx).collect();
+
+            let in_flight = AtomicUsize::new(0);
+            let max_in_flight = AtomicUsize::new(0);
+
+            update_tx_sync_results(
+                &chain_id,
+                max_concurrent_queries,
+                &mut tx_sync_results,
+                |hash| {
+                    let (in_flight, max_in_flight) = (&in_flight, &max_in_flight);
+
+                    async move {
+                        let count = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                        max_in_flight.fetch_max(count, Ordering::SeqCst);
+
+                        // Give the other pending queries a chance to start
+                        for _ in 0..TX_COUNT {
+                            tokio::task::yield_now().await;
+                        }
+
+                        in_flight.fetch_sub(1, Ordering::SeqCst);
+
+                        Ok(Some(tx_response(hash)))
+                    }
+                },
+            )
+            .await;
+
+            assert!(all_tx_results_found(&tx_sync_results));
+            assert_eq!(max_in_flight.load(Ordering::SeqCst), max_concurrent_queries);
+        }
+    }
+}
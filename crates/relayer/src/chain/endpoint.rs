@@ -37,6 +37,7 @@ use tendermint_rpc::endpoint::broadcast::tx_sync::Response as TxResponse;
 use crate::account::Balance;
 use crate::chain::client::ClientSettings;
 use crate::chain::handle::Subscription;
+use crate::chain::health::HealthReport;
 use crate::chain::requests::*;
 use crate::chain::tracking::TrackedMsgs;
 use crate::chain::version::Specs;
@@ -50,13 +51,6 @@ use crate::event::IbcEventWithHeight;
 use crate::keyring::{AnySigningKeyPair, KeyRing, SigningKeyPairSized};
 use crate::misbehaviour::MisbehaviourEvidence;
 
-/// The result of a health check.
-#[derive(Debug)]
-pub enum HealthCheck {
-    Healthy,
-    Unhealthy(Box<Error>),
-}
-
 /// The result of the application status query.
 #[derive(Clone, Debug)]
 pub struct ChainStatus {
@@ -98,8 +92,12 @@ pub trait ChainEndpoint: Sized {
     /// Shutdown the chain runtime
     fn shutdown(self) -> Result<(), Error>;
 
-    /// Perform a health check
-    fn health_check(&mut self) -> Result<HealthCheck, Error>;
+    /// Prepares the chain for an operation of the chain runtime, which
+    /// the runtime calls before handling each of its requests
+    fn start_operation(&mut self) {}
+
+    /// Perform a health check, reporting the outcome of each of its checks
+    fn health_check(&mut self) -> Result<HealthReport, Error>;
 
     // Events
     fn subscribe(&mut self) -> Result<Subscription, Error>;
@@ -186,6 +184,10 @@ pub trait ChainEndpoint: Sized {
     /// Query the latest height and timestamp the application is at
     fn query_application_status(&self) -> Result<ChainStatus, Error>;
 
+    /// Query the earliest height at which the node still has the state of the chain,
+    /// ie. below which the state has been pruned.
+    fn query_earliest_height(&self) -> Result<ICSHeight, Error>;
+
     /// Performs a query to retrieve the state of all clients that a chain hosts.
     fn query_clients(
         &self,
@@ -706,4 +708,13 @@ pub trait ChainEndpoint: Sized {
     ) -> Result<(ErrorReceipt, Option<MerkleProof>), Error>;
 
     fn query_ccv_consumer_id(&self, client_id: ClientId) -> Result<ConsumerId, Error>;
+
+    /// Delay the broadcast of the transactions subsequently submitted to the chain
+    /// by the given amount, or stop delaying them if `None`, for testing.
+    #[cfg(feature = "submit-delay")]
+    fn set_submit_delay(&mut self, delay: Option<core::time::Duration>) -> Result<(), Error>;
+
+    /// The number of broadcasts of transactions to the chain delayed so far.
+    #[cfg(feature = "submit-delay")]
+    fn query_delayed_submissions(&self) -> Result<u64, Error>;
 }
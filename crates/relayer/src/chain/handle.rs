@@ -47,11 +47,8 @@ use crate::{
 };
 
 use super::{
-    client::ClientSettings,
-    endpoint::{ChainStatus, HealthCheck},
-    requests::*,
-    tracking::TrackedMsgs,
-    version::Specs,
+    client::ClientSettings, endpoint::ChainStatus, health::HealthReport, requests::*,
+    tracking::TrackedMsgs, version::Specs,
 };
 
 mod base;
@@ -109,7 +106,7 @@ pub enum ChainRequest {
     },
 
     HealthCheck {
-        reply_to: ReplyTo<HealthCheck>,
+        reply_to: ReplyTo<HealthReport>,
     },
 
     Subscribe {
@@ -134,6 +131,15 @@ pub enum ChainRequest {
         reply_to: ReplyTo<Signer>,
     },
 
+    KeySigner {
+        key_name: String,
+        reply_to: ReplyTo<Signer>,
+    },
+
+    NextSigningAccount {
+        reply_to: ReplyTo<Option<String>>,
+    },
+
     GetKey {
         reply_to: ReplyTo<AnySigningKeyPair>,
     },
@@ -168,6 +174,10 @@ pub enum ChainRequest {
         reply_to: ReplyTo<ChainStatus>,
     },
 
+    QueryEarliestHeight {
+        reply_to: ReplyTo<Height>,
+    },
+
     QueryClients {
         request: QueryClientStatesRequest,
         reply_to: ReplyTo<Vec<IdentifiedAnyClientState>>,
@@ -393,6 +403,17 @@ pub enum ChainRequest {
         client_id: ClientId,
         reply_to: ReplyTo<ConsumerId>,
     },
+
+    #[cfg(feature = "submit-delay")]
+    SetSubmitDelay {
+        delay: Option<core::time::Duration>,
+        reply_to: ReplyTo<()>,
+    },
+
+    #[cfg(feature = "submit-delay")]
+    QueryDelayedSubmissions {
+        reply_to: ReplyTo<u64>,
+    },
 }
 
 pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
@@ -404,8 +425,8 @@ pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
     /// Shutdown the chain runtime.
     fn shutdown(&self) -> Result<(), Error>;
 
-    /// Perform a health check
-    fn health_check(&self) -> Result<HealthCheck, Error>;
+    /// Perform a health check, reporting the outcome of each of its checks
+    fn health_check(&self) -> Result<HealthReport, Error>;
 
     /// Subscribe to the events emitted by the chain.
     fn subscribe(&self) -> Result<Subscription, Error>;
@@ -428,6 +449,13 @@ pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
 
     fn get_signer(&self) -> Result<Signer, Error>;
 
+    /// Get the signer of the key with the given name in the chain's keyring.
+    fn get_key_signer(&self, key_name: String) -> Result<Signer, Error>;
+
+    /// Get the name of the key which signs the next transaction relayed to the chain,
+    /// taking its `signing_accounts` in turn, or `None` if the chain has none.
+    fn next_signing_account(&self) -> Result<Option<String>, Error>;
+
     fn config(&self) -> Result<ChainConfig, Error>;
 
     fn get_key(&self) -> Result<AnySigningKeyPair, Error>;
@@ -460,6 +488,10 @@ pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
         Ok(self.query_application_status()?.height)
     }
 
+    /// Query the earliest height at which the node still has the state of the chain,
+    /// ie. below which the state has been pruned.
+    fn query_earliest_height(&self) -> Result<Height, Error>;
+
     /// Performs a query to retrieve the state of all clients that a chain hosts.
     fn query_clients(
         &self,
@@ -722,4 +754,14 @@ pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
     ) -> Result<(ErrorReceipt, Option<MerkleProof>), Error>;
 
     fn query_ccv_consumer_id(&self, client_id: &ClientId) -> Result<ConsumerId, Error>;
+
+    /// Delay the broadcast of the transactions subsequently submitted to the chain
+    /// by the given amount, or stop delaying them if `None`, for testing.
+    #[cfg(feature = "submit-delay")]
+    fn set_submit_delay(&self, delay: Option<core::time::Duration>) -> Result<(), Error>;
+
+    /// The number of broadcasts of transactions to the chain delayed so far,
+    /// see [`ChainHandle::set_submit_delay`].
+    #[cfg(feature = "submit-delay")]
+    fn query_delayed_submissions(&self) -> Result<u64, Error>;
 }
@@ -48,7 +48,7 @@ use crate::{
     misbehaviour::MisbehaviourEvidence,
 };
 
-use super::{reply_channel, ChainHandle, ChainRequest, HealthCheck, ReplyTo, Subscription};
+use super::{reply_channel, ChainHandle, ChainRequest, HealthReport, ReplyTo, Subscription};
 
 /// A basic chain handle implementation.
 /// For use in interactive CLIs, e.g., `query`, `tx`, etc.
@@ -102,7 +102,7 @@ impl ChainHandle for BaseChainHandle {
         self.chain_id.clone()
     }
 
-    fn health_check(&self) -> Result<HealthCheck, Error> {
+    fn health_check(&self) -> Result<HealthReport, Error> {
         self.send(|reply_to| ChainRequest::HealthCheck { reply_to })
     }
 
@@ -138,6 +138,14 @@ impl ChainHandle for BaseChainHandle {
         self.send(|reply_to| ChainRequest::Signer { reply_to })
     }
 
+    fn get_key_signer(&self, key_name: String) -> Result<Signer, Error> {
+        self.send(|reply_to| ChainRequest::KeySigner { key_name, reply_to })
+    }
+
+    fn next_signing_account(&self) -> Result<Option<String>, Error> {
+        self.send(|reply_to| ChainRequest::NextSigningAccount { reply_to })
+    }
+
     fn config(&self) -> Result<ChainConfig, Error> {
         self.send(|reply_to| ChainRequest::Config { reply_to })
     }
@@ -182,6 +190,10 @@ impl ChainHandle for BaseChainHandle {
         self.send(|reply_to| ChainRequest::QueryApplicationStatus { reply_to })
     }
 
+    fn query_earliest_height(&self) -> Result<Height, Error> {
+        self.send(|reply_to| ChainRequest::QueryEarliestHeight { reply_to })
+    }
+
     fn query_clients(
         &self,
         request: QueryClientStatesRequest,
@@ -563,4 +575,14 @@ impl ChainHandle for BaseChainHandle {
             reply_to,
         })
     }
+
+    #[cfg(feature = "submit-delay")]
+    fn set_submit_delay(&self, delay: Option<core::time::Duration>) -> Result<(), Error> {
+        self.send(|reply_to| ChainRequest::SetSubmitDelay { delay, reply_to })
+    }
+
+    #[cfg(feature = "submit-delay")]
+    fn query_delayed_submissions(&self) -> Result<u64, Error> {
+        self.send(|reply_to| ChainRequest::QueryDelayedSubmissions { reply_to })
+    }
 }
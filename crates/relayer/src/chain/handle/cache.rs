@@ -30,8 +30,9 @@ use ibc_relayer_types::Height;
 use crate::account::Balance;
 use crate::cache::{Cache, CacheStatus};
 use crate::chain::client::ClientSettings;
-use crate::chain::endpoint::{ChainStatus, HealthCheck};
+use crate::chain::endpoint::ChainStatus;
 use crate::chain::handle::{ChainHandle, ChainRequest, Subscription};
+use crate::chain::health::HealthReport;
 use crate::chain::requests::*;
 use crate::chain::tracking::TrackedMsgs;
 use crate::chain::version::Specs;
@@ -90,7 +91,7 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
         self.inner().shutdown()
     }
 
-    fn health_check(&self) -> Result<HealthCheck, Error> {
+    fn health_check(&self) -> Result<HealthReport, Error> {
         self.inner().health_check()
     }
 
@@ -116,6 +117,14 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
         self.inner().get_signer()
     }
 
+    fn get_key_signer(&self, key_name: String) -> Result<Signer, Error> {
+        self.inner().get_key_signer(key_name)
+    }
+
+    fn next_signing_account(&self) -> Result<Option<String>, Error> {
+        self.inner().next_signing_account()
+    }
+
     fn config(&self) -> Result<ChainConfig, Error> {
         self.inner().config()
     }
@@ -152,6 +161,10 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
         self.inner().query_application_status()
     }
 
+    fn query_earliest_height(&self) -> Result<Height, Error> {
+        self.inner().query_earliest_height()
+    }
+
     fn query_latest_height(&self) -> Result<Height, Error> {
         let handle = self.inner();
         let (result, in_cache) = self
@@ -543,4 +556,14 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
     fn query_ccv_consumer_id(&self, client_id: &ClientId) -> Result<ConsumerId, Error> {
         self.inner.query_ccv_consumer_id(client_id)
     }
+
+    #[cfg(feature = "submit-delay")]
+    fn set_submit_delay(&self, delay: Option<core::time::Duration>) -> Result<(), Error> {
+        self.inner.set_submit_delay(delay)
+    }
+
+    #[cfg(feature = "submit-delay")]
+    fn query_delayed_submissions(&self) -> Result<u64, Error> {
+        self.inner.query_delayed_submissions()
+    }
 }
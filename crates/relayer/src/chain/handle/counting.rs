@@ -31,8 +31,9 @@ use ibc_relayer_types::Height;
 
 use crate::account::Balance;
 use crate::chain::client::ClientSettings;
-use crate::chain::endpoint::{ChainStatus, HealthCheck};
+use crate::chain::endpoint::ChainStatus;
 use crate::chain::handle::{ChainHandle, ChainRequest, Subscription};
+use crate::chain::health::HealthReport;
 use crate::chain::requests::*;
 use crate::chain::tracking::TrackedMsgs;
 use crate::chain::version::Specs;
@@ -108,7 +109,7 @@ impl<Handle: ChainHandle> ChainHandle for CountingChainHandle<Handle> {
         self.inner().shutdown()
     }
 
-    fn health_check(&self) -> Result<HealthCheck, Error> {
+    fn health_check(&self) -> Result<HealthReport, Error> {
         self.inc_metric("health_check");
         self.inner().health_check()
     }
@@ -139,6 +140,16 @@ impl<Handle: ChainHandle> ChainHandle for CountingChainHandle<Handle> {
         self.inner().get_signer()
     }
 
+    fn get_key_signer(&self, key_name: String) -> Result<Signer, Error> {
+        self.inc_metric("get_key_signer");
+        self.inner().get_key_signer(key_name)
+    }
+
+    fn next_signing_account(&self) -> Result<Option<String>, Error> {
+        self.inc_metric("next_signing_account");
+        self.inner().next_signing_account()
+    }
+
     fn config(&self) -> Result<ChainConfig, Error> {
         self.inc_metric("config");
         self.inner().config()
@@ -188,6 +199,11 @@ impl<Handle: ChainHandle> ChainHandle for CountingChainHandle<Handle> {
         self.inner().query_latest_height()
     }
 
+    fn query_earliest_height(&self) -> Result<Height, Error> {
+        self.inc_metric("query_earliest_height");
+        self.inner().query_earliest_height()
+    }
+
     fn query_clients(
         &self,
         request: QueryClientStatesRequest,
@@ -538,4 +554,16 @@ impl<Handle: ChainHandle> ChainHandle for CountingChainHandle<Handle> {
         self.inc_metric("query_ccv_consumer_id");
         self.inner.query_ccv_consumer_id(client_id)
     }
+
+    #[cfg(feature = "submit-delay")]
+    fn set_submit_delay(&self, delay: Option<core::time::Duration>) -> Result<(), Error> {
+        self.inc_metric("set_submit_delay");
+        self.inner.set_submit_delay(delay)
+    }
+
+    #[cfg(feature = "submit-delay")]
+    fn query_delayed_submissions(&self) -> Result<u64, Error> {
+        self.inc_metric("query_delayed_submissions");
+        self.inner.query_delayed_submissions()
+    }
 }
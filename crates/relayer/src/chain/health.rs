@@ -0,0 +1,170 @@
+//! Report of the health check of a chain, performed by the supervisor on startup
+//! and by the `health-check` command.
+
+use core::fmt::{Display, Error as FmtError, Formatter};
+
+use serde::Serialize;
+use tracing::{error, info, warn};
+
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+/// The outcome of a check, ordered from the best to the worst.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// The checks performed by the health check of a chain.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthCheckKind {
+    /// The RPC endpoint of the node responds
+    RpcReachable,
+    /// The gRPC endpoint of the node accepts connections
+    GrpcReachable,
+    /// The node is synced with the network
+    NodeSynced,
+    /// The node reports the configured chain identifier
+    ChainId,
+    /// The inclusion of transactions can be confirmed
+    TxIndexing,
+    /// The versions of the SDK and of ibc-go of the chain are supported
+    VersionCompatible,
+    /// The gas price of the relayer is accepted by the node
+    MinGasPrice,
+    /// The staking module keeps the historical entries needed by the handshakes
+    HistoricalEntries,
+    /// The account of the relayer exists on the chain
+    AccountExists,
+    /// The relayer is part of the relayer whitelist of the chain
+    RelayerWhitelist,
+    /// The configuration of the chain is consistent with its parameters
+    Params,
+}
+
+impl Display for HealthCheckKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        let name = match self {
+            Self::RpcReachable => "rpc_reachable",
+            Self::GrpcReachable => "grpc_reachable",
+            Self::NodeSynced => "node_synced",
+            Self::ChainId => "chain_id",
+            Self::TxIndexing => "tx_indexing",
+            Self::VersionCompatible => "version_compatible",
+            Self::MinGasPrice => "min_gas_price",
+            Self::HistoricalEntries => "historical_entries",
+            Self::AccountExists => "account_exists",
+            Self::RelayerWhitelist => "relayer_whitelist",
+            Self::Params => "params",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+/// The outcome of one of the checks of a health check.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct HealthCheckEntry {
+    pub check: HealthCheckKind,
+    pub status: HealthStatus,
+    pub message: String,
+}
+
+/// The outcome of each of the checks performed by the health check of a chain.
+#[derive(Clone, Debug, Serialize)]
+pub struct HealthReport {
+    pub chain_id: ChainId,
+    pub checks: Vec<HealthCheckEntry>,
+}
+
+impl HealthReport {
+    pub fn new(chain_id: ChainId) -> Self {
+        Self {
+            chain_id,
+            checks: Vec::new(),
+        }
+    }
+
+    pub fn pass(&mut self, check: HealthCheckKind, message: impl Into<String>) {
+        self.push(check, HealthStatus::Pass, message)
+    }
+
+    pub fn warn(&mut self, check: HealthCheckKind, message: impl Into<String>) {
+        self.push(check, HealthStatus::Warn, message)
+    }
+
+    pub fn fail(&mut self, check: HealthCheckKind, message: impl Into<String>) {
+        self.push(check, HealthStatus::Fail, message)
+    }
+
+    fn push(&mut self, check: HealthCheckKind, status: HealthStatus, message: impl Into<String>) {
+        self.checks.push(HealthCheckEntry {
+            check,
+            status,
+            message: message.into(),
+        });
+    }
+
+    /// The outcome of the given check, if it was performed.
+    pub fn get(&self, check: HealthCheckKind) -> Option<&HealthCheckEntry> {
+        self.checks.iter().find(|entry| entry.check == check)
+    }
+
+    /// The worst outcome among the checks, `Pass` if no check was performed.
+    pub fn status(&self) -> HealthStatus {
+        self.checks
+            .iter()
+            .map(|entry| entry.status)
+            .max()
+            .unwrap_or(HealthStatus::Pass)
+    }
+
+    /// Whether none of the checks failed. Warnings do not make a chain unhealthy.
+    pub fn is_healthy(&self) -> bool {
+        self.status() != HealthStatus::Fail
+    }
+
+    /// Logs the outcome of each check, followed by the health of the chain.
+    pub fn log(&self) {
+        for entry in &self.checks {
+            match entry.status {
+                HealthStatus::Pass => info!(check = %entry.check, "{}", entry.message),
+                HealthStatus::Warn => warn!(check = %entry.check, "{}", entry.message),
+                HealthStatus::Fail => error!(check = %entry.check, "{}", entry.message),
+            }
+        }
+
+        match self.status() {
+            HealthStatus::Pass => info!("chain is healthy"),
+            HealthStatus::Warn => warn!("chain is healthy, but some checks reported warnings"),
+            HealthStatus::Fail => {
+                warn!("chain is not healthy");
+                warn!("some Hermes features may not work in this mode!");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_is_the_worst_outcome() {
+        let mut report = HealthReport::new(ChainId::from_string("ibc-0"));
+        assert_eq!(report.status(), HealthStatus::Pass);
+
+        report.pass(HealthCheckKind::RpcReachable, "reachable");
+        report.warn(HealthCheckKind::MinGasPrice, "too low");
+        assert_eq!(report.status(), HealthStatus::Warn);
+        assert!(report.is_healthy());
+
+        report.fail(HealthCheckKind::AccountExists, "not found");
+        report.pass(HealthCheckKind::Params, "consistent");
+        assert_eq!(report.status(), HealthStatus::Fail);
+        assert!(!report.is_healthy());
+    }
+}
@@ -4,6 +4,7 @@ use prost::Message;
 use std::thread;
 use tracing::debug;
 
+use core::num::NonZeroUsize;
 use core::time::Duration;
 
 use ibc_proto::ibc::applications::fee::v1::{
@@ -17,6 +18,7 @@ use ibc_relayer_types::clients::ics07_tendermint::client_state::{
 };
 use ibc_relayer_types::clients::ics07_tendermint::consensus_state::ConsensusState as TmConsensusState;
 use ibc_relayer_types::clients::ics07_tendermint::header::Header as TmHeader;
+use ibc_relayer_types::core::ics02_client::client_type::ClientType;
 use ibc_relayer_types::core::ics02_client::events::UpdateClient;
 use ibc_relayer_types::core::ics03_connection::connection::{
     ConnectionEnd, IdentifiedConnectionEnd,
@@ -66,8 +68,10 @@ use crate::account::Balance;
 use crate::chain::client::ClientSettings;
 use crate::chain::cosmos::batch::response_to_tx_sync_result;
 use crate::chain::cosmos::config::CosmosSdkConfig;
-use crate::chain::endpoint::{ChainEndpoint, ChainStatus, HealthCheck};
+use crate::chain::cosmos::request_rate::PacedRpcClient;
+use crate::chain::endpoint::{ChainEndpoint, ChainStatus};
 use crate::chain::handle::Subscription;
+use crate::chain::health::{HealthCheckKind, HealthReport};
 use crate::chain::requests::*;
 use crate::chain::tracking::TrackedMsgs;
 use crate::chain::version::{ConsensusVersion, Specs};
@@ -77,12 +81,13 @@ use crate::config::ChainConfig;
 use crate::consensus_state::AnyConsensusState;
 use crate::denom::DenomTrace;
 use crate::error::Error;
-use crate::event::source::{EventSource, TxEventSourceCmd};
+use crate::event::source::{EventSource, ReconnectBackoff, TxEventSourceCmd};
 use crate::event::IbcEventWithHeight;
 use crate::keyring::{KeyRing, NamadaKeyPair, SigningKeyPair};
 use crate::light_client::tendermint::LightClient as TmLightClient;
 use crate::light_client::{LightClient, Verified};
 use crate::misbehaviour::MisbehaviourEvidence;
+use crate::util::semaphore::map_bounded;
 
 use self::error::Error as NamadaError;
 
@@ -123,11 +128,19 @@ impl NamadaChain {
             .parse()
             .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
         let (event_source, monitor_tx) = match &self.config.event_source {
-            Mode::Push { url, batch_delay } => EventSource::websocket(
+            Mode::Push {
+                url,
+                batch_delay,
+                queries,
+                reconnect_backoff,
+                max_reconnect_backoff,
+            } => EventSource::websocket(
                 self.config.id.clone(),
                 url.clone(),
                 compat_mode,
                 *batch_delay,
+                queries.iter().cloned().map(Into::into).collect(),
+                ReconnectBackoff::new(*reconnect_backoff, *max_reconnect_backoff),
                 self.rt.clone(),
             ),
             Mode::Pull {
@@ -135,9 +148,10 @@ impl NamadaChain {
                 max_retries,
             } => EventSource::rpc(
                 self.config.id.clone(),
-                http_client,
+                PacedRpcClient::new(http_client, None),
                 *interval,
                 *max_retries,
+                crate::event::source::rpc::last_processed_height_path(&self.config.id),
                 self.rt.clone(),
             ),
         }
@@ -222,7 +236,7 @@ impl ChainEndpoint for NamadaChain {
                 .try_into()
                 .expect("node ID should be able to converted"),
         );
-        let light_client = TmLightClient::from_cosmos_sdk_config(&config, node_id)?;
+        let light_client = TmLightClient::from_cosmos_sdk_config(&config, node_id, None, None)?;
 
         let keybase =
             KeyRing::new_namada(config.key_store_type, &config.id, &config.key_store_folder)
@@ -280,19 +294,27 @@ impl ChainEndpoint for NamadaChain {
         Ok(())
     }
 
-    fn health_check(&mut self) -> Result<HealthCheck, Error> {
-        self.rt
-            .block_on(Client::health(self.ctx.client()))
-            .map_err(|e| {
+    fn health_check(&mut self) -> Result<HealthReport, Error> {
+        let mut report = HealthReport::new(self.config.id.clone());
+
+        match self.rt.block_on(Client::health(self.ctx.client())) {
+            Ok(_) => report.pass(
+                HealthCheckKind::RpcReachable,
+                format!("RPC endpoint {} is reachable", self.config.rpc_addr),
+            ),
+            Err(e) => report.fail(
+                HealthCheckKind::RpcReachable,
                 NamadaError::health_check_json_rpc(
                     self.config.id.clone(),
                     self.config.rpc_addr.to_string(),
                     "/health".to_string(),
                     e,
                 )
-            })?;
+                .to_string(),
+            ),
+        }
 
-        Ok(HealthCheck::Healthy)
+        Ok(report)
     }
 
     fn subscribe(&mut self) -> Result<Subscription, Error> {
@@ -372,7 +394,8 @@ impl ChainEndpoint for NamadaChain {
         let msg_chunks = proto_msgs.chunks(max_msg_num);
         let mut tx_sync_results = vec![];
         for msg_chunk in msg_chunks {
-            let response = self.batch_txs(msg_chunk)?;
+            let response =
+                self.batch_txs(msg_chunk, tracked_msgs.counterparty_chain_id.as_ref())?;
             tx_sync_results.push(response_to_tx_sync_result(
                 &self.config.id,
                 msg_chunk.len(),
@@ -415,7 +438,7 @@ impl ChainEndpoint for NamadaChain {
         let msg_chunks = proto_msgs.chunks(max_msg_num);
         let mut responses = vec![];
         for msg_chunk in msg_chunks {
-            let resp = self.batch_txs(msg_chunk)?;
+            let resp = self.batch_txs(msg_chunk, tracked_msgs.counterparty_chain_id.as_ref())?;
             let response = into_tm_response(resp);
             if response.code.is_err() {
                 return Err(Error::send_tx(response.log));
@@ -594,6 +617,27 @@ impl ChainEndpoint for NamadaChain {
         })
     }
 
+    fn query_earliest_height(&self) -> Result<ICSHeight, Error> {
+        crate::time!(
+            "query_earliest_height",
+            {
+                "src_chain": self.config.id.to_string(),
+            }
+        );
+        crate::telemetry!(query, self.id(), "query_earliest_height");
+
+        let status = self
+            .rt
+            .block_on(Client::status(self.ctx.client()))
+            .map_err(|e| NamadaError::rpc(self.config.rpc_addr.clone(), e))?;
+
+        ICSHeight::new(
+            ChainId::chain_version(status.node_info.network.as_str()),
+            u64::from(status.sync_info.earliest_block_height),
+        )
+        .map_err(Error::ics02)
+    }
+
     fn query_clients(
         &self,
         _request: QueryClientStatesRequest,
@@ -606,21 +650,38 @@ impl ChainEndpoint for NamadaChain {
         );
         crate::telemetry!(query, self.id(), "query_clients");
 
-        let prefix = storage::ibc_key("clients").expect("the path should be parsable");
-        let mut states = vec![];
-        for prefix_value in self.query_prefix(prefix)? {
-            let PrefixValue { key, value } = prefix_value;
-            if key.to_string().ends_with("clientState") {
-                let client_id =
-                    storage::client_id(&key).map_err(|e| Error::query(e.to_string()))?;
-                let client_id = ClientId::from_str(&client_id.to_string())
-                    .expect("ibc-rs ClientId should be parsable with the relayer-types one");
-                let client_state = AnyClientState::decode_vec(&value).map_err(Error::decode)?;
-                states.push(IdentifiedAnyClientState::new(client_id, client_state));
-            }
-        }
+        let (value, _) = self.query(
+            storage::client_counter_key(),
+            QueryHeight::Latest,
+            IncludeProof::No,
+        )?;
+        let client_counter = if value.is_empty() {
+            0
+        } else {
+            u64::try_from_slice(&value[..]).map_err(NamadaError::borsh_decode)?
+        };
 
-        Ok(states)
+        // Only share the runtime and the RPC client with the threads running the queries
+        let (rt, client) = (&self.rt, self.ctx.client());
+
+        query_client_states(
+            client_counter,
+            self.config.max_concurrent_client_state_queries,
+            |client_id| {
+                let path = ClientStatePath(client_id.clone());
+                let key = storage::ibc_key(path.to_string()).expect("the path should be parsable");
+                let (value, _) = rt
+                    .block_on(rpc::query_storage_value_bytes(client, &key, None, false))
+                    .map_err(NamadaError::namada)?;
+
+                match value {
+                    Some(value) if !value.is_empty() => AnyClientState::decode_vec(&value)
+                        .map(Some)
+                        .map_err(Error::decode),
+                    _ => Ok(None),
+                }
+            },
+        )
     }
 
     fn query_client_state(
@@ -1071,21 +1132,21 @@ impl ChainEndpoint for NamadaChain {
         );
         crate::telemetry!(query, self.id(), "query_packet_acknowledgements");
 
+        if request.packet_commitment_sequences.is_empty() {
+            return Ok((Vec::new(), self.query_application_status()?.height));
+        }
+
+        // Query the acknowledgements of all the packets of the channel at once,
+        // rather than the acknowledgement of each sequence in turn
         let path = format!(
             "acks/ports/{}/channels/{}/sequences",
             request.port_id, request.channel_id
         );
         let prefix = storage::ibc_key(path).expect("the path should be parsable");
-        let mut sequences = vec![];
-        for prefix_value in self.query_prefix(prefix)? {
-            let PrefixValue { key, value: _ } = prefix_value;
-            let (_, _, sequence) =
-                storage::port_channel_sequence_id(&key).map_err(|e| Error::query(e.to_string()))?;
-            let sequence = u64::from(sequence).into();
-            if request.packet_commitment_sequences.contains(&sequence) {
-                sequences.push(sequence);
-            }
-        }
+        let sequences = query::stored_sequences(
+            self.query_prefix(prefix)?,
+            &request.packet_commitment_sequences,
+        )?;
 
         // NOTE the height might be mismatched with the previous query
         let status = self.query_application_status()?;
@@ -1172,6 +1233,10 @@ impl ChainEndpoint for NamadaChain {
 
                 self.query_tx_events(&tm_hash)
             }
+            // Namada transactions are not indexed by their sender
+            QueryTxRequest::Sender(_) => Ok(vec![]),
+            // The packets sent by Namada are only looked up by sequence
+            QueryTxRequest::SendPackets(_) => Ok(vec![]),
         }
     }
 
@@ -1370,6 +1435,43 @@ impl ChainEndpoint for NamadaChain {
         // not supported
         unimplemented!()
     }
+
+    #[cfg(feature = "submit-delay")]
+    fn set_submit_delay(&mut self, _delay: Option<Duration>) -> Result<(), Error> {
+        Err(Error::submit_delay_not_supported(self.config.id.clone()))
+    }
+
+    #[cfg(feature = "submit-delay")]
+    fn query_delayed_submissions(&self) -> Result<u64, Error> {
+        Err(Error::submit_delay_not_supported(self.config.id.clone()))
+    }
+}
+
+/// Queries the states of the clients created on the chain, ie. whose identifier counters are
+/// below `client_counter`, with at most `max_concurrent` queries at once, in the order of their
+/// identifiers. The clients whose state is not stored, eg. of another client type, are left out.
+fn query_client_states<F>(
+    client_counter: u64,
+    max_concurrent: NonZeroUsize,
+    query_client_state: F,
+) -> Result<Vec<IdentifiedAnyClientState>, Error>
+where
+    F: Fn(&ClientId) -> Result<Option<AnyClientState>, Error> + Sync,
+{
+    let client_ids = (0..client_counter)
+        .map(|counter| {
+            ClientId::new(ClientType::Tendermint, counter)
+                .expect("client identifiers built from a counter should be valid")
+        })
+        .collect();
+
+    map_bounded(client_ids, max_concurrent, |client_id| {
+        let client_state = query_client_state(&client_id)?;
+        Ok(client_state.map(|client_state| IdentifiedAnyClientState::new(client_id, client_state)))
+    })
+    .into_iter()
+    .filter_map(Result::transpose)
+    .collect()
 }
 
 /// Fetch the node info
@@ -1402,3 +1504,90 @@ fn into_tm_response(response: Response) -> tendermint_rpc::endpoint::broadcast::
         hash,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use ibc_relayer_types::core::ics02_client::trust_threshold::TrustThreshold;
+    use ibc_relayer_types::core::ics23_commitment::specs::ProofSpecs;
+
+    use super::*;
+
+    fn client_state(counter: u64) -> AnyClientState {
+        TmClientState::new(
+            ChainId::from_string(&format!("chain-{counter}")),
+            TrustThreshold::ONE_THIRD,
+            Duration::from_secs(64_000),
+            Duration::from_secs(128_000),
+            Duration::from_millis(3000),
+            ICSHeight::new(0, 10).unwrap(),
+            ProofSpecs::default(),
+            vec![],
+            AllowUpdate {
+                after_expiry: false,
+                after_misbehaviour: false,
+            },
+        )
+        .unwrap()
+        .into()
+    }
+
+    #[test]
+    fn client_states_are_queried_concurrently_and_listed_in_order() {
+        let max_concurrent = NonZeroUsize::new(8).unwrap();
+        let (in_flight, max_in_flight) = (AtomicUsize::new(0), AtomicUsize::new(0));
+
+        // The queries of the latest clients complete first, and client 7 is of another type
+        let states = query_client_states(200, max_concurrent, |client_id| {
+            let running = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            max_in_flight.fetch_max(running, Ordering::SeqCst);
+
+            let counter: u64 = client_id
+                .as_str()
+                .rsplit('-')
+                .next()
+                .unwrap()
+                .parse()
+                .unwrap();
+            thread::sleep(Duration::from_micros(200 - counter));
+
+            in_flight.fetch_sub(1, Ordering::SeqCst);
+            Ok((counter != 7).then(|| client_state(counter)))
+        })
+        .unwrap();
+
+        let expected: Vec<_> = (0..200)
+            .filter(|counter| *counter != 7)
+            .map(|counter| {
+                (
+                    format!("07-tendermint-{counter}"),
+                    ChainId::from_string(&format!("chain-{counter}")),
+                )
+            })
+            .collect();
+
+        let listed: Vec<_> = states
+            .iter()
+            .map(|state| (state.client_id.to_string(), state.client_state.chain_id()))
+            .collect();
+
+        assert_eq!(listed, expected);
+        assert!(max_in_flight.load(Ordering::SeqCst) <= max_concurrent.get());
+    }
+
+    #[test]
+    fn client_states_query_fails_with_any_of_the_queries() {
+        let result =
+            query_client_states(
+                20,
+                NonZeroUsize::new(8).unwrap(),
+                |client_id| match client_id.as_str() {
+                    "07-tendermint-12" => Err(Error::query("client state".to_string())),
+                    _ => Ok(Some(client_state(0))),
+                },
+            );
+
+        assert!(result.is_err());
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use ibc_relayer_types::core::ics04_channel::packet::Sequence;
 use ibc_relayer_types::core::ics23_commitment::merkle::convert_tm_to_ics_merkle_proof;
 use ibc_relayer_types::core::ics23_commitment::merkle::MerkleProof;
@@ -7,7 +9,7 @@ use namada_sdk::address::{Address, InternalAddress};
 use namada_sdk::borsh::BorshDeserialize;
 use namada_sdk::events::extend::Height as HeightAttr;
 use namada_sdk::events::Event as NamadaEvent;
-use namada_sdk::ibc::storage::{ibc_trace_key_prefix, is_ibc_trace_key};
+use namada_sdk::ibc::storage::{ibc_trace_key_prefix, is_ibc_trace_key, port_channel_sequence_id};
 use namada_sdk::io::Client;
 use namada_sdk::io::NamadaIo;
 use namada_sdk::queries::RPC;
@@ -320,6 +322,35 @@ impl NamadaChain {
     }
 }
 
+/// Decodes the sequences of the packets from the keys returned by a prefixed
+/// storage query, keeping only the ones among the `requested` sequences.
+///
+/// The keys whose value is empty, ie. which store nothing for their packet,
+/// are omitted from the result.
+pub(super) fn stored_sequences(
+    prefix_values: Vec<PrefixValue>,
+    requested: &[Sequence],
+) -> Result<Vec<Sequence>, Error> {
+    let requested: HashSet<&Sequence> = requested.iter().collect();
+    let mut sequences = vec![];
+
+    for PrefixValue { key, value } in prefix_values {
+        if value.is_empty() {
+            continue;
+        }
+
+        let (_, _, sequence) =
+            port_channel_sequence_id(&key).map_err(|e| Error::query(e.to_string()))?;
+        let sequence = Sequence::from(u64::from(sequence));
+
+        if requested.contains(&sequence) {
+            sequences.push(sequence);
+        }
+    }
+
+    Ok(sequences)
+}
+
 fn into_tm_proof(proof_ops: ProofOps) -> tendermint::merkle::proof::ProofOps {
     let ops = proof_ops
         .ops
@@ -344,3 +375,40 @@ fn into_abci_event(event: TmEvent) -> tendermint::abci::Event {
         attributes,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use namada_sdk::ibc::storage::ibc_key;
+
+    fn ack(sequence: u64, value: &[u8]) -> PrefixValue {
+        PrefixValue {
+            key: ibc_key(format!(
+                "acks/ports/transfer/channels/channel-0/sequences/{sequence}"
+            ))
+            .unwrap(),
+            value: value.to_vec(),
+        }
+    }
+
+    #[test]
+    fn stored_sequences_are_filtered_by_request() {
+        let acks = vec![ack(1, &[1]), ack(2, &[1]), ack(3, &[1]), ack(5, &[1])];
+        let requested = [2, 3, 4, 5].map(Sequence::from);
+
+        let sequences = stored_sequences(acks, &requested).unwrap();
+
+        assert_eq!(sequences, [2, 3, 5].map(Sequence::from));
+    }
+
+    #[test]
+    fn empty_values_are_omitted() {
+        let acks = vec![ack(1, &[1]), ack(2, &[]), ack(3, &[1])];
+        let requested = [1, 2, 3].map(Sequence::from);
+
+        let sequences = stored_sequences(acks, &requested).unwrap();
+
+        assert_eq!(sequences, [1, 3].map(Sequence::from));
+    }
+}
@@ -5,6 +5,7 @@ use std::thread;
 use std::time::Instant;
 
 use ibc_proto::google::protobuf::Any;
+use ibc_relayer_types::core::ics24_host::identifier::ChainId as IbcChainId;
 use itertools::Itertools;
 use namada_sdk::address::{Address, ImplicitAddress};
 use namada_sdk::args::TxBuilder;
@@ -29,12 +30,16 @@ use super::NamadaChain;
 const WAIT_BACKOFF: Duration = Duration::from_millis(300);
 
 impl NamadaChain {
-    pub fn batch_txs(&mut self, msgs: &[Any]) -> Result<Response, Error> {
+    pub fn batch_txs(
+        &mut self,
+        msgs: &[Any],
+        counterparty_chain_id: Option<&IbcChainId>,
+    ) -> Result<Response, Error> {
         if msgs.is_empty() {
             return Err(Error::send_tx("No message to be batched".to_string()));
         }
 
-        let tx_args = self.make_tx_args()?;
+        let tx_args = self.make_tx_args(counterparty_chain_id)?;
 
         let relayer_key = self.get_key()?;
         let relayer_addr = relayer_key.address;
@@ -119,7 +124,10 @@ impl NamadaChain {
         }
     }
 
-    fn make_tx_args(&mut self) -> Result<TxArgs, Error> {
+    fn make_tx_args(
+        &mut self,
+        counterparty_chain_id: Option<&IbcChainId>,
+    ) -> Result<TxArgs, Error> {
         let chain_id = ChainId::from_str(self.config.id.as_str()).expect("invalid chain ID");
 
         let namada_key = self.get_key()?;
@@ -131,11 +139,7 @@ impl NamadaChain {
         // Confirm the transaction later
         let mut tx_args = tx_args.broadcast_only(true);
 
-        let memo = self
-            .config
-            .memo_overwrite
-            .as_ref()
-            .unwrap_or(&self.config.memo_prefix);
+        let memo = self.config.tx_memo(counterparty_chain_id);
         let memo = if !memo.as_str().is_empty() {
             Some(memo.as_str().to_string().as_bytes().to_vec())
         } else {
@@ -161,10 +165,10 @@ impl NamadaChain {
         signing_data: &signing::SigningTxData,
     ) -> Result<Option<(Address, u64, f64)>, Error> {
         let chain_id = self.config.id.clone();
-        let fee_token_str = self.config.gas_price.denom.clone();
+        let fee_token_str = self.config.gas_price.preferred().denom.clone();
         let fee_token = Address::from_str(&fee_token_str)
             .map_err(|_| NamadaError::address_decode(fee_token_str.clone()))?;
-        let gas_price = self.config.gas_price.price;
+        let gas_price = self.config.gas_price.preferred().price;
 
         let max_block_gas_key = namada_sdk::parameters::storage::get_max_block_gas_key();
         let max_block_gas: u64 = self
@@ -36,12 +36,13 @@ use std::time::Duration;
 use tracing::info;
 
 use crate::chain::client::ClientSettings;
+use crate::chain::cosmos::request_rate::{PacedRpcClient, RequestRateLimiter};
 use crate::chain::endpoint::ChainStatus;
 use crate::chain::requests::*;
 use crate::chain::tracking::TrackedMsgs;
 use crate::client_state::{AnyClientState, IdentifiedAnyClientState};
 use crate::consensus_state::AnyConsensusState;
-use crate::event::source::{EventSource, TxEventSourceCmd};
+use crate::event::source::{rpc, EventSource, TxEventSourceCmd};
 use crate::event::{ibc_event_try_from_abci_event, IbcEventWithHeight};
 use crate::keyring::KeyRing;
 use crate::light_client::tendermint::LightClient as TmLightClient;
@@ -88,7 +89,7 @@ use signature::rand_core::OsRng;
 
 use tendermint::time::Time as TmTime;
 use tendermint_light_client::verifier::types::LightBlock as TmLightBlock;
-use tendermint_rpc::{Client as _, HttpClient};
+use tendermint_rpc::HttpClient;
 use tokio::runtime::Runtime as TokioRuntime;
 use tokio::sync::Mutex;
 use tonic::IntoRequest;
@@ -97,8 +98,9 @@ use std::path::PathBuf;
 
 use crate::{
     chain::{
-        endpoint::{ChainEndpoint, HealthCheck},
+        endpoint::ChainEndpoint,
         handle::Subscription,
+        health::{HealthCheckKind, HealthReport},
     },
     config::{ChainConfig, Error as ConfigError},
     error::Error,
@@ -118,7 +120,7 @@ pub struct PenumbraChain {
     ibc_connection_grpc_client: IbcConnectionQueryClient<tonic::transport::Channel>,
     ibc_channel_grpc_client: IbcChannelQueryClient<tonic::transport::Channel>,
 
-    tendermint_rpc_client: HttpClient,
+    tendermint_rpc_client: PacedRpcClient,
     tendermint_light_client: TmLightClient,
 
     tx_monitor_cmd: Option<TxEventSourceCmd>,
@@ -146,6 +148,7 @@ impl PenumbraChain {
                 self.tendermint_rpc_client.clone(),
                 *interval,
                 *max_retries,
+                rpc::last_processed_height_path(&self.config.id),
                 self.rt.clone(),
             ),
             _ => unimplemented!(),
@@ -493,8 +496,15 @@ impl ChainEndpoint for PenumbraChain {
             return Err(Error::config(ConfigError::wrong_type()));
         };
 
-        let rpc_client = HttpClient::new(config.rpc_addr.clone())
-            .map_err(|e| Error::rpc(config.rpc_addr.clone(), e))?;
+        let request_limiter = config
+            .max_requests_per_second
+            .map(|rps| Arc::new(RequestRateLimiter::new(rps)));
+
+        let rpc_client = PacedRpcClient::new(
+            HttpClient::new(config.rpc_addr.clone())
+                .map_err(|e| Error::rpc(config.rpc_addr.clone(), e))?,
+            request_limiter.clone(),
+        );
 
         let node_info = rt.block_on(fetch_node_info(&rpc_client, &config))?;
 
@@ -586,7 +596,8 @@ impl ChainEndpoint for PenumbraChain {
             .block_on(IbcChannelQueryClient::connect(grpc_addr.clone()))
             .map_err(Error::grpc_transport)?;
 
-        let tendermint_light_client = TmLightClient::from_penumbra_config(&config, node_info.id)?;
+        let tendermint_light_client =
+            TmLightClient::from_penumbra_config(&config, node_info.id, request_limiter)?;
 
         tracing::info!("ibc grpc query clients connected");
 
@@ -615,7 +626,7 @@ impl ChainEndpoint for PenumbraChain {
         Ok(())
     }
 
-    fn health_check(&mut self) -> Result<HealthCheck, Error> {
+    fn health_check(&mut self) -> Result<HealthReport, Error> {
         let mut view_client = self.rt.block_on(self.view_client.lock()).clone();
         let catching_up = self
             .rt
@@ -625,15 +636,15 @@ impl ChainEndpoint for PenumbraChain {
             })
             .map_err(|e: anyhow::Error| Error::temp_penumbra_error(e.to_string()))?;
 
+        let mut report = HealthReport::new(self.config.id.clone());
+
         if catching_up {
-            Ok(HealthCheck::Unhealthy(Box::new(
-                Error::temp_penumbra_error(
-                    anyhow::anyhow!("view service is not synced").to_string(),
-                ),
-            )))
+            report.fail(HealthCheckKind::NodeSynced, "view service is not synced");
         } else {
-            Ok(HealthCheck::Healthy)
+            report.pass(HealthCheckKind::NodeSynced, "view service is synced");
         }
+
+        Ok(report)
     }
 
     fn subscribe(&mut self) -> Result<Subscription, Error> {
@@ -814,6 +825,27 @@ impl ChainEndpoint for PenumbraChain {
         Ok(ChainStatus { height, timestamp })
     }
 
+    fn query_earliest_height(&self) -> Result<ICSHeight, Error> {
+        crate::time!(
+            "query_earliest_height",
+            {
+                "src_chain": self.config().id().to_string(),
+            }
+        );
+        crate::telemetry!(query, self.id(), "query_earliest_height");
+
+        let status = self
+            .rt
+            .block_on(self.tendermint_rpc_client.status())
+            .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
+
+        ICSHeight::new(
+            ChainId::chain_version(status.node_info.network.as_str()),
+            u64::from(status.sync_info.earliest_block_height),
+        )
+        .map_err(|_| Error::invalid_height_no_source())
+    }
+
     fn query_clients(
         &self,
         request: QueryClientStatesRequest,
@@ -1773,6 +1805,16 @@ impl ChainEndpoint for PenumbraChain {
     ) -> Result<ibc_relayer_types::applications::ics28_ccv::msgs::ConsumerId, Error> {
         unimplemented!("not currently implemented in penumbra")
     }
+
+    #[cfg(feature = "submit-delay")]
+    fn set_submit_delay(&mut self, _delay: Option<Duration>) -> Result<(), Error> {
+        Err(Error::submit_delay_not_supported(self.config.id.clone()))
+    }
+
+    #[cfg(feature = "submit-delay")]
+    fn query_delayed_submissions(&self) -> Result<u64, Error> {
+        Err(Error::submit_delay_not_supported(self.config.id.clone()))
+    }
 }
 
 /// Returns the suffix counter for a CosmosSDK client id.
@@ -1833,7 +1875,7 @@ pub static IBC_PROOF_SPECS: Lazy<Vec<ics23::ProofSpec>> =
     Lazy::new(|| vec![ics23_spec(), ics23_spec()]);
 
 async fn fetch_node_info(
-    rpc_client: &HttpClient,
+    rpc_client: &PacedRpcClient,
     config: &PenumbraConfig,
 ) -> Result<tendermint::node::Info, Error> {
     crate::time!("fetch_node_info",
@@ -1,3 +1,4 @@
+use core::num::NonZeroU32;
 use core::time::Duration;
 
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
@@ -39,6 +40,11 @@ pub struct PenumbraConfig {
     #[serde(default = "default::rpc_timeout", with = "humantime_serde")]
     pub rpc_timeout: Duration,
 
+    /// The maximum number of RPC requests made to the chain per second, for nodes
+    /// whose provider enforces such a limit. Unlimited if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_requests_per_second: Option<NonZeroU32>,
+
     /// Controls which packets will be relayed.
     #[serde(default)]
     pub packet_filter: PacketFilter,
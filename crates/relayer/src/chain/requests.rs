@@ -38,6 +38,7 @@ use ibc_relayer_types::core::ics24_host::identifier::{
     ChainId, ChannelId, ClientId, ConnectionId, PortId,
 };
 use ibc_relayer_types::events::WithBlockDataType;
+use ibc_relayer_types::signer::Signer;
 use ibc_relayer_types::Height;
 
 use serde::{Deserialize, Serialize};
@@ -544,11 +545,30 @@ impl From<QueryConsensusStateRequest> for RawQueryConsensusStateRequest {
 pub enum QueryTxRequest {
     Client(QueryClientEventRequest),
     Transaction(QueryTxHash),
+    Sender(QuerySenderTxsRequest),
+    SendPackets(QuerySendPacketsRequest),
 }
 
 #[derive(Clone, Debug)]
 pub struct QueryTxHash(pub TxHash);
 
+/// Used to query the IBC events of all the transactions
+/// submitted by `sender` from `min_height` onwards.
+#[derive(Clone, Debug)]
+pub struct QuerySenderTxsRequest {
+    pub sender: Signer,
+    pub min_height: Height,
+}
+
+/// Used to query the `SendPacket` events of the packets
+/// sent on a channel from `min_height` onwards.
+#[derive(Clone, Debug)]
+pub struct QuerySendPacketsRequest {
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub min_height: Height,
+}
+
 /// Used to query packet events:
 /// - for events of type `event_id`,
 /// - for a specific channel
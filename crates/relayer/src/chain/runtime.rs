@@ -42,15 +42,18 @@ use crate::{
     denom::DenomTrace,
     error::Error,
     event::IbcEventWithHeight,
-    keyring::AnySigningKeyPair,
+    keyring::{AnySigningKeyPair, SigningKeyPair},
     misbehaviour::MisbehaviourEvidence,
 };
 
 use super::{
     client::ClientSettings,
-    endpoint::{ChainEndpoint, ChainStatus, HealthCheck},
+    cosmos::tx::encode_signer,
+    endpoint::{ChainEndpoint, ChainStatus},
     handle::{ChainHandle, ChainRequest, ReplyTo, Subscription},
+    health::HealthReport,
     requests::*,
+    signing_accounts::SigningAccounts,
     tracking::TrackedMsgs,
     version::Specs,
 };
@@ -72,6 +75,9 @@ pub struct ChainRuntime<Endpoint: ChainEndpoint> {
     /// in through this channel.
     request_receiver: channel::Receiver<(Span, ChainRequest)>,
 
+    /// The rotation of the keys signing the transactions relayed to the chain
+    signing_accounts: SigningAccounts,
+
     #[allow(dead_code)]
     rt: Arc<TokioRuntime>, // Making this future-proof, so we keep the runtime around.
 }
@@ -124,6 +130,7 @@ where
             chain,
             request_sender,
             request_receiver,
+            signing_accounts: SigningAccounts::default(),
         }
     }
 
@@ -148,6 +155,8 @@ where
 
                     let _span = span.entered();
 
+                    self.chain.start_operation();
+
                     match event {
                         ChainRequest::Shutdown { reply_to } => {
                             let res = self.chain.shutdown();
@@ -178,6 +187,14 @@ where
                             self.get_signer(reply_to)?
                         },
 
+                        ChainRequest::KeySigner { key_name, reply_to } => {
+                            self.get_key_signer(key_name, reply_to)?
+                        },
+
+                        ChainRequest::NextSigningAccount { reply_to } => {
+                            self.next_signing_account(reply_to)?
+                        },
+
                         ChainRequest::Config { reply_to } => {
                             self.get_config(reply_to)?
                         },
@@ -234,6 +251,10 @@ where
                             self.query_application_status(reply_to)?
                         },
 
+                        ChainRequest::QueryEarliestHeight { reply_to } => {
+                            self.query_earliest_height(reply_to)?
+                        },
+
                         ChainRequest::QueryClients { request, reply_to } => {
                             self.query_clients(request, reply_to)?
                         },
@@ -369,6 +390,16 @@ where
                         ChainRequest::QueryConsumerId { client_id, reply_to } => {
                             self.query_ccv_consumer_id(client_id, reply_to)?
                         },
+
+                        #[cfg(feature = "submit-delay")]
+                        ChainRequest::SetSubmitDelay { delay, reply_to } => {
+                            self.set_submit_delay(delay, reply_to)?
+                        },
+
+                        #[cfg(feature = "submit-delay")]
+                        ChainRequest::QueryDelayedSubmissions { reply_to } => {
+                            self.query_delayed_submissions(reply_to)?
+                        },
                     }
                 },
             }
@@ -377,7 +408,7 @@ where
         Ok(())
     }
 
-    fn health_check(&mut self, reply_to: ReplyTo<HealthCheck>) -> Result<(), Error> {
+    fn health_check(&mut self, reply_to: ReplyTo<HealthReport>) -> Result<(), Error> {
         let result = self.chain.health_check();
         reply_to.send(result).map_err(Error::send)
     }
@@ -437,11 +468,34 @@ where
         reply_to.send(latest_timestamp).map_err(Error::send)
     }
 
+    fn query_earliest_height(&self, reply_to: ReplyTo<Height>) -> Result<(), Error> {
+        let earliest_height = self.chain.query_earliest_height();
+        reply_to.send(earliest_height).map_err(Error::send)
+    }
+
     fn get_signer(&mut self, reply_to: ReplyTo<Signer>) -> Result<(), Error> {
         let result = self.chain.get_signer();
         reply_to.send(result).map_err(Error::send)
     }
 
+    fn get_key_signer(&mut self, key_name: String, reply_to: ReplyTo<Signer>) -> Result<(), Error> {
+        let result = self
+            .chain
+            .keybase()
+            .get_key(&key_name)
+            .map_err(Error::key_base)
+            .and_then(|key| encode_signer(&key.account(), self.chain.config().signer_encoding()));
+
+        reply_to.send(result).map_err(Error::send)
+    }
+
+    fn next_signing_account(&mut self, reply_to: ReplyTo<Option<String>>) -> Result<(), Error> {
+        let key_names = self.chain.config().signing_accounts();
+        let result = Ok(self.signing_accounts.next(&key_names).cloned());
+
+        reply_to.send(result).map_err(Error::send)
+    }
+
     fn get_config(&self, reply_to: ReplyTo<ChainConfig>) -> Result<(), Error> {
         let result = Ok(self.chain.config().clone());
         reply_to.send(result).map_err(Error::send)
@@ -916,4 +970,24 @@ where
 
         Ok(())
     }
+
+    #[cfg(feature = "submit-delay")]
+    fn set_submit_delay(
+        &mut self,
+        delay: Option<core::time::Duration>,
+        reply_to: ReplyTo<()>,
+    ) -> Result<(), Error> {
+        let result = self.chain.set_submit_delay(delay);
+        reply_to.send(result).map_err(Error::send)?;
+
+        Ok(())
+    }
+
+    #[cfg(feature = "submit-delay")]
+    fn query_delayed_submissions(&self, reply_to: ReplyTo<u64>) -> Result<(), Error> {
+        let result = self.chain.query_delayed_submissions();
+        reply_to.send(result).map_err(Error::send)?;
+
+        Ok(())
+    }
 }
@@ -0,0 +1,42 @@
+//! Rotates the keys which sign the transactions relayed to a chain configured with
+//! several `signing_accounts`, so that the link workers of the channels of the chain
+//! do not all contend on the account sequence of a single key.
+
+/// The position in its signing accounts of the key which signs
+/// the next transaction relayed to a chain.
+#[derive(Debug, Default)]
+pub struct SigningAccounts {
+    next: usize,
+}
+
+impl SigningAccounts {
+    /// Returns the name of the key which signs the next transaction relayed to the
+    /// chain, taking the given keys in turn, or `None` if there are none.
+    pub fn next<'a>(&mut self, key_names: &'a [String]) -> Option<&'a String> {
+        if key_names.is_empty() {
+            return None;
+        }
+
+        let key_name = &key_names[self.next % key_names.len()];
+        self.next = self.next.wrapping_add(1);
+
+        Some(key_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_are_taken_in_turn() {
+        let mut accounts = SigningAccounts::default();
+        let keys = ["relayer-1".to_owned(), "relayer-2".to_owned()];
+
+        assert_eq!(accounts.next(&[]), None);
+
+        assert_eq!(accounts.next(&keys).unwrap(), "relayer-1");
+        assert_eq!(accounts.next(&keys).unwrap(), "relayer-2");
+        assert_eq!(accounts.next(&keys).unwrap(), "relayer-1");
+    }
+}
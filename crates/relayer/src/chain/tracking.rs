@@ -1,6 +1,7 @@
 use core::fmt::{Display, Error as FmtError, Formatter};
 
 use ibc_proto::google::protobuf::Any;
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use uuid::Uuid;
 
 /// Identifier used to track an `EventBatch` along
@@ -70,17 +71,30 @@ impl Display for TrackingId {
 pub struct TrackedMsgs {
     pub msgs: Vec<Any>,
     pub tracking_id: TrackingId,
+    /// The name of the key to sign the messages with,
+    /// instead of the key configured for the chain.
+    pub key_name: Option<String>,
+    /// The counterparty chain of the channel the messages relay on, if any,
+    /// which the memo of the transaction may be overridden for.
+    pub counterparty_chain_id: Option<ChainId>,
 }
 
 impl TrackedMsgs {
     pub fn new(msgs: Vec<Any>, tracking_id: TrackingId) -> Self {
-        Self { msgs, tracking_id }
+        Self {
+            msgs,
+            tracking_id,
+            key_name: None,
+            counterparty_chain_id: None,
+        }
     }
 
     pub fn new_static(msgs: Vec<Any>, tracking_id: &'static str) -> Self {
         Self {
             msgs,
             tracking_id: TrackingId::Static(tracking_id),
+            key_name: None,
+            counterparty_chain_id: None,
         }
     }
 
@@ -88,6 +102,8 @@ impl TrackedMsgs {
         Self {
             msgs,
             tracking_id: TrackingId::Uuid(tracking_id),
+            key_name: None,
+            counterparty_chain_id: None,
         }
     }
 
@@ -95,6 +111,8 @@ impl TrackedMsgs {
         Self {
             msgs: vec![msg],
             tracking_id: TrackingId::Static(tracking_id),
+            key_name: None,
+            counterparty_chain_id: None,
         }
     }
 
@@ -102,6 +120,8 @@ impl TrackedMsgs {
         Self {
             msgs: vec![msg],
             tracking_id: TrackingId::Uuid(tracking_id),
+            key_name: None,
+            counterparty_chain_id: None,
         }
     }
 
@@ -112,4 +132,17 @@ impl TrackedMsgs {
     pub fn tracking_id(&self) -> TrackingId {
         self.tracking_id
     }
+
+    /// Sign the messages with the given key instead of the key configured for the chain.
+    pub fn with_key_name(self, key_name: Option<String>) -> Self {
+        Self { key_name, ..self }
+    }
+
+    /// Relay the messages on a channel to the given counterparty chain.
+    pub fn with_counterparty_chain_id(self, counterparty_chain_id: ChainId) -> Self {
+        Self {
+            counterparty_chain_id: Some(counterparty_chain_id),
+            ..self
+        }
+    }
 }
@@ -765,7 +765,15 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
         &mut self,
         state: State,
     ) -> Result<(Option<IbcEvent>, Next), ChannelError> {
-        let event = match (state, self.counterparty_state()?) {
+        let counterparty_state = self.counterparty_state()?;
+
+        // A stalled upgrade handshake is timed out rather than retried, the counterparty
+        // of the timed out channel end is then cancelled on the next step
+        if let Some(event) = self.timeout_stalled_upgrade(state, counterparty_state)? {
+            return Ok((Some(event), Next::Continue));
+        }
+
+        let event = match (state, counterparty_state) {
             // Open handshake steps
             (State::Init, State::Uninitialized) => Some(self.build_chan_open_try_and_send()?),
             (State::Init, State::Init) => Some(self.build_chan_open_try_and_send()?),
@@ -2209,6 +2217,143 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
         }
     }
 
+    /// Registers the address of the relayer on each chain as the counterparty payee of the
+    /// channel end hosted by the other chain, so that the relayer is paid the ICS-29 fees of
+    /// the packets it relays over the channel.
+    ///
+    /// Returns whether the payees were registered, which is not the case if the channel is
+    /// not open yet, or if its application is not wrapped by the fee middleware.
+    pub fn maybe_register_counterparty_payees(&self) -> Result<bool, ChannelError> {
+        let (Some(src_channel_id), Some(dst_channel_id)) =
+            (self.src_channel_id(), self.dst_channel_id())
+        else {
+            return Ok(false);
+        };
+
+        let (src_channel, _) = self
+            .src_chain()
+            .query_channel(
+                QueryChannelRequest {
+                    port_id: self.src_port_id().clone(),
+                    channel_id: src_channel_id.clone(),
+                    height: QueryHeight::Latest,
+                },
+                IncludeProof::No,
+            )
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+        if !src_channel.is_open() || !src_channel.version.supports_fee() {
+            return Ok(false);
+        }
+
+        let src_address = self
+            .src_chain()
+            .get_signer()
+            .map_err(|e| ChannelError::fetch_signer(self.src_chain().id(), e))?;
+
+        let dst_address = self
+            .dst_chain()
+            .get_signer()
+            .map_err(|e| ChannelError::fetch_signer(self.dst_chain().id(), e))?;
+
+        info!(
+            "registering counterparty payees of fee-enabled channel {}/{} on chains {} and {}",
+            src_channel_id,
+            dst_channel_id,
+            self.src_chain().id(),
+            self.dst_chain().id(),
+        );
+
+        self.dst_chain()
+            .maybe_register_counterparty_payee(
+                dst_channel_id.clone(),
+                self.dst_port_id().clone(),
+                src_address,
+            )
+            .map_err(|e| ChannelError::submit(self.dst_chain().id(), e))?;
+
+        self.src_chain()
+            .maybe_register_counterparty_payee(
+                src_channel_id.clone(),
+                self.src_port_id().clone(),
+                dst_address,
+            )
+            .map_err(|e| ChannelError::submit(self.src_chain().id(), e))?;
+
+        Ok(true)
+    }
+
+    /// Returns whether the upgrade of the destination channel end timed out, ie. whether
+    /// the source chain reached the upgrade timeout without completing the handshake.
+    pub fn upgrade_timeout_elapsed(&self) -> Result<bool, ChannelError> {
+        let dst_channel_id = self
+            .dst_channel_id()
+            .ok_or_else(ChannelError::missing_counterparty_channel_id)?;
+
+        let dst_latest_height = self
+            .dst_chain()
+            .query_latest_height()
+            .map_err(|e| ChannelError::chain_query(self.dst_chain().id(), e))?;
+
+        let (upgrade, _) = self
+            .dst_chain()
+            .query_upgrade(
+                QueryUpgradeRequest {
+                    port_id: self.dst_port_id().to_string(),
+                    channel_id: dst_channel_id.to_string(),
+                },
+                dst_latest_height,
+                IncludeProof::No,
+            )
+            .map_err(|e| ChannelError::chain_query(self.dst_chain().id(), e))?;
+
+        let Some(timeout) = upgrade.timeout else {
+            return Ok(false);
+        };
+
+        let src_status = self
+            .src_chain()
+            .query_application_status()
+            .map_err(|e| ChannelError::chain_query(self.src_chain().id(), e))?;
+
+        Ok(timeout.has_elapsed(src_status.height, src_status.timestamp))
+    }
+
+    /// Times out the upgrade of the channel end, on either side, which is flushing while
+    /// its counterparty reached the upgrade timeout before completing its own flush.
+    ///
+    /// The timed out channel end is restored to its state before the upgrade, and its
+    /// counterparty is left to be cancelled. Returns `None` if no upgrade timed out.
+    pub fn timeout_stalled_upgrade(
+        &self,
+        state: State,
+        counterparty_state: State,
+    ) -> Result<Option<IbcEvent>, ChannelError> {
+        if can_timeout_upgrade(counterparty_state, state) && self.upgrade_timeout_elapsed()? {
+            warn!(
+                "upgrade of channel {} on chain {} timed out, restoring the channel",
+                PrettyOption(&self.dst_channel_id()),
+                self.dst_chain().id()
+            );
+
+            return Ok(Some(self.build_chan_upgrade_timeout_and_send()?));
+        }
+
+        let flipped = self.flipped();
+
+        if can_timeout_upgrade(state, counterparty_state) && flipped.upgrade_timeout_elapsed()? {
+            warn!(
+                "upgrade of channel {} on chain {} timed out, restoring the channel",
+                PrettyOption(&flipped.dst_channel_id()),
+                flipped.dst_chain().id()
+            );
+
+            return Ok(Some(flipped.build_chan_upgrade_timeout_and_send()?));
+        }
+
+        Ok(None)
+    }
+
     pub fn map_chain<ChainC: ChainHandle, ChainD: ChainHandle>(
         self,
         mapper_a: impl Fn(ChainA) -> ChainC,
@@ -2267,6 +2412,17 @@ pub fn extract_channel_id(event: &IbcEvent) -> Result<&ChannelId, ChannelError>
     .ok_or_else(|| ChannelError::missing_event("cannot extract channel_id from result".to_string()))
 }
 
+/// Returns whether the upgrade of a channel end in the given state can be timed out,
+/// with a proof of its counterparty in the given state: the channel end must be flushing,
+/// and its counterparty must not have completed its flush nor the upgrade.
+fn can_timeout_upgrade(state: State, counterparty_state: State) -> bool {
+    matches!(state, State::Flushing | State::FlushComplete)
+        && matches!(
+            counterparty_state,
+            State::Open(UpgradeState::Upgrading) | State::Flushing
+        )
+}
+
 /// Enumeration of proof carrying ICS4 message, helper for relayer.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum ChannelMsgType {
@@ -5,13 +5,18 @@ pub mod dynamic_gas;
 pub mod error;
 pub mod filter;
 pub mod gas_multiplier;
+pub mod gas_sampling;
 pub mod proof_specs;
 pub mod refresh_rate;
+pub mod retry_strategy;
+pub mod shadow_gas;
+pub mod tx_priority;
 pub mod types;
 
 use alloc::collections::BTreeMap;
 use core::cmp::Ordering;
 use core::fmt::{Display, Error as FmtError, Formatter};
+use core::num::NonZeroUsize;
 use core::str::FromStr;
 use core::time::Duration;
 use ibc_relayer_types::core::ics04_channel::packet::Sequence;
@@ -30,8 +35,9 @@ use ibc_relayer_types::timestamp::ZERO_DURATION;
 
 use crate::chain::cosmos::config::CosmosSdkConfig;
 use crate::chain::penumbra::config::PenumbraConfig;
+use crate::config::types::event_query::EventQuery;
 use crate::config::types::ics20_field_size_limit::Ics20FieldSizeLimit;
-use crate::config::types::TrustThreshold;
+use crate::config::types::{MaxMsgNum, MaxTxSize, TrustThreshold};
 use crate::error::Error as RelayerError;
 use crate::extension_options::ExtensionOptionDynamicFeeTx;
 use crate::keyring::{AnySigningKeyPair, KeyRing, Store};
@@ -101,6 +107,124 @@ impl PartialOrd for GasPrice {
     }
 }
 
+/// The gas prices at which the relayer pays the fees of its transactions on a chain, one for
+/// each denomination it may pay fees in, in order of preference.
+///
+/// Configured either as a single gas price, eg. `gas_price = { price = 0.025, denom = 'uatom' }`,
+/// or as a list of gas prices, eg.
+/// `gas_price = [{ price = 0.025, denom = 'uatom' }, { price = 0.1, denom = 'uosmo' }]`.
+/// In the latter case, the fees of a transaction are paid in the first denomination in which
+/// the balance of the relayer account covers the maximum fee.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GasPrices {
+    preferred: GasPrice,
+    fallbacks: Vec<GasPrice>,
+}
+
+impl GasPrices {
+    /// The gas prices in the given denominations, in order of preference.
+    /// Fails if there is none, if a denomination is listed more than once, or if there are
+    /// several of them and one is not a positive number, since the settings expressed in the
+    /// preferred denomination apply to the others at the rate of their gas prices.
+    pub fn new(gas_prices: Vec<GasPrice>) -> Result<Self, String> {
+        let mut gas_prices = gas_prices.into_iter();
+
+        let preferred = gas_prices
+            .next()
+            .ok_or_else(|| "at least one gas price must be configured".to_string())?;
+
+        let fallbacks: Vec<GasPrice> = gas_prices.collect();
+
+        if !fallbacks.is_empty() {
+            let not_positive = core::iter::once(&preferred)
+                .chain(&fallbacks)
+                .find(|gas_price| !(gas_price.price.is_finite() && gas_price.price > 0.0));
+
+            if let Some(gas_price) = not_positive {
+                return Err(format!(
+                    "the gas price in denomination '{}' must be a positive number, \
+                    as several gas prices are configured",
+                    gas_price.denom
+                ));
+            }
+        }
+
+        for (i, gas_price) in fallbacks.iter().enumerate() {
+            if gas_price.denom == preferred.denom
+                || fallbacks[..i]
+                    .iter()
+                    .any(|other| other.denom == gas_price.denom)
+            {
+                return Err(format!(
+                    "the gas price in denomination '{}' is configured more than once",
+                    gas_price.denom
+                ));
+            }
+        }
+
+        Ok(Self {
+            preferred,
+            fallbacks,
+        })
+    }
+
+    /// The gas price in the preferred denomination, ie. the first one configured.
+    pub fn preferred(&self) -> &GasPrice {
+        &self.preferred
+    }
+
+    /// Whether gas prices are configured in other denominations than the preferred one.
+    pub fn has_fallbacks(&self) -> bool {
+        !self.fallbacks.is_empty()
+    }
+
+    /// All the gas prices, starting with the preferred one.
+    pub fn iter(&self) -> impl Iterator<Item = &GasPrice> {
+        core::iter::once(&self.preferred).chain(&self.fallbacks)
+    }
+}
+
+impl From<GasPrice> for GasPrices {
+    fn from(gas_price: GasPrice) -> Self {
+        Self {
+            preferred: gas_price,
+            fallbacks: Vec::new(),
+        }
+    }
+}
+
+impl Serialize for GasPrices {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if self.has_fallbacks() {
+            serializer.collect_seq(self.iter())
+        } else {
+            self.preferred.serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for GasPrices {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum OneOrMany {
+            One(GasPrice),
+            Many(Vec<GasPrice>),
+        }
+
+        match OneOrMany::deserialize(deserializer)? {
+            OneOrMany::One(gas_price) => Ok(Self::from(gas_price)),
+            OneOrMany::Many(gas_prices) => Self::new(gas_prices).map_err(serde::de::Error::custom),
+        }
+    }
+}
+
 /// Attempts to parse 0 or more `GasPrice`s from a String,
 /// returning the successfully parsed prices in a Vec. Any
 /// single price that fails to be parsed does not affect
@@ -171,6 +295,14 @@ pub mod default {
         50
     }
 
+    pub fn max_concurrent_tx_confirmations() -> usize {
+        10
+    }
+
+    pub fn max_concurrent_client_state_queries() -> NonZeroUsize {
+        NonZeroUsize::new(8).unwrap()
+    }
+
     pub fn rpc_timeout() -> Duration {
         Duration::from_secs(10)
     }
@@ -187,6 +319,14 @@ pub mod default {
         Duration::from_millis(500)
     }
 
+    pub fn reconnect_backoff() -> Duration {
+        Duration::from_secs(1)
+    }
+
+    pub fn max_reconnect_backoff() -> Duration {
+        Duration::from_secs(60)
+    }
+
     pub fn clock_drift() -> Duration {
         Duration::from_secs(5)
     }
@@ -195,6 +335,26 @@ pub mod default {
         Duration::from_secs(30)
     }
 
+    pub fn reference_price_refresh_interval() -> Duration {
+        Duration::from_secs(60)
+    }
+
+    pub fn reference_price_max_scale() -> f64 {
+        10.0
+    }
+
+    pub fn txfees_spot_price_refresh_interval() -> Duration {
+        Duration::from_secs(60)
+    }
+
+    pub fn block_gas_usage_sample_blocks() -> usize {
+        10
+    }
+
+    pub fn block_gas_usage_headroom() -> f64 {
+        0.5
+    }
+
     pub fn trusted_node() -> bool {
         false
     }
@@ -255,6 +415,34 @@ pub mod default {
     pub fn clear_limit() -> usize {
         50
     }
+
+    pub fn compaction_limit() -> usize {
+        100
+    }
+
+    pub fn verify_client_chain_id() -> bool {
+        true
+    }
+
+    pub fn canary_port() -> PortId {
+        PortId::transfer()
+    }
+
+    pub fn canary_amount() -> u64 {
+        1
+    }
+
+    pub fn canary_interval() -> Duration {
+        Duration::from_secs(5 * 60)
+    }
+
+    pub fn canary_threshold() -> Duration {
+        Duration::from_secs(60)
+    }
+
+    pub fn grpc_height_header_name() -> String {
+        "x-cosmos-block-height".to_string()
+    }
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -299,12 +487,21 @@ impl Config {
         match self.find_chain(chain_id) {
             Some(chain_config) => chain_config
                 .packet_filter()
-                .channel_policy
-                .is_allowed(port_id, channel_id),
+                .is_channel_allowed(port_id, channel_id),
             None => false,
         }
     }
 
+    /// Whether to detect the misbehaviour of the chains referenced by the clients hosted on
+    /// the given chain, as set for that chain, or globally with `mode.clients.misbehaviour`.
+    pub fn detects_misbehaviour(&self, host_chain_id: &ChainId) -> bool {
+        self.mode.clients.enabled
+            && self
+                .find_chain(host_chain_id)
+                .and_then(ChainConfig::detect_misbehaviour)
+                .unwrap_or(self.mode.clients.misbehaviour)
+    }
+
     pub fn chains_map(&self) -> BTreeMap<&ChainId, &ChainConfig> {
         self.chains.iter().map(|c| (c.id(), c)).collect()
     }
@@ -335,11 +532,35 @@ impl Config {
         // Check for invalid mode config
         self.mode.validate()?;
 
+        let detects_misbehaviour = self
+            .chains
+            .iter()
+            .any(|chain| self.detects_misbehaviour(chain.id()));
+
+        if self.mode.clients.enabled && !self.mode.clients.refresh && !detects_misbehaviour {
+            return Err(Diagnostic::Error(Error::invalid_mode(
+                "either `refresh` or `misbehaviour` must be set to true if `clients.enabled` \
+                is set to true, or `detect_misbehaviour` for one of the chains"
+                    .to_string(),
+            )));
+        }
+
+        for path in &self.mode.connections.paths {
+            for chain_id in [&path.a_chain, &path.b_chain] {
+                if !self.has_chain(chain_id) {
+                    return Err(Diagnostic::Error(Error::invalid_mode(format!(
+                        "connection path between `{}` and `{}` has unknown chain `{chain_id}`",
+                        path.a_chain, path.b_chain
+                    ))));
+                }
+            }
+        }
+
         Ok(())
     }
 }
 
-#[derive(Copy, Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct ModeConfig {
     pub clients: Clients,
@@ -363,12 +584,6 @@ impl ModeConfig {
             )));
         }
 
-        if self.clients.enabled && !self.clients.refresh && !self.clients.misbehaviour {
-            return Err(Diagnostic::Error(Error::invalid_mode(
-                "either `refresh` or `misbehaviour` must be set to true if `clients.enabled` is set to true".to_string(),
-            )));
-        }
-
         Ok(())
     }
 }
@@ -381,8 +596,12 @@ impl Default for ModeConfig {
                 enabled: true,
                 refresh: true,
                 misbehaviour: true,
+                smooth_refresh: false,
+            },
+            connections: Connections {
+                enabled: false,
+                ..Default::default()
             },
-            connections: Connections { enabled: false },
             channels: Channels { enabled: false },
             packets: Packets {
                 enabled: true,
@@ -400,12 +619,32 @@ pub struct Clients {
     pub refresh: bool,
     #[serde(default)]
     pub misbehaviour: bool,
+    /// Spread the refreshes of the clients over their refresh period,
+    /// instead of refreshing the clients updated together at once.
+    #[serde(default)]
+    pub smooth_refresh: bool,
 }
 
-#[derive(Copy, Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct Connections {
     pub enabled: bool,
+    /// Create on start the connections of the `paths` which have none yet.
+    #[serde(default)]
+    pub create_on_start: bool,
+    #[serde(default)]
+    pub paths: Vec<ConnectionPath>,
+}
+
+/// A pair of chains which must be connected with each other, with the delay
+/// period of the connection to create between them if there is none yet.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConnectionPath {
+    pub a_chain: ChainId,
+    pub b_chain: ChainId,
+    #[serde(default = "default::connection_delay", with = "humantime_serde")]
+    pub delay: Duration,
 }
 
 #[derive(Copy, Clone, Debug, Default, Deserialize, Serialize)]
@@ -432,6 +671,43 @@ pub struct Packets {
     pub ics20_max_receiver_size: Ics20FieldSizeLimit,
     #[serde(default = "default::clear_limit")]
     pub clear_limit: usize,
+    /// Time budget of a packet clearing pass, after which the pass yields
+    /// and the next one resumes from the first packet left to clear.
+    #[serde(default, with = "humantime_serde")]
+    pub max_clear_duration: Option<Duration>,
+    /// Number of recent blocks of both chains of a path which are scanned for the
+    /// transactions of the relayer when it starts, to find out which packets it
+    /// already relayed. Disabled if 0.
+    #[serde(default)]
+    pub recovery_lookback: u64,
+    /// Number of blocks which must be built on top of the block of a packet
+    /// event before the packet is relayed, unless overridden per chain or channel.
+    #[serde(default)]
+    pub min_confirmation_blocks: u64,
+    /// Trust threshold used to verify the headers of the chains locally, if stricter
+    /// than the trust threshold of the clients, unless overridden per chain or channel.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local_trust_threshold: Option<TrustThreshold>,
+    /// Whether the packets sent by the transactions of the relayer, eg. the packets
+    /// forwarded by a packet forwarding middleware upon receiving a packet, are
+    /// relayed as soon as these transactions are confirmed.
+    #[serde(default)]
+    pub relay_forwarded_packets: bool,
+    /// Whether the relayer checks, on startup and before each packet clearing pass,
+    /// that the clients of the connection of each path track the chains of that path.
+    #[serde(default = "default::verify_client_chain_id")]
+    pub verify_client_chain_id: bool,
+    /// The order in which the pending packets are relayed by a packet clearing pass.
+    #[serde(default)]
+    pub clear_order: ClearOrder,
+    /// Interval between the passes dropping from the operational data scheduled by
+    /// the packet workers the messages of the packets relayed out-of-band.
+    #[serde(default, with = "humantime_serde")]
+    pub compaction_interval: Option<Duration>,
+    /// Maximum number of scheduled messages checked by a compaction pass, per path
+    /// and target chain.
+    #[serde(default = "default::compaction_limit")]
+    pub compaction_limit: usize,
 
     #[serde(skip)]
     pub force_disable_clear_on_start: bool,
@@ -448,11 +724,45 @@ impl Default for Packets {
             ics20_max_memo_size: default::ics20_max_memo_size(),
             ics20_max_receiver_size: default::ics20_max_receiver_size(),
             clear_limit: default::clear_limit(),
+            max_clear_duration: None,
+            recovery_lookback: 0,
+            min_confirmation_blocks: 0,
+            local_trust_threshold: None,
+            relay_forwarded_packets: false,
+            verify_client_chain_id: default::verify_client_chain_id(),
+            clear_order: ClearOrder::default(),
+            compaction_interval: None,
+            compaction_limit: default::compaction_limit(),
             force_disable_clear_on_start: false,
         }
     }
 }
 
+impl Packets {
+    /// The global verification settings, which apply to
+    /// the channels of chains which do not override them.
+    pub fn channel_verification(&self) -> ChannelVerification {
+        ChannelVerification {
+            min_confirmation_blocks: self.min_confirmation_blocks,
+            local_trust_threshold: self.local_trust_threshold,
+            verify_client_chain_id: self.verify_client_chain_id,
+        }
+    }
+}
+
+/// The order in which the pending packets are relayed by a packet clearing pass.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClearOrder {
+    /// Relay the packets with the lowest sequences first
+    #[default]
+    OldestFirst,
+    /// Relay the packets with the highest sequences first, so that the most
+    /// recent packets are not held back by a backlog of older ones.
+    /// Only applies to unordered channels.
+    NewestFirst,
+}
+
 /// Log levels are wrappers over [`tracing_core::Level`].
 ///
 /// [`tracing_core::Level`]: https://docs.rs/tracing-core/0.1.17/tracing_core/struct.Level.html
@@ -613,6 +923,356 @@ impl Display for AddressType {
     }
 }
 
+/// How the account of the relayer is extracted from the response
+/// of the auth module `Account` gRPC query.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AccountQuery {
+    /// The chain returns a plain `BaseAccount`.
+    Auth,
+
+    /// The chain returns its own account type, which wraps a `BaseAccount`,
+    /// possibly within other wrappers such as vesting accounts.
+    Base,
+}
+
+/// Settings of a channel which affect how strictly the packets
+/// sent on it are checked before they are relayed.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ChannelVerification {
+    /// Number of blocks which must be built on top of the block of a packet
+    /// event before the packet is relayed.
+    pub min_confirmation_blocks: u64,
+
+    /// Trust threshold used to verify the headers of the chain locally,
+    /// if stricter than the trust threshold of the client.
+    pub local_trust_threshold: Option<TrustThreshold>,
+
+    /// Whether the packets are only relayed if the clients of the connection
+    /// of the channel track the chains of the path.
+    pub verify_client_chain_id: bool,
+}
+
+/// Overrides for a single channel of the [`ChannelVerification`]
+/// settings of its chain, or of the global ones.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChannelOverrides {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_confirmation_blocks: Option<u64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local_trust_threshold: Option<TrustThreshold>,
+
+    /// Relay the packets sent on this channel, if unordered, one at a time in the order
+    /// of their sequence, waiting for each transaction to be committed before the next.
+    #[serde(default, skip_serializing_if = "core::ops::Not::not")]
+    pub force_ordered_relay: bool,
+}
+
+/// A canary packet, ie. a transfer of a dust amount from the relayer account to
+/// its account on the counterparty chain, periodically sent on a channel to
+/// check that the path relays packets end-to-end.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct CanaryConfig {
+    #[serde(default = "default::canary_port")]
+    pub port: PortId,
+
+    pub denom: String,
+
+    #[serde(default = "default::canary_amount")]
+    pub amount: u64,
+
+    /// Time between two canary packets.
+    #[serde(default = "default::canary_interval", with = "humantime_serde")]
+    pub interval: Duration,
+
+    /// Time within which a canary packet must be received and acknowledged
+    /// for the path to be reported as healthy.
+    #[serde(default = "default::canary_threshold", with = "humantime_serde")]
+    pub threshold: Duration,
+}
+
+/// The gRPC metadata header through which the height of a historical query
+/// is passed to the node of a chain.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct GrpcHeightHeader {
+    #[serde(default = "default::grpc_height_header_name")]
+    pub name: String,
+
+    #[serde(default)]
+    pub format: HeightHeaderFormat,
+}
+
+impl Default for GrpcHeightHeader {
+    fn default() -> Self {
+        Self {
+            name: default::grpc_height_header_name(),
+            format: HeightHeaderFormat::default(),
+        }
+    }
+}
+
+/// How the session pinning the relayer to a backend node of a load balancer with sticky
+/// sessions is captured from the responses of the load balancer.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum StickySessionConfig {
+    /// The session is the cookie with the given name, which is sent back in a `cookie` header.
+    Cookie(String),
+
+    /// The session is the value of the response header with the given name,
+    /// which is sent back in a request header of the same name.
+    Header(String),
+}
+
+/// Limits of the batches in which the timeouts of packets are submitted to a chain,
+/// separately from the other packet messages, to pace the fees spent on mass timeouts.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TimeoutBatchConfig {
+    /// Maximum number of timeout messages in a transaction.
+    #[serde(default)]
+    pub max_msg_num: MaxMsgNum,
+
+    /// Maximum total size of the timeout messages of a transaction, in bytes.
+    #[serde(default)]
+    pub max_tx_size: MaxTxSize,
+
+    /// Minimum time between two transactions of timeouts.
+    #[serde(default, with = "humantime_serde")]
+    pub interval: Duration,
+}
+
+/// How the inclusion of the transactions sent to a chain is confirmed.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TxConfirmationStrategy {
+    /// Search the transactions by hash, until the node reports that its transaction
+    /// indexing is disabled, from which point on search the new blocks of the chain.
+    #[default]
+    Auto,
+
+    /// Search the transactions by hash, which requires the transaction indexing of the node.
+    TxSearch,
+
+    /// Search the new blocks of the chain for the transactions.
+    BlockSearch,
+}
+
+/// The multisig account which the relayer signs transactions as, of which
+/// the key given by `key_name` is one of the members.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct MultisigConfig {
+    /// The number of members whose signature is required.
+    pub threshold: u32,
+
+    /// The base64-encoded secp256k1 public keys of the members of the account,
+    /// in the order of the public key of the account.
+    pub public_keys: Vec<String>,
+
+    /// The command run to obtain the signature of another member, with the
+    /// base64-encoded public key of the member appended to its arguments.
+    /// It reads the base64-encoded bytes to sign on its standard input and
+    /// prints the base64-encoded signature on its standard output.
+    pub cosigner_command: Vec<String>,
+}
+
+/// Scales the gas price by the ratio between a reference price of the fee denomination
+/// and its current price, both expressed in a reference denomination, eg. USD, so that the
+/// value of the gas price paid stays the same when the value of the fee denomination changes.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReferenceGasPriceConfig {
+    /// The price of the fee denomination, in the reference denomination,
+    /// at which the configured gas price is paid as is.
+    pub reference_price: f64,
+
+    /// The command run to obtain the current price of the fee denomination, in the
+    /// reference denomination, with the fee denomination appended to its arguments.
+    /// It prints the price as a decimal number on its standard output.
+    pub price_command: Vec<String>,
+
+    /// How long the current price obtained from `price_command` is used for.
+    #[serde(
+        default = "default::reference_price_refresh_interval",
+        with = "humantime_serde"
+    )]
+    pub refresh_interval: Duration,
+
+    /// The largest factor by which the gas price is scaled, up or down, so that an
+    /// outlandish price obtained from `price_command` does not skew the fees paid.
+    #[serde(default = "default::reference_price_max_scale")]
+    pub max_scale: f64,
+}
+
+/// Derives the gas price in the fee denomination from its spot price in the base
+/// denomination of the Osmosis `txfees` module, for chains which accept fees in
+/// other denominations than their base one at the rate set by that module.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TxFeesGasPriceConfig {
+    /// The gas price in the base denomination of the `txfees` module, eg. `uosmo`.
+    pub base_gas_price: f64,
+
+    /// How long the spot price queried from the `txfees` module is used for.
+    #[serde(
+        default = "default::txfees_spot_price_refresh_interval",
+        with = "humantime_serde"
+    )]
+    pub refresh_interval: Duration,
+}
+
+/// Sizes the batches of messages so that the gas they need fits comfortably in the gas
+/// left available in a block, given the gas used by the transactions of the recent blocks,
+/// rather than only in the maximum gas of a transaction.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct BlockGasUsageConfig {
+    /// How many of the most recent blocks sampled the gas usage is averaged over.
+    #[serde(default = "default::block_gas_usage_sample_blocks")]
+    pub sample_blocks: usize,
+
+    /// The fraction of the gas left available in a block that a batch may need.
+    #[serde(default = "default::block_gas_usage_headroom")]
+    pub headroom: f64,
+}
+
+/// The format of the height passed in the [`GrpcHeightHeader`] of a historical query.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HeightHeaderFormat {
+    /// The revision height, eg. `100`.
+    #[default]
+    Height,
+
+    /// The revision number and the revision height, eg. `1-100`.
+    Revision,
+}
+
+/// The unit in which a chain interprets the timeout timestamps of the packets it
+/// receives. The IBC specification mandates nanoseconds, but some chains differ.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimestampUnit {
+    #[default]
+    Nanoseconds,
+    Microseconds,
+    Milliseconds,
+    Seconds,
+}
+
+impl TimestampUnit {
+    /// The number of nanoseconds in one unit.
+    pub fn nanoseconds(self) -> u64 {
+        match self {
+            Self::Nanoseconds => 1,
+            Self::Microseconds => 1_000,
+            Self::Milliseconds => 1_000_000,
+            Self::Seconds => 1_000_000_000,
+        }
+    }
+}
+
+/// Which trusting period the refreshes of the clients of a chain are scheduled
+/// against, when the configured one and the one of the client state differ.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrustingPeriodSource {
+    /// The trusting period of the client state on chain.
+    #[default]
+    OnChain,
+
+    /// The `trusting_period` of the chain configuration, if set.
+    Config,
+}
+
+/// How to relay packets whose proofs must be built at a height which
+/// the node of the chain has already pruned the state of.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PrunedHeightHandling {
+    /// Build the proofs at the latest height of the chain instead,
+    /// updating the counterparty client to that height.
+    #[default]
+    Refresh,
+
+    /// Give up relaying the packets and report the pruned height.
+    Fail,
+}
+
+/// How to select the height at which the proofs of the packet messages are built.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProofHeightStrategy {
+    /// Build the proofs at the height at which the packet events were committed,
+    /// updating the counterparty client to the following height if needed.
+    #[default]
+    LatestCommitted,
+
+    /// Build the proofs at the latest height of the counterparty client
+    /// if it is past the packet events, so that the client needs no update.
+    MinimalUpdate,
+}
+
+/// Where the relayer obtains the commitment of a packet sent by a chain.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PacketCommitmentSource {
+    /// Query the commitment stored by the chain.
+    #[default]
+    Store,
+
+    /// Reconstruct the commitment from the data of the `SendPacket` event, and only
+    /// query the proof of the commitment, for chains whose commitment store queries
+    /// do not return the value of the commitments. The packets pending on such chains
+    /// are found from their `SendPacket` events, as their commitments cannot be listed.
+    Events,
+}
+
+/// How to handle a batch of messages whose transaction is rejected
+/// because of one of its messages.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchFailureMode {
+    /// Fail the whole batch.
+    #[default]
+    Abort,
+
+    /// Bisect the batch to isolate the failing messages,
+    /// and submit the other messages without them.
+    BestEffort,
+}
+
+/// The encoding of the address of the relayer in the `signer` field
+/// of the messages it builds for a chain.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SignerEncoding {
+    /// The bech32 address of the account, eg. `evmos1...`.
+    #[default]
+    Bech32,
+
+    /// The hex-encoded bytes of the address of the account, prefixed with `0x`,
+    /// as expected by some EVM-compatible chains.
+    Hex,
+}
+
+/// The encoding of the data of the packets sent by an application.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PacketDataEncoding {
+    /// JSON, as used by the ICS-20 application.
+    #[default]
+    Json,
+
+    /// Protobuf, as used by newer applications.
+    Protobuf,
+}
+
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct GenesisRestart {
@@ -631,6 +1291,21 @@ pub enum EventSourceMode {
         /// Maximum amount of time to wait for a NewBlock event before emitting the event batch
         #[serde(default = "default::batch_delay", with = "humantime_serde")]
         batch_delay: Duration,
+
+        /// Precise subscription queries to register instead of the default
+        /// ones, so that the node only pushes the matching transactions
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        queries: Vec<EventQuery>,
+
+        /// Delay before reconnecting to an endpoint which drops the connection
+        /// again shortly after having been reconnected to, doubled on each such disconnect
+        #[serde(default = "default::reconnect_backoff", with = "humantime_serde")]
+        reconnect_backoff: Duration,
+
+        /// Maximum delay before reconnecting to a flapping endpoint, after which
+        /// a connection which stays up is considered stable again
+        #[serde(default = "default::max_reconnect_backoff", with = "humantime_serde")]
+        max_reconnect_backoff: Duration,
     },
 
     /// Pull-based event source, via RPC /block_results
@@ -765,6 +1440,13 @@ impl ChainConfig {
         }
     }
 
+    pub fn max_memo_bytes(&self) -> Option<usize> {
+        match self {
+            Self::CosmosSdk(config) | Self::Namada(config) => config.max_memo_bytes,
+            Self::Penumbra(_config) => None,
+        }
+    }
+
     pub fn excluded_sequences(&self, channel_id: &ChannelId) -> Cow<'_, [Sequence]> {
         match self {
             Self::CosmosSdk(config) | Self::Namada(config) => config
@@ -777,6 +1459,135 @@ impl ChainConfig {
         }
     }
 
+    /// Resolves the verification settings of the given channel of this chain,
+    /// from the overrides of the channel, then from the settings of the chain,
+    /// and then from the given global settings.
+    pub fn channel_verification(
+        &self,
+        channel_id: &ChannelId,
+        global: ChannelVerification,
+    ) -> ChannelVerification {
+        match self {
+            Self::CosmosSdk(config) | Self::Namada(config) => {
+                let overrides = config.channel_overrides.get(channel_id);
+
+                ChannelVerification {
+                    min_confirmation_blocks: overrides
+                        .and_then(|o| o.min_confirmation_blocks)
+                        .or(config.min_confirmation_blocks)
+                        .unwrap_or(global.min_confirmation_blocks),
+                    local_trust_threshold: overrides
+                        .and_then(|o| o.local_trust_threshold)
+                        .or(config.local_trust_threshold)
+                        .or(global.local_trust_threshold),
+                    verify_client_chain_id: global.verify_client_chain_id,
+                }
+            }
+            Self::Penumbra(_config) => global,
+        }
+    }
+
+    /// Whether the packets sent on the given channel of this chain are relayed
+    /// one at a time in the order of their sequence, see [`ChannelOverrides`].
+    pub fn force_ordered_relay(&self, channel_id: &ChannelId) -> bool {
+        match self {
+            Self::CosmosSdk(config) | Self::Namada(config) => config
+                .channel_overrides
+                .get(channel_id)
+                .is_some_and(|overrides| overrides.force_ordered_relay),
+            Self::Penumbra(_config) => false,
+        }
+    }
+
+    /// The trusting period against which the refreshes of the clients of this chain
+    /// are scheduled, given the trusting period of their client state.
+    pub fn refresh_trusting_period(&self, client_trusting_period: Duration) -> Duration {
+        match self {
+            Self::CosmosSdk(config) | Self::Namada(config) => match config.trusting_period_source {
+                TrustingPeriodSource::OnChain => client_trusting_period,
+                TrustingPeriodSource::Config => {
+                    config.trusting_period.unwrap_or(client_trusting_period)
+                }
+            },
+            Self::Penumbra(_config) => client_trusting_period,
+        }
+    }
+
+    pub fn timeout_timestamp_unit(&self) -> TimestampUnit {
+        match self {
+            Self::CosmosSdk(config) | Self::Namada(config) => config.timeout_timestamp_unit,
+            Self::Penumbra(_config) => TimestampUnit::default(),
+        }
+    }
+
+    /// The maximum number of client updates submitted to this chain at once
+    /// by the refreshes of the clients it hosts.
+    pub fn max_concurrent_client_updates(&self) -> Option<NonZeroUsize> {
+        match self {
+            Self::CosmosSdk(config) | Self::Namada(config) => config.max_concurrent_client_updates,
+            Self::Penumbra(_config) => None,
+        }
+    }
+
+    /// The maximum number of headers of this chain built at once when updating
+    /// a client referencing it to several heights.
+    pub fn max_parallel_header_builds(&self) -> Option<NonZeroUsize> {
+        match self {
+            Self::CosmosSdk(config) | Self::Namada(config) => config.max_parallel_header_builds,
+            Self::Penumbra(_config) => None,
+        }
+    }
+
+    /// Whether to detect the misbehaviour of the chains referenced by the clients hosted
+    /// on this chain, if set for this chain rather than globally.
+    pub fn detect_misbehaviour(&self) -> Option<bool> {
+        match self {
+            Self::CosmosSdk(config) | Self::Namada(config) => config.detect_misbehaviour,
+            Self::Penumbra(_config) => None,
+        }
+    }
+
+    /// The encoding of the address of the relayer in the messages built for this chain.
+    pub fn signer_encoding(&self) -> SignerEncoding {
+        match self {
+            Self::CosmosSdk(config) | Self::Namada(config) => config.signer_encoding,
+            Self::Penumbra(_config) => SignerEncoding::default(),
+        }
+    }
+
+    /// Limits of the batches in which the timeouts of packets are submitted to this chain.
+    pub fn timeout_batch(&self) -> Option<TimeoutBatchConfig> {
+        match self {
+            Self::CosmosSdk(config) | Self::Namada(config) => config.timeout_batch,
+            Self::Penumbra(_config) => None,
+        }
+    }
+
+    /// Delay after which the receipts of the packets are checked again on this chain
+    /// right before submitting their `RecvPacket` messages, if enabled.
+    pub fn receipt_recheck_delay(&self) -> Option<Duration> {
+        match self {
+            Self::CosmosSdk(config) | Self::Namada(config) => config.receipt_recheck_delay,
+            Self::Penumbra(_config) => None,
+        }
+    }
+
+    /// Where the commitments of the packets sent by this chain are obtained from.
+    pub fn packet_commitment_source(&self) -> PacketCommitmentSource {
+        match self {
+            Self::CosmosSdk(config) | Self::Namada(config) => config.packet_commitment_source,
+            Self::Penumbra(_config) => PacketCommitmentSource::default(),
+        }
+    }
+
+    /// The canary packets to send on the channels of this chain.
+    pub fn canaries(&self) -> Cow<'_, BTreeMap<ChannelId, CanaryConfig>> {
+        match self {
+            Self::CosmosSdk(config) | Self::Namada(config) => Cow::Borrowed(&config.canaries),
+            Self::Penumbra(_config) => Cow::Owned(BTreeMap::new()),
+        }
+    }
+
     pub fn allow_ccq(&self) -> bool {
         match self {
             Self::CosmosSdk(config) | Self::Namada(config) => config.allow_ccq,
@@ -791,6 +1602,44 @@ impl ChainConfig {
         }
     }
 
+    /// The denominations in which the relayer account pays fees on the chain,
+    /// starting with the one of the gas price.
+    pub fn fee_denoms(&self) -> Vec<String> {
+        match self {
+            Self::CosmosSdk(config) | Self::Namada(config) => {
+                let mut denoms: Vec<String> = config
+                    .gas_price
+                    .iter()
+                    .map(|gas_price| gas_price.denom.clone())
+                    .collect();
+                for denom in &config.extra_fee_denoms {
+                    if !denoms.contains(denom) {
+                        denoms.push(denom.clone());
+                    }
+                }
+                denoms
+            }
+            Self::Penumbra(_config) => Vec::new(),
+        }
+    }
+
+    /// The names of the keys which sign in turn the transactions relayed to the chain,
+    /// each listed once. Empty if the transactions are signed with the configured key.
+    pub fn signing_accounts(&self) -> Vec<String> {
+        match self {
+            Self::CosmosSdk(config) | Self::Namada(config) => {
+                let mut key_names: Vec<String> = Vec::new();
+                for key_name in &config.signing_accounts {
+                    if !key_names.contains(key_name) {
+                        key_names.push(key_name.clone());
+                    }
+                }
+                key_names
+            }
+            Self::Penumbra(_config) => Vec::new(),
+        }
+    }
+
     pub fn keyring_support(&self) -> bool {
         match self {
             Self::Namada(_) | Self::CosmosSdk(_) => true,
@@ -911,9 +1760,13 @@ impl From<CosmosConfigError> for Error {
 #[cfg(test)]
 mod tests {
     use core::str::FromStr;
+    use core::time::Duration;
 
     use super::{load, parse_gas_prices, store_writer, ChainConfig};
-    use crate::config::GasPrice;
+    use crate::config::types::TrustThreshold;
+    use crate::config::{ConnectionPath, GasPrice, GasPrices};
+    use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId};
+    use serde::{Deserialize, Serialize};
     use test_log::test;
 
     #[test]
@@ -940,6 +1793,20 @@ mod tests {
         dbg!(config);
     }
 
+    #[test]
+    fn fee_denoms_start_with_gas_price_denom() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example_fee_filter.toml"
+        );
+
+        let config = load(path).expect("could not parse config");
+
+        // Each denom is reported once, even if also configured as an extra fee denom
+        assert_eq!(config.chains[0].fee_denoms(), vec!["stake", "uatom"]);
+        assert_eq!(config.chains[1].fee_denoms(), vec!["stake"]);
+    }
+
     #[test]
     fn parse_valid_decoding_size_config() {
         let path = concat!(
@@ -952,6 +1819,29 @@ mod tests {
         dbg!(config);
     }
 
+    #[test]
+    fn decoding_size_config_is_parsed_per_chain() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example_decoding_size.toml"
+        );
+
+        let config = load(path).expect("could not parse config");
+
+        let decoding_sizes: Vec<_> = config
+            .chains
+            .iter()
+            .map(|chain| match chain {
+                ChainConfig::CosmosSdk(chain_config) | ChainConfig::Namada(chain_config) => {
+                    chain_config.max_grpc_decoding_size.get_bytes()
+                }
+                ChainConfig::Penumbra(_) => panic!("unexpected Penumbra chain"),
+            })
+            .collect();
+
+        assert_eq!(decoding_sizes, vec![4 * 1024 * 1024, 5_910_000]);
+    }
+
     #[test]
     fn parse_valid_telemetry() {
         let path = concat!(
@@ -964,6 +1854,133 @@ mod tests {
         dbg!(config);
     }
 
+    #[test]
+    fn high_value_channel_waits_more_confirmations() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example_channel_overrides.toml"
+        );
+
+        let config = load(path).expect("could not parse config");
+        let global = config.mode.packets.channel_verification();
+
+        let high_value = ChannelId::new(0);
+        let low_value = ChannelId::new(1);
+
+        let chain_a = &config.chains[0];
+        let high = chain_a.channel_verification(&high_value, global);
+        let low = chain_a.channel_verification(&low_value, global);
+
+        assert_eq!(high.min_confirmation_blocks, 20);
+        assert_eq!(low.min_confirmation_blocks, 2);
+        assert!(high.min_confirmation_blocks > low.min_confirmation_blocks);
+
+        assert_eq!(high.local_trust_threshold, Some(TrustThreshold::TWO_THIRDS));
+        assert_eq!(low.local_trust_threshold, None);
+
+        // Chains without overrides fall back to the global settings
+        let chain_b = &config.chains[1];
+        assert_eq!(chain_b.channel_verification(&high_value, global), global);
+    }
+
+    #[test]
+    fn force_ordered_relay_is_set_per_channel() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example_channel_overrides.toml"
+        );
+
+        let config = load(path).expect("could not parse config");
+
+        let chain_a = &config.chains[0];
+        assert!(chain_a.force_ordered_relay(&ChannelId::new(2)));
+        assert!(!chain_a.force_ordered_relay(&ChannelId::new(0)));
+        assert!(!chain_a.force_ordered_relay(&ChannelId::new(1)));
+
+        // Overriding other settings of a channel leaves its verification settings as is
+        let global = config.mode.packets.channel_verification();
+        assert_eq!(
+            chain_a
+                .channel_verification(&ChannelId::new(2), global)
+                .min_confirmation_blocks,
+            2
+        );
+    }
+
+    #[test]
+    fn misbehaviour_detection_is_overridden_per_host_chain() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+
+        let mut config = load(path).expect("could not parse config");
+        let chain_a = config.chains[0].id().clone();
+        let chain_b = config.chains[1].id().clone();
+
+        match &mut config.chains[1] {
+            ChainConfig::CosmosSdk(chain_config) => chain_config.detect_misbehaviour = Some(false),
+            _ => panic!("unexpected chain type"),
+        }
+
+        assert!(config.mode.clients.misbehaviour);
+        assert!(config.detects_misbehaviour(&chain_a));
+        assert!(!config.detects_misbehaviour(&chain_b));
+
+        // The chains without override follow the global setting
+        config.mode.clients.misbehaviour = false;
+        assert!(!config.detects_misbehaviour(&chain_a));
+
+        match &mut config.chains[0] {
+            ChainConfig::CosmosSdk(chain_config) => chain_config.detect_misbehaviour = Some(true),
+            _ => panic!("unexpected chain type"),
+        }
+        assert!(config.detects_misbehaviour(&chain_a));
+
+        // No misbehaviour is detected when the client workers are disabled
+        config.mode.clients.enabled = false;
+        assert!(!config.detects_misbehaviour(&chain_a));
+    }
+
+    #[test]
+    fn misbehaviour_detection_may_be_enabled_for_a_single_chain() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+
+        let mut config = load(path).expect("could not parse config");
+        config.mode.clients.refresh = false;
+        config.mode.clients.misbehaviour = false;
+        assert!(config.validate_config().is_err());
+
+        match &mut config.chains[1] {
+            ChainConfig::CosmosSdk(chain_config) => chain_config.detect_misbehaviour = Some(true),
+            _ => panic!("unexpected chain type"),
+        }
+        assert!(config.validate_config().is_ok());
+    }
+
+    #[test]
+    fn connection_paths_must_refer_to_configured_chains() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+
+        let mut config = load(path).expect("could not parse config");
+
+        config.mode.connections.paths = vec![ConnectionPath {
+            a_chain: config.chains[0].id().clone(),
+            b_chain: config.chains[1].id().clone(),
+            delay: Duration::from_secs(10),
+        }];
+        assert!(config.validate_config().is_ok());
+
+        config.mode.connections.paths[0].b_chain = ChainId::from_string("unknown-0");
+        assert!(config.validate_config().is_err());
+    }
+
     #[test]
     fn parse_invalid_telemetry() {
         let path = concat!(
@@ -1056,6 +2073,56 @@ mod tests {
         assert_eq!(gp, gp_original);
     }
 
+    #[test]
+    fn gas_price_may_be_a_list() {
+        #[derive(Debug, Deserialize, Serialize)]
+        struct Chain {
+            gas_price: GasPrices,
+        }
+
+        let parse = |s: &str| toml::from_str::<Chain>(s).map(|chain| chain.gas_price);
+
+        let single = parse("gas_price = { price = 0.025, denom = 'uatom' }").unwrap();
+        assert_eq!(
+            single.preferred(),
+            &GasPrice::new(0.025, "uatom".to_owned())
+        );
+        assert!(!single.has_fallbacks());
+
+        let many = parse(
+            "gas_price = [{ price = 0.025, denom = 'uatom' }, { price = 0.1, denom = 'uosmo' }]",
+        )
+        .unwrap();
+        assert_eq!(many.preferred(), &GasPrice::new(0.025, "uatom".to_owned()));
+        assert_eq!(
+            many.iter()
+                .map(|price| price.denom.as_str())
+                .collect::<Vec<_>>(),
+            ["uatom", "uosmo"]
+        );
+
+        // Serialized back as configured
+        for gas_price in [single, many] {
+            let chain = Chain { gas_price };
+            let serialized = toml::to_string(&chain).unwrap();
+            assert_eq!(parse(&serialized).unwrap(), chain.gas_price);
+        }
+
+        assert!(parse("gas_price = []").is_err());
+        assert!(parse(
+            "gas_price = [{ price = 0.0, denom = 'uatom' }, { price = 0.1, denom = 'uosmo' }]"
+        )
+        .is_err());
+        assert!(parse(
+            "gas_price = [{ price = 0.025, denom = 'uatom' }, { price = -1.0, denom = 'uosmo' }]"
+        )
+        .is_err());
+        assert!(parse(
+            "gas_price = [{ price = 0.025, denom = 'uatom' }, { price = 0.1, denom = 'uatom' }]"
+        )
+        .is_err());
+    }
+
     #[test]
     fn parse_multiple_gas_prices() {
         let expected = vec![
@@ -1,7 +1,11 @@
+use core::str::FromStr;
+
+use ibc_relayer_types::applications::transfer::Amount;
 use serde::de::Error as DeserializeError;
 use serde::de::Unexpected;
 use serde::Deserialize;
 use serde::Deserializer;
+use serde::Serializer;
 use serde_derive::Serialize;
 
 flex_error::define_error! {
@@ -20,6 +24,17 @@ pub struct DynamicGasPrice {
     pub enabled: bool,
     pub multiplier: f64,
     pub max: f64,
+    /// The maximum fee of a transaction, in the denomination of the gas price,
+    /// to which the fee computed at the dynamic gas price is clamped.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_max_fee"
+    )]
+    pub max_fee: Option<Amount>,
+    /// The number of base fees last queried whose exponentially-weighted average
+    /// is used instead of the latest base fee, to smooth out the spikes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smoothing_window: Option<usize>,
 }
 
 impl DynamicGasPrice {
@@ -36,6 +51,8 @@ impl DynamicGasPrice {
             enabled: false,
             multiplier: Self::DEFAULT_MULTIPLIER,
             max: Self::DEFAULT_MAX,
+            max_fee: None,
+            smoothing_window: None,
         }
     }
 
@@ -48,6 +65,8 @@ impl DynamicGasPrice {
             enabled,
             multiplier,
             max,
+            max_fee: None,
+            smoothing_window: None,
         })
     }
 
@@ -57,10 +76,39 @@ impl DynamicGasPrice {
             enabled,
             multiplier,
             max,
+            max_fee: None,
+            smoothing_window: None,
+        }
+    }
+
+    /// Clamp the fee of the transactions to the given amount.
+    pub fn with_max_fee(self, max_fee: Amount) -> Self {
+        Self {
+            max_fee: Some(max_fee),
+            ..self
+        }
+    }
+
+    /// Smooth the queried base fees over the given number of queries.
+    pub fn with_smoothing_window(self, smoothing_window: usize) -> Self {
+        Self {
+            smoothing_window: Some(smoothing_window),
+            ..self
         }
     }
 }
 
+/// Serialize the maximum fee as a decimal amount, as it is configured.
+fn serialize_max_fee<S>(max_fee: &Option<Amount>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match max_fee {
+        Some(max_fee) => serializer.serialize_str(&max_fee.to_string()),
+        None => serializer.serialize_none(),
+    }
+}
+
 impl Default for DynamicGasPrice {
     fn default() -> Self {
         Self::disabled()
@@ -72,28 +120,58 @@ impl<'de> Deserialize<'de> for DynamicGasPrice {
     where
         D: Deserializer<'de>,
     {
+        /// The maximum fee, either as an integer or as a decimal string
+        /// for the amounts which do not fit in an integer.
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum MaxFee {
+            Integer(u64),
+            Decimal(String),
+        }
+
         #[derive(Deserialize)]
         struct DynGas {
             enabled: bool,
             multiplier: f64,
             max: f64,
+            #[serde(default)]
+            max_fee: Option<MaxFee>,
+            #[serde(default)]
+            smoothing_window: Option<usize>,
         }
 
         let DynGas {
             enabled,
             multiplier,
             max,
+            max_fee,
+            smoothing_window,
         } = DynGas::deserialize(deserializer)?;
 
-        DynamicGasPrice::new(enabled, multiplier, max).map_err(|e| match e.detail() {
-            ErrorDetail::MultiplierTooSmall(_) => D::Error::invalid_value(
-                Unexpected::Float(multiplier),
-                &format!(
-                    "a floating-point value greater than {}",
-                    Self::MIN_MULTIPLIER
-                )
-                .as_str(),
-            ),
+        let max_fee = match max_fee {
+            None => None,
+            Some(MaxFee::Integer(max_fee)) => Some(Amount::from(max_fee)),
+            Some(MaxFee::Decimal(max_fee)) => Some(Amount::from_str(&max_fee).map_err(|_| {
+                D::Error::invalid_value(Unexpected::Str(&max_fee), &"a decimal amount")
+            })?),
+        };
+
+        let dynamic_gas_price =
+            DynamicGasPrice::new(enabled, multiplier, max).map_err(|e| match e.detail() {
+                ErrorDetail::MultiplierTooSmall(_) => D::Error::invalid_value(
+                    Unexpected::Float(multiplier),
+                    &format!(
+                        "a floating-point value greater than {}",
+                        Self::MIN_MULTIPLIER
+                    )
+                    .as_str(),
+                ),
+            })?;
+
+        Ok(DynamicGasPrice {
+            max_fee,
+            smoothing_window,
+            ..dynamic_gas_price
         })
     }
 }
@@ -133,6 +211,62 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_max_fee() {
+        #[derive(Debug, Deserialize)]
+        struct DummyConfig {
+            dynamic_gas: DynamicGasPrice,
+        }
+
+        let config = toml::from_str::<DummyConfig>(
+            "dynamic_gas = { enabled = true, multiplier = 1.1, max = 0.6 }",
+        )
+        .unwrap();
+        assert_eq!(config.dynamic_gas.max_fee, None);
+
+        let config = toml::from_str::<DummyConfig>(
+            "dynamic_gas = { enabled = true, multiplier = 1.1, max = 0.6, max_fee = 250000 }",
+        )
+        .unwrap();
+        assert_eq!(config.dynamic_gas.max_fee, Some(Amount::from(250000u64)));
+
+        let config = toml::from_str::<DummyConfig>(
+            "dynamic_gas = { enabled = true, multiplier = 1.1, max = 0.6, max_fee = '100000000000000000000' }",
+        )
+        .unwrap();
+        assert_eq!(
+            config.dynamic_gas.max_fee,
+            Some(Amount::from(100_000_000_000_000_000_000u128))
+        );
+
+        let err = toml::from_str::<DummyConfig>(
+            "dynamic_gas = { enabled = true, multiplier = 1.1, max = 0.6, max_fee = 'lots' }",
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("expected a decimal amount"));
+    }
+
+    #[test]
+    fn parse_smoothing_window() {
+        #[derive(Debug, Deserialize)]
+        struct DummyConfig {
+            dynamic_gas: DynamicGasPrice,
+        }
+
+        let config = toml::from_str::<DummyConfig>(
+            "dynamic_gas = { enabled = true, multiplier = 1.1, max = 0.6 }",
+        )
+        .unwrap();
+        assert_eq!(config.dynamic_gas.smoothing_window, None);
+
+        let config = toml::from_str::<DummyConfig>(
+            "dynamic_gas = { enabled = true, multiplier = 1.1, max = 0.6, smoothing_window = 5 }",
+        )
+        .unwrap();
+        assert_eq!(config.dynamic_gas.smoothing_window, Some(5));
+    }
+
     #[test]
     fn unsafe_gas_multiplier() {
         let dynamic_gas = DynamicGasPrice::unsafe_new(true, 0.6, 0.4);
@@ -14,6 +14,12 @@ use ibc_relayer_types::bigint::U256;
 use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, PortId};
 use ibc_relayer_types::events::IbcEventType;
 
+/// Prefix of the ports of the interchain accounts controllers, followed by the owner of the account.
+const ICA_CONTROLLER_PORT_PREFIX: &str = "icacontroller-";
+
+/// Port of the interchain accounts host.
+const ICA_HOST_PORT: &str = "icahost";
+
 /// Represents all the filtering policies for packets.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PacketFilter {
@@ -21,6 +27,12 @@ pub struct PacketFilter {
     pub channel_policy: ChannelPolicy,
     #[serde(default)]
     pub min_fees: HashMap<ChannelFilterMatch, FeePolicy>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub denom_patterns: HashMap<ChannelFilterMatch, DenomPolicy>,
+    /// Whether the interchain accounts channels, on the `icacontroller-*` and `icahost`
+    /// ports, are allowed in addition to the channels of an `allow` policy.
+    #[serde(default)]
+    pub include_ica_channels: bool,
 }
 
 impl Default for PacketFilter {
@@ -29,6 +41,8 @@ impl Default for PacketFilter {
         Self {
             channel_policy: ChannelPolicy::default(),
             min_fees: HashMap::new(),
+            denom_patterns: HashMap::new(),
+            include_ica_channels: false,
         }
     }
 }
@@ -41,9 +55,55 @@ impl PacketFilter {
         Self {
             channel_policy,
             min_fees,
+            denom_patterns: HashMap::new(),
+            include_ica_channels: false,
+        }
+    }
+
+    /// Sets whether the interchain accounts channels are allowed in addition
+    /// to the channels of an `allow` policy.
+    pub fn with_ica_channels(mut self, include_ica_channels: bool) -> Self {
+        self.include_ica_channels = include_ica_channels;
+        self
+    }
+
+    /// Returns true if the packets can be relayed on the channel with [`PortId`] and [`ChannelId`],
+    /// following the channel policy and including the interchain accounts channels if enabled.
+    pub fn is_channel_allowed(&self, port_id: &PortId, channel_id: &ChannelId) -> bool {
+        match &self.channel_policy {
+            ChannelPolicy::Allow(_) if self.include_ica_channels && is_ica_port(port_id) => true,
+            policy => policy.is_allowed(port_id, channel_id),
         }
     }
 
+    /// Returns the list of channels of an `allow` policy if it only contains exact patterns,
+    /// in which case the allowed channels are known without discovering the channels of the chain.
+    pub fn exact_allow_list(&self) -> Option<&ChannelFilters> {
+        match &self.channel_policy {
+            ChannelPolicy::Allow(filters) if filters.is_exact() && !self.include_ica_channels => {
+                Some(filters)
+            }
+            _ => None,
+        }
+    }
+
+    /// Sets the policies filtering the ICS-20 transfers by denom on the matching channels.
+    pub fn with_denom_patterns(
+        mut self,
+        denom_patterns: HashMap<ChannelFilterMatch, DenomPolicy>,
+    ) -> Self {
+        self.denom_patterns = denom_patterns;
+        self
+    }
+
+    /// Returns the policy filtering the ICS-20 transfers by denom on the given channel, if any.
+    pub fn denom_policy(&self, channel_id: &ChannelId) -> Option<&DenomPolicy> {
+        self.denom_patterns
+            .iter()
+            .find(|(channel, _)| channel.matches(channel_id))
+            .map(|(_, policy)| policy)
+    }
+
     pub fn allow(filters: Vec<(PortFilterMatch, ChannelFilterMatch)>) -> PacketFilter {
         PacketFilter::new(
             ChannelPolicy::Allow(ChannelFilters::new(filters)),
@@ -52,6 +112,14 @@ impl PacketFilter {
     }
 }
 
+/// Whether the port is one of the ports of the interchain accounts,
+/// ie. `icacontroller-*` on the controller chain and `icahost` on the host chain.
+pub fn is_ica_port(port_id: &PortId) -> bool {
+    let port_id = port_id.as_str();
+
+    port_id == ICA_HOST_PORT || port_id.starts_with(ICA_CONTROLLER_PORT_PREFIX)
+}
+
 /// Represents the ways in which packets can be filtered.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(
@@ -112,6 +180,35 @@ impl MinFee {
     }
 }
 
+/// Represents the policy used to filter the ICS-20 transfers by denom.
+///
+/// The denom of a transfer is matched as it appears in its packet data,
+/// ie. with its trace path if it is not native to the sending chain.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DenomPolicy {
+    /// Patterns of the denoms of the transfers to relay.
+    pub allow: Vec<Wildcard>,
+    /// Whether the packets which are not ICS-20 transfers are not relayed either.
+    #[serde(default)]
+    pub strict: bool,
+}
+
+impl DenomPolicy {
+    pub fn new(allow: Vec<Wildcard>, strict: bool) -> Self {
+        Self { allow, strict }
+    }
+
+    /// Returns true if the packet should be relayed, given the denom of the transfer,
+    /// or `None` if the packet is not an ICS-20 transfer.
+    pub fn should_relay(&self, denom: Option<&str>) -> bool {
+        match denom {
+            Some(denom) => self.allow.iter().any(|pattern| pattern.is_match(denom)),
+            None => !self.strict,
+        }
+    }
+}
+
 impl Default for ChannelPolicy {
     /// By default, allows all channels & ports.
     fn default() -> Self {
@@ -276,6 +373,13 @@ impl Serialize for Wildcard {
     }
 }
 
+impl<'de> Deserialize<'de> for Wildcard {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let pattern = String::deserialize(deserializer)?;
+        Self::new(pattern.trim().to_string()).map_err(de::Error::custom)
+    }
+}
+
 impl PartialEq for Wildcard {
     fn eq(&self, other: &Self) -> bool {
         self.pattern == other.pattern
@@ -625,4 +729,90 @@ mod tests {
         let assert_allow = matches!(pf, ChannelPolicy::Allow(filters) if filters.is_exact());
         assert!(assert_allow);
     }
+
+    #[test]
+    fn packet_filter_denom_patterns() {
+        let packet_filter = r#"
+            policy = 'allow'
+            list = [
+              ['transfer', '*'],
+            ]
+
+            [denom_patterns.'channel-0']
+            allow = ['uatom', 'transfer/channel-1/*']
+
+            [denom_patterns.'channel-1*']
+            allow = ['ibc/*']
+            strict = true
+            "#;
+
+        let pf: PacketFilter =
+            toml::from_str(packet_filter).expect("could not parse packet filter");
+
+        let lenient = pf
+            .denom_policy(&ChannelId::from_str("channel-0").unwrap())
+            .expect("missing denom policy for channel-0");
+        assert!(lenient.should_relay(Some("uatom")));
+        assert!(lenient.should_relay(Some("transfer/channel-1/uosmo")));
+        assert!(!lenient.should_relay(Some("uosmo")));
+        assert!(!lenient.should_relay(Some("transfer/channel-2/uatom")));
+        assert!(lenient.should_relay(None));
+
+        let strict = pf
+            .denom_policy(&ChannelId::from_str("channel-12").unwrap())
+            .expect("missing denom policy for channel-12");
+        assert!(strict.should_relay(Some("ibc/27394FB092D2ECCD56123C74F36E4C1F")));
+        assert!(!strict.should_relay(Some("uatom")));
+        assert!(!strict.should_relay(None));
+
+        assert!(pf
+            .denom_policy(&ChannelId::from_str("channel-2").unwrap())
+            .is_none());
+    }
+
+    #[test]
+    fn packet_filter_include_ica_channels() {
+        let packet_filter = r#"
+            policy = 'allow'
+            list = [
+              ['transfer', 'channel-0'],
+            ]
+            include_ica_channels = true
+            "#;
+
+        let pf: PacketFilter =
+            toml::from_str(packet_filter).expect("could not parse packet filter");
+
+        let port = |port: &str| PortId::from_str(port).unwrap();
+        let channel_5 = ChannelId::from_str("channel-5").unwrap();
+
+        assert!(pf.is_channel_allowed(
+            &port("transfer"),
+            &ChannelId::from_str("channel-0").unwrap()
+        ));
+        assert!(pf.is_channel_allowed(&port("icacontroller-cosmos1owner"), &channel_5));
+        assert!(pf.is_channel_allowed(&port("icahost"), &channel_5));
+        assert!(!pf.is_channel_allowed(&port("transfer"), &channel_5));
+        assert!(!pf.is_channel_allowed(&port("icahostile"), &channel_5));
+
+        // The ICA channels must be discovered despite the exact allow list
+        assert!(pf.exact_allow_list().is_none());
+        assert!(pf
+            .clone()
+            .with_ica_channels(false)
+            .exact_allow_list()
+            .is_some());
+
+        // An explicit deny policy still applies to the ICA channels
+        let deny = PacketFilter::new(
+            ChannelPolicy::Deny(ChannelFilters::new(vec![(
+                FilterPattern::Wildcard("ica*".parse().unwrap()),
+                FilterPattern::Wildcard("*".parse().unwrap()),
+            )])),
+            HashMap::new(),
+        )
+        .with_ica_channels(true);
+
+        assert!(!deny.is_channel_allowed(&port("icahost"), &channel_5));
+    }
 }
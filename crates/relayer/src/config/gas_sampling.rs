@@ -0,0 +1,79 @@
+use serde::de::Error as DeserializeError;
+use serde::de::Unexpected;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde_derive::Serialize;
+
+flex_error::define_error! {
+    Error {
+        IntervalTooSmall
+            { value: u64 }
+            |e| {
+                format_args!("`interval` in gas_estimation_sampling configuration must be greater than or equal to {}, found {}",
+                GasEstimationSampling::MIN_INTERVAL, e.value)
+            },
+    }
+}
+
+/// Controls how often the gas needed by a transaction is estimated by simulating it.
+///
+/// When enabled, only every `interval`-th transaction is simulated, and the
+/// transactions in between reuse the last simulated estimate, as long as
+/// they contain the same kinds of messages as the simulated transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct GasEstimationSampling {
+    pub enabled: bool,
+    pub interval: u64,
+}
+
+impl GasEstimationSampling {
+    const DEFAULT_INTERVAL: u64 = 1;
+    const MIN_INTERVAL: u64 = 1;
+
+    pub fn enabled(interval: u64) -> Result<Self, Error> {
+        Self::new(true, interval)
+    }
+
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            interval: Self::DEFAULT_INTERVAL,
+        }
+    }
+
+    pub fn new(enabled: bool, interval: u64) -> Result<Self, Error> {
+        if interval < Self::MIN_INTERVAL {
+            return Err(Error::interval_too_small(interval));
+        }
+
+        Ok(Self { enabled, interval })
+    }
+}
+
+impl Default for GasEstimationSampling {
+    fn default() -> Self {
+        Self::disabled()
+    }
+}
+
+impl<'de> Deserialize<'de> for GasEstimationSampling {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Sampling {
+            enabled: bool,
+            interval: u64,
+        }
+
+        let Sampling { enabled, interval } = Sampling::deserialize(deserializer)?;
+
+        GasEstimationSampling::new(enabled, interval).map_err(|e| match e.detail() {
+            ErrorDetail::IntervalTooSmall(_) => D::Error::invalid_value(
+                Unexpected::Unsigned(interval),
+                &format!("an integer greater than or equal to {}", Self::MIN_INTERVAL).as_str(),
+            ),
+        })
+    }
+}
@@ -0,0 +1,177 @@
+use core::num::NonZeroU32;
+use core::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// How the delay before retrying the submission of a transaction grows with the retries.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Backoff {
+    /// Wait `base_delay` before every retry.
+    #[default]
+    Fixed,
+    /// Wait `base_delay` times the number of the retry, ie. `base_delay`, `2 * base_delay`, ...
+    Linear,
+    /// Wait `base_delay` doubled at every retry, ie. `base_delay`, `2 * base_delay`, `4 * base_delay`, ...
+    Exponential,
+}
+
+/// Strategy for retrying the submission of a transaction which failed with
+/// a transient error, such as a mismatched account sequence number.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RetryStrategy {
+    pub backoff: Backoff,
+
+    #[serde(with = "humantime_serde")]
+    pub base_delay: Duration,
+
+    /// The maximum number of submissions of a transaction, including the first one.
+    pub max_attempts: NonZeroU32,
+}
+
+impl RetryStrategy {
+    const DEFAULT_BASE_DELAY: Duration = Duration::from_millis(300);
+    const DEFAULT_MAX_ATTEMPTS: NonZeroU32 = NonZeroU32::MIN.saturating_add(1);
+
+    /// The maximum delay to wait before a retry, whatever the backoff.
+    pub const MAX_DELAY: Duration = Duration::from_secs(30);
+
+    /// The delay to wait before the given retry, numbered from 1, at most [`Self::MAX_DELAY`].
+    pub fn delay(&self, retry: u32) -> Duration {
+        let factor = match self.backoff {
+            Backoff::Fixed => 1,
+            Backoff::Linear => retry.max(1),
+            Backoff::Exponential => 2_u32.saturating_pow(retry.saturating_sub(1)),
+        };
+
+        self.base_delay.saturating_mul(factor).min(Self::MAX_DELAY)
+    }
+
+    /// The delays to wait before each of the retries, ie. `max_attempts - 1` delays.
+    pub fn delays(&self) -> impl Iterator<Item = Duration> + '_ {
+        (1..self.max_attempts.get()).map(|retry| self.delay(retry))
+    }
+}
+
+impl Default for RetryStrategy {
+    /// Retry once after 300ms.
+    fn default() -> Self {
+        Self {
+            backoff: Backoff::Fixed,
+            base_delay: Self::DEFAULT_BASE_DELAY,
+            max_attempts: Self::DEFAULT_MAX_ATTEMPTS,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strategy(backoff: Backoff, max_attempts: u32) -> RetryStrategy {
+        RetryStrategy {
+            backoff,
+            base_delay: Duration::from_millis(100),
+            max_attempts: NonZeroU32::new(max_attempts).unwrap(),
+        }
+    }
+
+    fn delays_ms(strategy: &RetryStrategy) -> Vec<u128> {
+        strategy.delays().map(|delay| delay.as_millis()).collect()
+    }
+
+    #[test]
+    fn fixed_backoff() {
+        assert_eq!(delays_ms(&strategy(Backoff::Fixed, 4)), vec![100, 100, 100]);
+    }
+
+    #[test]
+    fn linear_backoff() {
+        assert_eq!(
+            delays_ms(&strategy(Backoff::Linear, 5)),
+            vec![100, 200, 300, 400]
+        );
+    }
+
+    #[test]
+    fn exponential_backoff() {
+        assert_eq!(
+            delays_ms(&strategy(Backoff::Exponential, 5)),
+            vec![100, 200, 400, 800]
+        );
+    }
+
+    #[test]
+    fn exponential_backoff_does_not_overflow() {
+        let strategy = RetryStrategy {
+            base_delay: Duration::from_secs(1),
+            ..strategy(Backoff::Exponential, 100)
+        };
+
+        assert_eq!(strategy.delay(99), RetryStrategy::MAX_DELAY);
+    }
+
+    #[test]
+    fn delays_are_capped() {
+        let strategy = RetryStrategy {
+            base_delay: Duration::from_secs(10),
+            ..strategy(Backoff::Linear, 5)
+        };
+
+        assert_eq!(
+            strategy
+                .delays()
+                .map(|delay| delay.as_secs())
+                .collect::<Vec<_>>(),
+            vec![10, 20, 30, 30]
+        );
+    }
+
+    #[test]
+    fn single_attempt_is_not_retried() {
+        assert_eq!(strategy(Backoff::Exponential, 1).delays().count(), 0);
+    }
+
+    #[test]
+    fn default_retries_once_after_fixed_delay() {
+        assert_eq!(
+            RetryStrategy::default().delays().collect::<Vec<_>>(),
+            vec![Duration::from_millis(300)]
+        );
+    }
+
+    #[test]
+    fn deserialize() {
+        #[derive(Deserialize)]
+        struct Config {
+            retry_strategy: RetryStrategy,
+        }
+
+        let config: Config = toml::from_str(
+            "retry_strategy = { backoff = 'exponential', base_delay = '500ms', max_attempts = 4 }",
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.retry_strategy,
+            RetryStrategy {
+                backoff: Backoff::Exponential,
+                base_delay: Duration::from_millis(500),
+                max_attempts: NonZeroU32::new(4).unwrap(),
+            }
+        );
+
+        let config: Config = toml::from_str("retry_strategy = { backoff = 'linear' }").unwrap();
+
+        assert_eq!(
+            config.retry_strategy,
+            RetryStrategy {
+                backoff: Backoff::Linear,
+                ..RetryStrategy::default()
+            }
+        );
+
+        assert!(toml::from_str::<Config>("retry_strategy = { max_attempts = 0 }").is_err());
+    }
+}
@@ -0,0 +1,20 @@
+use serde_derive::{Deserialize, Serialize};
+
+use crate::config::dynamic_gas::DynamicGasPrice;
+use crate::config::gas_multiplier::GasMultiplier;
+use crate::config::tx_priority::TxPriority;
+use crate::config::GasPrice;
+
+/// A gas strategy which is evaluated alongside the active one for every transaction,
+/// without being used to submit it, in order to safely assess a change of the gas
+/// settings of a chain before applying it.
+///
+/// Each setting left unset is the same as in the active strategy.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ShadowGasStrategy {
+    pub gas_multiplier: Option<GasMultiplier>,
+    pub gas_price: Option<GasPrice>,
+    pub dynamic_gas_price: Option<DynamicGasPrice>,
+    pub tx_priority: Option<TxPriority>,
+}
@@ -0,0 +1,89 @@
+use serde::de::Error as DeserializeError;
+use serde::de::Unexpected;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde_derive::Serialize;
+
+flex_error::define_error! {
+    Error {
+        TipMultiplierTooSmall
+            { value: f64 }
+            |e| {
+                format_args!("`tip_multiplier` in tx_priority configuration must be greater than or equal to {}, found {}",
+                TxPriority::MIN_TIP_MULTIPLIER, e.value)
+            },
+    }
+}
+
+/// Raises the gas price paid by the relayer transactions, so that they are
+/// ordered ahead of other transactions by chains with a fee-based priority mempool.
+///
+/// When enabled, the gas price, either static or dynamic, is multiplied by `tip_multiplier`.
+/// With dynamic gas price enabled, the resulting price is still bounded by its `max`.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize)]
+pub struct TxPriority {
+    pub enabled: bool,
+    pub tip_multiplier: f64,
+}
+
+impl TxPriority {
+    const DEFAULT_TIP_MULTIPLIER: f64 = 1.0;
+    const MIN_TIP_MULTIPLIER: f64 = 1.0;
+
+    pub fn enabled(tip_multiplier: f64) -> Result<Self, Error> {
+        Self::new(true, tip_multiplier)
+    }
+
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            tip_multiplier: Self::DEFAULT_TIP_MULTIPLIER,
+        }
+    }
+
+    pub fn new(enabled: bool, tip_multiplier: f64) -> Result<Self, Error> {
+        if tip_multiplier < Self::MIN_TIP_MULTIPLIER {
+            return Err(Error::tip_multiplier_too_small(tip_multiplier));
+        }
+
+        Ok(Self {
+            enabled,
+            tip_multiplier,
+        })
+    }
+}
+
+impl Default for TxPriority {
+    fn default() -> Self {
+        Self::disabled()
+    }
+}
+
+impl<'de> Deserialize<'de> for TxPriority {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Priority {
+            enabled: bool,
+            tip_multiplier: f64,
+        }
+
+        let Priority {
+            enabled,
+            tip_multiplier,
+        } = Priority::deserialize(deserializer)?;
+
+        TxPriority::new(enabled, tip_multiplier).map_err(|e| match e.detail() {
+            ErrorDetail::TipMultiplierTooSmall(_) => D::Error::invalid_value(
+                Unexpected::Float(tip_multiplier),
+                &format!(
+                    "a floating-point value greater than or equal to {}",
+                    Self::MIN_TIP_MULTIPLIER
+                )
+                .as_str(),
+            ),
+        })
+    }
+}
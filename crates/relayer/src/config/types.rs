@@ -265,6 +265,63 @@ pub mod memo {
     }
 }
 
+pub mod event_query {
+    use core::fmt::{Display, Error as FmtError, Formatter};
+    use core::str::FromStr;
+
+    use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+    use tendermint_rpc::query::Query;
+
+    /// A Tendermint subscription query, eg. `message.action = '/ibc.core.channel.v1.MsgRecvPacket'`,
+    /// that the WebSocket event source registers with the node.
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct EventQuery(Query);
+
+    impl EventQuery {
+        pub fn new(query: Query) -> Self {
+            Self(query)
+        }
+
+        pub fn as_query(&self) -> &Query {
+            &self.0
+        }
+    }
+
+    impl From<EventQuery> for Query {
+        fn from(query: EventQuery) -> Self {
+            query.0
+        }
+    }
+
+    impl<'de> Deserialize<'de> for EventQuery {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let value = String::deserialize(deserializer)?;
+
+            Query::from_str(&value)
+                .map(Self)
+                .map_err(|e| D::Error::custom(format!("invalid event query `{value}`: {e}")))
+        }
+    }
+
+    impl Serialize for EventQuery {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            self.0.to_string().serialize(serializer)
+        }
+    }
+
+    impl Display for EventQuery {
+        fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+            write!(f, "{}", self.0)
+        }
+    }
+}
+
 pub mod ics20_field_size_limit {
     use byte_unit::Byte;
     use serde_derive::{Deserialize, Serialize};
@@ -385,4 +442,31 @@ mod tests {
 
         assert!(err.contains("a string length of at most"));
     }
+
+    #[test]
+    fn parse_event_query() {
+        use event_query::EventQuery;
+        use tendermint_rpc::query::Query;
+
+        #[derive(Debug, Deserialize)]
+        struct DummyConfig {
+            query: EventQuery,
+        }
+
+        let config = toml::from_str::<DummyConfig>(
+            r#"query = "message.action = '/ibc.core.channel.v1.MsgRecvPacket'""#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.query.as_query(),
+            &Query::eq("message.action", "/ibc.core.channel.v1.MsgRecvPacket")
+        );
+
+        let err = toml::from_str::<DummyConfig>(r#"query = "message.action ==""#)
+            .unwrap_err()
+            .to_string();
+
+        assert!(err.contains("invalid event query"));
+    }
 }
@@ -8,12 +8,15 @@ use humantime::format_duration;
 use prost::{DecodeError, EncodeError};
 use regex::Regex;
 use tonic::{
-    metadata::errors::InvalidMetadataValue, transport::Error as TransportError,
+    metadata::errors::{InvalidMetadataKey, InvalidMetadataValue},
+    transport::Error as TransportError,
     Status as GrpcStatus,
 };
 
 use tendermint::abci;
+use tendermint::block::Height as BlockHeight;
 use tendermint::Error as TendermintError;
+use tendermint::Hash;
 use tendermint_light_client::builder::error::Error as LightClientBuilderError;
 use tendermint_light_client::components::io::IoError as LightClientIoError;
 use tendermint_light_client::errors::{
@@ -22,6 +25,7 @@ use tendermint_light_client::errors::{
 use tendermint_proto::Error as TendermintProtoError;
 use tendermint_rpc::endpoint::abci_query::AbciQuery;
 use tendermint_rpc::endpoint::broadcast::tx_sync::Response as TxSyncResponse;
+use tendermint_rpc::error::ErrorDetail as TendermintRpcErrorDetail;
 use tendermint_rpc::Error as TendermintRpcError;
 
 use ibc_relayer_types::applications::ics29_fee::error::Error as FeeError;
@@ -125,6 +129,19 @@ define_error! {
             [ LightClientIoError ]
             |e| { format!("light client error for RPC address {0}", e.address) },
 
+        LightClientValidatorSetHashMismatch
+            {
+                chain_id: String,
+                height: BlockHeight,
+                expected: Hash,
+                got: Hash,
+            }
+            |e| {
+                format!("validator set hash mismatch for chain id {0} at height {1}, even after re-fetching the validator set: \
+                    the header commits to {2} but the validator set fetched from the full node hashes to {3}",
+                    e.chain_id, e.height, e.expected, e.got)
+            },
+
         ChainNotCaughtUp
             {
                 address: String,
@@ -180,6 +197,10 @@ define_error! {
             [ TraceError<InvalidMetadataValue> ]
             |_| { "invalid metadata" },
 
+        InvalidMetadataKey
+            [ TraceError<InvalidMetadataKey> ]
+            |_| { "invalid metadata key" },
+
         BuildClientStateFailure
             |_| { "failed to create client state" },
 
@@ -322,6 +343,41 @@ define_error! {
         TxNoConfirmation
             |_| { "failed tx: no confirmation" },
 
+        DynamicFeeCapTooLow
+            {
+                chain_id: ChainId,
+                max_fee: String,
+                min_fee: String,
+            }
+            |e| {
+                format_args!("the `max_fee` {} of the dynamic gas price of chain {} is lower than the fee {} at its base gas price, skipping the tx",
+                    e.max_fee, e.chain_id, e.min_fee)
+            },
+
+        DailyFeeBudgetExhausted
+            {
+                chain_id: ChainId,
+                denom: String,
+                budget: u128,
+                spent: u128,
+                fee: u128,
+            }
+            |e| {
+                format_args!("fee of {}{} would raise the fees spent today on chain {} from {}{} \
+                    over its daily fee budget of {}{}, skipping the tx",
+                    e.fee, e.denom, e.chain_id, e.spent, e.denom, e.budget, e.denom)
+            },
+
+        TxRetriesExhausted
+            {
+                attempts: u32,
+                last_error: String,
+            }
+            |e| {
+                format_args!("failed to submit tx after {} attempts, last error: {}",
+                    e.attempts, e.last_error)
+            },
+
         Misbehaviour
             { reason: String }
             |e| { format!("error raised while submitting the misbehaviour evidence: {0}", e.reason) },
@@ -520,6 +576,15 @@ define_error! {
         EmptyBaseAccount
             |_| { "empty BaseAccount within EthAccount" },
 
+        MissingBaseAccount
+            {
+                type_url: String,
+                address: String,
+            }
+            |e| {
+                format!("no BaseAccount for address {} found within account of type {}", e.address, e.type_url)
+            },
+
         EmptyQueryAccount
             { address: String }
             |e| { format!("Query/Account RPC returned an empty account for address: {}", e.address) },
@@ -555,6 +620,46 @@ define_error! {
             { chain_id: ChainId }
             |e| { format!("Hermes gas price is lower than the minimum gas price set by node operator'{}'", e.chain_id) },
 
+        ChainIdChanged
+            {
+                chain_id: ChainId,
+                successor: ChainId,
+            }
+            |e| {
+                format_args!(
+                    "chain '{0}' now reports its configured successor chain id '{1}', \
+                    the chain must be reconfigured: set the `id` of the chain to '{1}' and remove \
+                    its `successor_chain_id` in the configuration, then recreate the clients of \
+                    the chain on its counterparty chains, eg. with `hermes create client \
+                    --host-chain <COUNTERPARTY_CHAIN_ID> --reference-chain {1}`",
+                    e.chain_id, e.successor
+                )
+            },
+
+        ChainIdMismatch
+            {
+                chain_id: ChainId,
+                network: String,
+            }
+            |e| {
+                format_args!(
+                    "transactions to chain '{0}' are rejected because its node reports chain id \
+                    '{1}', the submission of transactions to the chain is halted: check that the \
+                    node configured for the chain is a node of '{0}', or if the chain id of the \
+                    chain changed, set the `id` of the chain to '{1}' in the configuration, \
+                    then restart Hermes and recreate the clients of the chain on its \
+                    counterparty chains",
+                    e.chain_id, e.network
+                )
+            },
+
+        SubmitDelayNotSupported
+            { chain_id: ChainId }
+            |e| {
+                format_args!("delaying the submission of transactions is not supported for chain '{}'",
+                    e.chain_id)
+            },
+
         TxIndexingDisabled
             { chain_id: ChainId }
             |e| {
@@ -571,6 +676,31 @@ define_error! {
                     "Query/DenomTrace RPC returned an empty denom trace for trace hash: {}", e.hash)
             },
 
+        InsufficientFeeDenomBalance
+            {
+                chain_id: ChainId,
+                address: String,
+                denom: String,
+                balance: u128,
+                required: u128,
+            }
+            |e| {
+                format!("balance {}{} of account {} on chain {} does not cover the maximum fee of {}{}",
+                    e.balance, e.denom, e.address, e.chain_id, e.required, e.denom)
+            },
+
+        InsufficientFeeDenomsBalance
+            {
+                chain_id: ChainId,
+                address: String,
+                denoms: Vec<String>,
+            }
+            |e| {
+                format!("no balance of account {} on chain {} covers the maximum fee \
+                    in any of the fee denominations [{}]",
+                    e.address, e.chain_id, e.denoms.join(", "))
+            },
+
         MessageTooBigForTx
             { len: usize }
             |e| {
@@ -672,6 +802,49 @@ impl Error {
             _ => false,
         }
     }
+
+    /// Whether this error is due to a query response missing the requested proof,
+    /// which is usually transient and worth retrying.
+    pub fn is_empty_response_proof_error(&self) -> bool {
+        matches!(self.detail(), ErrorDetail::EmptyResponseProof(_))
+    }
+
+    /// Whether this error is due to the node having pruned the state at the queried height.
+    pub fn is_pruned_state_error(&self) -> bool {
+        self.detail().is_pruned_state_error()
+    }
+
+    /// Whether this error is due to the node reporting that its transaction indexing
+    /// is disabled, in which case transactions cannot be searched by their hash.
+    pub fn is_tx_indexing_disabled_error(&self) -> bool {
+        match self.detail() {
+            ErrorDetail::TxIndexingDisabled(_) => true,
+            ErrorDetail::Rpc(e) => match &e.source {
+                TendermintRpcErrorDetail::Response(e) => {
+                    let response = &e.source;
+
+                    response.message().contains("indexing is disabled")
+                        || response
+                            .data()
+                            .is_some_and(|data| data.contains("indexing is disabled"))
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+impl ErrorDetail {
+    /// Whether this error is due to the node having pruned the state at the queried height,
+    /// which it reports even if it still stores the block at that height.
+    pub fn is_pruned_state_error(&self) -> bool {
+        match self {
+            ErrorDetail::AbciQuery(e) => is_pruned_state_message(&e.query.log),
+            ErrorDetail::GrpcStatus(e) => is_pruned_state_message(e.status.message()),
+            _ => false,
+        }
+    }
 }
 
 impl GrpcStatusSubdetail {
@@ -756,6 +929,14 @@ impl GrpcStatusSubdetail {
     }
 }
 
+/// Whether the given error message reports that the state at the queried height was pruned,
+/// eg. "failed to load state at height 10; version does not exist (latest height: 200)"
+/// returned by cosmos-sdk, or "height 10 is not available, lowest height is 100".
+fn is_pruned_state_message(message: &str) -> bool {
+    message.contains("version does not exist")
+        || message.contains("is not available, lowest height is")
+}
+
 /// Assumes that the cosmos-sdk account sequence mismatch error message, that may be seen
 /// during simulating or broadcasting a transaction, includes the following pattern:
 /// "account sequence mismatch, expected E, got G".
@@ -842,4 +1023,25 @@ mod tests {
             )
         }
     }
+
+    #[test]
+    fn pruned_state_errors_are_detected() {
+        let abci_query_error = |log: &str| {
+            Error::abci_query(AbciQuery {
+                code: tendermint::abci::Code::from(1),
+                log: log.to_owned(),
+                ..Default::default()
+            })
+        };
+
+        assert!(abci_query_error(
+            "failed to load state at height 10; version does not exist (latest height: 200)"
+        )
+        .is_pruned_state_error());
+        assert!(
+            abci_query_error("height 10 is not available, lowest height is 100")
+                .is_pruned_state_error()
+        );
+        assert!(!abci_query_error("unknown query path").is_pruned_state_error());
+    }
 }
@@ -29,6 +29,7 @@ use ibc_relayer_types::{
             packet::{Packet, Sequence},
             timeout::TimeoutHeight,
         },
+        ics24_host::identifier::{ChannelId, PortId},
     },
     events::{Error as IbcEventError, IbcEvent, IbcEventType},
     timestamp::Timestamp,
@@ -58,6 +59,50 @@ impl IbcEventWithHeight {
     }
 }
 
+/// An ICS-29 fee distribution, along with the channel
+/// of the packet for which the fee was distributed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChannelFeeDistribution<'a> {
+    pub port_id: &'a PortId,
+    pub channel_id: &'a ChannelId,
+    pub distribution: &'a DistributeFeePacket,
+}
+
+/// Pairs every fee distribution event with the channel of the packet it was emitted for.
+///
+/// The fee middleware distributes the fees of a packet when its acknowledgement
+/// or timeout is processed, hence `distribute_fee` events follow the
+/// `acknowledge_packet` or `timeout_packet` event of the packet they pay for.
+/// Distribution events which do not follow such an event are ignored.
+pub fn fee_distributions_per_channel(
+    events: &[IbcEventWithHeight],
+) -> Vec<ChannelFeeDistribution<'_>> {
+    let mut last_packet = None;
+    let mut distributions = vec![];
+
+    for event_with_height in events {
+        match &event_with_height.event {
+            IbcEvent::AcknowledgePacket(channel_events::AcknowledgePacket { packet })
+            | IbcEvent::TimeoutPacket(channel_events::TimeoutPacket { packet })
+            | IbcEvent::TimeoutOnClosePacket(channel_events::TimeoutOnClosePacket { packet }) => {
+                last_packet = Some(packet);
+            }
+            IbcEvent::DistributeFeePacket(distribution) => {
+                if let Some(packet) = last_packet {
+                    distributions.push(ChannelFeeDistribution {
+                        port_id: &packet.source_port,
+                        channel_id: &packet.source_channel,
+                        distribution,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    distributions
+}
+
 impl Display for IbcEventWithHeight {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
         write!(f, "{} at height {}", self.event, self.height)
@@ -744,4 +789,53 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn fee_distributions_are_paired_with_channel() {
+        use ibc_relayer_types::applications::ics29_fee::events::DistributionType;
+
+        let height = Height::new(0, 10).unwrap();
+
+        let packet = Packet {
+            source_port: "transfer".parse().unwrap(),
+            source_channel: "channel-0".parse().unwrap(),
+            ..Packet::default()
+        };
+
+        let distribution = |distribution_type| DistributeFeePacket {
+            receiver: "cosmos1relayer".parse().unwrap(),
+            fee: "100uatom".parse().unwrap(),
+            distribution_type,
+        };
+
+        let reward = distribution(DistributionType::Reward);
+        let refund = distribution(DistributionType::Refund);
+
+        // Round-trip through the ABCI event to ensure the fee amount is preserved
+        let abci_event = AbciEvent::from(reward.clone());
+        match ibc_event_try_from_abci_event(&abci_event) {
+            Ok(IbcEvent::DistributeFeePacket(e)) => assert_eq!(e, reward),
+            _ => panic!("converted event was wrong"),
+        }
+
+        let events = [
+            IbcEvent::DistributeFeePacket(refund.clone()),
+            IbcEvent::AcknowledgePacket(channel_events::AcknowledgePacket { packet }),
+            IbcEvent::DistributeFeePacket(reward.clone()),
+            IbcEvent::DistributeFeePacket(refund.clone()),
+        ]
+        .into_iter()
+        .map(|event| IbcEventWithHeight::new(event, height))
+        .collect::<Vec<_>>();
+
+        let distributions = fee_distributions_per_channel(&events);
+
+        assert_eq!(distributions.len(), 2);
+
+        for (fee, expected) in distributions.iter().zip([&reward, &refund]) {
+            assert_eq!(fee.port_id.as_str(), "transfer");
+            assert_eq!(fee.channel_id.as_str(), "channel-0");
+            assert_eq!(fee.distribution, expected);
+        }
+    }
 }
@@ -45,6 +45,19 @@ define_error! {
             [ TraceError<RpcError> ]
             |_| { "subscription cancelled" },
 
+        SubscriptionsEnded
+            |_| { "all the WebSocket subscriptions ended" },
+
+        ResyncNeeded
+            { chain_id: ChainId }
+            |e| {
+                format!(
+                    "events of chain {0} may have been missed while disconnected \
+                     from its WebSocket endpoint, they must be cleared",
+                    e.chain_id
+                )
+            },
+
         Rpc
             [ TraceError<RpcError> ]
             |_| { "RPC error" },
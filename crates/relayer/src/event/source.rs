@@ -1,13 +1,14 @@
 pub mod rpc;
 pub mod websocket;
 
-use std::{sync::Arc, time::Duration};
+use std::{path::PathBuf, sync::Arc, time::Duration};
 
 use crossbeam_channel as channel;
 
 use futures::Stream;
 use tendermint_rpc::{
-    client::CompatMode, event::Event as RpcEvent, Error as RpcError, HttpClient, WebSocketClientUrl,
+    client::CompatMode, event::Event as RpcEvent, query::Query, Error as RpcError,
+    WebSocketClientUrl,
 };
 use tokio::runtime::Runtime as TokioRuntime;
 
@@ -16,8 +17,10 @@ use ibc_relayer_types::{
 };
 
 pub use super::error::{Error, ErrorDetail};
+pub use websocket::ReconnectBackoff;
 
 use super::IbcEventWithHeight;
+use crate::chain::cosmos::request_rate::PacedRpcClient;
 use crate::chain::{handle::Subscription, tracking::TrackingId};
 
 pub type Result<T> = core::result::Result<T, Error>;
@@ -33,10 +36,19 @@ impl EventSource {
         ws_url: WebSocketClientUrl,
         rpc_compat: CompatMode,
         batch_delay: Duration,
+        tx_queries: Vec<Query>,
+        reconnect_backoff: ReconnectBackoff,
         rt: Arc<TokioRuntime>,
     ) -> Result<(Self, TxEventSourceCmd)> {
-        let (mut source, tx) =
-            websocket::EventSource::new(chain_id, ws_url, rpc_compat, batch_delay, rt)?;
+        let (mut source, tx) = websocket::EventSource::new(
+            chain_id,
+            ws_url,
+            rpc_compat,
+            batch_delay,
+            tx_queries,
+            reconnect_backoff,
+            rt,
+        )?;
 
         source.init_subscriptions()?;
 
@@ -45,13 +57,20 @@ impl EventSource {
 
     pub fn rpc(
         chain_id: ChainId,
-        rpc_client: HttpClient,
+        rpc_client: PacedRpcClient,
         poll_interval: Duration,
         max_retries: u32,
+        height_path: Option<PathBuf>,
         rt: Arc<TokioRuntime>,
     ) -> Result<(Self, TxEventSourceCmd)> {
-        let (source, tx) =
-            rpc::EventSource::new(chain_id, rpc_client, poll_interval, max_retries, rt)?;
+        let (source, tx) = rpc::EventSource::new(
+            chain_id,
+            rpc_client,
+            poll_interval,
+            max_retries,
+            height_path,
+            rt,
+        )?;
         Ok((Self::Rpc(source), tx))
     }
 
@@ -108,7 +127,8 @@ pub enum EventSourceCmd {
 
 // TODO: These are SDK specific, should be eventually moved.
 pub mod queries {
-    use tendermint_rpc::query::{EventType, Query};
+    use tendermint_rpc::event::Event as RpcEvent;
+    use tendermint_rpc::query::{Condition, EventType, Operand, Operation, Query};
 
     pub fn all() -> Vec<Query> {
         // Note: Tendermint-go supports max 5 query specifiers!
@@ -123,6 +143,56 @@ pub mod queries {
         ]
     }
 
+    /// The queries to subscribe to given the configured transaction queries,
+    /// ie. `NewBlock` along with these queries, or [`all`] if there are none.
+    pub fn with_tx_queries(tx_queries: &[Query]) -> Vec<Query> {
+        if tx_queries.is_empty() {
+            return all();
+        }
+
+        core::iter::once(new_block())
+            .chain(tx_queries.iter().cloned())
+            .collect()
+    }
+
+    /// Whether the given event matches the query.
+    ///
+    /// Only the event type and the conditions on string values, ie. `=`, `CONTAINS`
+    /// and `EXISTS`, are checked, any other condition is assumed to match.
+    pub fn matches(query: &Query, event: &RpcEvent) -> bool {
+        if query
+            .event_type
+            .as_ref()
+            .is_some_and(|event_type| event.event_type().as_ref() != Some(event_type))
+        {
+            return false;
+        }
+
+        let Some(attributes) = &event.events else {
+            return true;
+        };
+
+        query.conditions.iter().all(|condition| {
+            let values = attributes.get(&condition.key);
+
+            match condition {
+                Condition {
+                    operation: Operation::Exists,
+                    ..
+                } => values.is_some(),
+                Condition {
+                    operation: Operation::Eq(Operand::String(operand)),
+                    ..
+                } => values.is_some_and(|values| values.contains(operand)),
+                Condition {
+                    operation: Operation::Contains(operand),
+                    ..
+                } => values.is_some_and(|values| values.iter().any(|v| v.contains(operand))),
+                _ => true,
+            }
+        })
+    }
+
     pub fn new_block() -> Query {
         Query::from(EventType::NewBlock)
     }
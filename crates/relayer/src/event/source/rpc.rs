@@ -1,5 +1,8 @@
 pub mod extract;
 
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use crossbeam_channel as channel;
@@ -7,11 +10,10 @@ use tokio::{
     runtime::Runtime as TokioRuntime,
     time::{sleep, Duration, Instant},
 };
-use tracing::{debug, error, error_span, trace};
+use tracing::{debug, error, error_span, trace, warn};
 
 use tendermint::abci;
 use tendermint::block::Height as BlockHeight;
-use tendermint_rpc::{Client, HttpClient};
 
 use ibc_relayer_types::{
     core::{
@@ -21,6 +23,7 @@ use ibc_relayer_types::{
     events::IbcEvent,
 };
 
+use crate::chain::cosmos::request_rate::PacedRpcClient;
 use crate::{
     chain::tracking::TrackingId,
     event::{bus::EventBus, error::ErrorDetail, source::Error, IbcEventWithHeight},
@@ -34,13 +37,24 @@ use self::extract::extract_events;
 
 pub type Result<T> = core::result::Result<T, Error>;
 
+/// The file in which the height of the last block processed on the given chain is kept,
+/// so that the event source of the chain resumes right after it once the relayer is
+/// restarted, instead of missing the blocks produced in the meantime.
+pub fn last_processed_height_path(chain_id: &ChainId) -> Option<PathBuf> {
+    dirs_next::home_dir().map(|home| {
+        home.join(".hermes/event_source")
+            .join(chain_id.to_string())
+            .join("last_processed_height")
+    })
+}
+
 /// An RPC endpoint that serves as a source of events for a given chain.
 pub struct EventSource {
     /// Chain identifier
     chain_id: ChainId,
 
     /// RPC client
-    rpc_client: HttpClient,
+    rpc_client: PacedRpcClient,
 
     /// Poll interval
     poll_interval: Duration,
@@ -59,14 +73,18 @@ pub struct EventSource {
 
     /// Last fetched block height
     last_fetched_height: BlockHeight,
+
+    /// File in which the last processed block height is kept across restarts, if any
+    height_path: Option<PathBuf>,
 }
 
 impl EventSource {
     pub fn new(
         chain_id: ChainId,
-        rpc_client: HttpClient,
+        rpc_client: PacedRpcClient,
         poll_interval: Duration,
         max_retries: u32,
+        height_path: Option<PathBuf>,
         rt: Arc<TokioRuntime>,
     ) -> Result<(Self, TxEventSourceCmd)> {
         let event_bus = EventBus::new();
@@ -81,6 +99,7 @@ impl EventSource {
             event_bus,
             rx_cmd,
             last_fetched_height: BlockHeight::from(0_u32),
+            height_path,
         };
 
         Ok((source, TxEventSourceCmd(tx_cmd)))
@@ -96,9 +115,25 @@ impl EventSource {
         rt.block_on(async {
             let mut backoff = poll_backoff(self.poll_interval);
 
-            // Initialize the latest fetched height
-            if let Ok(latest_height) = latest_height(&self.rpc_client).await {
-                self.last_fetched_height = latest_height;
+            // Resume after the last block processed before the relayer was restarted, if any,
+            // otherwise start from the latest height
+            match (
+                self.load_processed_height(),
+                latest_height(&self.rpc_client).await,
+            ) {
+                (Some(processed), Ok(latest)) if processed > latest => {
+                    warn!(
+                        "last processed height {processed} is past the latest height {latest}, \
+                         starting from the latter"
+                    );
+                    self.last_fetched_height = latest;
+                }
+                (Some(processed), _) => {
+                    debug!("resuming after the last processed height {processed}");
+                    self.last_fetched_height = processed;
+                }
+                (None, Ok(latest)) => self.last_fetched_height = latest,
+                (None, Err(_)) => {}
             }
 
             // Continuously run the event loop, so that when it aborts
@@ -147,6 +182,12 @@ impl EventSource {
 
         let latest_height = latest_height(&self.rpc_client).await?;
 
+        // The latest height could not be queried on start, start from it now
+        // rather than from the first block of the chain
+        if self.last_fetched_height.value() == 0 {
+            self.last_fetched_height = latest_height;
+        }
+
         let batches = if latest_height > self.last_fetched_height {
             trace!(
                 "latest height ({latest_height}) > latest fetched height ({})",
@@ -173,8 +214,12 @@ impl EventSource {
             return Ok(Next::Abort);
         }
 
-        for batch in batches.unwrap_or_default() {
-            self.broadcast_batch(batch);
+        if let Some(batches) = batches {
+            for batch in batches {
+                self.broadcast_batch(batch);
+            }
+
+            self.save_processed_height();
         }
 
         Ok(Next::Continue)
@@ -261,6 +306,51 @@ impl EventSource {
 
         self.event_bus.broadcast(Arc::new(Ok(batch)));
     }
+
+    /// The height of the last block processed before the relayer was restarted, if any.
+    fn load_processed_height(&self) -> Option<BlockHeight> {
+        let path = self.height_path.as_ref()?;
+
+        let height = match fs::read_to_string(path) {
+            Ok(height) => height,
+            Err(e) if e.kind() == ErrorKind::NotFound => return None,
+            Err(e) => {
+                warn!(
+                    "failed to read the last processed height from {}: {e}",
+                    path.display()
+                );
+                return None;
+            }
+        };
+
+        match height.trim().parse() {
+            Ok(height) => Some(height),
+            Err(e) => {
+                warn!("invalid last processed height in {}: {e}", path.display());
+                None
+            }
+        }
+    }
+
+    /// Keep the height of the last processed block, so as to resume right after it
+    /// once the relayer is restarted.
+    fn save_processed_height(&self) {
+        let Some(path) = &self.height_path else {
+            return;
+        };
+
+        let result = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::write(path, self.last_fetched_height.to_string()));
+
+        if let Err(e) = result {
+            warn!(
+                "failed to save the last processed height to {}: {e}",
+                path.display()
+            );
+        }
+    }
 }
 
 fn poll_backoff(poll_interval: Duration) -> impl Iterator<Item = Duration> {
@@ -322,7 +412,7 @@ fn dedupe(events: Vec<abci::Event>) -> Vec<abci::Event> {
 
 /// Collect the IBC events from an RPC event
 async fn collect_events(
-    rpc_client: &HttpClient,
+    rpc_client: &PacedRpcClient,
     chain_id: &ChainId,
     latest_block_height: BlockHeight,
 ) -> Result<Option<EventBatch>> {
@@ -356,7 +446,7 @@ async fn collect_events(
 }
 
 async fn fetch_all_events(
-    rpc_client: &HttpClient,
+    rpc_client: &PacedRpcClient,
     height: BlockHeight,
 ) -> Result<Vec<abci::Event>> {
     let mut response = rpc_client.block_results(height).await.map_err(Error::rpc)?;
@@ -386,7 +476,7 @@ async fn fetch_all_events(
     Ok(events)
 }
 
-async fn latest_height(rpc_client: &HttpClient) -> Result<BlockHeight> {
+async fn latest_height(rpc_client: &PacedRpcClient) -> Result<BlockHeight> {
     rpc_client
         .abci_info()
         .await
@@ -433,3 +523,137 @@ impl Iterator for HeightRangeInclusive {
 }
 
 impl ExactSizeIterator for HeightRangeInclusive {}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::mpsc;
+    use std::thread;
+
+    use tendermint_rpc::HttpClient;
+
+    use super::*;
+    use crate::util::mock_http::{spawn_mock_http_server, MockResponse};
+
+    /// A node whose latest height is the one set by the test, and which forwards
+    /// the height of each block whose results are requested.
+    fn spawn_node(latest_height: Arc<AtomicU64>) -> (String, mpsc::Receiver<u64>) {
+        let (address, requests) = spawn_mock_http_server(move |request| {
+            let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+            let latest_height = latest_height.load(Ordering::SeqCst);
+
+            let result = match body["method"].as_str().unwrap() {
+                "abci_info" => serde_json::json!({
+                    "response": {
+                        "data": "node",
+                        "version": "1",
+                        "app_version": "1",
+                        "last_block_height": latest_height.to_string(),
+                        "last_block_app_hash": "",
+                    }
+                }),
+                "block_results" => serde_json::json!({
+                    "height": body["params"]["height"],
+                    "txs_results": null,
+                    "begin_block_events": null,
+                    "end_block_events": null,
+                    "finalize_block_events": [],
+                    "validator_updates": [],
+                    "consensus_param_updates": null,
+                    "app_hash": "",
+                }),
+                method => panic!("unexpected request: {method}"),
+            };
+
+            MockResponse::json(
+                serde_json::json!({ "jsonrpc": "2.0", "id": body["id"], "result": result })
+                    .to_string(),
+            )
+        });
+
+        let (sender, heights) = mpsc::channel();
+        thread::spawn(move || {
+            for request in requests {
+                let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+                if body["method"] == "block_results" {
+                    let height = body["params"]["height"].as_str().unwrap();
+                    let _ = sender.send(height.parse().unwrap());
+                }
+            }
+        });
+
+        (format!("http://{address}"), heights)
+    }
+
+    /// Runs an event source until it broadcast the batches up to the given height,
+    /// returning the heights of the batches.
+    fn poll_until(url: &str, height_path: &std::path::Path, until: u64) -> Vec<u64> {
+        let chain_id = ChainId::from_string("ibc-0");
+        let rpc_client = PacedRpcClient::new(HttpClient::new(url).unwrap(), None);
+        let rt = Arc::new(TokioRuntime::new().unwrap());
+
+        let (source, tx_cmd) = EventSource::new(
+            chain_id,
+            rpc_client,
+            Duration::from_millis(10),
+            1,
+            Some(height_path.to_path_buf()),
+            rt,
+        )
+        .unwrap();
+
+        let handle = thread::spawn(move || source.run());
+        let batches = tx_cmd.subscribe().unwrap();
+
+        let mut heights = vec![];
+        while heights.last() != Some(&until) {
+            let batch = batches.recv_timeout(Duration::from_secs(10)).unwrap();
+            heights.push(batch.as_ref().as_ref().unwrap().height.revision_height());
+        }
+
+        tx_cmd.shutdown().unwrap();
+        handle.join().unwrap();
+
+        heights
+    }
+
+    #[test]
+    fn blocks_produced_while_stopped_are_polled_once_restarted() {
+        let latest_height = Arc::new(AtomicU64::new(7));
+        let (url, requested) = spawn_node(latest_height.clone());
+
+        let height_path = std::env::temp_dir()
+            .join(format!("hermes-pull-{}", std::process::id()))
+            .join("last_processed_height");
+        let _ = fs::remove_file(&height_path);
+
+        // Without a processed height, the event source starts from the latest height
+        let source = thread::spawn({
+            let (url, height_path) = (url.clone(), height_path.clone());
+            move || poll_until(&url, &height_path, 8)
+        });
+        thread::sleep(Duration::from_millis(200));
+        latest_height.store(8, Ordering::SeqCst);
+
+        assert_eq!(source.join().unwrap(), [8]);
+        assert_eq!(fs::read_to_string(&height_path).unwrap(), "8");
+
+        // The blocks produced while the relayer is stopped are polled once it is restarted
+        latest_height.store(11, Ordering::SeqCst);
+        assert_eq!(poll_until(&url, &height_path, 11), [9, 10, 11]);
+        assert_eq!(fs::read_to_string(&height_path).unwrap(), "11");
+
+        assert_eq!(requested.try_iter().collect::<Vec<_>>(), [8, 9, 10, 11]);
+
+        // Unless the chain was reset since
+        latest_height.store(3, Ordering::SeqCst);
+        let source = thread::spawn({
+            let (url, height_path) = (url.clone(), height_path.clone());
+            move || poll_until(&url, &height_path, 4)
+        });
+        thread::sleep(Duration::from_millis(200));
+        latest_height.store(4, Ordering::SeqCst);
+
+        assert_eq!(source.join().unwrap(), [4]);
+    }
+}
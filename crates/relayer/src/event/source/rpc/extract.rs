@@ -1,13 +1,16 @@
 use tendermint::abci;
+use tracing::warn;
 
 use ibc_relayer_types::applications::ics29_fee::events::DistributionType;
 use ibc_relayer_types::core::ics02_client::height::Height;
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
-use ibc_relayer_types::events::IbcEvent;
+use ibc_relayer_types::events::{ErrorDetail as IbcEventErrorDetail, IbcEvent};
 
 use crate::telemetry;
 
-use crate::event::{ibc_event_try_from_abci_event, IbcEventWithHeight};
+use crate::event::{
+    fee_distributions_per_channel, ibc_event_try_from_abci_event, IbcEventWithHeight,
+};
 
 pub fn extract_events(
     _chain_id: &ChainId,
@@ -24,12 +27,39 @@ pub fn extract_events(
                     if let DistributionType::Reward = dist.distribution_type {
                         telemetry!(fees_amount, _chain_id, &dist.receiver, dist.fee.clone());
                     }
-                } else {
-                    events_with_height.push(IbcEventWithHeight { height, event });
                 }
+
+                events_with_height.push(IbcEventWithHeight { height, event });
+            }
+
+            Ok(_) => {}
+
+            // Events which are not IBC events are expected and ignored
+            Err(e) if matches!(e.detail(), IbcEventErrorDetail::UnsupportedAbciEvent(_)) => {}
+
+            // A malformed IBC event must not prevent the other events
+            // of the block from being delivered, so skip it
+            Err(e) => {
+                warn!(
+                    %height,
+                    "skipping malformed `{}` event: {e}",
+                    abci_event.kind
+                );
             }
+        }
+    }
 
-            _ => {}
+    for fee in fee_distributions_per_channel(&events_with_height) {
+        // Only record rewarded fees
+        if let DistributionType::Reward = fee.distribution.distribution_type {
+            telemetry!(
+                channel_fees_amount,
+                _chain_id,
+                fee.channel_id,
+                fee.port_id,
+                &fee.distribution.receiver,
+                fee.distribution.fee.clone()
+            );
         }
     }
 
@@ -86,6 +116,12 @@ fn event_is_type_channel(ev: &IbcEvent) -> bool {
             | IbcEvent::OpenConfirmChannel(_)
             | IbcEvent::CloseInitChannel(_)
             | IbcEvent::CloseConfirmChannel(_)
+            | IbcEvent::UpgradeInitChannel(_)
+            | IbcEvent::UpgradeTryChannel(_)
+            | IbcEvent::UpgradeAckChannel(_)
+            | IbcEvent::UpgradeConfirmChannel(_)
+            | IbcEvent::UpgradeOpenChannel(_)
+            | IbcEvent::UpgradeErrorChannel(_)
     )
 }
 
@@ -99,3 +135,134 @@ fn event_is_type_fee(ev: &IbcEvent) -> bool {
         IbcEvent::IncentivizedPacket(_) | IbcEvent::DistributeFeePacket(_)
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::BTreeMap;
+
+    use ibc_relayer_types::core::ics02_client::client_type::ClientType;
+    use ibc_relayer_types::core::ics02_client::events::{Attributes, CreateClient};
+    use ibc_relayer_types::core::ics04_channel::events::OpenInit;
+    use ibc_relayer_types::events::IbcEventType;
+    use tendermint_rpc::event::{Event as RpcEvent, EventData, TxInfo, TxResult};
+
+    use crate::event::source::queries;
+    use crate::event::source::websocket::extract::extract_events as extract_pushed_events;
+
+    fn create_client_event(client_id: &str) -> abci::Event {
+        abci::Event::from(CreateClient::from(Attributes {
+            client_id: client_id.parse().unwrap(),
+            client_type: ClientType::Tendermint,
+            consensus_height: Height::new(0, 10).unwrap(),
+        }))
+    }
+
+    #[test]
+    fn malformed_event_does_not_abort_block() {
+        let chain_id = ChainId::from_string("ibc-0");
+        let height = Height::new(0, 42).unwrap();
+
+        // A `create_client` event with an unparsable consensus height
+        let malformed = abci::Event::new(
+            IbcEventType::CreateClient.as_str(),
+            [("consensus_height", "not-a-height")],
+        );
+
+        let events = vec![
+            create_client_event("07-tendermint-0"),
+            malformed,
+            abci::Event::new("transfer", Vec::<abci::EventAttribute>::new()),
+            create_client_event("07-tendermint-1"),
+            create_client_event("07-tendermint-2"),
+        ];
+
+        let extracted = extract_events(&chain_id, height, &events).unwrap();
+
+        let client_ids: Vec<_> = extracted
+            .iter()
+            .map(|ev| match &ev.event {
+                IbcEvent::CreateClient(e) => e.client_id().to_string(),
+                e => panic!("unexpected event: {e:?}"),
+            })
+            .collect();
+
+        assert_eq!(
+            client_ids,
+            ["07-tendermint-0", "07-tendermint-1", "07-tendermint-2"]
+        );
+        assert!(extracted.iter().all(|ev| ev.height == height));
+    }
+
+    /// The events extracted by the push-based event source from the given transaction
+    /// events, as pushed once for each of the subscriptions they match.
+    fn pushed_events(
+        chain_id: &ChainId,
+        height: Height,
+        events: &[abci::Event],
+    ) -> Vec<IbcEventWithHeight> {
+        queries::all()
+            .into_iter()
+            .flat_map(|query| {
+                let rpc_event = RpcEvent {
+                    query: query.to_string(),
+                    data: EventData::Tx {
+                        tx_result: TxInfo {
+                            height: height.revision_height() as i64,
+                            index: Some(0),
+                            tx: vec![],
+                            result: TxResult {
+                                log: None,
+                                gas_wanted: None,
+                                gas_used: None,
+                                events: events.to_vec(),
+                            },
+                        },
+                    },
+                    events: Some(BTreeMap::new()),
+                };
+
+                extract_pushed_events(chain_id, rpc_event).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn pulled_events_are_the_pushed_ones() {
+        let chain_id = ChainId::from_string("ibc-0");
+        let height = Height::new(0, 42).unwrap();
+
+        let channel_open_init = abci::Event::from(OpenInit {
+            port_id: "transfer".parse().unwrap(),
+            channel_id: Some("channel-0".parse().unwrap()),
+            connection_id: "connection-0".parse().unwrap(),
+            counterparty_port_id: "transfer".parse().unwrap(),
+            counterparty_channel_id: None,
+        });
+
+        let channel_upgrade_init = abci::Event::new(
+            IbcEventType::UpgradeInitChannel.as_str(),
+            [
+                ("port_id", "transfer"),
+                ("channel_id", "channel-0"),
+                ("counterparty_port_id", "transfer"),
+                ("counterparty_channel_id", "channel-1"),
+                ("upgrade_sequence", "1"),
+            ],
+        );
+
+        let events = vec![
+            create_client_event("07-tendermint-0"),
+            abci::Event::new("transfer", Vec::<abci::EventAttribute>::new()),
+            channel_open_init,
+            channel_upgrade_init,
+        ];
+
+        let pulled = extract_events(&chain_id, height, &events).unwrap();
+        let pushed = pushed_events(&chain_id, height, &events);
+
+        assert_eq!(pulled.len(), 3);
+        assert_eq!(pulled, pushed);
+    }
+}
@@ -2,21 +2,24 @@ pub mod extract;
 
 use alloc::sync::Arc;
 use core::cmp::Ordering;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use crossbeam_channel as channel;
 use futures::{
-    pin_mut,
+    future, pin_mut,
     stream::{self, select_all, StreamExt},
     Stream, TryStreamExt,
 };
 use tokio::task::JoinHandle;
 use tokio::{runtime::Runtime as TokioRuntime, sync::mpsc};
-use tracing::{debug, error, info, instrument, trace};
+use tracing::{debug, error, info, instrument, trace, warn};
 
 use tendermint_rpc::{
-    client::CompatMode, event::Event as RpcEvent, query::Query, SubscriptionClient,
-    WebSocketClient, WebSocketClientDriver, WebSocketClientUrl,
+    client::CompatMode,
+    event::Event as RpcEvent,
+    query::{EventType, Query},
+    Error as RpcError, SubscriptionClient, WebSocketClient, WebSocketClientDriver,
+    WebSocketClientUrl,
 };
 
 use ibc_relayer_types::{core::ics24_host::identifier::ChainId, events::IbcEvent};
@@ -31,7 +34,10 @@ use crate::{
     },
 };
 
-use super::{EventBatch, EventSourceCmd, Result, SubscriptionStream, TxEventSourceCmd};
+use super::{
+    queries, EventBatch, EventSourceCmd, Result, SubscriptionResult, SubscriptionStream,
+    TxEventSourceCmd,
+};
 
 use self::extract::extract_events;
 
@@ -50,6 +56,84 @@ mod retry_strategy {
     }
 }
 
+/// Tracks the disconnects from a WebSocket endpoint, so that an endpoint
+/// which keeps dropping the connection shortly after being reconnected to
+/// is reconnected to after an increasing delay, capped at a maximum.
+///
+/// A connection which stays up for longer than the maximum delay is
+/// considered stable, and the next disconnect is reconnected immediately.
+#[derive(Clone, Debug)]
+pub struct ReconnectBackoff {
+    /// Delay before the first reconnect in a disconnect storm
+    initial: Duration,
+    /// Maximum delay before reconnecting
+    max: Duration,
+    /// Delay before the latest reconnect
+    delay: Duration,
+    /// When the connection was last reestablished
+    reconnected_at: Option<Instant>,
+    /// Number of disconnects since the connection was last stable
+    storm_disconnects: u64,
+    /// Total number of reconnects
+    reconnects: u64,
+}
+
+impl ReconnectBackoff {
+    pub fn new(initial: Duration, max: Duration) -> Self {
+        Self {
+            initial: initial.min(max),
+            max,
+            delay: Duration::ZERO,
+            reconnected_at: None,
+            storm_disconnects: 0,
+            reconnects: 0,
+        }
+    }
+
+    /// Whether a disconnect at the given time is part of a disconnect storm,
+    /// ie. whether the connection was reestablished less than the maximum delay ago.
+    pub fn is_storm(&self, now: Instant) -> bool {
+        self.reconnected_at
+            .is_some_and(|at| now.saturating_duration_since(at) < self.max)
+    }
+
+    /// Records a disconnect at the given time, and returns the delay
+    /// to wait for before reconnecting to the endpoint.
+    pub fn disconnected(&mut self, now: Instant) -> Duration {
+        if self.is_storm(now) {
+            self.storm_disconnects += 1;
+            self.delay = (self.delay * 2).clamp(self.initial, self.max);
+        } else {
+            self.storm_disconnects = 1;
+            self.delay = Duration::ZERO;
+        }
+
+        self.reconnects += 1;
+        self.reconnected_at = Some(now + self.delay);
+
+        self.delay
+    }
+
+    /// Number of disconnects since the connection was last stable.
+    pub fn storm_disconnects(&self) -> u64 {
+        self.storm_disconnects
+    }
+
+    /// Total number of reconnects to the endpoint.
+    pub fn reconnects(&self) -> u64 {
+        self.reconnects
+    }
+}
+
+impl Default for ReconnectBackoff {
+    fn default() -> Self {
+        Self::new(
+            crate::config::default::reconnect_backoff(),
+            crate::config::default::max_reconnect_backoff(),
+        )
+    }
+}
+
 /// A batch of events received from a WebSocket endpoint from a
 /// chain at a specific height.
 ///
@@ -60,6 +144,10 @@ mod retry_strategy {
 /// The default events that are queried are:
 /// - [`EventType::NewBlock`](tendermint_rpc::query::EventType::NewBlock)
 /// - [`EventType::Tx`](tendermint_rpc::query::EventType::Tx)
+///
+/// When precise transaction queries are configured, they replace the
+/// default transaction queries, and transactions which do not match
+/// any of them are dropped.
 pub struct EventSource {
     chain_id: ChainId,
     /// Delay until batch is emitted
@@ -82,8 +170,12 @@ pub struct EventSource {
     rpc_compat: CompatMode,
     /// Queries
     event_queries: Vec<Query>,
+    /// Precise transaction queries, if any
+    tx_queries: Vec<Query>,
     /// All subscriptions combined in a single stream
     subscriptions: Box<SubscriptionStream>,
+    /// Backoff for reconnecting to a flapping endpoint
+    reconnect_backoff: ReconnectBackoff,
     /// Tokio runtime
     rt: Arc<TokioRuntime>,
 }
@@ -101,6 +193,8 @@ impl EventSource {
         ws_url: WebSocketClientUrl,
         rpc_compat: CompatMode,
         batch_delay: Duration,
+        tx_queries: Vec<Query>,
+        reconnect_backoff: ReconnectBackoff,
         rt: Arc<TokioRuntime>,
     ) -> Result<(Self, TxEventSourceCmd)> {
         let event_bus = EventBus::new();
@@ -115,8 +209,7 @@ impl EventSource {
         let (tx_err, rx_err) = mpsc::unbounded_channel();
         let driver_handle = rt.spawn(run_driver(driver, tx_err.clone()));
 
-        // TODO: move them to config file(?)
-        let event_queries = super::queries::all();
+        let event_queries = super::queries::with_tx_queries(&tx_queries);
 
         let source = Self {
             rt,
@@ -125,6 +218,7 @@ impl EventSource {
             client,
             driver_handle,
             event_queries,
+            tx_queries,
             event_bus,
             rx_err,
             tx_err,
@@ -132,6 +226,7 @@ impl EventSource {
             ws_url,
             rpc_compat,
             subscriptions: Box::new(stream::empty()),
+            reconnect_backoff,
         };
 
         Ok((source, TxEventSourceCmd(tx_cmd)))
@@ -155,7 +250,7 @@ impl EventSource {
                 .block_on(self.client.subscribe(query.clone()))
                 .map_err(Error::client_subscription_failed)?;
 
-            subscriptions.push(subscription);
+            subscriptions.push(until_ended(query, subscription));
         }
 
         self.subscriptions = Box::new(select_all(subscriptions));
@@ -216,7 +311,12 @@ impl EventSource {
         self.init_subscriptions()
     }
 
-    /// Attempt to reconnect the WebSocket client using the given retry strategy.
+    /// Attempt to reconnect the WebSocket client using the given retry strategy,
+    /// and to subscribe again to all the queries.
+    ///
+    /// Once reconnected, or once the attempts are exhausted during a longer outage,
+    /// notifies the subscribers that the events emitted while disconnected were
+    /// missed, so that they scan the chain for them.
     ///
     /// See the [`retry`](https://docs.rs/retry) crate and the
     /// [`crate::util::retry`] module for more information.
@@ -244,15 +344,24 @@ impl EventSource {
         });
 
         match result {
+            // Only report the first reconnect of a disconnect storm
+            Ok(()) if self.reconnect_backoff.storm_disconnects() > 1 => debug!(
+                "successfully reconnected to WebSocket endpoint {}",
+                self.ws_url
+            ),
             Ok(()) => info!(
                 "successfully reconnected to WebSocket endpoint {}",
                 self.ws_url
             ),
+            // The missed events can still be cleared through the RPC endpoint of the chain,
+            // while the event source keeps trying to reconnect
             Err(e) => error!(
                 "failed to reconnect to {} after {} retries",
                 self.ws_url, e.tries
             ),
         }
+
+        self.propagate_error(Error::resync_needed(self.chain_id.clone()));
     }
 
     /// Event source loop
@@ -277,6 +386,24 @@ impl EventSource {
                 Next::Abort => break,
                 Next::Reconnect => {
                     telemetry!(ws_reconnect, &self.chain_id);
+
+                    let delay = self.reconnect_backoff.disconnected(Instant::now());
+
+                    if !delay.is_zero() {
+                        if self.reconnect_backoff.storm_disconnects() == 2 {
+                            warn!(
+                                "WebSocket endpoint {} is flapping, backing off reconnects for up to {:?}",
+                                self.ws_url, self.reconnect_backoff.max
+                            );
+                        }
+
+                        debug!("waiting {delay:?} before reconnecting");
+
+                        if let Next::Abort = self.wait_for(delay) {
+                            break;
+                        }
+                    }
+
                     self.reconnect();
 
                     continue;
@@ -300,7 +427,12 @@ impl EventSource {
         let subscriptions = core::mem::replace(&mut self.subscriptions, Box::new(stream::empty()));
 
         // Convert the stream of RPC events into a stream of event batches.
-        let batches = stream_batches(subscriptions, self.chain_id.clone(), self.batch_delay);
+        let batches = stream_batches(
+            subscriptions,
+            self.chain_id.clone(),
+            self.tx_queries.clone(),
+            self.batch_delay,
+        );
 
         // Needed to be able to poll the stream
         pin_mut!(batches);
@@ -312,7 +444,9 @@ impl EventSource {
             }
 
             let result = tokio::select! {
-                Some(batch) = batches.next() => batch,
+                batch = batches.next() => {
+                    batch.unwrap_or_else(|| Err(Error::subscriptions_ended()))
+                }
                 Some(e) = self.rx_err.recv() => Err(Error::web_socket_driver(e)),
             };
 
@@ -331,19 +465,29 @@ impl EventSource {
             match result {
                 Ok(batch) => self.broadcast_batch(batch),
                 Err(e) => {
-                    if let ErrorDetail::SubscriptionCancelled(reason) = e.detail() {
-                        error!("subscription cancelled, reason: {}", reason);
-
-                        self.propagate_error(e);
+                    // Only report the first disconnect of a disconnect storm,
+                    // so that a flapping endpoint does not flood the logs.
+                    let in_storm = self.reconnect_backoff.is_storm(Instant::now());
 
-                        // Reconnect to the WebSocket endpoint, and subscribe again to the queries.
-                        return Next::Reconnect;
+                    if let ErrorDetail::SubscriptionCancelled(reason) = e.detail() {
+                        if in_storm {
+                            debug!("subscription cancelled, reason: {}", reason);
+                        } else {
+                            error!("subscription cancelled, reason: {}", reason);
+                        }
+                    } else if in_storm {
+                        debug!("failed to collect events: {}", e);
                     } else {
                         error!("failed to collect events: {}", e);
+                    }
 
-                        // Reconnect to the WebSocket endpoint, and subscribe again to the queries.
-                        return Next::Reconnect;
-                    };
+                    self.propagate_error(e);
+
+                    // Report the disconnect, then reconnect to the WebSocket endpoint, and
+                    // subscribe again to all the queries. The subscribers are told to scan
+                    // the chain for the events missed meanwhile once reconnected, not before,
+                    // lest they miss the events emitted until the subscriptions are back.
+                    return Next::Reconnect;
                 }
             }
         }
@@ -351,11 +495,13 @@ impl EventSource {
 
     /// Propagate error to subscribers.
     ///
-    /// The main use case for propagating RPC errors is for the [`Supervisor`]
+    /// The main use case for propagating errors is for the [`Supervisor`]
     /// to notice that the WebSocket connection or subscription has been closed,
     /// and to trigger a clearing of packets, as this typically means that we have
     /// missed a bunch of events which were emitted after the subscription was closed.
-    /// In that case, this error will be handled in [`Supervisor::handle_batch`].
+    /// In that case, the error is propagated as soon as the connection is closed, and
+    /// a [`ResyncNeeded`](ErrorDetail::ResyncNeeded) error once reconnected, which is
+    /// handled in [`Supervisor::handle_batch`].
     fn propagate_error(&mut self, error: Error) {
         self.event_bus.broadcast(Arc::new(Err(error)));
     }
@@ -378,18 +524,46 @@ impl EventSource {
     /// Process a pending command, if any.
     fn try_process_cmd(&mut self) -> Next {
         if let Ok(cmd) = self.rx_cmd.try_recv() {
-            match cmd {
-                EventSourceCmd::Shutdown => return Next::Abort,
+            return self.process_cmd(cmd);
+        }
 
-                EventSourceCmd::Subscribe(tx) => {
-                    if let Err(e) = tx.send(self.event_bus.subscribe()) {
-                        error!("failed to send back subscription: {e}");
+        Next::Continue
+    }
+
+    /// Wait for the given delay, while still processing the incoming commands.
+    fn wait_for(&mut self, delay: Duration) -> Next {
+        let deadline = Instant::now() + delay;
+
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+
+            match self.rx_cmd.recv_timeout(remaining) {
+                Ok(cmd) => {
+                    if let Next::Abort = self.process_cmd(cmd) {
+                        return Next::Abort;
                     }
                 }
+                Err(channel::RecvTimeoutError::Timeout) => return Next::Continue,
+                Err(channel::RecvTimeoutError::Disconnected) => {
+                    std::thread::sleep(deadline.saturating_duration_since(Instant::now()));
+                    return Next::Continue;
+                }
             }
         }
+    }
 
-        Next::Continue
+    fn process_cmd(&mut self, cmd: EventSourceCmd) -> Next {
+        match cmd {
+            EventSourceCmd::Shutdown => Next::Abort,
+
+            EventSourceCmd::Subscribe(tx) => {
+                if let Err(e) = tx.send(self.event_bus.subscribe()) {
+                    error!("failed to send back subscription: {e}");
+                }
+
+                Next::Continue
+            }
+        }
     }
 }
 
@@ -402,16 +576,42 @@ fn collect_events(
     stream::iter(events).map(Ok)
 }
 
+/// Ends the given subscription to `query` with an error once it ends, eg. when the
+/// node drops it, so that the event source subscribes again to all the queries
+/// instead of silently going on without the events of this one.
+fn until_ended<S>(query: &Query, subscription: S) -> impl Stream<Item = SubscriptionResult>
+where
+    S: Stream<Item = SubscriptionResult>,
+{
+    let ended = RpcError::client_internal(format!("subscription to `{query}` ended"));
+
+    subscription.chain(stream::once(future::ready(Err(ended))))
+}
+
+/// Whether the given RPC event is relevant given the precise transaction queries, if any.
+///
+/// Transactions which do not match any of these queries are dropped,
+/// in case the node pushes more events than what was subscribed to.
+fn is_relevant(tx_queries: &[Query], event: &RpcEvent) -> bool {
+    tx_queries.is_empty()
+        || event.event_type() != Some(EventType::Tx)
+        || tx_queries
+            .iter()
+            .any(|query| queries::matches(query, event))
+}
+
 /// Convert a stream of RPC event into a stream of event batches
 fn stream_batches(
     subscriptions: Box<SubscriptionStream>,
     chain_id: ChainId,
+    tx_queries: Vec<Query>,
     batch_delay: Duration,
 ) -> impl Stream<Item = Result<EventBatch>> {
     let id = chain_id.clone();
 
-    // Collect IBC events from each RPC event
+    // Collect IBC events from each relevant RPC event
     let events = subscriptions
+        .try_filter(move |rpc_event| future::ready(is_relevant(&tx_queries, rpc_event)))
         .map_ok(move |rpc_event| {
             trace!(chain = %id, "received an RPC event: {}", rpc_event.query);
             collect_events(&id, rpc_event)
@@ -467,3 +667,360 @@ pub enum Next {
     Continue,
     Reconnect,
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use tendermint_rpc::event::{Event as RpcEvent, EventData, TxInfo, TxResult};
+    use tendermint_rpc::query::Query;
+
+    use std::time::{Duration, Instant};
+
+    use futures::{pin_mut, stream, StreamExt};
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+    use std::sync::{mpsc, Arc};
+    use std::thread;
+
+    use bitcoin::hashes::{sha1, Hash};
+    use subtle_encoding::base64;
+    use tendermint_rpc::client::CompatMode;
+    use tokio::runtime::Runtime as TokioRuntime;
+
+    use super::{
+        is_relevant, queries, select_all, stream_batches, until_ended, ErrorDetail, EventSource,
+        ReconnectBackoff, SubscriptionResult, SubscriptionStream,
+    };
+
+    /// The GUID which the WebSocket handshake appends to the key of the client.
+    const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+    fn tx_event(action: &str) -> RpcEvent {
+        let events = BTreeMap::from([
+            ("tm.event".to_owned(), vec!["Tx".to_owned()]),
+            ("message.action".to_owned(), vec![action.to_owned()]),
+        ]);
+
+        RpcEvent {
+            query: "tm.event = 'Tx'".to_owned(),
+            data: EventData::Tx {
+                tx_result: TxInfo {
+                    height: 10,
+                    index: Some(0),
+                    tx: vec![],
+                    result: TxResult {
+                        log: None,
+                        gas_wanted: None,
+                        gas_used: None,
+                        events: vec![],
+                    },
+                },
+            },
+            events: Some(events),
+        }
+    }
+
+    const RECV_PACKET: &str = "/ibc.core.channel.v1.MsgRecvPacket";
+    const ACKNOWLEDGEMENT: &str = "/ibc.core.channel.v1.MsgAcknowledgement";
+    const SEND: &str = "/cosmos.bank.v1beta1.MsgSend";
+
+    #[test]
+    fn precise_queries_only_let_matching_events_through() {
+        let tx_queries = vec![
+            Query::eq("message.action", RECV_PACKET),
+            Query::eq("message.action", ACKNOWLEDGEMENT),
+        ];
+
+        let received = [RECV_PACKET, SEND, ACKNOWLEDGEMENT, SEND]
+            .into_iter()
+            .map(tx_event)
+            .filter(|event| is_relevant(&tx_queries, event))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            received,
+            vec![tx_event(RECV_PACKET), tx_event(ACKNOWLEDGEMENT)]
+        );
+    }
+
+    #[test]
+    fn default_queries_let_all_events_through() {
+        assert!(is_relevant(&[], &tx_event(SEND)));
+    }
+
+    #[test]
+    fn precise_queries_subscribe_to_new_blocks() {
+        let tx_queries = vec![Query::eq("message.action", RECV_PACKET)];
+        let queries = super::queries::with_tx_queries(&tx_queries);
+
+        assert_eq!(
+            queries,
+            vec![super::queries::new_block(), tx_queries[0].clone()]
+        );
+        assert_eq!(super::queries::with_tx_queries(&[]), super::queries::all());
+    }
+
+    #[tokio::test]
+    async fn dropped_subscription_ends_the_batches() {
+        let new_blocks: Box<SubscriptionStream> = Box::new(until_ended(
+            &queries::new_block(),
+            stream::pending::<SubscriptionResult>(),
+        ));
+        let txs: Box<SubscriptionStream> = Box::new(until_ended(
+            &queries::ibc_client(),
+            stream::iter([Ok(tx_event(SEND))]),
+        ));
+
+        let batches = stream_batches(
+            Box::new(select_all([new_blocks, txs])),
+            ChainId::from_string("ibc-0"),
+            vec![],
+            Duration::from_millis(10),
+        );
+        pin_mut!(batches);
+
+        // The node dropped the subscription to the transactions, while the one to the new
+        // blocks is still up: the drop is reported instead of waiting for the new blocks only
+        let next = tokio::time::timeout(Duration::from_secs(1), batches.next())
+            .await
+            .expect("the dropped subscription was not reported");
+
+        match next {
+            Some(Err(e)) => assert!(matches!(e.detail(), ErrorDetail::Rpc(_)), "{e}"),
+            _ => panic!("unexpected batch: {next:?}"),
+        }
+    }
+
+    #[test]
+    fn rapid_disconnects_back_off_reconnects() {
+        let mut backoff = ReconnectBackoff::new(Duration::from_secs(1), Duration::from_secs(10));
+        let mut now = Instant::now();
+
+        // The first disconnect is reconnected immediately
+        assert_eq!(backoff.disconnected(now), Duration::ZERO);
+        assert_eq!(backoff.reconnects(), 1);
+
+        // The connection keeps dropping shortly after each reconnect
+        let mut delays = Vec::new();
+        for _ in 0..5 {
+            now += Duration::from_millis(100);
+            let delay = backoff.disconnected(now);
+            now += delay;
+            delays.push(delay);
+        }
+
+        assert_eq!(
+            delays,
+            [1, 2, 4, 8, 10].map(Duration::from_secs),
+            "backoff grows up to its maximum"
+        );
+        assert_eq!(backoff.reconnects(), 6);
+        assert_eq!(backoff.storm_disconnects(), 6);
+
+        // Once the connection stayed up for long enough, reconnects are immediate again
+        now += Duration::from_secs(10);
+        assert!(!backoff.is_storm(now));
+        assert_eq!(backoff.disconnected(now), Duration::ZERO);
+        assert_eq!(backoff.reconnects(), 7);
+        assert_eq!(backoff.storm_disconnects(), 1);
+    }
+
+    /// Reads a frame sent by the client, returning its opcode and unmasked payload.
+    fn read_frame(stream: &mut TcpStream) -> std::io::Result<(u8, Vec<u8>)> {
+        let mut head = [0; 2];
+        stream.read_exact(&mut head)?;
+
+        let len = match head[1] & 0x7f {
+            126 => {
+                let mut len = [0; 2];
+                stream.read_exact(&mut len)?;
+                u16::from_be_bytes(len) as usize
+            }
+            127 => {
+                let mut len = [0; 8];
+                stream.read_exact(&mut len)?;
+                u64::from_be_bytes(len) as usize
+            }
+            len => len as usize,
+        };
+
+        let mut mask = [0; 4];
+        stream.read_exact(&mut mask)?;
+
+        let mut payload = vec![0; len];
+        stream.read_exact(&mut payload)?;
+
+        for (i, byte) in payload.iter_mut().enumerate() {
+            *byte ^= mask[i % 4];
+        }
+
+        Ok((head[0] & 0x0f, payload))
+    }
+
+    fn write_frame(stream: &mut TcpStream, opcode: u8, payload: &[u8]) -> std::io::Result<()> {
+        let mut frame = vec![0x80 | opcode];
+
+        match payload.len() {
+            len @ 0..=125 => frame.push(len as u8),
+            len @ 126..=0xffff => {
+                frame.push(126);
+                frame.extend((len as u16).to_be_bytes());
+            }
+            len => {
+                frame.push(127);
+                frame.extend((len as u64).to_be_bytes());
+            }
+        }
+
+        frame.extend(payload);
+        stream.write_all(&frame)
+    }
+
+    /// Spawns a WebSocket node which accepts every subscription, and which drops
+    /// the first connection once `subscriptions` queries were subscribed to over it.
+    /// Forwards the index of the connection and the query of each subscription.
+    fn spawn_dropping_node(subscriptions: usize) -> (SocketAddr, mpsc::Receiver<(usize, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (index, stream) in listener.incoming().enumerate() {
+                let mut stream = stream.unwrap();
+                let sender = sender.clone();
+
+                thread::spawn(move || {
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+                    let mut key = String::new();
+                    loop {
+                        let mut header = String::new();
+                        reader.read_line(&mut header).unwrap();
+
+                        match header.trim_end().split_once(':') {
+                            Some((name, value))
+                                if name.eq_ignore_ascii_case("sec-websocket-key") =>
+                            {
+                                key = value.trim().to_string()
+                            }
+                            Some(_) => {}
+                            None if header.trim_end().is_empty() => break,
+                            None => {}
+                        }
+                    }
+
+                    let digest = sha1::Hash::hash(format!("{key}{WEBSOCKET_GUID}").as_bytes());
+                    let accept = String::from_utf8(base64::encode(digest.as_byte_array())).unwrap();
+
+                    write!(
+                        stream,
+                        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\n\
+                         Connection: Upgrade\r\nSec-WebSocket-Accept: {accept}\r\n\r\n"
+                    )
+                    .unwrap();
+
+                    let mut subscribed = 0;
+                    while let Ok((opcode, payload)) = read_frame(&mut stream) {
+                        match opcode {
+                            0x1 => {
+                                let request: serde_json::Value =
+                                    serde_json::from_slice(&payload).unwrap();
+
+                                if request["method"] != "subscribe" {
+                                    continue;
+                                }
+
+                                let query = request["params"]["query"].as_str().unwrap();
+                                let _ = sender.send((index, query.to_string()));
+
+                                let response = serde_json::json!({
+                                    "jsonrpc": "2.0",
+                                    "id": request["id"],
+                                    "result": {},
+                                });
+                                let _ =
+                                    write_frame(&mut stream, 0x1, response.to_string().as_bytes());
+
+                                subscribed += 1;
+                                if index == 0 && subscribed == subscriptions {
+                                    let _ = stream.shutdown(Shutdown::Both);
+                                    return;
+                                }
+                            }
+                            0x8 => return,
+                            0x9 => {
+                                let _ = write_frame(&mut stream, 0xa, &payload);
+                            }
+                            _ => {}
+                        }
+                    }
+                });
+            }
+        });
+
+        (address, receiver)
+    }
+
+    #[test]
+    fn dropped_connection_is_resubscribed_and_resynced() {
+        let chain_id = ChainId::from_string("ibc-0");
+        let rt = Arc::new(TokioRuntime::new().unwrap());
+
+        let (address, subscriptions) = spawn_dropping_node(queries::all().len());
+        let ws_url = format!("ws://{address}/websocket").parse().unwrap();
+
+        let (mut source, tx_cmd) = EventSource::new(
+            chain_id.clone(),
+            ws_url,
+            CompatMode::V0_37,
+            Duration::from_millis(10),
+            vec![],
+            ReconnectBackoff::default(),
+            rt,
+        )
+        .unwrap();
+
+        source.init_subscriptions().unwrap();
+        let queries = source.queries().to_vec();
+
+        thread::spawn(move || source.run());
+        let events = tx_cmd.subscribe().unwrap();
+
+        // The disconnect is reported as soon as the connection is dropped
+        let next = events.recv_timeout(Duration::from_secs(10)).unwrap();
+        match next.as_ref() {
+            Err(e) => assert!(!matches!(e.detail(), ErrorDetail::ResyncNeeded(_)), "{e}"),
+            Ok(batch) => panic!("unexpected batch: {batch:?}"),
+        }
+
+        // And the missed events must be cleared once reconnected
+        let next = events.recv_timeout(Duration::from_secs(10)).unwrap();
+        match next.as_ref() {
+            Err(e) => assert!(
+                matches!(e.detail(), ErrorDetail::ResyncNeeded(e) if e.chain_id == chain_id),
+                "{e}"
+            ),
+            Ok(batch) => panic!("unexpected batch: {batch:?}"),
+        }
+
+        // All the queries were subscribed to again over the new connection
+        let subscribed: Vec<_> = subscriptions.try_iter().collect();
+        for index in [0, 1] {
+            let mut subscribed: Vec<_> = subscribed
+                .iter()
+                .filter(|(i, _)| *i == index)
+                .map(|(_, query)| query.clone())
+                .collect();
+            subscribed.sort();
+
+            let mut expected: Vec<_> = queries.iter().map(|query| query.to_string()).collect();
+            expected.sort();
+
+            assert_eq!(subscribed, expected, "subscriptions of connection {index}");
+        }
+
+        tx_cmd.shutdown().unwrap();
+    }
+}
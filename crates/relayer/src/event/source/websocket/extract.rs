@@ -15,7 +15,9 @@ use crate::chain::cosmos::types::events::raw_object::RawObject;
 use crate::event::source::queries;
 use crate::telemetry;
 
-use crate::event::{ibc_event_try_from_abci_event, IbcEventWithHeight};
+use crate::event::{
+    fee_distributions_per_channel, ibc_event_try_from_abci_event, IbcEventWithHeight,
+};
 
 /// Extract IBC events from Tendermint RPC events
 ///
@@ -191,12 +193,14 @@ pub fn extract_events(
                     } else if query == queries::ibc_channel().to_string()
                         && event_is_type_distribute_fee(&ibc_event)
                     {
-                        if let IbcEvent::DistributeFeePacket(dist) = ibc_event {
+                        if let IbcEvent::DistributeFeePacket(dist) = &ibc_event {
                             // Only record rewarded fees
                             if let DistributionType::Reward = dist.distribution_type {
-                                telemetry!(fees_amount, chain_id, &dist.receiver, dist.fee);
+                                telemetry!(fees_amount, chain_id, &dist.receiver, dist.fee.clone());
                             }
                         }
+
+                        events_with_height.push(IbcEventWithHeight::new(ibc_event, height));
                     }
                 }
             }
@@ -204,6 +208,20 @@ pub fn extract_events(
         _ => {}
     }
 
+    for fee in fee_distributions_per_channel(&events_with_height) {
+        // Only record rewarded fees
+        if let DistributionType::Reward = fee.distribution.distribution_type {
+            telemetry!(
+                channel_fees_amount,
+                chain_id,
+                fee.channel_id,
+                fee.port_id,
+                &fee.distribution.receiver,
+                fee.distribution.fee.clone()
+            );
+        }
+    }
+
     Ok(events_with_height)
 }
 
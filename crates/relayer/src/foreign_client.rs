@@ -5,6 +5,7 @@
 //! refers to light clients running *locally* as part of the relayer.
 
 use core::{fmt, time::Duration};
+use std::num::NonZeroUsize;
 use std::thread;
 use std::time::Instant;
 
@@ -44,9 +45,17 @@ use crate::misbehaviour::{AnyMisbehaviour, MisbehaviourEvidence};
 use crate::telemetry;
 use crate::util::collate::CollatedIterExt;
 use crate::util::pretty::{PrettyDuration, PrettySlice};
+use crate::util::semaphore::{map_bounded, Semaphore};
+
+pub mod pending_refresh;
+
+use pending_refresh::PendingRefreshes;
 
 const MAX_MISBEHAVIOUR_CHECK_DURATION: Duration = Duration::from_secs(120);
 
+/// Maximum time to wait for a refresh in progress of a client before updating it.
+const PENDING_REFRESH_TIMEOUT: Duration = Duration::from_secs(60);
+
 const MAX_RETRIES: usize = 5;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -292,6 +301,13 @@ define_error! {
                 format_args!("the chain ID ({}) at the source and destination chains must be different", e.chain_id)
             },
 
+        DryRunUnsupported
+            { chain_id: ChainId }
+            |e| {
+                format_args!("dry run of the creation of a client of chain {} is not supported for Penumbra chains",
+                    e.chain_id)
+            },
+
         MissingClientIdFromEvent
             { event: IbcEvent }
             |e| {
@@ -384,6 +400,10 @@ pub struct ForeignClient<DstChain: ChainHandle, SrcChain: ChainHandle> {
 
     /// A handle to the chain whose headers this client is verifying, aka the source chain.
     pub src_chain: SrcChain,
+
+    /// Trust threshold used by the relayer to verify the headers of the source chain,
+    /// if stricter than the trust threshold of the client.
+    pub local_trust_threshold: Option<TrustThreshold>,
 }
 
 /// Used in Output messages.
@@ -424,6 +444,7 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
             id: ClientId::default(),
             dst_chain,
             src_chain,
+            local_trust_threshold: None,
         };
 
         client.create()?;
@@ -440,9 +461,17 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
             id,
             dst_chain,
             src_chain,
+            local_trust_threshold: None,
         }
     }
 
+    /// Sets the trust threshold used to verify the headers of the source chain
+    /// before they are submitted, when stricter than the one of the client.
+    pub fn with_local_trust_threshold(mut self, trust_threshold: Option<TrustThreshold>) -> Self {
+        self.local_trust_threshold = trust_threshold;
+        self
+    }
+
     /// Queries `host_chain` to verify that a client with identifier `client_id` exists.
     /// If the client does not exist, returns an error. If the client exists, cross-checks that the
     /// identifier for the target chain of this client (i.e., the chain whose headers this client is
@@ -494,7 +523,7 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
     )]
     pub fn upgrade(&self, src_upgrade_height: Height) -> Result<Vec<IbcEvent>, ForeignClientError> {
         let msgs = self
-            .build_update_client_with_trusted(src_upgrade_height, None)
+            .build_update_client_with_trusted(&[src_upgrade_height], None)
             .map_err(|_| {
                 ForeignClientError::client_upgrade_no_source(
                     self.id.clone(),
@@ -616,6 +645,55 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
             )
         })?;
 
+        let (client_state, consensus_state) = self.build_client_and_consensus_state(options)?;
+
+        //TODO Get acct_prefix
+        let msg = MsgCreateClient::new(client_state.into(), consensus_state.into(), signer)
+            .map_err(ForeignClientError::client)?;
+
+        Ok(msg)
+    }
+
+    /// Returns the client state that creating a client with the given options would
+    /// set, built from the latest header of the source chain, without submitting any
+    /// transaction, eg. to compare the settings of the client with an existing one.
+    pub fn dry_run_create(
+        &self,
+        options: CreateOptions,
+    ) -> Result<AnyClientState, ForeignClientError> {
+        let src_config = self.src_chain.config().map_err(|e| {
+            ForeignClientError::client_create(
+                self.src_chain.id(),
+                "failed while querying the source chain for configuration".to_string(),
+                e,
+            )
+        })?;
+
+        if let ChainConfig::Penumbra(_) = src_config {
+            return Err(ForeignClientError::dry_run_unsupported(self.src_chain.id()));
+        }
+
+        let (client_state, _) = self.build_client_and_consensus_state(options)?;
+
+        Ok(client_state)
+    }
+
+    /// Returns the settings of a client created with the default options, ie. once the
+    /// settings of both chains in the configuration, the clock drift derived from them,
+    /// and the trusting period derived from the unbonding period of the source chain
+    /// are applied, so that they can be checked against the configured ones.
+    pub fn effective_client_settings(&self) -> Result<ClientSettings, ForeignClientError> {
+        let client_state = self.dry_run_create(CreateOptions::default())?;
+
+        Ok(ClientSettings::from_client_state(&client_state))
+    }
+
+    /// Builds the client and consensus states of a new client from the data of the
+    /// source chain at its latest height.
+    fn build_client_and_consensus_state(
+        &self,
+        options: CreateOptions,
+    ) -> Result<(AnyClientState, AnyConsensusState), ForeignClientError> {
         // Build client create message with the data from source chain at latest height.
         let latest_height = self.src_chain.query_latest_height().map_err(|e| {
             ForeignClientError::client_create(
@@ -671,11 +749,7 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
                 )
             })?;
 
-        //TODO Get acct_prefix
-        let msg = MsgCreateClient::new(client_state.into(), consensus_state.into(), signer)
-            .map_err(ForeignClientError::client)?;
-
-        Ok(msg)
+        Ok((client_state, consensus_state))
     }
 
     /// Returns the identifier of the newly created client.
@@ -858,13 +932,30 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
         }
     }
 
+    pub fn refresh(&mut self) -> Result<Option<Vec<IbcEvent>>, ForeignClientError> {
+        self.refresh_with(|refresh_period| refresh_period, None, None)
+    }
+
+    /// Refreshes the client like [`Self::refresh`], but once the time elapsed since its
+    /// last update exceeds its refresh period as adjusted by `adjust_refresh_period`.
+    ///
+    /// With an `update_limit`, the client update is only submitted while holding one of
+    /// its permits, which is not needed if the client does not need to be refreshed.
+    ///
+    /// With `pending_refreshes`, the refresh is marked as in progress in it until the
+    /// client update is submitted, see [`Self::wait_for_refresh_and_build_update_client`].
     #[instrument(
         name = "foreign_client.refresh",
         level = "error",
         skip_all,
         fields(client = %self)
     )]
-    pub fn refresh(&mut self) -> Result<Option<Vec<IbcEvent>>, ForeignClientError> {
+    pub fn refresh_with(
+        &mut self,
+        adjust_refresh_period: impl Fn(Duration) -> Duration,
+        update_limit: Option<&Semaphore>,
+        pending_refreshes: Option<&PendingRefreshes>,
+    ) -> Result<Option<Vec<IbcEvent>>, ForeignClientError> {
         fn check_no_errors(
             ibc_events: &[IbcEvent],
             dst_chain_id: ChainId,
@@ -886,7 +977,9 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
 
         // If elapsed < refresh_window for the client, `try_refresh()` will
         // be successful with an empty vector.
-        if let Some(events) = self.try_refresh()? {
+        if let Some(events) =
+            self.try_refresh(adjust_refresh_period, update_limit, pending_refreshes)?
+        {
             check_no_errors(&events, self.dst_chain().id())?;
             Ok(Some(events))
         } else {
@@ -894,7 +987,12 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
         }
     }
 
-    fn try_refresh(&mut self) -> Result<Option<Vec<IbcEvent>>, ForeignClientError> {
+    fn try_refresh(
+        &mut self,
+        adjust_refresh_period: impl Fn(Duration) -> Duration,
+        update_limit: Option<&Semaphore>,
+        pending_refreshes: Option<&PendingRefreshes>,
+    ) -> Result<Option<Vec<IbcEvent>>, ForeignClientError> {
         let (client_state, elapsed) = self.validated_client_state()?;
 
         let src_config = self.src_chain.config().map_err(|e| {
@@ -905,16 +1003,18 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
             )
         })?;
 
-        let refresh_rate = match src_config {
+        let refresh_rate = match &src_config {
             ChainConfig::CosmosSdk(config) | ChainConfig::Namada(config) => {
                 config.client_refresh_rate
             }
             ChainConfig::Penumbra(config) => config.client_refresh_rate,
         };
 
-        let refresh_period = client_state
-            .trusting_period()
-            .mul_f64(refresh_rate.as_f64());
+        let refresh_period = adjust_refresh_period(
+            src_config
+                .refresh_trusting_period(client_state.trusting_period())
+                .mul_f64(refresh_rate.as_f64()),
+        );
 
         match (elapsed, refresh_period) {
             (None, _) => Ok(None),
@@ -922,8 +1022,22 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
                 if elapsed > refresh_period {
                     info!(?elapsed, ?refresh_period, "client needs to be refreshed");
 
-                    self.build_latest_update_client_and_send()
-                        .map_or_else(Err, |ev| Ok(Some(ev)))
+                    let _permit = update_limit.map(Semaphore::acquire);
+
+                    let pending = pending_refreshes
+                        .map(|refreshes| refreshes.start(&self.dst_chain.id(), &self.id));
+
+                    let result = self.build_latest_update_client_and_send();
+
+                    if let Some(pending) = pending {
+                        pending.complete(result.as_ref().is_ok_and(|events| {
+                            events
+                                .iter()
+                                .all(|e| e.event_type() != IbcEventType::ChainError)
+                        }));
+                    }
+
+                    result.map(Some)
                 } else {
                     Ok(None)
                 }
@@ -939,6 +1053,22 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
         self.wait_and_build_update_client_with_trusted(target_height, None)
     }
 
+    /// Like [`Self::wait_and_build_update_client`], but first waits for a refresh of the
+    /// client in progress in `pending_refreshes`, if any, to complete, so that the update
+    /// is built against the refreshed client rather than racing its refresh, which may
+    /// find the client expired.
+    pub fn wait_for_refresh_and_build_update_client(
+        &self,
+        target_height: Height,
+        pending_refreshes: &PendingRefreshes,
+    ) -> Result<Vec<Any>, ForeignClientError> {
+        if pending_refreshes.wait(&self.dst_chain.id(), &self.id, PENDING_REFRESH_TIMEOUT) {
+            debug!(client = %self, "building the client update against the refreshed client");
+        }
+
+        self.wait_and_build_update_client(target_height)
+    }
+
     /// Returns a trusted height that is lower than the target height, so
     /// that the relayer can update the client to the target height based
     /// on the returned trusted height.
@@ -1199,22 +1329,30 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
             }
         }
 
-        let messages = self.build_update_client_with_trusted(target_height, trusted_height)?;
+        let messages = self.build_update_client_with_trusted(&[target_height], trusted_height)?;
 
         let encoded_messages = messages.into_iter().map(Msg::to_any).collect();
 
         Ok(encoded_messages)
     }
 
+    /// Builds the messages updating the client to each of the given target heights, from
+    /// the given trusted height or, if unset, from the highest height below each target
+    /// height at which the client stores a consensus state.
+    ///
+    /// The headers for the target heights are built concurrently, at most
+    /// `max_parallel_header_builds` of the source chain at once, and the messages are
+    /// ordered by strictly increasing height of their header, so that the client stores
+    /// the consensus state each of the headers is verified against when it is applied.
     #[instrument(
         name = "foreign_client.build_update_client_with_trusted",
         level = "error",
         skip_all,
-        fields(client = %self, %target_height)
+        fields(client = %self, target_heights = %PrettySlice(target_heights))
     )]
     pub fn build_update_client_with_trusted(
         &self,
-        target_height: Height,
+        target_heights: &[Height],
         maybe_trusted_height: Option<Height>,
     ) -> Result<Vec<MsgUpdateClient>, ForeignClientError> {
         crate::time!(
@@ -1227,57 +1365,80 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
         // Get the latest client state on destination.
         let (client_state, _) = self.validated_client_state()?;
 
-        let trusted_height = match maybe_trusted_height {
-            Some(trusted_height) => {
-                self.validate_trusted_height(trusted_height, &client_state)?;
-                trusted_height
-            }
-            None => self.solve_trusted_height(target_height, &client_state)?,
-        };
+        let mut updates = Vec::with_capacity(target_heights.len());
 
-        if trusted_height != client_state.latest_height() {
-            // If we're using a trusted height that is different from the client latest height,
-            // then check if the consensus state at `trusted_height` is within trusting period
-            if let ConsensusStateTrusted::NotTrusted {
-                elapsed,
-                consensus_state_timestamp,
-                network_timestamp,
-            } = self.check_consensus_state_trusting_period(&client_state, &trusted_height)?
-            {
-                error!(
-                    %trusted_height,
-                    %network_timestamp,
-                    %consensus_state_timestamp,
-                    ?elapsed,
-                    "cannot build client update message because the provided trusted height is outside of trusting period!",
+        for &target_height in target_heights.iter().sorted().dedup() {
+            let trusted_height =
+                self.trusted_height_for_update(target_height, maybe_trusted_height, &client_state)?;
+
+            if trusted_height >= target_height {
+                warn!(
+                    "skipping update: trusted height ({}) >= chain target height ({})",
+                    trusted_height, target_height
                 );
 
-                return Err(ForeignClientError::consensus_state_not_trusted(
-                    trusted_height,
-                    elapsed,
-                ));
+                continue;
             }
-        }
 
-        if trusted_height >= target_height {
-            warn!(
-                "skipping update: trusted height ({}) >= chain target height ({})",
-                trusted_height, target_height
-            );
+            updates.push((trusted_height, target_height));
+        }
 
+        if updates.is_empty() {
             return Ok(vec![]);
         }
 
-        let (header, support) = self
-            .src_chain()
-            .build_header(trusted_height, target_height, client_state.clone())
+        let max_parallel = self
+            .src_chain
+            .config()
             .map_err(|e| {
                 ForeignClientError::client_update(
-                    self.dst_chain.id(),
-                    "failed building header with error".to_string(),
+                    self.src_chain.id(),
+                    "failed while querying the source chain for configuration".to_string(),
                     e,
                 )
-            })?;
+            })?
+            .max_parallel_header_builds()
+            .unwrap_or(NonZeroUsize::MIN);
+
+        let verification_state = self.local_verification_state(&client_state);
+
+        let built = map_bounded(updates, max_parallel, |(trusted_height, target_height)| {
+            self.src_chain()
+                .build_header(trusted_height, target_height, verification_state.clone())
+                .map(|built| (trusted_height, built))
+                .map_err(|e| {
+                    ForeignClientError::client_update(
+                        self.dst_chain.id(),
+                        "failed building header with error".to_string(),
+                        e,
+                    )
+                })
+        });
+
+        let mut headers = Vec::new();
+
+        for result in built {
+            let (trusted_height, (header, support)) = result?;
+
+            debug!(
+                "building a MsgUpdateAnyClient from trusted height {} to target height {} \
+                with {} intermediate headers",
+                trusted_height,
+                header.height(),
+                support.len(),
+            );
+
+            headers.extend(support);
+            headers.push(header);
+        }
+
+        // A header shared by several target heights, eg. an intermediate header
+        // of a higher target height, is only applied once
+        let headers = headers
+            .into_iter()
+            .sorted_by_key(|header| header.height())
+            .dedup_by(|a, b| a.height() == b.height())
+            .collect_vec();
 
         let signer = self.dst_chain().get_signer().map_err(|e| {
             ForeignClientError::client_update(
@@ -1287,34 +1448,18 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
             )
         })?;
 
-        self.wait_for_header_validation_delay(&client_state, &header)?;
-
-        let mut msgs = vec![];
-
-        for header in support {
-            debug!(
-                "building a MsgUpdateAnyClient for intermediate height {}",
-                header.height(),
-            );
+        if let Some(header) = headers.last() {
+            self.wait_for_header_validation_delay(&client_state, header)?;
+        }
 
-            msgs.push(MsgUpdateClient {
+        let msgs: Vec<MsgUpdateClient> = headers
+            .into_iter()
+            .map(|header| MsgUpdateClient {
                 header: header.into(),
                 client_id: self.id.clone(),
                 signer: signer.clone(),
-            });
-        }
-
-        debug!(
-            "building a MsgUpdateAnyClient from trusted height {} to target height {}",
-            trusted_height,
-            header.height(),
-        );
-
-        msgs.push(MsgUpdateClient {
-            header: header.into(),
-            signer,
-            client_id: self.id.clone(),
-        });
+            })
+            .collect();
 
         telemetry!(
             client_updates_submitted,
@@ -1327,6 +1472,50 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
         Ok(msgs)
     }
 
+    /// The height from which the client is updated to the given target height, checked
+    /// to be within the trusting period if it is not the latest height of the client.
+    fn trusted_height_for_update(
+        &self,
+        target_height: Height,
+        maybe_trusted_height: Option<Height>,
+        client_state: &AnyClientState,
+    ) -> Result<Height, ForeignClientError> {
+        let trusted_height = match maybe_trusted_height {
+            Some(trusted_height) => {
+                self.validate_trusted_height(trusted_height, client_state)?;
+                trusted_height
+            }
+            None => self.solve_trusted_height(target_height, client_state)?,
+        };
+
+        if trusted_height != client_state.latest_height() {
+            // If we're using a trusted height that is different from the client latest height,
+            // then check if the consensus state at `trusted_height` is within trusting period
+            if let ConsensusStateTrusted::NotTrusted {
+                elapsed,
+                consensus_state_timestamp,
+                network_timestamp,
+            } = self.check_consensus_state_trusting_period(client_state, &trusted_height)?
+            {
+                error!(
+                    %trusted_height,
+                    %network_timestamp,
+                    %consensus_state_timestamp,
+                    ?elapsed,
+                    "cannot build client update message because the provided trusted height \
+                    is outside of trusting period!",
+                );
+
+                return Err(ForeignClientError::consensus_state_not_trusted(
+                    trusted_height,
+                    elapsed,
+                ));
+            }
+        }
+
+        Ok(trusted_height)
+    }
+
     pub fn build_latest_update_client_and_send(&self) -> Result<Vec<IbcEvent>, ForeignClientError> {
         self.build_update_client_and_send(QueryHeight::Latest, None)
     }
@@ -1919,6 +2108,22 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
         }
     }
 
+    /// Returns the client state against which the relayer verifies the headers of the
+    /// source chain, whose trust threshold is raised to the local trust threshold if set.
+    fn local_verification_state(&self, client_state: &AnyClientState) -> AnyClientState {
+        let mut client_state = client_state.clone();
+
+        if let Some(local_trust_threshold) = self.local_trust_threshold {
+            match &mut client_state {
+                AnyClientState::Tendermint(state) => {
+                    state.trust_threshold = state.trust_threshold.max(local_trust_threshold);
+                }
+            }
+        }
+
+        client_state
+    }
+
     pub fn map_chain<DstChain2: ChainHandle, SrcChain2: ChainHandle>(
         self,
         map_dst: impl Fn(DstChain) -> DstChain2,
@@ -1928,6 +2133,7 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
             id: self.id,
             dst_chain: map_dst(self.dst_chain),
             src_chain: map_src(self.src_chain),
+            local_trust_threshold: self.local_trust_threshold,
         }
     }
 }
@@ -1967,3 +2173,274 @@ pub fn fetch_ccv_consumer_id(
 
     Ok(consumer_id)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Instant;
+
+    use ibc_relayer_types::clients::ics07_tendermint::client_state::{
+        AllowUpdate, ClientState as TmClientState,
+    };
+    use ibc_relayer_types::clients::ics07_tendermint::{
+        consensus_state::ConsensusState as TmConsensusState, header::Header as TmHeader,
+    };
+    use ibc_relayer_types::core::ics23_commitment::commitment::CommitmentRoot;
+    use ibc_relayer_types::core::ics23_commitment::specs::ProofSpecs;
+    use tendermint::Hash;
+    use tendermint_testgen::light_block::{LightBlock as TestgenLightBlock, TmLightBlock};
+    use tendermint_testgen::Generator;
+
+    use crate::chain::cosmos::batch::test_fixtures::{cosmos_config_mut, example_config};
+    use crate::chain::endpoint::ChainStatus;
+    use crate::chain::handle::{BaseChainHandle, ChainRequest};
+    use crate::util::mock_chain::spawn_mock_chain;
+
+    fn height(height: u64) -> Height {
+        Height::new(0, height).unwrap()
+    }
+
+    fn status() -> ChainStatus {
+        ChainStatus {
+            height: height(100),
+            timestamp: Timestamp::now(),
+        }
+    }
+
+    fn header(trusted_height: Height, target_height: Height) -> AnyHeader {
+        let TmLightBlock {
+            signed_header,
+            validators,
+            ..
+        } = TestgenLightBlock::new_default(target_height.revision_height())
+            .generate()
+            .unwrap();
+
+        AnyHeader::Tendermint(TmHeader {
+            signed_header,
+            validator_set: validators.clone(),
+            trusted_height,
+            trusted_validator_set: validators,
+        })
+    }
+
+    /// The chain hosting the client, at the trusted height of 10.
+    fn hosting_chain(src_chain_id: ChainId) -> BaseChainHandle {
+        hosting_chain_updated_at(src_chain_id, Timestamp::now(), || {
+            panic!("unexpected client update")
+        })
+    }
+
+    /// The chain hosting the client, last updated at `updated_at` to the trusted height
+    /// of 10, which answers the client updates submitted to it with `on_update`.
+    fn hosting_chain_updated_at(
+        src_chain_id: ChainId,
+        updated_at: Timestamp,
+        on_update: impl Fn() + Send + 'static,
+    ) -> BaseChainHandle {
+        spawn_mock_chain(
+            ChainId::from_string("chain_B"),
+            move |request| match request {
+                ChainRequest::QueryClientState { reply_to, .. } => {
+                    let client_state = TmClientState::new(
+                        src_chain_id.clone(),
+                        TrustThreshold::ONE_THIRD,
+                        Duration::from_secs(64_000),
+                        Duration::from_secs(128_000),
+                        Duration::from_millis(3000),
+                        height(10),
+                        ProofSpecs::default(),
+                        vec![],
+                        AllowUpdate {
+                            after_expiry: false,
+                            after_misbehaviour: false,
+                        },
+                    )
+                    .unwrap();
+
+                    reply_to.send(Ok((client_state.into(), None))).unwrap()
+                }
+                ChainRequest::QueryConsensusState {
+                    request, reply_to, ..
+                } if request.consensus_height == height(10) => {
+                    let consensus_state = TmConsensusState::new(
+                        CommitmentRoot::from_bytes(&[]),
+                        updated_at.into_tm_time().unwrap(),
+                        Hash::None,
+                    );
+
+                    reply_to.send(Ok((consensus_state.into(), None))).unwrap()
+                }
+                ChainRequest::QueryConsensusState { reply_to, .. } => reply_to
+                    .send(Err(RelayerError::query("no consensus state".to_string())))
+                    .unwrap(),
+                ChainRequest::QueryApplicationStatus { reply_to } => {
+                    reply_to.send(Ok(status())).unwrap()
+                }
+                ChainRequest::Signer { reply_to } => reply_to
+                    .send(Ok("cosmos1relayer".parse().unwrap()))
+                    .unwrap(),
+                ChainRequest::SendMessagesAndWaitCommit { reply_to, .. } => {
+                    on_update();
+                    reply_to.send(Ok(vec![])).unwrap()
+                }
+                request => panic!("unexpected request: {request:?}"),
+            },
+        )
+    }
+
+    /// The source chain of the client, which records when it builds a header.
+    fn source_chain(built_at: Arc<Mutex<Option<Instant>>>) -> BaseChainHandle {
+        let src_config = example_config().chains[0].clone();
+
+        spawn_mock_chain(src_config.id().clone(), move |request| match request {
+            ChainRequest::Config { reply_to } => reply_to.send(Ok(src_config.clone())).unwrap(),
+            ChainRequest::QueryApplicationStatus { reply_to } => {
+                reply_to.send(Ok(status())).unwrap()
+            }
+            ChainRequest::BuildHeader {
+                trusted_height,
+                target_height,
+                reply_to,
+                ..
+            } => {
+                *built_at.lock().unwrap() = Some(Instant::now());
+                reply_to
+                    .send(Ok((header(trusted_height, target_height), vec![])))
+                    .unwrap()
+            }
+            request => panic!("unexpected request: {request:?}"),
+        })
+    }
+
+    #[test]
+    fn headers_of_several_heights_are_built_concurrently_and_applied_in_order() {
+        let mut src_config = example_config().chains[0].clone();
+        cosmos_config_mut(&mut src_config).max_parallel_header_builds = NonZeroUsize::new(2);
+
+        let building = Arc::new(AtomicUsize::new(0));
+        let max_building = Arc::new(AtomicUsize::new(0));
+
+        // The chain builds each header in 100ms, supported by a header at the height
+        // halfway from the trusted height
+        let src_chain = spawn_mock_chain(src_config.id().clone(), {
+            let (building, max_building) = (building.clone(), max_building.clone());
+
+            move |request| match request {
+                ChainRequest::Config { reply_to } => reply_to.send(Ok(src_config.clone())).unwrap(),
+                ChainRequest::QueryApplicationStatus { reply_to } => {
+                    reply_to.send(Ok(status())).unwrap()
+                }
+                ChainRequest::BuildHeader {
+                    trusted_height,
+                    target_height,
+                    reply_to,
+                    ..
+                } => {
+                    let (building, max_building) = (building.clone(), max_building.clone());
+
+                    thread::spawn(move || {
+                        let now_building = building.fetch_add(1, Ordering::SeqCst) + 1;
+                        max_building.fetch_max(now_building, Ordering::SeqCst);
+                        thread::sleep(Duration::from_millis(100));
+                        building.fetch_sub(1, Ordering::SeqCst);
+
+                        let support = height(
+                            (trusted_height.revision_height() + target_height.revision_height())
+                                / 2,
+                        );
+
+                        reply_to
+                            .send(Ok((
+                                header(trusted_height, target_height),
+                                vec![header(trusted_height, support)],
+                            )))
+                            .unwrap()
+                    });
+                }
+                request => panic!("unexpected request: {request:?}"),
+            }
+        });
+
+        let dst_chain = hosting_chain(src_chain.id());
+        let client = ForeignClient::restore(ClientId::default(), dst_chain, src_chain);
+
+        let msgs = client
+            .build_update_client_with_trusted(&[height(40), height(20), height(30)], None)
+            .unwrap();
+
+        // The header at height 20 supporting the update to height 30 is applied once
+        let heights = msgs
+            .into_iter()
+            .map(|msg| AnyHeader::try_from(msg.header).unwrap().height())
+            .collect_vec();
+        let expected = [15, 20, 25, 30, 40].into_iter().map(height).collect_vec();
+        assert_eq!(heights, expected);
+
+        // The three headers were built two at a time
+        assert_eq!(max_building.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn update_waits_for_the_pending_refresh_of_the_client() {
+        let built_at = Arc::new(Mutex::new(None));
+        let src_chain = source_chain(built_at.clone());
+        let dst_chain = hosting_chain(src_chain.id());
+        let client = ForeignClient::restore(ClientId::default(), dst_chain, src_chain);
+
+        let pending_refreshes = PendingRefreshes::default();
+
+        let refreshed_at = thread::scope(|s| {
+            let refresh = pending_refreshes.start(&client.dst_chain.id(), &client.id);
+
+            let refresher = s.spawn(move || {
+                thread::sleep(Duration::from_millis(200));
+                refresh.complete(true);
+                Instant::now()
+            });
+
+            let msgs = client
+                .wait_for_refresh_and_build_update_client(height(20), &pending_refreshes)
+                .unwrap();
+            assert_eq!(msgs.len(), 1);
+
+            refresher.join().unwrap()
+        });
+
+        assert!(built_at.lock().unwrap().unwrap() >= refreshed_at);
+    }
+
+    #[test]
+    fn refresh_is_pending_until_the_client_update_is_submitted() {
+        let pending_refreshes = Arc::new(PendingRefreshes::default());
+        let pending_on_update = Arc::new(Mutex::new(None));
+
+        let src_chain = source_chain(Arc::default());
+
+        // The client was last updated past its refresh period
+        let updated_at = (Timestamp::now() - Duration::from_secs(60_000)).unwrap();
+        let dst_chain = hosting_chain_updated_at(src_chain.id(), updated_at, {
+            let (pending_refreshes, pending_on_update) =
+                (pending_refreshes.clone(), pending_on_update.clone());
+            let (chain_id, client_id) = (ChainId::from_string("chain_B"), ClientId::default());
+
+            move || {
+                *pending_on_update.lock().unwrap() =
+                    Some(pending_refreshes.is_pending(&chain_id, &client_id));
+            }
+        });
+
+        let mut client = ForeignClient::restore(ClientId::default(), dst_chain, src_chain);
+
+        let events = client
+            .refresh_with(|period| period, None, Some(&pending_refreshes))
+            .unwrap();
+        assert!(events.is_some());
+
+        assert_eq!(*pending_on_update.lock().unwrap(), Some(true));
+        assert!(!pending_refreshes.is_pending(&client.dst_chain.id(), &client.id));
+    }
+}
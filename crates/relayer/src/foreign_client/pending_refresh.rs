@@ -0,0 +1,170 @@
+//! Tracks the refreshes of clients which are in progress, so that relaying over a
+//! client which is being refreshed can wait for the refresh to complete and build
+//! its client update against the refreshed client, instead of racing the refresh.
+
+use core::time::Duration;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Condvar, Mutex};
+
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId};
+
+type ClientKey = (ChainId, ClientId);
+
+/// A set of clients, identified by their host chain and their identifier,
+/// whose refresh is in progress.
+///
+/// The [`WorkerMap`](crate::worker::WorkerMap) holds one, shared by the client workers
+/// refreshing the clients and the packet workers relaying over them.
+#[derive(Debug, Default)]
+pub struct PendingRefreshes {
+    state: Mutex<RefreshesState>,
+    completed: Condvar,
+}
+
+#[derive(Debug, Default)]
+struct RefreshesState {
+    pending: HashSet<ClientKey>,
+    /// Whether the last completed refresh of each client succeeded.
+    succeeded: HashMap<ClientKey, bool>,
+}
+
+impl PendingRefreshes {
+    /// Marks the refresh of the given client as in progress, until the returned guard is
+    /// completed or dropped, the latter marking the refresh as failed.
+    pub fn start(&self, chain_id: &ChainId, client_id: &ClientId) -> PendingRefresh<'_> {
+        let key = (chain_id.clone(), client_id.clone());
+
+        self.state
+            .lock()
+            .expect("poisoned lock")
+            .pending
+            .insert(key.clone());
+
+        PendingRefresh {
+            refreshes: self,
+            key,
+            succeeded: false,
+        }
+    }
+
+    pub fn is_pending(&self, chain_id: &ChainId, client_id: &ClientId) -> bool {
+        self.state
+            .lock()
+            .expect("poisoned lock")
+            .pending
+            .contains(&(chain_id.clone(), client_id.clone()))
+    }
+
+    /// Waits for the refresh in progress of the given client to complete, for at most `timeout`.
+    ///
+    /// Returns `true` if a refresh was in progress and succeeded within the timeout, and
+    /// `false` if there was no refresh in progress, or if it failed or did not complete in time.
+    pub fn wait(&self, chain_id: &ChainId, client_id: &ClientId, timeout: Duration) -> bool {
+        let key = (chain_id.clone(), client_id.clone());
+        let state = self.state.lock().expect("poisoned lock");
+
+        if !state.pending.contains(&key) {
+            return false;
+        }
+
+        let (state, result) = self
+            .completed
+            .wait_timeout_while(state, timeout, |state| state.pending.contains(&key))
+            .expect("poisoned lock");
+
+        !result.timed_out() && state.succeeded.get(&key).copied().unwrap_or(false)
+    }
+
+    fn complete(&self, key: &ClientKey, succeeded: bool) {
+        let mut state = self.state.lock().expect("poisoned lock");
+
+        state.pending.remove(key);
+        state.succeeded.insert(key.clone(), succeeded);
+
+        drop(state);
+
+        self.completed.notify_all();
+    }
+}
+
+/// Guard marking the refresh of a client as in progress, see [`PendingRefreshes::start`].
+pub struct PendingRefresh<'a> {
+    refreshes: &'a PendingRefreshes,
+    key: ClientKey,
+    succeeded: bool,
+}
+
+impl PendingRefresh<'_> {
+    /// Marks the refresh as completed, successfully or not.
+    pub fn complete(mut self, succeeded: bool) {
+        self.succeeded = succeeded;
+    }
+}
+
+impl Drop for PendingRefresh<'_> {
+    fn drop(&mut self) {
+        self.refreshes.complete(&self.key, self.succeeded);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::thread;
+
+    use super::*;
+
+    #[test]
+    fn wait_returns_after_pending_refresh_completes() {
+        let refreshes = Arc::new(PendingRefreshes::default());
+        let chain_id = ChainId::from_string("ibc-0");
+        let client_id = ClientId::default();
+
+        assert!(!refreshes.wait(&chain_id, &client_id, Duration::from_secs(1)));
+
+        let refresh = refreshes.start(&chain_id, &client_id);
+        assert!(refreshes.is_pending(&chain_id, &client_id));
+
+        let waiter = {
+            let refreshes = refreshes.clone();
+            let (chain_id, client_id) = (chain_id.clone(), client_id.clone());
+            thread::spawn(move || refreshes.wait(&chain_id, &client_id, Duration::from_secs(10)))
+        };
+
+        thread::sleep(Duration::from_millis(100));
+        refresh.complete(true);
+
+        assert!(waiter.join().unwrap());
+        assert!(!refreshes.is_pending(&chain_id, &client_id));
+    }
+
+    #[test]
+    fn wait_returns_false_if_pending_refresh_fails() {
+        let refreshes = PendingRefreshes::default();
+        let chain_id = ChainId::from_string("ibc-0");
+        let client_id = ClientId::default();
+
+        thread::scope(|s| {
+            let refresh = refreshes.start(&chain_id, &client_id);
+
+            let waiter = s.spawn(|| refreshes.wait(&chain_id, &client_id, Duration::from_secs(10)));
+
+            thread::sleep(Duration::from_millis(100));
+            drop(refresh);
+
+            assert!(!waiter.join().unwrap());
+        });
+    }
+
+    #[test]
+    fn wait_times_out_if_refresh_does_not_complete() {
+        let refreshes = PendingRefreshes::default();
+        let chain_id = ChainId::from_string("ibc-0");
+        let client_id = ClientId::default();
+
+        let _refresh = refreshes.start(&chain_id, &client_id);
+
+        assert!(!refreshes.wait(&chain_id, &client_id, Duration::from_millis(100)));
+        assert!(refreshes.is_pending(&chain_id, &client_id));
+    }
+}
@@ -2,6 +2,7 @@ pub mod errors;
 pub use any_signing_key_pair::AnySigningKeyPair;
 pub use ed25519_key_pair::Ed25519KeyPair;
 pub use key_type::KeyType;
+pub use multisig::{CommandSignatureSource, MultisigKey, MultisigSigner, PartialSignatureSource};
 pub use secp256k1_key_pair::Secp256k1KeyPair;
 pub use signing_key_pair::{SigningKeyPair, SigningKeyPairSized};
 
@@ -11,6 +12,7 @@ mod any_signing_key_pair;
 mod ed25519_key_pair;
 mod key_type;
 mod key_utils;
+mod multisig;
 mod pub_key;
 mod secp256k1_key_pair;
 mod signing_key_pair;
@@ -156,6 +156,22 @@ define_error! {
             }
             |e| {
                 format!("Invalid public key length: expected {}, got {}", e.expected, e.got)
+            },
+
+        InvalidMultisig
+            { reason: String }
+            |e| {
+                format!("invalid multisig account: {}", e.reason)
+            },
+
+        PartialSignature
+            {
+                public_key: String,
+                reason: String,
+            }
+            |e| {
+                format!("cannot obtain the signature of multisig member {}: {}",
+                    e.public_key, e.reason)
             }
     }
 }
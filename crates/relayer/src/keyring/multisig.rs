@@ -0,0 +1,307 @@
+//! Multisig accounts which the relayer signs transactions as, with its key as one
+//! of the members and the partial signatures of the other members obtained from
+//! a [`PartialSignatureSource`].
+//!
+//! The members sign the `SIGN_MODE_DIRECT` sign bytes of the transaction, which
+//! commit to the members signing it, so these are chosen before signing: the key
+//! of the relayer along with the first other members of the account needed to
+//! reach its threshold.
+
+use core::fmt::Debug;
+use core::time::Duration;
+use std::sync::Arc;
+
+use digest::Digest;
+use ibc_proto::cosmos::crypto::multisig::v1beta1::{CompactBitArray, MultiSignature};
+use ibc_proto::cosmos::crypto::multisig::LegacyAminoPubKey;
+use ibc_proto::cosmos::tx::v1beta1::mode_info::{Multi, Single, Sum};
+use ibc_proto::cosmos::tx::v1beta1::ModeInfo;
+use ibc_proto::google::protobuf::Any;
+use prost::Message as _;
+use secp256k1::ecdsa::Signature;
+use secp256k1::{Message, PublicKey, Secp256k1};
+use sha2::Sha256;
+use subtle_encoding::base64;
+
+use super::errors::Error;
+use super::key_utils::encode_bech32;
+use crate::config::MultisigConfig;
+use crate::util::command::run_command;
+
+/// How long the cosigner command may run before it is killed.
+const COSIGNER_COMMAND_TIMEOUT: Duration = Duration::from_secs(60);
+
+pub const LEGACY_AMINO_PUBKEY_TYPE_URL: &str = "/cosmos.crypto.multisig.LegacyAminoPubKey";
+
+const SECP256K1_PUBKEY_TYPE_URL: &str = "/cosmos.crypto.secp256k1.PubKey";
+
+/// The sign mode of the signatures of the members, `SIGN_MODE_DIRECT`.
+const SIGN_MODE_DIRECT: i32 = 1;
+
+/// Amino prefix of the `tendermint/PubKeyMultisigThreshold` type.
+const AMINO_MULTISIG_PREFIX: [u8; 4] = [0x22, 0xc1, 0xf7, 0xe2];
+
+/// Amino prefix of the `tendermint/PubKeySecp256k1` type, followed by the key length.
+const AMINO_SECP256K1_PREFIX: [u8; 5] = [0xeb, 0x5a, 0xe9, 0x87, 0x21];
+
+/// The public key of a multisig account, ie. the threshold of signatures
+/// required from the secp256k1 keys of its members.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultisigKey {
+    threshold: u32,
+    public_keys: Vec<PublicKey>,
+}
+
+impl MultisigKey {
+    pub fn new(threshold: u32, public_keys: Vec<PublicKey>) -> Result<Self, Error> {
+        if threshold == 0 || threshold as usize > public_keys.len() {
+            return Err(Error::invalid_multisig(format!(
+                "threshold {threshold} is not between 1 and the number of members ({})",
+                public_keys.len()
+            )));
+        }
+
+        Ok(Self {
+            threshold,
+            public_keys,
+        })
+    }
+
+    pub fn threshold(&self) -> u32 {
+        self.threshold
+    }
+
+    pub fn public_keys(&self) -> &[PublicKey] {
+        &self.public_keys
+    }
+
+    pub fn is_member(&self, public_key: &PublicKey) -> bool {
+        self.public_keys.contains(public_key)
+    }
+
+    /// The address of the account, ie. the first 20 bytes of the SHA-256
+    /// hash of the Amino encoding of its public key.
+    pub fn address(&self) -> [u8; 20] {
+        let mut bytes = AMINO_MULTISIG_PREFIX.to_vec();
+
+        bytes.push(0x08);
+        prost::encoding::encode_varint(u64::from(self.threshold), &mut bytes);
+
+        for public_key in &self.public_keys {
+            bytes.push(0x12);
+            prost::encoding::encode_varint(38, &mut bytes);
+            bytes.extend(AMINO_SECP256K1_PREFIX);
+            bytes.extend(public_key.serialize());
+        }
+
+        let mut address = [0; 20];
+        address.copy_from_slice(&Sha256::digest(bytes)[..20]);
+        address
+    }
+
+    pub fn account(&self, account_prefix: &str) -> Result<String, Error> {
+        encode_bech32(account_prefix, &self.address())
+    }
+
+    pub fn to_any(&self) -> Any {
+        let public_keys = self
+            .public_keys
+            .iter()
+            .map(|public_key| Any {
+                type_url: SECP256K1_PUBKEY_TYPE_URL.to_string(),
+                value: public_key.serialize().to_vec().encode_to_vec(),
+            })
+            .collect();
+
+        Any {
+            type_url: LEGACY_AMINO_PUBKEY_TYPE_URL.to_string(),
+            value: LegacyAminoPubKey {
+                threshold: self.threshold,
+                public_keys,
+            }
+            .encode_to_vec(),
+        }
+    }
+
+    /// Returns the indices of the members signing along with the given member,
+    /// ie. the given member and the first other members needed to reach the threshold.
+    pub fn signers(&self, member: &PublicKey) -> Option<Vec<usize>> {
+        let member_index = self.public_keys.iter().position(|key| key == member)?;
+
+        let mut signers = (0..self.public_keys.len())
+            .filter(|&index| index != member_index)
+            .take(self.threshold as usize - 1)
+            .collect::<Vec<_>>();
+
+        signers.push(member_index);
+        signers.sort_unstable();
+
+        Some(signers)
+    }
+
+    /// The mode info of the signatures of the given members.
+    pub fn mode_info(&self, signers: &[usize]) -> ModeInfo {
+        let mut elems = vec![0; self.public_keys.len().div_ceil(8)];
+
+        for &index in signers {
+            elems[index / 8] |= 1 << (7 - index % 8);
+        }
+
+        let single = ModeInfo {
+            sum: Some(Sum::Single(Single {
+                mode: SIGN_MODE_DIRECT,
+            })),
+        };
+
+        ModeInfo {
+            sum: Some(Sum::Multi(Multi {
+                bitarray: Some(CompactBitArray {
+                    extra_bits_stored: (self.public_keys.len() % 8) as u32,
+                    elems,
+                }),
+                mode_infos: vec![single; signers.len()],
+            })),
+        }
+    }
+
+    /// Verifies that the given encoded `MultiSignature`, with the given mode info,
+    /// holds valid signatures of the sign bytes from at least `threshold` members.
+    pub fn verify(&self, sign_bytes: &[u8], mode_info: &ModeInfo, signature: &[u8]) -> bool {
+        let Some(Sum::Multi(Multi {
+            bitarray: Some(bitarray),
+            ..
+        })) = &mode_info.sum
+        else {
+            return false;
+        };
+
+        let Ok(multi_signature) = MultiSignature::decode(signature) else {
+            return false;
+        };
+
+        let signers = (0..self.public_keys.len())
+            .filter(|&index| {
+                bitarray
+                    .elems
+                    .get(index / 8)
+                    .is_some_and(|elem| elem & (1 << (7 - index % 8)) != 0)
+            })
+            .collect::<Vec<_>>();
+
+        if signers.len() < self.threshold as usize
+            || signers.len() != multi_signature.signatures.len()
+        {
+            return false;
+        }
+
+        let message = Message::from_digest_slice(&Sha256::digest(sign_bytes))
+            .expect("SHA-256 digest is 32 bytes");
+
+        let secp = Secp256k1::verification_only();
+
+        signers
+            .iter()
+            .zip(&multi_signature.signatures)
+            .all(|(&index, signature)| {
+                Signature::from_compact(signature).is_ok_and(|signature| {
+                    secp.verify_ecdsa(&message, &signature, &self.public_keys[index])
+                        .is_ok()
+                })
+            })
+    }
+}
+
+/// A source of the signatures of the other members of a multisig account.
+pub trait PartialSignatureSource: Debug + Send + Sync {
+    /// Returns the signature of the given bytes by the member with the given public key.
+    fn partial_signature(
+        &self,
+        public_key: &PublicKey,
+        sign_bytes: &[u8],
+    ) -> Result<Vec<u8>, Error>;
+}
+
+/// Obtains the signatures of the other members by running a command, see
+/// [`MultisigConfig::cosigner_command`].
+#[derive(Clone, Debug)]
+pub struct CommandSignatureSource {
+    command: Vec<String>,
+}
+
+impl CommandSignatureSource {
+    pub fn new(command: Vec<String>) -> Self {
+        Self { command }
+    }
+}
+
+impl PartialSignatureSource for CommandSignatureSource {
+    fn partial_signature(
+        &self,
+        public_key: &PublicKey,
+        sign_bytes: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+        let encoded_key = String::from_utf8(base64::encode(public_key.serialize()))
+            .expect("base64 is valid UTF-8");
+
+        let failed = |reason: String| Error::partial_signature(encoded_key.clone(), reason);
+
+        let output = run_command(
+            &self.command,
+            std::slice::from_ref(&encoded_key),
+            Some(&base64::encode(sign_bytes)),
+            COSIGNER_COMMAND_TIMEOUT,
+        )
+        .map_err(|e| failed(e.to_string()))?;
+
+        base64::decode(String::from_utf8_lossy(&output).trim()).map_err(|e| failed(e.to_string()))
+    }
+}
+
+/// Signs transactions as a multisig account, with the partial signatures
+/// of the other members obtained from a [`PartialSignatureSource`].
+#[derive(Clone, Debug)]
+pub struct MultisigSigner {
+    key: MultisigKey,
+    source: Arc<dyn PartialSignatureSource>,
+}
+
+impl MultisigSigner {
+    pub fn new(key: MultisigKey, source: Arc<dyn PartialSignatureSource>) -> Self {
+        Self { key, source }
+    }
+
+    pub fn from_config(config: &MultisigConfig) -> Result<Self, Error> {
+        let public_keys = config
+            .public_keys
+            .iter()
+            .map(|public_key| {
+                base64::decode(public_key)
+                    .ok()
+                    .and_then(|bytes| PublicKey::from_slice(&bytes).ok())
+                    .ok_or_else(|| {
+                        Error::invalid_multisig(format!(
+                            "`{public_key}` is not a base64-encoded secp256k1 public key"
+                        ))
+                    })
+            })
+            .collect::<Result<_, _>>()?;
+
+        let key = MultisigKey::new(config.threshold, public_keys)?;
+        let source = Arc::new(CommandSignatureSource::new(config.cosigner_command.clone()));
+
+        Ok(Self::new(key, source))
+    }
+
+    pub fn key(&self) -> &MultisigKey {
+        &self.key
+    }
+
+    /// Returns the signature of the given bytes by the member with the given public key.
+    pub fn partial_signature(
+        &self,
+        public_key: &PublicKey,
+        sign_bytes: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+        self.source.partial_signature(public_key, sign_bytes)
+    }
+}
@@ -6,6 +6,7 @@
     rust_2018_idioms
 )]
 #![allow(clippy::too_many_arguments)]
+#![recursion_limit = "256"]
 // TODO: disable unwraps:
 //  https://github.com/informalsystems/hermes/issues/987
 // #![cfg_attr(not(test), deny(clippy::unwrap_used))]
@@ -20,6 +21,7 @@ extern crate alloc;
 
 pub mod account;
 pub mod cache;
+pub mod canary;
 pub mod chain;
 pub mod channel;
 pub mod client_state;
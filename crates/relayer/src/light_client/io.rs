@@ -1,8 +1,13 @@
+use std::sync::Arc;
+
 use tendermint::{account, block::Height};
 use tendermint_light_client::{
     components::io::{AtHeight, Io, IoError, ProdIo},
     types::LightBlock,
 };
+use tendermint_rpc::endpoint::validators::DEFAULT_VALIDATORS_PER_PAGE;
+
+use crate::chain::cosmos::request_rate::{PacedRpcClient, RequestRateLimiter};
 
 #[derive(Clone, Debug)]
 pub enum AnyIo {
@@ -18,23 +23,11 @@ impl AnyIo {
         }
     }
 
-    pub fn fetch_validator_set(
-        &self,
-        height: AtHeight,
-        proposer_address: Option<account::Id>,
-    ) -> Result<tendermint::validator::Set, IoError> {
-        match self {
-            AnyIo::Prod(io) => io.fetch_validator_set(height, proposer_address),
-            AnyIo::RestartAware(io) => io.fetch_validator_set(height, proposer_address),
-        }
-    }
-}
-
-impl Io for AnyIo {
-    fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, IoError> {
+    /// The IO to fetch the light block at the given height from.
+    fn io_at(&self, height: &AtHeight) -> &ProdIo {
         match self {
-            AnyIo::Prod(io) => io.fetch_light_block(height),
-            AnyIo::RestartAware(io) => io.fetch_light_block(height),
+            AnyIo::Prod(io) => io,
+            AnyIo::RestartAware(io) => io.io_at(height),
         }
     }
 }
@@ -59,27 +52,94 @@ impl RestartAwareIo {
         self.live_io.rpc_client()
     }
 
+    fn io_at(&self, height: &AtHeight) -> &ProdIo {
+        match height {
+            AtHeight::At(height) if *height <= self.restart_height => &self.archive_io,
+            _ => &self.live_io,
+        }
+    }
+}
+
+/// Light client IO whose RPC requests are paced by the limiter of the chain, if any.
+///
+/// The validator sets are fetched page by page by the RPC client, the number of pages
+/// being only known once the set is fetched. The first page is paced like any other
+/// request, the next ones are accounted for once the set is fetched, delaying the
+/// requests that follow.
+#[derive(Clone, Debug)]
+pub struct PacedIo {
+    io: AnyIo,
+    limiter: Option<Arc<RequestRateLimiter>>,
+}
+
+impl PacedIo {
+    pub fn new(io: AnyIo, limiter: Option<Arc<RequestRateLimiter>>) -> Self {
+        Self { io, limiter }
+    }
+
+    pub fn rpc_client(&self) -> &tendermint_rpc::HttpClient {
+        self.io.rpc_client()
+    }
+
+    /// The RPC client of the IO, paced by the same limiter.
+    pub fn paced_rpc_client(&self) -> PacedRpcClient {
+        PacedRpcClient::new(self.io.rpc_client().clone(), self.limiter.clone())
+    }
+
     pub fn fetch_validator_set(
         &self,
         height: AtHeight,
         proposer_address: Option<account::Id>,
     ) -> Result<tendermint::validator::Set, IoError> {
-        let io = match height {
-            AtHeight::At(height) if height <= self.restart_height => &self.archive_io,
-            _ => &self.live_io,
-        };
+        self.fetch_validator_set_from(self.io.io_at(&height), height, proposer_address)
+    }
 
-        io.fetch_validator_set(height, proposer_address)
+    fn fetch_validator_set_from(
+        &self,
+        io: &ProdIo,
+        height: AtHeight,
+        proposer_address: Option<account::Id>,
+    ) -> Result<tendermint::validator::Set, IoError> {
+        self.pace(1);
+        let validator_set = io.fetch_validator_set(height, proposer_address)?;
+
+        let pages = validator_set
+            .validators()
+            .len()
+            .div_ceil(DEFAULT_VALIDATORS_PER_PAGE.into());
+        self.pace(pages.saturating_sub(1));
+
+        Ok(validator_set)
+    }
+
+    fn pace(&self, requests: usize) {
+        if let Some(limiter) = &self.limiter {
+            (0..requests).for_each(|_| limiter.wait_blocking());
+        }
     }
 }
 
-impl Io for RestartAwareIo {
+impl Io for PacedIo {
     fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, IoError> {
-        let io = match height {
-            AtHeight::At(height) if height <= self.restart_height => &self.archive_io,
-            _ => &self.live_io,
-        };
+        // The whole light block is fetched from the IO serving the requested height
+        let io = self.io.io_at(&height);
+
+        self.pace(1);
+        let signed_header = io.fetch_signed_header(height)?;
+
+        let height = signed_header.header.height;
+        let proposer_address = signed_header.header.proposer_address;
+
+        let validator_set =
+            self.fetch_validator_set_from(io, height.into(), Some(proposer_address))?;
+        let next_validator_set =
+            self.fetch_validator_set_from(io, height.increment().into(), None)?;
 
-        io.fetch_light_block(height)
+        Ok(LightBlock::new(
+            signed_header,
+            validator_set,
+            next_validator_set,
+            io.peer_id(),
+        ))
     }
 }
@@ -1,21 +1,25 @@
 mod detector;
 
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use itertools::Itertools;
-use tendermint::Time;
+use tendermint::{account, Hash, Time};
 use tracing::{debug, error, trace, warn};
 
 use tendermint_light_client::{
     components::{
         self,
-        io::{AtHeight, Io, ProdIo},
+        io::{AtHeight, Io, IoError, ProdIo},
     },
     light_client::LightClient as TmLightClient,
     state::State as LightClientState,
     store::{memory::MemoryStore, LightStore},
-    verifier::types::{Height as TMHeight, LightBlock, PeerId, Status},
-    verifier::ProdVerifier,
+    verifier::options::Options,
+    verifier::types::{
+        Height as TMHeight, LightBlock, PeerId, Status, UntrustedBlockState, ValidatorSet,
+    },
+    verifier::{ProdVerifier, Verdict, Verifier},
 };
 use tendermint_light_client_detector::Divergence;
 use tendermint_rpc as rpc;
@@ -28,23 +32,27 @@ use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use ibc_relayer_types::Height as ICSHeight;
 
 use crate::{
-    chain::cosmos::{config::CosmosSdkConfig, CosmosSdkChain},
+    chain::cosmos::{
+        config::CosmosSdkConfig,
+        request_rate::RequestRateLimiter,
+        sticky_session::{build_rpc_client, Session},
+        CosmosSdkChain,
+    },
     chain::penumbra::config::PenumbraConfig,
     client_state::AnyClientState,
     error::Error,
     misbehaviour::{AnyMisbehaviour, MisbehaviourEvidence},
-    HERMES_VERSION,
 };
 
 use super::{
-    io::{AnyIo, RestartAwareIo},
+    io::{AnyIo, PacedIo, RestartAwareIo},
     Verified,
 };
 
 pub struct LightClient {
     chain_id: ChainId,
     peer_id: PeerId,
-    io: AnyIo,
+    io: PacedIo,
     enable_verification: bool,
 }
 
@@ -105,13 +113,21 @@ impl super::LightClient<CosmosSdkChain> for LightClient {
             });
         }
 
-        let client = self.prepare_client(client_state, now)?;
+        let mismatch = Arc::new(Mutex::new(None));
+        let client = self.prepare_client(client_state, now, mismatch.clone())?;
         let mut state = self.prepare_state(trusted_height)?;
 
         // Verify the target header
         let target = client
             .verify_to_target(target_height.into(), &mut state)
-            .map_err(|e| Error::light_client_verification(self.chain_id.to_string(), e))?;
+            .map_err(|e| {
+                // Report a validator set which still does not match its header after
+                // being re-fetched, rather than the opaque verification error it caused
+                match mismatch.lock().unwrap_or_else(|e| e.into_inner()).take() {
+                    Some(mismatch) => mismatch.into_error(&self.chain_id),
+                    None => Error::light_client_verification(self.chain_id.to_string(), e),
+                }
+            })?;
 
         // Collect the verification trace for the target block
         let target_trace = state.get_trace(target.height());
@@ -191,7 +207,7 @@ impl super::LightClient<CosmosSdkChain> for LightClient {
 
         let divergence = detector::detect(
             self.peer_id,
-            self.io.rpc_client().clone(),
+            self.io.clone(),
             target_block,
             trusted_block,
             client_state,
@@ -215,7 +231,7 @@ impl super::LightClient<CosmosSdkChain> for LightClient {
                 std::thread::sleep(Duration::from_secs(5));
 
                 match detector::report_evidence(
-                    self.io.rpc_client().clone(),
+                    self.io.paced_rpc_client(),
                     evidence.against_primary,
                 ) {
                     Ok(hash) => warn!("evidence reported to RPC witness node with hash: {hash}"),
@@ -263,21 +279,53 @@ impl super::LightClient<CosmosSdkChain> for LightClient {
     }
 }
 
+/// Verifies the `target` light block against the `trusted` one the same way the header
+/// of a `MsgUpdateClient` is verified by a Tendermint client, without fetching anything
+/// from the chain.
+pub fn verify_update_header(
+    trusted: &LightBlock,
+    target: &LightBlock,
+    options: &Options,
+    now: Time,
+) -> Verdict {
+    // As in a `MsgUpdateClient`, the next validator set of the target header is not known
+    let untrusted = UntrustedBlockState {
+        signed_header: &target.signed_header,
+        validators: &target.validators,
+        next_validators: None,
+    };
+
+    ProdVerifier::default().verify_update_header(
+        untrusted,
+        trusted.as_trusted_state(),
+        options,
+        now,
+    )
+}
+
 fn io_for_addr(
     addr: &rpc::Url,
     peer_id: PeerId,
-    timeout: Option<Duration>,
+    timeout: Duration,
+    session: Option<&Session>,
 ) -> Result<ProdIo, Error> {
-    let rpc_client = rpc::HttpClient::builder(addr.clone().try_into().unwrap())
-        .user_agent(format!("hermes/{}", HERMES_VERSION))
-        .build()
-        .map_err(|e| Error::rpc(addr.clone(), e))?;
-    Ok(ProdIo::new(peer_id, rpc_client, timeout))
+    let rpc_client = build_rpc_client(addr, timeout, session)?;
+    Ok(ProdIo::new(peer_id, rpc_client, Some(timeout)))
 }
 
 impl LightClient {
-    pub fn from_penumbra_config(config: &PenumbraConfig, peer_id: PeerId) -> Result<Self, Error> {
-        let live_io = io_for_addr(&config.rpc_addr, peer_id, Some(config.rpc_timeout))?;
+    /// The id of the full node the light client queries.
+    pub fn peer_id(&self) -> PeerId {
+        self.peer_id
+    }
+
+    /// The queries to the full node are paced by the given limiter of the chain, if any.
+    pub fn from_penumbra_config(
+        config: &PenumbraConfig,
+        peer_id: PeerId,
+        limiter: Option<Arc<RequestRateLimiter>>,
+    ) -> Result<Self, Error> {
+        let live_io = io_for_addr(&config.rpc_addr, peer_id, config.rpc_timeout, None)?;
 
         let io = match &config.genesis_restart {
             None => AnyIo::Prod(live_io),
@@ -285,7 +333,8 @@ impl LightClient {
                 let archive_io = io_for_addr(
                     &genesis_restart.archive_addr,
                     peer_id,
-                    Some(config.rpc_timeout),
+                    config.rpc_timeout,
+                    None,
                 )?;
 
                 AnyIo::RestartAware(RestartAwareIo::new(
@@ -304,17 +353,22 @@ impl LightClient {
         Ok(Self {
             chain_id: config.id.clone(),
             peer_id,
-            io,
+            io: PacedIo::new(io, limiter),
 
             enable_verification,
         })
     }
 
+    /// The queries to the full node are pinned to the same backend node
+    /// as the ones of the chain endpoint through the given session, if any,
+    /// and paced by the given limiter of the chain, if any.
     pub fn from_cosmos_sdk_config(
         config: &CosmosSdkConfig,
         peer_id: PeerId,
+        session: Option<&Session>,
+        limiter: Option<Arc<RequestRateLimiter>>,
     ) -> Result<Self, Error> {
-        let live_io = io_for_addr(&config.rpc_addr, peer_id, Some(config.rpc_timeout))?;
+        let live_io = io_for_addr(&config.rpc_addr, peer_id, config.rpc_timeout, session)?;
 
         let io = match &config.genesis_restart {
             None => AnyIo::Prod(live_io),
@@ -322,7 +376,8 @@ impl LightClient {
                 let archive_io = io_for_addr(
                     &genesis_restart.archive_addr,
                     peer_id,
-                    Some(config.rpc_timeout),
+                    config.rpc_timeout,
+                    None,
                 )?;
 
                 AnyIo::RestartAware(RestartAwareIo::new(
@@ -341,7 +396,7 @@ impl LightClient {
         Ok(Self {
             chain_id: config.id.clone(),
             peer_id,
-            io,
+            io: PacedIo::new(io, limiter),
 
             enable_verification,
         })
@@ -351,6 +406,7 @@ impl LightClient {
         &self,
         client_state: &AnyClientState,
         now: Time,
+        mismatch: Arc<Mutex<Option<ValidatorSetMismatch>>>,
     ) -> Result<TmLightClient, Error> {
         let clock = components::clock::FixedClock::new(now);
         let verifier = ProdVerifier::default();
@@ -366,7 +422,10 @@ impl LightClient {
             clock,
             scheduler,
             verifier,
-            self.io.clone(),
+            ValidatorSetCheckingIo {
+                io: self.io.clone(),
+                mismatch,
+            },
         ))
     }
 
@@ -380,9 +439,20 @@ impl LightClient {
     }
 
     fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, Error> {
-        self.io
+        let light_block = self
+            .io
             .fetch_light_block(height)
-            .map_err(|e| Error::light_client_io(self.chain_id.to_string(), e))
+            .and_then(|light_block| {
+                refetch_validator_sets(light_block, |height, proposer_address| {
+                    self.io.fetch_validator_set(height, proposer_address)
+                })
+            })
+            .map_err(|e| Error::light_client_io(self.chain_id.to_string(), e))?;
+
+        match validator_set_mismatch(&light_block) {
+            Some(mismatch) => Err(mismatch.into_error(&self.chain_id)),
+            None => Ok(light_block),
+        }
     }
 
     fn adjust_headers(
@@ -450,3 +520,320 @@ impl LightClient {
         Ok((target_header, supporting_headers))
     }
 }
+
+/// Maximum number of times the validator sets of a light block are re-fetched
+/// when they do not match the hashes committed to in its header.
+const MAX_VALIDATOR_SET_REFETCHES: usize = 2;
+
+/// A validator set which does not match the hash committed to in a header.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct ValidatorSetMismatch {
+    height: TMHeight,
+    expected: Hash,
+    got: Hash,
+}
+
+impl ValidatorSetMismatch {
+    fn into_error(self, chain_id: &ChainId) -> Error {
+        Error::light_client_validator_set_hash_mismatch(
+            chain_id.to_string(),
+            self.height,
+            self.expected,
+            self.got,
+        )
+    }
+}
+
+/// Checks that the validator set and next validator set of the given light block
+/// match the hashes committed to in its header.
+fn validator_set_mismatch(light_block: &LightBlock) -> Option<ValidatorSetMismatch> {
+    let header = &light_block.signed_header.header;
+
+    let validators_hash = light_block.validators.hash();
+    if validators_hash != header.validators_hash {
+        return Some(ValidatorSetMismatch {
+            height: header.height,
+            expected: header.validators_hash,
+            got: validators_hash,
+        });
+    }
+
+    let next_validators_hash = light_block.next_validators.hash();
+    if next_validators_hash != header.next_validators_hash {
+        return Some(ValidatorSetMismatch {
+            height: header.height.increment(),
+            expected: header.next_validators_hash,
+            got: next_validators_hash,
+        });
+    }
+
+    None
+}
+
+/// Re-fetches the validator sets of the given light block which do not match the hashes
+/// committed to in its header, as a full node may transiently return the validator set
+/// of another height while the validator set of the chain changes rapidly.
+///
+/// The returned light block may still have mismatching validator sets if they do not
+/// match after [`MAX_VALIDATOR_SET_REFETCHES`] attempts.
+fn refetch_validator_sets(
+    mut light_block: LightBlock,
+    mut fetch_validator_set: impl FnMut(AtHeight, Option<account::Id>) -> Result<ValidatorSet, IoError>,
+) -> Result<LightBlock, IoError> {
+    for attempt in 1..=MAX_VALIDATOR_SET_REFETCHES {
+        let Some(mismatch) = validator_set_mismatch(&light_block) else {
+            break;
+        };
+
+        warn!(
+            height = %mismatch.height,
+            expected = %mismatch.expected,
+            got = %mismatch.got,
+            attempt,
+            "validator set hash mismatch, re-fetching the validator set"
+        );
+
+        let header = &light_block.signed_header.header;
+
+        if mismatch.height == header.height {
+            light_block.validators =
+                fetch_validator_set(AtHeight::At(header.height), Some(header.proposer_address))?;
+        } else {
+            light_block.next_validators = fetch_validator_set(AtHeight::At(mismatch.height), None)?;
+        }
+    }
+
+    Ok(light_block)
+}
+
+/// Light client IO which re-fetches the validator sets of the fetched light blocks
+/// when they do not match the hashes committed to in their header.
+///
+/// If they still do not match, the light block is returned as is, to be rejected by the
+/// verifier, and the mismatch is recorded in order to report it instead of the
+/// verification error.
+#[derive(Clone, Debug)]
+struct ValidatorSetCheckingIo {
+    io: PacedIo,
+    mismatch: Arc<Mutex<Option<ValidatorSetMismatch>>>,
+}
+
+impl Io for ValidatorSetCheckingIo {
+    fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, IoError> {
+        let light_block = refetch_validator_sets(
+            self.io.fetch_light_block(height)?,
+            |height, proposer_address| self.io.fetch_validator_set(height, proposer_address),
+        )?;
+
+        if let Some(mismatch) = validator_set_mismatch(&light_block) {
+            *self.mismatch.lock().unwrap_or_else(|e| e.into_inner()) = Some(mismatch);
+        }
+
+        Ok(light_block)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tendermint::validator;
+    use tendermint_light_client::verifier::errors::VerificationErrorDetail;
+    use tendermint_light_client::verifier::types::TrustThreshold;
+    use tendermint_testgen::light_block::{LightBlock as TestgenLightBlock, TmLightBlock};
+    use tendermint_testgen::validator::generate_validators;
+    use tendermint_testgen::{Generator, Header, Validator};
+
+    fn generate(light_block: TestgenLightBlock) -> LightBlock {
+        let TmLightBlock {
+            signed_header,
+            validators,
+            next_validators,
+            provider,
+        } = light_block.generate().unwrap();
+
+        LightBlock::new(signed_header, validators, next_validators, provider)
+    }
+
+    fn light_block(height: u64) -> LightBlock {
+        generate(TestgenLightBlock::new_default(height))
+    }
+
+    fn verify_options() -> Options {
+        Options {
+            trust_threshold: TrustThreshold::ONE_THIRD,
+            trusting_period: Duration::from_secs(14 * 24 * 3600),
+            clock_drift: Duration::from_secs(5),
+        }
+    }
+
+    fn verify_at_target_time(trusted: &LightBlock, target: &LightBlock) -> Verdict {
+        let now = (target.time() + Duration::from_secs(1)).unwrap();
+        verify_update_header(trusted, target, &verify_options(), now)
+    }
+
+    #[test]
+    fn verify_valid_update_header() {
+        let trusted = TestgenLightBlock::new_default(1);
+        let target = trusted.next();
+
+        let verdict = verify_at_target_time(&generate(trusted), &generate(target));
+
+        assert_eq!(verdict, Verdict::Success);
+    }
+
+    #[test]
+    fn verify_update_header_signed_by_other_validators() {
+        let trusted = light_block(1);
+
+        // An adjacent header signed by validators other than the trusted next validators
+        let validators = [
+            Validator::new("3").voting_power(50),
+            Validator::new("4").voting_power(50),
+        ];
+        let target = generate(TestgenLightBlock::new_default_with_header(
+            Header::new(&validators)
+                .height(2)
+                .chain_id("test-chain")
+                .next_validators(&validators)
+                .time(Time::from_unix_timestamp(2, 0).unwrap()),
+        ));
+
+        let verdict = verify_at_target_time(&trusted, &target);
+
+        match verdict {
+            Verdict::Invalid(VerificationErrorDetail::InvalidNextValidatorSet(e)) => {
+                assert_eq!(
+                    e.header_next_validators_hash,
+                    target.signed_header.header.validators_hash
+                );
+                assert_eq!(
+                    e.next_validators_hash,
+                    trusted.signed_header.header.next_validators_hash
+                );
+            }
+            verdict => panic!("unexpected verdict: {verdict:?}"),
+        }
+    }
+
+    #[test]
+    fn verify_update_header_without_enough_trust() {
+        let trusted = light_block(1);
+
+        // A non-adjacent header signed by none of the trusted validators
+        let validators = [
+            Validator::new("3").voting_power(50),
+            Validator::new("4").voting_power(50),
+        ];
+        let target = generate(TestgenLightBlock::new_default_with_header(
+            Header::new(&validators)
+                .height(3)
+                .chain_id("test-chain")
+                .next_validators(&validators)
+                .time(Time::from_unix_timestamp(3, 0).unwrap()),
+        ));
+
+        let verdict = verify_at_target_time(&trusted, &target);
+
+        match verdict {
+            Verdict::NotEnoughTrust(tally) => {
+                assert_eq!(tally.tallied, 0);
+                assert_eq!(tally.total, 100);
+            }
+            verdict => panic!("unexpected verdict: {verdict:?}"),
+        }
+    }
+
+    fn fetched_height(height: AtHeight) -> TMHeight {
+        match height {
+            AtHeight::At(height) => height,
+            AtHeight::Highest => panic!("unexpected fetch at the highest height"),
+        }
+    }
+
+    fn other_validator_set() -> ValidatorSet {
+        validator::Set::without_proposer(generate_validators(&[Validator::new("other")]).unwrap())
+    }
+
+    #[test]
+    fn transient_validator_set_mismatch_is_recovered() {
+        let expected = light_block(5);
+
+        // The full node returned the validator set of another height,
+        // and keeps doing so for the first re-fetch
+        let mut fetched = expected.clone();
+        fetched.validators = other_validator_set();
+
+        let mut fetches = vec![];
+        let recovered = refetch_validator_sets(fetched, |height, proposer_address| {
+            fetches.push((fetched_height(height), proposer_address));
+
+            if fetches.len() < MAX_VALIDATOR_SET_REFETCHES {
+                Ok(other_validator_set())
+            } else {
+                Ok(expected.validators.clone())
+            }
+        })
+        .unwrap();
+
+        assert_eq!(validator_set_mismatch(&recovered), None);
+        assert_eq!(recovered.validators.hash(), expected.validators.hash());
+        assert_eq!(fetches.len(), MAX_VALIDATOR_SET_REFETCHES);
+        assert!(fetches.iter().all(|(height, proposer_address)| {
+            *height == TMHeight::from(5_u32)
+                && *proposer_address == Some(expected.signed_header.header.proposer_address)
+        }));
+    }
+
+    #[test]
+    fn transient_next_validator_set_mismatch_is_recovered() {
+        let expected = light_block(5);
+
+        let mut fetched = expected.clone();
+        fetched.next_validators = other_validator_set();
+
+        let mut fetched_heights = vec![];
+        let recovered = refetch_validator_sets(fetched, |height, _| {
+            fetched_heights.push(fetched_height(height));
+            Ok(expected.next_validators.clone())
+        })
+        .unwrap();
+
+        assert_eq!(validator_set_mismatch(&recovered), None);
+        assert_eq!(fetched_heights, [TMHeight::from(6_u32)]);
+    }
+
+    #[test]
+    fn persistent_validator_set_mismatch_is_reported() {
+        let expected = light_block(5);
+
+        let mut fetched = expected.clone();
+        fetched.validators = other_validator_set();
+
+        let mut fetches = 0;
+        let light_block = refetch_validator_sets(fetched, |_, _| {
+            fetches += 1;
+            Ok(other_validator_set())
+        })
+        .unwrap();
+
+        assert_eq!(fetches, MAX_VALIDATOR_SET_REFETCHES);
+
+        let mismatch = validator_set_mismatch(&light_block).unwrap();
+
+        assert_eq!(
+            mismatch,
+            ValidatorSetMismatch {
+                height: TMHeight::from(5_u32),
+                expected: expected.signed_header.header.validators_hash,
+                got: other_validator_set().hash(),
+            }
+        );
+
+        let chain_id = ChainId::from_string("ibc-0");
+        assert!(matches!(
+            mismatch.into_error(&chain_id).detail(),
+            crate::error::ErrorDetail::LightClientValidatorSetHashMismatch(_)
+        ));
+    }
+}
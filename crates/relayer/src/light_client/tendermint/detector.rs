@@ -6,24 +6,25 @@ use tendermint::{
 };
 use tendermint_light_client::{
     builder::LightClientBuilder,
-    components::{clock::FixedClock, io::ProdIo, scheduler},
+    components::{clock::FixedClock, scheduler},
     predicates::ProdPredicates,
     store::memory::MemoryStore,
     types::{LightBlock, PeerId},
     verifier::ProdVerifier,
 };
 use tendermint_light_client_detector::{detect_divergence, Divergence, Provider};
-use tendermint_rpc::{Client, HttpClient};
 
 use ibc_relayer_types::clients::ics07_tendermint::client_state::ClientState;
 
+use crate::chain::cosmos::request_rate::PacedRpcClient;
+use crate::light_client::io::PacedIo;
 use crate::{error::Error, util::block_on};
 
 type Hasher = tendermint::crypto::default::Sha256;
 
 pub fn detect(
     peer_id: PeerId,
-    rpc_client: HttpClient,
+    io: PacedIo,
     target_block: LightBlock,
     trusted_block: LightBlock,
     client_state: &ClientState,
@@ -31,7 +32,7 @@ pub fn detect(
 ) -> Result<Option<Divergence>, Error> {
     let primary_trace = vec![trusted_block.clone(), target_block];
     let options = client_state.as_light_client_options();
-    let mut provider = make_provider(peer_id, rpc_client, client_state, trusted_block, now)?;
+    let mut provider = make_provider(peer_id, io, client_state, trusted_block, now)?;
 
     let divergence = block_on(detect_divergence::<Hasher>(
         None,
@@ -69,9 +70,10 @@ pub fn detect(
     }
 }
 
+/// The light blocks are fetched through the given IO, paced by the limiter of the chain.
 fn make_provider(
     peer_id: PeerId,
-    rpc_client: HttpClient,
+    io: PacedIo,
     client_state: &ClientState,
     trusted_block: LightBlock,
     now: Time,
@@ -83,7 +85,7 @@ fn make_provider(
         peer_id,
         options,
         light_store,
-        Box::new(ProdIo::new(peer_id, rpc_client.clone(), None)),
+        Box::new(io.clone()),
         Box::new(FixedClock::new(now)),
         Box::<ProdVerifier>::default(),
         Box::new(scheduler::basic_bisecting_schedule),
@@ -98,12 +100,12 @@ fn make_provider(
     Ok(Provider::new(
         client_state.chain_id.to_string(),
         instance,
-        rpc_client,
+        io.rpc_client().clone(),
     ))
 }
 
 pub fn report_evidence(
-    rpc_client: HttpClient,
+    rpc_client: PacedRpcClient,
     attack: LightClientAttackEvidence,
 ) -> Result<Hash, Error> {
     block_on(rpc_client.broadcast_evidence(Evidence::from(attack)))
@@ -1,3 +1,4 @@
+use core::time::Duration;
 use ibc_relayer_types::core::{
     ics03_connection::connection::State as ConnectionState,
     ics04_channel::channel::State as ChannelState,
@@ -14,17 +15,26 @@ use crate::link::error::LinkError;
 use crate::{
     chain::requests::{QueryChannelRequest, QueryHeight},
     config::types::ics20_field_size_limit::Ics20FieldSizeLimit,
+    config::{ChannelVerification, ClearOrder},
 };
 
+pub mod clear_progress;
 pub mod cli;
 pub mod error;
 pub mod operational_data;
+pub mod packet_data;
 pub mod packet_events;
+pub mod relay_plan;
 
+mod compaction;
 mod pending;
+mod receipt_recheck;
 mod relay_path;
 mod relay_sender;
 mod relay_summary;
+mod relayed_packets;
+mod sent_packets;
+mod timeout_lane;
 mod tx_hashes;
 
 use tx_hashes::TxHashes;
@@ -32,6 +42,8 @@ use tx_hashes::TxHashes;
 // Re-export the telemetries summary
 pub use relay_summary::RelaySummary;
 
+pub use clear_progress::{ClearingStatus, SequenceRange};
+pub use packet_data::{JsonDecoder, PacketDataDecoder, PacketDataDecoderRegistry, ProtobufDecoder};
 pub use relay_path::{RelayPath, Resubmit};
 
 #[derive(Clone, Debug)]
@@ -41,6 +53,9 @@ pub struct LinkParameters {
     pub max_memo_size: Ics20FieldSizeLimit,
     pub max_receiver_size: Ics20FieldSizeLimit,
     pub exclude_src_sequences: Vec<Sequence>,
+    pub max_clear_duration: Option<Duration>,
+    pub verification: ChannelVerification,
+    pub clear_order: ClearOrder,
 }
 
 pub struct Link<ChainA: ChainHandle, ChainB: ChainHandle> {
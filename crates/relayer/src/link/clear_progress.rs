@@ -0,0 +1,416 @@
+use core::fmt::{Display, Error as FmtError, Formatter};
+use core::time::Duration;
+use std::time::Instant;
+
+use ibc_relayer_types::core::ics04_channel::packet::Sequence;
+
+use crate::config::ClearOrder;
+use crate::link::error::LinkError;
+use crate::util::lock::{LockExt, RwArc};
+
+/// The time budget of a packet clearing pass.
+#[derive(Copy, Clone, Debug)]
+pub struct ClearBudget {
+    deadline: Option<Instant>,
+}
+
+impl ClearBudget {
+    /// Starts the budget of a pass which may last for `max_duration`,
+    /// or which is not limited in time if `None`.
+    pub fn start(max_duration: Option<Duration>) -> Self {
+        Self {
+            deadline: max_duration.map(|duration| Instant::now() + duration),
+        }
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
+
+/// The sequences of the packets considered by a packet clearing pass,
+/// between `start` and `end` inclusive. A missing bound leaves the range
+/// open on that side.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct SequenceRange {
+    pub start: Option<Sequence>,
+    pub end: Option<Sequence>,
+}
+
+impl SequenceRange {
+    /// The sequences between `start` and `end` inclusive. Fails if `start` is greater
+    /// than `end`, as the range would then hold no sequence.
+    pub fn new(start: Option<Sequence>, end: Option<Sequence>) -> Result<Self, LinkError> {
+        match (start, end) {
+            (Some(start), Some(end)) if start > end => {
+                Err(LinkError::invalid_sequence_range(start, end))
+            }
+            _ => Ok(Self { start, end }),
+        }
+    }
+
+    /// The range of the sequences up to `end` inclusive.
+    pub fn up_to(end: Sequence) -> Self {
+        Self {
+            start: None,
+            end: Some(end),
+        }
+    }
+
+    /// The range of all the sequences.
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// Whether the range holds all the sequences.
+    pub fn is_all(&self) -> bool {
+        self.start.is_none() && self.end.is_none()
+    }
+
+    pub fn contains(&self, sequence: &Sequence) -> bool {
+        self.start.map_or(true, |start| *sequence >= start)
+            && self.end.map_or(true, |end| *sequence <= end)
+    }
+}
+
+impl Display for SequenceRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        let bound = |bound: Option<Sequence>| bound.map(|s| s.to_string()).unwrap_or_default();
+
+        write!(f, "[{}..={}]", bound(self.start), bound(self.end))
+    }
+}
+
+/// Whether a packet clearing pass went through all the packets left to clear.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ClearOutcome {
+    Completed,
+    Yielded,
+}
+
+/// How far the relaying of the packets pending on a path got, telling apart a path
+/// with nothing left to relay from one whose relayed packets are not confirmed yet.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ClearingStatus {
+    /// Packets or acknowledgements are left to relay.
+    Pending { packets: usize, acks: usize },
+    /// Transactions relaying packets or acknowledgements wait for their confirmation,
+    /// until which the packets they relay still appear as pending on the chains.
+    AwaitingConfirmations,
+    /// Nothing is left to relay, and all the transactions relaying packets were confirmed.
+    Complete,
+}
+
+impl ClearingStatus {
+    pub fn new(pending_txs: usize, packets: usize, acks: usize) -> Self {
+        if pending_txs > 0 {
+            Self::AwaitingConfirmations
+        } else if packets > 0 || acks > 0 {
+            Self::Pending { packets, acks }
+        } else {
+            Self::Complete
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        *self == Self::Complete
+    }
+}
+
+/// Tracks the last sequence processed by a packet clearing pass which
+/// stopped before clearing all the pending packets, so that the next
+/// pass resumes right after it instead of starting over.
+#[derive(Debug)]
+pub struct ClearProgress {
+    order: ClearOrder,
+    last_sequence: RwArc<Option<Sequence>>,
+}
+
+impl ClearProgress {
+    pub fn new() -> Self {
+        Self::with_order(ClearOrder::default())
+    }
+
+    /// Tracks the progress of passes clearing the packets in the given order.
+    pub fn with_order(order: ClearOrder) -> Self {
+        Self {
+            order,
+            last_sequence: RwArc::new_lock(None),
+        }
+    }
+
+    /// Whether `sequence` comes after `last` in the order of the clearing.
+    fn comes_after(&self, sequence: Sequence, last: Sequence) -> bool {
+        match self.order {
+            ClearOrder::OldestFirst => sequence > last,
+            ClearOrder::NewestFirst => sequence < last,
+        }
+    }
+
+    /// Runs a clearing pass over the pending `sequences`, handing them over to
+    /// `clear_chunk` in chunks of `chunk_size` in the order of the clearing,
+    /// starting after the last sequence processed by the previous pass if it
+    /// stopped early. At most `clear_limit` sequences are cleared in the pass.
+    ///
+    /// The pass yields once its budget is exhausted, after clearing at least one chunk,
+    /// or as soon as `clear_chunk` returns `false`, in which case the chunk is cleared
+    /// again by the next pass.
+    pub fn run<E>(
+        &self,
+        sequences: &[Sequence],
+        clear_limit: usize,
+        chunk_size: usize,
+        budget: &ClearBudget,
+        mut clear_chunk: impl FnMut(&[Sequence]) -> Result<bool, E>,
+    ) -> Result<ClearOutcome, E> {
+        let last_sequence = *self.last_sequence.acquire_read();
+
+        let mut ordered = sequences.to_vec();
+        match self.order {
+            ClearOrder::OldestFirst => ordered.sort_unstable(),
+            ClearOrder::NewestFirst => ordered.sort_unstable_by(|a, b| b.cmp(a)),
+        }
+
+        let mut remaining: Vec<Sequence> = ordered
+            .iter()
+            .copied()
+            .filter(|sequence| last_sequence.map_or(true, |last| self.comes_after(*sequence, last)))
+            .collect();
+
+        // All the sequences after the last processed one have been cleared, start over
+        if remaining.is_empty() {
+            remaining = ordered;
+        }
+
+        remaining.truncate(clear_limit);
+
+        let mut chunks = remaining.chunks(chunk_size).peekable();
+
+        while let Some(chunk) = chunks.next() {
+            if !clear_chunk(chunk)? {
+                return Ok(ClearOutcome::Yielded);
+            }
+
+            *self.last_sequence.acquire_write() = chunk.last().copied();
+
+            if chunks.peek().is_some() && budget.is_exhausted() {
+                return Ok(ClearOutcome::Yielded);
+            }
+        }
+
+        *self.last_sequence.acquire_write() = None;
+
+        Ok(ClearOutcome::Completed)
+    }
+
+    /// Forgets the last sequence processed, so that the next pass starts over.
+    pub fn reset(&self) {
+        *self.last_sequence.acquire_write() = None;
+    }
+}
+
+impl Default for ClearProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::convert::Infallible;
+
+    use crate::link::error::LinkErrorDetail;
+
+    fn sequences(range: core::ops::RangeInclusive<u64>) -> Vec<Sequence> {
+        range.map(Sequence::from).collect()
+    }
+
+    fn run_pass(
+        progress: &ClearProgress,
+        pending: &[Sequence],
+        budget: &ClearBudget,
+        cleared: &mut Vec<Sequence>,
+    ) -> ClearOutcome {
+        progress
+            .run(pending, usize::MAX, 10, budget, |chunk| {
+                cleared.extend_from_slice(chunk);
+                Ok::<_, Infallible>(true)
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn unlimited_pass_clears_everything() {
+        let progress = ClearProgress::new();
+        let pending = sequences(1..=100);
+        let mut cleared = vec![];
+
+        let outcome = run_pass(&progress, &pending, &ClearBudget::start(None), &mut cleared);
+
+        assert_eq!(outcome, ClearOutcome::Completed);
+        assert_eq!(cleared, pending);
+    }
+
+    #[test]
+    fn exhausted_pass_yields_and_resumes() {
+        let progress = ClearProgress::new();
+        let mut pending = sequences(1..=100);
+        let mut passes = 0;
+        let mut all_cleared = vec![];
+
+        loop {
+            let budget = ClearBudget::start(Some(Duration::ZERO));
+            let mut cleared = vec![];
+
+            let outcome = run_pass(&progress, &pending, &budget, &mut cleared);
+            passes += 1;
+
+            // Each pass yields after a single chunk
+            assert_eq!(cleared.len(), 10);
+
+            // The cleared packets are not pending anymore
+            pending.retain(|sequence| !cleared.contains(sequence));
+            all_cleared.extend(cleared);
+
+            if outcome == ClearOutcome::Completed {
+                break;
+            }
+        }
+
+        assert_eq!(passes, 10);
+        assert_eq!(all_cleared, sequences(1..=100));
+    }
+
+    #[test]
+    fn pass_resumes_after_last_sequence_while_earlier_ones_are_pending() {
+        let progress = ClearProgress::new();
+        let budget = ClearBudget::start(Some(Duration::ZERO));
+
+        // Packets of the first chunk fail to be relayed and stay pending
+        let pending = sequences(1..=30);
+
+        let mut cleared = vec![];
+        assert_eq!(
+            run_pass(&progress, &pending, &budget, &mut cleared),
+            ClearOutcome::Yielded
+        );
+        assert_eq!(cleared, sequences(1..=10));
+
+        let mut cleared = vec![];
+        assert_eq!(
+            run_pass(&progress, &pending, &budget, &mut cleared),
+            ClearOutcome::Yielded
+        );
+        assert_eq!(cleared, sequences(11..=20));
+
+        let mut cleared = vec![];
+        assert_eq!(
+            run_pass(&progress, &pending, &budget, &mut cleared),
+            ClearOutcome::Completed
+        );
+        assert_eq!(cleared, sequences(21..=30));
+
+        // Once all the packets went through, clearing starts over
+        let mut cleared = vec![];
+        run_pass(&progress, &pending, &budget, &mut cleared);
+        assert_eq!(cleared, sequences(1..=10));
+    }
+
+    #[test]
+    fn failed_chunk_is_retried_by_next_pass() {
+        let progress = ClearProgress::new();
+        let budget = ClearBudget::start(None);
+        let pending = sequences(1..=30);
+
+        let mut attempted = vec![];
+        let outcome = progress
+            .run(&pending, usize::MAX, 10, &budget, |chunk| {
+                attempted.extend_from_slice(chunk);
+                Ok::<_, Infallible>(chunk[0] != Sequence::from(11))
+            })
+            .unwrap();
+
+        assert_eq!(outcome, ClearOutcome::Yielded);
+        assert_eq!(attempted, sequences(1..=20));
+
+        let mut cleared = vec![];
+        run_pass(&progress, &pending[10..], &budget, &mut cleared);
+        assert_eq!(cleared, sequences(11..=30));
+    }
+
+    #[test]
+    fn reset_pass_starts_over() {
+        let progress = ClearProgress::new();
+        let budget = ClearBudget::start(Some(Duration::ZERO));
+        let pending = sequences(1..=30);
+
+        let mut cleared = vec![];
+        run_pass(&progress, &pending, &budget, &mut cleared);
+        assert_eq!(cleared, sequences(1..=10));
+
+        progress.reset();
+
+        let mut cleared = vec![];
+        run_pass(&progress, &pending, &budget, &mut cleared);
+        assert_eq!(cleared, sequences(1..=10));
+    }
+
+    #[test]
+    fn sequence_range_bounds() {
+        let from_five = SequenceRange::new(Some(Sequence::from(5)), None).unwrap();
+        assert!(!from_five.contains(&Sequence::from(4)));
+        assert!(from_five.contains(&Sequence::from(5)));
+        assert!(from_five.contains(&Sequence::from(u64::MAX)));
+
+        let up_to_ten = SequenceRange::up_to(Sequence::from(10));
+        assert!(up_to_ten.contains(&Sequence::from(1)));
+        assert!(up_to_ten.contains(&Sequence::from(10)));
+        assert!(!up_to_ten.contains(&Sequence::from(11)));
+
+        assert!(SequenceRange::all().is_all());
+        assert!(!from_five.is_all());
+
+        let five = SequenceRange::new(Some(Sequence::from(5)), Some(Sequence::from(5))).unwrap();
+        assert!(five.contains(&Sequence::from(5)));
+        assert!(!five.contains(&Sequence::from(6)));
+    }
+
+    #[test]
+    fn inverted_sequence_range_is_rejected() {
+        let inverted = SequenceRange::new(Some(Sequence::from(10)), Some(Sequence::from(5)));
+
+        match inverted.unwrap_err().detail() {
+            LinkErrorDetail::InvalidSequenceRange(e) => {
+                assert_eq!((e.start, e.end), (Sequence::from(10), Sequence::from(5)));
+            }
+            e => panic!("unexpected error: {e}"),
+        }
+    }
+
+    #[test]
+    fn clearing_completes_once_confirmed_and_nothing_is_left() {
+        assert_eq!(
+            ClearingStatus::new(0, 3, 1),
+            ClearingStatus::Pending {
+                packets: 3,
+                acks: 1
+            }
+        );
+
+        // The relayed packets are still pending on chain until their transactions are confirmed
+        assert_eq!(
+            ClearingStatus::new(2, 3, 0),
+            ClearingStatus::AwaitingConfirmations
+        );
+        assert_eq!(
+            ClearingStatus::new(1, 0, 0),
+            ClearingStatus::AwaitingConfirmations
+        );
+        assert!(!ClearingStatus::new(1, 0, 0).is_complete());
+
+        assert!(ClearingStatus::new(0, 0, 0).is_complete());
+    }
+}
@@ -4,17 +4,17 @@ use std::time::{Duration, Instant};
 
 use ibc_relayer_types::core::ics04_channel::packet::Sequence;
 use itertools::Itertools;
-use tracing::{error_span, info};
+use tracing::{error_span, info, warn};
 
 use ibc_relayer_types::events::IbcEvent;
 use ibc_relayer_types::Height;
 
-use crate::chain::counterparty::{unreceived_acknowledgements, unreceived_packets};
 use crate::chain::handle::ChainHandle;
-use crate::chain::requests::{Paginate, Qualified};
+use crate::chain::requests::Qualified;
 use crate::chain::tracking::TrackingId;
 use crate::error::Error;
 use crate::event::IbcEventWithHeight;
+use crate::link::clear_progress::SequenceRange;
 use crate::link::error::LinkError;
 use crate::link::operational_data::{OperationalData, TrackedEvents};
 use crate::link::packet_events::{
@@ -27,6 +27,12 @@ use crate::path::PathIdentifiers;
 use crate::util::collate::CollatedIterExt;
 use crate::util::pretty::{PrettyDuration, PrettySlice};
 
+/// The maximum number of passes of [`Link::clear_packets_until_complete`].
+pub const MAX_CLEARING_PASSES: usize = 10;
+
+/// A step of a clearing pass, with its description.
+type ClearingStep<'a> = (&'a str, &'a dyn Fn() -> Result<Vec<IbcEvent>, LinkError>);
+
 impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
     /// Fetches an operational data that has fulfilled its predefined delay period. May _block_
     /// waiting for the delay period to pass.
@@ -62,8 +68,21 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         results: &mut Vec<IbcEvent>,
     ) -> Result<(), LinkError> {
         for od in from {
-            let mut last_res = self.relay_from_operational_data::<SyncSender>(od)?;
-            results.append(&mut last_res.events);
+            let lane = self.timeout_lane_for(&od);
+
+            if let Some(lane) = lane {
+                thread::sleep(lane.delay(Instant::now()));
+            }
+
+            // Each piece is committed before the next one is submitted
+            for od in self.split_for_relay(od) {
+                let mut last_res = self.relay_from_operational_data::<SyncSender>(od)?;
+                results.append(&mut last_res.events);
+            }
+
+            if let Some(lane) = lane {
+                lane.record_sent(Instant::now());
+            }
         }
 
         Ok(())
@@ -93,13 +112,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Link<ChainA, ChainB> {
         .entered();
 
         // Find the sequence numbers of unreceived packets
-        let (mut sequences, src_response_height) = unreceived_packets(
-            self.a_to_b.dst_chain(),
-            self.a_to_b.src_chain(),
-            &self.a_to_b.path_id,
-            Paginate::All,
-        )
-        .map_err(LinkError::supervisor)?;
+        let (mut sequences, src_response_height) = self.a_to_b.unreceived_packet_sequences()?;
 
         if sequences.is_empty() {
             return Ok(vec![]);
@@ -165,13 +178,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Link<ChainA, ChainB> {
         .entered();
 
         // Find the sequence numbers of unreceived acknowledgements
-        let Some((mut sequences, src_response_height)) = unreceived_acknowledgements(
-            self.a_to_b.dst_chain(),
-            self.a_to_b.src_chain(),
-            &self.a_to_b.path_id,
-            Paginate::All,
-        )
-        .map_err(LinkError::supervisor)?
+        let Some((mut sequences, src_response_height)) = self.a_to_b.unreceived_ack_sequences()?
         else {
             return Ok(vec![]);
         };
@@ -217,6 +224,86 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Link<ChainA, ChainB> {
         )
     }
 
+    /// Clears the packets pending on the channel in both directions, along with the
+    /// acknowledgements of the packets received along the way, pass after pass until
+    /// all the packets which were pending when the clearing started are relayed and
+    /// acknowledged. The packets sent in the meantime do not hold up the completion.
+    ///
+    /// Fails if some of them are still left to relay after [`MAX_CLEARING_PASSES`]
+    /// passes, eg. because the transactions relaying them keep failing.
+    pub fn clear_packets_until_complete(
+        &self,
+        reverse: &Link<ChainB, ChainA>,
+    ) -> Result<Vec<IbcEvent>, LinkError> {
+        let _span = error_span!(
+            "clear_packets_until_complete",
+            src_chain = %self.a_to_b.src_chain().id(),
+            src_port = %self.a_to_b.src_port_id(),
+            src_channel = %self.a_to_b.src_channel_id(),
+            dst_chain = %self.a_to_b.dst_chain().id(),
+        )
+        .entered();
+
+        let (a_packets, b_acks) = self.a_to_b.unrelayed_sequences()?;
+        let (b_packets, a_acks) = reverse.a_to_b.unrelayed_sequences()?;
+
+        // The packets sent later than the ones pending now have higher sequences. A side with
+        // no pending packet gets an empty range, as packet sequences start at 1.
+        let up_to_last = |packets: &[Sequence], acks: &[Sequence]| {
+            let last = packets.iter().chain(acks).max().copied();
+            SequenceRange::up_to(last.unwrap_or_default())
+        };
+
+        let a_sent = up_to_last(&a_packets, &a_acks);
+        let b_sent = up_to_last(&b_packets, &b_acks);
+
+        let max_block_time = self
+            .a_to_b
+            .src_max_block_time()?
+            .max(self.a_to_b.dst_max_block_time()?);
+
+        let mut events = vec![];
+
+        for pass in 1.. {
+            let forward = self.a_to_b.clearing_status(a_sent, b_sent)?;
+            let backward = reverse.a_to_b.clearing_status(b_sent, a_sent)?;
+
+            if forward.is_complete() && backward.is_complete() {
+                info!("all the pending packets were relayed and acknowledged");
+                return Ok(events);
+            }
+
+            if pass > MAX_CLEARING_PASSES {
+                break;
+            }
+
+            info!(pass, ?forward, ?backward, "clearing pending packets");
+
+            // Receive the packets first, so that their acks are relayed in the same pass
+            let steps: [ClearingStep<'_>; 4] = [
+                ("forward recv and timeout", &|| {
+                    self.relay_recv_packet_and_timeout_messages(vec![])
+                }),
+                ("reverse recv and timeout", &|| {
+                    reverse.relay_recv_packet_and_timeout_messages(vec![])
+                }),
+                ("reverse ack", &|| reverse.relay_ack_packet_messages(vec![])),
+                ("forward ack", &|| self.relay_ack_packet_messages(vec![])),
+            ];
+
+            for (desc, step) in steps {
+                match step() {
+                    Ok(mut step_events) => events.append(&mut step_events),
+                    Err(e) => warn!(pass, "failed to relay {desc} packets: {e}"),
+                }
+            }
+
+            thread::sleep(max_block_time);
+        }
+
+        Err(LinkError::clearing_incomplete(MAX_CLEARING_PASSES))
+    }
+
     fn relay_packet_messages<QueryFn>(
         &self,
         sequences: Vec<Sequence>,
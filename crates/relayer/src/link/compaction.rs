@@ -0,0 +1,249 @@
+//! The periodic compaction of the operational data scheduled by a relaying path, which
+//! drops the messages of the packets resolved out-of-band, eg. relayed by another relayer,
+//! which a long-running packet worker would otherwise keep around until submitting them.
+
+use alloc::collections::VecDeque;
+use core::ops::Range;
+
+use ibc_relayer_types::events::IbcEvent;
+
+use crate::link::operational_data::OperationalData;
+use crate::util::lock::{LockExt, RwArc};
+
+/// Tracks the position in a queue of operational data of the last message checked by
+/// a compaction pass, so that the next pass resumes right after it instead of checking
+/// the oldest messages over and over.
+#[derive(Debug)]
+pub struct Compaction {
+    next_position: RwArc<usize>,
+}
+
+impl Compaction {
+    pub fn new() -> Self {
+        Self {
+            next_position: RwArc::new_lock(0),
+        }
+    }
+
+    /// Drops the messages of `queue` whose packet was resolved according to `is_resolved`,
+    /// checking at most `limit` messages, followed by the operational data left without
+    /// messages. The memory held by the queue is released. Returns the number of messages
+    /// dropped.
+    ///
+    /// If `is_resolved` fails, the messages already found to be resolved are dropped.
+    pub fn run<E>(
+        &self,
+        queue: &mut VecDeque<OperationalData>,
+        limit: usize,
+        mut is_resolved: impl FnMut(&IbcEvent) -> Result<bool, E>,
+    ) -> Result<usize, E> {
+        let total: usize = queue.iter().map(|od| od.batch.len()).sum();
+
+        let mut start = *self.next_position.acquire_read();
+
+        // All the messages after the last checked one have been checked, start over
+        if start >= total {
+            start = 0;
+        }
+
+        let end = start.saturating_add(limit).min(total);
+
+        let mut dropped = 0;
+        let result = check_range(queue, start..end, &mut dropped, &mut is_resolved);
+
+        queue.retain(|od| !od.batch.is_empty());
+        queue.shrink_to_fit();
+
+        result?;
+
+        *self.next_position.acquire_write() = end - dropped;
+
+        Ok(dropped)
+    }
+}
+
+impl Default for Compaction {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Drops the resolved messages among the ones at the given positions of `queue`,
+/// counting the messages of all its operational data in order.
+fn check_range<E>(
+    queue: &mut VecDeque<OperationalData>,
+    range: Range<usize>,
+    dropped: &mut usize,
+    is_resolved: &mut impl FnMut(&IbcEvent) -> Result<bool, E>,
+) -> Result<(), E> {
+    let mut position = 0;
+
+    for od in queue.iter_mut() {
+        let len = od.batch.len();
+
+        if position >= range.end {
+            break;
+        }
+
+        if position + len <= range.start {
+            position += len;
+            continue;
+        }
+
+        let result = od.drop_messages(|offset, msg| {
+            if range.contains(&(position + offset)) {
+                is_resolved(&msg.event_with_height.event)
+            } else {
+                Ok(false)
+            }
+        });
+        od.batch.shrink_to_fit();
+
+        *dropped += len - od.batch.len();
+        position += len;
+
+        result?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::convert::Infallible;
+    use core::time::Duration;
+
+    use ibc_proto::google::protobuf::Any;
+    use ibc_relayer_types::core::ics04_channel::events::SendPacket;
+    use ibc_relayer_types::core::ics04_channel::packet::{Packet, Sequence};
+    use ibc_relayer_types::Height;
+
+    use crate::chain::tracking::TrackingId;
+    use crate::event::IbcEventWithHeight;
+    use crate::link::operational_data::{OperationalDataTarget, TransitMessage};
+
+    /// Operational data holding the timeouts of the packets with the given sequences.
+    fn operational_data(sequences: impl IntoIterator<Item = u64>) -> OperationalData {
+        let mut od = OperationalData::new(
+            Height::new(0, 10).unwrap(),
+            OperationalDataTarget::Source,
+            TrackingId::new_static("compaction"),
+            Duration::ZERO,
+        );
+
+        for sequence in sequences {
+            od.push(TransitMessage {
+                event_with_height: IbcEventWithHeight::new(
+                    IbcEvent::SendPacket(SendPacket {
+                        packet: Packet {
+                            sequence: Sequence::from(sequence),
+                            ..Packet::default()
+                        },
+                    }),
+                    Height::new(0, 10).unwrap(),
+                ),
+                msg: Any {
+                    type_url: "/ibc.core.channel.v1.MsgTimeout".to_owned(),
+                    value: vec![],
+                },
+            });
+        }
+
+        od
+    }
+
+    fn sequences(queue: &VecDeque<OperationalData>) -> Vec<u64> {
+        queue
+            .iter()
+            .flat_map(|od| &od.batch)
+            .map(|msg| u64::from(msg.event_with_height.event.packet().unwrap().sequence))
+            .collect()
+    }
+
+    /// The packets with a sequence up to 100 were resolved out-of-band.
+    fn resolved_out_of_band(event: &IbcEvent) -> Result<bool, Infallible> {
+        Ok(event.packet().unwrap().sequence <= Sequence::from(100))
+    }
+
+    #[test]
+    fn stale_entries_are_dropped_and_memory_is_released() {
+        let mut queue: VecDeque<_> = (0..50)
+            .map(|i| operational_data(i * 4 + 1..=i * 4 + 4))
+            .collect();
+        queue.push_back(operational_data([201, 202]));
+
+        let capacity = queue.capacity();
+        let compaction = Compaction::new();
+
+        let dropped = compaction
+            .run(&mut queue, usize::MAX, resolved_out_of_band)
+            .unwrap();
+
+        assert_eq!(dropped, 100);
+        assert_eq!(sequences(&queue)[..3], [101, 102, 103]);
+        assert_eq!(sequences(&queue).len(), 102);
+
+        // The operational data holding only resolved packets are dropped altogether
+        assert_eq!(queue.len(), 26);
+        assert!(queue.capacity() < capacity);
+    }
+
+    #[test]
+    fn passes_are_bounded_and_resume_after_the_last_checked_message() {
+        let mut queue: VecDeque<_> = [
+            operational_data([150, 1, 2]),
+            operational_data([3, 151]),
+            operational_data([4, 5]),
+        ]
+        .into_iter()
+        .collect();
+
+        let compaction = Compaction::new();
+        let mut checked = vec![];
+
+        let mut run = |queue: &mut VecDeque<OperationalData>| {
+            compaction
+                .run(queue, 3, |event| {
+                    checked.push(u64::from(event.packet().unwrap().sequence));
+                    resolved_out_of_band(event)
+                })
+                .unwrap()
+        };
+
+        // Only the first 3 messages are checked
+        assert_eq!(run(&mut queue), 2);
+        assert_eq!(sequences(&queue), [150, 3, 151, 4, 5]);
+
+        // The next pass resumes with the first message not checked yet
+        assert_eq!(run(&mut queue), 2);
+        assert_eq!(sequences(&queue), [150, 151, 5]);
+
+        // Then with the last one, and starts over once all of them were checked
+        assert_eq!(run(&mut queue), 1);
+        assert_eq!(run(&mut queue), 0);
+
+        assert_eq!(sequences(&queue), [150, 151]);
+        assert_eq!(checked, [150, 1, 2, 3, 151, 4, 5, 150, 151]);
+    }
+
+    #[test]
+    fn failed_pass_keeps_the_unchecked_messages() {
+        let mut queue: VecDeque<_> = [operational_data([1, 2]), operational_data([3, 4])]
+            .into_iter()
+            .collect();
+
+        let compaction = Compaction::new();
+
+        let result = compaction.run(&mut queue, 10, |event| {
+            match u64::from(event.packet().unwrap().sequence) {
+                3 => Err("query failed"),
+                _ => Ok(true),
+            }
+        });
+
+        assert!(result.is_err());
+        assert_eq!(sequences(&queue), [3, 4]);
+    }
+}
@@ -1,6 +1,7 @@
 use flex_error::define_error;
 use ibc_relayer_types::core::ics02_client::error::Error as Ics02Error;
-use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc_relayer_types::core::ics04_channel::packet::Sequence;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, PortId};
 use ibc_relayer_types::events::IbcEvent;
 use ibc_relayer_types::Height;
 
@@ -70,6 +71,12 @@ define_error! {
         OldPacketClearingFailed
             |_| { "clearing of old packets failed" },
 
+        ClearingIncomplete
+            { passes: usize }
+            |e| {
+                format!("packets are still left to relay after {} clearing passes", e.passes)
+            },
+
         Send
             { event: IbcEvent }
             |e| {
@@ -148,9 +155,55 @@ define_error! {
 
         UpdateClientFailed
              |_| { "failed to update client" },
+
+        ProofHeightPruned
+            {
+                chain_id: ChainId,
+                height: Height,
+                earliest_height: Height,
+            }
+            |e| {
+                format!("cannot build proofs at height {} on chain {}, whose node has pruned the state below height {}",
+                    e.height, e.chain_id, e.earliest_height)
+            },
+
+        ClientChainIdMismatch
+            {
+                chain_id: ChainId,
+                client_id: ClientId,
+                expected: ChainId,
+                actual: ChainId,
+            }
+            |e| {
+                format!("client {} on chain {} tracks chain {} instead of the counterparty chain {} of the path, refusing to relay",
+                    e.client_id, e.chain_id, e.actual, e.expected)
+            },
+
+        InvalidSequenceRange
+            {
+                start: Sequence,
+                end: Sequence,
+            }
+            |e| {
+                format!("the start {} of the sequence range is greater than its end {}, which would clear no packet",
+                    e.start, e.end)
+            },
    }
 }
 
+impl LinkError {
+    /// Whether this error is due to the node of a chain having pruned the state
+    /// at the height at which it was queried, eg. to build the proofs of packets.
+    pub fn is_pruned_state_error(&self) -> bool {
+        match self.detail() {
+            LinkErrorDetail::Relayer(e) => e.source.is_pruned_state_error(),
+            LinkErrorDetail::PacketProofsConstructor(e) => e.source.is_pruned_state_error(),
+            LinkErrorDetail::Query(e) => e.source.is_pruned_state_error(),
+            _ => false,
+        }
+    }
+}
+
 impl HasExpiredOrFrozenError for LinkErrorDetail {
     fn is_frozen_error(&self) -> bool {
         match self {
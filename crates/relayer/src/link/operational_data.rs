@@ -2,10 +2,24 @@ use core::fmt::{Display, Error as FmtError, Formatter};
 use std::ops::Add;
 use std::time::{Duration, Instant};
 
+use prost::Message;
+use serde::Serialize;
 use tracing::{debug, info};
 
 use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::core::channel::v1::{
+    MsgAcknowledgement as RawMsgAcknowledgement,
+    MsgChannelCloseConfirm as RawMsgChannelCloseConfirm, MsgRecvPacket as RawMsgRecvPacket,
+    MsgTimeout as RawMsgTimeout, MsgTimeoutOnClose as RawMsgTimeoutOnClose,
+};
+use ibc_proto::Protobuf;
 use ibc_relayer_types::core::ics02_client::client_state::ClientState;
+use ibc_relayer_types::core::ics02_client::msgs::update_client::{self, MsgUpdateClient};
+use ibc_relayer_types::core::ics04_channel::msgs::{
+    acknowledgement, chan_close_confirm, recv_packet, timeout, timeout_on_close,
+};
+use ibc_relayer_types::signer::Signer;
+use ibc_relayer_types::tx_msg::Msg;
 use ibc_relayer_types::Height;
 
 use crate::chain::handle::ChainHandle;
@@ -14,12 +28,13 @@ use crate::chain::requests::QueryClientStateRequest;
 use crate::chain::requests::QueryHeight;
 use crate::chain::tracking::TrackedMsgs;
 use crate::chain::tracking::TrackingId;
+use crate::config::types::{MaxMsgNum, MaxTxSize};
 use crate::event::IbcEventWithHeight;
 use crate::link::error::LinkError;
 use crate::link::RelayPath;
 
 /// The chain that the events associated with a piece of [`OperationalData`] are bound for.
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
 pub enum OperationalDataTarget {
     /// The chain which generated the events associated with the `OperationalData`.
     Source,
@@ -66,6 +81,15 @@ impl TrackedEvents {
     pub fn len(&self) -> usize {
         self.events.len()
     }
+
+    /// Sets the height of all the events, so that the proofs of the messages
+    /// built from them are queried at that height.
+    pub fn with_height(mut self, height: Height) -> Self {
+        for event_with_height in self.events.iter_mut() {
+            event_with_height.height = height;
+        }
+        self
+    }
 }
 
 /// A packet message that is prepared for sending
@@ -79,6 +103,14 @@ pub struct TransitMessage {
     pub msg: Any,
 }
 
+/// A key used to sign the messages of an [`OperationalData`]
+/// instead of the key configured for the target chain.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SigningKey {
+    pub key_name: String,
+    pub signer: Signer,
+}
+
 /// Holds all the necessary information for handling a batch of in-transit messages. This includes
 /// an event received from a chain along with any other packets related to the event (i.e.
 /// 'receive' or 'timeout' packets) that the relayer has to submit in response to the event.
@@ -96,8 +128,12 @@ pub struct OperationalData {
     /// A unique ID for tracking this batch of events starting from when they were received
     /// until the transactions corresponding to those events is submitted.
     pub tracking_id: TrackingId,
+    /// The key to sign the messages with, if not the key configured for the target chain.
+    pub signing_key: Option<SigningKey>,
     /// Stores `Some(ConnectionDelay)` if the delay is non-zero and `None` otherwise
     connection_delay: Option<ConnectionDelay>,
+    /// The time at which the operational data was scheduled
+    scheduled_time: Instant,
 }
 
 impl OperationalData {
@@ -119,6 +155,8 @@ impl OperationalData {
             target,
             connection_delay,
             tracking_id,
+            signing_key: None,
+            scheduled_time: Instant::now(),
         }
     }
 
@@ -126,6 +164,128 @@ impl OperationalData {
         self.batch.push(msg)
     }
 
+    /// The time at which the operational data was scheduled.
+    pub fn scheduled_time(&self) -> Instant {
+        self.scheduled_time
+    }
+
+    /// Drops the messages, given with their position in the batch, for which `is_dropped`
+    /// returns `true`. Returns the number of messages dropped.
+    ///
+    /// If `is_dropped` fails, the messages already found to be dropped are dropped and the
+    /// remaining ones are kept.
+    pub fn drop_messages<E>(
+        &mut self,
+        mut is_dropped: impl FnMut(usize, &TransitMessage) -> Result<bool, E>,
+    ) -> Result<usize, E> {
+        let len = self.batch.len();
+        let mut dropped = Vec::with_capacity(len);
+        let mut result = Ok(());
+
+        for (position, msg) in self.batch.iter().enumerate() {
+            match is_dropped(position, msg) {
+                Ok(drop) => dropped.push(drop),
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
+            }
+        }
+
+        let mut dropped = dropped.into_iter();
+        self.batch.retain(|_| !dropped.next().unwrap_or(false));
+
+        result.map(|()| len - self.batch.len())
+    }
+
+    /// Moves the messages for which `signing_key_for` returns a key into separate
+    /// operational data, one per key, which sign their messages with that key.
+    ///
+    /// Returns the operational data holding the remaining messages, if any,
+    /// followed by the operational data for each key.
+    pub fn split_by_signing_key<F>(
+        mut self,
+        mut signing_key_for: F,
+    ) -> Result<Vec<OperationalData>, LinkError>
+    where
+        F: FnMut(&TransitMessage) -> Result<Option<SigningKey>, LinkError>,
+    {
+        let batch = core::mem::take(&mut self.batch);
+        let mut split = vec![self];
+
+        for msg in batch {
+            let signing_key = match signing_key_for(&msg)? {
+                Some(signing_key) => Some(signing_key),
+                None => split[0].signing_key.clone(),
+            };
+
+            match split.iter_mut().find(|od| od.signing_key == signing_key) {
+                Some(od) => od.push(msg),
+                None => split.push(OperationalData {
+                    batch: vec![msg],
+                    signing_key,
+                    ..split[0].clone()
+                }),
+            }
+        }
+
+        split.retain(|od| !od.batch.is_empty());
+
+        Ok(split)
+    }
+
+    /// Splits the messages, in order, into operational data holding at most `max_msg_num`
+    /// messages of at most `max_tx_size` bytes in total. A message larger than `max_tx_size`
+    /// is held on its own.
+    pub fn split_by_limits(
+        mut self,
+        max_msg_num: MaxMsgNum,
+        max_tx_size: MaxTxSize,
+    ) -> Vec<OperationalData> {
+        let batch = core::mem::take(&mut self.batch);
+        let mut split: Vec<OperationalData> = vec![];
+        let mut size = 0;
+
+        for msg in batch {
+            let msg_size = msg.msg.encoded_len();
+
+            match split.last_mut() {
+                Some(od)
+                    if od.batch.len() < max_msg_num.to_usize()
+                        && size + msg_size <= max_tx_size.to_usize() =>
+                {
+                    size += msg_size;
+                    od.push(msg);
+                }
+                _ => {
+                    size = msg_size;
+                    split.push(OperationalData {
+                        batch: vec![msg],
+                        ..self.clone()
+                    });
+                }
+            }
+        }
+
+        split
+    }
+
+    /// Splits the messages into operational data holding a single message each,
+    /// in ascending order of the sequence of their packet.
+    pub fn split_in_sequence_order(mut self) -> Vec<OperationalData> {
+        let mut batch = core::mem::take(&mut self.batch);
+
+        batch.sort_by_key(|msg| msg.event_with_height.event.packet().map(|p| p.sequence));
+
+        batch
+            .into_iter()
+            .map(|msg| OperationalData {
+                batch: vec![msg],
+                ..self.clone()
+            })
+            .collect()
+    }
+
     /// Returns displayable information on the operation's data.
     pub fn info(&self) -> OperationalInfo {
         OperationalInfo {
@@ -136,6 +296,25 @@ impl OperationalData {
         }
     }
 
+    /// Moves the proofs of the messages to the given height, at which they
+    /// are queried again when the operational data is regenerated.
+    ///
+    /// The client update which the former proofs were verified against no longer
+    /// applies, so that the connection delay must start over from a new one.
+    pub fn with_proofs_height(mut self, height: Height) -> Self {
+        if height != self.proofs_height {
+            if let Some(delay) = self.connection_delay.as_mut() {
+                delay.update_height = None;
+            }
+        }
+
+        self.proofs_height = height;
+        for msg in self.batch.iter_mut() {
+            msg.event_with_height.height = height;
+        }
+        self
+    }
+
     /// Transforms `self` into the list of events accompanied with the tracking ID.
     pub fn into_events(self) -> TrackedEvents {
         let events = self
@@ -208,12 +387,40 @@ impl OperationalData {
             vec![]
         };
 
-        let msgs = client_update_msgs
+        let msgs: Vec<Any> = client_update_msgs
             .into_iter()
             .chain(self.batch.iter().map(|gm| gm.msg.clone()))
             .collect();
 
-        let tm = TrackedMsgs::new(msgs, self.tracking_id);
+        let (msgs, signing_key) = match &self.signing_key {
+            // The client update must be signed by the same key as the messages which depend on it
+            Some(signing_key) => (
+                msgs.into_iter()
+                    .map(|msg| with_update_client_signer(msg, &signing_key.signer))
+                    .collect(),
+                Some(signing_key.clone()),
+            ),
+            // The messages are built with the signer of the configured key, and are signed
+            // instead by the next of the signing accounts of the target chain, if any
+            None => match relay_path.next_signing_key(self.target)? {
+                Some(signing_key) => (
+                    msgs.into_iter()
+                        .map(|msg| with_signer(msg, &signing_key.signer))
+                        .collect(),
+                    Some(signing_key),
+                ),
+                None => (msgs, None),
+            },
+        };
+
+        let counterparty_chain_id = match self.target {
+            OperationalDataTarget::Source => relay_path.dst_chain().id(),
+            OperationalDataTarget::Destination => relay_path.src_chain().id(),
+        };
+
+        let tm = TrackedMsgs::new(msgs, self.tracking_id)
+            .with_key_name(signing_key.map(|signing_key| signing_key.key_name))
+            .with_counterparty_chain_id(counterparty_chain_id);
 
         info!("assembled batch of {} message(s)", tm.messages().len());
 
@@ -255,6 +462,27 @@ impl OperationalData {
         }
     }
 
+    /// The start of the connection delay, ie. the time and the height at which the client
+    /// update which the proofs of the messages are verified against was processed, if set.
+    pub fn conn_delay_start(&self) -> Option<(Instant, Height)> {
+        self.get_delay_if_needed().and_then(|delay| {
+            delay
+                .update_height
+                .map(|update_height| (delay.scheduled_time, update_height))
+        })
+    }
+
+    /// The earliest time at which the messages may be submitted, ie. once the connection
+    /// delay has elapsed since the client update was processed, or `None` if the connection
+    /// delay need not be taken into account.
+    ///
+    /// Before that time, the chain time cannot have reached the end of the delay either,
+    /// so that there is no need to query the chain to find out whether it has elapsed.
+    pub fn conn_delay_eligible_at(&self) -> Option<Instant> {
+        self.get_delay_if_needed()
+            .map(|delay| delay.scheduled_time + delay.delay)
+    }
+
     /// Returns `Ok(remaining-delay)` on success or `LinkError` if the input closure fails.
     fn conn_time_delay_remaining<ChainTime>(
         &self,
@@ -299,6 +527,13 @@ impl OperationalData {
         MaxBlockTime: Fn() -> Result<Duration, LinkError>,
         LatestHeight: Fn() -> Result<Height, LinkError>,
     {
+        if self
+            .conn_delay_eligible_at()
+            .is_some_and(|eligible_at| Instant::now() < eligible_at)
+        {
+            return Ok(false);
+        }
+
         Ok(self.conn_time_delay_remaining(chain_time)?.is_zero()
             && self.conn_block_delay_remaining(max_expected_time_per_block, latest_height)? == 0)
     }
@@ -384,6 +619,60 @@ fn calculate_block_delay(
     (delay_period_time.as_secs_f64() / max_expected_time_per_block.as_secs_f64()).ceil() as u64
 }
 
+/// Replaces the signer of the given message if it is a `MsgUpdateClient`.
+fn with_update_client_signer(msg: Any, signer: &Signer) -> Any {
+    if msg.type_url != update_client::TYPE_URL {
+        return msg;
+    }
+
+    match MsgUpdateClient::decode_vec(&msg.value) {
+        Ok(update) => MsgUpdateClient {
+            signer: signer.clone(),
+            ..update
+        }
+        .to_any(),
+        Err(_) => msg,
+    }
+}
+
+/// Replaces the signer of the given message if it is one of the messages
+/// relayed to a chain, ie. a client update or a packet or channel message.
+fn with_signer(msg: Any, signer: &Signer) -> Any {
+    fn replace<M: Message + Default>(
+        msg: Any,
+        signer_of: fn(&mut M) -> &mut String,
+        signer: &Signer,
+    ) -> Any {
+        match M::decode(msg.value.as_slice()) {
+            Ok(mut raw) => {
+                *signer_of(&mut raw) = signer.as_ref().to_owned();
+
+                Any {
+                    type_url: msg.type_url,
+                    value: raw.encode_to_vec(),
+                }
+            }
+            Err(_) => msg,
+        }
+    }
+
+    match msg.type_url.as_str() {
+        update_client::TYPE_URL => with_update_client_signer(msg, signer),
+        recv_packet::TYPE_URL => replace::<RawMsgRecvPacket>(msg, |m| &mut m.signer, signer),
+        acknowledgement::TYPE_URL => {
+            replace::<RawMsgAcknowledgement>(msg, |m| &mut m.signer, signer)
+        }
+        timeout::TYPE_URL => replace::<RawMsgTimeout>(msg, |m| &mut m.signer, signer),
+        timeout_on_close::TYPE_URL => {
+            replace::<RawMsgTimeoutOnClose>(msg, |m| &mut m.signer, signer)
+        }
+        chan_close_confirm::TYPE_URL => {
+            replace::<RawMsgChannelCloseConfirm>(msg, |m| &mut m.signer, signer)
+        }
+        _ => msg,
+    }
+}
+
 /// A lightweight informational data structure that can be extracted
 /// out of [`OperationalData`] for e.g. logging purposes.
 pub struct OperationalInfo {
@@ -413,3 +702,286 @@ impl Display for OperationalInfo {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_relayer_types::core::ics04_channel::events::SendPacket;
+    use ibc_relayer_types::core::ics04_channel::packet::{Packet, Sequence};
+    use ibc_relayer_types::events::IbcEvent;
+
+    fn transit_message(sequence: u64) -> TransitMessage {
+        TransitMessage {
+            event_with_height: IbcEventWithHeight::new(
+                IbcEvent::SendPacket(SendPacket {
+                    packet: Packet {
+                        sequence: Sequence::from(sequence),
+                        ..Packet::default()
+                    },
+                }),
+                Height::new(0, 10).unwrap(),
+            ),
+            msg: Any {
+                type_url: "/ibc.core.channel.v1.MsgRecvPacket".to_owned(),
+                value: vec![],
+            },
+        }
+    }
+
+    fn sequences(od: &OperationalData) -> Vec<u64> {
+        od.batch
+            .iter()
+            .map(|msg| u64::from(msg.event_with_height.event.packet().unwrap().sequence))
+            .collect()
+    }
+
+    fn delayed_operational_data(delay: Duration) -> OperationalData {
+        let mut od = OperationalData::new(
+            Height::new(0, 10).unwrap(),
+            OperationalDataTarget::Destination,
+            TrackingId::new_static("delay"),
+            delay,
+        );
+
+        od.push(transit_message(1));
+        od
+    }
+
+    /// Whether the connection delay of `od` has elapsed at the given chain time and height,
+    /// with a maximum block time of 10s.
+    fn elapsed(od: &OperationalData, chain_time: Instant, latest_height: u64) -> bool {
+        od.has_conn_delay_elapsed(&|| Ok(chain_time), &|| Ok(Duration::from_secs(10)), &|| {
+            Ok(Height::new(0, latest_height).unwrap())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn packets_are_submitted_only_after_conn_delay() {
+        let delay = Duration::from_secs(30);
+        let mut od = delayed_operational_data(delay);
+
+        // The client update the proofs are verified against was processed 10s ago, at height 20
+        let update_time = Instant::now() - Duration::from_secs(10);
+        od.set_scheduled_time(update_time);
+        od.set_update_height(Height::new(0, 20).unwrap());
+
+        assert_eq!(od.conn_delay_eligible_at(), Some(update_time + delay));
+
+        // Not before the earliest eligible submission time, whatever the chain reports
+        assert!(!elapsed(&od, update_time + delay, 100));
+
+        // Once the delay has elapsed on the local clock, but neither on the chain time
+        // nor in blocks, 3 blocks being expected in 30s
+        let update_time = Instant::now() - delay;
+        od.set_scheduled_time(update_time);
+        assert!(!elapsed(
+            &od,
+            update_time + delay - Duration::from_secs(1),
+            23
+        ));
+        assert!(!elapsed(&od, update_time + delay, 22));
+
+        // Once the delay has elapsed both in time and in blocks
+        assert!(elapsed(&od, Instant::now(), 23));
+    }
+
+    #[test]
+    fn conn_delay_starts_over_with_new_proofs_height() {
+        let mut od = delayed_operational_data(Duration::from_secs(30));
+        let update_time = Instant::now();
+
+        od.set_scheduled_time(update_time);
+        od.set_update_height(Height::new(0, 20).unwrap());
+
+        let od = od.with_proofs_height(Height::new(0, 10).unwrap());
+        assert_eq!(
+            od.conn_delay_start(),
+            Some((update_time, Height::new(0, 20).unwrap()))
+        );
+
+        let od = od.with_proofs_height(Height::new(0, 15).unwrap());
+        assert_eq!(od.conn_delay_start(), None);
+
+        // Without connection delay, there is no delay to start
+        let od = delayed_operational_data(Duration::ZERO);
+        assert_eq!(od.conn_delay_start(), None);
+        assert_eq!(od.conn_delay_eligible_at(), None);
+    }
+
+    #[test]
+    fn split_by_signing_key() {
+        let mut od = OperationalData::new(
+            Height::new(0, 10).unwrap(),
+            OperationalDataTarget::Destination,
+            TrackingId::new_static("split"),
+            Duration::ZERO,
+        );
+
+        for sequence in 1..=5 {
+            od.push(transit_message(sequence));
+        }
+
+        // Odd sequences are signed with a dedicated key
+        let split = od
+            .split_by_signing_key(|msg| {
+                let sequence = u64::from(msg.event_with_height.event.packet().unwrap().sequence);
+
+                Ok((sequence % 2 == 1).then(|| SigningKey {
+                    key_name: "odd".to_owned(),
+                    signer: "cosmos1odd".parse().unwrap(),
+                }))
+            })
+            .unwrap();
+
+        assert_eq!(split.len(), 2);
+
+        assert_eq!(split[0].signing_key, None);
+        assert_eq!(sequences(&split[0]), [2, 4]);
+
+        assert_eq!(
+            split[1]
+                .signing_key
+                .as_ref()
+                .map(|key| key.key_name.as_str()),
+            Some("odd")
+        );
+        assert_eq!(sequences(&split[1]), [1, 3, 5]);
+    }
+
+    #[test]
+    fn split_by_limits() {
+        let mut od = OperationalData::new(
+            Height::new(0, 10).unwrap(),
+            OperationalDataTarget::Source,
+            TrackingId::new_static("split"),
+            Duration::ZERO,
+        );
+
+        for sequence in 1..=5 {
+            od.push(transit_message(sequence));
+        }
+
+        let msg_size = transit_message(1).msg.encoded_len();
+
+        // At most two messages per batch
+        let split = od
+            .clone()
+            .split_by_limits(MaxMsgNum::new(2).unwrap(), MaxTxSize::default());
+
+        assert_eq!(
+            split.iter().map(sequences).collect::<Vec<_>>(),
+            [vec![1, 2], vec![3, 4], vec![5]]
+        );
+
+        // At most three messages per batch, by size
+        let split = od.split_by_limits(
+            MaxMsgNum::default(),
+            MaxTxSize::new(3 * msg_size + 1).unwrap(),
+        );
+
+        assert_eq!(
+            split.iter().map(sequences).collect::<Vec<_>>(),
+            [vec![1, 2, 3], vec![4, 5]]
+        );
+        assert!(split
+            .iter()
+            .all(|od| od.target == OperationalDataTarget::Source));
+    }
+
+    #[test]
+    fn split_in_sequence_order() {
+        let mut od = OperationalData::new(
+            Height::new(0, 10).unwrap(),
+            OperationalDataTarget::Destination,
+            TrackingId::new_static("split"),
+            Duration::ZERO,
+        );
+
+        for sequence in [3, 1, 5, 2, 4] {
+            od.push(transit_message(sequence));
+        }
+
+        let split = od.split_in_sequence_order();
+
+        assert_eq!(
+            split.iter().map(sequences).collect::<Vec<_>>(),
+            [vec![1], vec![2], vec![3], vec![4], vec![5]]
+        );
+        assert!(split
+            .iter()
+            .all(|od| od.target == OperationalDataTarget::Destination));
+    }
+
+    #[test]
+    fn update_client_signer_is_replaced() {
+        let signer: Signer = "cosmos1relayer".parse().unwrap();
+
+        let update = MsgUpdateClient::new(
+            "07-tendermint-0".parse().unwrap(),
+            Any::default(),
+            "cosmos1default".parse().unwrap(),
+        );
+
+        let msg = with_update_client_signer(update.clone().to_any(), &signer);
+        let resigned = MsgUpdateClient::decode_vec(&msg.value).unwrap();
+
+        assert_eq!(resigned, MsgUpdateClient { signer, ..update });
+    }
+
+    #[test]
+    fn packet_msg_signer_is_replaced() {
+        let signer: Signer = "cosmos1relayer".parse().unwrap();
+
+        let recv = RawMsgRecvPacket {
+            proof_commitment: vec![1, 2, 3],
+            signer: "cosmos1default".to_owned(),
+            ..RawMsgRecvPacket::default()
+        };
+        let msg = Any {
+            type_url: recv_packet::TYPE_URL.to_owned(),
+            value: recv.encode_to_vec(),
+        };
+
+        let msg = with_signer(msg, &signer);
+        let resigned = RawMsgRecvPacket::decode(msg.value.as_slice()).unwrap();
+
+        assert_eq!(msg.type_url, recv_packet::TYPE_URL);
+        assert_eq!(
+            resigned,
+            RawMsgRecvPacket {
+                signer: "cosmos1relayer".to_owned(),
+                ..recv
+            }
+        );
+
+        // Other messages are left as is
+        let other = Any {
+            type_url: "/cosmos.bank.v1beta1.MsgSend".to_owned(),
+            value: vec![1, 2, 3],
+        };
+        assert_eq!(with_signer(other.clone(), &signer), other);
+    }
+
+    #[test]
+    fn with_proofs_height_moves_all_events() {
+        let mut od = OperationalData::new(
+            Height::new(0, 10).unwrap(),
+            OperationalDataTarget::Destination,
+            TrackingId::new_static("pruned"),
+            Duration::ZERO,
+        );
+        od.batch = vec![transit_message(1), transit_message(2)];
+
+        let refreshed = od.with_proofs_height(Height::new(0, 100).unwrap());
+
+        assert_eq!(refreshed.proofs_height, Height::new(0, 100).unwrap());
+        assert_eq!(sequences(&refreshed), vec![1, 2]);
+        assert!(refreshed
+            .into_events()
+            .events()
+            .iter()
+            .all(|ev| ev.height == Height::new(0, 100).unwrap()));
+    }
+}
@@ -0,0 +1,338 @@
+use alloc::collections::BTreeMap;
+use alloc::sync::Arc;
+use core::fmt::{Debug, Display, Error as FmtError, Formatter};
+
+use ibc_proto::ibc::applications::transfer::v2::FungibleTokenPacketData as RawPacketData;
+use prost::Message;
+
+use ibc_relayer_types::core::ics04_channel::packet::Packet;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+
+use crate::config::PacketDataEncoding;
+
+/// Decodes the data of the ICS-20 transfer packets sent by an application,
+/// so that the filters applied to transfers can inspect their fields.
+pub trait PacketDataDecoder: Debug + Send + Sync {
+    /// Decodes the given packet data as ICS-20 transfer packet data,
+    /// or returns why the data is not transfer packet data.
+    fn decode_transfer(&self, data: &[u8]) -> Result<RawPacketData, String>;
+}
+
+/// Decodes JSON-encoded packet data, as sent by the ICS-20 application.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct JsonDecoder;
+
+impl PacketDataDecoder for JsonDecoder {
+    fn decode_transfer(&self, data: &[u8]) -> Result<RawPacketData, String> {
+        serde_json::from_slice(data).map_err(|e| e.to_string())
+    }
+}
+
+/// Decodes protobuf-encoded packet data.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ProtobufDecoder;
+
+impl PacketDataDecoder for ProtobufDecoder {
+    fn decode_transfer(&self, data: &[u8]) -> Result<RawPacketData, String> {
+        RawPacketData::decode(data).map_err(|e| e.to_string())
+    }
+}
+
+impl From<PacketDataEncoding> for Arc<dyn PacketDataDecoder> {
+    fn from(encoding: PacketDataEncoding) -> Self {
+        match encoding {
+            PacketDataEncoding::Json => Arc::new(JsonDecoder),
+            PacketDataEncoding::Protobuf => Arc::new(ProtobufDecoder),
+        }
+    }
+}
+
+/// The decoders of the packet data of the applications bound to each port,
+/// falling back to [`JsonDecoder`] for the ports without a registered decoder.
+#[derive(Clone, Debug)]
+pub struct PacketDataDecoderRegistry {
+    default: Arc<dyn PacketDataDecoder>,
+    ports: BTreeMap<PortId, Arc<dyn PacketDataDecoder>>,
+}
+
+impl PacketDataDecoderRegistry {
+    /// Registers the decoders of the packet data encodings configured per port.
+    pub fn from_encodings<'a>(
+        encodings: impl IntoIterator<Item = (&'a PortId, &'a PacketDataEncoding)>,
+    ) -> Self {
+        let mut registry = Self::default();
+
+        for (port_id, encoding) in encodings {
+            registry.register(port_id.clone(), (*encoding).into());
+        }
+
+        registry
+    }
+
+    /// Registers the decoder of the packet data sent from the given port,
+    /// replacing the decoder previously registered for it, if any.
+    pub fn register(&mut self, port_id: PortId, decoder: Arc<dyn PacketDataDecoder>) {
+        self.ports.insert(port_id, decoder);
+    }
+
+    /// The decoder of the packet data sent from the given port.
+    pub fn decoder(&self, port_id: &PortId) -> &dyn PacketDataDecoder {
+        self.ports.get(port_id).unwrap_or(&self.default).as_ref()
+    }
+
+    /// Decodes the data of the given packet as ICS-20 transfer packet data,
+    /// with the decoder of the port the packet was sent from.
+    pub fn decode_transfer(&self, packet: &Packet) -> Result<RawPacketData, String> {
+        self.decoder(&packet.source_port)
+            .decode_transfer(&packet.data)
+    }
+
+    /// Returns the hops through which the receiving chain forwards the given
+    /// packet onward, if it is an ICS-20 transfer with a forwarding memo.
+    pub fn forwarding_hops(&self, packet: &Packet) -> Vec<ForwardHop> {
+        self.decode_transfer(packet)
+            .map(|packet_data| forwarding_hops(&packet_data.memo))
+            .unwrap_or_default()
+    }
+}
+
+impl Default for PacketDataDecoderRegistry {
+    fn default() -> Self {
+        Self {
+            default: Arc::new(JsonDecoder),
+            ports: BTreeMap::new(),
+        }
+    }
+}
+
+/// A hop through which a transfer is forwarded onward by the packet forwarding
+/// middleware, as specified by the `forward` field of the memo of the transfer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ForwardHop {
+    pub receiver: String,
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+}
+
+/// Parses the hops of the forwarding memo of a transfer, from the chain receiving
+/// the transfer to its final destination. Returns no hops if the memo is not a
+/// forwarding memo.
+///
+/// The `next` field of each hop holds the memo of the following hop, either as
+/// a JSON object or as a JSON string.
+pub fn forwarding_hops(memo: &str) -> Vec<ForwardHop> {
+    let mut hops = Vec::new();
+    let mut memo = serde_json::from_str::<serde_json::Value>(memo).ok();
+
+    while let Some(forward) = memo.as_ref().and_then(|memo| memo.get("forward")) {
+        let hop = (|| {
+            Some(ForwardHop {
+                receiver: forward.get("receiver")?.as_str()?.to_string(),
+                port_id: forward.get("port")?.as_str()?.parse().ok()?,
+                channel_id: forward.get("channel")?.as_str()?.parse().ok()?,
+            })
+        })();
+
+        let Some(hop) = hop else {
+            break;
+        };
+
+        hops.push(hop);
+
+        memo = match forward.get("next") {
+            Some(serde_json::Value::String(next)) => serde_json::from_str(next).ok(),
+            next => next.cloned(),
+        };
+    }
+
+    hops
+}
+
+/// The final destination of a transfer forwarded onward through one or more hops.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FinalDestination {
+    /// The identifier of the final destination chain, known when the transfer
+    /// is forwarded through a single hop, from the chain receiving it.
+    pub chain_id: Option<ChainId>,
+    /// The last hop through which the transfer is forwarded.
+    pub hop: ForwardHop,
+    /// The number of hops through which the transfer is forwarded.
+    pub hops: usize,
+}
+
+impl FinalDestination {
+    /// The final destination of a transfer forwarded through the given hops, where
+    /// `next_chain_id` is the chain at the other end of the first hop, if known.
+    pub fn new(mut hops: Vec<ForwardHop>, next_chain_id: Option<ChainId>) -> Option<Self> {
+        let count = hops.len();
+        let hop = hops.pop()?;
+
+        Some(Self {
+            chain_id: next_chain_id.filter(|_| count == 1),
+            hop,
+            hops: count,
+        })
+    }
+}
+
+impl Display for FinalDestination {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match &self.chain_id {
+            Some(chain_id) => write!(f, "{chain_id}"),
+            None => write!(f, "{}/{}", self.hop.port_id, self.hop.channel_id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::str::FromStr;
+
+    use ibc_relayer_types::core::ics24_host::identifier::ChannelId;
+
+    fn transfer_data() -> RawPacketData {
+        RawPacketData {
+            denom: "uatom".to_string(),
+            amount: "1000".to_string(),
+            sender: "cosmos1sender".to_string(),
+            receiver: "cosmos1receiver".to_string(),
+            memo: String::new(),
+        }
+    }
+
+    fn packet(port_id: &PortId, data: Vec<u8>) -> Packet {
+        Packet {
+            sequence: 1.into(),
+            source_port: port_id.clone(),
+            source_channel: ChannelId::new(0),
+            destination_port: PortId::transfer(),
+            destination_channel: ChannelId::new(1),
+            data,
+            timeout_height: Default::default(),
+            timeout_timestamp: Default::default(),
+        }
+    }
+
+    #[test]
+    fn protobuf_transfer_data_is_decoded_on_registered_port() {
+        let port_id = PortId::from_str("transfer-proto").unwrap();
+
+        let registry =
+            PacketDataDecoderRegistry::from_encodings([(&port_id, &PacketDataEncoding::Protobuf)]);
+
+        let proto_packet = packet(&port_id, transfer_data().encode_to_vec());
+        let decoded = registry.decode_transfer(&proto_packet).unwrap();
+
+        // Filters on the amount and denom of the transfer apply to the decoded data
+        assert_eq!(decoded.amount.parse::<u64>().unwrap(), 1000);
+        assert_eq!(decoded.denom, "uatom");
+
+        // Without a decoder registered for the port, the data is expected to be JSON
+        let default_registry = PacketDataDecoderRegistry::default();
+        assert!(default_registry.decode_transfer(&proto_packet).is_err());
+
+        let json_packet = packet(
+            &PortId::transfer(),
+            serde_json::to_vec(&transfer_data()).unwrap(),
+        );
+        assert_eq!(
+            registry.decode_transfer(&json_packet).unwrap(),
+            transfer_data()
+        );
+    }
+
+    /// Decodes the packet data of an application sending transfers as `<amount><denom>`.
+    #[derive(Debug)]
+    struct CoinDecoder;
+
+    impl PacketDataDecoder for CoinDecoder {
+        fn decode_transfer(&self, data: &[u8]) -> Result<RawPacketData, String> {
+            let coin = core::str::from_utf8(data).map_err(|e| e.to_string())?;
+            let split = coin
+                .find(|c: char| !c.is_ascii_digit())
+                .ok_or_else(|| format!("no denom in coin `{coin}`"))?;
+
+            Ok(RawPacketData {
+                denom: coin[split..].to_string(),
+                amount: coin[..split].to_string(),
+                ..RawPacketData::default()
+            })
+        }
+    }
+
+    #[test]
+    fn registered_decoder_replaces_the_decoder_of_the_port() {
+        let port_id = PortId::from_str("transfer-coin").unwrap();
+        let coin_packet = packet(&port_id, b"1000uatom".to_vec());
+
+        let mut registry =
+            PacketDataDecoderRegistry::from_encodings([(&port_id, &PacketDataEncoding::Protobuf)]);
+        assert!(registry.decode_transfer(&coin_packet).is_err());
+
+        registry.register(port_id, Arc::new(CoinDecoder));
+
+        let decoded = registry.decode_transfer(&coin_packet).unwrap();
+        assert_eq!(decoded.amount.parse::<u64>().unwrap(), 1000);
+        assert_eq!(decoded.denom, "uatom");
+
+        // The other ports keep their decoders
+        let json_packet = packet(
+            &PortId::transfer(),
+            serde_json::to_vec(&transfer_data()).unwrap(),
+        );
+        assert_eq!(
+            registry.decode_transfer(&json_packet).unwrap(),
+            transfer_data()
+        );
+    }
+
+    #[test]
+    fn final_destination_of_forwarded_transfer_is_identified() {
+        let forwarded = RawPacketData {
+            memo: r#"{"forward":{"receiver":"osmo1receiver","port":"transfer","channel":"channel-7","timeout":"10m","retries":2}}"#.to_string(),
+            ..transfer_data()
+        };
+
+        let json_packet = packet(&PortId::transfer(), serde_json::to_vec(&forwarded).unwrap());
+        let hops = PacketDataDecoderRegistry::default().forwarding_hops(&json_packet);
+
+        assert_eq!(
+            hops,
+            [ForwardHop {
+                receiver: "osmo1receiver".to_string(),
+                port_id: PortId::transfer(),
+                channel_id: ChannelId::new(7),
+            }]
+        );
+
+        let destination =
+            FinalDestination::new(hops, Some(ChainId::from_string("osmosis-1"))).unwrap();
+
+        assert_eq!(
+            destination.chain_id,
+            Some(ChainId::from_string("osmosis-1"))
+        );
+        assert_eq!(destination.to_string(), "osmosis-1");
+
+        // The final destination of a transfer forwarded through several hops is the last hop
+        let memo = r#"{"forward":{"receiver":"pfm","port":"transfer","channel":"channel-7","next":"{\"forward\":{\"receiver\":\"juno1receiver\",\"port\":\"transfer\",\"channel\":\"channel-42\"}}"}}"#;
+        let hops = forwarding_hops(memo);
+
+        assert_eq!(hops.len(), 2);
+        assert_eq!(hops[1].receiver, "juno1receiver");
+
+        let destination =
+            FinalDestination::new(hops, Some(ChainId::from_string("osmosis-1"))).unwrap();
+
+        assert_eq!(destination.chain_id, None);
+        assert_eq!(destination.hops, 2);
+        assert_eq!(destination.to_string(), "transfer/channel-42");
+
+        // Transfers which are not forwarded have no final destination beyond the receiving chain
+        assert!(forwarding_hops(r#"{"wasm":{"contract":"juno1contract"}}"#).is_empty());
+        assert!(forwarding_hops("not json").is_empty());
+        assert_eq!(FinalDestination::new(vec![], None), None);
+    }
+}
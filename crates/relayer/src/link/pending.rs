@@ -9,6 +9,7 @@ use ibc_relayer_types::events::IbcEvent;
 use crate::chain::requests::{QueryTxHash, QueryTxRequest};
 use crate::chain::tracking::TrackingId;
 use crate::error::Error as RelayerError;
+use crate::event::IbcEventWithHeight;
 use crate::link::{error::LinkError, RelayPath};
 use crate::telemetry;
 use crate::util::queue::Queue;
@@ -125,7 +126,17 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
         self.pending_queue.push_back(u);
     }
 
-    fn check_tx_events(&self, tx_hashes: &TxHashes) -> Result<Option<Vec<IbcEvent>>, RelayerError> {
+    /// Stops tracking all the pending transactions, returning how many were dropped.
+    ///
+    /// The transactions are not recalled from the chain, which may still commit them.
+    pub fn clear(&self) -> usize {
+        self.pending_queue.take().len()
+    }
+
+    fn check_tx_events(
+        &self,
+        tx_hashes: &TxHashes,
+    ) -> Result<Option<Vec<IbcEventWithHeight>>, RelayerError> {
         let mut all_events = Vec::new();
         for hash in &tx_hashes.0 {
             let mut events = self
@@ -138,7 +149,7 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
                 all_events.append(&mut events)
             }
         }
-        Ok(Some(all_events.into_iter().map(|ev| ev.event).collect()))
+        Ok(Some(all_events))
     }
 
     /// Try and process one pending transaction within the given timeout duration if one
@@ -228,7 +239,7 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
                         Ok(None)
                     }
                 }
-                Ok(Some(mut events)) => {
+                Ok(Some(events)) => {
                     // We get a list of events for the transaction hashes,
                     // Meaning the transaction has been committed successfully
                     // to the chain.
@@ -250,11 +261,12 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
                         &self.counterparty_chain_id
                     );
 
-                    // Append the events corresponding to errors from the pending tx.
-                    events.extend(pending.error_events);
+                    // Convert the events to RelaySummary, append the events
+                    // corresponding to errors from the pending tx, and return them.
+                    let mut summary = RelaySummary::from_events_with_heights(events);
+                    summary.extend(RelaySummary::from_events(pending.error_events));
 
-                    // Convert the events to RelaySummary and return them.
-                    Ok(Some(RelaySummary::from_events(events)))
+                    Ok(Some(summary))
                 }
                 Err(e) => {
                     // There are errors querying for the transaction hashes.
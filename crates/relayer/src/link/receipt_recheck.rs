@@ -0,0 +1,172 @@
+//! The grace re-check of the receipts of the packets on the destination chain, made
+//! right before submitting their `RecvPacket` messages, for chains which index the
+//! receipt of a packet received by another relayer a block late.
+
+use std::thread;
+use std::time::Duration;
+
+use ibc_relayer_types::core::ics04_channel::packet::Packet;
+use ibc_relayer_types::events::IbcEvent;
+
+use crate::link::operational_data::{OperationalData, OperationalDataTarget};
+
+#[derive(Copy, Clone, Debug)]
+pub struct ReceiptRecheck {
+    delay: Duration,
+}
+
+impl ReceiptRecheck {
+    pub fn new(delay: Duration) -> Self {
+        Self { delay }
+    }
+
+    /// Waits until the grace delay has elapsed since `od` was scheduled, then drops the
+    /// `RecvPacket` messages of `od` whose packet has been received by the destination chain
+    /// meanwhile, according to `is_received`. Returns the number of messages dropped.
+    ///
+    /// The operational data scheduled together are relayed one after the other, which
+    /// only waits for the grace delay once. Operational data targeting the source chain
+    /// is left untouched.
+    ///
+    /// If `is_received` fails, the messages already found to be received are dropped.
+    pub fn recheck<E>(
+        &self,
+        od: &mut OperationalData,
+        mut is_received: impl FnMut(&Packet) -> Result<bool, E>,
+    ) -> Result<usize, E> {
+        if od.target != OperationalDataTarget::Destination || !has_recv_packets(od) {
+            return Ok(0);
+        }
+
+        thread::sleep(self.delay.saturating_sub(od.scheduled_time().elapsed()));
+
+        od.drop_messages(|_, msg| match &msg.event_with_height.event {
+            IbcEvent::SendPacket(event) => is_received(&event.packet),
+            _ => Ok(false),
+        })
+    }
+}
+
+fn has_recv_packets(od: &OperationalData) -> bool {
+    od.batch
+        .iter()
+        .any(|msg| matches!(msg.event_with_height.event, IbcEvent::SendPacket(_)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::convert::Infallible;
+    use std::time::Instant;
+
+    use ibc_proto::google::protobuf::Any;
+    use ibc_relayer_types::core::ics04_channel::events::{SendPacket, WriteAcknowledgement};
+    use ibc_relayer_types::core::ics04_channel::packet::Sequence;
+    use ibc_relayer_types::Height;
+
+    use crate::chain::tracking::TrackingId;
+    use crate::event::IbcEventWithHeight;
+    use crate::link::operational_data::TransitMessage;
+
+    const BLOCK_TIME: Duration = Duration::from_millis(200);
+
+    fn packet(sequence: u64) -> Packet {
+        Packet {
+            sequence: Sequence::from(sequence),
+            ..Packet::default()
+        }
+    }
+
+    fn transit_message(event: IbcEvent, type_url: &str) -> TransitMessage {
+        TransitMessage {
+            event_with_height: IbcEventWithHeight::new(event, Height::new(0, 10).unwrap()),
+            msg: Any {
+                type_url: type_url.to_owned(),
+                value: vec![],
+            },
+        }
+    }
+
+    fn operational_data(target: OperationalDataTarget) -> OperationalData {
+        let mut od = OperationalData::new(
+            Height::new(0, 10).unwrap(),
+            target,
+            TrackingId::new_static("recheck"),
+            Duration::ZERO,
+        );
+
+        for sequence in 1..=3 {
+            od.push(transit_message(
+                IbcEvent::SendPacket(SendPacket {
+                    packet: packet(sequence),
+                }),
+                "/ibc.core.channel.v1.MsgRecvPacket",
+            ));
+        }
+
+        od.push(transit_message(
+            IbcEvent::WriteAcknowledgement(WriteAcknowledgement {
+                packet: packet(1),
+                ack: vec![1],
+            }),
+            "/ibc.core.channel.v1.MsgAcknowledgement",
+        ));
+
+        od
+    }
+
+    fn sequences(od: &OperationalData) -> Vec<u64> {
+        od.batch
+            .iter()
+            .map(|msg| u64::from(msg.event_with_height.event.packet().unwrap().sequence))
+            .collect()
+    }
+
+    #[test]
+    fn grace_delay_is_waited_once_for_the_operational_data_scheduled_together() {
+        let mut first = operational_data(OperationalDataTarget::Destination);
+        let mut second = operational_data(OperationalDataTarget::Destination);
+
+        let recheck = ReceiptRecheck::new(BLOCK_TIME);
+        let start = Instant::now();
+
+        recheck
+            .recheck(&mut first, |_| Ok::<_, Infallible>(false))
+            .unwrap();
+        assert!(start.elapsed() >= BLOCK_TIME);
+
+        recheck
+            .recheck(&mut second, |_| Ok::<_, Infallible>(false))
+            .unwrap();
+        assert!(start.elapsed() < 2 * BLOCK_TIME);
+    }
+
+    #[test]
+    fn source_operational_data_is_not_rechecked() {
+        let mut od = operational_data(OperationalDataTarget::Source);
+
+        let dropped = ReceiptRecheck::new(Duration::ZERO)
+            .recheck(&mut od, |_| Ok::<_, Infallible>(true))
+            .unwrap();
+
+        assert_eq!(dropped, 0);
+        assert_eq!(sequences(&od), [1, 2, 3, 1]);
+    }
+
+    #[test]
+    fn failed_recheck_keeps_the_packets_not_found_to_be_received() {
+        let mut od = operational_data(OperationalDataTarget::Destination);
+
+        let result = ReceiptRecheck::new(Duration::ZERO).recheck(&mut od, |packet| {
+            if packet.sequence == Sequence::from(3) {
+                Err("query failed")
+            } else {
+                Ok(true)
+            }
+        });
+
+        assert!(result.is_err());
+        assert_eq!(sequences(&od), [3, 1]);
+    }
+}
@@ -1,12 +1,14 @@
 use alloc::collections::BTreeMap as HashMap;
-use alloc::collections::VecDeque;
+use alloc::collections::{BTreeSet, VecDeque};
+use alloc::sync::Arc;
 use ibc_relayer_types::core::ics04_channel::packet::Sequence;
 use std::ops::Sub;
 use std::time::{Duration, Instant};
 
+use crossbeam_channel::Sender;
 use ibc_proto::google::protobuf::Any;
-use ibc_proto::ibc::applications::transfer::v2::FungibleTokenPacketData as RawPacketData;
 use itertools::Itertools;
+use sha2::{Digest, Sha256};
 use tracing::{debug, error, info, span, trace, warn, Level};
 
 use ibc_relayer_types::core::ics02_client::events::ClientMisbehaviour as ClientMisbehaviourEvent;
@@ -14,29 +16,44 @@ use ibc_relayer_types::core::ics04_channel::channel::{
     ChannelEnd, Ordering, State as ChannelState,
 };
 use ibc_relayer_types::core::ics04_channel::events::{SendPacket, WriteAcknowledgement};
+use ibc_relayer_types::core::ics04_channel::msgs::recv_packet;
 use ibc_relayer_types::core::ics04_channel::msgs::{
     acknowledgement::MsgAcknowledgement, chan_close_confirm::MsgChannelCloseConfirm,
     recv_packet::MsgRecvPacket, timeout::MsgTimeout, timeout_on_close::MsgTimeoutOnClose,
 };
 use ibc_relayer_types::core::ics04_channel::packet::{Packet, PacketMsgType};
-use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use ibc_relayer_types::core::ics04_channel::timeout::TimeoutHeight;
+use ibc_relayer_types::core::ics23_commitment::merkle::MerkleProof;
+use ibc_relayer_types::core::ics24_host::identifier::{
+    ChainId, ChannelId, ClientId, ConnectionId, PortId,
+};
 use ibc_relayer_types::events::{IbcEvent, IbcEventType, WithBlockDataType};
 use ibc_relayer_types::signer::Signer;
 use ibc_relayer_types::timestamp::Timestamp;
 use ibc_relayer_types::tx_msg::Msg;
 use ibc_relayer_types::Height;
 
+use crate::chain::cosmos::timeout::encode_timeout_timestamp;
 use crate::chain::counterparty::unreceived_acknowledgements;
 use crate::chain::counterparty::unreceived_packets;
+use crate::chain::counterparty::{
+    packet_acknowledgements, unreceived_acknowledgements_sequences, unreceived_packets_sequences,
+};
 use crate::chain::endpoint::ChainStatus;
 use crate::chain::handle::ChainHandle;
+use crate::chain::requests::PageRequest;
 use crate::chain::requests::Paginate;
+use crate::chain::requests::QueryChannelClientStateRequest;
 use crate::chain::requests::QueryChannelRequest;
 use crate::chain::requests::QueryClientEventRequest;
+use crate::chain::requests::QueryClientStateRequest;
+use crate::chain::requests::QueryConsensusStateHeightsRequest;
 use crate::chain::requests::QueryHeight;
 use crate::chain::requests::QueryHostConsensusStateRequest;
 use crate::chain::requests::QueryNextSequenceReceiveRequest;
 use crate::chain::requests::QueryPacketCommitmentRequest;
+use crate::chain::requests::QuerySendPacketsRequest;
+use crate::chain::requests::QuerySenderTxsRequest;
 use crate::chain::requests::QueryTxRequest;
 use crate::chain::requests::QueryUnreceivedAcksRequest;
 use crate::chain::requests::QueryUnreceivedPacketsRequest;
@@ -45,31 +62,57 @@ use crate::chain::tracking::TrackedMsgs;
 use crate::chain::tracking::TrackingId;
 use crate::channel::error::ChannelError;
 use crate::channel::Channel;
+use crate::client_state::AnyClientState;
+use crate::config::filter::DenomPolicy;
 use crate::config::types::ics20_field_size_limit::Ics20FieldSizeLimit;
 use crate::config::types::ics20_field_size_limit::ValidationResult;
+use crate::config::{
+    ChainConfig, ChannelVerification, ClearOrder, PacketCommitmentSource, ProofHeightStrategy,
+    PrunedHeightHandling, TimestampUnit,
+};
 use crate::event::source::EventBatch;
 use crate::event::IbcEventWithHeight;
+use crate::foreign_client::pending_refresh::PendingRefreshes;
 use crate::foreign_client::{ForeignClient, ForeignClientError};
+use crate::link::clear_progress::{
+    ClearBudget, ClearOutcome, ClearProgress, ClearingStatus, SequenceRange,
+};
+use crate::link::compaction::Compaction;
 use crate::link::error::{self, LinkError};
 use crate::link::operational_data::{
-    OperationalData, OperationalDataTarget, TrackedEvents, TransitMessage,
+    OperationalData, OperationalDataTarget, SigningKey, TrackedEvents, TransitMessage,
 };
+use crate::link::packet_data::{FinalDestination, PacketDataDecoder, PacketDataDecoderRegistry};
 use crate::link::packet_events::query_packet_events_with;
 use crate::link::packet_events::query_send_packet_events;
 use crate::link::packet_events::query_write_ack_events;
 use crate::link::pending::PendingTxs;
+use crate::link::receipt_recheck::ReceiptRecheck;
+use crate::link::relay_plan::RelayPlan;
 use crate::link::relay_sender::{AsyncReply, SubmitReply};
 use crate::link::relay_summary::RelaySummary;
+use crate::link::relayed_packets::RelayedPackets;
+use crate::link::sent_packets::SentPackets;
+use crate::link::timeout_lane::TimeoutLane;
 use crate::link::LinkParameters;
 use crate::link::{pending, relay_sender};
 use crate::path::PathIdentifiers;
 use crate::telemetry;
 use crate::util::collate::CollatedIterExt;
+use crate::util::lock::{LockExt, RwArc};
 use crate::util::pretty::PrettyEvents;
 use crate::util::queue::Queue;
 
 const MAX_RETRIES: usize = 5;
 
+/// How many of the latest packets sent on the source channel the relaying path
+/// remembers having scheduled from event batches, see [`RelayPath::update_schedule`].
+const MAX_SCHEDULED_SEND_PACKETS: usize = 10_000;
+
+/// How many of the latest transfers forwarded onward by the destination chain the
+/// relaying path remembers having reported, see [`RelayPath::report_forwarded_transfers`].
+const MAX_REPORTED_FORWARDED_TRANSFERS: usize = 10_000;
+
 /// Whether or not to resubmit packets when pending transactions
 /// fail to process within the given timeout duration.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -118,6 +161,104 @@ pub struct RelayPath<ChainA: ChainHandle, ChainB: ChainHandle> {
     pub max_memo_size: Ics20FieldSizeLimit,
     pub max_receiver_size: Ics20FieldSizeLimit,
     pub exclude_src_sequences: Vec<Sequence>,
+
+    // Names of the keys signing the `RecvPacket` messages of
+    // ICS-20 transfers on the destination chain, indexed by denom.
+    dst_denom_key_names: HashMap<String, String>,
+
+    // Names of the keys signing in turn the transactions
+    // relayed to the source and destination chains.
+    src_signing_accounts: Vec<String>,
+    dst_signing_accounts: Vec<String>,
+
+    // Whether ICS-20 transfers with an empty memo must not be
+    // received on the destination channel.
+    skip_empty_memo: bool,
+
+    // Policy filtering the ICS-20 transfers sent on the source
+    // channel by denom, from the packet filter of the source chain.
+    denom_policy: Option<DenomPolicy>,
+
+    // How to relay messages whose proofs must be built at a height
+    // which the source chain has pruned the state of.
+    pruned_height_handling: PrunedHeightHandling,
+
+    // How to select the height at which the proofs of the messages
+    // built from the events of the source chain are built.
+    proof_height_strategy: ProofHeightStrategy,
+
+    // Where the commitments of the packets sent by the
+    // source and destination chains are obtained from.
+    src_packet_commitment_source: PacketCommitmentSource,
+    dst_packet_commitment_source: PacketCommitmentSource,
+
+    // The unit in which the destination chain interprets the timeout
+    // timestamps of packets, which its timestamp is compared in.
+    dst_timeout_timestamp_unit: TimestampUnit,
+
+    // The lane in which timeouts are submitted to the source chain, in batches
+    // paced separately from the other messages, if configured for that chain.
+    timeout_lane: Option<TimeoutLane>,
+
+    // The grace re-check of the receipts of the packets on the destination
+    // chain before submitting their messages, if configured for that chain.
+    dst_receipt_recheck: Option<ReceiptRecheck>,
+
+    // Time budget and order of a packet clearing pass, and the progress of the
+    // clearing of packets and acknowledgments made by the previous pass.
+    max_clear_duration: Option<Duration>,
+    clear_order: ClearOrder,
+    recv_clear_progress: ClearProgress,
+    ack_clear_progress: ClearProgress,
+
+    // The progress of the compaction of the operational data
+    // scheduled for relaying to the source and destination chains.
+    src_compaction: Compaction,
+    dst_compaction: Compaction,
+
+    // Packets which the relayer relayed in the blocks preceding its start,
+    // which are not relayed again by packet clearing.
+    relayed_packets: RelayedPackets,
+
+    // Packets sent on the source and destination channels which may still be pending,
+    // found from the `SendPacket` events of the chains whose commitments are obtained
+    // from these events, as their commitments cannot be listed.
+    src_sent_packets: RwArc<SentPackets>,
+    dst_sent_packets: RwArc<SentPackets>,
+
+    // Sequences of the latest packets sent on the source channel which were scheduled
+    // from event batches, so that a packet delivered both by the event source and by
+    // the packet worker handing over the packets sent by its transactions is only
+    // relayed once.
+    scheduled_send_packets: RwArc<BTreeSet<Sequence>>,
+
+    // Sequences of the latest transfers forwarded onward by the destination chain whose
+    // final destination was reported, so that each of them is only reported once.
+    reported_forwarded_transfers: RwArc<BTreeSet<Sequence>>,
+
+    // The chains at the other end of the channels of the destination chain through
+    // which it forwards transfers onward, if known.
+    forwarding_channel_chains: RwArc<HashMap<(PortId, ChannelId), Option<ChainId>>>,
+
+    // Verification settings of the source and destination channels, which
+    // apply to the packet events of the source and destination chains.
+    src_verification: ChannelVerification,
+    dst_verification: ChannelVerification,
+
+    // Whether the packets are relayed one at a time in the order of their sequence,
+    // even though the channel is unordered.
+    force_ordered_relay: bool,
+
+    // Decoders of the data of the packets sent from the ports of the source chain.
+    packet_data_decoders: PacketDataDecoderRegistry,
+
+    // Where to hand over the packets sent by the confirmed transactions
+    // of the relayer, so that they are relayed onward straight away.
+    sent_packets_tx: Option<Sender<EventBatch>>,
+
+    // The refreshes in progress of the clients on both chains, which
+    // the client updates built to relay wait for to complete.
+    pending_refreshes: Option<Arc<PendingRefreshes>>,
 }
 
 impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
@@ -145,6 +286,62 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         let src_port_id = channel.src_port_id().clone();
         let dst_port_id = channel.dst_port_id().clone();
 
+        let src_config = src_chain.config().map_err(LinkError::relayer)?;
+        let dst_config = dst_chain.config().map_err(LinkError::relayer)?;
+
+        let src_verification =
+            src_config.channel_verification(&src_channel_id, link_parameters.verification);
+        let dst_verification =
+            dst_config.channel_verification(&dst_channel_id, link_parameters.verification);
+
+        let force_ordered_relay = channel.ordering == Ordering::Unordered
+            && src_config.force_ordered_relay(&src_channel_id);
+
+        // The packets of an ordered channel can only be received in the order of their
+        // sequence, and so are those of a channel whose relay is forced to be ordered
+        let clear_order = if channel.ordering != Ordering::Unordered || force_ordered_relay {
+            ClearOrder::OldestFirst
+        } else {
+            link_parameters.clear_order
+        };
+
+        let denom_policy = src_config
+            .packet_filter()
+            .denom_policy(&src_channel_id)
+            .cloned();
+
+        let dst_timeout_timestamp_unit = dst_config.timeout_timestamp_unit();
+        let timeout_lane = src_config.timeout_batch().map(TimeoutLane::new);
+        let dst_receipt_recheck = dst_config.receipt_recheck_delay().map(ReceiptRecheck::new);
+
+        let src_signing_accounts = src_config.signing_accounts();
+        let dst_signing_accounts = dst_config.signing_accounts();
+
+        let src_packet_commitment_source = src_config.packet_commitment_source();
+        let dst_packet_commitment_source = dst_config.packet_commitment_source();
+
+        let (dst_denom_key_names, skip_empty_memo) = match dst_config {
+            ChainConfig::CosmosSdk(config) => (
+                config.denom_key_names.into_iter().collect(),
+                config.skip_empty_memo_channels.contains(&dst_channel_id),
+            ),
+            _ => (HashMap::new(), false),
+        };
+
+        let (pruned_height_handling, proof_height_strategy, packet_data_decoders) = match src_config
+        {
+            ChainConfig::CosmosSdk(config) => (
+                config.pruned_height_handling,
+                config.proof_height_strategy,
+                PacketDataDecoderRegistry::from_encodings(&config.packet_data_encodings),
+            ),
+            _ => (
+                PrunedHeightHandling::default(),
+                ProofHeightStrategy::default(),
+                PacketDataDecoderRegistry::default(),
+            ),
+        };
+
         let path = PathIdentifiers {
             port_id: dst_port_id.clone(),
             channel_id: dst_channel_id.clone(),
@@ -152,7 +349,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             counterparty_channel_id: src_channel_id.clone(),
         };
 
-        Ok(Self {
+        let relay_path = Self {
             channel,
 
             path_id: path,
@@ -168,7 +365,71 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             max_receiver_size: link_parameters.max_receiver_size,
 
             exclude_src_sequences: link_parameters.exclude_src_sequences,
-        })
+
+            dst_denom_key_names,
+            src_signing_accounts,
+            dst_signing_accounts,
+            skip_empty_memo,
+            denom_policy,
+            pruned_height_handling,
+            proof_height_strategy,
+            src_packet_commitment_source,
+            dst_packet_commitment_source,
+            dst_timeout_timestamp_unit,
+            timeout_lane,
+            dst_receipt_recheck,
+
+            max_clear_duration: link_parameters.max_clear_duration,
+            clear_order,
+            recv_clear_progress: ClearProgress::with_order(clear_order),
+            ack_clear_progress: ClearProgress::with_order(clear_order),
+
+            src_compaction: Compaction::new(),
+            dst_compaction: Compaction::new(),
+
+            relayed_packets: RelayedPackets::default(),
+            src_sent_packets: RwArc::new_lock(SentPackets::default()),
+            dst_sent_packets: RwArc::new_lock(SentPackets::default()),
+            scheduled_send_packets: RwArc::new_lock(BTreeSet::new()),
+            reported_forwarded_transfers: RwArc::new_lock(BTreeSet::new()),
+            forwarding_channel_chains: RwArc::new_lock(HashMap::new()),
+
+            src_verification,
+            dst_verification,
+
+            force_ordered_relay,
+
+            packet_data_decoders,
+
+            sent_packets_tx: None,
+
+            pending_refreshes: None,
+        };
+
+        relay_path.verify_client_chain_ids()?;
+
+        Ok(relay_path)
+    }
+
+    /// Checks that the clients of the connection of the channel track the chains of the path,
+    /// ie. that the client hosted by each chain tracks the other chain, unless disabled.
+    /// Fails with [`LinkError::client_chain_id_mismatch`] otherwise, as the packets would
+    /// be relayed to the wrong chain.
+    pub fn verify_client_chain_ids(&self) -> Result<(), LinkError> {
+        if !self.src_verification.verify_client_chain_id {
+            return Ok(());
+        }
+
+        check_client_chain_id(
+            self.src_chain(),
+            self.src_client_id(),
+            &self.dst_chain().id(),
+        )?;
+        check_client_chain_id(
+            self.dst_chain(),
+            self.dst_client_id(),
+            &self.src_chain().id(),
+        )
     }
 
     pub fn src_chain(&self) -> &ChainA {
@@ -255,6 +516,193 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             .map_err(|e| LinkError::signer(self.dst_chain().id(), e))
     }
 
+    /// Returns the name of the key configured to sign the `RecvPacket`
+    /// message of the given packet, if it is an ICS-20 transfer of a
+    /// denom for which the destination chain has a dedicated key.
+    fn recv_packet_key_name(&self, packet: &Packet) -> Option<&String> {
+        if self.dst_denom_key_names.is_empty() {
+            return None;
+        }
+
+        let denom = ics20_transfer_denom(&self.packet_data_decoders, packet)?;
+        self.dst_denom_key_names.get(&denom)
+    }
+
+    fn recv_packet_signer(&self, packet: &Packet) -> Result<Signer, LinkError> {
+        match self.recv_packet_key_name(packet) {
+            Some(key_name) => self
+                .dst_chain()
+                .get_key_signer(key_name.clone())
+                .map_err(|e| LinkError::signer(self.dst_chain().id(), e)),
+            None => self.dst_signer(),
+        }
+    }
+
+    /// Returns the key which signed the given `RecvPacket` message, if it is
+    /// not the default key of the destination chain.
+    fn recv_packet_signing_key(
+        &self,
+        msg: &TransitMessage,
+    ) -> Result<Option<SigningKey>, LinkError> {
+        if msg.msg.type_url != recv_packet::TYPE_URL {
+            return Ok(None);
+        }
+
+        let Some(key_name) = msg
+            .event_with_height
+            .event
+            .packet()
+            .and_then(|packet| self.recv_packet_key_name(packet))
+        else {
+            return Ok(None);
+        };
+
+        let signer = self
+            .dst_chain()
+            .get_key_signer(key_name.clone())
+            .map_err(|e| LinkError::signer(self.dst_chain().id(), e))?;
+
+        Ok(Some(SigningKey {
+            key_name: key_name.clone(),
+            signer,
+        }))
+    }
+
+    /// Returns the key which signs the next transaction relayed to the given chain,
+    /// if the chain is configured with `signing_accounts`, which sign in turn.
+    pub(crate) fn next_signing_key(
+        &self,
+        target: OperationalDataTarget,
+    ) -> Result<Option<SigningKey>, LinkError> {
+        let (chain_id, key_name) = match target {
+            OperationalDataTarget::Source => (
+                self.src_chain().id(),
+                self.src_chain().next_signing_account(),
+            ),
+            OperationalDataTarget::Destination => (
+                self.dst_chain().id(),
+                self.dst_chain().next_signing_account(),
+            ),
+        };
+
+        let Some(key_name) = key_name.map_err(|e| LinkError::signer(chain_id.clone(), e))? else {
+            return Ok(None);
+        };
+
+        let signer = match target {
+            OperationalDataTarget::Source => self.src_chain().get_key_signer(key_name.clone()),
+            OperationalDataTarget::Destination => self.dst_chain().get_key_signer(key_name.clone()),
+        }
+        .map_err(|e| LinkError::signer(chain_id, e))?;
+
+        Ok(Some(SigningKey { key_name, signer }))
+    }
+
+    /// Registers the decoder of the data of the packets sent from the given port of the
+    /// source chain, replacing the decoder set up from the configuration of the chain.
+    pub fn register_packet_data_decoder(
+        &mut self,
+        port_id: PortId,
+        decoder: Arc<dyn PacketDataDecoder>,
+    ) {
+        self.packet_data_decoders.register(port_id, decoder);
+    }
+
+    /// Hands over the packets sent by the transactions of the relayer once they are
+    /// confirmed, eg. the packets forwarded by a packet forwarding middleware upon
+    /// receiving a packet, to the given channel, so that they are relayed onward
+    /// without waiting for the event source or packet clearing to pick them up.
+    pub fn forward_sent_packets(&mut self, tx: Sender<EventBatch>) {
+        self.sent_packets_tx = Some(tx);
+    }
+
+    /// Has the client updates built to relay wait for the refresh of their client
+    /// in progress in the given `pending_refreshes`, if any, to complete first.
+    pub fn wait_for_pending_refreshes(&mut self, pending_refreshes: Arc<PendingRefreshes>) {
+        self.pending_refreshes = Some(pending_refreshes);
+    }
+
+    /// Scans the transactions submitted by the relayer in the last `lookback` blocks
+    /// of both chains, in order to find out which packets it relayed before it was
+    /// restarted, possibly without confirming them, so that packet clearing does
+    /// not relay them again.
+    pub fn recover_relayed_packets(&mut self, lookback: u64) -> Result<(), LinkError> {
+        let _span = span!(Level::ERROR, "recover_relayed_packets", lookback).entered();
+
+        let mut src_signers = vec![self.src_signer()?];
+
+        for key_name in &self.src_signing_accounts {
+            let signer = self
+                .src_chain()
+                .get_key_signer(key_name.clone())
+                .map_err(|e| LinkError::signer(self.src_chain().id(), e))?;
+
+            src_signers.push(signer);
+        }
+
+        let mut dst_signers = vec![self.dst_signer()?];
+
+        for key_name in self
+            .dst_denom_key_names
+            .values()
+            .chain(&self.dst_signing_accounts)
+            .unique()
+        {
+            let signer = self
+                .dst_chain()
+                .get_key_signer(key_name.clone())
+                .map_err(|e| LinkError::signer(self.dst_chain().id(), e))?;
+
+            dst_signers.push(signer);
+        }
+
+        let src_min_height = lookback_height(self.src_latest_height()?, lookback);
+        let dst_min_height = lookback_height(self.dst_latest_height()?, lookback);
+
+        let mut events = Vec::new();
+
+        for sender in src_signers {
+            let src_events = self
+                .src_chain()
+                .query_txs(QueryTxRequest::Sender(QuerySenderTxsRequest {
+                    sender,
+                    min_height: src_min_height,
+                }))
+                .map_err(|e| LinkError::query(self.src_chain().id(), e))?;
+
+            events.extend(src_events);
+        }
+
+        for sender in dst_signers {
+            let dst_events = self
+                .dst_chain()
+                .query_txs(QueryTxRequest::Sender(QuerySenderTxsRequest {
+                    sender,
+                    min_height: dst_min_height,
+                }))
+                .map_err(|e| LinkError::query(self.dst_chain().id(), e))?;
+
+            events.extend(dst_events);
+        }
+
+        self.relayed_packets = RelayedPackets::from_events(
+            self.src_port_id(),
+            self.src_channel_id(),
+            self.dst_port_id(),
+            self.dst_channel_id(),
+            &events,
+        );
+
+        if !self.relayed_packets.is_empty() {
+            info!(
+                count = self.relayed_packets.len(),
+                "recovered packets relayed in the last {lookback} blocks"
+            );
+        }
+
+        Ok(())
+    }
+
     pub(crate) fn src_latest_height(&self) -> Result<Height, LinkError> {
         self.src_chain()
             .query_latest_height()
@@ -329,18 +777,39 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         self.channel.ordering == Ordering::Ordered
     }
 
+    /// Whether the packets of this unordered channel are relayed one at a time
+    /// in the order of their sequence, as configured for the source channel.
+    fn forces_ordered_relay(&self) -> bool {
+        self.force_ordered_relay
+    }
+
+    /// Splits the given operational data into the pieces relayed one after the other,
+    /// ie. one per message in the order of their sequence if the packets are relayed
+    /// one at a time, or the operational data as is otherwise.
+    pub(crate) fn split_for_relay(&self, od: OperationalData) -> Vec<OperationalData> {
+        if self.forces_ordered_relay() {
+            od.split_in_sequence_order()
+        } else {
+            vec![od]
+        }
+    }
+
     pub fn build_update_client_on_dst(&self, height: Height) -> Result<Vec<Any>, LinkError> {
         let client = self.restore_dst_client();
-        client
-            .wait_and_build_update_client(height)
-            .map_err(LinkError::client)
+        match &self.pending_refreshes {
+            Some(refreshes) => client.wait_for_refresh_and_build_update_client(height, refreshes),
+            None => client.wait_and_build_update_client(height),
+        }
+        .map_err(LinkError::client)
     }
 
     pub fn build_update_client_on_src(&self, height: Height) -> Result<Vec<Any>, LinkError> {
         let client = self.restore_src_client();
-        client
-            .wait_and_build_update_client(height)
-            .map_err(LinkError::client)
+        match &self.pending_refreshes {
+            Some(refreshes) => client.wait_for_refresh_and_build_update_client(height, refreshes),
+            None => client.wait_and_build_update_client(height),
+        }
+        .map_err(LinkError::client)
     }
 
     fn build_chan_close_confirm_from_event(
@@ -397,6 +866,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                 IbcEvent::SendPacket(send_packet_ev) => {
                     if src_channel_id == send_packet_ev.src_channel_id()
                         && self.src_port_id() == send_packet_ev.src_port_id()
+                        && self.is_first_schedule(send_packet_ev.packet.sequence)
                     {
                         result.push(event_with_height);
                     }
@@ -434,8 +904,9 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         &self,
         height: Option<Height>,
         clear_limit: usize,
+        sequences: SequenceRange,
     ) -> Result<(), LinkError> {
-        let _span = span!(Level::ERROR, "relay_pending_packets", ?height).entered();
+        let _span = span!(Level::ERROR, "relay_pending_packets", ?height, %sequences).entered();
 
         let tracking_id = TrackingId::new_packet_clearing();
         telemetry!(received_event_batch, tracking_id);
@@ -443,19 +914,34 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         let src_config = self.src_chain().config().map_err(LinkError::relayer)?;
         let chunk_size = src_config.query_packets_chunk_size();
 
+        // The budget is shared by the retries, which resume where the failed attempt stopped
+        let budget = ClearBudget::start(self.max_clear_duration);
+
         for i in 1..=MAX_RETRIES {
             let cleared_recv = self.schedule_recv_packet_and_timeout_msgs(
                 height,
                 chunk_size,
                 clear_limit,
+                sequences,
                 tracking_id,
+                &budget,
             );
 
-            let cleared_ack =
-                self.schedule_packet_ack_msgs(height, chunk_size, clear_limit, tracking_id);
+            let cleared_ack = self.schedule_packet_ack_msgs(
+                height,
+                chunk_size,
+                clear_limit,
+                sequences,
+                tracking_id,
+                &budget,
+            );
 
             match cleared_recv.and(cleared_ack) {
                 Ok(()) => return Ok(()),
+                // Retrying cannot help, the proofs height is gone for good
+                Err(e) if matches!(e.detail(), error::LinkErrorDetail::ProofHeightPruned(_)) => {
+                    return Err(e)
+                }
                 Err(e) => error!(
                     "failed to clear packets, retry {}/{}: {}",
                     i, MAX_RETRIES, e
@@ -468,18 +954,22 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
 
     /// Clears any packets that were sent before `height`.
     /// If no height is passed in, then the latest height of the source chain is used.
+    ///
+    /// Only the packets whose sequence is within `sequences` are cleared, the others
+    /// are left pending.
     pub fn schedule_packet_clearing(
         &self,
         height: Option<Height>,
         clear_limit: usize,
+        sequences: SequenceRange,
     ) -> Result<(), LinkError> {
-        let _span = span!(Level::ERROR, "schedule_packet_clearing", ?height).entered();
+        let _span = span!(Level::ERROR, "schedule_packet_clearing", ?height, %sequences).entered();
 
         let clear_height = height
             .map(|h| h.decrement().map_err(|e| LinkError::decrement_height(h, e)))
             .transpose()?;
 
-        self.relay_pending_packets(clear_height, clear_limit)?;
+        self.relay_pending_packets(clear_height, clear_limit, sequences)?;
 
         debug!(height = ?clear_height, "done relaying pending packets at clear height");
 
@@ -510,6 +1000,25 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         self.events_to_operational_data(events)
     }
 
+    /// Whether the packet sent on the source channel with the given sequence was not
+    /// scheduled from an earlier event batch yet, eg. as a packet handed over by another
+    /// packet worker once its transaction was confirmed, which the event source delivers
+    /// as well. Records the packet as scheduled.
+    fn is_first_schedule(&self, sequence: Sequence) -> bool {
+        let mut scheduled = self.scheduled_send_packets.acquire_write();
+
+        if !scheduled.insert(sequence) {
+            debug!(%sequence, "skipping packet already scheduled for relaying");
+            return false;
+        }
+
+        if scheduled.len() > MAX_SCHEDULED_SEND_PACKETS {
+            scheduled.pop_first();
+        }
+
+        true
+    }
+
     /// Produces and schedules operational data for this relaying path based on the input events.
     pub(crate) fn events_to_operational_data(
         &self,
@@ -523,7 +1032,10 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             self.schedule_operational_data(src_od)?;
         }
         if let Some(dst_od) = dst_opt {
-            self.schedule_operational_data(dst_od)?;
+            // Transfers of denoms with a dedicated key are submitted in separate transactions
+            for od in dst_od.split_by_signing_key(|msg| self.recv_packet_signing_key(msg))? {
+                self.schedule_operational_data(od)?;
+            }
         }
 
         Ok(())
@@ -537,9 +1049,65 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
     ///
     /// For the source chain, the op. data will contain timeout packet messages (`MsgTimeoutOnClose`
     /// or `MsgTimeout`).
+    ///
+    /// The proofs of the messages are built at the height selected by the
+    /// `proof_height_strategy` of the source chain, see [`Self::select_proofs_height`].
+    ///
+    /// If the proofs of the messages cannot be built because the source chain has
+    /// pruned the height of the events, handles them as configured by its
+    /// `pruned_height_handling`, see [`Self::refresh_pruned_proofs_height`].
     fn generate_operational_data(
         &self,
         events: TrackedEvents,
+    ) -> Result<(Option<OperationalData>, Option<OperationalData>), LinkError> {
+        let events = self.select_proofs_height(events)?;
+
+        match self.try_generate_operational_data(&events) {
+            Err(e) => {
+                let Some(proofs_height) = events.events().iter().map(|ev| ev.height).min() else {
+                    return Err(e);
+                };
+
+                let height = self.refresh_pruned_proofs_height(proofs_height, e)?;
+
+                self.try_generate_operational_data(&events.with_height(height))
+            }
+            result => result,
+        }
+    }
+
+    /// Under the `minimal_update` proof height strategy, moves the given events to the
+    /// height preceding the latest height of the client on the destination chain if that
+    /// client is already past all of them, so that the messages built from them can be
+    /// verified without updating the client first.
+    fn select_proofs_height(&self, events: TrackedEvents) -> Result<TrackedEvents, LinkError> {
+        if self.proof_height_strategy != ProofHeightStrategy::MinimalUpdate {
+            return Ok(events);
+        }
+
+        let Some(events_height) = events.events().iter().map(|ev| ev.height).max() else {
+            return Ok(events);
+        };
+
+        let client_state = query_latest_client_state(self.dst_chain(), self.dst_client_id())?;
+
+        match minimal_update_proofs_height(events_height, client_state.latest_height()) {
+            Some(height) => {
+                debug!(
+                    %events_height,
+                    proofs_height = %height,
+                    "building proofs at the latest height of the client"
+                );
+
+                Ok(events.with_height(height))
+            }
+            None => Ok(events),
+        }
+    }
+
+    fn try_generate_operational_data(
+        &self,
+        events: &TrackedEvents,
     ) -> Result<(Option<OperationalData>, Option<OperationalData>), LinkError> {
         let _span = span!(
             Level::ERROR,
@@ -584,7 +1152,8 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                 // If the event is a ICS-04 packet event, and the packet contains ICS-20
                 // packet data, check that the ICS-20 fields are within the configured limits.
                 if !check_ics20_fields_size(
-                    &packet.data,
+                    &self.packet_data_decoders,
+                    packet,
                     self.max_memo_size,
                     self.max_receiver_size,
                 ) {
@@ -630,6 +1199,11 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                     if self.send_packet_event_handled(event)? {
                         debug!(?event, "SendPacket event has already been handled");
 
+                        // Eg. the packet timed out, so it is not pending anymore
+                        self.src_sent_packets
+                            .acquire_write()
+                            .forget([event.packet.sequence]);
+
                         (None, None)
                     } else {
                         self.build_recv_or_timeout_from_send_packet_event(
@@ -693,6 +1267,30 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         Ok((src_od, dst_od))
     }
 
+    /// Drops the `RecvPacket` messages of `odata` whose packet was received by the
+    /// destination chain during the grace delay, if configured for the destination chain.
+    ///
+    /// The messages not found to be received are submitted if the receipts cannot be queried.
+    fn recheck_receipts(&self, odata: &mut OperationalData) {
+        let Some(recheck) = &self.dst_receipt_recheck else {
+            return;
+        };
+
+        match recheck.recheck(odata, |packet| self.send_packet_received_on_dst(packet)) {
+            Ok(0) => {}
+            Ok(dropped) => info!(
+                dropped,
+                "packets received by the destination chain during the grace delay, \
+                not relaying them"
+            ),
+            Err(e) => warn!(
+                error = %e,
+                "failed to recheck the receipts of the packets on the destination chain, \
+                submitting them anyway"
+            ),
+        }
+    }
+
     /// Relays an [`OperationalData`] using a specific
     /// sender, which implements [`relay_sender::Submit`].
     pub(crate) fn relay_from_operational_data<S: relay_sender::Submit>(
@@ -704,6 +1302,12 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
 
         let mut odata = initial_od;
 
+        self.recheck_receipts(&mut odata);
+
+        if odata.batch.is_empty() {
+            return Ok(S::Reply::empty());
+        }
+
         for i in 0..MAX_RETRIES {
             debug!(retry.current = i + 1, retry.max = MAX_RETRIES, "retrying");
 
@@ -713,6 +1317,10 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                     // Done with this op. data
                     info!("submitted");
 
+                    if odata.target == OperationalDataTarget::Destination {
+                        self.report_forwarded_transfers(&odata);
+                    }
+
                     telemetry!({
                         let (chain, counterparty, channel_id, port_id) =
                             self.target_info(odata.target);
@@ -742,6 +1350,16 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                         }
                     }
                 }
+                Err(e) if odata.target == OperationalDataTarget::Destination => {
+                    // The messages cannot be relayed if their proofs were built at a height
+                    // which the source chain has pruned since, otherwise the error is unrecoverable
+                    let height = self.refresh_pruned_proofs_height(odata.proofs_height, e)?;
+
+                    match self.regenerate_operational_data(odata.with_proofs_height(height)) {
+                        None => return Ok(S::Reply::empty()), // Nothing to retry
+                        Some(new_od) => odata = new_od,
+                    }
+                }
                 Err(e) => {
                     // Unrecoverable error, propagate up the stack
                     return Err(e);
@@ -752,6 +1370,49 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         Ok(S::Reply::empty())
     }
 
+    /// Handles the `error` which occurred while relaying messages whose proofs are
+    /// built at `proofs_height` on the source chain.
+    ///
+    /// If the source chain has pruned the state at that height, the messages can never be
+    /// relayed as they are. Depending on the `pruned_height_handling` of the source chain,
+    /// either returns its latest height, at which to build the proofs instead, or a
+    /// `ProofHeightPruned` error. Otherwise, returns the original error.
+    ///
+    /// The state is found to be pruned either from the earliest height reported by the
+    /// node of the source chain, or from the error itself, see [`pruned_state_height`].
+    fn refresh_pruned_proofs_height(
+        &self,
+        proofs_height: Height,
+        error: LinkError,
+    ) -> Result<Height, LinkError> {
+        let earliest_height = self.src_chain().query_earliest_height().ok();
+
+        let Some(earliest_height) = pruned_state_height(proofs_height, earliest_height, &error)
+        else {
+            return Err(error);
+        };
+
+        if !must_refresh_proofs_height(
+            self.pruned_height_handling,
+            &self.src_chain().id(),
+            proofs_height,
+            earliest_height,
+        )? {
+            return Err(error);
+        }
+
+        let latest_height = self.src_latest_height()?;
+
+        warn!(
+            %proofs_height,
+            %earliest_height,
+            %latest_height,
+            "source chain has pruned the proofs height, building the proofs at its latest height instead: {error}"
+        );
+
+        Ok(latest_height)
+    }
+
     /// Generates fresh operational data for a tx given the initial operational data
     /// that failed to send.
     ///
@@ -767,6 +1428,9 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         initial_odata: OperationalData,
     ) -> Option<OperationalData> {
         let op_info = initial_odata.info();
+        let signing_key = initial_odata.signing_key.clone();
+        let initial_proofs_height = initial_odata.proofs_height;
+        let conn_delay_start = initial_odata.conn_delay_start();
 
         warn!(
             "failed. Regenerate operational data from {} events",
@@ -790,7 +1454,11 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             if src_od.target == op_info.target() {
                 // Our target is the _source_ chain, retry these messages
                 info!(odata = %src_od.info(), "will retry");
-                return Some(src_od);
+                return self.retry_after_conn_delay(
+                    src_od,
+                    initial_proofs_height,
+                    conn_delay_start,
+                );
             } else {
                 // Our target is the _destination_ chain, the data in `src_od` contains
                 // potentially new timeout messages that have to be handled separately.
@@ -805,11 +1473,18 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             }
         }
 
-        if let Some(dst_od) = dst_opt {
+        if let Some(mut dst_od) = dst_opt {
             if dst_od.target == op_info.target() {
+                // The messages were signed with the key of the initial operational data
+                dst_od.signing_key = signing_key;
+
                 // Our target is the _destination_ chain, retry these messages
                 info!(odata = %dst_od.info(), "will retry");
-                return Some(dst_od);
+                return self.retry_after_conn_delay(
+                    dst_od,
+                    initial_proofs_height,
+                    conn_delay_start,
+                );
             } else {
                 // Our target is the _source_ chain, but `dst_od` has new messages
                 // intended for the destination chain, this should never be the case
@@ -829,6 +1504,47 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         None
     }
 
+    /// Returns the regenerated operational data `od` to retry sending it right away, unless
+    /// the connection delay must be taken into account for its messages and it cannot start
+    /// from the client update of the initial operational data, whose proofs were built at
+    /// `initial_proofs_height`, eg. because the proofs height of `od` was refreshed.
+    ///
+    /// In that case, the client update which the new proofs are verified against has not
+    /// been processed yet, and `od` is scheduled again instead, for the client to be updated
+    /// and for the connection delay to elapse before its messages are submitted.
+    fn retry_after_conn_delay(
+        &self,
+        mut od: OperationalData,
+        initial_proofs_height: Height,
+        conn_delay_start: Option<(Instant, Height)>,
+    ) -> Option<OperationalData> {
+        if !od.conn_delay_needed() {
+            return Some(od);
+        }
+
+        match conn_delay_start {
+            Some((scheduled_time, update_height)) if od.proofs_height == initial_proofs_height => {
+                od.set_scheduled_time(scheduled_time);
+                od.set_update_height(update_height);
+
+                Some(od)
+            }
+            _ => {
+                info!(
+                    odata = %od.info(),
+                    "connection delay must elapse again for the regenerated operational data, \
+                    scheduling it"
+                );
+
+                if let Err(e) = self.schedule_operational_data(od) {
+                    error!("failed to schedule regenerated operational data: {e}");
+                }
+
+                None
+            }
+        }
+    }
+
     /// Sends a transaction based on the [`OperationalData`] to
     /// the corresponding target chain.
     ///
@@ -886,8 +1602,16 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
 
     /// Checks if a packet commitment has been cleared on source.
     /// The packet commitment is cleared when either an acknowledgment or a timeout is received on source.
+    ///
+    /// For a source chain which does not return the value of its commitments, the
+    /// commitment is reconstructed from the packet and looked up in the proof instead.
     fn send_packet_commitment_cleared_on_src(&self, packet: &Packet) -> Result<bool, LinkError> {
-        let (bytes, _) = self
+        let include_proof = match self.src_packet_commitment_source {
+            PacketCommitmentSource::Store => IncludeProof::No,
+            PacketCommitmentSource::Events => IncludeProof::Yes,
+        };
+
+        let (bytes, proof) = self
             .src_chain()
             .query_packet_commitment(
                 QueryPacketCommitmentRequest {
@@ -896,11 +1620,14 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                     sequence: packet.sequence,
                     height: QueryHeight::Latest,
                 },
-                IncludeProof::No,
+                include_proof,
             )
             .map_err(LinkError::relayer)?;
 
-        Ok(bytes.is_empty())
+        match self.src_packet_commitment_source {
+            PacketCommitmentSource::Store => Ok(bytes.is_empty()),
+            PacketCommitmentSource::Events => Ok(!is_commitment_proven(packet, proof.as_ref())),
+        }
     }
 
     /// Checks if a send packet event has already been handled (e.g. by another relayer).
@@ -1142,77 +1869,200 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         }
     }
 
-    /// Schedules the relaying of [`MsgRecvPacket`] and [`MsgTimeout`] messages.
-    ///
-    /// The optional [`Height`] parameter allows specify a height on the source
-    /// chain where to query for packet data. If `None`, the latest available
-    /// height on the source chain is used.
+    /// Returns the sequences of the packets sent on the source chain which the destination
+    /// chain has not received, and the height of the source chain at which they were found.
     ///
-    /// Blocks until _all_ outstanding messages have been scheduled.
-    pub fn schedule_recv_packet_and_timeout_msgs(
-        &self,
-        opt_query_height: Option<Height>,
-        chunk_size: usize,
-        clear_limit: usize,
-        tracking_id: TrackingId,
-    ) -> Result<(), LinkError> {
-        let _span = span!(
-            Level::ERROR,
-            "schedule_recv_packet_and_timeout_msgs",
-            query_height = %opt_query_height.map(|h| h.to_string()).unwrap_or_default()
-        )
-        .entered();
+    /// The packets sent by a source chain whose commitments are obtained from its
+    /// `SendPacket` events are found from these events, as its commitments cannot
+    /// be listed.
+    pub(crate) fn unreceived_packet_sequences(&self) -> Result<(Vec<Sequence>, Height), LinkError> {
+        if self.src_packet_commitment_source == PacketCommitmentSource::Store {
+            return unreceived_packets(
+                self.dst_chain(),
+                self.src_chain(),
+                &self.path_id,
+                Paginate::All,
+            )
+            .map_err(LinkError::supervisor);
+        }
 
-        // Pull the s.n. of all packets that the destination chain has not yet received.
-        let (sequences, src_response_height) = unreceived_packets(
-            self.dst_chain(),
+        let (sent, src_response_height) = scan_sent_packets(
             self.src_chain(),
-            &self.path_id,
-            Paginate::All,
+            self.src_port_id(),
+            self.src_channel_id(),
+            &self.src_sent_packets,
+        )?;
+
+        let unreceived = unreceived_packets_sequences(
+            self.dst_chain(),
+            self.dst_port_id(),
+            self.dst_channel_id(),
+            sent.clone(),
         )
         .map_err(LinkError::supervisor)?;
 
-        let query_height = opt_query_height.unwrap_or(src_response_height);
+        // The packets received by the destination chain are not pending anymore
+        self.src_sent_packets
+            .acquire_write()
+            .forget(sent.into_iter().filter(|s| !unreceived.contains(s)));
 
-        // Skip: no relevant events found.
-        if sequences.is_empty() {
-            return Ok(());
+        Ok((unreceived, src_response_height))
+    }
+
+    /// Returns the sequences of the packets sent on the destination chain whose acknowledgments,
+    /// written on the source chain, the destination chain has not received, and the height of
+    /// the source chain at which the acknowledgments were found, or `None` if the destination
+    /// chain has no packets pending.
+    ///
+    /// The packets sent by a destination chain whose commitments are obtained from its
+    /// `SendPacket` events are found from these events, as its commitments cannot be listed.
+    pub(crate) fn unreceived_ack_sequences(
+        &self,
+    ) -> Result<Option<(Vec<Sequence>, Height)>, LinkError> {
+        if self.dst_packet_commitment_source == PacketCommitmentSource::Store {
+            return unreceived_acknowledgements(
+                self.dst_chain(),
+                self.src_chain(),
+                &self.path_id,
+                Paginate::All,
+            )
+            .map_err(LinkError::supervisor);
+        }
+
+        let (sent, _) = scan_sent_packets(
+            self.dst_chain(),
+            self.dst_port_id(),
+            self.dst_channel_id(),
+            &self.dst_sent_packets,
+        )?;
+
+        let Some((acks, src_response_height)) = packet_acknowledgements(
+            self.src_chain(),
+            self.src_port_id(),
+            self.src_channel_id(),
+            sent,
+            Paginate::All,
+        )
+        .map_err(LinkError::supervisor)?
+        else {
+            return Ok(None);
+        };
+
+        let unreceived = unreceived_acknowledgements_sequences(
+            self.dst_chain(),
+            self.dst_port_id(),
+            self.dst_channel_id(),
+            acks.clone(),
+        )
+        .map_err(LinkError::supervisor)?;
+
+        // The packets whose acknowledgments the destination chain received are not pending anymore
+        self.dst_sent_packets
+            .acquire_write()
+            .forget(acks.into_iter().filter(|s| !unreceived.contains(s)));
+
+        Ok(Some((unreceived, src_response_height)))
+    }
+
+    /// Schedules the relaying of [`MsgRecvPacket`] and [`MsgTimeout`] messages.
+    ///
+    /// The optional [`Height`] parameter allows specify a height on the source
+    /// chain where to query for packet data. If `None`, the latest available
+    /// height on the source chain is used.
+    ///
+    /// Blocks until _all_ outstanding messages have been scheduled, or until the
+    /// `budget` of the clearing pass is exhausted, in which case the next pass
+    /// resumes from the first sequence which was not scheduled.
+    ///
+    /// Only the packets whose sequence is within `sequence_range` are scheduled.
+    pub fn schedule_recv_packet_and_timeout_msgs(
+        &self,
+        opt_query_height: Option<Height>,
+        chunk_size: usize,
+        clear_limit: usize,
+        sequence_range: SequenceRange,
+        tracking_id: TrackingId,
+        budget: &ClearBudget,
+    ) -> Result<(), LinkError> {
+        let _span = span!(
+            Level::ERROR,
+            "schedule_recv_packet_and_timeout_msgs",
+            query_height = %opt_query_height.map(|h| h.to_string()).unwrap_or_default()
+        )
+        .entered();
+
+        // Pull the s.n. of all packets that the destination chain has not yet received.
+        let (sequences, src_response_height) = self.unreceived_packet_sequences()?;
+
+        let query_height = opt_query_height.unwrap_or(src_response_height);
+
+        // Skip: no relevant events found.
+        if sequences.is_empty() {
+            return Ok(());
         }
 
-        // Retain only sequences which should not be filtered out
+        // Retain only sequences within the range which should not be filtered out,
+        // nor were relayed before the relayer was restarted
         let raw_sequences: Vec<Sequence> = sequences
             .into_iter()
+            .filter(|sequence| sequence_range.contains(sequence))
             .filter(|sequence| !self.exclude_src_sequences.contains(sequence))
+            .filter(|sequence| !self.relayed_packets.is_recv_or_timeout_relayed(sequence))
             .collect();
 
-        let sequences = &raw_sequences[..raw_sequences.len().min(clear_limit)];
-
         debug!(
             dst_chain = %self.dst_chain().id(),
             src_chain = %self.src_chain().id(),
-            total = sequences.len(),
-            sequences = %sequences.iter().copied().collated().format(", "),
+            total = raw_sequences.len(),
+            sequences = %raw_sequences.iter().copied().collated().format(", "),
             "sequence numbers of unreceived packets to send to the destination chain out of the ones with commitments on the source chain",
         );
 
+        // A ranged clearing does not move the progress of the regular clearings
+        let ranged_progress = ClearProgress::with_order(self.clear_order);
+        let progress = if sequence_range.is_all() {
+            &self.recv_clear_progress
+        } else {
+            &ranged_progress
+        };
+
         // Chunk-up the list of sequence nrs. into smaller parts,
         // and schedule operational data incrementally across each chunk.
-        for events_chunk in query_packet_events_with(
-            sequences,
-            Qualified::SmallerEqual(query_height),
-            self.src_chain(),
-            &self.path_id,
+        let outcome = progress.run(
+            &raw_sequences,
+            clear_limit,
             chunk_size,
-            query_send_packet_events,
-        ) {
-            // Update telemetry info
-            telemetry!({
-                for event_with_height in events_chunk.iter() {
-                    self.record_cleared_send_packet(event_with_height);
-                }
-            });
+            budget,
+            |sequences| {
+                let Some(events_chunk) = query_packet_events_with(
+                    sequences,
+                    Qualified::SmallerEqual(query_height),
+                    self.src_chain(),
+                    &self.path_id,
+                    chunk_size,
+                    query_send_packet_events,
+                )
+                .next() else {
+                    return Ok(false);
+                };
+
+                // Update telemetry info
+                telemetry!({
+                    for event_with_height in events_chunk.iter() {
+                        self.record_cleared_send_packet(event_with_height);
+                    }
+                });
+
+                self.events_to_operational_data(TrackedEvents::new(events_chunk, tracking_id))?;
 
-            self.events_to_operational_data(TrackedEvents::new(events_chunk, tracking_id))?;
+                Ok(true)
+            },
+        )?;
+
+        if outcome == ClearOutcome::Yielded {
+            info!(
+                "clearing of unreceived packets stopped early, will resume with the next clearing"
+            );
         }
 
         Ok(())
@@ -1223,12 +2073,19 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
     /// The `opt_query_height` parameter allows to optionally use a specific height on the source
     /// chain where to query for packet data. If `None`, the latest available height on the source
     /// chain is used.
+    ///
+    /// Stops early if the `budget` of the clearing pass is exhausted,
+    /// in which case the next pass resumes where this one stopped.
+    ///
+    /// Only the packets whose sequence is within `sequence_range` are scheduled.
     pub fn schedule_packet_ack_msgs(
         &self,
         opt_query_height: Option<Height>,
         chunk_size: usize,
         clear_limit: usize,
+        sequence_range: SequenceRange,
         tracking_id: TrackingId,
+        budget: &ClearBudget,
     ) -> Result<(), LinkError> {
         let _span = span!(
             Level::ERROR,
@@ -1237,13 +2094,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         )
         .entered();
 
-        let sequences_and_height = unreceived_acknowledgements(
-            self.dst_chain(),
-            self.src_chain(),
-            &self.path_id,
-            Paginate::All,
-        )
-        .map_err(LinkError::supervisor)?;
+        let sequences_and_height = self.unreceived_ack_sequences()?;
 
         let Some((sequences, src_response_height)) = sequences_and_height else {
             return Ok(());
@@ -1256,38 +2107,132 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             return Ok(());
         }
 
-        // Retain only sequences which should not be filtered out
+        // Retain only sequences within the range which should not be filtered out,
+        // nor were relayed before the relayer was restarted
         let raw_sequences: Vec<Sequence> = sequences
             .into_iter()
+            .filter(|sequence| sequence_range.contains(sequence))
             .filter(|sequence| !self.exclude_src_sequences.contains(sequence))
+            .filter(|sequence| !self.relayed_packets.is_ack_relayed(sequence))
             .collect();
 
-        let sequences = &raw_sequences[..raw_sequences.len().min(clear_limit)];
-
         debug!(
             dst_chain = %self.dst_chain().id(),
             src_chain = %self.src_chain().id(),
-            total = sequences.len(),
-            sequences = %sequences.iter().copied().collated().format(", "),
+            total = raw_sequences.len(),
+            sequences = %raw_sequences.iter().copied().collated().format(", "),
             "sequence numbers of ack packets to send to the destination chain out of the ones with acknowledgments on the source chain",
         );
 
+        // A ranged clearing does not move the progress of the regular clearings
+        let ranged_progress = ClearProgress::with_order(self.clear_order);
+        let progress = if sequence_range.is_all() {
+            &self.ack_clear_progress
+        } else {
+            &ranged_progress
+        };
+
         // Incrementally process all the available sequence numbers in chunks
-        for events_chunk in query_packet_events_with(
-            sequences,
-            Qualified::SmallerEqual(query_height),
-            self.src_chain(),
-            &self.path_id,
+        let outcome = progress.run(
+            &raw_sequences,
+            clear_limit,
             chunk_size,
-            query_write_ack_events,
-        ) {
-            telemetry!(self.record_cleared_acknowledgments(events_chunk.iter()));
-            self.events_to_operational_data(TrackedEvents::new(events_chunk, tracking_id))?;
+            budget,
+            |sequences| {
+                let Some(events_chunk) = query_packet_events_with(
+                    sequences,
+                    Qualified::SmallerEqual(query_height),
+                    self.src_chain(),
+                    &self.path_id,
+                    chunk_size,
+                    query_write_ack_events,
+                )
+                .next() else {
+                    return Ok(false);
+                };
+
+                telemetry!(self.record_cleared_acknowledgments(events_chunk.iter()));
+                self.events_to_operational_data(TrackedEvents::new(events_chunk, tracking_id))?;
+
+                Ok(true)
+            },
+        )?;
+
+        if outcome == ClearOutcome::Yielded {
+            info!("clearing of acknowledgments stopped early, will resume with the next clearing");
         }
 
         Ok(())
     }
 
+    /// Computes the messages which would be submitted in order to relay the packet
+    /// with the given sequence, along with the client update they depend on,
+    /// without submitting anything.
+    ///
+    /// Returns `None` if the source chain holds no packet data for this sequence,
+    /// or if there is nothing to relay for this packet.
+    pub fn relay_plan(&self, sequence: Sequence) -> Result<Option<RelayPlan>, LinkError> {
+        let query_height = self
+            .src_chain()
+            .query_latest_height()
+            .map_err(|e| LinkError::query(self.src_chain().id(), e))?;
+
+        let events = query_send_packet_events(
+            self.src_chain(),
+            &self.path_id,
+            &[sequence],
+            Qualified::SmallerEqual(query_height),
+        )
+        .map_err(LinkError::relayer)?;
+
+        let Some(commitment_height) = events.first().map(|ev| ev.height) else {
+            return Ok(None);
+        };
+
+        // Proofs are built at the query height, as when clearing packets
+        let events = events
+            .into_iter()
+            .map(|ev| ev.with_height(query_height))
+            .collect();
+
+        let (src_od, dst_od) = self.generate_operational_data(TrackedEvents::new(
+            events,
+            TrackingId::new_static("relay-plan"),
+        ))?;
+
+        let Some(od) = dst_od
+            .into_iter()
+            .chain(src_od)
+            .find(|od| !od.batch.is_empty())
+        else {
+            return Ok(None);
+        };
+
+        let consensus_heights =
+            match od.target {
+                OperationalDataTarget::Source => self.src_chain().query_consensus_state_heights(
+                    QueryConsensusStateHeightsRequest {
+                        client_id: self.src_client_id().clone(),
+                        pagination: Some(PageRequest::all()),
+                    },
+                ),
+                OperationalDataTarget::Destination => self
+                    .dst_chain()
+                    .query_consensus_state_heights(QueryConsensusStateHeightsRequest {
+                        client_id: self.dst_client_id().clone(),
+                        pagination: Some(PageRequest::all()),
+                    }),
+            }
+            .map_err(LinkError::relayer)?;
+
+        Ok(Some(RelayPlan::new(
+            sequence,
+            commitment_height,
+            &od,
+            &consensus_heights,
+        )))
+    }
+
     fn build_recv_packet(&self, packet: &Packet, height: Height) -> Result<Option<Any>, LinkError> {
         let proofs = self
             .src_chain()
@@ -1300,7 +2245,11 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             )
             .map_err(|e| LinkError::packet_proofs_constructor(self.src_chain().id(), e))?;
 
-        let msg = MsgRecvPacket::new(packet.clone(), proofs.clone(), self.dst_signer()?);
+        let msg = MsgRecvPacket::new(
+            packet.clone(),
+            proofs.clone(),
+            self.recv_packet_signer(packet)?,
+        );
 
         trace!(packet = %packet, height = %proofs.height(), "built recv_packet msg");
 
@@ -1451,13 +2400,24 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             .state_matches(&ChannelState::Closed)
         {
             Ok(self.build_timeout_on_close_packet(&event.packet, dst_info.height)?)
-        } else if packet.timed_out(&dst_info.timestamp, dst_info.height) {
+        } else if self.has_timed_out(&packet, dst_info) {
             Ok(self.build_timeout_packet(&event.packet, dst_info.height)?)
         } else {
             Ok(None)
         }
     }
 
+    /// Returns true if the destination chain reached the timeout of the given packet,
+    /// as of its latest block described by `dst_info`.
+    fn has_timed_out(&self, packet: &Packet, dst_info: &ChainStatus) -> bool {
+        has_timed_out(
+            packet,
+            dst_info.height,
+            dst_info.timestamp,
+            self.dst_timeout_timestamp_unit,
+        )
+    }
+
     fn build_recv_or_timeout_from_send_packet_event(
         &self,
         event: &SendPacket,
@@ -1476,11 +2436,163 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
 
         if timeout.is_some() {
             Ok((None, timeout))
+        } else if self.skip_empty_memo
+            && has_empty_ics20_memo(&self.packet_data_decoders, &event.packet)
+        {
+            // The packet is left to time out, so that the tokens are refunded
+            warn!(
+                packet = %event.packet,
+                "not relaying ICS-20 transfer with an empty memo, as channel `{}` on chain `{}` \
+                is configured to skip them in `skip_empty_memo_channels`",
+                event.packet.destination_channel,
+                self.dst_chain().id(),
+            );
+
+            telemetry!(
+                filtered_packets,
+                &self.src_chain().id(),
+                &self.dst_chain().id(),
+                &event.packet.source_channel,
+                &event.packet.destination_channel,
+                &event.packet.source_port,
+                &event.packet.destination_port,
+                1
+            );
+
+            Ok((None, None))
+        } else if !self.is_denom_allowed(&event.packet) {
+            // The packet is left to time out, so that the tokens are refunded
+            warn!(
+                packet = %event.packet,
+                "not relaying packet, as its denom does not match the `denom_patterns` \
+                configured for channel `{}` in the packet filter of chain `{}`",
+                event.packet.source_channel,
+                self.src_chain().id(),
+            );
+
+            telemetry!(
+                filtered_packets,
+                &self.src_chain().id(),
+                &self.dst_chain().id(),
+                &event.packet.source_channel,
+                &event.packet.destination_channel,
+                &event.packet.source_port,
+                &event.packet.destination_port,
+                1
+            );
+
+            Ok((None, None))
         } else {
             Ok((self.build_recv_packet(&event.packet, height)?, None))
         }
     }
 
+    /// Returns true if the given packet is allowed by the policy filtering
+    /// the ICS-20 transfers sent on the source channel by denom, if any.
+    fn is_denom_allowed(&self, packet: &Packet) -> bool {
+        self.denom_policy.as_ref().map_or(true, |policy| {
+            is_denom_allowed(&self.packet_data_decoders, policy, packet)
+        })
+    }
+
+    /// Returns the final destination of the given packet, if it is an ICS-20 transfer
+    /// which the destination chain forwards onward to another chain.
+    ///
+    /// The final destination chain is resolved from the channel of the first hop on the
+    /// destination chain, and is only known when the transfer is forwarded in a single hop.
+    fn forwarded_transfer_destination(&self, packet: &Packet) -> Option<FinalDestination> {
+        let hops = self.packet_data_decoders.forwarding_hops(packet);
+
+        let next_chain_id = match hops.as_slice() {
+            [hop] => self.forwarding_channel_chain(&hop.port_id, &hop.channel_id),
+            _ => None,
+        };
+
+        FinalDestination::new(hops, next_chain_id)
+    }
+
+    /// The chain at the other end of the given channel of the destination chain, which
+    /// is queried once per channel. Query errors are not cached, so that the chain is
+    /// queried again for the next transfer forwarded through that channel.
+    fn forwarding_channel_chain(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Option<ChainId> {
+        let key = (port_id.clone(), channel_id.clone());
+
+        if let Some(chain_id) = self.forwarding_channel_chains.acquire_read().get(&key) {
+            return chain_id.clone();
+        }
+
+        let request = QueryChannelClientStateRequest {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+        };
+
+        let chain_id = match self.dst_chain().query_channel_client_state(request) {
+            Ok(state) => state.map(|state| state.client_state.chain_id()),
+            Err(e) => {
+                debug!("failed to query the client state of channel {port_id}/{channel_id}: {e}");
+                return None;
+            }
+        };
+
+        self.forwarding_channel_chains
+            .acquire_write()
+            .insert(key, chain_id.clone());
+
+        chain_id
+    }
+
+    /// Reports the final destination of the transfers received by the given operational
+    /// data submitted to the destination chain which the destination chain forwards onward
+    /// to another chain. Each transfer is reported once, even if it is submitted again,
+    /// eg. when it is cleared or its operational data is regenerated.
+    fn report_forwarded_transfers(&self, odata: &OperationalData) {
+        for transit_msg in &odata.batch {
+            let IbcEvent::SendPacket(event) = &transit_msg.event_with_height.event else {
+                continue;
+            };
+
+            let packet = &event.packet;
+
+            let Some(destination) = self.forwarded_transfer_destination(packet) else {
+                continue;
+            };
+
+            {
+                let mut reported = self.reported_forwarded_transfers.acquire_write();
+
+                if !reported.insert(packet.sequence) {
+                    continue;
+                }
+
+                if reported.len() > MAX_REPORTED_FORWARDED_TRANSFERS {
+                    reported.pop_first();
+                }
+            }
+
+            info!(
+                packet = %packet,
+                final_destination = %destination,
+                hops = destination.hops,
+                receiver = %destination.hop.receiver,
+                "relayed transfer forwarded onward to another chain",
+            );
+
+            telemetry!(
+                forwarded_transfers,
+                &self.src_chain().id(),
+                &self.dst_chain().id(),
+                &packet.source_channel,
+                &packet.source_port,
+                &destination.to_string(),
+                destination.hops
+            );
+        }
+    }
+
     /// Drives the relaying of elapsed operational data items meant for
     /// a specified target chain forward.
     ///
@@ -1513,6 +2625,14 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         let mut unprocessed = VecDeque::new();
 
         while let Some(od) = operations.next() {
+            // Batches of timeouts wait for the interval of the timeout lane to elapse
+            if let Some(lane) = self.timeout_lane_for(&od) {
+                if !lane.delay(Instant::now()).is_zero() {
+                    unprocessed.push_back(od);
+                    continue;
+                }
+            }
+
             let elapsed_result = match target_chain {
                 OperationalDataTarget::Source => od.has_conn_delay_elapsed(
                     &|| self.src_time_latest(),
@@ -1526,7 +2646,36 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                 ),
             };
 
+            let elapsed_result = elapsed_result.and_then(|elapsed| {
+                if elapsed {
+                    self.has_min_confirmations(&od)
+                } else {
+                    Ok(false)
+                }
+            });
+
             match elapsed_result {
+                Ok(true) if self.forces_ordered_relay() => {
+                    // Relay the messages one at a time, in the order of their sequence, each
+                    // transaction being committed before the next one is submitted.
+                    let lane = self.timeout_lane_for(&od);
+                    let mut ordered = self.split_for_relay(od).into_iter();
+
+                    while let Some(od) = ordered.next() {
+                        if let Err(e) =
+                            self.relay_from_operational_data::<relay_sender::SyncSender>(od)
+                        {
+                            unprocessed.extend(ordered);
+                            unprocessed.extend(operations);
+
+                            return Err((unprocessed, e));
+                        }
+                    }
+
+                    if let Some(lane) = lane {
+                        lane.record_sent(Instant::now());
+                    }
+                }
                 Ok(elapsed) => {
                     if elapsed {
                         // The current piece of operational data has elapsed; we can go ahead and
@@ -1535,7 +2684,13 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                             .relay_from_operational_data::<relay_sender::AsyncSender>(od.clone())
                         {
                             // The operational data was successfully relayed; enqueue the associated tx.
-                            Ok(reply) => self.enqueue_pending_tx(reply, od),
+                            Ok(reply) => {
+                                if let Some(lane) = self.timeout_lane_for(&od) {
+                                    lane.record_sent(Instant::now());
+                                }
+
+                                self.enqueue_pending_tx(reply, od)
+                            }
                             // The relaying process failed; return all of the subsequent pieces of operational
                             // data along with the underlying error that occurred.
                             Err(e) => {
@@ -1600,6 +2755,117 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         Ok(())
     }
 
+    /// Drops the operational data scheduled for relaying to the source and destination
+    /// chains, as well as the pending transactions awaiting confirmation, and resets the
+    /// progress of packet clearing so that the next clearing pass scans all the pending
+    /// packets again.
+    ///
+    /// Only the local buffers are cleared: transactions which were already broadcast are
+    /// not recalled, and may still be committed by the chains. The packets they carry are
+    /// found again by the next packet clearing pass if they are not.
+    pub fn clear_pending_operational_data(&self) {
+        let src_odata = self.src_operational_data.take().len();
+        let dst_odata = self.dst_operational_data.take().len();
+        let src_pending = self.pending_txs_src.clear();
+        let dst_pending = self.pending_txs_dst.clear();
+
+        self.recv_clear_progress.reset();
+        self.ack_clear_progress.reset();
+
+        info!(
+            src_odata,
+            dst_odata, src_pending, dst_pending, "cleared pending operational data"
+        );
+    }
+
+    /// Drops from the operational data scheduled for relaying to the source and destination
+    /// chains the messages of the packets which were relayed meanwhile by another relayer,
+    /// or timed out, checking at most `limit` messages of each. Returns the number of
+    /// messages dropped.
+    pub fn compact_operational_data(&self, limit: usize) -> Result<usize, LinkError> {
+        let _span = span!(Level::ERROR, "compact_operational_data", limit).entered();
+
+        let is_resolved = |event: &IbcEvent| match event {
+            IbcEvent::SendPacket(event) => self.send_packet_event_handled(event),
+            IbcEvent::WriteAcknowledgement(event) => self.write_ack_event_handled(event),
+            _ => Ok(false),
+        };
+
+        let mut src_odata = self.src_operational_data.clone_vec();
+        let src_dropped = self.src_compaction.run(&mut src_odata, limit, is_resolved);
+        self.src_operational_data.replace(src_odata);
+        let src_dropped = src_dropped?;
+
+        let mut dst_odata = self.dst_operational_data.clone_vec();
+        let dst_dropped = self.dst_compaction.run(&mut dst_odata, limit, is_resolved);
+        self.dst_operational_data.replace(dst_odata);
+        let dst_dropped = dst_dropped?;
+
+        if src_dropped > 0 || dst_dropped > 0 {
+            info!(
+                src_dropped,
+                dst_dropped, "dropped the messages of packets relayed out-of-band"
+            );
+        }
+
+        Ok(src_dropped + dst_dropped)
+    }
+
+    /// The sequences of the packets sent by the source chain which the destination chain did
+    /// not receive yet, and of the packets sent by the destination chain whose acknowledgements
+    /// were not relayed yet, both of which this path relays. The excluded sequences are left out.
+    ///
+    /// The sequences are found from the commitments of the packets as clearing finds them,
+    /// ie. from the `SendPacket` events of a chain whose commitments are obtained from these.
+    pub fn unrelayed_sequences(&self) -> Result<(Vec<Sequence>, Vec<Sequence>), LinkError> {
+        let (packets, _) = self.unreceived_packet_sequences()?;
+
+        let acks = self
+            .unreceived_ack_sequences()?
+            .map(|(acks, _)| acks)
+            .unwrap_or_default();
+
+        let retain = |sequences: Vec<Sequence>| -> Vec<Sequence> {
+            sequences
+                .into_iter()
+                .filter(|sequence| !self.exclude_src_sequences.contains(sequence))
+                .collect()
+        };
+
+        Ok((retain(packets), retain(acks)))
+    }
+
+    /// How far the relaying of the packets of this path got, counting only the packets sent
+    /// by the source chain within `packets`, and the acknowledgements of the packets sent by
+    /// the destination chain within `acks`.
+    pub fn clearing_status(
+        &self,
+        packets: SequenceRange,
+        acks: SequenceRange,
+    ) -> Result<ClearingStatus, LinkError> {
+        let pending_txs =
+            self.pending_txs_src.pending_queue.len() + self.pending_txs_dst.pending_queue.len();
+
+        if pending_txs > 0 {
+            return Ok(ClearingStatus::new(pending_txs, 0, 0));
+        }
+
+        let (unreceived_packets, unreceived_acks) = self.unrelayed_sequences()?;
+
+        let count_in = |sequences: &[Sequence], range: SequenceRange| {
+            sequences
+                .iter()
+                .filter(|sequence| range.contains(sequence))
+                .count()
+        };
+
+        Ok(ClearingStatus::new(
+            0,
+            count_in(&unreceived_packets, packets),
+            count_in(&unreceived_acks, acks),
+        ))
+    }
+
     /// Kicks off the process of relaying pending txs to the source and destination chains.
     ///
     /// See [`Resubmit::from_clear_interval`] for more info about the `resubmit` parameter.
@@ -1639,6 +2905,8 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             .process_pending(pending::TIMEOUT, self, do_resubmit)?
             .unwrap_or_else(RelaySummary::empty);
 
+        self.hand_over_sent_packets(self.src_chain().id(), &res);
+
         Ok(res)
     }
 
@@ -1655,9 +2923,49 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             .process_pending(pending::TIMEOUT, self, do_resubmit)?
             .unwrap_or_else(RelaySummary::empty);
 
+        self.hand_over_sent_packets(self.dst_chain().id(), &res);
+
         Ok(res)
     }
 
+    /// Hands over the packets sent by the confirmed transactions of the relayer
+    /// on the given chain, if enabled with [`RelayPath::forward_sent_packets`],
+    /// as one event batch per height at which they were sent.
+    fn hand_over_sent_packets(&self, chain_id: ChainId, summary: &RelaySummary) {
+        let Some(tx) = &self.sent_packets_tx else {
+            return;
+        };
+
+        let mut by_height: HashMap<Height, Vec<IbcEventWithHeight>> = HashMap::new();
+
+        for event_with_height in &summary.sent_packets {
+            by_height
+                .entry(event_with_height.height)
+                .or_default()
+                .push(event_with_height.clone());
+        }
+
+        for (height, events) in by_height {
+            debug!(
+                chain = %chain_id,
+                %height,
+                "handing over {} packets sent by relayed transactions",
+                events.len()
+            );
+
+            let batch = EventBatch {
+                chain_id: chain_id.clone(),
+                tracking_id: TrackingId::new_uuid(),
+                height,
+                events,
+            };
+
+            if tx.send(batch).is_err() {
+                debug!("cannot hand over sent packets, the receiving end is gone");
+            }
+        }
+    }
+
     /// Refreshes the scheduled batches.
     /// Verifies if any sendPacket messages timed-out. If so, moves them from destination op. data
     /// to source operational data, and adjusts the events and messages accordingly.
@@ -1768,7 +3076,21 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
     /// Adds a new operational data item for this relaying path to process later.
     /// If the relaying path has non-zero packet delays, this method also updates the client on the
     /// target chain with the appropriate headers.
-    fn schedule_operational_data(&self, mut od: OperationalData) -> Result<(), LinkError> {
+    fn schedule_operational_data(&self, od: OperationalData) -> Result<(), LinkError> {
+        // Timeouts are scheduled in the batches of the timeout lane, if configured
+        match self.timeout_lane_for(&od) {
+            Some(lane) => {
+                for od in lane.split(od) {
+                    self.schedule_operational_data_batch(od)?;
+                }
+
+                Ok(())
+            }
+            None => self.schedule_operational_data_batch(od),
+        }
+    }
+
+    fn schedule_operational_data_batch(&self, mut od: OperationalData) -> Result<(), LinkError> {
         let _span = span!(Level::INFO, "schedule", odata = %od.info()).entered();
 
         if od.batch.is_empty() {
@@ -1839,20 +3161,20 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
 
         let (elapsed_src_ods, unelapsed_src_ods) =
             partition(self.src_operational_data.take(), |op| {
-                op.has_conn_delay_elapsed(
+                Ok(op.has_conn_delay_elapsed(
                     &|| self.src_time_latest(),
                     &|| self.src_max_block_time(),
                     &|| self.src_latest_height(),
-                )
+                )? && self.has_min_confirmations(op)?)
             })?;
 
         let (elapsed_dst_ods, unelapsed_dst_ods) =
             partition(self.dst_operational_data.take(), |op| {
-                op.has_conn_delay_elapsed(
+                Ok(op.has_conn_delay_elapsed(
                     &|| self.dst_time_latest(),
                     &|| self.dst_max_block_time(),
                     &|| self.dst_latest_height(),
-                )
+                )? && self.has_min_confirmations(op)?)
             })?;
 
         self.src_operational_data.replace(unelapsed_src_ods);
@@ -1860,12 +3182,21 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         Ok((elapsed_src_ods, elapsed_dst_ods))
     }
 
+    /// Returns the timeout lane which the given operational data is submitted in,
+    /// if it targets the source chain, where timeouts are submitted.
+    pub(crate) fn timeout_lane_for(&self, od: &OperationalData) -> Option<&TimeoutLane> {
+        self.timeout_lane
+            .as_ref()
+            .filter(|_| od.target == OperationalDataTarget::Source)
+    }
+
     fn restore_src_client(&self) -> ForeignClient<ChainA, ChainB> {
         ForeignClient::restore(
             self.src_client_id().clone(),
             self.src_chain().clone(),
             self.dst_chain().clone(),
         )
+        .with_local_trust_threshold(self.dst_verification.local_trust_threshold)
     }
 
     fn restore_dst_client(&self) -> ForeignClient<ChainB, ChainA> {
@@ -1874,6 +3205,31 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             self.dst_chain().clone(),
             self.src_chain().clone(),
         )
+        .with_local_trust_threshold(self.src_verification.local_trust_threshold)
+    }
+
+    /// Returns whether enough blocks were built on top of the height of the proofs
+    /// of the given operational data, on the chain the proofs are built from.
+    fn has_min_confirmations(&self, od: &OperationalData) -> Result<bool, LinkError> {
+        let min_confirmation_blocks = match od.target {
+            OperationalDataTarget::Source => self.dst_verification.min_confirmation_blocks,
+            OperationalDataTarget::Destination => self.src_verification.min_confirmation_blocks,
+        };
+
+        if min_confirmation_blocks == 0 {
+            return Ok(true);
+        }
+
+        let latest_height = match od.target {
+            OperationalDataTarget::Source => self.dst_latest_height()?,
+            OperationalDataTarget::Destination => self.src_latest_height()?,
+        };
+
+        Ok(has_min_confirmations(
+            od.proofs_height,
+            latest_height,
+            min_confirmation_blocks,
+        ))
     }
 
     // we need fully qualified ChainId to avoid unneeded imports warnings
@@ -1975,13 +3331,264 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
     }
 }
 
+/// Queries the latest state of the client `client_id` hosted by the chain `host`.
+fn query_latest_client_state(
+    host: &impl ChainHandle,
+    client_id: &ClientId,
+) -> Result<AnyClientState, LinkError> {
+    let (client_state, _) = host
+        .query_client_state(
+            QueryClientStateRequest {
+                client_id: client_id.clone(),
+                height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        )
+        .map_err(|e| LinkError::query(host.id(), e))?;
+
+    Ok(client_state)
+}
+
+/// Fails if the client `client_id` hosted by the chain `host` does not track the chain `expected`.
+fn check_client_chain_id(
+    host: &impl ChainHandle,
+    client_id: &ClientId,
+    expected: &ChainId,
+) -> Result<(), LinkError> {
+    let client_state = query_latest_client_state(host, client_id)?;
+
+    ensure_client_chain_id(&host.id(), client_id, client_state.chain_id(), expected)
+}
+
+/// Fails if the chain `actual` tracked by the client `client_id` hosted
+/// by the chain `chain_id` is not the chain `expected`.
+fn ensure_client_chain_id(
+    chain_id: &ChainId,
+    client_id: &ClientId,
+    actual: ChainId,
+    expected: &ChainId,
+) -> Result<(), LinkError> {
+    if actual == *expected {
+        Ok(())
+    } else {
+        Err(LinkError::client_chain_id_mismatch(
+            chain_id.clone(),
+            client_id.clone(),
+            expected.clone(),
+            actual,
+        ))
+    }
+}
+
+/// Returns the height `lookback` blocks below the given height, or the first height
+/// of the same revision if there are less than `lookback` blocks below it.
+fn lookback_height(height: Height, lookback: u64) -> Height {
+    let revision_height = height.revision_height().saturating_sub(lookback).max(1);
+
+    Height::new(height.revision_number(), revision_height).unwrap_or(height)
+}
+
+/// Returns whether at least `min_confirmation_blocks` blocks were built on top of
+/// `proofs_height`. Proofs built in a previous revision of the chain are confirmed.
+fn has_min_confirmations(
+    proofs_height: Height,
+    latest_height: Height,
+    min_confirmation_blocks: u64,
+) -> bool {
+    if latest_height.revision_number() > proofs_height.revision_number() {
+        return true;
+    }
+
+    latest_height
+        .revision_height()
+        .saturating_sub(proofs_height.revision_height())
+        >= min_confirmation_blocks
+}
+
+/// Returns the height at which to build the proofs of the events committed up to
+/// `events_height`, so that they are verified with the consensus state of the client
+/// at its latest height `client_latest_height`, if the client is past these events.
+fn minimal_update_proofs_height(
+    events_height: Height,
+    client_latest_height: Height,
+) -> Option<Height> {
+    if client_latest_height.revision_number() != events_height.revision_number() {
+        return None;
+    }
+
+    client_latest_height
+        .decrement()
+        .ok()
+        .filter(|height| *height >= events_height)
+}
+
+/// Returns the height below which the node of a chain has pruned the state, given the earliest
+/// height it reports, if it could be queried, and the `error` which occurred while relaying
+/// messages whose proofs are built at `proofs_height`.
+///
+/// Nodes usually keep the blocks for longer than the state, in which case the earliest height
+/// they report is below the height of a pruned state, but the error of the query reveals it.
+fn pruned_state_height(
+    proofs_height: Height,
+    earliest_height: Option<Height>,
+    error: &LinkError,
+) -> Option<Height> {
+    if !error.is_pruned_state_error() {
+        return earliest_height;
+    }
+
+    let pruned_height = proofs_height.increment();
+
+    Some(earliest_height.map_or(pruned_height, |height| height.max(pruned_height)))
+}
+
+/// Returns whether the proofs built at `proofs_height` on the chain `chain_id`, whose node
+/// has pruned the state below `earliest_height`, must be built at a recent height instead.
+/// Returns an error if the proofs height is pruned and the chain is configured to fail then.
+fn must_refresh_proofs_height(
+    handling: PrunedHeightHandling,
+    chain_id: &ChainId,
+    proofs_height: Height,
+    earliest_height: Height,
+) -> Result<bool, LinkError> {
+    if proofs_height >= earliest_height {
+        return Ok(false);
+    }
+
+    match handling {
+        PrunedHeightHandling::Refresh => Ok(true),
+        PrunedHeightHandling::Fail => Err(LinkError::proof_height_pruned(
+            chain_id.clone(),
+            proofs_height,
+            earliest_height,
+        )),
+    }
+}
+
+/// Returns true if the destination chain, whose latest block is at `dst_height` and
+/// `dst_timestamp`, reached the timeout of the given packet, which can then be timed out.
+///
+/// Only the timeout fields set on the packet are taken into account. The destination chain
+/// rejects packets included in a block at or past their timeout height or timestamp, and
+/// a packet times out as soon as the destination chain reaches either of them. Until then,
+/// the packet is relayed, even if it may time out before being included in a block.
+fn has_timed_out(
+    packet: &Packet,
+    dst_height: Height,
+    dst_timestamp: Timestamp,
+    dst_timestamp_unit: TimestampUnit,
+) -> bool {
+    let height_reached = match packet.timeout_height {
+        TimeoutHeight::At(timeout_height) => dst_height >= timeout_height,
+        TimeoutHeight::Never => false,
+    };
+
+    let timestamp_reached = packet.timeout_timestamp != Timestamp::none()
+        && encode_timeout_timestamp(dst_timestamp, dst_timestamp_unit).nanoseconds()
+            >= packet.timeout_timestamp.nanoseconds();
+
+    height_reached || timestamp_reached
+}
+
+/// Scans the `SendPacket` events of the packets sent on the channel `port_id/channel_id`
+/// of the given chain since the previous scan, recording them in `sent`, and returns the
+/// sequences of the packets recorded as sent which are not known to be relayed, along
+/// with the latest height of the chain, queried before the scan.
+fn scan_sent_packets(
+    chain: &impl ChainHandle,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    sent: &RwArc<SentPackets>,
+) -> Result<(Vec<Sequence>, Height), LinkError> {
+    let latest_height = chain
+        .query_latest_height()
+        .map_err(|e| LinkError::query(chain.id(), e))?;
+
+    // The first scan goes through all the blocks of the current revision of the chain
+    let min_height = sent.acquire_read().scan_from().unwrap_or_else(|| {
+        Height::new(latest_height.revision_number(), 1).unwrap_or(latest_height)
+    });
+
+    let events = chain
+        .query_txs(QueryTxRequest::SendPackets(QuerySendPacketsRequest {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            min_height,
+        }))
+        .map_err(|e| LinkError::query(chain.id(), e))?;
+
+    let mut sent = sent.acquire_write();
+    sent.record(port_id, channel_id, &events);
+
+    Ok((sent.sequences(), latest_height))
+}
+
+/// Computes the commitment which the sending chain stores for the given packet, ie. the
+/// hash of its timeout timestamp and height, followed by the hash of its data.
+fn packet_commitment(packet: &Packet) -> Vec<u8> {
+    let mut preimage = Vec::with_capacity(3 * 8 + 32);
+    preimage.extend(packet.timeout_timestamp.nanoseconds().to_be_bytes());
+    preimage.extend(
+        packet
+            .timeout_height
+            .commitment_revision_number()
+            .to_be_bytes(),
+    );
+    preimage.extend(
+        packet
+            .timeout_height
+            .commitment_revision_height()
+            .to_be_bytes(),
+    );
+    preimage.extend(Sha256::digest(&packet.data));
+
+    Sha256::digest(preimage).to_vec()
+}
+
+/// Whether the given proof proves that the commitment of the given packet,
+/// as reconstructed from the packet, is stored by the sending chain.
+fn is_commitment_proven(packet: &Packet, proof: Option<&MerkleProof>) -> bool {
+    proof.and_then(MerkleProof::proven_value) == Some(packet_commitment(packet).as_slice())
+}
+
+/// Returns the denom of the transfer if the data of the given packet is ICS-20 packet data.
+#[tracing::instrument(skip_all)]
+fn ics20_transfer_denom(decoders: &PacketDataDecoderRegistry, packet: &Packet) -> Option<String> {
+    decoders
+        .decode_transfer(packet)
+        .ok()
+        .map(|packet_data| packet_data.denom)
+}
+
+/// Returns true if the data of the given packet is ICS-20 packet data with an empty memo.
+fn has_empty_ics20_memo(decoders: &PacketDataDecoderRegistry, packet: &Packet) -> bool {
+    decoders
+        .decode_transfer(packet)
+        .map(|packet_data| packet_data.memo.trim().is_empty())
+        .unwrap_or(false)
+}
+
+/// Returns true if the given packet is allowed by the given policy filtering the ICS-20
+/// transfers by denom, ie. if its denom matches one of the policy's patterns, or if it is
+/// not an ICS-20 transfer and the policy is not strict.
+fn is_denom_allowed(
+    decoders: &PacketDataDecoderRegistry,
+    policy: &DenomPolicy,
+    packet: &Packet,
+) -> bool {
+    // A transfer cannot have an empty denom, such data is not ICS-20 packet data
+    let denom = ics20_transfer_denom(decoders, packet).filter(|denom| !denom.is_empty());
+    policy.should_relay(denom.as_deref())
+}
+
 #[tracing::instrument(skip_all)]
 fn check_ics20_fields_size(
-    data: &[u8],
+    decoders: &PacketDataDecoderRegistry,
+    packet: &Packet,
     memo_limit: Ics20FieldSizeLimit,
     receiver_limit: Ics20FieldSizeLimit,
 ) -> bool {
-    match serde_json::from_slice::<RawPacketData>(data) {
+    match decoders.decode_transfer(packet) {
         Ok(packet_data) => {
             match (
                 memo_limit.check_field_size(&packet_data.memo),
@@ -2005,3 +3612,887 @@ fn check_ics20_fields_size(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::ops::RangeInclusive;
+    use std::sync::mpsc;
+
+    use tendermint_rpc::endpoint::abci_query::AbciQuery;
+
+    use crate::chain::cosmos::batch::test_fixtures::{cosmos_config_mut, example_config};
+    use crate::chain::endpoint::ChainStatus;
+    use crate::chain::handle::{BaseChainHandle, ChainRequest};
+    use crate::channel::ChannelSide;
+    use crate::error::Error;
+    use crate::util::mock_chain::spawn_mock_chain;
+
+    fn height(height: u64) -> Height {
+        Height::new(0, height).unwrap()
+    }
+
+    /// Builds the relaying path from `transfer/channel-0` on chain A to `transfer/channel-1`
+    /// on chain B, whose mock chains answer the requests for their config and hand the
+    /// other requests over to `respond_a` and `respond_b`.
+    fn mock_relay_path(
+        ordering: Ordering,
+        config_a: ChainConfig,
+        respond_a: impl Fn(ChainRequest) + Send + 'static,
+        config_b: ChainConfig,
+        respond_b: impl Fn(ChainRequest) + Send + 'static,
+    ) -> RelayPath<BaseChainHandle, BaseChainHandle> {
+        try_mock_relay_path(
+            ordering,
+            link_parameters(),
+            config_a,
+            respond_a,
+            config_b,
+            respond_b,
+        )
+        .unwrap()
+    }
+
+    /// The parameters of the relaying path built by [`mock_relay_path`].
+    fn link_parameters() -> LinkParameters {
+        let config = example_config();
+
+        LinkParameters {
+            src_port_id: PortId::transfer(),
+            src_channel_id: ChannelId::new(0),
+            max_memo_size: config.mode.packets.ics20_max_memo_size,
+            max_receiver_size: config.mode.packets.ics20_max_receiver_size,
+            exclude_src_sequences: vec![],
+            max_clear_duration: None,
+            verification: ChannelVerification::default(),
+            clear_order: ClearOrder::OldestFirst,
+        }
+    }
+
+    /// Same as [`mock_relay_path`], with the given link parameters.
+    fn try_mock_relay_path(
+        ordering: Ordering,
+        parameters: LinkParameters,
+        config_a: ChainConfig,
+        respond_a: impl Fn(ChainRequest) + Send + 'static,
+        config_b: ChainConfig,
+        respond_b: impl Fn(ChainRequest) + Send + 'static,
+    ) -> Result<RelayPath<BaseChainHandle, BaseChainHandle>, LinkError> {
+        let mock_chain = |config: ChainConfig, respond: Box<dyn Fn(ChainRequest) + Send>| {
+            spawn_mock_chain(config.id().clone(), move |request| match request {
+                ChainRequest::Config { reply_to } => reply_to.send(Ok(config.clone())).unwrap(),
+                request => respond(request),
+            })
+        };
+
+        let chain_a = mock_chain(config_a, Box::new(respond_a));
+        let chain_b = mock_chain(config_b, Box::new(respond_b));
+
+        let channel = Channel {
+            ordering,
+            a_side: ChannelSide::new(
+                chain_a,
+                ClientId::default(),
+                ConnectionId::default(),
+                PortId::transfer(),
+                Some(ChannelId::new(0)),
+                None,
+            ),
+            b_side: ChannelSide::new(
+                chain_b,
+                ClientId::default(),
+                ConnectionId::default(),
+                PortId::transfer(),
+                Some(ChannelId::new(1)),
+                None,
+            ),
+            connection_delay: Duration::ZERO,
+        };
+
+        RelayPath::new(channel, false, parameters)
+    }
+
+    fn packet(sequence: u64) -> Packet {
+        Packet {
+            sequence: sequence.into(),
+            source_port: PortId::transfer(),
+            source_channel: ChannelId::new(0),
+            destination_port: PortId::transfer(),
+            destination_channel: ChannelId::new(1),
+            data: b"data".to_vec(),
+            timeout_height: TimeoutHeight::Never,
+            timeout_timestamp: Timestamp::none(),
+        }
+    }
+
+    #[test]
+    fn relaying_is_refused_on_a_path_whose_client_tracks_another_chain() {
+        use ibc_relayer_types::clients::ics07_tendermint::client_state::{
+            AllowUpdate, ClientState as TmClientState,
+        };
+        use ibc_relayer_types::core::ics02_client::trust_threshold::TrustThreshold;
+        use ibc_relayer_types::core::ics23_commitment::specs::ProofSpecs;
+
+        // The chain answers the client state queries with a client tracking `tracked`
+        let hosting = |tracked: &'static str| {
+            move |request| match request {
+                ChainRequest::QueryClientState { reply_to, .. } => {
+                    let client_state = TmClientState::new(
+                        ChainId::from_string(tracked),
+                        TrustThreshold::ONE_THIRD,
+                        Duration::from_secs(64_000),
+                        Duration::from_secs(128_000),
+                        Duration::from_millis(3000),
+                        height(10),
+                        ProofSpecs::default(),
+                        vec![],
+                        AllowUpdate {
+                            after_expiry: false,
+                            after_misbehaviour: false,
+                        },
+                    )
+                    .unwrap();
+
+                    reply_to.send(Ok((client_state.into(), None))).unwrap()
+                }
+                request => panic!("unexpected request: {request:?}"),
+            }
+        };
+
+        let config = example_config();
+        let verification = ChannelVerification {
+            verify_client_chain_id: true,
+            ..Default::default()
+        };
+
+        let relay_path = |verification, tracked_by_a, tracked_by_b| {
+            try_mock_relay_path(
+                Ordering::Unordered,
+                LinkParameters {
+                    verification,
+                    ..link_parameters()
+                },
+                config.chains[0].clone(),
+                hosting(tracked_by_a),
+                config.chains[1].clone(),
+                hosting(tracked_by_b),
+            )
+        };
+
+        assert!(relay_path(verification, "chain_B", "chain_A").is_ok());
+
+        // The client hosted by chain B tracks chain C instead of chain A
+        let error = relay_path(verification, "chain_B", "chain_C")
+            .err()
+            .unwrap();
+
+        match error.detail() {
+            error::LinkErrorDetail::ClientChainIdMismatch(e) => {
+                assert_eq!(e.chain_id, ChainId::from_string("chain_B"));
+                assert_eq!(e.expected, ChainId::from_string("chain_A"));
+                assert_eq!(e.actual, ChainId::from_string("chain_C"));
+            }
+            _ => panic!("unexpected error: {error}"),
+        }
+
+        // Without the verification, the clients are not even queried
+        let relay_path = try_mock_relay_path(
+            Ordering::Unordered,
+            link_parameters(),
+            config.chains[0].clone(),
+            |request| panic!("unexpected request to chain A: {request:?}"),
+            config.chains[1].clone(),
+            |request| panic!("unexpected request to chain B: {request:?}"),
+        );
+
+        assert!(relay_path.is_ok());
+    }
+
+    #[test]
+    fn minimal_update_reuses_recent_client_height() {
+        // The client is past the events: the proofs are built at the height preceding its
+        // latest height, so the client update prepended to the messages, which targets the
+        // height following the proofs height, is the latest height of the client already.
+        let proofs_height = minimal_update_proofs_height(height(100), height(120)).unwrap();
+        assert_eq!(proofs_height, height(119));
+        assert_eq!(proofs_height.increment(), height(120));
+
+        // The proofs of events right before the latest height of the client need no update either
+        assert_eq!(
+            minimal_update_proofs_height(height(119), height(120)),
+            Some(height(119))
+        );
+
+        // The client is not past the events, it must be updated as usual
+        assert_eq!(minimal_update_proofs_height(height(120), height(120)), None);
+        assert_eq!(minimal_update_proofs_height(height(130), height(120)), None);
+
+        // The client is in another revision of the chain
+        assert_eq!(
+            minimal_update_proofs_height(height(100), Height::new(1, 120).unwrap()),
+            None
+        );
+    }
+
+    #[test]
+    fn available_proofs_height_is_not_refreshed() {
+        let chain_id = ChainId::from_string("ibc-0");
+
+        for handling in [PrunedHeightHandling::Refresh, PrunedHeightHandling::Fail] {
+            assert!(
+                !must_refresh_proofs_height(handling, &chain_id, height(100), height(100)).unwrap()
+            );
+            assert!(
+                !must_refresh_proofs_height(handling, &chain_id, height(150), height(100)).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn pruned_proofs_height_is_refreshed() {
+        let chain_id = ChainId::from_string("ibc-0");
+
+        assert!(must_refresh_proofs_height(
+            PrunedHeightHandling::Refresh,
+            &chain_id,
+            height(42),
+            height(100)
+        )
+        .unwrap());
+    }
+
+    #[test]
+    fn proofs_height_with_pruned_state_is_refreshed() {
+        let chain_id = ChainId::from_string("ibc-0");
+
+        // The node still stores the blocks since height 1, but has pruned the state at height 42
+        let error = LinkError::packet_proofs_constructor(
+            chain_id.clone(),
+            Error::abci_query(AbciQuery {
+                code: tendermint::abci::Code::from(1),
+                log: "failed to load state at height 42; version does not exist \
+                    (latest height: 200)"
+                    .to_owned(),
+                ..Default::default()
+            }),
+        );
+
+        let earliest_height = pruned_state_height(height(42), Some(height(1)), &error).unwrap();
+        assert_eq!(earliest_height, height(43));
+
+        assert!(must_refresh_proofs_height(
+            PrunedHeightHandling::Refresh,
+            &chain_id,
+            height(42),
+            earliest_height,
+        )
+        .unwrap());
+
+        // Other errors are left to the earliest height reported by the node
+        let other = LinkError::update_client_failed();
+        assert_eq!(
+            pruned_state_height(height(42), Some(height(1)), &other),
+            Some(height(1))
+        );
+        assert_eq!(pruned_state_height(height(42), None, &other), None);
+    }
+
+    #[test]
+    fn pruned_proofs_height_fails() {
+        let chain_id = ChainId::from_string("ibc-0");
+
+        let e = must_refresh_proofs_height(
+            PrunedHeightHandling::Fail,
+            &chain_id,
+            height(42),
+            height(100),
+        )
+        .unwrap_err();
+
+        match e.detail() {
+            error::LinkErrorDetail::ProofHeightPruned(e) => {
+                assert_eq!(e.chain_id, chain_id);
+                assert_eq!(e.height, height(42));
+                assert_eq!(e.earliest_height, height(100));
+            }
+            e => panic!("unexpected error: {e}"),
+        }
+    }
+
+    #[test]
+    fn proofs_wait_for_min_confirmations() {
+        assert!(has_min_confirmations(height(100), height(100), 0));
+        assert!(!has_min_confirmations(height(100), height(104), 5));
+        assert!(has_min_confirmations(height(100), height(105), 5));
+
+        // Proofs built in a previous revision are confirmed
+        assert!(has_min_confirmations(
+            height(100),
+            Height::new(1, 1).unwrap(),
+            5
+        ));
+    }
+
+    #[test]
+    fn height_only_timeout_is_reconciled_with_timestamp_enforcing_destination() {
+        use ibc_relayer_types::core::ics24_host::identifier::PortId;
+
+        let packet = Packet {
+            sequence: 1.into(),
+            source_port: PortId::transfer(),
+            source_channel: ChannelId::new(0),
+            destination_port: PortId::transfer(),
+            destination_channel: ChannelId::new(1),
+            data: vec![],
+            timeout_height: TimeoutHeight::At(height(100)),
+            timeout_timestamp: Timestamp::none(),
+        };
+
+        // The destination chain enforces timeout timestamps, in seconds, and has a recent time,
+        // which must not time out the packet as it only sets a timeout height
+        let dst_timestamp = Timestamp::from_nanoseconds(1_700_000_000_000_000_000).unwrap();
+        let timed_out =
+            |dst_height| has_timed_out(&packet, dst_height, dst_timestamp, TimestampUnit::Seconds);
+
+        assert!(!timed_out(height(50)));
+
+        // A packet which may still be received with the next block is relayed
+        assert!(!timed_out(height(99)));
+
+        // The destination chain rejects packets at their timeout height, which can be timed out
+        assert!(timed_out(height(100)));
+        assert!(timed_out(height(101)));
+        assert!(timed_out(Height::new(1, 1).unwrap()));
+
+        // A timestamp-only packet times out once the time of the destination chain reaches it
+        let packet = Packet {
+            timeout_height: TimeoutHeight::Never,
+            timeout_timestamp: Timestamp::from_nanoseconds(1_700_000_060).unwrap(),
+            ..packet
+        };
+        let timed_out = |dst_timestamp: Timestamp| {
+            has_timed_out(
+                &packet,
+                height(1_000),
+                dst_timestamp,
+                TimestampUnit::Seconds,
+            )
+        };
+
+        assert!(!timed_out(dst_timestamp));
+        assert!(!timed_out(
+            (dst_timestamp + Duration::from_secs(59)).unwrap()
+        ));
+        assert!(timed_out(
+            (dst_timestamp + Duration::from_secs(60)).unwrap()
+        ));
+    }
+
+    #[test]
+    fn filters_apply_to_protobuf_transfer_data() {
+        use core::str::FromStr;
+
+        use byte_unit::Byte;
+        use ibc_proto::ibc::applications::transfer::v2::FungibleTokenPacketData as RawPacketData;
+        use ibc_relayer_types::core::ics24_host::identifier::PortId;
+        use prost::Message;
+
+        use crate::config::PacketDataEncoding;
+
+        let port_id = PortId::from_str("transfer-proto").unwrap();
+
+        let data = RawPacketData {
+            denom: "uatom".to_string(),
+            amount: "1000".to_string(),
+            sender: "cosmos1sender".to_string(),
+            receiver: "x".repeat(100),
+            memo: String::new(),
+        };
+
+        let packet = Packet {
+            sequence: 1.into(),
+            source_port: port_id.clone(),
+            source_channel: ChannelId::new(0),
+            destination_port: PortId::transfer(),
+            destination_channel: ChannelId::new(1),
+            data: data.encode_to_vec(),
+            timeout_height: Default::default(),
+            timeout_timestamp: Default::default(),
+        };
+
+        let memo_limit = Ics20FieldSizeLimit::new(true, Byte::from_bytes(32));
+        let receiver_limit = Ics20FieldSizeLimit::new(true, Byte::from_bytes(32));
+
+        // Without a decoder for the port, the data cannot be decoded and is not filtered
+        let json_decoders = PacketDataDecoderRegistry::default();
+        assert_eq!(ics20_transfer_denom(&json_decoders, &packet), None);
+        assert!(!has_empty_ics20_memo(&json_decoders, &packet));
+        assert!(check_ics20_fields_size(
+            &json_decoders,
+            &packet,
+            memo_limit,
+            receiver_limit
+        ));
+
+        let decoders =
+            PacketDataDecoderRegistry::from_encodings([(&port_id, &PacketDataEncoding::Protobuf)]);
+        assert_eq!(
+            ics20_transfer_denom(&decoders, &packet),
+            Some("uatom".to_string())
+        );
+        assert!(has_empty_ics20_memo(&decoders, &packet));
+        assert!(!check_ics20_fields_size(
+            &decoders,
+            &packet,
+            memo_limit,
+            receiver_limit
+        ));
+    }
+
+    #[test]
+    fn transfers_are_signed_by_denom_with_the_registered_packet_data_decoder() {
+        use ibc_proto::ibc::applications::transfer::v2::FungibleTokenPacketData as RawPacketData;
+        use prost::Message;
+
+        use crate::link::packet_data::ProtobufDecoder;
+
+        let config = example_config();
+        let mut config_b = config.chains[1].clone();
+        cosmos_config_mut(&mut config_b)
+            .denom_key_names
+            .insert("uatom".to_string(), "atom-key".to_string());
+
+        let unexpected = |request: ChainRequest| panic!("unexpected request: {request:?}");
+        let mut relay_path = mock_relay_path(
+            Ordering::Unordered,
+            config.chains[0].clone(),
+            unexpected,
+            config_b,
+            unexpected,
+        );
+
+        let data = RawPacketData {
+            denom: "uatom".to_string(),
+            amount: "1000".to_string(),
+            sender: "cosmos1sender".to_string(),
+            receiver: "cosmos1receiver".to_string(),
+            memo: String::new(),
+        };
+        let packet = Packet {
+            sequence: 1.into(),
+            source_port: PortId::transfer(),
+            source_channel: ChannelId::new(0),
+            destination_port: PortId::transfer(),
+            destination_channel: ChannelId::new(1),
+            data: data.encode_to_vec(),
+            timeout_height: Default::default(),
+            timeout_timestamp: Default::default(),
+        };
+
+        // The data of the packets sent from the port is expected to be JSON
+        assert_eq!(relay_path.recv_packet_key_name(&packet), None);
+
+        relay_path.register_packet_data_decoder(PortId::transfer(), Arc::new(ProtobufDecoder));
+        assert_eq!(
+            relay_path.recv_packet_key_name(&packet),
+            Some(&"atom-key".to_string())
+        );
+    }
+
+    #[test]
+    fn denom_policy_filters_transfers() {
+        use ibc_proto::ibc::applications::transfer::v2::FungibleTokenPacketData as RawPacketData;
+        use ibc_relayer_types::core::ics24_host::identifier::PortId;
+
+        let packet = |port_id: PortId, data: Vec<u8>| Packet {
+            sequence: 1.into(),
+            source_port: port_id,
+            source_channel: ChannelId::new(0),
+            destination_port: PortId::transfer(),
+            destination_channel: ChannelId::new(1),
+            data,
+            timeout_height: Default::default(),
+            timeout_timestamp: Default::default(),
+        };
+
+        let transfer = |denom: &str| {
+            let data = RawPacketData {
+                denom: denom.to_string(),
+                amount: "1000".to_string(),
+                sender: "cosmos1sender".to_string(),
+                receiver: "cosmos1receiver".to_string(),
+                memo: String::new(),
+            };
+            packet(PortId::transfer(), serde_json::to_vec(&data).unwrap())
+        };
+
+        let ica = packet(
+            "icahost".parse().unwrap(),
+            br#"{"type":"TYPE_EXECUTE_TX","data":"","memo":""}"#.to_vec(),
+        );
+
+        let decoders = PacketDataDecoderRegistry::default();
+        let allow = vec![
+            "uatom".parse().unwrap(),
+            "transfer/channel-*/uosmo".parse().unwrap(),
+        ];
+
+        let policy = DenomPolicy::new(allow.clone(), false);
+        assert!(is_denom_allowed(&decoders, &policy, &transfer("uatom")));
+        assert!(is_denom_allowed(
+            &decoders,
+            &policy,
+            &transfer("transfer/channel-3/uosmo")
+        ));
+        assert!(!is_denom_allowed(&decoders, &policy, &transfer("uosmo")));
+        assert!(!is_denom_allowed(&decoders, &policy, &transfer("uatomx")));
+        assert!(is_denom_allowed(&decoders, &policy, &ica));
+
+        let strict = DenomPolicy::new(allow, true);
+        assert!(is_denom_allowed(&decoders, &strict, &transfer("uatom")));
+        assert!(!is_denom_allowed(&decoders, &strict, &transfer("uosmo")));
+        assert!(!is_denom_allowed(&decoders, &strict, &ica));
+    }
+
+    #[test]
+    fn commitment_is_reconstructed_from_packet() {
+        use ibc_proto::ics23::commitment_proof::Proof;
+        use ibc_proto::ics23::{CommitmentProof, ExistenceProof, NonExistenceProof};
+
+        let packet = Packet {
+            sequence: 1.into(),
+            source_port: PortId::transfer(),
+            source_channel: ChannelId::new(0),
+            destination_port: PortId::transfer(),
+            destination_channel: ChannelId::new(1),
+            data: br#"{"amount":"1000","denom":"uatom"}"#.to_vec(),
+            timeout_height: TimeoutHeight::At(Height::new(1, 100).unwrap()),
+            timeout_timestamp: Timestamp::from_nanoseconds(1_700_000_000_000_000_000).unwrap(),
+        };
+
+        let commitment = packet_commitment(&packet);
+        assert_eq!(
+            hex::encode(&commitment),
+            "661573ee5e7e4273916559ebbed6295423e6f0ccb2a05ebd79b2e44d7f6150d1"
+        );
+
+        let proof = |proof: Proof| MerkleProof {
+            proofs: vec![CommitmentProof { proof: Some(proof) }],
+        };
+        let existence = |value: Vec<u8>| {
+            proof(Proof::Exist(ExistenceProof {
+                key: b"commitments/ports/transfer/channels/channel-0/sequences/1".to_vec(),
+                value,
+                leaf: None,
+                path: vec![],
+            }))
+        };
+
+        // The chain proves to store the commitment reconstructed from the packet
+        assert!(is_commitment_proven(
+            &packet,
+            Some(&existence(commitment.clone()))
+        ));
+
+        // The chain stores the commitment of another packet
+        let other = Packet {
+            data: br#"{"amount":"2000","denom":"uatom"}"#.to_vec(),
+            ..packet.clone()
+        };
+        assert!(!is_commitment_proven(&other, Some(&existence(commitment))));
+
+        // The commitment was cleared, or the chain returned no proof
+        let non_existence = proof(Proof::Nonexist(NonExistenceProof::default()));
+        assert!(!is_commitment_proven(&packet, Some(&non_existence)));
+        assert!(!is_commitment_proven(&packet, None));
+    }
+
+    #[test]
+    fn ranged_clearing_only_schedules_the_packets_in_range() {
+        // Packets 1 to 20 sent by chain A are not received by chain B, which sent no packet
+        let (queries, queried) = mpsc::channel();
+        let respond_a = move |request| match request {
+            ChainRequest::QueryPacketCommitments { reply_to, .. } => {
+                let sequences = (1..=20).map(Sequence::from).collect();
+                reply_to.send(Ok((sequences, height(20)))).unwrap()
+            }
+            ChainRequest::QueryPacketEventData { request, reply_to } => {
+                queries.send(request.sequences).unwrap();
+                reply_to.send(Ok(vec![])).unwrap()
+            }
+            request => panic!("unexpected request to chain A: {request:?}"),
+        };
+
+        let respond_b = |request| match request {
+            ChainRequest::QueryUnreceivedPackets { request, reply_to } => reply_to
+                .send(Ok(request.packet_commitment_sequences))
+                .unwrap(),
+            ChainRequest::QueryPacketCommitments { reply_to, .. } => {
+                reply_to.send(Ok((vec![], height(20)))).unwrap()
+            }
+            request => panic!("unexpected request to chain B: {request:?}"),
+        };
+
+        let config = example_config();
+        let relay_path = mock_relay_path(
+            Ordering::Unordered,
+            config.chains[0].clone(),
+            respond_a,
+            config.chains[1].clone(),
+            respond_b,
+        );
+
+        let range = SequenceRange::new(Some(5.into()), Some(10.into())).unwrap();
+        relay_path
+            .schedule_packet_clearing(None, 50, range)
+            .unwrap();
+
+        let sequences: Vec<Sequence> = (5..=10).map(Sequence::from).collect();
+        assert_eq!(queried.try_recv().unwrap(), sequences);
+        assert!(queried.try_recv().is_err());
+
+        relay_path
+            .schedule_packet_clearing(None, 50, SequenceRange::all())
+            .unwrap();
+
+        let sequences: Vec<Sequence> = (1..=20).map(Sequence::from).collect();
+        assert_eq!(queried.try_recv().unwrap(), sequences);
+    }
+
+    #[test]
+    fn newest_first_clearing_is_only_applied_to_unordered_channels() {
+        // Packets 1 to 20 sent by chain A are not received by chain B, which sent no packet
+        let cleared_with = |ordering| {
+            let (queries, queried) = mpsc::channel();
+            let respond_a = move |request| match request {
+                ChainRequest::QueryPacketCommitments { reply_to, .. } => {
+                    let sequences = (1..=20).map(Sequence::from).collect();
+                    reply_to.send(Ok((sequences, height(20)))).unwrap()
+                }
+                ChainRequest::QueryPacketEventData { request, reply_to } => {
+                    queries.send(request.sequences).unwrap();
+                    reply_to.send(Ok(vec![])).unwrap()
+                }
+                request => panic!("unexpected request to chain A: {request:?}"),
+            };
+
+            let respond_b = |request| match request {
+                ChainRequest::QueryUnreceivedPackets { request, reply_to } => reply_to
+                    .send(Ok(request.packet_commitment_sequences))
+                    .unwrap(),
+                ChainRequest::QueryPacketCommitments { reply_to, .. } => {
+                    reply_to.send(Ok((vec![], height(20)))).unwrap()
+                }
+                request => panic!("unexpected request to chain B: {request:?}"),
+            };
+
+            let config = example_config();
+            let relay_path = try_mock_relay_path(
+                ordering,
+                LinkParameters {
+                    clear_order: ClearOrder::NewestFirst,
+                    ..link_parameters()
+                },
+                config.chains[0].clone(),
+                respond_a,
+                config.chains[1].clone(),
+                respond_b,
+            )
+            .unwrap();
+
+            // The backlog is cleared 5 packets at a time
+            relay_path
+                .schedule_packet_clearing(None, 5, SequenceRange::all())
+                .unwrap();
+
+            queried.try_iter().flatten().collect::<Vec<Sequence>>()
+        };
+
+        let sequences = |range: RangeInclusive<u64>| range.map(Sequence::from).collect::<Vec<_>>();
+
+        let mut newest = sequences(16..=20);
+        newest.reverse();
+        assert_eq!(cleared_with(Ordering::Unordered), newest);
+
+        // The packets of an ordered channel are cleared from the oldest, which chain B
+        // expects to receive next
+        assert_eq!(cleared_with(Ordering::Ordered), sequences(1..=5));
+    }
+
+    #[test]
+    fn clearing_finds_the_packets_of_a_chain_without_commitment_values_from_its_events() {
+        use ibc_relayer_types::core::ics04_channel::events::SendPacket;
+
+        let config = example_config();
+        let mut config_a = config.chains[0].clone();
+        cosmos_config_mut(&mut config_a).packet_commitment_source = PacketCommitmentSource::Events;
+
+        // Chain A sent packets 1 to 3, at heights 10 to 12, and does not list its commitments
+        let (scans, scanned_from) = mpsc::channel();
+        let respond_a = move |request| match request {
+            ChainRequest::QueryApplicationStatus { reply_to } => {
+                let status = ChainStatus {
+                    height: height(20),
+                    timestamp: Timestamp::none(),
+                };
+                reply_to.send(Ok(status)).unwrap()
+            }
+            ChainRequest::QueryPacketEventDataFromTxs {
+                request: QueryTxRequest::SendPackets(request),
+                reply_to,
+            } => {
+                let events = (1..=3)
+                    .map(|sequence| {
+                        IbcEventWithHeight::new(
+                            IbcEvent::SendPacket(SendPacket {
+                                packet: packet(sequence),
+                            }),
+                            height(9 + sequence),
+                        )
+                    })
+                    .filter(|event| event.height >= request.min_height)
+                    .collect();
+
+                scans.send(request.min_height).unwrap();
+                reply_to.send(Ok(events)).unwrap()
+            }
+            request => panic!("unexpected request to chain A: {request:?}"),
+        };
+
+        // Chain B received packet 2
+        let (queries, queried) = mpsc::channel();
+        let respond_b = move |request| match request {
+            ChainRequest::QueryUnreceivedPackets { request, reply_to } => {
+                let sequences = request.packet_commitment_sequences;
+                let unreceived = sequences.iter().copied().filter(|s| *s != 2.into());
+
+                let unreceived = unreceived.collect();
+
+                queries.send(sequences).unwrap();
+                reply_to.send(Ok(unreceived)).unwrap()
+            }
+            // Chain B sent no packets
+            ChainRequest::QueryPacketCommitments { reply_to, .. } => {
+                reply_to.send(Ok((vec![], height(30)))).unwrap()
+            }
+            request => panic!("unexpected request to chain B: {request:?}"),
+        };
+
+        let relay_path = mock_relay_path(
+            Ordering::Unordered,
+            config_a,
+            respond_a,
+            config.chains[1].clone(),
+            respond_b,
+        );
+
+        // The first pass scans all the blocks of chain A
+        let (sequences, src_height) = relay_path.unreceived_packet_sequences().unwrap();
+        assert_eq!(sequences, vec![1.into(), 3.into()]);
+        assert_eq!(src_height, height(20));
+        assert_eq!(scanned_from.try_recv().unwrap(), height(1));
+        assert_eq!(
+            queried.try_recv().unwrap(),
+            vec![1.into(), 2.into(), 3.into()]
+        );
+
+        // The next pass scans from the latest packet found, without the received packet
+        let (sequences, _) = relay_path.unreceived_packet_sequences().unwrap();
+        assert_eq!(sequences, vec![1.into(), 3.into()]);
+        assert_eq!(scanned_from.try_recv().unwrap(), height(12));
+        assert_eq!(queried.try_recv().unwrap(), vec![1.into(), 3.into()]);
+
+        // The unrelayed packets reported by the clearing status are found the same way
+        let (packets, acks) = relay_path.unrelayed_sequences().unwrap();
+        assert_eq!(packets, vec![1.into(), 3.into()]);
+        assert!(acks.is_empty());
+        assert_eq!(scanned_from.try_recv().unwrap(), height(12));
+    }
+
+    #[test]
+    fn packet_received_during_the_grace_delay_is_not_relayed() {
+        use std::time::Instant;
+
+        use ibc_proto::google::protobuf::Any;
+        use ibc_relayer_types::core::ics04_channel::events::SendPacket;
+
+        use crate::chain::tracking::TrackingId;
+
+        const BLOCK_TIME: Duration = Duration::from_millis(200);
+
+        // Rechecks the receipts of packets 1 to 3 on chain B, which indexes the receipt of
+        // packet 2, received by another relayer, a block after the packets were scheduled
+        let recheck = |delay: Duration, fails: bool| {
+            let config = example_config();
+            let mut config_b = config.chains[1].clone();
+            cosmos_config_mut(&mut config_b).receipt_recheck_delay = Some(delay);
+
+            let scheduled_at = Instant::now();
+            let respond_b = move |request| match request {
+                ChainRequest::QueryUnreceivedPackets { request, reply_to } if fails => {
+                    let query = format!("{:?}", request.packet_commitment_sequences);
+                    reply_to.send(Err(Error::query(query))).unwrap()
+                }
+                ChainRequest::QueryUnreceivedPackets { request, reply_to } => {
+                    let indexed = scheduled_at.elapsed() >= BLOCK_TIME;
+                    let unreceived = request
+                        .packet_commitment_sequences
+                        .into_iter()
+                        .filter(|s| *s != 2.into() || !indexed)
+                        .collect();
+
+                    reply_to.send(Ok(unreceived)).unwrap()
+                }
+                request => panic!("unexpected request to chain B: {request:?}"),
+            };
+
+            let relay_path = mock_relay_path(
+                Ordering::Unordered,
+                config.chains[0].clone(),
+                |request| panic!("unexpected request to chain A: {request:?}"),
+                config_b,
+                respond_b,
+            );
+
+            let mut odata = OperationalData::new(
+                height(10),
+                OperationalDataTarget::Destination,
+                TrackingId::new_static("recheck"),
+                Duration::ZERO,
+            );
+
+            for sequence in 1..=3 {
+                odata.push(TransitMessage {
+                    event_with_height: IbcEventWithHeight::new(
+                        IbcEvent::SendPacket(SendPacket {
+                            packet: packet(sequence),
+                        }),
+                        height(10),
+                    ),
+                    msg: Any {
+                        type_url: "/ibc.core.channel.v1.MsgRecvPacket".to_owned(),
+                        value: vec![],
+                    },
+                });
+            }
+
+            relay_path.recheck_receipts(&mut odata);
+
+            odata
+                .batch
+                .iter()
+                .map(|msg| u64::from(msg.event_with_height.event.packet().unwrap().sequence))
+                .collect::<Vec<_>>()
+        };
+
+        // Packet 2 is found to be received once the grace delay has elapsed
+        assert_eq!(recheck(BLOCK_TIME, false), [1, 3]);
+
+        // Without the grace delay, the receipt of packet 2 is not indexed yet
+        assert_eq!(recheck(Duration::ZERO, false), [1, 2, 3]);
+
+        // The packets are all relayed if their receipts cannot be queried
+        assert_eq!(recheck(BLOCK_TIME, true), [1, 2, 3]);
+    }
+}
@@ -0,0 +1,205 @@
+use core::fmt::{Display, Error as FmtError, Formatter};
+
+use serde::Serialize;
+
+use ibc_relayer_types::core::ics04_channel::packet::Sequence;
+use ibc_relayer_types::Height;
+
+use crate::link::operational_data::{OperationalData, OperationalDataTarget};
+
+/// The client update that must be submitted to the target chain
+/// before the packet messages of a [`RelayPlan`] can be verified.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ClientUpdatePlan {
+    /// The height of the consensus state on the target chain the update is verified against.
+    /// Is `None` if the client does not hold any consensus state below the target height.
+    pub trusted_height: Option<Height>,
+    /// The height of the header submitted by the update.
+    pub target_height: Height,
+}
+
+impl ClientUpdatePlan {
+    /// Computes the client update needed to verify proofs taken at `proofs_height`,
+    /// given the heights of the consensus states held by the client on the target chain.
+    ///
+    /// Returns `None` if the client already has a consensus state at the required height.
+    pub fn new(proofs_height: Height, consensus_heights: &[Height]) -> Option<Self> {
+        // For Tendermint chains, a consensus state at `proofs_height + 1`
+        // is required in order to verify the proofs.
+        let target_height = proofs_height.increment();
+
+        if consensus_heights.contains(&target_height) {
+            return None;
+        }
+
+        let trusted_height = consensus_heights
+            .iter()
+            .filter(|height| **height < target_height)
+            .max()
+            .copied();
+
+        Some(Self {
+            trusted_height,
+            target_height,
+        })
+    }
+}
+
+/// The messages Hermes would submit in order to relay a given packet,
+/// as computed by [`RelayPath::relay_plan`](crate::link::RelayPath::relay_plan).
+#[derive(Clone, Debug, Serialize)]
+pub struct RelayPlan {
+    pub sequence: Sequence,
+    /// The height at which the packet commitment was written on the source chain.
+    pub commitment_height: Height,
+    /// The chain the messages are submitted to.
+    pub target: OperationalDataTarget,
+    /// The height at which the proofs of the packet messages are queried.
+    pub proofs_height: Height,
+    /// The client update to submit ahead of the packet messages, if any.
+    pub client_update: Option<ClientUpdatePlan>,
+    /// The type URLs of the packet messages.
+    pub messages: Vec<String>,
+}
+
+impl RelayPlan {
+    pub fn new(
+        sequence: Sequence,
+        commitment_height: Height,
+        od: &OperationalData,
+        consensus_heights: &[Height],
+    ) -> Self {
+        Self {
+            sequence,
+            commitment_height,
+            target: od.target,
+            proofs_height: od.proofs_height,
+            client_update: ClientUpdatePlan::new(od.proofs_height, consensus_heights),
+            messages: od
+                .batch
+                .iter()
+                .map(|transit| transit.msg.type_url.clone())
+                .collect(),
+        }
+    }
+}
+
+impl Display for RelayPlan {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        writeln!(
+            f,
+            "Relay plan for packet {} committed at height {}:",
+            self.sequence, self.commitment_height
+        )?;
+
+        match &self.client_update {
+            Some(ClientUpdatePlan {
+                trusted_height: Some(trusted_height),
+                target_height,
+            }) => writeln!(
+                f,
+                "  client update on {} chain: trusted height {trusted_height}, target height {target_height}",
+                self.target
+            )?,
+            Some(ClientUpdatePlan {
+                trusted_height: None,
+                target_height,
+            }) => writeln!(
+                f,
+                "  client update on {} chain: no trusted height found, target height {target_height}",
+                self.target
+            )?,
+            None => writeln!(f, "  no client update needed on {} chain", self.target)?,
+        }
+
+        for msg in &self.messages {
+            writeln!(f, "  {msg} with proofs at height {}", self.proofs_height)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_proto::google::protobuf::Any;
+    use ibc_relayer_types::core::ics04_channel::events::SendPacket;
+    use ibc_relayer_types::core::ics04_channel::packet::Packet;
+    use ibc_relayer_types::events::IbcEvent;
+
+    use crate::chain::tracking::TrackingId;
+    use crate::event::IbcEventWithHeight;
+    use crate::link::operational_data::TransitMessage;
+
+    fn height(h: u64) -> Height {
+        Height::new(0, h).unwrap()
+    }
+
+    fn recv_operational_data(proofs_height: Height) -> OperationalData {
+        let mut od = OperationalData::new(
+            proofs_height,
+            OperationalDataTarget::Destination,
+            TrackingId::new_static("relay-plan"),
+            Default::default(),
+        );
+
+        od.push(TransitMessage {
+            event_with_height: IbcEventWithHeight::new(
+                IbcEvent::SendPacket(SendPacket {
+                    packet: Packet::default(),
+                }),
+                proofs_height,
+            ),
+            msg: Any {
+                type_url: "/ibc.core.channel.v1.MsgRecvPacket".to_owned(),
+                value: vec![],
+            },
+        });
+
+        od
+    }
+
+    #[test]
+    fn plan_heights_consistent_with_commitment_height() {
+        let commitment_height = height(42);
+        let proofs_height = height(45);
+        let od = recv_operational_data(proofs_height);
+
+        let plan = RelayPlan::new(
+            Sequence::from(1),
+            commitment_height,
+            &od,
+            &[height(10), height(30), height(50)],
+        );
+
+        // The proofs must be taken at a height where the commitment exists,
+        // and the client must be updated to the height right after it.
+        assert!(plan.proofs_height >= plan.commitment_height);
+        assert_eq!(
+            plan.client_update,
+            Some(ClientUpdatePlan {
+                trusted_height: Some(height(30)),
+                target_height: proofs_height.increment(),
+            })
+        );
+        assert_eq!(plan.messages, ["/ibc.core.channel.v1.MsgRecvPacket"]);
+    }
+
+    #[test]
+    fn plan_without_client_update() {
+        let commitment_height = height(42);
+        let od = recv_operational_data(commitment_height);
+
+        let plan = RelayPlan::new(
+            Sequence::from(1),
+            commitment_height,
+            &od,
+            &[height(30), height(43)],
+        );
+
+        assert_eq!(plan.proofs_height, commitment_height);
+        assert_eq!(plan.client_update, None);
+    }
+}
@@ -63,12 +63,7 @@ impl Submit for SyncSender {
 
         match ev {
             Some(ev) => Err(LinkError::send(ev.event)),
-            None => Ok(RelaySummary::from_events(
-                tx_events
-                    .into_iter()
-                    .map(|event_with_height| event_with_height.event)
-                    .collect(),
-            )),
+            None => Ok(RelaySummary::from_events_with_heights(tx_events)),
         }
     }
 }
@@ -2,16 +2,24 @@ use core::fmt::{Display, Error as FmtError, Formatter};
 
 use ibc_relayer_types::events::IbcEvent;
 
+use crate::event::IbcEventWithHeight;
+
 #[derive(Clone, Debug)]
 pub struct RelaySummary {
     pub events: Vec<IbcEvent>,
+    /// The packets sent by the relayed transactions, eg. the packets
+    /// forwarded by a packet forwarding middleware upon receiving a packet.
+    pub sent_packets: Vec<IbcEventWithHeight>,
     // errors: todo!(),
     // timings: todo!(),
 }
 
 impl RelaySummary {
     pub fn empty() -> Self {
-        Self { events: vec![] }
+        Self {
+            events: vec![],
+            sent_packets: vec![],
+        }
     }
 
     pub fn is_empty(&self) -> bool {
@@ -19,11 +27,33 @@ impl RelaySummary {
     }
 
     pub fn from_events(events: Vec<IbcEvent>) -> Self {
-        Self { events }
+        Self {
+            events,
+            sent_packets: vec![],
+        }
+    }
+
+    /// Builds a summary out of the events of committed transactions,
+    /// keeping track of the packets sent by these transactions.
+    pub fn from_events_with_heights(events: Vec<IbcEventWithHeight>) -> Self {
+        let sent_packets = events
+            .iter()
+            .filter(|event_with_height| matches!(event_with_height.event, IbcEvent::SendPacket(_)))
+            .cloned()
+            .collect();
+
+        Self {
+            events: events
+                .into_iter()
+                .map(|event_with_height| event_with_height.event)
+                .collect(),
+            sent_packets,
+        }
     }
 
     pub fn extend(&mut self, other: RelaySummary) {
-        self.events.extend(other.events)
+        self.events.extend(other.events);
+        self.sent_packets.extend(other.sent_packets);
     }
 }
 
@@ -36,3 +66,56 @@ impl Display for RelaySummary {
         write!(f, "total events = {}", self.events.len())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_relayer_types::core::ics04_channel::events::{SendPacket, WriteAcknowledgement};
+    use ibc_relayer_types::core::ics04_channel::packet::Packet;
+    use ibc_relayer_types::Height;
+
+    fn packet() -> Packet {
+        Packet {
+            sequence: 1.into(),
+            source_port: "transfer".parse().unwrap(),
+            source_channel: "channel-1".parse().unwrap(),
+            destination_port: "transfer".parse().unwrap(),
+            destination_channel: "channel-2".parse().unwrap(),
+            data: vec![],
+            timeout_height: Default::default(),
+            timeout_timestamp: Default::default(),
+        }
+    }
+
+    #[test]
+    fn packets_forwarded_upon_receive_are_sent_packets() {
+        let height = Height::new(0, 10).unwrap();
+
+        // A `MsgRecvPacket` transaction whose packet is forwarded onward
+        let events = vec![
+            IbcEventWithHeight::new(
+                IbcEvent::WriteAcknowledgement(WriteAcknowledgement {
+                    packet: packet(),
+                    ack: vec![],
+                }),
+                height,
+            ),
+            IbcEventWithHeight::new(
+                IbcEvent::SendPacket(SendPacket { packet: packet() }),
+                height,
+            ),
+        ];
+
+        let mut summary = RelaySummary::from_events_with_heights(events);
+        summary.extend(RelaySummary::from_events(vec![]));
+
+        assert_eq!(summary.events.len(), 2);
+        assert_eq!(summary.sent_packets.len(), 1);
+        assert_eq!(summary.sent_packets[0].height, height);
+        assert!(matches!(
+            summary.sent_packets[0].event,
+            IbcEvent::SendPacket(_)
+        ));
+    }
+}
@@ -0,0 +1,174 @@
+use alloc::collections::BTreeSet;
+
+use ibc_relayer_types::core::ics04_channel::packet::{Packet, Sequence};
+use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, PortId};
+use ibc_relayer_types::events::IbcEvent;
+
+use crate::event::IbcEventWithHeight;
+
+/// The packets of a relaying path which the relayer already relayed in recent
+/// blocks, reconstructed from its own transactions when it starts, so that
+/// packets included right before a restart are not submitted again.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RelayedPackets {
+    /// Packets sent on the source chain and received on the destination chain
+    recv: BTreeSet<Sequence>,
+    /// Packets sent on the source chain and timed out on the source chain
+    timeout: BTreeSet<Sequence>,
+    /// Packets sent on the destination chain and acknowledged on the destination chain
+    ack: BTreeSet<Sequence>,
+}
+
+impl RelayedPackets {
+    /// Collects the packets relayed on the path from the source channel
+    /// `src_port_id/src_channel_id` to the destination channel
+    /// `dst_port_id/dst_channel_id`, out of the events of the
+    /// transactions submitted by the relayer on both chains.
+    pub fn from_events<'a>(
+        src_port_id: &PortId,
+        src_channel_id: &ChannelId,
+        dst_port_id: &PortId,
+        dst_channel_id: &ChannelId,
+        events: impl IntoIterator<Item = &'a IbcEventWithHeight>,
+    ) -> Self {
+        let sent_on = |packet: &Packet, port_id: &PortId, channel_id: &ChannelId| {
+            &packet.source_port == port_id && &packet.source_channel == channel_id
+        };
+
+        let mut relayed = Self::default();
+
+        for event_with_height in events {
+            match &event_with_height.event {
+                IbcEvent::ReceivePacket(ev) if sent_on(&ev.packet, src_port_id, src_channel_id) => {
+                    relayed.recv.insert(ev.packet.sequence);
+                }
+                IbcEvent::TimeoutPacket(ev) if sent_on(&ev.packet, src_port_id, src_channel_id) => {
+                    relayed.timeout.insert(ev.packet.sequence);
+                }
+                IbcEvent::TimeoutOnClosePacket(ev)
+                    if sent_on(&ev.packet, src_port_id, src_channel_id) =>
+                {
+                    relayed.timeout.insert(ev.packet.sequence);
+                }
+                IbcEvent::AcknowledgePacket(ev)
+                    if sent_on(&ev.packet, dst_port_id, dst_channel_id) =>
+                {
+                    relayed.ack.insert(ev.packet.sequence);
+                }
+                _ => {}
+            }
+        }
+
+        relayed
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.recv.is_empty() && self.timeout.is_empty() && self.ack.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.recv.len() + self.timeout.len() + self.ack.len()
+    }
+
+    /// Whether the packet sent on the source chain with the given
+    /// sequence was already either received or timed out.
+    pub fn is_recv_or_timeout_relayed(&self, sequence: &Sequence) -> bool {
+        self.recv.contains(sequence) || self.timeout.contains(sequence)
+    }
+
+    /// Whether the acknowledgment of the packet sent on the destination
+    /// chain with the given sequence was already relayed.
+    pub fn is_ack_relayed(&self, sequence: &Sequence) -> bool {
+        self.ack.contains(sequence)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_relayer_types::core::ics04_channel::events::{
+        AcknowledgePacket, ReceivePacket, TimeoutPacket,
+    };
+    use ibc_relayer_types::Height;
+
+    fn packet(port_id: &PortId, channel_id: &ChannelId, sequence: u64) -> Packet {
+        Packet {
+            sequence: sequence.into(),
+            source_port: port_id.clone(),
+            source_channel: channel_id.clone(),
+            destination_port: PortId::transfer(),
+            destination_channel: ChannelId::new(99),
+            data: vec![],
+            timeout_height: Default::default(),
+            timeout_timestamp: Default::default(),
+        }
+    }
+
+    fn with_height(event: IbcEvent) -> IbcEventWithHeight {
+        IbcEventWithHeight::new(event, Height::new(0, 10).unwrap())
+    }
+
+    #[test]
+    fn restart_does_not_resubmit_included_packets() {
+        let port_id = PortId::transfer();
+        let src_channel_id = ChannelId::new(0);
+        let dst_channel_id = ChannelId::new(1);
+        let other_channel_id = ChannelId::new(2);
+
+        // Before crashing, the relayer broadcast a tx receiving packet 5, which got
+        // included in a block although the relayer did not confirm it. It also relayed
+        // packets of another channel in the same blocks.
+        let events = vec![
+            with_height(IbcEvent::ReceivePacket(ReceivePacket {
+                packet: packet(&port_id, &src_channel_id, 5),
+            })),
+            with_height(IbcEvent::ReceivePacket(ReceivePacket {
+                packet: packet(&port_id, &other_channel_id, 7),
+            })),
+            with_height(IbcEvent::TimeoutPacket(TimeoutPacket {
+                packet: packet(&port_id, &src_channel_id, 3),
+            })),
+            with_height(IbcEvent::AcknowledgePacket(AcknowledgePacket {
+                packet: packet(&port_id, &dst_channel_id, 4),
+            })),
+        ];
+
+        let relayed = RelayedPackets::from_events(
+            &port_id,
+            &src_channel_id,
+            &port_id,
+            &dst_channel_id,
+            &events,
+        );
+
+        assert_eq!(relayed.len(), 3);
+
+        // On restart, the packet is still reported as unreceived
+        // by a node which did not catch up with the block yet.
+        let pending: Vec<Sequence> = [3, 5, 6, 7].into_iter().map(Sequence::from).collect();
+
+        let to_relay: Vec<Sequence> = pending
+            .into_iter()
+            .filter(|sequence| !relayed.is_recv_or_timeout_relayed(sequence))
+            .collect();
+
+        assert_eq!(to_relay, vec![Sequence::from(6), Sequence::from(7)]);
+
+        assert!(relayed.is_ack_relayed(&Sequence::from(4)));
+        assert!(!relayed.is_ack_relayed(&Sequence::from(5)));
+    }
+
+    #[test]
+    fn no_relayed_packets_without_events() {
+        let relayed = RelayedPackets::from_events(
+            &PortId::transfer(),
+            &ChannelId::new(0),
+            &PortId::transfer(),
+            &ChannelId::new(1),
+            &[],
+        );
+
+        assert!(relayed.is_empty());
+    }
+}
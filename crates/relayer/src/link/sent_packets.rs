@@ -0,0 +1,123 @@
+use alloc::collections::BTreeSet;
+
+use ibc_relayer_types::core::ics04_channel::packet::Sequence;
+use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, PortId};
+use ibc_relayer_types::events::IbcEvent;
+use ibc_relayer_types::Height;
+
+use crate::event::IbcEventWithHeight;
+
+/// The packets sent on a channel by a chain whose commitments cannot be listed,
+/// which are instead found from the `SendPacket` events of the chain, so that
+/// packet clearing knows which packets may still be pending.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SentPackets {
+    /// Sequences of the packets sent on the channel which are not known to be relayed
+    sequences: BTreeSet<Sequence>,
+    /// Height of the latest `SendPacket` event found, from which the next scan starts
+    scanned_to: Option<Height>,
+}
+
+impl SentPackets {
+    /// The height from which the `SendPacket` events of the chain are scanned next,
+    /// or `None` if the events were never scanned.
+    pub fn scan_from(&self) -> Option<Height> {
+        self.scanned_to
+    }
+
+    /// Adds the packets sent on the channel `port_id/channel_id` out of the given events.
+    pub fn record<'a>(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        events: impl IntoIterator<Item = &'a IbcEventWithHeight>,
+    ) {
+        for event_with_height in events {
+            let IbcEvent::SendPacket(event) = &event_with_height.event else {
+                continue;
+            };
+
+            if &event.packet.source_port != port_id || &event.packet.source_channel != channel_id {
+                continue;
+            }
+
+            self.sequences.insert(event.packet.sequence);
+            self.scanned_to = self.scanned_to.max(Some(event_with_height.height));
+        }
+    }
+
+    /// Forgets the given packets, which were relayed.
+    pub fn forget(&mut self, sequences: impl IntoIterator<Item = Sequence>) {
+        for sequence in sequences {
+            self.sequences.remove(&sequence);
+        }
+    }
+
+    pub fn sequences(&self) -> Vec<Sequence> {
+        self.sequences.iter().copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_relayer_types::core::ics04_channel::events::SendPacket;
+    use ibc_relayer_types::core::ics04_channel::packet::Packet;
+
+    fn send_packet(channel_id: &ChannelId, sequence: u64, height: u64) -> IbcEventWithHeight {
+        let packet = Packet {
+            sequence: sequence.into(),
+            source_port: PortId::transfer(),
+            source_channel: channel_id.clone(),
+            destination_port: PortId::transfer(),
+            destination_channel: ChannelId::new(99),
+            data: vec![],
+            timeout_height: Default::default(),
+            timeout_timestamp: Default::default(),
+        };
+
+        IbcEventWithHeight::new(
+            IbcEvent::SendPacket(SendPacket { packet }),
+            Height::new(0, height).unwrap(),
+        )
+    }
+
+    #[test]
+    fn sent_packets_are_tracked_until_relayed() {
+        let port_id = PortId::transfer();
+        let channel_id = ChannelId::new(0);
+
+        let mut sent = SentPackets::default();
+        assert_eq!(sent.scan_from(), None);
+
+        sent.record(
+            &port_id,
+            &channel_id,
+            &[
+                send_packet(&channel_id, 1, 10),
+                send_packet(&channel_id, 3, 12),
+                send_packet(&ChannelId::new(1), 2, 15),
+            ],
+        );
+
+        // The packets sent on other channels are left out
+        assert_eq!(sent.sequences(), vec![1.into(), 3.into()]);
+        assert_eq!(sent.scan_from(), Some(Height::new(0, 12).unwrap()));
+
+        // The next scan starts from the latest event found, which it finds again
+        sent.record(
+            &port_id,
+            &channel_id,
+            &[
+                send_packet(&channel_id, 3, 12),
+                send_packet(&channel_id, 4, 13),
+            ],
+        );
+        assert_eq!(sent.sequences(), vec![1.into(), 3.into(), 4.into()]);
+        assert_eq!(sent.scan_from(), Some(Height::new(0, 13).unwrap()));
+
+        sent.forget([1.into(), 4.into()]);
+        assert_eq!(sent.sequences(), vec![3.into()]);
+    }
+}
@@ -0,0 +1,117 @@
+//! The dedicated lane in which the timeouts of packets are submitted to a chain,
+//! in batches paced separately from the other packet messages, as configured by
+//! the [`TimeoutBatchConfig`] of the chain.
+
+use std::time::{Duration, Instant};
+
+use crate::config::TimeoutBatchConfig;
+use crate::link::operational_data::OperationalData;
+use crate::util::lock::{LockExt, RwArc};
+
+#[derive(Clone, Debug)]
+pub struct TimeoutLane {
+    config: TimeoutBatchConfig,
+    last_sent: RwArc<Option<Instant>>,
+}
+
+impl TimeoutLane {
+    pub fn new(config: TimeoutBatchConfig) -> Self {
+        Self {
+            config,
+            last_sent: RwArc::new_lock(None),
+        }
+    }
+
+    /// Splits operational data holding timeouts into batches within the limits of the lane.
+    pub fn split(&self, od: OperationalData) -> Vec<OperationalData> {
+        od.split_by_limits(self.config.max_msg_num, self.config.max_tx_size)
+    }
+
+    /// Returns how long to wait from `now` until the next batch of timeouts can be sent.
+    pub fn delay(&self, now: Instant) -> Duration {
+        match *self.last_sent.acquire_read() {
+            Some(last_sent) => (last_sent + self.config.interval).saturating_duration_since(now),
+            None => Duration::ZERO,
+        }
+    }
+
+    /// Records that a batch of timeouts was sent at `now`.
+    pub fn record_sent(&self, now: Instant) {
+        *self.last_sent.acquire_write() = Some(now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::chain::tracking::TrackingId;
+    use crate::config::types::{MaxMsgNum, MaxTxSize};
+    use crate::event::IbcEventWithHeight;
+    use crate::link::operational_data::{OperationalDataTarget, TransitMessage};
+
+    use ibc_proto::google::protobuf::Any;
+    use ibc_relayer_types::core::ics04_channel::events::TimeoutPacket;
+    use ibc_relayer_types::core::ics04_channel::packet::{Packet, Sequence};
+    use ibc_relayer_types::events::IbcEvent;
+    use ibc_relayer_types::Height;
+
+    fn timeouts(count: u64) -> OperationalData {
+        let mut od = OperationalData::new(
+            Height::new(0, 10).unwrap(),
+            OperationalDataTarget::Source,
+            TrackingId::new_static("timeouts"),
+            Duration::ZERO,
+        );
+
+        for sequence in 1..=count {
+            od.push(TransitMessage {
+                event_with_height: IbcEventWithHeight::new(
+                    IbcEvent::TimeoutPacket(TimeoutPacket {
+                        packet: Packet {
+                            sequence: Sequence::from(sequence),
+                            ..Packet::default()
+                        },
+                    }),
+                    Height::new(0, 10).unwrap(),
+                ),
+                msg: Any {
+                    type_url: "/ibc.core.channel.v1.MsgTimeout".to_owned(),
+                    value: vec![0; 100],
+                },
+            });
+        }
+
+        od
+    }
+
+    #[test]
+    fn timeouts_are_paced_in_batches_within_limits() {
+        let lane = TimeoutLane::new(TimeoutBatchConfig {
+            max_msg_num: MaxMsgNum::new(10).unwrap(),
+            max_tx_size: MaxTxSize::default(),
+            interval: Duration::from_secs(5),
+        });
+
+        let batches = lane.split(timeouts(25));
+
+        assert_eq!(
+            batches.iter().map(|od| od.batch.len()).collect::<Vec<_>>(),
+            [10, 10, 5]
+        );
+
+        // Sending the batches in turn, each batch waits for the interval
+        // to elapse since the previous batch was sent
+        let start = Instant::now();
+        let mut now = start;
+
+        for _ in &batches {
+            now += lane.delay(now);
+            lane.record_sent(now);
+        }
+
+        assert_eq!(now - start, Duration::from_secs(10));
+        assert_eq!(lane.delay(now), Duration::from_secs(5));
+        assert_eq!(lane.delay(now + Duration::from_secs(5)), Duration::ZERO);
+    }
+}
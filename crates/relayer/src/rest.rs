@@ -4,6 +4,7 @@ use tracing::{error, trace};
 
 use crate::{
     config::Config,
+    link::SequenceRange,
     rest::request::ReplySender,
     rest::request::{Request, VersionInfo},
     supervisor::dump_state::SupervisorState,
@@ -32,7 +33,8 @@ pub type Receiver = crossbeam_channel::Receiver<Request>;
 //  e.g., adjusting chain config, removing chains, etc.
 pub enum Command {
     DumpState(ReplySender<SupervisorState>),
-    ClearPackets(Option<ChainId>, ReplySender<()>),
+    ClearPackets(Option<ChainId>, SequenceRange, ReplySender<()>),
+    ClearPendingOperationalData(Option<ChainId>, ReplySender<()>),
 }
 
 /// Process incoming REST requests.
@@ -85,10 +87,20 @@ pub fn process_incoming_requests(config: &Config, channel: &Receiver) -> Option<
                 return Some(Command::DumpState(reply_to));
             }
 
-            Request::ClearPackets { chain_id, reply_to } => {
-                trace!("ClearPackets");
+            Request::ClearPackets {
+                chain_id,
+                sequences,
+                reply_to,
+            } => {
+                trace!("ClearPackets {}", sequences);
 
-                return Some(Command::ClearPackets(chain_id, reply_to));
+                return Some(Command::ClearPackets(chain_id, sequences, reply_to));
+            }
+
+            Request::ClearPendingOperationalData { chain_id, reply_to } => {
+                trace!("ClearPendingOperationalData");
+
+                return Some(Command::ClearPendingOperationalData(chain_id, reply_to));
             }
         },
         Err(e) => {
@@ -2,7 +2,10 @@ use serde::{Deserialize, Serialize};
 
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 
-use crate::{config::ChainConfig, rest::RestApiError, supervisor::dump_state::SupervisorState};
+use crate::{
+    config::ChainConfig, link::SequenceRange, rest::RestApiError,
+    supervisor::dump_state::SupervisorState,
+};
 
 pub type ReplySender<T> = crossbeam_channel::Sender<Result<T, RestApiError>>;
 pub type ReplyReceiver<T> = crossbeam_channel::Receiver<Result<T, RestApiError>>;
@@ -38,6 +41,12 @@ pub enum Request {
     },
 
     ClearPackets {
+        chain_id: Option<ChainId>,
+        sequences: SequenceRange,
+        reply_to: ReplySender<()>,
+    },
+
+    ClearPendingOperationalData {
         chain_id: Option<ChainId>,
         reply_to: ReplySender<()>,
     },
@@ -4,24 +4,35 @@ use core::convert::Infallible;
 use core::ops::Deref;
 use core::time::Duration;
 use std::sync::RwLock;
+use std::time::Instant;
 
-use crossbeam_channel::{unbounded, Receiver, Sender};
+use crossbeam_channel::{unbounded, Receiver, Sender, TryRecvError};
 use itertools::Itertools;
 use tracing::{debug, error, error_span, info, instrument, trace, warn};
 
 use ibc_relayer_types::{
-    core::ics24_host::identifier::{ChainId, ChannelId, PortId},
+    core::ics24_host::identifier::{ChainId, ChannelId, ClientId, PortId},
     events::IbcEvent,
     Height,
 };
 
 use crate::{
-    chain::{endpoint::HealthCheck, handle::ChainHandle, tracking::TrackingId},
-    config::Config,
+    canary::send_canary,
+    chain::counterparty::counterparty_chain_from_channel,
+    chain::{
+        handle::ChainHandle,
+        requests::{PageRequest, QueryClientConnectionsRequest, QueryClientStatesRequest},
+        tracking::TrackingId,
+    },
+    config::{CanaryConfig, Config, ConnectionPath},
+    connection::Connection,
+    error::Error as RelayerError,
     event::{
         source::{self, Error as EventError, ErrorDetail as EventErrorDetail, EventBatch},
         IbcEventWithHeight,
     },
+    foreign_client::ForeignClient,
+    link::SequenceRange,
     object::Object,
     registry::{Registry, SharedRegistry},
     rest,
@@ -54,6 +65,11 @@ use self::{scan::ChainScanner, spawn::SpawnContext};
 type ArcBatch = Arc<source::Result<EventBatch>>;
 type Subscription = Receiver<ArcBatch>;
 
+/// The delay before the first retry to resubscribe to the events of a chain whose
+/// runtime failed to respawn, doubled after each failure up to `MAX_RESUBSCRIBE_DELAY`.
+const MIN_RESUBSCRIBE_DELAY: Duration = Duration::from_secs(1);
+const MAX_RESUBSCRIBE_DELAY: Duration = Duration::from_secs(60);
+
 /**
     A wrapper around the SupervisorCmd sender so that we can
     send stop signal to the supervisor before stopping the
@@ -131,6 +147,23 @@ impl SupervisorHandle {
 
         Ok(state)
     }
+
+    /// Ask the supervisor to reload its configuration, and to stop the workers of
+    /// the chains and channels it no longer relays for, dropping their state.
+    /// The workers of the chains whose configuration changed are restarted, while
+    /// the workers relaying between unchanged chains keep running.
+    /// Returns the objects whose workers were stopped.
+    pub fn reload_config(&self, config: Config) -> Result<Vec<Object>, Error> {
+        let (tx, rx) = crossbeam_channel::bounded(1);
+
+        self.sender
+            .send(SupervisorCmd::ReloadConfig(Box::new(config), tx))
+            .map_err(|_| Error::handle_send())?;
+
+        let stopped = rx.recv().map_err(|_| Error::handle_recv())?;
+
+        Ok(stopped)
+    }
 }
 
 /// Whether the supervisor should scan the chains for clients, connections, and channels.
@@ -145,8 +178,11 @@ fn should_scan(config: &Config, options: &SupervisorOptions) -> bool {
         || (config.mode.packets.enabled && config.mode.packets.clear_on_start)
         || config.mode.connections.enabled
         || config.mode.channels.enabled
-        || (config.mode.clients.enabled
-            && (config.mode.clients.misbehaviour || config.mode.clients.refresh))
+        || (config.mode.clients.enabled && config.mode.clients.refresh)
+        || config
+            .chains
+            .iter()
+            .any(|chain| config.detects_misbehaviour(chain.id()))
 }
 
 pub fn spawn_supervisor_tasks<Chain: ChainHandle>(
@@ -160,6 +196,12 @@ pub fn spawn_supervisor_tasks<Chain: ChainHandle>(
         health_check(&config, &mut registry.write());
     }
 
+    // Create the missing connections in the background, so that the handshakes
+    // do not hold up the relaying on the other chains
+    let connections_task = config.mode.connections.create_on_start.then(|| {
+        spawn_connection_creation_worker(config.mode.connections.paths.clone(), registry.clone())
+    });
+
     // If telemetry is enabled, for each chain register the relayer's address
     // in the list of visible fee addresses.
     if config.telemetry.enabled {
@@ -170,7 +212,16 @@ pub fn spawn_supervisor_tasks<Chain: ChainHandle>(
         }
     }
 
-    let workers = Arc::new(RwLock::new(WorkerMap::new()));
+    let mut worker_map = WorkerMap::new();
+
+    // Have the packet workers hand over the packets sent by their transactions, if enabled.
+    let sent_packets_rx = config.mode.packets.relay_forwarded_packets.then(|| {
+        let (tx, rx) = unbounded();
+        worker_map.forward_sent_packets(tx);
+        rx
+    });
+
+    let workers = Arc::new(RwLock::new(worker_map));
     let client_state_filter = Arc::new(RwLock::new(FilterPolicy::default()));
 
     // Only scan when needed
@@ -196,21 +247,42 @@ pub fn spawn_supervisor_tasks<Chain: ChainHandle>(
 
     let subscriptions = init_subscriptions(&config, &mut registry.write())?;
 
+    // The configuration of the tasks processing events, which can be reloaded.
+    let shared_config = Arc::new(RwLock::new(config.clone()));
+
     let batch_tasks = spawn_batch_workers(
-        &config,
+        shared_config.clone(),
         registry.clone(),
-        client_state_filter,
+        client_state_filter.clone(),
         workers.clone(),
         subscriptions,
     );
 
-    let cmd_task = spawn_cmd_worker(registry.clone(), workers.clone(), cmd_rx);
+    let cmd_task = spawn_cmd_worker(
+        shared_config.clone(),
+        registry.clone(),
+        client_state_filter.clone(),
+        workers.clone(),
+        cmd_rx,
+    );
 
     let mut tasks = vec![cmd_task];
     tasks.extend(batch_tasks);
+    tasks.extend(connections_task);
+
+    if let Some(sent_packets_rx) = sent_packets_rx {
+        let sent_packets_task = spawn_sent_packets_worker(
+            shared_config.clone(),
+            registry.clone(),
+            client_state_filter,
+            workers.clone(),
+            sent_packets_rx,
+        );
+        tasks.push(sent_packets_task);
+    }
 
     if let Some(rest_rx) = rest_rx {
-        let rest_task = spawn_rest_worker(config, registry, workers.clone(), rest_rx);
+        let rest_task = spawn_rest_worker(shared_config, registry, workers.clone(), rest_rx);
         tasks.push(rest_task);
     }
 
@@ -221,7 +293,7 @@ pub fn spawn_supervisor_tasks<Chain: ChainHandle>(
 }
 
 fn spawn_batch_workers<Chain: ChainHandle>(
-    config: &Config,
+    config: Arc<RwLock<Config>>,
     registry: SharedRegistry<Chain>,
     client_state_filter: Arc<RwLock<FilterPolicy>>,
     workers: Arc<RwLock<WorkerMap>>,
@@ -229,25 +301,56 @@ fn spawn_batch_workers<Chain: ChainHandle>(
 ) -> Vec<TaskHandle> {
     let mut handles = Vec::with_capacity(subscriptions.len());
 
-    for (chain, subscription) in subscriptions {
+    for (mut chain, mut subscription) in subscriptions {
         let config = config.clone();
         let registry = registry.clone();
         let client_state_filter = client_state_filter.clone();
         let workers = workers.clone();
+        let mut backoff = ResubscribeBackoff::default();
 
         let handle = spawn_background_task(
             error_span!("worker.batch", chain = %chain.id()),
             Some(Duration::from_millis(5)),
             move || -> Result<Next, TaskError<Infallible>> {
-                if let Ok(batch) = subscription.try_recv() {
-                    handle_batch(
-                        &config,
-                        &mut registry.write(),
-                        &mut client_state_filter.acquire_write(),
-                        &mut workers.acquire_write(),
-                        chain.clone(),
-                        batch,
-                    );
+                match subscription.try_recv() {
+                    Ok(batch) => {
+                        handle_batch(
+                            &config.acquire_read(),
+                            &mut registry.write(),
+                            &mut client_state_filter.acquire_write(),
+                            &mut workers.acquire_write(),
+                            chain.clone(),
+                            batch,
+                        );
+                    }
+                    // The runtime of the chain was shut down by a reload of the configuration,
+                    // which either removed the chain or changed its configuration, in which
+                    // case subscribe to the events of the runtime respawned in its stead.
+                    Err(TryRecvError::Disconnected) => {
+                        let chain_id = chain.id();
+
+                        if !config.acquire_read().has_chain(&chain_id) {
+                            return Ok(Next::Abort);
+                        }
+
+                        match backoff.attempt(Instant::now(), || resubscribe(&registry, &chain_id))
+                        {
+                            Some(Ok((new_chain, new_subscription))) => {
+                                info!("resubscribed to the events of chain {chain_id}");
+
+                                chain = new_chain;
+                                subscription = new_subscription;
+                            }
+                            Some(Err((e, delay))) => {
+                                error!(
+                                    "failed to resubscribe to the events of {chain_id}, \
+                                    retrying in {delay:?}: {e}"
+                                )
+                            }
+                            None => {}
+                        }
+                    }
+                    Err(TryRecvError::Empty) => {}
                 }
 
                 Ok(Next::Continue)
@@ -260,20 +363,250 @@ fn spawn_batch_workers<Chain: ChainHandle>(
     handles
 }
 
+/// Spaces the attempts to resubscribe to the events of a chain whose runtime fails to
+/// respawn, eg. after a reload of the configuration with a bad RPC address for the chain.
+#[derive(Debug, Default)]
+struct ResubscribeBackoff {
+    failures: u32,
+    next_attempt: Option<Instant>,
+}
+
+impl ResubscribeBackoff {
+    /// Runs the given attempt to resubscribe unless the previous one failed less than the
+    /// current delay ago, in which case returns `None`. Returns the error of a failed attempt
+    /// along with the delay before the next one.
+    fn attempt<T, E>(
+        &mut self,
+        now: Instant,
+        resubscribe: impl FnOnce() -> Result<T, E>,
+    ) -> Option<Result<T, (E, Duration)>> {
+        if self
+            .next_attempt
+            .is_some_and(|next_attempt| now < next_attempt)
+        {
+            return None;
+        }
+
+        match resubscribe() {
+            Ok(subscribed) => {
+                *self = Self::default();
+                Some(Ok(subscribed))
+            }
+            Err(e) => {
+                let delay = MIN_RESUBSCRIBE_DELAY
+                    .saturating_mul(1 << self.failures.min(16))
+                    .min(MAX_RESUBSCRIBE_DELAY);
+
+                self.failures = self.failures.saturating_add(1);
+                self.next_attempt = Some(now + delay);
+
+                Some(Err((e, delay)))
+            }
+        }
+    }
+}
+
+/// Subscribe to the events of the runtime of the given chain, spawning it if needed.
+fn resubscribe<Chain: ChainHandle>(
+    registry: &SharedRegistry<Chain>,
+    chain_id: &ChainId,
+) -> Result<(Chain, Subscription), Error> {
+    let chain = registry.get_or_spawn(chain_id).map_err(Error::spawn)?;
+    let subscription = chain.subscribe().map_err(Error::relayer)?;
+
+    Ok((chain, subscription))
+}
+
+/// Spawn a background task which relays the packets sent by the confirmed
+/// transactions of the packet workers, eg. the packets forwarded by a packet
+/// forwarding middleware upon receiving a packet, as soon as they are handed over.
+fn spawn_sent_packets_worker<Chain: ChainHandle>(
+    config: Arc<RwLock<Config>>,
+    registry: SharedRegistry<Chain>,
+    client_state_filter: Arc<RwLock<FilterPolicy>>,
+    workers: Arc<RwLock<WorkerMap>>,
+    sent_packets_rx: Receiver<EventBatch>,
+) -> TaskHandle {
+    spawn_background_task(
+        error_span!("worker.sent_packets"),
+        Some(Duration::from_millis(5)),
+        move || -> Result<Next, TaskError<Infallible>> {
+            if let Ok(batch) = sent_packets_rx.try_recv() {
+                let config = config.acquire_read();
+                let mut registry = registry.write();
+
+                match registry.get_or_spawn(&batch.chain_id) {
+                    Ok(chain) => {
+                        if let Err(e) = process_batch(
+                            &config,
+                            &mut registry,
+                            &mut client_state_filter.acquire_write(),
+                            &mut workers.acquire_write(),
+                            chain,
+                            &batch,
+                        ) {
+                            error!("error during processing of sent packets: {}", e);
+                        }
+                    }
+                    Err(e) => {
+                        error!(
+                            "skipping packets sent on chain {}, reason: failed to spawn chain runtime with error: {}",
+                            batch.chain_id, e
+                        );
+                    }
+                }
+            }
+
+            Ok(Next::Continue)
+        },
+    )
+}
+
+/// Spawn a background task for each canary configured on the channels of the chains,
+/// which periodically sends a canary packet and reports whether it was relayed in time.
+/// No canary packets are sent if packet relaying is disabled.
+fn spawn_canary_workers<Chain: ChainHandle>(
+    config: &Config,
+    registry: SharedRegistry<Chain>,
+) -> Vec<TaskHandle> {
+    let mut handles = Vec::new();
+
+    if !config.mode.packets.enabled {
+        return handles;
+    }
+
+    for chain_config in &config.chains {
+        for (channel_id, canary) in chain_config.canaries().iter() {
+            let chain_id = chain_config.id().clone();
+            let channel_id = channel_id.clone();
+            let canary = canary.clone();
+            let registry = registry.clone();
+
+            let mut next_canary_at = Instant::now();
+
+            // Wake up often, rather than once per interval, so that the task stops promptly
+            let handle = spawn_background_task(
+                error_span!("worker.canary", chain = %chain_id, port = %canary.port, channel = %channel_id),
+                Some(Duration::from_secs(1)),
+                move || -> Result<Next, TaskError<Infallible>> {
+                    if Instant::now() < next_canary_at {
+                        return Ok(Next::Continue);
+                    }
+
+                    next_canary_at = Instant::now() + canary.interval;
+
+                    if let Err(e) = run_canary(&registry, &chain_id, &channel_id, &canary) {
+                        error!("failed to send canary packet: {}", e);
+                    }
+
+                    Ok(Next::Continue)
+                },
+            );
+
+            handles.push(handle);
+        }
+    }
+
+    handles
+}
+
+fn run_canary<Chain: ChainHandle>(
+    registry: &SharedRegistry<Chain>,
+    chain_id: &ChainId,
+    channel_id: &ChannelId,
+    canary: &CanaryConfig,
+) -> Result<(), Error> {
+    let chain = registry.get_or_spawn(chain_id).map_err(Error::spawn)?;
+
+    let counterparty_chain_id = counterparty_chain_from_channel(&chain, channel_id, &canary.port)?;
+
+    let counterparty_chain = registry
+        .get_or_spawn(&counterparty_chain_id)
+        .map_err(Error::spawn)?;
+
+    let report =
+        send_canary(&chain, &counterparty_chain, channel_id, canary).map_err(Error::canary)?;
+
+    if report.success {
+        info!(
+            sequence = %report.sequence,
+            "canary packet relayed in {} ms",
+            report.latency.as_millis()
+        );
+    } else {
+        warn!(
+            sequence = %report.sequence,
+            "canary packet not relayed within {} ms",
+            canary.threshold.as_millis()
+        );
+    }
+
+    telemetry!(
+        canary_packet,
+        chain_id,
+        &counterparty_chain_id,
+        channel_id,
+        &canary.port,
+        report.success,
+        report.latency.as_millis() as u64
+    );
+
+    Ok(())
+}
+
+/// Spawn a background task which handles the commands sent to the supervisor.
+///
+/// The task owns the canary workers, which it respawns with the new configuration
+/// when the configuration is reloaded, as well as the tasks processing the events
+/// of the chains added by a reload of the configuration.
 pub fn spawn_cmd_worker<Chain: ChainHandle>(
+    config: Arc<RwLock<Config>>,
     registry: SharedRegistry<Chain>,
+    client_state_filter: Arc<RwLock<FilterPolicy>>,
     workers: Arc<RwLock<WorkerMap>>,
     cmd_rx: Receiver<SupervisorCmd>,
 ) -> TaskHandle {
+    let mut canary_tasks = spawn_canary_workers(&config.acquire_read(), registry.clone());
+    let mut stopping_canary_tasks: Vec<TaskHandle> = Vec::new();
+    let mut added_chain_tasks: Vec<TaskHandle> = Vec::new();
+
     spawn_background_task(
         error_span!("worker.cmd"),
         Some(Duration::from_millis(500)),
         move || -> Result<Next, TaskError<Infallible>> {
+            stopping_canary_tasks.retain(|task| !task.is_stopped());
+            added_chain_tasks.retain(|task| !task.is_stopped());
+
             if let Ok(cmd) = cmd_rx.try_recv() {
                 match cmd {
                     SupervisorCmd::DumpState(reply_to) => {
                         dump_state(&registry.read(), &workers.acquire_read(), reply_to);
                     }
+                    SupervisorCmd::ReloadConfig(new_config, reply_to) => {
+                        let added = added_chains(&config.acquire_read(), &new_config);
+
+                        let stopped = reload_config(&config, &registry, &workers, *new_config);
+                        let _ = reply_to.try_send(stopped);
+
+                        // Stop the canaries without waiting for the packets they are tracking
+                        for task in canary_tasks.drain(..) {
+                            task.shutdown();
+                            stopping_canary_tasks.push(task);
+                        }
+
+                        canary_tasks =
+                            spawn_canary_workers(&config.acquire_read(), registry.clone());
+
+                        let subscriptions = subscribe_chains(&added, &mut registry.write());
+
+                        added_chain_tasks.extend(spawn_batch_workers(
+                            config.clone(),
+                            registry.clone(),
+                            client_state_filter.clone(),
+                            workers.clone(),
+                            subscriptions,
+                        ));
+                    }
                 }
             }
 
@@ -283,7 +616,7 @@ pub fn spawn_cmd_worker<Chain: ChainHandle>(
 }
 
 pub fn spawn_rest_worker<Chain: ChainHandle>(
-    config: Config,
+    config: Arc<RwLock<Config>>,
     registry: SharedRegistry<Chain>,
     workers: Arc<RwLock<WorkerMap>>,
     rest_rx: rest::Receiver,
@@ -292,7 +625,12 @@ pub fn spawn_rest_worker<Chain: ChainHandle>(
         error_span!("rest"),
         Some(Duration::from_millis(500)),
         move || -> Result<Next, TaskError<Infallible>> {
-            handle_rest_requests(&config, &registry.read(), &workers.acquire_read(), &rest_rx);
+            handle_rest_requests(
+                &config.acquire_read(),
+                &registry.read(),
+                &workers.acquire_read(),
+                &rest_rx,
+            );
 
             Ok(Next::Continue)
         },
@@ -446,7 +784,7 @@ pub fn collect_events(
     let mut collected =
         CollectedEvents::new(batch.height, batch.chain_id.clone(), batch.tracking_id);
 
-    let mode = config.mode;
+    let mode = &config.mode;
 
     for event_with_height in &batch.events {
         match &event_with_height.event {
@@ -641,8 +979,6 @@ fn chain_scanner<'a, Chain: ChainHandle>(
 
 /// Perform a health check on all connected chains
 fn health_check<Chain: ChainHandle>(config: &Config, registry: &mut Registry<Chain>) {
-    use HealthCheck::*;
-
     let chains = &config.chains;
 
     for config in chains {
@@ -653,8 +989,7 @@ fn health_check<Chain: ChainHandle>(config: &Config, registry: &mut Registry<Cha
 
         match chain {
             Ok(chain) => match chain.health_check() {
-                Ok(Healthy) => info!("chain is healthy"),
-                Ok(Unhealthy(e)) => warn!("chain is not healthy: {}", e),
+                Ok(report) => report.log(),
                 Err(e) => error!("failed to perform health check: {}", e),
             },
             Err(e) => {
@@ -667,25 +1002,139 @@ fn health_check<Chain: ChainHandle>(config: &Config, registry: &mut Registry<Cha
     }
 }
 
+fn spawn_connection_creation_worker<Chain: ChainHandle>(
+    paths: Vec<ConnectionPath>,
+    registry: SharedRegistry<Chain>,
+) -> TaskHandle {
+    spawn_background_task(
+        error_span!("worker.create_connections"),
+        None,
+        move || -> Result<Next, TaskError<Infallible>> {
+            create_missing_connections(&paths, &registry);
+            Ok(Next::Abort)
+        },
+    )
+}
+
+/// Create a connection between the chains of each of the given paths which have
+/// no connection with each other yet, but which both host a client of the other chain.
+#[instrument(
+    name = "supervisor.create_missing_connections",
+    level = "error",
+    skip_all
+)]
+fn create_missing_connections<Chain: ChainHandle>(
+    paths: &[ConnectionPath],
+    registry: &SharedRegistry<Chain>,
+) {
+    for path in paths {
+        let (chain_id_a, chain_id_b) = (&path.a_chain, &path.b_chain);
+        let _span = error_span!("create_connection", a = %chain_id_a, b = %chain_id_b).entered();
+
+        let (chain_a, chain_b) = match (
+            registry.get_or_spawn(chain_id_a),
+            registry.get_or_spawn(chain_id_b),
+        ) {
+            (Ok(chain_a), Ok(chain_b)) => (chain_a, chain_b),
+            (Err(e), _) | (_, Err(e)) => {
+                error!("skipping connection creation, reason: failed to spawn chain runtime with error: {e}");
+                continue;
+            }
+        };
+
+        let clients = client_without_connection(&chain_a, chain_id_b).and_then(|client_a| {
+            client_without_connection(&chain_b, chain_id_a).map(|client_b| (client_a, client_b))
+        });
+
+        let (client_id_a, client_id_b) = match clients {
+            Ok((Some(client_id_a), Some(client_id_b))) => (client_id_a, client_id_b),
+            Ok(_) => {
+                debug!("no missing connection to create");
+                continue;
+            }
+            Err(e) => {
+                error!("skipping connection creation, reason: failed to query clients: {e}");
+                continue;
+            }
+        };
+
+        info!("creating missing connection between clients {client_id_a} and {client_id_b}");
+
+        let connection = Connection::new(
+            ForeignClient::restore(client_id_a, chain_a.clone(), chain_b.clone()),
+            ForeignClient::restore(client_id_b, chain_b, chain_a),
+            path.delay,
+        );
+
+        match connection {
+            Ok(connection) => info!("created connection {connection}"),
+            Err(e) => error!("failed to create connection: {e}"),
+        }
+    }
+}
+
+/// Returns a client hosted on the given chain and tracking the given counterparty chain,
+/// provided that no client of the counterparty chain is used by a connection yet.
+fn client_without_connection<Chain: ChainHandle>(
+    chain: &Chain,
+    counterparty_chain_id: &ChainId,
+) -> Result<Option<ClientId>, RelayerError> {
+    let clients = chain
+        .query_clients(QueryClientStatesRequest {
+            pagination: Some(PageRequest::all()),
+        })?
+        .into_iter()
+        .filter(|client| client.client_state.chain_id() == *counterparty_chain_id)
+        .collect_vec();
+
+    for client in &clients {
+        let connections = chain.query_client_connections(QueryClientConnectionsRequest {
+            client_id: client.client_id.clone(),
+        })?;
+
+        if !connections.is_empty() {
+            return Ok(None);
+        }
+    }
+
+    Ok(clients
+        .into_iter()
+        .find(|client| client.client_state.frozen_height().is_none())
+        .map(|client| client.client_id))
+}
+
 /// Subscribe to the events emitted by the chains the supervisor is connected to.
 #[instrument(name = "supervisor.init_subscriptions", level = "error", skip_all)]
 fn init_subscriptions<Chain: ChainHandle>(
     config: &Config,
     registry: &mut Registry<Chain>,
 ) -> Result<Vec<(Chain, Subscription)>, Error> {
-    let chains = &config.chains;
+    let chain_ids: Vec<ChainId> = config.chains.iter().map(|c| c.id().clone()).collect();
 
-    let mut subscriptions = Vec::with_capacity(chains.len());
+    let subscriptions = subscribe_chains(&chain_ids, registry);
+
+    // At least one chain runtime should be available, otherwise the supervisor
+    // cannot do anything and will hang indefinitely.
+    if registry.size() == 0 {
+        return Err(Error::no_chains_available());
+    }
+
+    Ok(subscriptions)
+}
 
-    for chain_config in chains {
-        let chain = match registry.get_or_spawn(chain_config.id()) {
+/// Subscribe to the events of the given chains, spawning their runtimes if needed,
+/// skipping the chains whose runtime cannot be spawned or subscribed to.
+fn subscribe_chains<Chain: ChainHandle>(
+    chain_ids: &[ChainId],
+    registry: &mut Registry<Chain>,
+) -> Vec<(Chain, Subscription)> {
+    let mut subscriptions = Vec::with_capacity(chain_ids.len());
+
+    for chain_id in chain_ids {
+        let chain = match registry.get_or_spawn(chain_id) {
             Ok(chain) => chain,
             Err(e) => {
-                error!(
-                    "failed to spawn chain runtime for {}: {}",
-                    chain_config.id(),
-                    e
-                );
+                error!("failed to spawn chain runtime for {}: {}", chain_id, e);
 
                 continue;
             }
@@ -693,21 +1142,123 @@ fn init_subscriptions<Chain: ChainHandle>(
 
         match chain.subscribe() {
             Ok(subscription) => subscriptions.push((chain, subscription)),
-            Err(e) => error!(
-                "failed to subscribe to events of {}: {}",
-                chain_config.id(),
-                e
-            ),
+            Err(e) => error!("failed to subscribe to events of {}: {}", chain_id, e),
         }
     }
 
-    // At least one chain runtime should be available, otherwise the supervisor
-    // cannot do anything and will hang indefinitely.
-    if registry.size() == 0 {
-        return Err(Error::no_chains_available());
+    subscriptions
+}
+
+/// Replace the configuration of the supervisor with the given one, stopping the
+/// workers of the objects it no longer relays for, which drops their pending
+/// operational data, and shutting down the runtimes of the removed chains.
+///
+/// The runtimes of the chains whose configuration changed are restarted with their
+/// new configuration, and the workers relaying to or from these chains are stopped,
+/// to be spawned again upon their next event. The workers which only relay between
+/// chains whose configuration is unchanged are left running.
+///
+/// Returns the objects whose workers were stopped.
+#[instrument(name = "supervisor.reload_config", level = "error", skip_all)]
+fn reload_config<Chain: ChainHandle>(
+    config: &Arc<RwLock<Config>>,
+    registry: &SharedRegistry<Chain>,
+    workers: &Arc<RwLock<WorkerMap>>,
+    new_config: Config,
+) -> Vec<Object> {
+    // Lock the configuration before the workers, as the tasks processing events do
+    let mut config = config.acquire_write();
+    let mut workers = workers.acquire_write();
+
+    let updated = updated_chains(&config, &new_config);
+    let stale = stale_objects(&new_config, &updated, &workers);
+
+    for object in &stale {
+        info!(
+            "stopping worker for {}, which is no longer relayed or whose chains were updated",
+            object.short_name()
+        );
+
+        workers.shutdown_worker(object);
     }
 
-    Ok(subscriptions)
+    // Respawn the runtimes with the new configuration from now on
+    registry.write().update_config(new_config.clone());
+
+    for chain_config in &config.chains {
+        if !new_config.has_chain(chain_config.id()) {
+            info!(
+                "shutting down runtime of removed chain {}",
+                chain_config.id()
+            );
+
+            registry.shutdown(chain_config.id());
+        }
+    }
+
+    for chain_id in &updated {
+        info!("restarting runtime of chain {chain_id} with its updated configuration");
+
+        registry.shutdown(chain_id);
+    }
+
+    *config = new_config;
+
+    stale
+}
+
+/// The identifiers of the chains present in the new configuration only.
+fn added_chains(old_config: &Config, new_config: &Config) -> Vec<ChainId> {
+    new_config
+        .chains
+        .iter()
+        .filter(|new| !old_config.has_chain(new.id()))
+        .map(|chain_config| chain_config.id().clone())
+        .collect()
+}
+
+/// The identifiers of the chains present in both configurations,
+/// but whose configuration differs between them.
+fn updated_chains(old_config: &Config, new_config: &Config) -> Vec<ChainId> {
+    old_config
+        .chains
+        .iter()
+        .filter(|old| {
+            new_config
+                .find_chain(old.id())
+                .is_some_and(|new| new != *old)
+        })
+        .map(|chain_config| chain_config.id().clone())
+        .collect()
+}
+
+/// The objects of the workers which the given configuration no longer relays for,
+/// ie. whose chains were removed from it, or whose channels it does not allow,
+/// as well as the objects of the workers relaying to or from the updated chains.
+fn stale_objects(config: &Config, updated: &[ChainId], workers: &WorkerMap) -> Vec<Object> {
+    workers
+        .objects()
+        .filter(|object| {
+            let chain_removed = !config.has_chain(object.src_chain_id())
+                || !config.has_chain(object.dst_chain_id());
+
+            let chain_updated =
+                updated.contains(object.src_chain_id()) || updated.contains(object.dst_chain_id());
+
+            let channel_denied = match object {
+                Object::Packet(p) => {
+                    !is_channel_allowed(config, &p.src_chain_id, &p.src_port_id, &p.src_channel_id)
+                }
+                Object::Channel(c) => {
+                    !is_channel_allowed(config, &c.src_chain_id, &c.src_port_id, &c.src_channel_id)
+                }
+                _ => false,
+            };
+
+            chain_removed || chain_updated || channel_denied
+        })
+        .cloned()
+        .collect()
 }
 
 /// Dump the state of the supervisor into a [`SupervisorState`] value,
@@ -753,17 +1304,17 @@ fn handle_rest_cmd<Chain: ChainHandle>(
                 .unwrap_or_else(|e| error!("error replying to a REST request {e}"));
         }
 
-        rest::Command::ClearPackets(chain_id, reply) => {
+        rest::Command::ClearPackets(chain_id, sequences, reply) => {
             if let Some(chain_id) = chain_id {
-                info!("clearing packets for chain {chain_id} after REST request");
+                info!("clearing packets {sequences} for chain {chain_id} after REST request");
 
-                clear_pending_packets(workers, &chain_id)
+                clear_pending_packets(workers, &chain_id, sequences)
                     .unwrap_or_else(|e| error!("error clearing packets for chain {chain_id}: {e}"));
             } else {
                 for chain_id in registry.chains().map(|c| c.id()) {
-                    info!("clearing packets for chain {chain_id} after REST request");
+                    info!("clearing packets {sequences} for chain {chain_id} after REST request");
 
-                    clear_pending_packets(workers, &chain_id).unwrap_or_else(|e| {
+                    clear_pending_packets(workers, &chain_id, sequences).unwrap_or_else(|e| {
                         error!("error clearing packets for chain {chain_id}: {e}")
                     });
                 }
@@ -773,6 +1324,23 @@ fn handle_rest_cmd<Chain: ChainHandle>(
                 .send(Ok(()))
                 .unwrap_or_else(|e| error!("error replying to a REST request {e}"));
         }
+
+        rest::Command::ClearPendingOperationalData(chain_id, reply) => {
+            let chain_ids = match chain_id {
+                Some(chain_id) => vec![chain_id],
+                None => registry.chains().map(|c| c.id()).collect(),
+            };
+
+            for chain_id in chain_ids {
+                info!("clearing pending operational data for chain {chain_id} after REST request");
+
+                clear_pending_operational_data(workers, &chain_id);
+            }
+
+            reply
+                .send(Ok(()))
+                .unwrap_or_else(|e| error!("error replying to a REST request {e}"));
+        }
     }
 }
 
@@ -782,14 +1350,34 @@ fn handle_rest_cmd<Chain: ChainHandle>(
     skip_all,
     fields(chain = %chain_id)
 )]
-fn clear_pending_packets(workers: &WorkerMap, chain_id: &ChainId) -> Result<(), Error> {
+fn clear_pending_packets(
+    workers: &WorkerMap,
+    chain_id: &ChainId,
+    sequences: SequenceRange,
+) -> Result<(), Error> {
     for worker in workers.workers_for_chain(chain_id) {
-        worker.clear_pending_packets();
+        worker.clear_pending_packets(sequences);
     }
 
     Ok(())
 }
 
+/// Instruct the workers relaying packets of the given chain to drop the
+/// operational data they buffered, and to clear the pending packets anew.
+///
+/// The transactions already broadcast by the workers are not recalled.
+#[instrument(
+    name = "supervisor.clear_pending_operational_data",
+    level = "error",
+    skip_all,
+    fields(chain = %chain_id)
+)]
+fn clear_pending_operational_data(workers: &WorkerMap, chain_id: &ChainId) {
+    for worker in workers.workers_for_chain(chain_id) {
+        worker.clear_pending_operational_data();
+    }
+}
+
 /// Process a batch of events received from a chain.
 #[instrument(
     name = "supervisor.process_batch",
@@ -963,10 +1551,10 @@ fn handle_batch<Chain: ChainHandle>(
                 error!("error during batch processing: {}", e);
             }
         }
-        Err(EventError(EventErrorDetail::SubscriptionCancelled(_), _)) => {
-            warn!("event subscription was cancelled, clearing pending packets");
+        Err(EventError(EventErrorDetail::ResyncNeeded(_), _)) => {
+            warn!("events may have been missed while disconnected, clearing pending packets");
 
-            let _ = clear_pending_packets(workers, &chain_id)
+            let _ = clear_pending_packets(workers, &chain_id, SequenceRange::all())
                 .map_err(|e| error!("error during clearing pending packets: {}", e));
         }
         Err(e) => {
@@ -1008,3 +1596,66 @@ impl CollectedEvents {
         self.new_block.is_some()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+    use std::time::Instant;
+
+    use super::{should_scan, ResubscribeBackoff, SupervisorOptions, MIN_RESUBSCRIBE_DELAY};
+    use crate::chain::cosmos::batch::test_fixtures::{cosmos_config_mut, example_config};
+
+    #[test]
+    fn chains_are_scanned_for_misbehaviour_detection_enabled_for_a_single_chain() {
+        let mut config = example_config();
+        config.mode.clients.refresh = false;
+        config.mode.clients.misbehaviour = false;
+        config.mode.packets.clear_on_start = false;
+
+        let options = SupervisorOptions {
+            health_check: false,
+            force_full_scan: false,
+        };
+        assert!(!should_scan(&config, &options));
+
+        cosmos_config_mut(&mut config.chains[1]).detect_misbehaviour = Some(true);
+        assert!(should_scan(&config, &options));
+    }
+
+    #[test]
+    fn failed_respawns_are_retried_with_a_growing_delay() {
+        let start = Instant::now();
+        let mut backoff = ResubscribeBackoff::default();
+        let mut attempts = vec![];
+        let mut delays = vec![];
+
+        // The batch worker ticks every 5 ms for 10 minutes, and the runtime fails to respawn
+        for tick in 0..120_000 {
+            let elapsed = Duration::from_millis(5 * tick);
+
+            if let Some(Err(((), delay))) = backoff.attempt(start + elapsed, || {
+                attempts.push(elapsed.as_secs());
+                Err::<(), ()>(())
+            }) {
+                delays.push(delay.as_secs());
+            }
+        }
+
+        // The delay doubles up to a minute, rather than retrying on every tick
+        assert_eq!(&attempts[..8], [0, 1, 3, 7, 15, 31, 63, 123]);
+        assert_eq!(&delays[..8], [1, 2, 4, 8, 16, 32, 60, 60]);
+        assert_eq!(attempts.len(), 15);
+
+        // No attempt is made before the delay elapsed
+        let now = start + Duration::from_secs(600);
+        assert!(backoff.attempt(now, || Ok::<_, ()>(())).is_none());
+
+        // Once the runtime respawned, a later failure is retried after the initial delay again
+        let now = start + Duration::from_secs(3600);
+        assert_eq!(backoff.attempt(now, || Ok::<_, ()>(())), Some(Ok(())));
+        assert_eq!(
+            backoff.attempt(now, || Err::<(), ()>(())),
+            Some(Err(((), MIN_RESUBSCRIBE_DELAY)))
+        );
+    }
+}
@@ -1,8 +1,14 @@
 use crossbeam_channel::Sender;
 
 use super::dump_state::SupervisorState;
+use crate::config::Config;
+use crate::object::Object;
 
 #[derive(Clone, Debug)]
 pub enum SupervisorCmd {
     DumpState(Sender<SupervisorState>),
+
+    /// Reload the configuration, replying with the objects whose
+    /// workers were stopped because it no longer relays for them.
+    ReloadConfig(Box<Config>, Sender<Vec<Object>>),
 }
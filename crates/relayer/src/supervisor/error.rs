@@ -3,6 +3,7 @@ use flex_error::define_error;
 use ibc_relayer_types::core::ics03_connection::connection::Counterparty;
 use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, ConnectionId, PortId};
 
+use crate::canary::CanaryError;
 use crate::error::Error as RelayerError;
 use crate::spawn::SpawnError;
 use crate::supervisor::scan::Error as ScanError;
@@ -70,6 +71,10 @@ define_error! {
             [ ScanError ]
             |_| { "supervisor encountered an error when scanning chains" },
 
+        Canary
+            [ CanaryError ]
+            |_| { "failed to send canary packet" },
+
         HandleSend
             |_| { "failed to send a command to the supervisor through a channel" },
 
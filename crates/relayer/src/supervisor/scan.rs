@@ -24,10 +24,7 @@ use crate::{
         },
     },
     client_state::IdentifiedAnyClientState,
-    config::{
-        filter::{ChannelFilters, ChannelPolicy},
-        ChainConfig, Config,
-    },
+    config::{filter::ChannelFilters, ChainConfig, Config},
     path::PathIdentifiers,
     registry::Registry,
     supervisor::client_state_filter::{FilterPolicy, Permission},
@@ -604,10 +601,7 @@ impl<'a, Chain: ChainHandle> ChainScanner<'a, Chain> {
             return None;
         }
 
-        match chain_config.packet_filter().channel_policy {
-            ChannelPolicy::Allow(ref filters) if filters.is_exact() => Some(filters),
-            _ => None,
-        }
+        chain_config.packet_filter().exact_allow_list()
     }
 
     fn client_allowed(&mut self, chain: &Chain, client: &IdentifiedAnyClientState) -> bool {
@@ -960,7 +954,7 @@ fn init_telemetry(
             chain_id,
             counterparty_chain_id,
             client,
-            config.mode.clients.misbehaviour && config.mode.clients.enabled
+            config.detects_misbehaviour(chain_id)
         );
     }
 
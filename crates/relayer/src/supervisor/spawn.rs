@@ -8,7 +8,7 @@ use ibc_relayer_types::core::{
 use crate::{
     chain::{counterparty::connection_state_on_destination, handle::ChainHandle},
     client_state::IdentifiedAnyClientState,
-    config::Config,
+    config::{Config, PacketCommitmentSource},
     object::{Channel, Client, Connection, Object, Packet, Wallet},
     registry::Registry,
     supervisor::error::Error as SupervisorError,
@@ -270,18 +270,31 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
             }
 
             if mode.packets.enabled {
+                // The packets sent by a chain whose commitments cannot be listed are
+                // only found by the packet worker, from the events of the chain
+                let lists_commitments = |chain: &Chain| {
+                    self.config.find_chain(&chain.id()).map_or(true, |config| {
+                        config.packet_commitment_source() == PacketCommitmentSource::Store
+                    })
+                };
+
                 let has_packets = || {
-                    !channel_scan
-                        .unreceived_packets_on_counterparty(&chain, &counterparty_chain)
-                        .unwrap_or_default()
-                        .is_empty()
+                    !lists_commitments(&chain)
+                        || !channel_scan
+                            .unreceived_packets_on_counterparty(&chain, &counterparty_chain)
+                            .unwrap_or_default()
+                            .is_empty()
                 };
 
                 let has_acks = || {
-                    !channel_scan
-                        .unreceived_acknowledgements_on_counterparty(&chain, &counterparty_chain)
-                        .unwrap_or_default()
-                        .is_empty()
+                    !lists_commitments(&counterparty_chain)
+                        || !channel_scan
+                            .unreceived_acknowledgements_on_counterparty(
+                                &chain,
+                                &counterparty_chain,
+                            )
+                            .unwrap_or_default()
+                            .is_empty()
                 };
 
                 // If there are any outstanding packets or acks to send, spawn the worker
@@ -17,9 +17,11 @@ use ibc_relayer_types::signer::Signer;
 use ibc_relayer_types::timestamp::{Timestamp, TimestampOverflowError};
 use ibc_relayer_types::tx_msg::Msg;
 
+use crate::chain::cosmos::timeout::encode_timeout_timestamp;
 use crate::chain::endpoint::ChainStatus;
 use crate::chain::handle::ChainHandle;
 use crate::chain::tracking::TrackedMsgs;
+use crate::config::TimestampUnit;
 use crate::error::Error;
 use crate::event::IbcEventWithHeight;
 
@@ -68,6 +70,17 @@ define_error! {
 
         ZeroTimeout
             | _ | { "packet timeout height and packet timeout timestamp cannot both be 0" },
+
+        MemoTooLarge
+            {
+                chain_id: ChainId,
+                size: usize,
+                max_size: usize,
+            }
+            |e| {
+                format!("memo of {} bytes exceeds the maximum of {} bytes accepted by chain {}",
+                    e.size, e.max_size, e.chain_id)
+            },
     }
 }
 
@@ -87,11 +100,15 @@ impl TransferTimeout {
        that the packet do not get expired at the given height or time.
        If both height offset and duration are zero, then the packet will
        never expire.
+
+       The timeout timestamp is encoded in the unit in which the destination
+       chain interprets it.
     */
     pub fn new(
         timeout_height_offset: u64,
         timeout_duration: Duration,
         destination_chain_status: &ChainStatus,
+        timeout_timestamp_unit: TimestampUnit,
     ) -> Result<Self, TransferError> {
         let timeout_height = if timeout_height_offset == 0 {
             TimeoutHeight::no_timeout()
@@ -105,8 +122,10 @@ impl TransferTimeout {
         let timeout_timestamp = if timeout_duration == Duration::ZERO {
             Timestamp::none()
         } else {
-            (destination_chain_status.timestamp + timeout_duration)
-                .map_err(TransferError::timestamp_overflow)?
+            let timestamp = (destination_chain_status.timestamp + timeout_duration)
+                .map_err(TransferError::timestamp_overflow)?;
+
+            encode_timeout_timestamp(timestamp, timeout_timestamp_unit)
         };
 
         Ok(TransferTimeout {
@@ -129,6 +148,21 @@ pub struct TransferOptions {
     pub memo: Option<String>,
 }
 
+/// Checks that the given memo of a transfer to the given destination chain
+/// is not larger than the maximum memo size accepted by that chain, if any.
+pub fn check_memo_size(
+    memo: Option<&str>,
+    max_memo_bytes: Option<usize>,
+    dst_chain_id: &ChainId,
+) -> Result<(), TransferError> {
+    match (memo, max_memo_bytes) {
+        (Some(memo), Some(max_size)) if memo.len() > max_size => Err(
+            TransferError::memo_too_large(dst_chain_id.clone(), memo.len(), max_size),
+        ),
+        _ => Ok(()),
+    }
+}
+
 pub fn build_transfer_message(
     src_port_id: PortId,
     src_channel_id: ChannelId,
@@ -173,10 +207,13 @@ pub fn build_transfer_messages<SrcChain: ChainHandle, DstChain: ChainHandle>(
         .query_application_status()
         .map_err(TransferError::relayer)?;
 
+    let destination_chain_config = dst_chain.config().map_err(TransferError::relayer)?;
+
     let timeout = TransferTimeout::new(
         opts.timeout_height_offset,
         opts.timeout_duration,
         &destination_chain_status,
+        destination_chain_config.timeout_timestamp_unit(),
     )?;
 
     let message = build_transfer_message(
@@ -232,3 +269,31 @@ pub fn build_and_send_transfer_messages<SrcChain: ChainHandle, DstChain: ChainHa
     let msgs = build_transfer_messages(src_chain, dst_chain, opts)?;
     send_messages(src_chain, msgs)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_relayer_types::Height;
+
+    #[test]
+    fn timeout_timestamp_uses_destination_unit() {
+        let status = ChainStatus {
+            height: Height::new(0, 100).unwrap(),
+            timestamp: Timestamp::from_nanoseconds(1_700_000_000_000_000_000).unwrap(),
+        };
+
+        let duration = Duration::from_secs(60);
+
+        let nanos = TransferTimeout::new(0, duration, &status, TimestampUnit::Nanoseconds)
+            .unwrap()
+            .timeout_timestamp;
+
+        let millis = TransferTimeout::new(0, duration, &status, TimestampUnit::Milliseconds)
+            .unwrap()
+            .timeout_timestamp;
+
+        assert_eq!(nanos.nanoseconds(), 1_700_000_060_000_000_000);
+        assert_eq!(millis.nanoseconds(), 1_700_000_060_000);
+    }
+}
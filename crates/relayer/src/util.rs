@@ -2,16 +2,23 @@ mod block_on;
 pub use block_on::{block_on, spawn_blocking};
 
 pub mod collate;
+pub mod command;
 pub mod compat_mode;
 pub mod debug_section;
 pub mod diff;
 pub mod excluded_sequences;
 pub mod iter;
 pub mod lock;
+#[cfg(test)]
+pub mod mock_chain;
+#[cfg(test)]
+pub mod mock_http;
 pub mod pretty;
 pub mod profiling;
 pub mod queue;
+pub mod refresh_cache;
 pub mod retry;
+pub mod semaphore;
 pub mod seq_range;
 pub mod stream;
 pub mod task;
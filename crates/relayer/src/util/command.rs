@@ -0,0 +1,150 @@
+//! Running of the external commands configured for a chain, eg. to obtain the price of
+//! the fee denomination, with a timeout so that a stuck command cannot hold up the relayer.
+
+use std::io::{Read, Write};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use flex_error::define_error;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+define_error! {
+    CommandError {
+        Missing
+            |_| { "no command configured" },
+
+        Io
+            { program: String, reason: String }
+            |e| { format!("failed to run `{}`: {}", e.program, e.reason) },
+
+        Failed
+            { program: String, status: String }
+            |e| { format!("`{}` exited with {}", e.program, e.status) },
+
+        Timeout
+            { program: String, timeout: Duration }
+            |e| { format!("`{}` did not exit within {:?}", e.program, e.timeout) },
+    }
+}
+
+/// Runs the given command, whose first element is the program, with the given arguments
+/// appended to its own and the given bytes written to its standard input, and returns
+/// its standard output. The command is killed if it does not exit within `timeout`.
+///
+/// Blocks the current thread until the command exits, see [`run_command_async`]
+/// to run it from an async task.
+pub fn run_command(
+    command: &[String],
+    args: &[String],
+    stdin: Option<&[u8]>,
+    timeout: Duration,
+) -> Result<Vec<u8>, CommandError> {
+    let (program, command_args) = command.split_first().ok_or_else(CommandError::missing)?;
+    let io_error = |e: std::io::Error| CommandError::io(program.clone(), e.to_string());
+
+    let mut child = Command::new(program)
+        .args(command_args)
+        .args(args)
+        .stdin(if stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(io_error)?;
+
+    // The pipes are written and read from their own threads, so that
+    // a command which does not consume or close them is still timed out
+    if let (Some(input), Some(mut pipe)) = (stdin, child.stdin.take()) {
+        let input = input.to_vec();
+        thread::spawn(move || pipe.write_all(&input));
+    }
+
+    let mut stdout = child.stdout.take().expect("stdout is piped");
+    let output = thread::spawn(move || {
+        let mut output = Vec::new();
+        stdout.read_to_end(&mut output).map(|_| output)
+    });
+
+    let deadline = Instant::now() + timeout;
+
+    let status = loop {
+        match child.try_wait().map_err(io_error)? {
+            Some(status) => break status,
+            None if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+
+                return Err(CommandError::timeout(program.clone(), timeout));
+            }
+            None => thread::sleep(POLL_INTERVAL),
+        }
+    };
+
+    if !status.success() {
+        return Err(CommandError::failed(program.clone(), status.to_string()));
+    }
+
+    output
+        .join()
+        .map_err(|_| CommandError::io(program.clone(), "failed to read the output".to_string()))?
+        .map_err(io_error)
+}
+
+/// Runs the given command as [`run_command`] does, on the blocking threads of
+/// the runtime rather than on the thread of the calling task.
+pub async fn run_command_async(
+    command: Vec<String>,
+    args: Vec<String>,
+    stdin: Option<Vec<u8>>,
+    timeout: Duration,
+) -> Result<Vec<u8>, CommandError> {
+    let program = command.first().cloned().unwrap_or_default();
+
+    tokio::task::spawn_blocking(move || run_command(&command, &args, stdin.as_deref(), timeout))
+        .await
+        .map_err(|e| CommandError::io(program, e.to_string()))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sh(script: &str) -> Vec<String> {
+        ["sh", "-c", script, "sh"].map(String::from).to_vec()
+    }
+
+    #[test]
+    fn command_receives_arguments_and_input() {
+        let output = run_command(
+            &sh(r#"echo "$1"; cat"#),
+            &["uatom".to_string()],
+            Some(b"input".as_slice()),
+            Duration::from_secs(10),
+        )
+        .unwrap();
+
+        assert_eq!(output, b"uatom\ninput");
+    }
+
+    #[test]
+    fn failing_command_is_reported() {
+        let error = run_command(&sh("exit 3"), &[], None, Duration::from_secs(10)).unwrap_err();
+
+        assert!(matches!(error.detail(), CommandErrorDetail::Failed(_)));
+    }
+
+    #[test]
+    fn stuck_command_is_killed_after_timeout() {
+        let start = Instant::now();
+
+        let error =
+            run_command(&sh("sleep 30"), &[], None, Duration::from_millis(200)).unwrap_err();
+
+        assert!(matches!(error.detail(), CommandErrorDetail::Timeout(_)));
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+}
@@ -0,0 +1,30 @@
+//! A chain for the tests, standing in for the runtime of a chain,
+//! which answers the requests made through its handle as computed by the test.
+
+use std::thread;
+
+use crossbeam_channel as channel;
+use tracing::Span;
+
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use crate::chain::handle::{BaseChainHandle, ChainRequest};
+
+/// Spawns a runtime which hands each request made through the returned handle over to
+/// `respond`, until the handle and its clones are dropped. The test answers a request by
+/// sending the reply to its `reply_to` channel, and panics on the requests it does not
+/// expect, which fails the call made through the handle.
+pub fn spawn_mock_chain(
+    chain_id: ChainId,
+    respond: impl Fn(ChainRequest) + Send + 'static,
+) -> BaseChainHandle {
+    let (sender, receiver) = channel::unbounded::<(Span, ChainRequest)>();
+
+    thread::spawn(move || {
+        for (_span, request) in receiver {
+            respond(request);
+        }
+    });
+
+    BaseChainHandle::new(chain_id, sender)
+}
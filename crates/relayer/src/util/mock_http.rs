@@ -0,0 +1,152 @@
+//! A minimal HTTP server for the tests, standing in for a node or a load balancer,
+//! which answers every request with the response computed by the test.
+
+use core::convert::Infallible;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener};
+use std::sync::mpsc;
+use std::thread;
+
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::body::BoxBody;
+use tonic::codegen::{http, Service};
+use tonic::server::NamedService;
+use tonic::transport::Server;
+
+/// A request received by the mock server.
+#[derive(Clone, Debug)]
+pub struct MockRequest {
+    /// The request line, eg. `GET /health HTTP/1.1`
+    pub line: String,
+
+    /// The headers of the request, with their names in lowercase
+    pub headers: Vec<(String, String)>,
+
+    pub body: Vec<u8>,
+}
+
+impl MockRequest {
+    /// Returns the value of the given header, whose name is in lowercase.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// A response of the mock server, always with status `200 OK`.
+#[derive(Clone, Debug, Default)]
+pub struct MockResponse {
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl MockResponse {
+    pub fn json(body: impl Into<String>) -> Self {
+        Self {
+            headers: vec![],
+            body: body.into(),
+        }
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+/// Spawns a server which answers each request with the response given by `respond`
+/// and then forwards the request, until the test exits.
+pub fn spawn_mock_http_server(
+    respond: impl Fn(&MockRequest) -> MockResponse + Send + 'static,
+) -> (SocketAddr, mpsc::Receiver<MockRequest>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let request = read_request(&mut stream);
+            let response = respond(&request);
+
+            let mut head = "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n".to_string();
+            for (name, value) in &response.headers {
+                head.push_str(&format!("{name}: {value}\r\n"));
+            }
+
+            write!(
+                stream,
+                "{head}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                response.body.len(),
+                response.body
+            )
+            .unwrap();
+
+            // The test may have stopped listening for the requests
+            let _ = sender.send(request);
+        }
+    });
+
+    (address, receiver)
+}
+
+/// Spawns a server for the given gRPC service of a node until the test exits.
+///
+/// gRPC needs HTTP/2, which the server above does not speak.
+pub async fn spawn_mock_grpc_server<S>(service: S) -> SocketAddr
+where
+    S: Service<http::Request<BoxBody>, Response = http::Response<BoxBody>, Error = Infallible>
+        + NamedService
+        + Clone
+        + Send
+        + 'static,
+    S::Future: Send + 'static,
+{
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+
+    tokio::spawn(
+        Server::builder()
+            .add_service(service)
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+
+    address
+}
+
+fn read_request(stream: &mut impl Read) -> MockRequest {
+    let mut reader = BufReader::new(stream);
+
+    let mut line = String::new();
+    reader.read_line(&mut line).unwrap();
+
+    let mut headers = Vec::new();
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header).unwrap();
+
+        match header.trim_end().split_once(':') {
+            Some((name, value)) => {
+                headers.push((name.trim().to_lowercase(), value.trim().to_string()))
+            }
+            None => break,
+        }
+    }
+
+    let mut request = MockRequest {
+        line: line.trim_end().to_string(),
+        headers,
+        body: Vec::new(),
+    };
+
+    let content_length = request
+        .header("content-length")
+        .map_or(0, |length| length.parse().unwrap());
+
+    request.body = vec![0; content_length];
+    reader.read_exact(&mut request.body).unwrap();
+
+    request
+}
@@ -0,0 +1,91 @@
+//! A cache of values obtained from a source which may fail, eg. the price of a denomination
+//! from a price feed, each of which is obtained anew once it is older than a refresh interval.
+
+use core::future::Future;
+use core::hash::Hash;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+pub struct RefreshCache<K, V> {
+    refresh_interval: Duration,
+    values: Mutex<HashMap<K, (V, Instant)>>,
+}
+
+impl<K, V> RefreshCache<K, V>
+where
+    K: Eq + Hash,
+    V: Clone,
+{
+    pub fn new(refresh_interval: Duration) -> Self {
+        Self {
+            refresh_interval,
+            values: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the value of the given key, obtained anew with `refresh` if the last value
+    /// obtained is older than the refresh interval. When `refresh` fails, the last known
+    /// value is returned, or `None` if no value was obtained before.
+    pub async fn get<E>(&self, key: K, refresh: impl Future<Output = Result<V, E>>) -> Option<V> {
+        let last = self
+            .values
+            .lock()
+            .expect("poisoned lock")
+            .get(&key)
+            .cloned();
+
+        if let Some((value, fetched_at)) = &last {
+            if fetched_at.elapsed() < self.refresh_interval {
+                return Some(value.clone());
+            }
+        }
+
+        match refresh.await {
+            Ok(value) => {
+                self.values
+                    .lock()
+                    .expect("poisoned lock")
+                    .insert(key, (value.clone(), Instant::now()));
+
+                Some(value)
+            }
+            Err(_) => last.map(|(value, _)| value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn last_known_value_is_used_when_refresh_fails() {
+        let cache = RefreshCache::new(Duration::ZERO);
+
+        assert_eq!(cache.get("uatom", async { Err::<f64, _>(()) }).await, None);
+        assert_eq!(
+            cache.get("uatom", async { Ok::<_, ()>(2.0) }).await,
+            Some(2.0)
+        );
+        assert_eq!(cache.get("uatom", async { Err(()) }).await, Some(2.0));
+
+        // The values are cached per key
+        assert_eq!(cache.get("uosmo", async { Err(()) }).await, None);
+    }
+
+    #[tokio::test]
+    async fn value_is_refreshed_once_per_refresh_interval() {
+        let cache = RefreshCache::new(Duration::from_secs(3600));
+
+        assert_eq!(
+            cache.get("uatom", async { Ok::<_, ()>(2.0) }).await,
+            Some(2.0)
+        );
+        assert_eq!(
+            cache.get("uatom", async { Ok::<_, ()>(4.0) }).await,
+            Some(2.0)
+        );
+    }
+}
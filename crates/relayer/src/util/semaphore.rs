@@ -0,0 +1,90 @@
+use std::num::NonZeroUsize;
+use std::panic;
+use std::sync::{Condvar, Mutex};
+use std::thread;
+
+/// A counting semaphore, limiting the number of threads which hold
+/// one of its permits at once.
+#[derive(Debug)]
+pub struct Semaphore {
+    permits: usize,
+    available: Mutex<usize>,
+    released: Condvar,
+}
+
+impl Semaphore {
+    pub fn new(permits: usize) -> Self {
+        Self {
+            permits,
+            available: Mutex::new(permits),
+            released: Condvar::new(),
+        }
+    }
+
+    /// The total number of permits of the semaphore.
+    pub fn permits(&self) -> usize {
+        self.permits
+    }
+
+    /// Blocks until a permit is available, and returns it. The permit
+    /// is released when the returned guard is dropped.
+    pub fn acquire(&self) -> SemaphorePermit<'_> {
+        let available = self.available.lock().expect("poisoned lock");
+
+        let mut available = self
+            .released
+            .wait_while(available, |available| *available == 0)
+            .expect("poisoned lock");
+
+        *available -= 1;
+
+        SemaphorePermit { semaphore: self }
+    }
+
+    fn release(&self) {
+        *self.available.lock().expect("poisoned lock") += 1;
+        self.released.notify_one();
+    }
+}
+
+/// A permit of a [`Semaphore`], released when dropped.
+#[derive(Debug)]
+pub struct SemaphorePermit<'a> {
+    semaphore: &'a Semaphore,
+}
+
+impl Drop for SemaphorePermit<'_> {
+    fn drop(&mut self) {
+        self.semaphore.release();
+    }
+}
+
+/// Applies `f` to each of the given items on its own thread, with at most
+/// `max_parallel` of the calls running at once, and returns the results in
+/// the order of the items. A panic of any of the calls is resumed.
+pub fn map_bounded<T, R, F>(items: Vec<T>, max_parallel: NonZeroUsize, f: F) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Sync,
+{
+    let semaphore = Semaphore::new(max_parallel.get());
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = items
+            .into_iter()
+            .map(|item| {
+                let (semaphore, f) = (&semaphore, &f);
+                scope.spawn(move || {
+                    let _permit = semaphore.acquire();
+                    f(item)
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+            .collect()
+    })
+}
@@ -1,15 +1,18 @@
 use alloc::sync::Arc;
 use core::fmt::{Display, Error as FmtError, Formatter};
+use crossbeam_channel::Sender;
 use ibc_relayer_types::core::ics04_channel::channel::Ordering;
 use serde::{Deserialize, Serialize};
 use std::sync::Mutex;
-use tracing::error;
+use tracing::{error, warn};
 
-use crate::foreign_client::ForeignClient;
+use crate::foreign_client::{pending_refresh::PendingRefreshes, ForeignClient};
 use crate::link::{Link, LinkParameters, Resubmit};
+use crate::util::semaphore::Semaphore;
 use crate::{
     chain::handle::{ChainHandle, ChainHandlePair},
     config::Config,
+    event::source::EventBatch,
     object::Object,
 };
 
@@ -59,22 +62,31 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
     id: WorkerId,
     object: Object,
     config: &Config,
+    sent_packets_tx: Option<Sender<EventBatch>>,
+    client_update_limit: Option<Arc<Semaphore>>,
+    pending_refreshes: Arc<PendingRefreshes>,
 ) -> WorkerHandle {
     let mut task_handles = Vec::new();
 
     let (cmd_tx, data) = match &object {
         Object::Client(client) => {
+            let detect_misbehaviour = config.detects_misbehaviour(&client.dst_chain_id);
             let client = ForeignClient::restore(client.dst_client_id.clone(), chains.b, chains.a);
 
             let (mut refresh, mut misbehaviour) = (false, false);
 
-            let refresh_task = client::spawn_refresh_client(client.clone());
+            let refresh_task = client::spawn_refresh_client(
+                client.clone(),
+                config.mode.clients.smooth_refresh,
+                client_update_limit,
+                pending_refreshes,
+            );
             if let Some(refresh_task) = refresh_task {
                 task_handles.push(refresh_task);
                 refresh = true;
             }
 
-            let cmd_tx = if config.mode.clients.misbehaviour {
+            let cmd_tx = if detect_misbehaviour {
                 let (cmd_tx, cmd_rx) = crossbeam_channel::unbounded();
                 let misbehavior_task = client::detect_misbehavior_task(cmd_rx, client);
                 if let Some(task) = misbehavior_task {
@@ -104,7 +116,12 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
         }
         Object::Channel(channel) => {
             let (cmd_tx, cmd_rx) = crossbeam_channel::unbounded();
-            let channel_task = channel::spawn_channel_worker(channel.clone(), chains, cmd_rx);
+            let channel_task = channel::spawn_channel_worker(
+                channel.clone(),
+                chains,
+                cmd_rx,
+                config.mode.packets.auto_register_counterparty_payee,
+            );
             task_handles.push(channel_task);
 
             (Some(cmd_tx), None)
@@ -126,13 +143,31 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
                     max_memo_size: packets_config.ics20_max_memo_size,
                     max_receiver_size: packets_config.ics20_max_receiver_size,
                     exclude_src_sequences,
+                    max_clear_duration: packets_config.max_clear_duration,
+                    verification: packets_config.channel_verification(),
+                    clear_order: packets_config.clear_order,
                 },
                 packets_config.tx_confirmation,
                 packets_config.auto_register_counterparty_payee,
             );
 
             match link_res {
-                Ok(link) => {
+                Ok(mut link) => {
+                    if let Some(tx) = sent_packets_tx {
+                        link.a_to_b.forward_sent_packets(tx);
+                    }
+
+                    link.a_to_b.wait_for_pending_refreshes(pending_refreshes);
+
+                    if packets_config.recovery_lookback > 0 {
+                        if let Err(e) = link
+                            .a_to_b
+                            .recover_relayed_packets(packets_config.recovery_lookback)
+                        {
+                            warn!("failed to recover the packets relayed before starting: {e}");
+                        }
+                    }
+
                     let channel_ordering = link.a_to_b.channel().ordering;
                     let should_clear_on_start =
                         should_clear_on_start(&packets_config, channel_ordering);
@@ -199,7 +234,13 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
                     };
                     task_handles.push(packet_task);
 
-                    let link_task = packet::spawn_packet_worker(path.clone(), link, resubmit);
+                    let link_task = packet::spawn_packet_worker(
+                        path.clone(),
+                        link,
+                        resubmit,
+                        packets_config.compaction_interval,
+                        packets_config.compaction_limit,
+                    );
                     task_handles.push(link_task);
 
                     (Some(cmd_tx), None)
@@ -1,7 +1,8 @@
 use core::time::Duration;
 use crossbeam_channel::Receiver;
+use ibc_relayer_types::core::ics04_channel::channel::{State, UpgradeState};
 use ibc_relayer_types::events::IbcEventType;
-use tracing::{debug, error_span, warn};
+use tracing::{debug, error_span, info, warn};
 
 use crate::chain::requests::QueryHeight;
 use crate::channel::{channel_handshake_retry, Channel as RelayChannel};
@@ -34,15 +35,21 @@ pub fn spawn_channel_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
     channel: Channel,
     chains: ChainHandlePair<ChainA, ChainB>,
     cmd_rx: Receiver<WorkerCmd>,
+    auto_register_counterparty_payee: bool,
 ) -> TaskHandle {
     let mut complete_handshake_on_new_block = true;
 
+    // Whether an upgrade handshake of the channel is in progress, in which case
+    // its upgrade timeout is checked on every new block, to time out the upgrade
+    // if it stalls until then
+    let mut upgrade_in_progress = false;
+
     spawn_background_task(
         error_span!("worker.channel", channel = %channel.short_name()),
         Some(Duration::from_millis(200)),
         move || {
             let max_block_times = max_block_times(&chains);
-            if let Ok(cmd) = cmd_rx.try_recv() {
+            let next = if let Ok(cmd) = cmd_rx.try_recv() {
                 match cmd {
                     WorkerCmd::IbcEvents { batch } => {
                         // there can be up to two event for this channel, e.g. init and try.
@@ -53,6 +60,14 @@ pub fn spawn_channel_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
                         complete_handshake_on_new_block = false;
 
                         if let Some(event_with_height) = last_event {
+                            upgrade_in_progress = matches!(
+                                event_with_height.event.event_type(),
+                                IbcEventType::UpgradeInitChannel
+                                    | IbcEventType::UpgradeTryChannel
+                                    | IbcEventType::UpgradeAckChannel
+                                    | IbcEventType::UpgradeConfirmChannel
+                            );
+
                             match event_with_height.event.event_type() {
                                 IbcEventType::UpgradeInitChannel
                                 | IbcEventType::UpgradeTryChannel
@@ -135,6 +150,13 @@ pub fn spawn_channel_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
                                 QueryHeight::Latest,
                             ) {
                                 Ok((mut handshake_channel, state)) => {
+                                    upgrade_in_progress = matches!(
+                                        state,
+                                        State::Open(UpgradeState::Upgrading)
+                                            | State::Flushing
+                                            | State::FlushComplete
+                                    );
+
                                     handshake_channel.step_state(state, index)
                                 }
                                 Err(_) => RetryResult::Retry(index),
@@ -143,12 +165,68 @@ pub fn spawn_channel_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
                         .map_err(|e| TaskError::Fatal(RunError::retry(e)))
                     }
 
+                    WorkerCmd::NewBlock { .. } if upgrade_in_progress => {
+                        match RelayChannel::restore_from_state(
+                            chains.a.clone(),
+                            chains.b.clone(),
+                            channel.clone(),
+                            QueryHeight::Latest,
+                        ) {
+                            Ok((handshake_channel, state)) => {
+                                match handshake_channel.counterparty_state().and_then(
+                                    |counterparty_state| {
+                                        handshake_channel
+                                            .timeout_stalled_upgrade(state, counterparty_state)
+                                    },
+                                ) {
+                                    Ok(Some(event)) => {
+                                        info!("timed out stalled channel upgrade: {event}")
+                                    }
+                                    Ok(None) => {}
+                                    Err(e) => warn!("failed to check channel upgrade timeout: {e}"),
+                                }
+                            }
+                            Err(e) => warn!("failed to check channel upgrade timeout: {e}"),
+                        }
+
+                        Ok(Next::Continue)
+                    }
+
                     // nothing to do
                     _ => Ok(Next::Continue),
                 }
             } else {
                 Ok(Next::Continue)
+            };
+
+            // The handshake of the channel is over, register the counterparty payees
+            // before the worker exits if the channel turns out to be fee-enabled
+            if auto_register_counterparty_payee && matches!(next, Ok(Next::Abort)) {
+                register_counterparty_payees(&chains, &channel);
             }
+
+            next
         },
     )
 }
+
+fn register_counterparty_payees<ChainA: ChainHandle, ChainB: ChainHandle>(
+    chains: &ChainHandlePair<ChainA, ChainB>,
+    channel: &Channel,
+) {
+    let registered = RelayChannel::restore_from_state(
+        chains.a.clone(),
+        chains.b.clone(),
+        channel.clone(),
+        QueryHeight::Latest,
+    )
+    .and_then(|(handshake_channel, _)| handshake_channel.maybe_register_counterparty_payees());
+
+    match registered {
+        Ok(true) => info!("registered counterparty payees of fee-enabled channel"),
+        Ok(false) => debug!("channel is not fee-enabled, not registering counterparty payees"),
+
+        // The packet workers of the channel register the payees again when they start
+        Err(e) => warn!("failed to register counterparty payees: {e}"),
+    }
+}
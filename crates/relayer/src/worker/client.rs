@@ -1,14 +1,20 @@
 use core::convert::Infallible;
+use core::hash::{Hash, Hasher};
 use core::time::Duration;
 use crossbeam_channel::Receiver;
 use retry::delay::Fibonacci;
 use retry::retry_with_index;
+use std::collections::hash_map::DefaultHasher;
+use std::sync::Arc;
 use tracing::{debug, debug_span, error_span, trace, warn};
 
 use ibc_relayer_types::core::ics02_client::events::UpdateClient;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId};
 use ibc_relayer_types::events::IbcEvent;
 
+use crate::foreign_client::pending_refresh::PendingRefreshes;
 use crate::util::retry::clamp_total;
+use crate::util::semaphore::Semaphore;
 use crate::util::task::{spawn_background_task, Next, TaskError, TaskHandle};
 use crate::{
     chain::handle::ChainHandle,
@@ -22,8 +28,20 @@ const INITIAL_BACKOFF: Duration = Duration::from_secs(5); // 5 seconds
 const MAX_REFRESH_DELAY: Duration = Duration::from_secs(60 * 60); // 1 hour
 const MAX_REFRESH_TOTAL_DELAY: Duration = Duration::from_secs(60 * 60 * 24); // 1 day
 
+/// Spawns the task refreshing the given client. With `smooth_refresh`, the client is
+/// refreshed at a point of its refresh period specific to it, see [`smoothed_refresh_period`].
+/// This only applies to the refreshes of the client, not to the updates needed to relay packets.
+///
+/// With an `update_limit`, shared by the refreshes of the clients hosted on the same chain,
+/// the client updates are only submitted while holding one of its permits.
+///
+/// The refreshes are marked as in progress in `pending_refreshes` while they are submitted,
+/// for the packet workers relaying over the client to wait for them to complete.
 pub fn spawn_refresh_client<ChainA: ChainHandle, ChainB: ChainHandle>(
     mut client: ForeignClient<ChainA, ChainB>,
+    smooth_refresh: bool,
+    update_limit: Option<Arc<Semaphore>>,
+    pending_refreshes: Arc<PendingRefreshes>,
 ) -> Option<TaskHandle> {
     if client.is_expired_or_frozen() {
         warn!(
@@ -45,7 +63,27 @@ pub fn spawn_refresh_client<ChainA: ChainHandle, ChainB: ChainHandle>(
         move || {
             // Try to refresh the client, but only if the refresh window has expired.
             // If the refresh fails, retry according to the given strategy.
-            let res = retry_with_index(refresh_strategy(), |_| client.refresh());
+            let res = retry_with_index(refresh_strategy(), |_| {
+                let update_limit = update_limit.as_deref();
+
+                if smooth_refresh {
+                    let (client_id, host_chain_id) = (client.id.clone(), client.dst_chain.id());
+
+                    client.refresh_with(
+                        |refresh_period| {
+                            smoothed_refresh_period(&host_chain_id, &client_id, refresh_period)
+                        },
+                        update_limit,
+                        Some(&pending_refreshes),
+                    )
+                } else {
+                    client.refresh_with(
+                        |refresh_period| refresh_period,
+                        update_limit,
+                        Some(&pending_refreshes),
+                    )
+                }
+            });
 
             match res {
                 // If `client.refresh()` was successful, continue
@@ -158,6 +196,45 @@ fn on_client_update<ChainA: ChainHandle, ChainB: ChainHandle>(
     }
 }
 
+/// Returns the time elapsed since the last update of a client after which the client
+/// is refreshed when refreshes are smoothed, somewhere in the last quarter of its
+/// `refresh_period`, so that it is never refreshed later than without smoothing.
+///
+/// Refreshing earlier costs fees, so the window is kept small: a client is refreshed
+/// at most a third more often than without smoothing.
+///
+/// The point within that window depends on the client, with the clients numbered
+/// consecutively on a chain spread evenly over it, so that clients updated around
+/// the same time, e.g. when they were created together, are not refreshed together.
+pub fn smoothed_refresh_period(
+    host_chain_id: &ChainId,
+    client_id: &ClientId,
+    refresh_period: Duration,
+) -> Duration {
+    // Inverse of the golden ratio, whose multiples modulo 1 are evenly spread
+    const SPREAD_FACTOR: f64 = 0.618_033_988_749_895;
+
+    let mut hasher = DefaultHasher::new();
+    host_chain_id.hash(&mut hasher);
+
+    let client_number = client_id
+        .as_str()
+        .rsplit('-')
+        .next()
+        .and_then(|number| number.parse::<u64>().ok())
+        .unwrap_or_else(|| {
+            client_id.hash(&mut hasher);
+            hasher.finish()
+        });
+
+    let chain_offset = (hasher.finish() % 1000) as f64 / 1000.0;
+    let position = (chain_offset + client_number as f64 * SPREAD_FACTOR).fract();
+
+    let window = refresh_period / 4;
+
+    refresh_period - window + window.mul_f64(position)
+}
+
 fn refresh_strategy() -> impl Iterator<Item = Duration> {
     clamp_total(
         Fibonacci::from(INITIAL_BACKOFF),
@@ -165,3 +242,51 @@ fn refresh_strategy() -> impl Iterator<Item = Duration> {
         MAX_REFRESH_TOTAL_DELAY,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_relayer_types::core::ics02_client::client_type::ClientType;
+
+    #[test]
+    fn refreshes_of_clients_due_together_are_spread_out() {
+        let chain_id = ChainId::from_string("ibc-0");
+        let refresh_period = Duration::from_secs(3 * 60 * 60);
+
+        // Clients created together, and so due for a refresh at the same time
+        let mut periods: Vec<Duration> = (0..10)
+            .map(|n| {
+                let client_id = ClientId::new(ClientType::Tendermint, n).unwrap();
+                smoothed_refresh_period(&chain_id, &client_id, refresh_period)
+            })
+            .collect();
+
+        // Clients are never refreshed later than without smoothing, and never earlier
+        // than the last quarter of their refresh period, which bounds the extra fees
+        let window = refresh_period / 4;
+
+        assert!(periods
+            .iter()
+            .all(|period| *period >= refresh_period - window && *period <= refresh_period));
+
+        periods.sort();
+
+        // The refreshes are spread over the window, without two of them close together
+        let min_gap = periods
+            .windows(2)
+            .map(|pair| pair[1] - pair[0])
+            .min()
+            .unwrap();
+
+        assert!(min_gap >= window / 20, "{min_gap:?}");
+        assert!(periods[9] - periods[0] >= window * 3 / 4);
+
+        // The refresh period of a client is stable across refreshes
+        let client_id = ClientId::new(ClientType::Tendermint, 3).unwrap();
+        assert_eq!(
+            smoothed_refresh_period(&chain_id, &client_id, refresh_period),
+            smoothed_refresh_period(&chain_id, &client_id, refresh_period)
+        );
+    }
+}
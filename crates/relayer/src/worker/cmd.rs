@@ -3,6 +3,7 @@ use core::fmt::{Display, Error as FmtError, Formatter};
 use ibc_relayer_types::{core::ics02_client::events::NewBlock, Height};
 
 use crate::event::source::EventBatch;
+use crate::link::SequenceRange;
 
 /// A command for a [`WorkerHandle`](crate::worker::WorkerHandle).
 #[derive(Debug, Clone)]
@@ -13,8 +14,11 @@ pub enum WorkerCmd {
     /// A new block has been committed
     NewBlock { height: Height, new_block: NewBlock },
 
-    /// Trigger a pending packets clear
-    ClearPendingPackets,
+    /// Trigger a clear of the pending packets whose sequence is within `sequences`
+    ClearPendingPackets { sequences: SequenceRange },
+
+    /// Drop the buffered operational data and trigger a pending packets clear
+    ClearPendingOperationalData,
 }
 
 impl WorkerCmd {
@@ -39,7 +43,15 @@ impl WorkerCmd {
     /// [`ClearPendingPackets`]: WorkerCmd::ClearPendingPackets
     #[must_use]
     pub fn is_clear_pending_packets(&self) -> bool {
-        matches!(self, Self::ClearPendingPackets)
+        matches!(self, Self::ClearPendingPackets { .. })
+    }
+
+    /// Returns `true` if the worker cmd is [`ClearPendingOperationalData`].
+    ///
+    /// [`ClearPendingOperationalData`]: WorkerCmd::ClearPendingOperationalData
+    #[must_use]
+    pub fn is_clear_pending_operational_data(&self) -> bool {
+        matches!(self, Self::ClearPendingOperationalData)
     }
 }
 
@@ -56,7 +68,10 @@ impl Display for WorkerCmd {
             WorkerCmd::NewBlock { height, new_block } => {
                 write!(f, "NewBlock({height}, {new_block})")
             }
-            WorkerCmd::ClearPendingPackets => write!(f, "ClearPendingPackets"),
+            WorkerCmd::ClearPendingPackets { sequences } => {
+                write!(f, "ClearPendingPackets({sequences})")
+            }
+            WorkerCmd::ClearPendingOperationalData => write!(f, "ClearPendingOperationalData"),
         }
     }
 }
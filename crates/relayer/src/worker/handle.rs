@@ -13,6 +13,7 @@ use ibc_relayer_types::{
 
 use crate::chain::tracking::TrackingId;
 use crate::event::IbcEventWithHeight;
+use crate::link::SequenceRange;
 use crate::util::lock::{LockExt, RwArc};
 use crate::util::task::TaskHandle;
 use crate::{event::source::EventBatch, object::Object};
@@ -86,9 +87,15 @@ impl WorkerHandle {
         self.try_send_command(WorkerCmd::NewBlock { height, new_block });
     }
 
-    /// Instruct the worker to clear pending packets.
-    pub fn clear_pending_packets(&self) {
-        self.try_send_command(WorkerCmd::ClearPendingPackets);
+    /// Instruct the worker to clear the pending packets whose sequence is within `sequences`.
+    pub fn clear_pending_packets(&self, sequences: SequenceRange) {
+        self.try_send_command(WorkerCmd::ClearPendingPackets { sequences });
+    }
+
+    /// Instruct the worker to drop its buffered operational data
+    /// and clear pending packets.
+    pub fn clear_pending_operational_data(&self) {
+        self.try_send_command(WorkerCmd::ClearPendingOperationalData);
     }
 
     /// Shutdown all worker tasks without waiting for them to terminate.
@@ -1,6 +1,8 @@
 use alloc::collections::btree_map::BTreeMap as HashMap;
+use alloc::sync::Arc;
 use core::mem;
 
+use crossbeam_channel::Sender;
 use ibc_relayer_types::core::ics02_client::events::NewBlock;
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use ibc_relayer_types::Height;
@@ -9,8 +11,11 @@ use tracing::{debug, trace};
 use crate::{
     chain::handle::{ChainHandle, ChainHandlePair},
     config::Config,
+    event::source::EventBatch,
+    foreign_client::pending_refresh::PendingRefreshes,
     object::Object,
     telemetry,
+    util::semaphore::Semaphore,
 };
 
 use super::{spawn_worker_tasks, WorkerHandle, WorkerId};
@@ -20,6 +25,13 @@ use super::{spawn_worker_tasks, WorkerHandle, WorkerId};
 pub struct WorkerMap {
     workers: HashMap<Object, WorkerHandle>,
     latest_worker_id: WorkerId,
+    sent_packets_tx: Option<Sender<EventBatch>>,
+    /// The limits on the number of client updates submitted at once to each chain
+    /// with `max_concurrent_client_updates`, shared by its client workers.
+    client_update_limits: HashMap<ChainId, Arc<Semaphore>>,
+    /// The refreshes in progress of the clients refreshed by the client workers,
+    /// shared with the packet workers relaying over these clients.
+    pending_refreshes: Arc<PendingRefreshes>,
 }
 
 impl Default for WorkerMap {
@@ -27,6 +39,9 @@ impl Default for WorkerMap {
         Self {
             workers: HashMap::new(),
             latest_worker_id: WorkerId::new(0),
+            sent_packets_tx: None,
+            client_update_limits: HashMap::new(),
+            pending_refreshes: Arc::default(),
         }
     }
 }
@@ -39,6 +54,12 @@ impl WorkerMap {
         Self::default()
     }
 
+    /// Have the packet workers spawned from now on hand over the packets sent
+    /// by their confirmed transactions to the given channel.
+    pub fn forward_sent_packets(&mut self, tx: Sender<EventBatch>) {
+        self.sent_packets_tx = Some(tx);
+    }
+
     /// Returns `true` if there is a spawned [`WorkerHandle`] associated with the given [`Object`].
     pub fn contains(&self, object: &Object) -> bool {
         self.workers.contains_key(object)
@@ -171,14 +192,53 @@ impl WorkerMap {
     ) -> WorkerHandle {
         telemetry!(worker, metric_type(object), 1);
 
+        let client_update_limit = match object {
+            Object::Client(client) => self.client_update_limit(&client.dst_chain_id, config),
+            _ => None,
+        };
+
         spawn_worker_tasks(
             ChainHandlePair { a: src, b: dst },
             self.next_worker_id(),
             object.clone(),
             config,
+            self.sent_packets_tx.clone(),
+            client_update_limit,
+            self.pending_refreshes.clone(),
         )
     }
 
+    /// The limit on the number of client updates submitted at once to the given chain,
+    /// following its `max_concurrent_client_updates` in the given configuration.
+    ///
+    /// The client workers of the chain spawned since the limit last changed share it,
+    /// while the workers spawned before a change keep the previous limit until they
+    /// are restarted, as happens when the configuration of the chain is reloaded.
+    fn client_update_limit(
+        &mut self,
+        chain_id: &ChainId,
+        config: &Config,
+    ) -> Option<Arc<Semaphore>> {
+        let Some(max) = config
+            .find_chain(chain_id)
+            .and_then(|chain_config| chain_config.max_concurrent_client_updates())
+        else {
+            self.client_update_limits.remove(chain_id);
+            return None;
+        };
+
+        let limit = self
+            .client_update_limits
+            .entry(chain_id.clone())
+            .or_insert_with(|| Arc::new(Semaphore::new(max.get())));
+
+        if limit.permits() != max.get() {
+            *limit = Arc::new(Semaphore::new(max.get()));
+        }
+
+        Some(limit.clone())
+    }
+
     /// Compute the next worker id
     fn next_worker_id(&mut self) -> WorkerId {
         let id = self.latest_worker_id.next();
@@ -204,6 +264,11 @@ impl WorkerMap {
             .collect()
     }
 
+    /// Return all the objects for which there is an associated worker.
+    pub fn objects(&self) -> impl Iterator<Item = &Object> {
+        self.workers.keys()
+    }
+
     /// Return all the handles to the workers tracked in this map.
     pub fn handles(&self) -> impl Iterator<Item = &WorkerHandle> {
         self.workers.values()
@@ -1,6 +1,7 @@
 use core::time::Duration;
 use std::borrow::BorrowMut;
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
 use crossbeam_channel::{Receiver, Sender};
 use itertools::Itertools;
@@ -26,7 +27,10 @@ use crate::event::source::EventBatch;
 use crate::event::IbcEventWithHeight;
 use crate::foreign_client::HasExpiredOrFrozenError;
 use crate::link::Resubmit;
-use crate::link::{error::LinkError, Link};
+use crate::link::{
+    error::{LinkError, LinkErrorDetail},
+    Link, SequenceRange,
+};
 use crate::object::Packet;
 use crate::telemetry;
 use crate::util::lock::{LockExt, RwArc};
@@ -47,6 +51,9 @@ fn handle_link_error_in_task(e: LinkError) -> TaskError<RunError> {
         // If the client is expired or frozen, terminate the packet worker
         // as there is no point of relaying further packets.
         TaskError::Fatal(RunError::link(e))
+    } else if matches!(e.detail(), LinkErrorDetail::ClientChainIdMismatch(_)) {
+        // The packets would be relayed to the wrong chain
+        TaskError::Fatal(RunError::link(e))
     } else {
         TaskError::Ignore(RunError::link(e))
     }
@@ -59,6 +66,8 @@ pub fn spawn_packet_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
     // Mutex is used to prevent race condition between the packet workers
     link: Arc<Mutex<Link<ChainA, ChainB>>>,
     resubmit: Resubmit,
+    compaction_interval: Option<Duration>,
+    compaction_limit: usize,
 ) -> TaskHandle {
     let span = {
         let relay_path = &link.lock().unwrap().a_to_b;
@@ -71,8 +80,21 @@ pub fn spawn_packet_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
         )
     };
 
+    let mut last_compaction = Instant::now();
+
     spawn_background_task(span, Some(Duration::from_millis(1000)), move || {
-        handle_execute_schedule(&mut link.lock().unwrap(), &path, resubmit)?;
+        let mut link = link.lock().unwrap();
+
+        handle_execute_schedule(&mut link, &path, resubmit)?;
+
+        if compaction_interval.is_some_and(|interval| last_compaction.elapsed() >= interval) {
+            if let Err(e) = link.a_to_b.compact_operational_data(compaction_limit) {
+                warn!("failed to compact the scheduled operational data: {e}");
+            }
+
+            last_compaction = Instant::now();
+        }
+
         Ok(Next::Continue)
     })
 }
@@ -304,6 +326,12 @@ fn handle_packet_cmd<ChainA: ChainHandle, ChainB: ChainHandle>(
 ///
 /// Given a `NewBlock` command, checks if packet clearing should occur
 /// and performs it if so.
+///
+/// Given a `ClearPendingPackets` command, clears the pending packets
+/// whose sequence is within the range of the command.
+///
+/// Given a `ClearPendingOperationalData` command, drops the buffered
+/// operational data before clearing packets.
 fn handle_clear_cmd<ChainA: ChainHandle, ChainB: ChainHandle>(
     link: &mut Link<ChainA, ChainB>,
     should_clear_on_start: &mut bool,
@@ -338,20 +366,31 @@ fn handle_clear_cmd<ChainA: ChainHandle, ChainB: ChainHandle>(
             }
         }
 
-        WorkerCmd::ClearPendingPackets => (true, None),
+        WorkerCmd::ClearPendingPackets { .. } => (true, None),
+
+        WorkerCmd::ClearPendingOperationalData => {
+            link.a_to_b.clear_pending_operational_data();
+            (true, None)
+        }
+    };
+
+    let sequences = match &cmd {
+        WorkerCmd::ClearPendingPackets { sequences } => *sequences,
+        _ => SequenceRange::all(),
     };
 
     if do_clear {
-        info!("packets clearing triggered, looking for packets to clear");
+        info!(%sequences, "packets clearing triggered, looking for packets to clear");
 
-        // Reset the `clear_on_start` flag and attempt packet clearing once now.
+        // Reset the `clear_on_start` flag and attempt packet clearing once now,
+        // unless the clearing is limited to a range of sequences.
         // More clearing will be done at clear interval.
-        if *should_clear_on_start {
+        if *should_clear_on_start && sequences.is_all() {
             *should_clear_on_start = false;
         }
 
         link.a_to_b
-            .schedule_packet_clearing(maybe_height, clear_limit)
+            .schedule_packet_clearing(maybe_height, clear_limit, sequences)
             .map_err(handle_link_error_in_task)?;
     }
 
@@ -513,7 +552,7 @@ fn handle_clear_packet<ChainA: ChainHandle, ChainB: ChainHandle>(
     clear_limit: usize,
 ) -> Result<(), TaskError<RunError>> {
     link.a_to_b
-        .schedule_packet_clearing(height, clear_limit)
+        .schedule_packet_clearing(height, clear_limit, SequenceRange::all())
         .map_err(handle_link_error_in_task)?;
 
     handle_execute_schedule(link, path, Resubmit::from_clear_interval(clear_interval))
@@ -27,34 +27,52 @@ pub fn spawn_wallet_worker<Chain: ChainHandle>(chain: Chain) -> TaskHandle {
             TaskError::Fatal(format!("failed to get key in use by the relayer: {e}"))
         })?;
 
-        let balance = chain.query_balance(None, None).map_err(|e| {
-            TaskError::Ignore(format!("failed to query balance for the account: {e}"))
-        })?;
+        // A failed query of the balance of a denom is reported to telemetry and does
+        // not prevent the balances of the other denoms from being reported
+        for denom in chain_config.fee_denoms() {
+            let balance = match chain.query_balance(None, Some(denom.clone())) {
+                Ok(balance) => balance,
+                Err(e) => {
+                    warn!(
+                        %denom, account = %key.account(),
+                        "failed to query the wallet balance: {e}"
+                    );
+                    telemetry!(
+                        wallet_balance_query_error,
+                        &chain.id(),
+                        &key.account(),
+                        &denom
+                    );
+                    continue;
+                }
+            };
 
-        match balance.amount.parse::<f64>() {
-            Ok(amount) => {
-                telemetry!(
-                    wallet_balance,
-                    &chain.id(),
-                    &key.account(),
-                    amount,
-                    &balance.denom,
-                );
-                trace!(%amount, denom = %balance.denom, account = %key.account(), "wallet balance");
-                telemetry!(
-                    update_period_fees,
-                    &chain.id(),
-                    &key.account(),
-                    &balance.denom
-                );
-            }
-            Err(e) => {
-                warn!(
-                    %balance.amount, denom = %balance.denom, account = %key.account(),
-                    "unable to parse the wallet balance into a f64, the balance will therefore not be reported to telemetry. Reason: {}", e
-                );
+            match balance.amount.parse::<f64>() {
+                Ok(amount) => {
+                    telemetry!(
+                        wallet_balance,
+                        &chain.id(),
+                        &key.account(),
+                        amount,
+                        &balance.denom,
+                    );
+                    trace!(%amount, denom = %balance.denom, account = %key.account(), "wallet balance");
+                    telemetry!(
+                        update_period_fees,
+                        &chain.id(),
+                        &key.account(),
+                        &balance.denom
+                    );
+                }
+                Err(e) => {
+                    warn!(
+                        %balance.amount, denom = %balance.denom, account = %key.account(),
+                        "unable to parse the wallet balance into a f64, the balance will therefore not be reported to telemetry. Reason: {}", e
+                    );
+                }
             }
         }
+
         Ok(Next::Continue)
     })
 }
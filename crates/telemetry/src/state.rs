@@ -132,9 +132,15 @@ pub struct TelemetryState {
     /// Number of messages submitted to a specific chain
     messages_submitted: Counter<u64>,
 
+    /// Fees spent by Hermes on confirmed transactions, per chain, channel, message type and denom
+    relay_fees_spent: Counter<u64>,
+
     /// The balance of each wallet Hermes uses per chain
     wallet_balance: ObservableGauge<f64>,
 
+    /// Number of failed queries of the balance of each wallet Hermes uses, per chain, account and denom
+    wallet_balance_query_errors: Counter<u64>,
+
     /// Indicates the latency for all transactions submitted to a specific chain,
     /// i.e. the difference between the moment when Hermes received a batch of events
     /// until the corresponding transaction(s) were submitted. Milliseconds.
@@ -189,6 +195,9 @@ pub struct TelemetryState {
     /// Total amount of fees received from ICS29 fees.
     fee_amounts: Counter<u64>,
 
+    /// Total amount of fees received from ICS29 fees, per channel.
+    channel_fee_amounts: Counter<u64>,
+
     /// List of addresses for which rewarded fees from ICS29 should be recorded.
     visible_fee_addresses: DashSet<String>,
 
@@ -213,6 +222,17 @@ pub struct TelemetryState {
     /// The EIP-1559 base fee successfully queried
     dynamic_gas_queried_success_fees: ObservableGauge<f64>,
 
+    /// Number of transactions whose fee at the dynamic gas price was clamped to the configured `max_fee`
+    dynamic_gas_capped_fees: Counter<u64>,
+
+    /// The difference between the gas limit of the last transaction under the
+    /// configured shadow gas strategy and under the active gas strategy
+    shadow_gas_limit_delta: ObservableGauge<f64>,
+
+    /// The difference between the fee of the last transaction under the
+    /// configured shadow gas strategy and under the active gas strategy
+    shadow_gas_fee_delta: ObservableGauge<f64>,
+
     /// Number of ICS-20 packets filtered because the memo and/or the receiver fields were exceeding the configured limits
     filtered_packets: Counter<u64>,
 
@@ -224,6 +244,18 @@ pub struct TelemetryState {
 
     /// Observed ICS31 CrossChainQuery error Responses
     cross_chain_query_error_responses: Counter<u64>,
+
+    /// Number of canary packets sent, per path and result
+    canary_packets: Counter<u64>,
+
+    /// Number of ICS-20 transfers relayed which are forwarded onward to another chain,
+    /// per path and final destination
+    forwarded_transfers: Counter<u64>,
+
+    /// Indicates the latency of the canary packets sent on a path, i.e. the difference
+    /// between the moment when the transfer of a canary packet was committed until the
+    /// packet was acknowledged. Milliseconds.
+    canary_latency: ObservableGauge<u64>,
 }
 
 impl TelemetryState {
@@ -316,11 +348,21 @@ impl TelemetryState {
                 .with_description("Number of messages submitted to a specific chain")
                 .init(),
 
+            relay_fees_spent: meter
+                .u64_counter("relay_fees_spent")
+                .with_description("Fees spent by Hermes on confirmed transactions, per chain, channel, message type and denom")
+                .init(),
+
             wallet_balance: meter
                 .f64_observable_gauge("wallet_balance")
                 .with_description("The balance of each wallet Hermes uses per chain. Please note that when converting the balance to f64 a loss in precision might be introduced in the displayed value")
                 .init(),
 
+            wallet_balance_query_errors: meter
+                .u64_counter("wallet_balance_query_errors")
+                .with_description("Number of failed queries of the balance of each wallet Hermes uses per chain")
+                .init(),
+
             send_packet_events: meter
                 .u64_counter("send_packet_events")
                 .with_description("Number of SendPacket events received")
@@ -390,6 +432,11 @@ impl TelemetryState {
                 .with_description("Total amount received from ICS29 fees")
                 .init(),
 
+            channel_fee_amounts: meter
+                .u64_counter("ics29_channel_fee_amounts")
+                .with_description("Total amount received from ICS29 fees, per channel")
+                .init(),
+
             visible_fee_addresses: DashSet::new(),
 
             cached_fees: Mutex::new(Vec::new()),
@@ -428,6 +475,21 @@ impl TelemetryState {
                 .with_description("The EIP-1559 base fee successfully queried")
                 .init(),
 
+            dynamic_gas_capped_fees: meter
+                .u64_counter("dynamic_gas_capped_fees")
+                .with_description("Number of transactions whose fee at the dynamic gas price was clamped to the configured `max_fee`")
+                .init(),
+
+            shadow_gas_limit_delta: meter
+                .f64_observable_gauge("shadow_gas_limit_delta")
+                .with_description("The difference between the gas limit of the last transaction under the shadow gas strategy and under the active gas strategy")
+                .init(),
+
+            shadow_gas_fee_delta: meter
+                .f64_observable_gauge("shadow_gas_fee_delta")
+                .with_description("The difference between the fee of the last transaction under the shadow gas strategy and under the active gas strategy")
+                .init(),
+
             filtered_packets: meter
                 .u64_counter("filtered_packets")
                 .with_description("Number of ICS-20 packets filtered because the memo and/or the receiver fields were exceeding the configured limits")
@@ -447,6 +509,24 @@ impl TelemetryState {
                 .u64_counter("cross_chain_query_error_responses")
                 .with_description("Number of ICS-31 error query responses")
                 .init(),
+
+            canary_packets: meter
+                .u64_counter("canary_packets")
+                .with_description("Number of canary packets sent, per path and result")
+                .init(),
+
+            forwarded_transfers: meter
+                .u64_counter("forwarded_transfers")
+                .with_description("Number of ICS-20 transfers relayed which are forwarded onward to another chain, per path and final destination")
+                .init(),
+
+            canary_latency: meter
+                .u64_observable_gauge("canary_latency")
+                .with_unit(Unit::new("milliseconds"))
+                .with_description("The latency of the canary packets sent on a path, \
+                    i.e. the difference between the moment when the transfer of a canary packet \
+                    was committed and when the packet was acknowledged. Milliseconds.")
+                .init(),
         }
     }
 
@@ -772,6 +852,28 @@ impl TelemetryState {
         self.messages_submitted.add(&cx, count, labels);
     }
 
+    /// The share of the fees of a confirmed transaction spent on a message,
+    /// per chain, channel, message type and denom
+    pub fn fees_spent(
+        &self,
+        chain_id: &ChainId,
+        channel: &str,
+        msg_type: &str,
+        denom: &str,
+        amount: u64,
+    ) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("channel", channel.to_string()),
+            KeyValue::new("msg_type", msg_type.to_string()),
+            KeyValue::new("denom", denom.to_string()),
+        ];
+
+        self.relay_fees_spent.add(&cx, amount, labels);
+    }
+
     /// The balance in each wallet that Hermes is using, per account, denom and chain.
     /// The amount given is of unit: 10^6 * `denom`
     pub fn wallet_balance(&self, chain_id: &ChainId, account: &str, amount: f64, denom: &str) {
@@ -786,6 +888,19 @@ impl TelemetryState {
         self.wallet_balance.observe(&cx, amount, labels);
     }
 
+    /// Increment the number of failed queries of the balance of a wallet that Hermes is using.
+    pub fn wallet_balance_query_error(&self, chain_id: &ChainId, account: &str, denom: &str) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("account", account.to_string()),
+            KeyValue::new("denom", denom.to_string()),
+        ];
+
+        self.wallet_balance_query_errors.add(&cx, 1, labels);
+    }
+
     pub fn received_event_batch(&self, tracking_id: impl ToString) {
         self.in_flight_events
             .insert(tracking_id.to_string(), Instant::now());
@@ -1155,6 +1270,34 @@ impl TelemetryState {
         self.period_fees.observe(&cx, sum, labels);
     }
 
+    /// Record the rewarded fee from ICS29 for the channel of the packet it was
+    /// paid for, if the address is in the registered addresses list.
+    pub fn channel_fees_amount(
+        &self,
+        chain_id: &ChainId,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+        receiver: &Signer,
+        fee_amounts: Coin<String>,
+    ) {
+        // If the address isn't in the filter list, don't record the metric.
+        if !self.visible_fee_addresses.contains(&receiver.to_string()) {
+            return;
+        }
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("channel", channel_id.to_string()),
+            KeyValue::new("port", port_id.to_string()),
+            KeyValue::new("receiver", receiver.to_string()),
+            KeyValue::new("denom", fee_amounts.denom.to_string()),
+        ];
+
+        self.channel_fee_amounts
+            .add(&cx, fee_amounts.amount.0.as_u64(), labels);
+    }
+
     pub fn update_period_fees(&self, chain_id: &ChainId, receiver: &String, denom: &String) {
         let cx = Context::current();
 
@@ -1233,6 +1376,28 @@ impl TelemetryState {
             .observe(&cx, amount, labels);
     }
 
+    /// Increment the number of transactions whose fee was clamped to the `max_fee`
+    /// of the dynamic gas price
+    pub fn dynamic_gas_capped_fees(&self, chain_id: &ChainId) {
+        let cx = Context::current();
+
+        let labels = &[KeyValue::new("identifier", chain_id.to_string())];
+
+        self.dynamic_gas_capped_fees.add(&cx, 1, labels);
+    }
+
+    /// Record the difference between the gas limit and fee of a transaction
+    /// under the shadow gas strategy and under the active gas strategy
+    pub fn shadow_gas_delta(&self, chain_id: &ChainId, gas_limit_delta: f64, fee_delta: f64) {
+        let cx = Context::current();
+
+        let labels = &[KeyValue::new("identifier", chain_id.to_string())];
+
+        self.shadow_gas_limit_delta
+            .observe(&cx, gas_limit_delta, labels);
+        self.shadow_gas_fee_delta.observe(&cx, fee_delta, labels);
+    }
+
     /// Increment number of packets filtered because the memo field is too big
     #[allow(clippy::too_many_arguments)]
     pub fn filtered_packets(
@@ -1295,6 +1460,59 @@ impl TelemetryState {
             }
         }
     }
+
+    /// Increment the number of transfers relayed which are forwarded onward to another chain
+    pub fn forwarded_transfers(
+        &self,
+        src_chain: &ChainId,
+        dst_chain: &ChainId,
+        src_channel: &ChannelId,
+        src_port: &PortId,
+        final_destination: &str,
+        hops: usize,
+    ) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("src_chain", src_chain.to_string()),
+            KeyValue::new("dst_chain", dst_chain.to_string()),
+            KeyValue::new("src_channel", src_channel.to_string()),
+            KeyValue::new("src_port", src_port.to_string()),
+            KeyValue::new("final_destination", final_destination.to_string()),
+            KeyValue::new("hops", hops.to_string()),
+        ];
+
+        self.forwarded_transfers.add(&cx, 1, labels);
+    }
+
+    /// Record the result and the latency of a canary packet
+    pub fn canary_packet(
+        &self,
+        chain_id: &ChainId,
+        counterparty_chain_id: &ChainId,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+        success: bool,
+        latency_ms: u64,
+    ) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("counterparty", counterparty_chain_id.to_string()),
+            KeyValue::new("channel", channel_id.to_string()),
+            KeyValue::new("port", port_id.to_string()),
+        ];
+
+        let result = if success { "success" } else { "failure" };
+        let result_labels = &[labels.as_slice(), &[KeyValue::new("result", result)]].concat();
+
+        self.canary_packets.add(&cx, 1, result_labels);
+
+        if success {
+            self.canary_latency.observe(&cx, latency_ms, labels);
+        }
+    }
 }
 
 use std::sync::Arc;
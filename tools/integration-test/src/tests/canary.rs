@@ -0,0 +1,93 @@
+//! Tests the canary packets, which are periodically sent on a channel to check
+//! that its path relays packets end-to-end.
+//!
+//! The test enables a canary on the channel of chain A and starts the supervisor,
+//! whose canary worker sends a canary packet right away. It asserts that the packet
+//! reaches the relayer account on chain B and is acknowledged on chain A.
+
+use std::time::Duration;
+
+use ibc_relayer::chain::requests::{Paginate, QueryHeight, QueryPacketCommitmentsRequest};
+use ibc_relayer::config::{CanaryConfig, ChainConfig};
+use ibc_test_framework::prelude::*;
+
+#[test]
+fn test_canary() -> Result<(), Error> {
+    run_binary_channel_test(&CanaryTest)
+}
+
+pub struct CanaryTest;
+
+impl TestOverrides for CanaryTest {}
+
+impl BinaryChannelTest for CanaryTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        mut relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        channel: ConnectedChannel<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        let denom_a = chains.node_a.denom();
+
+        // A single canary packet is sent during the test, when the supervisor starts
+        let canary = CanaryConfig {
+            port: channel.port_a.value().clone(),
+            denom: denom_a.value().to_string(),
+            amount: 1,
+            interval: Duration::from_secs(60 * 60),
+            threshold: Duration::from_secs(60),
+        };
+
+        match &mut relayer.config.chains[0] {
+            ChainConfig::CosmosSdk(chain_config) | ChainConfig::Namada(chain_config) => {
+                chain_config
+                    .canaries
+                    .insert(channel.channel_id_a.value().clone(), canary);
+            }
+            ChainConfig::Penumbra(_) => panic!("running tests with Penumbra chain not supported"),
+        }
+
+        let denom_b = derive_ibc_denom(
+            &chains.node_b.chain_driver().value().chain_type,
+            &channel.port_b.as_ref(),
+            &channel.channel_id_b.as_ref(),
+            &denom_a,
+        )?;
+
+        relayer.with_supervisor(|| {
+            info!("waiting for the canary packet to reach the relayer on chain B");
+
+            chains.node_b.chain_driver().assert_eventual_wallet_amount(
+                &chains.node_b.wallets().relayer().address(),
+                &denom_b.with_amount(1u64).as_ref(),
+            )?;
+
+            assert_eventually_succeed(
+                "the canary packet should be acknowledged on chain A",
+                30,
+                Duration::from_secs(1),
+                || {
+                    let (commitments, _) = chains.handle_a().query_packet_commitments(
+                        QueryPacketCommitmentsRequest {
+                            query_height: QueryHeight::Latest,
+                            port_id: channel.port_a.value().clone(),
+                            channel_id: channel.channel_id_a.value().clone(),
+                            pagination: Paginate::All,
+                        },
+                    )?;
+
+                    if commitments.is_empty() {
+                        Ok(())
+                    } else {
+                        Err(Error::generic(eyre!(
+                            "canary packets {commitments:?} are not acknowledged yet"
+                        )))
+                    }
+                },
+            )?;
+
+            Ok(())
+        })
+    }
+}
@@ -12,6 +12,10 @@
 //! - `ChannelUpgradeHandshakeTimeoutWhenFlushing` tests that the channel worker will timeout the
 //!   upgrade handshake if the counterparty does not finish flushing the packets before the upgrade timeout.
 //!
+//! - `ChannelUpgradeHandshakeTimeoutStalledFlushing` tests that the supervisor will submit
+//!   the upgrade timeout, and cancel the upgrade on the counterparty, when the packets are not
+//!   flushed before the upgrade timeout, restoring both channel ends to their original state.
+//!
 //! - `ChannelUpgradeHandshakeTimeoutOnAck` tests that the channel worker will cancel the
 //!   upgrade handshake if the Ack step fails due to an upgrade timeout.
 //!
@@ -56,6 +60,11 @@ fn test_channel_upgrade_handshake_timeout_when_flushing() -> Result<(), Error> {
     run_binary_channel_test(&ChannelUpgradeHandshakeTimeoutWhenFlushing)
 }
 
+#[test]
+fn test_channel_upgrade_handshake_timeout_stalled_flushing() -> Result<(), Error> {
+    run_binary_channel_test(&ChannelUpgradeHandshakeTimeoutStalledFlushing)
+}
+
 #[test]
 fn test_channel_upgrade_handshake_timeout_on_ack() -> Result<(), Error> {
     run_binary_channel_test(&ChannelUpgradeHandshakeTimeoutOnAck)
@@ -89,6 +98,25 @@ impl TestOverrides for ChannelUpgradeTestOverrides {
     }
 }
 
+struct ChannelUpgradeStalledFlushingTestOverrides;
+
+impl TestOverrides for ChannelUpgradeStalledFlushingTestOverrides {
+    fn modify_genesis_file(&self, genesis: &mut serde_json::Value) -> Result<(), Error> {
+        ChannelUpgradeTestOverrides.modify_genesis_file(genesis)
+    }
+
+    fn modify_relayer_config(&self, config: &mut Config) {
+        ChannelUpgradeTestOverrides.modify_relayer_config(config);
+
+        // Do not relay the in-flight packet, so that flushing stalls
+        config.mode.packets.enabled = false;
+    }
+
+    fn should_spawn_supervisor(&self) -> bool {
+        false
+    }
+}
+
 struct ChannelUpgradeTimeoutAckHandshake;
 
 impl BinaryChannelTest for ChannelUpgradeTimeoutAckHandshake {
@@ -683,6 +711,159 @@ impl BinaryChannelTest for ChannelUpgradeManualTimeoutWhenFlushing {
     }
 }
 
+struct ChannelUpgradeHandshakeTimeoutStalledFlushing;
+
+impl BinaryChannelTest for ChannelUpgradeHandshakeTimeoutStalledFlushing {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        channels: ConnectedChannel<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        info!("Check that channels are both in OPEN State");
+
+        assert_eventually_channel_established(
+            &chains.handle_b,
+            &chains.handle_a,
+            &channels.channel_id_b.as_ref(),
+            &channels.port_b.as_ref(),
+        )?;
+
+        let channel_end_a = chains
+            .handle_a
+            .query_channel(
+                QueryChannelRequest {
+                    port_id: channels.port_a.0.clone(),
+                    channel_id: channels.channel_id_a.0.clone(),
+                    height: QueryHeight::Latest,
+                },
+                IncludeProof::No,
+            )
+            .map(|(channel_end, _)| channel_end)
+            .map_err(|e| eyre!("Error querying ChannelEnd A: {e}"))?;
+
+        let channel_end_b = chains
+            .handle_b
+            .query_channel(
+                QueryChannelRequest {
+                    port_id: channels.port_b.0.clone(),
+                    channel_id: channels.channel_id_b.0.clone(),
+                    height: QueryHeight::Latest,
+                },
+                IncludeProof::No,
+            )
+            .map(|(channel_end, _)| channel_end)
+            .map_err(|e| eyre!("Error querying ChannelEnd B: {e}"))?;
+
+        let old_version = channel_end_a.version;
+        let old_ordering = channel_end_a.ordering;
+        let old_connection_hops_a = channel_end_a.connection_hops;
+        let old_connection_hops_b = channel_end_b.connection_hops;
+
+        let channel = channels.channel;
+        let new_version = Version::ics20_with_fee();
+
+        let old_attrs = ChannelUpgradableAttributes::new(
+            old_version.clone(),
+            old_version.clone(),
+            old_ordering,
+            old_connection_hops_a.clone(),
+            old_connection_hops_b.clone(),
+            Sequence::from(1),
+        );
+
+        info!("Will update channel params to set a shorter upgrade timeout...");
+
+        chains.node_b.chain_driver().update_channel_params(
+            25000000000,
+            chains.handle_b().get_signer().unwrap().as_ref(),
+            "1",
+        )?;
+
+        info!("Will initialise upgrade handshake with governance proposal...");
+
+        chains.node_a.chain_driver().initialise_channel_upgrade(
+            channel.src_port_id().as_str(),
+            channel.src_channel_id().unwrap().as_str(),
+            old_ordering.as_str(),
+            old_connection_hops_a.first().unwrap().as_str(),
+            &serde_json::to_string(&new_version.0).unwrap(),
+            chains.handle_a().get_signer().unwrap().as_ref(),
+            "1",
+        )?;
+
+        info!("Will run ChanUpgradeTry step...");
+
+        channel.build_chan_upgrade_try_and_send()?;
+
+        info!("Check that the step ChanUpgradeTry was correctly executed...");
+
+        assert_eventually_channel_upgrade_try(
+            &chains.handle_b,
+            &chains.handle_a,
+            &channels.channel_id_b.as_ref(),
+            &channels.port_b.as_ref(),
+            &old_attrs.flipped(),
+        )?;
+
+        // send a IBC transfer message from chain a to chain b, which is never relayed,
+        // so that chain a moves to `FLUSHING` during Ack and never completes flushing
+        let denom_a = chains.node_a.denom();
+        let wallet_a = chains.node_a.wallets().user1().cloned();
+        let wallet_b = chains.node_b.wallets().user1().cloned();
+
+        chains.node_a.chain_driver().ibc_transfer_token(
+            &channels.port_a.as_ref(),
+            &channels.channel_id_a.as_ref(),
+            &wallet_a.as_ref(),
+            &wallet_b.address(),
+            &denom_a.with_amount(12345u64).as_ref(),
+        )?;
+
+        info!("Will run ChanUpgradeAck step...");
+
+        channel.flipped().build_chan_upgrade_ack_and_send()?;
+
+        info!("Check that the step ChanUpgradeAck was correctly executed...");
+
+        assert_eventually_channel_upgrade_flushing(
+            &chains.handle_a,
+            &chains.handle_b,
+            &channels.channel_id_a.as_ref(),
+            &channels.port_a.as_ref(),
+            &old_attrs,
+        )?;
+
+        // wait enough time so that the upgrade timeout expires while chain a is in FLUSHING
+        sleep(Duration::from_nanos(35000000000));
+
+        relayer.with_supervisor(|| {
+            info!("Check that the supervisor timed out the upgrade on chain b...");
+
+            assert_eventually_channel_upgrade_cancel(
+                &chains.handle_b,
+                &chains.handle_a,
+                &channels.channel_id_b.as_ref(),
+                &channels.port_b.as_ref(),
+                &old_attrs.flipped(),
+            )?;
+
+            info!("Check that the supervisor cancelled the upgrade on chain a...");
+
+            assert_eventually_channel_upgrade_cancel(
+                &chains.handle_a,
+                &chains.handle_b,
+                &channels.channel_id_a.as_ref(),
+                &channels.port_a.as_ref(),
+                &old_attrs,
+            )?;
+
+            Ok(())
+        })
+    }
+}
+
 struct ChannelUpgradeHandshakeTimeoutOnAck;
 
 impl BinaryChannelTest for ChannelUpgradeHandshakeTimeoutOnAck {
@@ -947,6 +1128,7 @@ impl BinaryChannelTest for ChannelUpgradeHandshakeTimeoutOnPacketAck {
                 &denom_a.with_amount(a_to_b_amount).as_ref(),
                 None,
                 Some(Duration::from_secs(600)),
+                None,
             )?;
 
         info!("Will run ChanUpgradeTry step...");
@@ -1051,6 +1233,14 @@ impl HasOverrides for ChannelUpgradeHandshakeTimeoutWhenFlushing {
     }
 }
 
+impl HasOverrides for ChannelUpgradeHandshakeTimeoutStalledFlushing {
+    type Overrides = ChannelUpgradeStalledFlushingTestOverrides;
+
+    fn get_overrides(&self) -> &ChannelUpgradeStalledFlushingTestOverrides {
+        &ChannelUpgradeStalledFlushingTestOverrides
+    }
+}
+
 impl HasOverrides for ChannelUpgradeHandshakeTimeoutOnAck {
     type Overrides = ChannelUpgradeTestOverrides;
 
@@ -22,8 +22,12 @@ impl TestOverrides for CleanPacketWorkersTest {
                 enabled: false,
                 refresh: false,
                 misbehaviour: false,
+                smooth_refresh: false,
+            },
+            connections: config::Connections {
+                enabled: false,
+                ..Default::default()
             },
-            connections: config::Connections { enabled: false },
             channels: config::Channels { enabled: false },
             packets: config::Packets {
                 enabled: true,
@@ -85,8 +89,12 @@ impl TestOverrides for CleanChannelWorkersTest {
                 enabled: false,
                 refresh: false,
                 misbehaviour: false,
+                smooth_refresh: false,
+            },
+            connections: config::Connections {
+                enabled: false,
+                ..Default::default()
             },
-            connections: config::Connections { enabled: false },
             channels: config::Channels { enabled: true },
             packets: config::Packets {
                 enabled: false,
@@ -36,12 +36,18 @@ fn test_limited_clear_packet() -> Result<(), Error> {
     run_binary_channel_test(&LimitedClearPacketTest)
 }
 
+#[test]
+fn test_clear_packets_until_complete() -> Result<(), Error> {
+    run_binary_channel_test(&ClearPacketsUntilCompleteTest)
+}
+
 pub struct DisabledClearPacketTest;
 pub struct ClearPacketRecoveryTest;
 pub struct ClearPacketNoScanTest;
 pub struct ClearPacketOverrideTest;
 pub struct ClearPacketSequencesTest;
 pub struct LimitedClearPacketTest;
+pub struct ClearPacketsUntilCompleteTest;
 
 impl TestOverrides for DisabledClearPacketTest {
     fn modify_relayer_config(&self, config: &mut Config) {
@@ -516,6 +522,9 @@ impl BinaryChannelTest for ClearPacketSequencesTest {
             max_memo_size: packet_config.ics20_max_memo_size,
             max_receiver_size: packet_config.ics20_max_receiver_size,
             exclude_src_sequences: vec![],
+            max_clear_duration: None,
+            verification: Default::default(),
+            clear_order: Default::default(),
         };
 
         let rev_opts = LinkParameters {
@@ -524,6 +533,9 @@ impl BinaryChannelTest for ClearPacketSequencesTest {
             max_memo_size: packet_config.ics20_max_memo_size,
             max_receiver_size: packet_config.ics20_max_receiver_size,
             exclude_src_sequences: vec![],
+            max_clear_duration: None,
+            verification: Default::default(),
+            clear_order: Default::default(),
         };
 
         // Clear all even packets
@@ -698,3 +710,104 @@ impl BinaryChannelTest for LimitedClearPacketTest {
         })
     }
 }
+
+impl TestOverrides for ClearPacketsUntilCompleteTest {
+    fn should_spawn_supervisor(&self) -> bool {
+        false
+    }
+}
+
+impl BinaryChannelTest for ClearPacketsUntilCompleteTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        channel: ConnectedChannel<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        const NUM_TRANSFERS: usize = 15;
+
+        let denom_a = chains.node_a.denom();
+        let denom_b = chains.node_b.denom();
+
+        let wallet_a = chains.node_a.wallets().user1().cloned();
+        let wallet_b = chains.node_b.wallets().user1().cloned();
+
+        let amount_a = denom_a.with_amount(random_u128_range(1000, 5000));
+        let amount_b = denom_b.with_amount(random_u128_range(1000, 5000));
+
+        info!("Performing {NUM_TRANSFERS} IBC transfers in each direction, which are not relayed");
+
+        chains.node_a.chain_driver().ibc_transfer_token_multiple(
+            &channel.port_a.as_ref(),
+            &channel.channel_id_a.as_ref(),
+            &wallet_a.as_ref(),
+            &wallet_b.address(),
+            &amount_a.as_ref(),
+            NUM_TRANSFERS,
+            None,
+        )?;
+
+        chains.node_b.chain_driver().ibc_transfer_token_multiple(
+            &channel.port_b.as_ref(),
+            &channel.channel_id_b.as_ref(),
+            &wallet_b.as_ref(),
+            &wallet_a.address(),
+            &amount_b.as_ref(),
+            NUM_TRANSFERS,
+            None,
+        )?;
+
+        sleep(Duration::from_secs(5));
+
+        let channel_end_a = query_identified_channel_end(
+            chains.handle_a(),
+            channel.channel_id_a.as_ref(),
+            channel.port_a.as_ref(),
+        )?;
+
+        let channel_end_b = query_identified_channel_end(
+            chains.handle_b(),
+            channel.channel_id_b.as_ref(),
+            channel.port_b.as_ref(),
+        )?;
+
+        let pending_packets_a = pending_packet_summary(
+            chains.handle_a(),
+            chains.handle_b(),
+            channel_end_a.value(),
+            Paginate::All,
+        )?;
+
+        assert_eq!(pending_packets_a.unreceived_packets.len(), NUM_TRANSFERS);
+
+        info!("Clearing the packets until none is left on the channel");
+
+        relayer.clear_packets_until_complete(&chains, &channel)?;
+
+        // Nothing is left to relay as soon as the clearing returns, in either direction
+        let pending_packets_a = pending_packet_summary(
+            chains.handle_a(),
+            chains.handle_b(),
+            channel_end_a.value(),
+            Paginate::All,
+        )?;
+
+        let pending_packets_b = pending_packet_summary(
+            chains.handle_b(),
+            chains.handle_a(),
+            channel_end_b.value(),
+            Paginate::All,
+        )?;
+
+        info!("Pending packets on chain A: {pending_packets_a:?}");
+        info!("Pending packets on chain B: {pending_packets_b:?}");
+
+        assert_eq!(pending_packets_a.unreceived_packets.len(), 0);
+        assert_eq!(pending_packets_a.unreceived_acks.len(), 0);
+        assert_eq!(pending_packets_b.unreceived_packets.len(), 0);
+        assert_eq!(pending_packets_b.unreceived_acks.len(), 0);
+
+        Ok(())
+    }
+}
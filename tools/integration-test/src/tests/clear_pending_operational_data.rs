@@ -0,0 +1,110 @@
+//! This test ensures that `RelayPath::clear_pending_operational_data` drops the
+//! operational data scheduled by a relay path, and that the packets it carried
+//! are found again by the next packet clearing pass.
+//!
+//! The test performs a few IBC transfers from chain A to chain B, schedules
+//! the relaying of their packets without executing the schedule, and then
+//! clears the scheduled operational data.
+
+use ibc_test_framework::prelude::*;
+use ibc_test_framework::util::random::random_u128_range;
+
+use ibc_relayer::link::{Link, LinkParameters, SequenceRange};
+
+/// The number of transfers whose packets are scheduled for relaying.
+const TRANSFER_COUNT: usize = 3;
+
+#[test]
+fn test_clear_pending_operational_data() -> Result<(), Error> {
+    run_binary_channel_test(&ClearPendingOperationalDataTest)
+}
+
+pub struct ClearPendingOperationalDataTest;
+
+impl TestOverrides for ClearPendingOperationalDataTest {
+    fn should_spawn_supervisor(&self) -> bool {
+        false
+    }
+}
+
+impl BinaryChannelTest for ClearPendingOperationalDataTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        channel: ConnectedChannel<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        let packet_config = relayer.config.mode.packets;
+
+        let link_opts = LinkParameters {
+            src_port_id: channel.port_a.clone().into_value(),
+            src_channel_id: channel.channel_id_a.clone().into_value(),
+            max_memo_size: packet_config.ics20_max_memo_size,
+            max_receiver_size: packet_config.ics20_max_receiver_size,
+            exclude_src_sequences: vec![],
+            max_clear_duration: None,
+            verification: Default::default(),
+            clear_order: Default::default(),
+        };
+
+        let link = Link::new_from_opts(
+            chains.handle_a().clone(),
+            chains.handle_b().clone(),
+            link_opts,
+            true,
+            false,
+        )?;
+
+        let relay_path_a_to_b = link.a_to_b;
+
+        for i in 0..TRANSFER_COUNT {
+            info!("Performing IBC transfer #{i} from chain A to chain B");
+
+            chains.node_a.chain_driver().ibc_transfer_token(
+                &channel.port_a.as_ref(),
+                &channel.channel_id_a.as_ref(),
+                &chains.node_a.wallets().user1(),
+                &chains.node_b.wallets().user1().address(),
+                &chains
+                    .node_a
+                    .denom()
+                    .with_amount(random_u128_range(1000, 5000))
+                    .as_ref(),
+            )?;
+        }
+
+        relay_path_a_to_b.schedule_packet_clearing(
+            None,
+            packet_config.clear_limit,
+            SequenceRange::all(),
+        )?;
+
+        assert!(
+            !relay_path_a_to_b.dst_operational_data.is_empty(),
+            "the packets of the transfers are scheduled for relaying to chain B"
+        );
+
+        relay_path_a_to_b.clear_pending_operational_data();
+
+        assert!(
+            relay_path_a_to_b.dst_operational_data.is_empty(),
+            "the operational data scheduled for chain B is dropped"
+        );
+        assert!(relay_path_a_to_b.src_operational_data.is_empty());
+
+        // The packets were never relayed, so the next clearing pass schedules them again
+        relay_path_a_to_b.schedule_packet_clearing(
+            None,
+            packet_config.clear_limit,
+            SequenceRange::all(),
+        )?;
+
+        assert!(
+            !relay_path_a_to_b.dst_operational_data.is_empty(),
+            "the packets of the transfers are scheduled again by the next clearing pass"
+        );
+
+        Ok(())
+    }
+}
@@ -100,8 +100,12 @@ impl TestOverrides for ExpirationTestOverrides {
                 enabled: true,
                 refresh: true,
                 misbehaviour: true,
+                smooth_refresh: false,
+            },
+            connections: config::Connections {
+                enabled: true,
+                ..Default::default()
             },
-            connections: config::Connections { enabled: true },
             channels: config::Channels { enabled: true },
             packets: config::Packets {
                 enabled: true,
@@ -0,0 +1,69 @@
+//! Tests that the detection of misbehaviour, enabled globally, can be disabled for
+//! the clients hosted on a given chain with its `detect_misbehaviour` setting.
+//!
+//! Misbehaviour detection is disabled on chain B, so that the client worker of the
+//! client hosted on chain B does not run the misbehaviour detection task, while the
+//! one of the client hosted on chain A does.
+
+use ibc_relayer::config::ChainConfig;
+use ibc_relayer::object::{Object, ObjectType};
+use ibc_relayer::worker::WorkerData;
+use ibc_test_framework::prelude::*;
+
+#[test]
+fn test_misbehaviour_detection_disabled_per_chain() -> Result<(), Error> {
+    run_binary_channel_test(&MisbehaviourDetectionPerChainTest)
+}
+
+struct MisbehaviourDetectionPerChainTest;
+
+impl TestOverrides for MisbehaviourDetectionPerChainTest {
+    fn modify_relayer_config(&self, config: &mut Config) {
+        config.mode.clients.enabled = true;
+        config.mode.clients.refresh = true;
+        config.mode.clients.misbehaviour = true;
+
+        match &mut config.chains[1] {
+            ChainConfig::CosmosSdk(chain_config) | ChainConfig::Namada(chain_config) => {
+                chain_config.detect_misbehaviour = Some(false);
+            }
+            ChainConfig::Penumbra(_) => panic!("running tests with Penumbra chain not supported"),
+        }
+    }
+}
+
+impl BinaryChannelTest for MisbehaviourDetectionPerChainTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        _channel: ConnectedChannel<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        let supervisor = relayer.spawn_supervisor()?;
+        let state = supervisor.dump_state()?;
+
+        let client_workers = state
+            .workers
+            .get(&ObjectType::Client)
+            .ok_or_else(|| Error::generic(eyre!("expected client workers to be spawned")))?;
+
+        let misbehaviour_on = |chain_id: &ChainId| {
+            client_workers
+                .iter()
+                .find_map(|desc| match (&desc.object, &desc.data) {
+                    (Object::Client(client), Some(WorkerData::Client { misbehaviour, .. }))
+                        if &client.dst_chain_id == chain_id =>
+                    {
+                        Some(*misbehaviour)
+                    }
+                    _ => None,
+                })
+        };
+
+        assert_eq!(misbehaviour_on(&chains.handle_a.id()), Some(true));
+        assert_eq!(misbehaviour_on(&chains.handle_b.id()), Some(false));
+
+        Ok(())
+    }
+}
@@ -1,7 +1,12 @@
+use std::sync::{mpsc, Arc};
+use std::thread;
+
 use ibc_relayer::config::gas_multiplier::GasMultiplier;
-use ibc_relayer::config::ChainConfig;
-use ibc_relayer::foreign_client::CreateOptions;
+use ibc_relayer::config::{ChainConfig, RefreshRate, TrustingPeriodSource};
+use ibc_relayer::foreign_client::{CreateOptions, ForeignClient};
+use ibc_relayer::util::semaphore::Semaphore;
 use ibc_relayer_types::core::ics02_client::trust_threshold::TrustThreshold;
+use ibc_relayer_types::events::IbcEvent;
 
 use ibc_test_framework::prelude::*;
 
@@ -19,11 +24,32 @@ fn test_client_fail_refresh() -> Result<(), Error> {
     run_binary_chain_test(&ClientFailsTest)
 }
 
+#[test]
+fn test_client_refresh_rate() -> Result<(), Error> {
+    run_binary_chain_test(&ClientRefreshRateTest)
+}
+
+#[test]
+fn test_client_trusting_period_source() -> Result<(), Error> {
+    run_binary_chain_test(&ClientTrustingPeriodSourceTest)
+}
+
+#[test]
+fn test_client_update_limit() -> Result<(), Error> {
+    run_binary_chain_test(&ClientUpdateLimitTest)
+}
+
 #[allow(dead_code)]
 struct ClientFailsTest;
 
 struct ClientDefaultsTest;
 
+struct ClientRefreshRateTest;
+
+struct ClientTrustingPeriodSourceTest;
+
+struct ClientUpdateLimitTest;
+
 // Override the clients `trusting_period` such that the refresh_window is 40 seconds.
 impl TestOverrides for ClientDefaultsTest {
     fn client_options_a_to_b(&self) -> CreateOptions {
@@ -96,6 +122,224 @@ impl BinaryChainTest for ClientDefaultsTest {
     }
 }
 
+// Override the clients `trusting_period` and the `client_refresh_rate` of chain A such that
+// the refresh_window of the client of chain A is 3 seconds, and the one of the client of
+// chain B is the default of 20 seconds.
+impl TestOverrides for ClientRefreshRateTest {
+    fn modify_relayer_config(&self, config: &mut Config) {
+        match &mut config.chains[0] {
+            ChainConfig::CosmosSdk(chain_config) | ChainConfig::Namada(chain_config) => {
+                chain_config.client_refresh_rate = RefreshRate::new(1, 20);
+            }
+            ChainConfig::Penumbra(_) => panic!("running tests with Penumbra chain not supported"),
+        }
+    }
+
+    fn client_options_a_to_b(&self) -> CreateOptions {
+        CreateOptions {
+            max_clock_drift: Some(Duration::from_secs(3)),
+            trusting_period: Some(Duration::from_secs(60)),
+            trust_threshold: Some(TrustThreshold::TWO_THIRDS),
+        }
+    }
+
+    fn client_options_b_to_a(&self) -> CreateOptions {
+        CreateOptions {
+            max_clock_drift: Some(Duration::from_secs(3)),
+            trusting_period: Some(Duration::from_secs(60)),
+            trust_threshold: Some(TrustThreshold::TWO_THIRDS),
+        }
+    }
+}
+
+impl BinaryChainTest for ClientRefreshRateTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        _relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        let mut client_a_to_b = chains.foreign_clients.client_a_to_b;
+        let mut client_b_to_a = chains.foreign_clients.client_b_to_a;
+
+        // Wait for the refresh_window of the client of chain A to elapse,
+        // but not the default refresh_window of the client of chain B
+        std::thread::sleep(core::time::Duration::from_secs(10));
+
+        let res = client_a_to_b.refresh();
+        // Check that the client of chain A was updated, as elapsed > refresh_window.
+        match res {
+            Ok(ibc_events) => assert!(
+                ibc_events.is_some(),
+                "Client refresh failed: {ibc_events:?}"
+            ),
+            Err(_) => panic!("Client refresh failed: {res:?}"),
+        }
+
+        let res = client_b_to_a.refresh();
+        // Check that the client of chain B was not updated, as elapsed < default refresh_window.
+        match res {
+            Ok(ibc_events) => assert!(
+                ibc_events.is_none(),
+                "Client refresh failed: {ibc_events:?}"
+            ),
+            Err(_) => panic!("Client refresh failed: {res:?}"),
+        }
+
+        Ok(())
+    }
+}
+
+// Override the clients `trusting_period` such that their refresh_window is 20 seconds, and
+// have the refreshes of the client of chain A scheduled against the `trusting_period` of
+// 15 seconds configured for chain A, such that its refresh_window is 5 seconds.
+impl TestOverrides for ClientTrustingPeriodSourceTest {
+    fn modify_relayer_config(&self, config: &mut Config) {
+        match &mut config.chains[0] {
+            ChainConfig::CosmosSdk(chain_config) | ChainConfig::Namada(chain_config) => {
+                chain_config.trusting_period = Some(Duration::from_secs(15));
+                chain_config.trusting_period_source = TrustingPeriodSource::Config;
+            }
+            ChainConfig::Penumbra(_) => panic!("running tests with Penumbra chain not supported"),
+        }
+    }
+
+    fn client_options_a_to_b(&self) -> CreateOptions {
+        CreateOptions {
+            max_clock_drift: Some(Duration::from_secs(3)),
+            trusting_period: Some(Duration::from_secs(60)),
+            trust_threshold: Some(TrustThreshold::TWO_THIRDS),
+        }
+    }
+
+    fn client_options_b_to_a(&self) -> CreateOptions {
+        CreateOptions {
+            max_clock_drift: Some(Duration::from_secs(3)),
+            trusting_period: Some(Duration::from_secs(60)),
+            trust_threshold: Some(TrustThreshold::TWO_THIRDS),
+        }
+    }
+}
+
+impl BinaryChainTest for ClientTrustingPeriodSourceTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        _relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        let mut client_a_to_b = chains.foreign_clients.client_a_to_b;
+        let mut client_b_to_a = chains.foreign_clients.client_b_to_a;
+
+        // Wait for the refresh_window of the configured trusting period of chain A to
+        // elapse, but not the one of the trusting period of the client states
+        std::thread::sleep(core::time::Duration::from_secs(10));
+
+        let res = client_a_to_b.refresh();
+        // Check that the client of chain A was updated, as elapsed > configured refresh_window.
+        match res {
+            Ok(ibc_events) => assert!(
+                ibc_events.is_some(),
+                "Client refresh failed: {ibc_events:?}"
+            ),
+            Err(_) => panic!("Client refresh failed: {res:?}"),
+        }
+
+        let res = client_b_to_a.refresh();
+        // Check that the client of chain B was not updated, as elapsed < on-chain refresh_window.
+        match res {
+            Ok(ibc_events) => assert!(
+                ibc_events.is_none(),
+                "Client refresh failed: {ibc_events:?}"
+            ),
+            Err(_) => panic!("Client refresh failed: {res:?}"),
+        }
+
+        Ok(())
+    }
+}
+
+// Same overrides as `ClientRefreshRateTest`: the refresh_window of the client of chain A
+// is 3 seconds, and the one of the client of chain B is the default of 20 seconds.
+impl TestOverrides for ClientUpdateLimitTest {
+    fn modify_relayer_config(&self, config: &mut Config) {
+        ClientRefreshRateTest.modify_relayer_config(config)
+    }
+
+    fn client_options_a_to_b(&self) -> CreateOptions {
+        ClientRefreshRateTest.client_options_a_to_b()
+    }
+
+    fn client_options_b_to_a(&self) -> CreateOptions {
+        ClientRefreshRateTest.client_options_b_to_a()
+    }
+}
+
+impl BinaryChainTest for ClientUpdateLimitTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        _relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        // A limit of a single client update at once, whose permit is held by the test
+        let update_limit = Arc::new(Semaphore::new(1));
+        let permit = update_limit.acquire();
+
+        // The client of chain B does not need to be refreshed, and so does not need a permit
+        let refresh_b_to_a = spawn_limited_refresh(
+            chains.foreign_clients.client_b_to_a.clone(),
+            update_limit.clone(),
+        );
+
+        match refresh_b_to_a.recv_timeout(Duration::from_secs(30)) {
+            Ok(Ok(None)) => {}
+            res => panic!("client of chain B should not be refreshed: {res:?}"),
+        }
+
+        // Wait for the refresh_window of the client of chain A to elapse
+        thread::sleep(Duration::from_secs(10));
+
+        let refresh_a_to_b = spawn_limited_refresh(
+            chains.foreign_clients.client_a_to_b.clone(),
+            update_limit.clone(),
+        );
+
+        // The client of chain A needs to be refreshed, but waits for the permit
+        if let Ok(res) = refresh_a_to_b.recv_timeout(Duration::from_secs(5)) {
+            panic!("client of chain A should wait for a permit to be refreshed: {res:?}");
+        }
+
+        drop(permit);
+
+        match refresh_a_to_b.recv_timeout(Duration::from_secs(60)) {
+            Ok(Ok(Some(_))) => {}
+            res => panic!("client of chain A should be refreshed: {res:?}"),
+        }
+
+        Ok(())
+    }
+}
+
+/// Refreshes the given client on another thread, under the given limit on the
+/// client updates, and returns the receiver of the outcome of the refresh.
+fn spawn_limited_refresh<DstChain: ChainHandle, SrcChain: ChainHandle>(
+    mut client: ForeignClient<DstChain, SrcChain>,
+    update_limit: Arc<Semaphore>,
+) -> mpsc::Receiver<Result<Option<Vec<IbcEvent>>, String>> {
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        let res = client
+            .refresh_with(|refresh_period| refresh_period, Some(&update_limit), None)
+            .map_err(|e| e.to_string());
+
+        let _ = tx.send(res);
+    });
+
+    rx
+}
+
 // Override the clients `trusting_period` such that the refresh_window is 40 seconds.
 impl TestOverrides for ClientFailsTest {
     fn client_options_a_to_b(&self) -> CreateOptions {
@@ -1,4 +1,8 @@
-use ibc_relayer::chain::requests::{IncludeProof, QueryClientStateRequest, QueryHeight};
+use ibc_relayer::chain::client::ClientSettings;
+use ibc_relayer::chain::client_settings::Settings;
+use ibc_relayer::chain::requests::{
+    IncludeProof, QueryClientStateRequest, QueryClientStatesRequest, QueryHeight,
+};
 use ibc_relayer::client_state::AnyClientState;
 use ibc_relayer::config::ChainConfig;
 use ibc_relayer::foreign_client::CreateOptions;
@@ -19,10 +23,18 @@ fn test_client_options() -> Result<(), Error> {
     run_binary_chain_test(&ClientOptionsTest)
 }
 
+/// A test to exercise the dry run of the creation of a client with custom settings.
+#[test]
+fn test_client_dry_run_create() -> Result<(), Error> {
+    run_binary_chain_test(&ClientDryRunCreateTest)
+}
+
 struct ClientDefaultsTest;
 
 struct ClientOptionsTest;
 
+struct ClientDryRunCreateTest;
+
 impl TestOverrides for ClientDefaultsTest {
     fn modify_relayer_config(&self, config: &mut Config) {
         match &mut config.chains[0] {
@@ -65,6 +77,23 @@ impl BinaryChainTest for ClientDefaultsTest {
         assert_eq!(state.max_clock_drift, Duration::from_secs(14));
         assert_eq!(state.trusting_period, Duration::from_secs(340_000));
         assert_eq!(state.trust_threshold, TrustThreshold::TWO_THIRDS);
+
+        // The configured trusting period and trust threshold are used as is, while the
+        // clock drift of the client adds up the clock drifts of both chains and the
+        // maximum block time of the host chain
+        let settings = effective_client_settings(&chains.foreign_clients.client_a_to_b)?;
+        assert_eq!(settings.max_clock_drift, Duration::from_secs(3 + 6 + 15));
+        assert_eq!(settings.trusting_period, Some(Duration::from_secs(120_000)));
+        assert_eq!(
+            settings.trust_threshold,
+            TrustThreshold::new(13, 23).unwrap()
+        );
+
+        let settings = effective_client_settings(&chains.foreign_clients.client_b_to_a)?;
+        assert_eq!(settings.max_clock_drift, Duration::from_secs(6 + 3 + 5));
+        assert_eq!(settings.trusting_period, Some(Duration::from_secs(340_000)));
+        assert_eq!(settings.trust_threshold, TrustThreshold::TWO_THIRDS);
+
         Ok(())
     }
 }
@@ -109,6 +138,57 @@ impl BinaryChainTest for ClientOptionsTest {
     }
 }
 
+impl TestOverrides for ClientDryRunCreateTest {}
+
+impl BinaryChainTest for ClientDryRunCreateTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        _relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        let clients_b = query_client_count(&chains.handle_b)?;
+
+        let state = chains
+            .foreign_clients
+            .client_a_to_b
+            .dry_run_create(ClientOptionsTest.client_options_a_to_b())
+            .map_err(Error::foreign_client)?;
+
+        #[allow(unreachable_patterns)]
+        let state = match state {
+            AnyClientState::Tendermint(state) => state,
+            _ => unreachable!("unexpected client state type"),
+        };
+
+        assert_eq!(&state.chain_id, *chains.chain_id_a().value());
+        assert_eq!(state.max_clock_drift, Duration::from_secs(3));
+        assert_eq!(state.trusting_period, Duration::from_secs(120_000));
+        assert_eq!(state.trust_threshold, TrustThreshold::new(13, 23).unwrap());
+
+        // No client was created on chain B
+        assert_eq!(query_client_count(&chains.handle_b)?, clients_b);
+
+        Ok(())
+    }
+}
+
+fn effective_client_settings<DstChain: ChainHandle, SrcChain: ChainHandle>(
+    client: &ForeignClient<DstChain, SrcChain>,
+) -> Result<Settings, Error> {
+    let ClientSettings::Tendermint(settings) = client
+        .effective_client_settings()
+        .map_err(Error::foreign_client)?;
+
+    Ok(settings)
+}
+
+fn query_client_count<Chain: ChainHandle>(handle: &Chain) -> Result<usize, Error> {
+    let clients = handle.query_clients(QueryClientStatesRequest { pagination: None })?;
+
+    Ok(clients.len())
+}
+
 fn query_client_state<Chain: ChainHandle>(
     handle: Chain,
     id: &ClientId,
@@ -3,18 +3,27 @@ use time::OffsetDateTime;
 use ibc_test_framework::prelude::*;
 use ibc_test_framework::util::random::random_u128_range;
 
-const CONNECTION_DELAY: Duration = Duration::from_secs(10);
-
 #[test]
 fn test_connection_delay() -> Result<(), Error> {
-    run_binary_channel_test(&ConnectionDelayTest)
+    run_binary_channel_test(&ConnectionDelayTest {
+        delay: Duration::from_secs(10),
+    })
+}
+
+#[test]
+fn test_long_connection_delay() -> Result<(), Error> {
+    run_binary_channel_test(&ConnectionDelayTest {
+        delay: Duration::from_secs(30),
+    })
 }
 
-pub struct ConnectionDelayTest;
+pub struct ConnectionDelayTest {
+    delay: Duration,
+}
 
 impl TestOverrides for ConnectionDelayTest {
     fn connection_delay(&self) -> Duration {
-        CONNECTION_DELAY
+        self.delay
     }
 }
 
@@ -64,6 +73,23 @@ impl BinaryChannelTest for ConnectionDelayTest {
                 &denom_a,
             )?;
 
+            // The packet is not submitted to chain B until the delay has elapsed
+            sleep(self.delay - Duration::from_secs(5));
+
+            let balance_b = chains
+                .node_b
+                .chain_driver()
+                .query_balance(&wallet_b.address(), &denom_b.as_ref())?;
+
+            assert_eq(
+                &format!(
+                    "Expect IBC transfer to not be received before {}s",
+                    self.delay.as_secs()
+                ),
+                &balance_b.amount(),
+                &0u64.into(),
+            )?;
+
             info!(
                 "Waiting for user on chain B to receive IBC transferred amount of {} {}",
                 a_to_b_amount, denom_b
@@ -90,10 +116,10 @@ impl BinaryChannelTest for ConnectionDelayTest {
             assert_gt(
                 &format!(
                     "Expect IBC transfer to only be successful after {}s",
-                    CONNECTION_DELAY.as_secs()
+                    self.delay.as_secs()
                 ),
                 &(time2 - time1).try_into().unwrap(),
-                &CONNECTION_DELAY,
+                &self.delay,
             )?;
 
             Ok(())
@@ -0,0 +1,160 @@
+//! Tests the `create_on_start` connection mode setting, which makes the relayer
+//! create the connection between two chains which host clients of each other
+//! but have no connection yet.
+//!
+//! The test starts the relayer with the clients of chains A and B, but without any
+//! connection between them, and with a path configured between the two chains.
+//! It asserts that the relayer creates the connection on start, with the delay
+//! configured for the path, and then relays a token transfer over a channel on top
+//! of that connection.
+
+use ibc_relayer::chain::requests::QueryClientConnectionsRequest;
+use ibc_relayer::config::{self, ConnectionPath, ModeConfig};
+
+use ibc_test_framework::prelude::*;
+use ibc_test_framework::relayer::channel::{assert_eventually_channel_established, init_channel};
+use ibc_test_framework::relayer::connection::{
+    assert_eventually_connection_established, query_connection_end,
+};
+
+const CONNECTION_DELAY: Duration = Duration::from_secs(3);
+
+#[test]
+fn test_create_connection_on_start() -> Result<(), Error> {
+    run_binary_chain_test(&CreateConnectionOnStartTest)
+}
+
+struct CreateConnectionOnStartTest;
+
+impl TestOverrides for CreateConnectionOnStartTest {
+    fn modify_relayer_config(&self, config: &mut Config) {
+        let path = ConnectionPath {
+            a_chain: config.chains[0].id().clone(),
+            b_chain: config.chains[1].id().clone(),
+            delay: CONNECTION_DELAY,
+        };
+
+        config.mode = ModeConfig {
+            clients: config::Clients {
+                enabled: true,
+                refresh: true,
+                misbehaviour: true,
+                smooth_refresh: false,
+            },
+            connections: config::Connections {
+                enabled: true,
+                create_on_start: true,
+                paths: vec![path],
+            },
+            channels: config::Channels { enabled: true },
+            packets: config::Packets {
+                enabled: true,
+                ..Default::default()
+            },
+        };
+    }
+}
+
+impl BinaryChainTest for CreateConnectionOnStartTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        _relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        let connection_id_a = assert_eventually_succeed(
+            "connection should be created on start",
+            20,
+            Duration::from_secs(1),
+            || {
+                let connection_ids =
+                    chains
+                        .handle_a()
+                        .query_client_connections(QueryClientConnectionsRequest {
+                            client_id: chains.client_id_a().cloned_value(),
+                        })?;
+
+                match connection_ids.as_slice() {
+                    [connection_id] => Ok(DualTagged::new(connection_id.clone())),
+                    _ => Err(Error::generic(eyre!(
+                        "expected a single connection on chain A, got {connection_ids:?}"
+                    ))),
+                }
+            },
+        )?;
+
+        let connection_id_b = assert_eventually_connection_established(
+            chains.handle_a(),
+            chains.handle_b(),
+            &connection_id_a.as_ref(),
+        )?;
+
+        let connection_end_a = query_connection_end(chains.handle_a(), &connection_id_a.as_ref())?;
+
+        assert_eq(
+            "connection should be created with the delay of the path",
+            &connection_end_a.value().delay_period(),
+            &CONNECTION_DELAY,
+        )?;
+
+        let port_a = tagged_transfer_port();
+        let port_b = tagged_transfer_port();
+
+        let (channel_id_b, _) = init_channel(
+            chains.handle_a(),
+            chains.handle_b(),
+            &chains.client_id_a(),
+            &chains.client_id_b(),
+            &connection_id_a.as_ref(),
+            &connection_id_b.as_ref(),
+            &port_a.as_ref(),
+            &port_b.as_ref(),
+        )?;
+
+        let channel_id_a = assert_eventually_channel_established(
+            chains.handle_b(),
+            chains.handle_a(),
+            &channel_id_b.as_ref(),
+            &port_b.as_ref(),
+        )?;
+
+        let denom_a = chains.node_a.denom();
+
+        let denom_b = derive_ibc_denom(
+            &chains.node_b.chain_driver().value().chain_type,
+            &port_b.as_ref(),
+            &channel_id_b.as_ref(),
+            &denom_a,
+        )?;
+
+        let wallet_a = chains.node_a.wallets().user1().cloned();
+        let wallet_b = chains.node_b.wallets().user1().cloned();
+
+        let transfer_amount = 1000u64;
+
+        let balance_a = chains
+            .node_a
+            .chain_driver()
+            .query_balance(&wallet_a.address(), &denom_a)?;
+
+        chains.node_a.chain_driver().ibc_transfer_token(
+            &port_a.as_ref(),
+            &channel_id_a.as_ref(),
+            &wallet_a.as_ref(),
+            &wallet_b.address(),
+            &denom_a.with_amount(transfer_amount).as_ref(),
+        )?;
+
+        chains.node_a.chain_driver().assert_eventual_wallet_amount(
+            &wallet_a.address(),
+            &(balance_a - transfer_amount).as_ref(),
+        )?;
+
+        chains.node_b.chain_driver().assert_eventual_wallet_amount(
+            &wallet_b.address(),
+            &denom_b.with_amount(transfer_amount).as_ref(),
+        )?;
+
+        Ok(())
+    }
+}
@@ -3,7 +3,7 @@ use ibc_relayer::{
         cosmos::query::consensus_state::query_consensus_states,
         requests::{PageRequest, QueryConsensusStateHeightsRequest, QueryConsensusStatesRequest},
     },
-    config::ChainConfig,
+    config::{default::max_grpc_decoding_size, ChainConfig},
 };
 
 use ibc_test_framework::prelude::*;
@@ -81,6 +81,9 @@ impl BinaryChainTest for ConsensusStateHeights {
                     .block_on(query_consensus_states(
                         chains.node_b.chain_id().value(),
                         &grpc_address,
+                        None,
+                        None,
+                        max_grpc_decoding_size().get_bytes() as usize,
                         QueryConsensusStatesRequest {
                             client_id: (*chains.client_id_b().value()).clone(),
                             pagination: Some(PageRequest::all()),
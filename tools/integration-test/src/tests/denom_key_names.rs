@@ -0,0 +1,169 @@
+//! Tests the `denom_key_names` configuration, which allows signing the
+//! `MsgRecvPacket` messages of ICS-20 transfers with a dedicated key per denom.
+//!
+//! The relayer is configured on chain B to receive transfers of chain A's
+//! native token with the `user1` key, and transfers of chain A's other coin
+//! with the `user2` key. The test transfers both denoms from chain A to
+//! chain B and asserts that each `MsgRecvPacket` is signed by its mapped key.
+
+use ibc_relayer::config::ChainConfig;
+use ibc_test_framework::prelude::*;
+use ibc_test_framework::util::random::random_u128_range;
+
+/// The denom of the non-native coin of the chains,
+/// when they are bootstrapped without random identifiers.
+const COIN_DENOM: &str = "samoleans";
+
+const NATIVE_DENOM_KEY: &str = "user1";
+const COIN_DENOM_KEY: &str = "user2";
+
+#[test]
+fn test_denom_key_names() -> Result<(), Error> {
+    run_binary_channel_test(&DenomKeyNamesTest)
+}
+
+pub struct DenomKeyNamesTest;
+
+impl TestOverrides for DenomKeyNamesTest {
+    fn modify_test_config(&self, config: &mut TestConfig) {
+        // Ensure the wallets and the coin denom have predictable names
+        config.bootstrap_with_random_ids = false;
+    }
+
+    fn modify_relayer_config(&self, config: &mut Config) {
+        let native_denom_a = match &config.chains[0] {
+            ChainConfig::CosmosSdk(chain_config) | ChainConfig::Namada(chain_config) => {
+                chain_config.gas_price.preferred().denom.clone()
+            }
+            ChainConfig::Penumbra(_) => panic!("running tests with Penumbra chain not supported"),
+        };
+
+        match &mut config.chains[1] {
+            ChainConfig::CosmosSdk(chain_config) | ChainConfig::Namada(chain_config) => {
+                chain_config
+                    .denom_key_names
+                    .insert(native_denom_a, NATIVE_DENOM_KEY.to_owned());
+
+                chain_config
+                    .denom_key_names
+                    .insert(COIN_DENOM.to_owned(), COIN_DENOM_KEY.to_owned());
+            }
+            ChainConfig::Penumbra(_) => panic!("running tests with Penumbra chain not supported"),
+        }
+    }
+}
+
+impl BinaryChannelTest for DenomKeyNamesTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        config: &TestConfig,
+        _relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        channel: ConnectedChannel<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        let native_denom_a: MonoTagged<ChainA, Denom> =
+            MonoTagged::new(Denom::base(config.native_token(0), config.native_token(0)));
+        let coin_denom_a = chains.node_a.denom();
+
+        assert_eq!(coin_denom_a.value().as_str(), COIN_DENOM);
+
+        let wallet_a = chains.node_a.wallets().user1().cloned();
+        let native_recipient_b = chains.node_b.wallets().user1().cloned();
+        let coin_recipient_b = chains.node_b.wallets().user2().cloned();
+
+        let native_amount = random_u128_range(1000, 5000);
+        let coin_amount = random_u128_range(1000, 5000);
+
+        chains.node_a.chain_driver().ibc_transfer_token(
+            &channel.port_a.as_ref(),
+            &channel.channel_id_a.as_ref(),
+            &wallet_a.as_ref(),
+            &native_recipient_b.address(),
+            &native_denom_a.with_amount(native_amount).as_ref(),
+        )?;
+
+        chains.node_a.chain_driver().ibc_transfer_token(
+            &channel.port_a.as_ref(),
+            &channel.channel_id_a.as_ref(),
+            &wallet_a.as_ref(),
+            &coin_recipient_b.address(),
+            &coin_denom_a.with_amount(coin_amount).as_ref(),
+        )?;
+
+        let native_denom_b = derive_ibc_denom(
+            &chains.node_b.chain_driver().value().chain_type,
+            &channel.port_b.as_ref(),
+            &channel.channel_id_b.as_ref(),
+            &native_denom_a.as_ref(),
+        )?;
+
+        let coin_denom_b = derive_ibc_denom(
+            &chains.node_b.chain_driver().value().chain_type,
+            &channel.port_b.as_ref(),
+            &channel.channel_id_b.as_ref(),
+            &coin_denom_a,
+        )?;
+
+        chains.node_b.chain_driver().assert_eventual_wallet_amount(
+            &native_recipient_b.address(),
+            &native_denom_b.with_amount(native_amount).as_ref(),
+        )?;
+
+        chains.node_b.chain_driver().assert_eventual_wallet_amount(
+            &coin_recipient_b.address(),
+            &coin_denom_b.with_amount(coin_amount).as_ref(),
+        )?;
+
+        let native_signer = query_recv_packet_signer(&chains, &native_recipient_b.address())?;
+        let coin_signer = query_recv_packet_signer(&chains, &coin_recipient_b.address())?;
+
+        assert_eq!(
+            native_signer,
+            chains
+                .node_b
+                .wallets()
+                .user1()
+                .address()
+                .value()
+                .to_string()
+        );
+
+        assert_eq!(
+            coin_signer,
+            chains
+                .node_b
+                .wallets()
+                .user2()
+                .address()
+                .value()
+                .to_string()
+        );
+
+        Ok(())
+    }
+}
+
+/// Returns the signer of the `MsgRecvPacket` which transferred tokens to the given recipient.
+fn query_recv_packet_signer<ChainA: ChainHandle, ChainB: ChainHandle>(
+    chains: &ConnectedChains<ChainA, ChainB>,
+    recipient: &MonoTagged<ChainB, &WalletAddress>,
+) -> Result<String, Error> {
+    let tx_info = chains
+        .node_b
+        .chain_driver()
+        .query_recipient_transactions(recipient)?;
+
+    debug!("looking up MsgRecvPacket signer in {}", tx_info);
+
+    let messages = tx_info["txs"][0]["tx"]["body"]["messages"]
+        .as_array()
+        .ok_or_else(|| eyre!("expect messages array field to be present in JSON"))?;
+
+    let signer = messages
+        .iter()
+        .find(|msg| msg["@type"] == "/ibc.core.channel.v1.MsgRecvPacket")
+        .and_then(|msg| msg["signer"].as_str())
+        .ok_or_else(|| eyre!("expect a MsgRecvPacket with a signer to be present in JSON"))?;
+
+    Ok(signer.to_owned())
+}
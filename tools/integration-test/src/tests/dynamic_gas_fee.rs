@@ -13,16 +13,22 @@
 //! The second test disables the dynamic gas price on both chains in
 //! order to ensure that the first IBC transfer will cost more if dynamic
 //! gas is disabled.
+//!
+//! The third test additionally caps the fee of the transactions on the
+//! second chain with `max_fee`, and asserts that the fee paid by the relayer
+//! for the first IBC transfer does not exceed it.
 
 use ibc_relayer::config::dynamic_gas::DynamicGasPrice;
 use ibc_relayer::config::ChainConfig;
 use ibc_relayer::config::GasPrice;
+use ibc_relayer_types::applications::transfer::Amount;
 use ibc_test_framework::prelude::*;
 
 #[test]
 fn test_dynamic_gas_transfer() -> Result<(), Error> {
     run_binary_channel_test(&DynamicGasTest {
         dynamic_gas_enabled: true,
+        max_fee: None,
     })
 }
 
@@ -30,14 +36,28 @@ fn test_dynamic_gas_transfer() -> Result<(), Error> {
 fn test_static_gas_transfer() -> Result<(), Error> {
     run_binary_channel_test(&DynamicGasTest {
         dynamic_gas_enabled: false,
+        max_fee: None,
+    })
+}
+
+#[test]
+fn test_dynamic_gas_transfer_with_max_fee() -> Result<(), Error> {
+    run_binary_channel_test(&DynamicGasTest {
+        dynamic_gas_enabled: true,
+        max_fee: Some(MAX_FEE),
     })
 }
 
 const MEMO_CHAR: &str = "a";
 const MEMO_SIZE: usize = 10000;
 
+/// Below the fee at the dynamic gas price raised by the multiplier of the
+/// transfer with a big memo, but above its fee at the base gas price.
+const MAX_FEE: u64 = 20000;
+
 pub struct DynamicGasTest {
     dynamic_gas_enabled: bool,
+    max_fee: Option<u64>,
 }
 
 impl TestOverrides for DynamicGasTest {
@@ -49,7 +69,7 @@ impl TestOverrides for DynamicGasTest {
         match &mut config.chains[0] {
             ChainConfig::CosmosSdk(chain_config_a) => {
                 chain_config_a.gas_price =
-                    GasPrice::new(0.1, chain_config_a.gas_price.denom.clone());
+                    GasPrice::new(0.1, chain_config_a.gas_price.preferred().denom.clone()).into();
                 chain_config_a.dynamic_gas_price = DynamicGasPrice::unsafe_new(false, 1.1, 0.6);
             }
             ChainConfig::Namada(_) => {}
@@ -59,9 +79,15 @@ impl TestOverrides for DynamicGasTest {
         match &mut config.chains[1] {
             ChainConfig::CosmosSdk(chain_config_b) => {
                 chain_config_b.gas_price =
-                    GasPrice::new(0.1, chain_config_b.gas_price.denom.clone());
+                    GasPrice::new(0.1, chain_config_b.gas_price.preferred().denom.clone()).into();
                 chain_config_b.dynamic_gas_price =
                     DynamicGasPrice::unsafe_new(self.dynamic_gas_enabled, 1.1, 0.6);
+
+                if let Some(max_fee) = self.max_fee {
+                    chain_config_b.dynamic_gas_price = chain_config_b
+                        .dynamic_gas_price
+                        .with_max_fee(Amount::from(max_fee));
+                }
             }
             ChainConfig::Namada(_) => {}
             ChainConfig::Penumbra(_) => panic!("running tests with Penumbra chain not supported"),
@@ -101,7 +127,7 @@ impl BinaryChannelTest for DynamicGasTest {
             .ok_or_else(|| eyre!("chain configuration is empty"))?
         {
             ChainConfig::CosmosSdk(chain_config) | ChainConfig::Namada(chain_config) => {
-                chain_config.gas_price.denom.clone()
+                chain_config.gas_price.preferred().denom.clone()
             }
             ChainConfig::Penumbra(_) => panic!("running tests with Penumbra chain not supported"),
         };
@@ -113,7 +139,7 @@ impl BinaryChannelTest for DynamicGasTest {
             .ok_or_else(|| eyre!("chain configuration is empty"))?
         {
             ChainConfig::CosmosSdk(chain_config) | ChainConfig::Namada(chain_config) => {
-                chain_config.gas_price.denom.clone()
+                chain_config.gas_price.preferred().denom.clone()
             }
             ChainConfig::Penumbra(_) => panic!("running tests with Penumbra chain not supported"),
         };
@@ -141,6 +167,7 @@ impl BinaryChannelTest for DynamicGasTest {
                 &denom_a.with_amount(a_to_b_amount).as_ref(),
                 Some(memo),
                 None,
+                None,
             )?;
 
         // Do a simple IBC transfer with the dynamic gas configuration
@@ -227,6 +254,13 @@ impl BinaryChannelTest for DynamicGasTest {
 
         info!("paid gas fees for Tx with memo `{tx1_paid_gas_relayer}`, without memo `{tx2_paid_gas_relayer}`");
 
+        if let Some(max_fee) = self.max_fee {
+            assert!(
+                tx1_paid_gas_relayer <= Amount::from(max_fee),
+                "with `max_fee` set, gas paid for the first TX should not exceed it"
+            );
+        }
+
         if self.dynamic_gas_enabled {
             assert!(
                 tx1_paid_gas_relayer < tx2_paid_gas_relayer,
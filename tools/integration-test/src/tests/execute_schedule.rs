@@ -15,7 +15,7 @@
 use ibc_test_framework::prelude::*;
 use ibc_test_framework::util::random::random_u128_range;
 
-use ibc_relayer::link::{Link, LinkParameters};
+use ibc_relayer::link::{Link, LinkParameters, SequenceRange};
 
 /// The number of messages to be sent in a batch contained in a piece of operational data.
 const BATCH_SIZE: usize = 10;
@@ -50,6 +50,9 @@ impl BinaryChannelTest for ExecuteScheduleTest {
             max_memo_size: packet_config.ics20_max_memo_size,
             max_receiver_size: packet_config.ics20_max_receiver_size,
             exclude_src_sequences: vec![],
+            max_clear_duration: None,
+            verification: Default::default(),
+            clear_order: Default::default(),
         };
 
         let chain_a_link = Link::new_from_opts(
@@ -72,8 +75,11 @@ impl BinaryChannelTest for ExecuteScheduleTest {
                 &chains.node_a.denom().with_amount(amount1).as_ref(),
             )?;
 
-            relay_path_a_to_b
-                .schedule_packet_clearing(None, relayer.config.mode.packets.clear_limit)?;
+            relay_path_a_to_b.schedule_packet_clearing(
+                None,
+                relayer.config.mode.packets.clear_limit,
+                SequenceRange::all(),
+            )?;
 
             info!("Performing IBC send packet with a token transfer #{} from chain A to be received by chain B", i);
         }
@@ -0,0 +1,125 @@
+//! Tests that the channel workers register the counterparty payees of the
+//! channels they open when the `auto_register_counterparty_payee` configuration
+//! option is toggled on, without waiting for the packet workers to start.
+//!
+//! The test opens a fee-enabled transfer channel with only the channel workers
+//! enabled, and checks that the relayer address on each chain is eventually
+//! registered as the counterparty payee of the channel end on the other chain.
+//! Opening a channel without fees leaves the counterparty payees unregistered.
+
+use ibc_relayer_types::core::ics04_channel::version::Version;
+use ibc_test_framework::prelude::*;
+use ibc_test_framework::relayer::channel::{
+    assert_eventually_channel_established, init_channel_version,
+};
+
+#[test]
+fn test_auto_register_payee_on_channel_open() -> Result<(), Error> {
+    run_binary_connection_test(&AutoRegisterPayeeTest { with_fee: true })
+}
+
+#[test]
+fn test_no_auto_register_payee_on_non_fee_channel() -> Result<(), Error> {
+    run_binary_connection_test(&AutoRegisterPayeeTest { with_fee: false })
+}
+
+struct AutoRegisterPayeeTest {
+    with_fee: bool,
+}
+
+impl TestOverrides for AutoRegisterPayeeTest {
+    fn modify_relayer_config(&self, config: &mut Config) {
+        config.mode.channels.enabled = true;
+        config.mode.packets.enabled = false;
+        config.mode.packets.auto_register_counterparty_payee = true;
+    }
+
+    fn should_spawn_supervisor(&self) -> bool {
+        false
+    }
+}
+
+impl BinaryConnectionTest for AutoRegisterPayeeTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        connection: ConnectedConnection<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        let chain_driver_a = chains.node_a.chain_driver();
+        let chain_driver_b = chains.node_b.chain_driver();
+
+        let relayer_a = chains.node_a.wallets().relayer().cloned();
+        let relayer_b = chains.node_b.wallets().relayer().cloned();
+
+        let port_a = tagged_transfer_port();
+        let port_b = tagged_transfer_port();
+
+        let version = if self.with_fee {
+            Version::ics20_with_fee()
+        } else {
+            Version::ics20()
+        };
+
+        let (channel_id_b, _) = init_channel_version(
+            &chains.handle_a,
+            &chains.handle_b,
+            &chains.client_id_a(),
+            &chains.client_id_b(),
+            &connection.connection_id_a.as_ref(),
+            &connection.connection_id_b.as_ref(),
+            &port_a.as_ref(),
+            &port_b.as_ref(),
+            version,
+        )?;
+
+        relayer.with_supervisor(|| {
+            let channel_id_a = assert_eventually_channel_established(
+                &chains.handle_b,
+                &chains.handle_a,
+                &channel_id_b.as_ref(),
+                &port_b.as_ref(),
+            )?;
+
+            if !self.with_fee {
+                // Leave some time to the channel worker to wrap up after the handshake
+                sleep(Duration::from_secs(5));
+
+                let counterparty_payee = chain_driver_b
+                    .query_counterparty_payee(&channel_id_b.as_ref(), &relayer_b.address())?;
+
+                return assert_eq(
+                    "counterparty payee should not be registered on a non-fee channel",
+                    &counterparty_payee,
+                    &None,
+                );
+            }
+
+            assert_eventually_succeed(
+                "counterparty payees should eventually be registered on both chains",
+                20,
+                Duration::from_secs(2),
+                || {
+                    let counterparty_payee_b = chain_driver_b
+                        .query_counterparty_payee(&channel_id_b.as_ref(), &relayer_b.address())?;
+
+                    assert_eq(
+                        "counterparty payee on chain B should be the relayer address on chain A",
+                        &counterparty_payee_b,
+                        &Some(relayer_a.address().cloned()),
+                    )?;
+
+                    let counterparty_payee_a = chain_driver_a
+                        .query_counterparty_payee(&channel_id_a.as_ref(), &relayer_a.address())?;
+
+                    assert_eq(
+                        "counterparty payee on chain A should be the relayer address on chain B",
+                        &counterparty_payee_a,
+                        &Some(relayer_b.address().cloned()),
+                    )
+                },
+            )
+        })
+    }
+}
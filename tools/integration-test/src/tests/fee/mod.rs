@@ -1,4 +1,5 @@
 pub mod auto_forward_relayer;
+pub mod auto_register_payee;
 pub mod filter_fees;
 pub mod forward_relayer;
 pub mod no_forward_relayer;
@@ -86,7 +86,7 @@ impl BinaryChannelTest for FeeGrantTest {
             .ok_or_else(|| eyre!("chain configuration is empty"))?
         {
             ChainConfig::CosmosSdk(chain_config) | ChainConfig::Namada(chain_config) => {
-                chain_config.gas_price.denom.clone()
+                chain_config.gas_price.preferred().denom.clone()
             }
             ChainConfig::Penumbra(_) => panic!("running tests with Penumbra chain not supported"),
         };
@@ -240,7 +240,7 @@ impl BinaryChannelTest for NoFeeGrantTest {
             .ok_or_else(|| eyre!("chain configuration is empty"))?
         {
             ChainConfig::CosmosSdk(chain_config) | ChainConfig::Namada(chain_config) => {
-                chain_config.gas_price.denom.clone()
+                chain_config.gas_price.preferred().denom.clone()
             }
             ChainConfig::Penumbra(_) => panic!("running tests with Penumbra chain not supported"),
         };
@@ -0,0 +1,180 @@
+//! Tests the `relay_fees_spent` telemetry counter, which records the fees spent
+//! by the relayer on confirmed transactions.
+//!
+//! The test transfers a few packets from chain A to chain B, and asserts that
+//! the fees recorded for the `MsgRecvPacket` messages relayed on chain B match
+//! the fees of the transactions broadcast by the relayer on chain B.
+
+use std::str::FromStr;
+
+use ibc_relayer::chain::cosmos::fees_spent::fees_spent_from_tx;
+use ibc_relayer::chain::cosmos::query::tx::query_txs_by_sender;
+use ibc_relayer::chain::cosmos::request_rate::PacedRpcClient;
+use ibc_relayer::config::ChainConfig;
+use ibc_relayer_types::core::ics04_channel::msgs::recv_packet;
+use ibc_relayer_types::signer::Signer;
+use ibc_test_framework::prelude::*;
+use ibc_test_framework::util::random::random_u128_range;
+use tendermint_rpc::HttpClient;
+
+const PACKET_COUNT: usize = 3;
+
+#[test]
+fn test_relay_fees_spent() -> Result<(), Error> {
+    run_binary_channel_test(&RelayFeesSpentTest)
+}
+
+pub struct RelayFeesSpentTest;
+
+impl TestOverrides for RelayFeesSpentTest {
+    fn modify_relayer_config(&self, config: &mut Config) {
+        // Fees are only recorded once transactions are confirmed
+        config.mode.packets.tx_confirmation = true;
+    }
+}
+
+impl BinaryChannelTest for RelayFeesSpentTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        channel: ConnectedChannel<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        let (rpc_addr_b, fee_denom_b) = match &relayer.config.chains[1] {
+            ChainConfig::CosmosSdk(chain_config) | ChainConfig::Namada(chain_config) => (
+                chain_config.rpc_addr.clone(),
+                chain_config.gas_price.preferred().denom.clone(),
+            ),
+            ChainConfig::Penumbra(_) => panic!("running tests with Penumbra chain not supported"),
+        };
+
+        let denom_a = chains.node_a.denom();
+        let wallet_a = chains.node_a.wallets().user1().cloned();
+        let wallet_b = chains.node_b.wallets().user1().cloned();
+
+        let denom_b = derive_ibc_denom(
+            &chains.node_b.chain_driver().value().chain_type,
+            &channel.port_b.as_ref(),
+            &channel.channel_id_b.as_ref(),
+            &denom_a,
+        )?;
+
+        let mut total_amount = 0;
+
+        for _ in 0..PACKET_COUNT {
+            let amount = random_u128_range(1000, 5000);
+            total_amount += amount;
+
+            chains.node_a.chain_driver().ibc_transfer_token(
+                &channel.port_a.as_ref(),
+                &channel.channel_id_a.as_ref(),
+                &wallet_a.as_ref(),
+                &wallet_b.address(),
+                &denom_a.with_amount(amount).as_ref(),
+            )?;
+        }
+
+        chains.node_b.chain_driver().assert_eventual_wallet_amount(
+            &wallet_b.address(),
+            &denom_b.with_amount(total_amount).as_ref(),
+        )?;
+
+        let rpc_client = PacedRpcClient::new(
+            HttpClient::new(rpc_addr_b.clone())
+                .map_err(|e| eyre!("failed to create RPC client: {e}"))?,
+            None,
+        );
+
+        let relayer_address_b = chains
+            .node_b
+            .wallets()
+            .relayer()
+            .address()
+            .value()
+            .to_string();
+        let relayer_b = Signer::from_str(&relayer_address_b)
+            .map_err(|e| eyre!("invalid relayer address {relayer_address_b}: {e}"))?;
+
+        let chain_id_b = chains.chain_id_b().value().to_string();
+        let channel_id_b = channel.channel_id_b.value().to_string();
+
+        assert_eventually_succeed(
+            "relay fees spent on MsgRecvPacket are recorded",
+            20,
+            Duration::from_secs(1),
+            || {
+                let txs =
+                    chains
+                        .node_b
+                        .chain_driver()
+                        .value()
+                        .runtime
+                        .block_on(query_txs_by_sender(
+                            &rpc_client,
+                            &rpc_addr_b,
+                            &relayer_b,
+                            100,
+                        ))?;
+
+                let mut broadcast_fees = 0;
+
+                for tx in txs {
+                    broadcast_fees += fees_spent_from_tx(&tx.tx)?
+                        .into_iter()
+                        .filter(|fee| {
+                            fee.msg_type == recv_packet::TYPE_URL
+                                && fee.channel == channel_id_b
+                                && fee.denom == fee_denom_b
+                        })
+                        .map(|fee| fee.amount)
+                        .sum::<u128>();
+                }
+
+                let recorded_fees =
+                    recorded_recv_packet_fees(&chain_id_b, &channel_id_b, &fee_denom_b);
+
+                info!(
+                    "fees spent on MsgRecvPacket: broadcast {broadcast_fees}, recorded {recorded_fees}"
+                );
+
+                if broadcast_fees > 0 && recorded_fees == broadcast_fees {
+                    Ok(())
+                } else {
+                    Err(Error::generic(eyre!(
+                        "expected recorded fees {recorded_fees} to match broadcast fees {broadcast_fees}"
+                    )))
+                }
+            },
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Sums up the `relay_fees_spent` counters of the `MsgRecvPacket` messages
+/// relayed on the given chain and channel.
+fn recorded_recv_packet_fees(chain_id: &str, channel_id: &str, denom: &str) -> u128 {
+    let expected_labels = [
+        ("chain", chain_id),
+        ("channel", channel_id),
+        ("msg_type", recv_packet::TYPE_URL),
+        ("denom", denom),
+    ];
+
+    ibc_telemetry::global()
+        .gather()
+        .iter()
+        .filter(|family| family.get_name().starts_with("relay_fees_spent"))
+        .flat_map(|family| family.get_metric())
+        .filter(|metric| {
+            expected_labels.iter().all(|(name, value)| {
+                metric
+                    .get_label()
+                    .iter()
+                    .any(|label| label.get_name() == *name && label.get_value() == *value)
+            })
+        })
+        .map(|metric| metric.get_counter().get_value() as u128)
+        .sum()
+}
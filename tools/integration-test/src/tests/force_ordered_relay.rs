@@ -0,0 +1,163 @@
+//! Tests that the packets sent on an unordered channel with `force_ordered_relay`
+//! set are relayed one at a time in the order of their sequence, each transaction
+//! being committed before the next one is submitted.
+//!
+//! The test sends a few transfers from chain A, and then clears them through the
+//! relay path of the channel, which is configured to clear the newest packets first.
+//! It asserts that the packets are received on chain B in the order of their
+//! sequence, each in a later block than the one before it, whereas they would
+//! otherwise be received in a single transaction.
+
+use std::collections::BTreeMap;
+
+use ibc_relayer::chain::requests::Qualified;
+use ibc_relayer::config::{ChainConfig, ChannelOverrides, ClearOrder};
+use ibc_relayer::link::packet_events::query_write_ack_events;
+use ibc_relayer::link::{Link, LinkParameters, SequenceRange};
+use ibc_relayer::path::PathIdentifiers;
+use ibc_relayer_types::core::ics04_channel::packet::Sequence;
+use ibc_test_framework::prelude::*;
+
+/// The number of transfers relayed by the test.
+const TRANSFERS: u64 = 4;
+
+#[test]
+fn test_force_ordered_relay() -> Result<(), Error> {
+    run_binary_channel_test(&ForceOrderedRelayTest)
+}
+
+struct ForceOrderedRelayTest;
+
+impl TestOverrides for ForceOrderedRelayTest {
+    fn modify_relayer_config(&self, config: &mut Config) {
+        // The channel of the test is the first one opened on chain A
+        let overrides = ChannelOverrides {
+            force_ordered_relay: true,
+            ..Default::default()
+        };
+
+        match &mut config.chains[0] {
+            ChainConfig::CosmosSdk(chain_config) | ChainConfig::Namada(chain_config) => {
+                chain_config.channel_overrides = BTreeMap::from([(ChannelId::new(0), overrides)]);
+            }
+            ChainConfig::Penumbra(_) => panic!("running tests with Penumbra chain not supported"),
+        }
+    }
+
+    fn should_spawn_supervisor(&self) -> bool {
+        false
+    }
+}
+
+impl BinaryChannelTest for ForceOrderedRelayTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        channel: ConnectedChannel<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        assert_eq(
+            "the channel of the test should be the one configured",
+            channel.channel_id_a.value(),
+            &ChannelId::new(0),
+        )?;
+
+        let packet_config = relayer.config.mode.packets;
+
+        let denom_a = chains.node_a.denom();
+        let amount = 1000u64;
+
+        for _ in 0..TRANSFERS {
+            chains.node_a.chain_driver().ibc_transfer_token(
+                &channel.port_a.as_ref(),
+                &channel.channel_id_a.as_ref(),
+                &chains.node_a.wallets().user1(),
+                &chains.node_b.wallets().user1().address(),
+                &denom_a.with_amount(amount).as_ref(),
+            )?;
+        }
+
+        let link = Link::new_from_opts(
+            chains.handle_a().clone(),
+            chains.handle_b().clone(),
+            LinkParameters {
+                src_port_id: channel.port_a.value().clone(),
+                src_channel_id: channel.channel_id_a.value().clone(),
+                max_memo_size: packet_config.ics20_max_memo_size,
+                max_receiver_size: packet_config.ics20_max_receiver_size,
+                exclude_src_sequences: vec![],
+                max_clear_duration: None,
+                verification: Default::default(),
+                clear_order: ClearOrder::NewestFirst,
+            },
+            false,
+            false,
+        )?;
+
+        let mut relay_path_a_to_b = link.a_to_b;
+
+        relay_path_a_to_b.schedule_packet_clearing(
+            None,
+            packet_config.clear_limit,
+            SequenceRange::all(),
+        )?;
+
+        relay_path_a_to_b.execute_schedule()?;
+
+        let denom_b = derive_ibc_denom(
+            &chains.node_b.chain_driver().value().chain_type,
+            &channel.port_b.as_ref(),
+            &channel.channel_id_b.as_ref(),
+            &denom_a,
+        )?;
+
+        chains.node_b.chain_driver().assert_eventual_wallet_amount(
+            &chains.node_b.wallets().user1().address(),
+            &denom_b.with_amount(amount * TRANSFERS).as_ref(),
+        )?;
+
+        // The packets sent from chain A are acknowledged on chain B
+        let path = PathIdentifiers {
+            port_id: channel.port_a.value().clone(),
+            channel_id: channel.channel_id_a.value().clone(),
+            counterparty_port_id: channel.port_b.value().clone(),
+            counterparty_channel_id: channel.channel_id_b.value().clone(),
+        };
+
+        let sequences: Vec<Sequence> = (1..=TRANSFERS).map(Sequence::from).collect();
+
+        let write_acks = query_write_ack_events(
+            chains.handle_b(),
+            &path,
+            &sequences,
+            Qualified::SmallerEqual(chains.handle_b().query_latest_height()?),
+        )?;
+
+        let mut received_at = write_acks
+            .iter()
+            .filter_map(|ack| Some((ack.event.packet()?.sequence, ack.height)))
+            .collect::<Vec<_>>();
+
+        received_at.sort();
+
+        assert_eq(
+            "all the packets should be received",
+            &received_at.len(),
+            &sequences.len(),
+        )?;
+
+        for pair in received_at.windows(2) {
+            let ((previous, previous_height), (sequence, height)) = (pair[0], pair[1]);
+
+            if height <= previous_height {
+                return Err(Error::generic(eyre!(
+                    "packet {sequence} should be received after packet {previous}, \
+                    got heights {height} and {previous_height}"
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -26,7 +26,10 @@ struct IbcForwardHopTransferTestOverrides;
 impl TestOverrides for IbcForwardHopTransferTestOverrides {
     fn modify_relayer_config(&self, config: &mut Config) {
         config.mode = ModeConfig {
-            connections: config::Connections { enabled: false },
+            connections: config::Connections {
+                enabled: false,
+                ..Default::default()
+            },
             channels: config::Channels { enabled: false },
             ..Default::default()
         };
@@ -132,6 +135,7 @@ impl NaryChannelTest<4> for IbcForwardHopTransferTest {
                 &denom_a.with_amount(a_to_d_amount).as_ref(),
                 Some(memo),
                 None,
+                None,
             )?;
 
         info!(
@@ -257,6 +261,7 @@ impl NaryChannelTest<4> for AtomicIbcForwardHopTransferTest {
                 &denom_a.with_amount(a_to_d_amount).as_ref(),
                 Some(memo),
                 None,
+                None,
             )?;
 
         info!("checking that the sender was refunded and other chains didn't receive tokens");
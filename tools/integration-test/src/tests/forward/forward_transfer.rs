@@ -46,7 +46,10 @@ struct IbcForwardTransferTestOverrides;
 impl TestOverrides for IbcForwardTransferTestOverrides {
     fn modify_relayer_config(&self, config: &mut Config) {
         config.mode = ModeConfig {
-            connections: config::Connections { enabled: false },
+            connections: config::Connections {
+                enabled: false,
+                ..Default::default()
+            },
             channels: config::Channels { enabled: false },
             ..Default::default()
         };
@@ -137,6 +140,7 @@ impl NaryChannelTest<3> for IbcForwardTransferTest {
                 &denom_a.with_amount(a_to_c_amount).as_ref(),
                 Some(memo),
                 None,
+                None,
             )?;
 
         info!(
@@ -275,6 +279,7 @@ impl NaryChannelTest<3> for MisspelledMemoFieldsIbcForwardTransferTest {
                     &denom_a.with_amount(a_to_c_amount).as_ref(),
                     Some(memo1),
                     None,
+                    None,
                 )?;
 
             // Wait before checking the balances
@@ -312,6 +317,7 @@ impl NaryChannelTest<3> for MisspelledMemoFieldsIbcForwardTransferTest {
                     &denom_a.with_amount(a_to_c_amount).as_ref(),
                     Some(memo2),
                     None,
+                    None,
                 )?;
 
             // Wait before checking the balances
@@ -349,6 +355,7 @@ impl NaryChannelTest<3> for MisspelledMemoFieldsIbcForwardTransferTest {
                     &denom_a.with_amount(a_to_c_amount).as_ref(),
                     Some(memo3),
                     None,
+                    None,
                 )?;
 
             info!("checking that the sender was refunded and other chains didn't receive tokens");
@@ -386,6 +393,7 @@ impl NaryChannelTest<3> for MisspelledMemoFieldsIbcForwardTransferTest {
                     &denom_a.with_amount(a_to_c_amount).as_ref(),
                     Some(memo4),
                     None,
+                    None,
                 )?;
 
             info!(
@@ -512,6 +520,7 @@ impl NaryChannelTest<3> for MisspelledMemoContentIbcForwardTransferTest {
                     &denom_a.with_amount(a_to_c_amount).as_ref(),
                     Some(memo2),
                     None,
+                    None,
                 )?;
 
             // Wait before checking the balances
@@ -550,6 +559,7 @@ impl NaryChannelTest<3> for MisspelledMemoContentIbcForwardTransferTest {
                     &denom_a.with_amount(a_to_c_amount).as_ref(),
                     Some(memo3),
                     None,
+                    None,
                 )?;
 
             // Wait before checking the balances
@@ -588,6 +598,7 @@ impl NaryChannelTest<3> for MisspelledMemoContentIbcForwardTransferTest {
                     &denom_a.with_amount(a_to_c_amount).as_ref(),
                     Some(memo1),
                     None,
+                    None,
                 )?;
 
             // Wait before checking the balances
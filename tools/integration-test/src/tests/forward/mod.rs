@@ -1,3 +1,4 @@
 pub mod forward_hop_transfer;
 pub mod forward_transfer;
 pub mod memo;
+pub mod relay_forwarded_packets;
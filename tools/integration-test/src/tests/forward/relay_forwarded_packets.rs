@@ -0,0 +1,174 @@
+//! Tests that the packets forwarded by the packet forwarding middleware of an
+//! intermediary chain upon receiving a packet are relayed onward as soon as the
+//! relayer confirms the transaction receiving the packet.
+//!
+//! The event source of every chain only subscribes to `MsgTransfer` transactions,
+//! and packet clearing is disabled, so that the `SendPacket` event of the forwarded
+//! packet, which is emitted by a `MsgRecvPacket` transaction, can only reach the
+//! supervisor by being handed over by the packet worker relaying the first leg.
+//!
+//! With the default event queries, the `SendPacket` event of the forwarded packet
+//! reaches the supervisor both ways, and the packet must still be relayed once.
+
+use core::str::FromStr;
+
+use ibc_relayer::config::types::event_query::EventQuery;
+use ibc_relayer::config::{self, ChainConfig, EventSourceMode, ModeConfig};
+use ibc_test_framework::prelude::*;
+use tendermint_rpc::query::Query;
+
+use crate::tests::forward::memo::{MemoField, MemoInfo};
+
+#[test]
+fn test_relay_forwarded_packets() -> Result<(), Error> {
+    run_nary_channel_test(&RelayForwardedPacketsTest {
+        transfer_events_only: true,
+    })
+}
+
+#[test]
+fn test_relay_forwarded_packets_with_event_source() -> Result<(), Error> {
+    run_nary_channel_test(&RelayForwardedPacketsTest {
+        transfer_events_only: false,
+    })
+}
+
+struct RelayForwardedPacketsTest {
+    transfer_events_only: bool,
+}
+
+impl TestOverrides for RelayForwardedPacketsTest {
+    fn modify_relayer_config(&self, config: &mut Config) {
+        config.mode = ModeConfig {
+            connections: config::Connections {
+                enabled: false,
+                ..Default::default()
+            },
+            channels: config::Channels { enabled: false },
+            packets: config::Packets {
+                enabled: true,
+                clear_interval: 0,
+                clear_on_start: false,
+                tx_confirmation: true,
+                relay_forwarded_packets: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        if !self.transfer_events_only {
+            return;
+        }
+
+        let transfer_query =
+            Query::from_str("message.action = '/ibc.applications.transfer.v1.MsgTransfer'")
+                .unwrap();
+
+        for chain_config in config.chains.iter_mut() {
+            match chain_config {
+                ChainConfig::CosmosSdk(chain_config) | ChainConfig::Namada(chain_config) => {
+                    if let EventSourceMode::Push { queries, .. } = &mut chain_config.event_source {
+                        *queries = vec![EventQuery::new(transfer_query.clone())];
+                    }
+                }
+                ChainConfig::Penumbra(_) => {
+                    panic!("running tests with Penumbra chain not supported")
+                }
+            }
+        }
+    }
+
+    fn modify_test_config(&self, config: &mut TestConfig) {
+        config.bootstrap_with_random_ids = false;
+    }
+
+    fn should_spawn_supervisor(&self) -> bool {
+        true
+    }
+}
+
+impl PortsOverride<3> for RelayForwardedPacketsTest {}
+
+impl NaryChannelTest<3> for RelayForwardedPacketsTest {
+    fn run<Handle: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        _relayer: RelayerDriver,
+        chains: NaryConnectedChains<Handle, 3>,
+        channels: NaryConnectedChannels<Handle, 3>,
+    ) -> Result<(), Error> {
+        let connected_chains = chains.connected_chains_at::<0, 1>()?;
+
+        let node_a = chains.full_node_at::<0>()?;
+        let node_b = chains.full_node_at::<1>()?;
+        let node_c = chains.full_node_at::<2>()?;
+
+        let channel_a_to_b = channels.channel_at::<0, 1>()?;
+        let channel_b_to_c = channels.channel_at::<1, 2>()?;
+
+        let denom_a = connected_chains.node_a.denom();
+
+        let denom_b = derive_ibc_denom(
+            &node_b.chain_driver().value().chain_type,
+            &channel_a_to_b.port_b.as_ref(),
+            &channel_a_to_b.channel_id_b.as_ref(),
+            &denom_a,
+        )?;
+
+        let denom_a_to_c = derive_ibc_denom(
+            &node_c.chain_driver().value().chain_type,
+            &channel_b_to_c.port_b.as_ref(),
+            &channel_b_to_c.channel_id_b.as_ref(),
+            &denom_b.as_ref(),
+        )?;
+
+        let wallets_a = node_a.wallets();
+        let wallet_a = wallets_a.user1();
+
+        let wallets_b = node_b.wallets();
+        let wallet_b = wallets_b.user1();
+
+        let wallets_c = node_c.wallets();
+        let wallet_c = wallets_c.user1();
+
+        let a_to_c_amount = 4000_u128;
+
+        let memo_field: MemoField<MemoInfo> = MemoField::new(
+            wallet_c.address().value().to_string(),
+            channel_b_to_c.port_a.to_string(),
+            channel_b_to_c.channel.a_channel_id().unwrap().to_string(),
+        );
+        let memo = serde_json::to_string(&memo_field).unwrap();
+
+        node_a
+            .chain_driver()
+            .ibc_transfer_token_with_memo_and_timeout(
+                &channel_a_to_b.port_a.as_ref(),
+                &channel_a_to_b.channel_id_a.as_ref(),
+                &wallet_a,
+                &wallet_b.address(),
+                &denom_a.with_amount(a_to_c_amount).as_ref(),
+                Some(memo),
+                None,
+                None,
+            )?;
+
+        info!(
+            "waiting for user on chain C to receive the forwarded amount of {}",
+            a_to_c_amount
+        );
+
+        node_c.chain_driver().assert_eventual_wallet_amount(
+            &wallet_c.address(),
+            &denom_a_to_c.with_amount(a_to_c_amount).as_ref(),
+        )?;
+
+        info!(
+            "successfully relayed the packet forwarded by chain {} to chain {}",
+            chains.chain_handle_at::<1>().unwrap().value(),
+            chains.chain_handle_at::<2>().unwrap().value(),
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,135 @@
+//! Tests that historical gRPC queries pass their height in the header configured
+//! through `grpc_height_header`.
+//!
+//! Chain A is configured with the header understood by the nodes of the test, and
+//! chain B with a header unknown to them. The test sends a transfer from each chain
+//! and queries the packet commitments of its channel at a height preceding the
+//! transfer. The query on chain A returns the commitments at that height, ie. none,
+//! while the node of chain B ignores the header and answers at its latest height.
+
+use ibc_relayer::chain::requests::{Paginate, QueryHeight, QueryPacketCommitmentsRequest};
+use ibc_relayer::config::{ChainConfig, GrpcHeightHeader};
+use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, PortId};
+use ibc_test_framework::prelude::*;
+
+#[test]
+fn test_grpc_height_header() -> Result<(), Error> {
+    run_binary_channel_test(&GrpcHeightHeaderTest)
+}
+
+struct GrpcHeightHeaderTest;
+
+impl TestOverrides for GrpcHeightHeaderTest {
+    fn modify_relayer_config(&self, config: &mut Config) {
+        for (i, chain_config) in config.chains.iter_mut().enumerate() {
+            if let ChainConfig::CosmosSdk(chain_config) = chain_config {
+                chain_config.grpc_height_header = if i == 0 {
+                    GrpcHeightHeader::default()
+                } else {
+                    GrpcHeightHeader {
+                        name: "x-unknown-block-height".to_string(),
+                        ..GrpcHeightHeader::default()
+                    }
+                };
+            }
+        }
+    }
+
+    fn should_spawn_supervisor(&self) -> bool {
+        false
+    }
+}
+
+impl BinaryChannelTest for GrpcHeightHeaderTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        _relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        channel: ConnectedChannel<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        let height_before_a = chains.handle_a().query_latest_height()?;
+        let height_before_b = chains.handle_b().query_latest_height()?;
+
+        chains.node_a.chain_driver().ibc_transfer_token(
+            &channel.port_a.as_ref(),
+            &channel.channel_id_a.as_ref(),
+            &chains.node_a.wallets().user1(),
+            &chains.node_b.wallets().user1().address(),
+            &chains.node_a.denom().with_amount(1000u64).as_ref(),
+        )?;
+
+        chains.node_b.chain_driver().ibc_transfer_token(
+            &channel.port_b.as_ref(),
+            &channel.channel_id_b.as_ref(),
+            &chains.node_b.wallets().user1(),
+            &chains.node_a.wallets().user1().address(),
+            &chains.node_b.denom().with_amount(1000u64).as_ref(),
+        )?;
+
+        let port_a = channel.port_a.value();
+        let channel_id_a = channel.channel_id_a.value();
+        let port_b = channel.port_b.value();
+        let channel_id_b = channel.channel_id_b.value();
+
+        assert_eventually_succeed(
+            "the transfers should be committed on both chains",
+            30,
+            Duration::from_secs(1),
+            || {
+                let latest_a =
+                    commitments_at(chains.handle_a(), port_a, channel_id_a, QueryHeight::Latest)?;
+                let latest_b =
+                    commitments_at(chains.handle_b(), port_b, channel_id_b, QueryHeight::Latest)?;
+
+                if latest_a == 1 && latest_b == 1 {
+                    Ok(())
+                } else {
+                    Err(Error::generic(eyre!(
+                        "expected one commitment on each chain, got {latest_a} and {latest_b}"
+                    )))
+                }
+            },
+        )?;
+
+        assert_eq(
+            "the historical query with the configured header should be at height",
+            &commitments_at(
+                chains.handle_a(),
+                port_a,
+                channel_id_a,
+                QueryHeight::Specific(height_before_a),
+            )?,
+            &0,
+        )?;
+
+        assert_eq(
+            "the historical query with an unknown header should be at the latest height",
+            &commitments_at(
+                chains.handle_b(),
+                port_b,
+                channel_id_b,
+                QueryHeight::Specific(height_before_b),
+            )?,
+            &1,
+        )?;
+
+        Ok(())
+    }
+}
+
+fn commitments_at<Chain: ChainHandle>(
+    chain: &Chain,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    query_height: QueryHeight,
+) -> Result<usize, Error> {
+    let (commitments, _) = chain.query_packet_commitments(QueryPacketCommitmentsRequest {
+        query_height,
+        port_id: port_id.clone(),
+        channel_id: channel_id.clone(),
+        pagination: Paginate::All,
+    })?;
+
+    Ok(commitments.len())
+}
@@ -42,6 +42,19 @@ fn test_ica_filter_allow() -> Result<(), Error> {
     )))
 }
 
+/// The ICA channels are discovered and relayed without being listed in the filter,
+/// despite an allow list which only contains an exact, unrelated channel.
+#[test]
+fn test_ica_filter_include_ica_channels() -> Result<(), Error> {
+    run_binary_connection_test(&IcaFilterTestAllow::new(
+        PacketFilter::allow(vec![(
+            FilterPattern::Exact(PortId::transfer()),
+            FilterPattern::Exact(ChannelId::new(0)),
+        )])
+        .with_ica_channels(true),
+    ))
+}
+
 #[test]
 fn test_ica_filter_deny() -> Result<(), Error> {
     run_binary_connection_test(&IcaFilterTestDeny)
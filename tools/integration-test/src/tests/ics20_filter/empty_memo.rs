@@ -0,0 +1,128 @@
+//! Tests the `skip_empty_memo_channels` configuration, which prevents Hermes from
+//! relaying ICS-20 transfers with an empty memo on channels of applications which
+//! reject them.
+//!
+//! An empty-memo transfer from chain A to chain B is expected to be skipped,
+//! while a transfer with a memo on the same channel is expected to be relayed.
+
+use ibc_relayer::config::ChainConfig;
+use ibc_relayer_types::core::ics24_host::identifier::ChannelId;
+use ibc_test_framework::prelude::*;
+
+#[test]
+fn test_skip_empty_memo() -> Result<(), Error> {
+    run_binary_channel_test(&SkipEmptyMemoTest)
+}
+
+pub struct SkipEmptyMemoTest;
+
+/// The identifier of the channel created on chain B,
+/// when the chains are bootstrapped without random identifiers.
+fn skipped_channel_id() -> ChannelId {
+    ChannelId::new(0)
+}
+
+impl TestOverrides for SkipEmptyMemoTest {
+    fn modify_test_config(&self, config: &mut TestConfig) {
+        config.bootstrap_with_random_ids = false;
+    }
+
+    fn modify_relayer_config(&self, config: &mut Config) {
+        match &mut config.chains[1] {
+            ChainConfig::CosmosSdk(chain_config) | ChainConfig::Namada(chain_config) => {
+                chain_config.skip_empty_memo_channels = vec![skipped_channel_id()];
+            }
+            ChainConfig::Penumbra(_) => panic!("running tests with Penumbra chain not supported"),
+        }
+    }
+}
+
+impl BinaryChannelTest for SkipEmptyMemoTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        _relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        channel: ConnectedChannel<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        assert_eq!(channel.channel_id_b.value(), &skipped_channel_id());
+
+        let denom_a = chains.node_a.denom();
+
+        let wallet_a = chains.node_a.wallets().user1().cloned();
+        let wallet_b = chains.node_b.wallets().user1().cloned();
+
+        let balance_a = chains
+            .node_a
+            .chain_driver()
+            .query_balance(&wallet_a.address(), &denom_a)?;
+
+        let a_to_b_amount = 23456u128;
+
+        info!(
+            "Sending IBC transfer with an empty memo from chain {} to chain {}",
+            chains.chain_id_a(),
+            chains.chain_id_b(),
+        );
+
+        chains.node_a.chain_driver().ibc_transfer_token(
+            &channel.port_a.as_ref(),
+            &channel.channel_id_a.as_ref(),
+            &wallet_a.as_ref(),
+            &wallet_b.address(),
+            &denom_a.with_amount(a_to_b_amount).as_ref(),
+        )?;
+
+        // Wait a bit before asserting that the transaction has not been relayed
+        sleep(Duration::from_secs(10));
+
+        info!("Assert that the IBC transfer with an empty memo was skipped");
+
+        let denom_b = derive_ibc_denom(
+            &chains.node_b.chain_driver().value().chain_type,
+            &channel.port_b.as_ref(),
+            &channel.channel_id_b.as_ref(),
+            &denom_a,
+        )?;
+
+        // The sender tokens will be escrowed since the packet will not have timed out
+        chains.node_a.chain_driver().assert_eventual_wallet_amount(
+            &wallet_a.address(),
+            &(balance_a.clone() - a_to_b_amount).as_ref(),
+        )?;
+
+        chains.node_b.chain_driver().assert_eventual_wallet_amount(
+            &wallet_b.address(),
+            &denom_b.with_amount(0u64).as_ref(),
+        )?;
+
+        info!("Sending IBC transfer with a memo on the same channel");
+
+        chains
+            .node_a
+            .chain_driver()
+            .ibc_transfer_token_with_memo_and_timeout(
+                &channel.port_a.as_ref(),
+                &channel.channel_id_a.as_ref(),
+                &wallet_a.as_ref(),
+                &wallet_b.address(),
+                &denom_a.with_amount(a_to_b_amount).as_ref(),
+                Some("route".to_owned()),
+                None,
+                None,
+            )?;
+
+        chains.node_a.chain_driver().assert_eventual_wallet_amount(
+            &wallet_a.address(),
+            &(balance_a - a_to_b_amount - a_to_b_amount).as_ref(),
+        )?;
+
+        // Only the transfer with a memo is received
+        chains.node_b.chain_driver().assert_eventual_wallet_amount(
+            &wallet_b.address(),
+            &denom_b.with_amount(a_to_b_amount).as_ref(),
+        )?;
+
+        Ok(())
+    }
+}
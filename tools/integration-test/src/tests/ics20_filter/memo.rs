@@ -63,6 +63,7 @@ impl BinaryChannelTest for IbcMemoFilterTest {
                 &denom_a.with_amount(a_to_b_amount).as_ref(),
                 Some(memo),
                 None,
+                None,
             )?;
 
         // Wait a bit before asserting that the transaction has not been relayed
@@ -102,6 +103,7 @@ impl BinaryChannelTest for IbcMemoFilterTest {
                 &denom_a.with_amount(a_to_b_amount).as_ref(),
                 None,
                 None,
+                None,
             )?;
 
         info!(
@@ -1 +1,2 @@
+pub mod empty_memo;
 pub mod memo;
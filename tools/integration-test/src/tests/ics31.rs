@@ -64,7 +64,10 @@ impl TestOverrides for ICS31Test {
     // will be created. So the channel worker needs to be enabled.
     fn modify_relayer_config(&self, config: &mut Config) {
         config.mode = ModeConfig {
-            connections: config::Connections { enabled: false },
+            connections: config::Connections {
+                enabled: false,
+                ..Default::default()
+            },
             channels: config::Channels { enabled: true },
             ..Default::default()
         };
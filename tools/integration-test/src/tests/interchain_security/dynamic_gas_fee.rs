@@ -13,7 +13,16 @@
 //! The second test disables the dynamic gas price on both chains in
 //! order to ensure that the first IBC transfer will cost more if dynamic
 //! gas is disabled.
-
+//!
+//! The [`SmoothedDynamicGasTest`] test alternates bursts of transfers with big
+//! memos, which raise the base fee of the second chain, with idle blocks, which
+//! lower it. It samples the dynamic gas price after each step, both from the
+//! latest base fee and from the base fees smoothed over a `smoothing_window`,
+//! and asserts that the base fee did vary and that the smoothed gas price
+//! varies less than the latest one.
+
+use ibc_relayer::chain::cosmos::gas::dynamic_gas_price;
+use ibc_relayer::chain::cosmos::types::gas::GasConfig;
 use ibc_relayer::config::dynamic_gas::DynamicGasPrice;
 use ibc_relayer::config::gas_multiplier::GasMultiplier;
 use ibc_relayer::config::ChainConfig;
@@ -37,6 +46,11 @@ fn test_fee_market_static_gas_transfer() -> Result<(), Error> {
     })
 }
 
+#[test]
+fn test_fee_market_smoothed_dynamic_gas_price() -> Result<(), Error> {
+    run_binary_interchain_security_channel_test(&SmoothedDynamicGasTest)
+}
+
 const MEMO_CHAR: &str = "a";
 const MEMO_SIZE: usize = 10000;
 
@@ -82,7 +96,7 @@ impl TestOverrides for DynamicGasTest {
         match &mut config.chains[0] {
             ChainConfig::CosmosSdk(chain_config_a) | ChainConfig::Namada(chain_config_a) => {
                 chain_config_a.gas_price =
-                    GasPrice::new(0.3, chain_config_a.gas_price.denom.clone());
+                    GasPrice::new(0.3, chain_config_a.gas_price.preferred().denom.clone()).into();
 
                 chain_config_a.dynamic_gas_price = DynamicGasPrice::unsafe_new(false, 1.1, 0.6);
             }
@@ -92,7 +106,7 @@ impl TestOverrides for DynamicGasTest {
         match &mut config.chains[1] {
             ChainConfig::CosmosSdk(chain_config_b) | ChainConfig::Namada(chain_config_b) => {
                 chain_config_b.gas_price =
-                    GasPrice::new(0.3, chain_config_b.gas_price.denom.clone());
+                    GasPrice::new(0.3, chain_config_b.gas_price.preferred().denom.clone()).into();
 
                 chain_config_b.gas_multiplier = Some(GasMultiplier::unsafe_new(1.8));
 
@@ -150,6 +164,7 @@ impl BinaryChannelTest for DynamicGasTest {
                 &denom_a.with_amount(a_to_b_amount).as_ref(),
                 Some(memo),
                 None,
+                None,
             )?;
 
         // Do a simple IBC transfer with the dynamic gas configuration
@@ -255,3 +270,136 @@ impl BinaryChannelTest for DynamicGasTest {
         Ok(())
     }
 }
+
+/// The number of times the dynamic gas price is sampled, alternately after a burst
+/// of transfers and after idle blocks.
+const SAMPLES: usize = 6;
+
+/// The number of transfers in a burst.
+const BURST_TRANSFERS: usize = 3;
+
+/// The number of base fees over which the dynamic gas price is smoothed.
+const SMOOTHING_WINDOW: usize = 3;
+
+pub struct SmoothedDynamicGasTest;
+
+impl TestOverrides for SmoothedDynamicGasTest {
+    fn modify_genesis_file(&self, genesis: &mut serde_json::Value) -> Result<(), Error> {
+        DynamicGasTest {
+            dynamic_gas_enabled: true,
+        }
+        .modify_genesis_file(genesis)
+    }
+
+    fn modify_relayer_config(&self, config: &mut Config) {
+        DynamicGasTest {
+            dynamic_gas_enabled: true,
+        }
+        .modify_relayer_config(config)
+    }
+
+    fn should_spawn_supervisor(&self) -> bool {
+        false
+    }
+}
+
+impl BinaryChannelTest for SmoothedDynamicGasTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        channel: ConnectedChannel<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        let chain_config_b = match relayer
+            .config
+            .chains
+            .get(1)
+            .ok_or_else(|| eyre!("chain configuration is empty"))?
+        {
+            ChainConfig::CosmosSdk(chain_config) | ChainConfig::Namada(chain_config) => {
+                chain_config.clone()
+            }
+            ChainConfig::Penumbra(_) => panic!("running tests with Penumbra chain not supported"),
+        };
+
+        let latest_config = GasConfig::from(&chain_config_b);
+        let mut smoothed_config = latest_config.clone();
+        smoothed_config.dynamic_gas_price = smoothed_config
+            .dynamic_gas_price
+            .with_smoothing_window(SMOOTHING_WINDOW);
+
+        let runtime = &chains.node_b.value().chain_driver.runtime;
+        let denom_b = chains.node_b.denom();
+        let memo: String = MEMO_CHAR.repeat(MEMO_SIZE);
+
+        let mut latest_prices = Vec::new();
+        let mut smoothed_prices = Vec::new();
+
+        for sample in 0..SAMPLES {
+            // Raise the base fee with a burst of transfers, then let it decrease
+            if sample % 2 == 0 {
+                for _ in 0..BURST_TRANSFERS {
+                    chains
+                        .node_b
+                        .chain_driver()
+                        .ibc_transfer_token_with_memo_and_timeout(
+                            &channel.port_b.as_ref(),
+                            &channel.channel_id_b.as_ref(),
+                            &chains.node_b.wallets().user1(),
+                            &chains.node_a.wallets().user1().address(),
+                            &denom_b.with_amount(1000u64).as_ref(),
+                            Some(memo.clone()),
+                            None,
+                            None,
+                        )?;
+                }
+            }
+
+            // Wait for the transfers to be included in a block, or for idle blocks
+            sleep(Duration::from_secs(2));
+
+            latest_prices.push(
+                runtime
+                    .block_on(dynamic_gas_price(
+                        &latest_config,
+                        &chain_config_b.id,
+                        &chain_config_b.rpc_addr,
+                        None,
+                    ))
+                    .price,
+            );
+
+            smoothed_prices.push(
+                runtime
+                    .block_on(dynamic_gas_price(
+                        &smoothed_config,
+                        &chain_config_b.id,
+                        &chain_config_b.rpc_addr,
+                        None,
+                    ))
+                    .price,
+            );
+        }
+
+        info!("latest gas prices: {latest_prices:?}, smoothed gas prices: {smoothed_prices:?}");
+
+        let spread = |prices: &[f64]| {
+            let max = prices.iter().copied().fold(f64::MIN, f64::max);
+            let min = prices.iter().copied().fold(f64::MAX, f64::min);
+            max - min
+        };
+
+        assert!(
+            spread(&latest_prices) > 0.0,
+            "the base fee should vary with the load of the chain"
+        );
+
+        assert!(
+            spread(&smoothed_prices) < spread(&latest_prices),
+            "the smoothed gas price should vary less than the latest gas price"
+        );
+
+        Ok(())
+    }
+}
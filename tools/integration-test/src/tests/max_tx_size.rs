@@ -0,0 +1,175 @@
+//! Tests that the packets cleared on an ordered channel are relayed in several
+//! transactions when their messages exceed the `max_tx_size` of the destination chain.
+//!
+//! The test sends 200 transfers from chain A to chain B, relays them by clearing the
+//! packets with a `max_tx_size` on chain B which only fits a few `MsgRecvPacket` messages
+//! in each transaction, and asserts that they were relayed in multiple transactions and
+//! that all of them were received.
+
+use std::str::FromStr;
+
+use ibc_relayer::chain::cosmos::query::tx::query_txs_by_sender;
+use ibc_relayer::chain::cosmos::request_rate::PacedRpcClient;
+use ibc_relayer::config::types::MaxTxSize;
+use ibc_relayer::config::ChainConfig;
+use ibc_relayer::link::{Link, LinkParameters, SequenceRange};
+use ibc_relayer_types::signer::Signer;
+use ibc_test_framework::prelude::*;
+use tendermint_rpc::HttpClient;
+
+const PACKET_COUNT: usize = 200;
+
+/// Fits a client update along with a few `MsgRecvPacket` messages.
+const MAX_TX_SIZE: usize = 30_000;
+
+#[test]
+fn test_clear_packets_with_small_max_tx_size() -> Result<(), Error> {
+    run_binary_channel_test(&SmallMaxTxSizeTest)
+}
+
+pub struct SmallMaxTxSizeTest;
+
+impl TestOverrides for SmallMaxTxSizeTest {
+    fn modify_relayer_config(&self, config: &mut Config) {
+        config.mode.packets.clear_limit = PACKET_COUNT;
+
+        match &mut config.chains[1] {
+            ChainConfig::CosmosSdk(chain_config) | ChainConfig::Namada(chain_config) => {
+                chain_config.max_tx_size = MaxTxSize::new(MAX_TX_SIZE).unwrap();
+            }
+            ChainConfig::Penumbra(_) => panic!("running tests with Penumbra chain not supported"),
+        }
+    }
+
+    fn should_spawn_supervisor(&self) -> bool {
+        false
+    }
+
+    fn channel_order(&self) -> Ordering {
+        Ordering::Ordered
+    }
+}
+
+impl BinaryChannelTest for SmallMaxTxSizeTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        channel: ConnectedChannel<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        let rpc_addr_b = match &relayer.config.chains[1] {
+            ChainConfig::CosmosSdk(chain_config) | ChainConfig::Namada(chain_config) => {
+                chain_config.rpc_addr.clone()
+            }
+            ChainConfig::Penumbra(_) => panic!("running tests with Penumbra chain not supported"),
+        };
+
+        let packet_config = relayer.config.mode.packets;
+
+        let denom_a = chains.node_a.denom();
+        let wallet_a = chains.node_a.wallets().user1().cloned();
+        let wallet_b = chains.node_b.wallets().user1().cloned();
+
+        let amount = 1000u64;
+        let total_amount = amount * u64::try_from(PACKET_COUNT).unwrap();
+
+        info!("Performing {PACKET_COUNT} IBC transfers on an ordered channel");
+
+        chains.node_a.chain_driver().ibc_transfer_token_multiple(
+            &channel.port_a.as_ref(),
+            &channel.channel_id_a.as_ref(),
+            &wallet_a.as_ref(),
+            &wallet_b.address(),
+            &denom_a.with_amount(amount).as_ref(),
+            PACKET_COUNT,
+            None,
+        )?;
+
+        sleep(Duration::from_secs(10));
+
+        let link = Link::new_from_opts(
+            chains.handle_a().clone(),
+            chains.handle_b().clone(),
+            LinkParameters {
+                src_port_id: channel.port_a.clone().into_value(),
+                src_channel_id: channel.channel_id_a.clone().into_value(),
+                max_memo_size: packet_config.ics20_max_memo_size,
+                max_receiver_size: packet_config.ics20_max_receiver_size,
+                exclude_src_sequences: vec![],
+                max_clear_duration: None,
+                verification: Default::default(),
+                clear_order: Default::default(),
+            },
+            true,
+            true,
+        )?;
+
+        let mut relay_path_a_to_b = link.a_to_b;
+        relay_path_a_to_b.schedule_packet_clearing(
+            None,
+            packet_config.clear_limit,
+            SequenceRange::all(),
+        )?;
+        relay_path_a_to_b.execute_schedule()?;
+
+        let denom_b = derive_ibc_denom(
+            &chains.node_b.chain_driver().value().chain_type,
+            &channel.port_b.as_ref(),
+            &channel.channel_id_b.as_ref(),
+            &denom_a,
+        )?;
+
+        // All the packets were received on chain B
+        chains.node_b.chain_driver().assert_eventual_wallet_amount(
+            &wallet_b.address(),
+            &denom_b.with_amount(total_amount).as_ref(),
+        )?;
+
+        let rpc_client = PacedRpcClient::new(
+            HttpClient::new(rpc_addr_b.clone())
+                .map_err(|e| eyre!("failed to create RPC client: {e}"))?,
+            None,
+        );
+
+        let relayer_address_b = chains
+            .node_b
+            .wallets()
+            .relayer()
+            .address()
+            .value()
+            .to_string();
+        let relayer_b = Signer::from_str(&relayer_address_b)
+            .map_err(|e| eyre!("invalid relayer address {relayer_address_b}: {e}"))?;
+
+        let txs = chains
+            .node_b
+            .chain_driver()
+            .value()
+            .runtime
+            .block_on(query_txs_by_sender(
+                &rpc_client,
+                &rpc_addr_b,
+                &relayer_b,
+                100,
+            ))?;
+
+        info!("relayer committed {} transactions on chain B", txs.len());
+
+        // The messages were split in multiple transactions of at most `max_tx_size`
+        assert!(
+            txs.len() > 1,
+            "expected the packets to be relayed in multiple transactions"
+        );
+
+        for tx in &txs {
+            assert!(
+                tx.tx.len() <= MAX_TX_SIZE,
+                "transaction of {} bytes exceeds the max_tx_size",
+                tx.tx.len()
+            );
+        }
+
+        Ok(())
+    }
+}
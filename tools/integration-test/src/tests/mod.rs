@@ -5,30 +5,52 @@
    will pick up the definition by default.
 */
 
+pub mod canary;
 pub mod clear_packet;
+pub mod clear_pending_operational_data;
 pub mod client_expiration;
 pub mod client_filter;
+pub mod client_misbehaviour;
 pub mod client_refresh;
 pub mod client_settings;
 #[cfg(not(any(feature = "celestia", feature = "namada")))]
 pub mod client_upgrade;
 pub mod connection_delay;
+pub mod connection_on_start;
 pub mod consensus_states;
+#[cfg(not(feature = "namada"))]
+pub mod denom_key_names;
 #[cfg(not(feature = "no-denom-trace"))]
 pub mod denom_trace;
 pub mod error_events;
 pub mod execute_schedule;
+#[cfg(not(feature = "namada"))]
+pub mod fees_spent;
+pub mod force_ordered_relay;
+#[cfg(not(feature = "namada"))]
+pub mod grpc_height_header;
 pub mod handshake_on_start;
 pub mod ics20_filter;
 pub mod memo;
+pub mod output_format;
+pub mod pending_refresh;
+#[cfg(not(feature = "namada"))]
+pub mod proof_height_strategy;
+#[cfg(not(feature = "namada"))]
+pub mod pruned_proofs_height;
+pub mod pull_event_source;
 #[cfg(not(feature = "namada"))]
 pub mod python;
 pub mod query_packet;
+pub mod reload_config;
+#[cfg(not(feature = "namada"))]
+pub mod signing_accounts;
 pub mod supervisor;
 pub mod tendermint;
 #[cfg(not(any(feature = "celestia")))]
 pub mod ternary_transfer;
 pub mod transfer;
+pub mod tx_retry_strategy;
 
 #[cfg(any(doc, feature = "async-icq"))]
 pub mod async_icq;
@@ -48,6 +70,9 @@ pub mod ordered_channel;
 #[cfg(any(doc, feature = "ordered"))]
 pub mod ordered_channel_clear;
 
+#[cfg(any(doc, feature = "ordered"))]
+pub mod max_tx_size;
+
 #[cfg(any(doc, feature = "ica"))]
 pub mod ica;
 
@@ -77,3 +102,7 @@ pub mod dynamic_gas_fee;
 
 #[cfg(any(doc, feature = "benchmark"))]
 pub mod benchmark;
+
+// Relies on the `submit-delay` feature of the relayer, which is not enabled for docs
+#[cfg(feature = "submit-delay")]
+pub mod submit_delay;
@@ -1,5 +1,5 @@
 use ibc_relayer::config::{types::MaxMsgNum, ChainConfig};
-use ibc_relayer::link::{Link, LinkParameters};
+use ibc_relayer::link::{Link, LinkParameters, SequenceRange};
 use ibc_relayer::transfer::{build_and_send_transfer_messages, TransferOptions};
 use ibc_relayer_types::events::IbcEvent;
 use ibc_test_framework::prelude::*;
@@ -126,6 +126,9 @@ impl BinaryChannelTest for OrderedChannelClearTest {
             max_memo_size: packet_config.ics20_max_memo_size,
             max_receiver_size: packet_config.ics20_max_receiver_size,
             exclude_src_sequences: vec![],
+            max_clear_duration: None,
+            verification: Default::default(),
+            clear_order: Default::default(),
         };
 
         let chain_a_link = Link::new_from_opts(
@@ -142,6 +145,9 @@ impl BinaryChannelTest for OrderedChannelClearTest {
             max_memo_size: packet_config.ics20_max_memo_size,
             max_receiver_size: packet_config.ics20_max_receiver_size,
             exclude_src_sequences: vec![],
+            max_clear_duration: None,
+            verification: Default::default(),
+            clear_order: Default::default(),
         };
 
         let chain_b_link = Link::new_from_opts(
@@ -154,8 +160,11 @@ impl BinaryChannelTest for OrderedChannelClearTest {
 
         // Send the transfer (recv) packets from A to B over the channel.
         let mut relay_path_a_to_b = chain_a_link.a_to_b;
-        relay_path_a_to_b
-            .schedule_packet_clearing(None, relayer.config.mode.packets.clear_limit)?;
+        relay_path_a_to_b.schedule_packet_clearing(
+            None,
+            relayer.config.mode.packets.clear_limit,
+            SequenceRange::all(),
+        )?;
         relay_path_a_to_b.execute_schedule()?;
 
         sleep(Duration::from_secs(10));
@@ -175,8 +184,11 @@ impl BinaryChannelTest for OrderedChannelClearTest {
 
         // Send the packet acknowledgments from B to A.
         let mut relay_path_b_to_a = chain_b_link.a_to_b;
-        relay_path_b_to_a
-            .schedule_packet_clearing(None, relayer.config.mode.packets.clear_limit)?;
+        relay_path_b_to_a.schedule_packet_clearing(
+            None,
+            relayer.config.mode.packets.clear_limit,
+            SequenceRange::all(),
+        )?;
         relay_path_b_to_a.execute_schedule()?;
 
         sleep(Duration::from_secs(10));
@@ -286,6 +298,9 @@ impl BinaryChannelTest for OrderedChannelClearEqualCLITest {
             max_memo_size: packet_config.ics20_max_memo_size,
             max_receiver_size: packet_config.ics20_max_receiver_size,
             exclude_src_sequences: vec![],
+            max_clear_duration: None,
+            verification: Default::default(),
+            clear_order: Default::default(),
         };
 
         let chain_a_link = Link::new_from_opts(
@@ -0,0 +1,107 @@
+//! Tests the `--output json` option of the query commands, by running the `hermes`
+//! binary against the chains of the test and asserting that each query succeeds and
+//! outputs its result in the `{status, result}` envelope.
+
+use std::env;
+use std::process::Command;
+
+use ibc_test_framework::prelude::*;
+
+#[test]
+fn test_query_output_json() -> Result<(), Error> {
+    run_binary_channel_test(&QueryOutputJsonTest)
+}
+
+struct QueryOutputJsonTest;
+
+impl TestOverrides for QueryOutputJsonTest {
+    fn should_spawn_supervisor(&self) -> bool {
+        false
+    }
+}
+
+impl BinaryChannelTest for QueryOutputJsonTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        channel: ConnectedChannel<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        let config_path = relayer
+            .config_path
+            .to_str()
+            .ok_or_else(|| eyre!("failed to format relayer config path"))?;
+
+        let chain_id_a = chains.chain_id_a().to_string();
+        let chain_id_b = chains.chain_id_b().to_string();
+        let connection_id_a = channel.connection.connection_id_a.to_string();
+        let channel_id_a = channel.channel_id_a.to_string();
+        let port_a = channel.port_a.to_string();
+
+        let queries: [&[&str]; 4] = [
+            &["query", "clients", "--host-chain", &chain_id_b],
+            &["query", "channels", "--chain", &chain_id_a],
+            &[
+                "query",
+                "connection",
+                "end",
+                "--chain",
+                &chain_id_a,
+                "--connection",
+                &connection_id_a,
+            ],
+            &[
+                "query",
+                "packet",
+                "pending",
+                "--chain",
+                &chain_id_a,
+                "--port",
+                &port_a,
+                "--channel",
+                &channel_id_a,
+            ],
+        ];
+
+        let current_dir = env::current_dir()?
+            .to_str()
+            .ok_or_else(|| eyre!("failed to format current directory"))?
+            .to_string();
+
+        // Use the directory where `cargo run` is called, instead of the
+        // package subdirectory automatically set by cargo
+        let base_dir = env::var("PWD").unwrap_or(current_dir);
+
+        for query in queries {
+            let output = Command::new("cargo")
+                .args(["run", "--quiet", "--bin", "hermes", "--"])
+                .args(["--config", config_path, "--output", "json"])
+                .args(query)
+                .current_dir(&base_dir)
+                .output()?;
+
+            let stdout = String::from_utf8_lossy(&output.stdout);
+
+            // The output is preceded by the logs, which are also emitted as JSON
+            let last_line = stdout.lines().last().unwrap_or_default();
+
+            let envelope: serde_json::Value = serde_json::from_str(last_line)
+                .map_err(|e| eyre!("invalid JSON output of {query:?}: {e}: {last_line}"))?;
+
+            assert_eq(
+                &format!("status of {query:?}, with output {last_line}"),
+                &envelope["status"],
+                &serde_json::Value::from("success"),
+            )?;
+
+            let result = envelope
+                .get("result")
+                .ok_or_else(|| eyre!("no result in the output of {query:?}: {last_line}"))?;
+
+            info!("output of {query:?}: {result}");
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,151 @@
+//! Tests that relaying over a client whose refresh is in progress waits for the
+//! refresh to complete, and then builds its client update against the refreshed client.
+//!
+//! The test marks the refresh of the client of chain A on chain B as in progress,
+//! refreshes that client after a delay in the background, and meanwhile relays a
+//! transfer from chain A. It asserts that the transfer is relayed, and only once
+//! the refresh has completed.
+
+use std::sync::Arc;
+use std::thread;
+use std::time::Instant;
+
+use ibc_relayer::chain::tracking::TrackingId;
+use ibc_relayer::event::source::EventBatch;
+use ibc_relayer::foreign_client::pending_refresh::PendingRefreshes;
+use ibc_relayer::link::{Link, LinkParameters};
+use ibc_relayer::transfer::{build_and_send_transfer_messages, TransferOptions};
+use ibc_test_framework::prelude::*;
+
+/// How long the refresh of the client takes in the test.
+const REFRESH_DELAY: Duration = Duration::from_secs(10);
+
+#[test]
+fn test_relay_waits_for_pending_refresh() -> Result<(), Error> {
+    run_binary_channel_test(&PendingRefreshTest)
+}
+
+struct PendingRefreshTest;
+
+impl TestOverrides for PendingRefreshTest {
+    fn should_spawn_supervisor(&self) -> bool {
+        false
+    }
+}
+
+impl BinaryChannelTest for PendingRefreshTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        channel: ConnectedChannel<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        let packet_config = relayer.config.mode.packets;
+
+        let denom_a = chains.node_a.denom();
+        let amount = 1000u64;
+
+        let transfer_options = TransferOptions {
+            src_port_id: channel.port_a.value().clone(),
+            src_channel_id: channel.channel_id_a.value().clone(),
+            amount: amount.into(),
+            denom: denom_a.value().to_string(),
+            receiver: Some(chains.node_b.wallets().user1().address().value().0.clone()),
+            timeout_height_offset: 1000,
+            timeout_duration: Duration::from_secs(0),
+            number_msgs: 1,
+            memo: None,
+        };
+
+        let events = build_and_send_transfer_messages(
+            chains.handle_a(),
+            chains.handle_b(),
+            &transfer_options,
+        )?;
+
+        let send_packet = events
+            .first()
+            .ok_or_else(|| Error::generic(eyre!("expected a send packet event")))?
+            .clone();
+
+        let link = Link::new_from_opts(
+            chains.handle_a().clone(),
+            chains.handle_b().clone(),
+            LinkParameters {
+                src_port_id: channel.port_a.value().clone(),
+                src_channel_id: channel.channel_id_a.value().clone(),
+                max_memo_size: packet_config.ics20_max_memo_size,
+                max_receiver_size: packet_config.ics20_max_receiver_size,
+                exclude_src_sequences: vec![],
+                max_clear_duration: None,
+                verification: Default::default(),
+                clear_order: Default::default(),
+            },
+            false,
+            false,
+        )?;
+
+        let pending_refreshes = Arc::new(PendingRefreshes::default());
+
+        let mut relay_path_a_to_b = link.a_to_b;
+        relay_path_a_to_b.wait_for_pending_refreshes(pending_refreshes.clone());
+
+        let client_a_to_b = chains.foreign_clients.client_a_to_b.clone();
+        let refresh = pending_refreshes.start(&chains.handle_b().id(), client_a_to_b.id());
+
+        info!("refreshing the client of chain A on chain B in {REFRESH_DELAY:?}");
+
+        let (relayed_at, refreshed_at) = thread::scope(|s| {
+            let refresher = s.spawn(move || {
+                thread::sleep(REFRESH_DELAY);
+
+                let result = client_a_to_b.build_latest_update_client_and_send();
+                let refreshed_at = Instant::now();
+
+                refresh.complete(result.is_ok());
+
+                result.map(|_| refreshed_at)
+            });
+
+            relay_path_a_to_b.update_schedule(EventBatch {
+                chain_id: chains.handle_a().id(),
+                tracking_id: TrackingId::new_uuid(),
+                height: send_packet.height,
+                events: vec![send_packet],
+            })?;
+
+            relay_path_a_to_b.execute_schedule()?;
+
+            let relayed_at = Instant::now();
+
+            let refreshed_at = refresher
+                .join()
+                .map_err(|_| Error::generic(eyre!("the refresh of the client panicked")))?
+                .map_err(handle_generic_error)?;
+
+            Ok::<_, Error>((relayed_at, refreshed_at))
+        })?;
+
+        if relayed_at < refreshed_at {
+            return Err(Error::generic(eyre!(
+                "the packet was relayed {:?} before the refresh of the client completed",
+                refreshed_at - relayed_at
+            )));
+        }
+
+        let denom_b = derive_ibc_denom(
+            &chains.node_b.chain_driver().value().chain_type,
+            &channel.port_b.as_ref(),
+            &channel.channel_id_b.as_ref(),
+            &denom_a,
+        )?;
+
+        chains.node_b.chain_driver().assert_eventual_wallet_amount(
+            &chains.node_b.wallets().user1().address(),
+            &denom_b.with_amount(amount).as_ref(),
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,161 @@
+//! Tests that, under the `minimal_update` proof height strategy, the proofs of the
+//! messages built from packet events are built at the latest height of the client
+//! on the destination chain if that client is already past the events, so that the
+//! client is not updated to the height following the events.
+//!
+//! The test sends a transfer from chain A, updates the client of chain A on chain B
+//! past the height of the transfer, and then relays the packet from its event. With
+//! the default `latest_committed` strategy, the client would get a new consensus state
+//! at the height following the transfer.
+
+use ibc_relayer::chain::requests::QueryConsensusStateHeightsRequest;
+use ibc_relayer::chain::tracking::TrackingId;
+use ibc_relayer::config::{ChainConfig, ProofHeightStrategy};
+use ibc_relayer::event::source::EventBatch;
+use ibc_relayer::link::{Link, LinkParameters};
+use ibc_relayer::transfer::{build_and_send_transfer_messages, TransferOptions};
+use ibc_test_framework::prelude::*;
+
+#[test]
+fn test_proof_height_strategy_minimal_update() -> Result<(), Error> {
+    run_binary_channel_test(&MinimalUpdateTest)
+}
+
+struct MinimalUpdateTest;
+
+impl TestOverrides for MinimalUpdateTest {
+    fn modify_relayer_config(&self, config: &mut Config) {
+        for chain_config in config.chains.iter_mut() {
+            if let ChainConfig::CosmosSdk(chain_config) = chain_config {
+                chain_config.proof_height_strategy = ProofHeightStrategy::MinimalUpdate;
+            }
+        }
+    }
+
+    fn should_spawn_supervisor(&self) -> bool {
+        false
+    }
+}
+
+impl BinaryChannelTest for MinimalUpdateTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        channel: ConnectedChannel<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        let packet_config = relayer.config.mode.packets;
+
+        let denom_a = chains.node_a.denom();
+        let amount = 1000u64;
+
+        let transfer_options = TransferOptions {
+            src_port_id: channel.port_a.value().clone(),
+            src_channel_id: channel.channel_id_a.value().clone(),
+            amount: amount.into(),
+            denom: denom_a.value().to_string(),
+            receiver: Some(chains.node_b.wallets().user1().address().value().0.clone()),
+            timeout_height_offset: 1000,
+            timeout_duration: Duration::from_secs(0),
+            number_msgs: 1,
+            memo: None,
+        };
+
+        let events = build_and_send_transfer_messages(
+            chains.handle_a(),
+            chains.handle_b(),
+            &transfer_options,
+        )?;
+
+        let send_packet = events
+            .first()
+            .ok_or_else(|| Error::generic(eyre!("expected a send packet event")))?
+            .clone();
+
+        let transfer_height = send_packet.height;
+
+        // Have the client go past the height following the transfer
+        assert_eventually_succeed(
+            "chain A should produce blocks past the transfer",
+            30,
+            Duration::from_secs(1),
+            || {
+                let latest_height = chains.handle_a().query_latest_height()?;
+
+                if latest_height > transfer_height.increment() {
+                    Ok(())
+                } else {
+                    Err(Error::generic(eyre!(
+                        "chain A is at height {latest_height}, not past {transfer_height} yet"
+                    )))
+                }
+            },
+        )?;
+
+        chains
+            .foreign_clients
+            .client_a_to_b
+            .build_latest_update_client_and_send()
+            .map_err(handle_generic_error)?;
+
+        let consensus_state_heights = || {
+            chains
+                .handle_b()
+                .query_consensus_state_heights(QueryConsensusStateHeightsRequest {
+                    client_id: chains.foreign_clients.client_a_to_b.id().clone(),
+                    pagination: None,
+                })
+        };
+
+        let heights_before_relay = consensus_state_heights()?;
+
+        let link = Link::new_from_opts(
+            chains.handle_a().clone(),
+            chains.handle_b().clone(),
+            LinkParameters {
+                src_port_id: channel.port_a.value().clone(),
+                src_channel_id: channel.channel_id_a.value().clone(),
+                max_memo_size: packet_config.ics20_max_memo_size,
+                max_receiver_size: packet_config.ics20_max_receiver_size,
+                exclude_src_sequences: vec![],
+                max_clear_duration: None,
+                verification: Default::default(),
+                clear_order: Default::default(),
+            },
+            false,
+            false,
+        )?;
+
+        let mut relay_path_a_to_b = link.a_to_b;
+
+        relay_path_a_to_b.update_schedule(EventBatch {
+            chain_id: chains.handle_a().id(),
+            tracking_id: TrackingId::new_uuid(),
+            height: transfer_height,
+            events: vec![send_packet],
+        })?;
+
+        relay_path_a_to_b.execute_schedule()?;
+
+        let denom_b = derive_ibc_denom(
+            &chains.node_b.chain_driver().value().chain_type,
+            &channel.port_b.as_ref(),
+            &channel.channel_id_b.as_ref(),
+            &denom_a,
+        )?;
+
+        chains.node_b.chain_driver().assert_eventual_wallet_amount(
+            &chains.node_b.wallets().user1().address(),
+            &denom_b.with_amount(amount).as_ref(),
+        )?;
+
+        assert_eq(
+            "the packet should be relayed without updating the client",
+            &consensus_state_heights()?,
+            &heights_before_relay,
+        )?;
+
+        Ok(())
+    }
+}
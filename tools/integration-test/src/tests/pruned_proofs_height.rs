@@ -0,0 +1,169 @@
+//! Tests the relaying of packets whose proofs must be built at a height whose
+//! state the source chain has pruned since, following the `pruned_height_handling`
+//! setting of the source chain.
+//!
+//! The chains prune their state every 10 blocks, keeping only the 2 most recent
+//! heights. The test sends a transfer from chain A, waits until chain A has pruned
+//! the state at the height of the transfer, and then relays the packet from that
+//! height, as with the `--packet-data-query-height` option of `tx packet-recv`.
+
+use ibc_relayer::chain::requests::{IncludeProof, QueryHeight, QueryPacketCommitmentRequest};
+use ibc_relayer::config::{ChainConfig, PrunedHeightHandling};
+use ibc_relayer::link::error::LinkErrorDetail;
+use ibc_relayer::link::{Link, LinkParameters};
+use ibc_relayer::transfer::{build_and_send_transfer_messages, TransferOptions};
+use ibc_test_framework::prelude::*;
+
+#[test]
+fn test_pruned_proofs_height_fail() -> Result<(), Error> {
+    run_binary_channel_test(&PrunedProofsHeightTest {
+        handling: PrunedHeightHandling::Fail,
+    })
+}
+
+struct PrunedProofsHeightTest {
+    handling: PrunedHeightHandling,
+}
+
+impl TestOverrides for PrunedProofsHeightTest {
+    fn modify_test_config(&self, config: &mut TestConfig) {
+        config.extra_start_args = [
+            "--pruning",
+            "custom",
+            "--pruning-keep-recent",
+            "2",
+            "--pruning-interval",
+            "10",
+        ]
+        .map(String::from)
+        .to_vec();
+    }
+
+    fn modify_relayer_config(&self, config: &mut Config) {
+        for chain_config in config.chains.iter_mut() {
+            if let ChainConfig::CosmosSdk(chain_config) = chain_config {
+                chain_config.pruned_height_handling = self.handling;
+            }
+        }
+    }
+
+    fn should_spawn_supervisor(&self) -> bool {
+        false
+    }
+}
+
+impl BinaryChannelTest for PrunedProofsHeightTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        channel: ConnectedChannel<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        let packet_config = relayer.config.mode.packets;
+
+        let transfer_options = TransferOptions {
+            src_port_id: channel.port_a.value().clone(),
+            src_channel_id: channel.channel_id_a.value().clone(),
+            amount: 1000u64.into(),
+            denom: chains.node_a.denom().value().to_string(),
+            receiver: Some(chains.node_b.wallets().user1().address().value().0.clone()),
+            timeout_height_offset: 1000,
+            timeout_duration: Duration::from_secs(0),
+            number_msgs: 1,
+            memo: None,
+        };
+
+        let events = build_and_send_transfer_messages(
+            chains.handle_a(),
+            chains.handle_b(),
+            &transfer_options,
+        )?;
+
+        let send_packet = events
+            .first()
+            .ok_or_else(|| Error::generic(eyre!("expected a send packet event")))?;
+
+        let transfer_height = send_packet.height;
+
+        let sequence = send_packet
+            .event
+            .packet()
+            .ok_or_else(|| {
+                Error::generic(eyre!(
+                    "expected a send packet event, got {:?}",
+                    send_packet.event
+                ))
+            })?
+            .sequence;
+
+        info!("waiting for chain A to prune the state at height {transfer_height}");
+
+        assert_eventually_succeed(
+            "state at the height of the transfer should be pruned",
+            60,
+            Duration::from_secs(1),
+            || {
+                let commitment = chains.handle_a().query_packet_commitment(
+                    QueryPacketCommitmentRequest {
+                        port_id: channel.port_a.value().clone(),
+                        channel_id: channel.channel_id_a.value().clone(),
+                        sequence,
+                        height: QueryHeight::Specific(transfer_height),
+                    },
+                    IncludeProof::Yes,
+                );
+
+                match commitment {
+                    Ok(_) => Err(Error::generic(eyre!(
+                        "state at height {transfer_height} is not pruned yet"
+                    ))),
+                    Err(_) => Ok(()),
+                }
+            },
+        )?;
+
+        let link = Link::new_from_opts(
+            chains.handle_a().clone(),
+            chains.handle_b().clone(),
+            LinkParameters {
+                src_port_id: channel.port_a.value().clone(),
+                src_channel_id: channel.channel_id_a.value().clone(),
+                max_memo_size: packet_config.ics20_max_memo_size,
+                max_receiver_size: packet_config.ics20_max_receiver_size,
+                exclude_src_sequences: vec![],
+                max_clear_duration: None,
+                verification: Default::default(),
+                clear_order: Default::default(),
+            },
+            false,
+            true,
+        )?;
+
+        let result = link.relay_recv_packet_and_timeout_messages_with_packet_data_query_height(
+            vec![],
+            Some(transfer_height),
+        );
+
+        // The relaying gives up with the pruned height, instead of retrying forever
+        match result {
+            Err(e) => match e.detail() {
+                LinkErrorDetail::ProofHeightPruned(e) => {
+                    assert_eq(
+                        "the pruned proofs height should be reported",
+                        &e.height,
+                        &transfer_height,
+                    )?;
+                }
+                _ => return Err(Error::generic(eyre!("unexpected relaying error: {e}"))),
+            },
+            Ok(events) => {
+                return Err(Error::generic(eyre!(
+                    "relaying from a pruned height should fail, got {events:?}"
+                )))
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+//! Tests that packets are relayed when the events of the chains are collected by
+//! polling the `/block_results` RPC endpoint rather than by WebSocket subscriptions.
+//!
+//! Packet clearing is disabled, so that the packets can only be relayed upon the
+//! events collected by the pull-based event source.
+
+use ibc_relayer::config::{self, ChainConfig, EventSourceMode};
+use ibc_test_framework::prelude::*;
+
+use crate::tests::transfer::IbcTransferTest;
+
+#[test]
+fn test_ibc_transfer_with_pull_event_source() -> Result<(), Error> {
+    run_binary_channel_test(&PullEventSourceTest)
+}
+
+struct PullEventSourceTest;
+
+impl TestOverrides for PullEventSourceTest {
+    fn modify_relayer_config(&self, config: &mut Config) {
+        config.mode.packets.clear_interval = 0;
+        config.mode.packets.clear_on_start = false;
+
+        for chain_config in config.chains.iter_mut() {
+            match chain_config {
+                ChainConfig::CosmosSdk(chain_config) | ChainConfig::Namada(chain_config) => {
+                    chain_config.event_source = EventSourceMode::Pull {
+                        interval: config::default::poll_interval(),
+                        max_retries: config::default::max_retries(),
+                    };
+                }
+                ChainConfig::Penumbra(_) => {
+                    panic!("running tests with Penumbra chain not supported")
+                }
+            }
+        }
+    }
+}
+
+impl BinaryChannelTest for PullEventSourceTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        config: &TestConfig,
+        relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        channel: ConnectedChannel<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        IbcTransferTest.run(config, relayer, chains, channel)
+    }
+}
@@ -1,6 +1,7 @@
 use ibc_relayer::chain::counterparty::{channel_on_destination, pending_packet_summary};
-use ibc_relayer::chain::requests::Paginate;
+use ibc_relayer::chain::requests::{Paginate, QueryPacketAcknowledgementsRequest};
 use ibc_relayer::link::{Link, LinkParameters};
+use ibc_relayer_types::core::ics04_channel::packet::Sequence;
 
 use ibc_test_framework::prelude::*;
 use ibc_test_framework::relayer::channel::query_identified_channel_end;
@@ -12,6 +13,11 @@ fn test_query_packet_pending() -> Result<(), Error> {
     run_binary_channel_test(&QueryPacketPendingTest)
 }
 
+#[test]
+fn test_query_packet_acknowledgements() -> Result<(), Error> {
+    run_binary_channel_test(&QueryPacketAcknowledgementsTest)
+}
+
 pub struct QueryPacketPendingTest;
 
 impl TestOverrides for QueryPacketPendingTest {
@@ -64,6 +70,9 @@ impl BinaryChannelTest for QueryPacketPendingTest {
             max_memo_size: packet_config.ics20_max_memo_size,
             max_receiver_size: packet_config.ics20_max_receiver_size,
             exclude_src_sequences: vec![],
+            max_clear_duration: None,
+            verification: Default::default(),
+            clear_order: Default::default(),
         };
 
         let rev_opts = LinkParameters {
@@ -72,6 +81,9 @@ impl BinaryChannelTest for QueryPacketPendingTest {
             max_memo_size: packet_config.ics20_max_memo_size,
             max_receiver_size: packet_config.ics20_max_receiver_size,
             exclude_src_sequences: vec![],
+            max_clear_duration: None,
+            verification: Default::default(),
+            clear_order: Default::default(),
         };
 
         let link = Link::new_from_opts(
@@ -176,3 +188,70 @@ impl BinaryChannelTest for QueryPacketPendingTest {
         Ok(())
     }
 }
+
+/// The number of transfers relayed in a batch before querying their acknowledgements.
+const BATCH_SIZE: u64 = 5;
+
+pub struct QueryPacketAcknowledgementsTest;
+
+impl TestOverrides for QueryPacketAcknowledgementsTest {}
+
+impl BinaryChannelTest for QueryPacketAcknowledgementsTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        _relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        channel: ConnectedChannel<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        let denom_a = chains.node_a.denom();
+
+        let wallet_a = chains.node_a.wallets().user1().cloned();
+        let wallet_b = chains.node_b.wallets().user1().cloned();
+
+        let amount = random_u128_range(1000, 5000);
+
+        info!("Performing a batch of {BATCH_SIZE} IBC transfers with amount {amount}");
+
+        for _ in 0..BATCH_SIZE {
+            chains.node_a.chain_driver().ibc_transfer_token(
+                &channel.port_a.as_ref(),
+                &channel.channel_id_a.as_ref(),
+                &wallet_a.as_ref(),
+                &wallet_b.address(),
+                &denom_a.with_amount(amount).as_ref(),
+            )?;
+        }
+
+        let denom_b = derive_ibc_denom(
+            &chains.node_b.chain_driver().value().chain_type,
+            &channel.port_b.as_ref(),
+            &channel.channel_id_b.as_ref(),
+            &denom_a,
+        )?;
+
+        chains.node_b.chain_driver().assert_eventual_wallet_amount(
+            &wallet_b.address(),
+            &denom_b
+                .with_amount(amount * u128::from(BATCH_SIZE))
+                .as_ref(),
+        )?;
+
+        // The next sequence was never sent, so no acknowledgement is stored for it
+        let (sequences, _) = chains.handle_b().query_packet_acknowledgements(
+            QueryPacketAcknowledgementsRequest {
+                port_id: channel.port_b.clone().into_value(),
+                channel_id: channel.channel_id_b.clone().into_value(),
+                pagination: Paginate::All,
+                packet_commitment_sequences: (1..=BATCH_SIZE + 1).map(Sequence::from).collect(),
+            },
+        )?;
+
+        assert_eq!(
+            sequences,
+            (1..=BATCH_SIZE).map(Sequence::from).collect::<Vec<_>>()
+        );
+
+        Ok(())
+    }
+}
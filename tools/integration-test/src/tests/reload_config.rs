@@ -0,0 +1,405 @@
+//! Tests the reload of the configuration of the supervisor, which stops the workers
+//! of the channels which the new configuration no longer relays for, and restarts
+//! the workers of the chains whose configuration changed.
+//!
+//! - [`ReloadConfigTest`] relays a transfer so that the packet workers of the channel
+//!   are spawned, then reloads a configuration denying the channel on both chains, and
+//!   asserts that the packet workers are stopped and removed from the supervisor state.
+//!
+//! - [`ReloadUpdatedChainConfigTest`] relays transfers over the channels between
+//!   chains A and B, and between chains B and C, then reloads a configuration which
+//!   only changes the gas price of chain C. It asserts that only the workers relaying
+//!   to or from chain C are stopped, and that the transfers over both channels are
+//!   still relayed afterwards.
+//!
+//! - [`ReloadAddedChainConfigTest`] starts the supervisor with a configuration without
+//!   chain B, then reloads the full configuration, and asserts that a transfer from
+//!   chain A to chain B gets acknowledged, which requires the supervisor to process
+//!   the events of chain B, as packet clearing is disabled.
+
+use ibc_relayer::chain::requests::{Paginate, QueryHeight, QueryPacketCommitmentsRequest};
+use ibc_relayer::config::filter::{ChannelFilters, ChannelPolicy, FilterPattern};
+use ibc_relayer::config::{self, ChainConfig, GasPrice, ModeConfig};
+use ibc_relayer::object::{Object, ObjectType};
+use ibc_test_framework::types::topology::TopologyType;
+use ibc_test_framework::{prelude::*, util::random::random_u128_range};
+
+#[test]
+fn test_reload_config_stops_removed_channel_workers() -> Result<(), Error> {
+    run_binary_channel_test(&ReloadConfigTest)
+}
+
+#[test]
+fn test_reload_config_subscribes_to_added_chains() -> Result<(), Error> {
+    run_binary_channel_test(&ReloadAddedChainConfigTest)
+}
+
+#[cfg(not(any(feature = "celestia")))]
+#[test]
+fn test_reload_config_restarts_updated_chain_workers() -> Result<(), Error> {
+    run_nary_channel_test(&ReloadUpdatedChainConfigTest)
+}
+
+/// Only relay packets, so that the packet workers are the only workers spawned
+fn packets_mode() -> ModeConfig {
+    ModeConfig {
+        clients: config::Clients {
+            enabled: false,
+            refresh: false,
+            misbehaviour: false,
+            smooth_refresh: false,
+        },
+        connections: config::Connections {
+            enabled: false,
+            ..Default::default()
+        },
+        channels: config::Channels { enabled: false },
+        packets: config::Packets {
+            enabled: true,
+            clear_interval: 10,
+            clear_on_start: false,
+            tx_confirmation: false,
+            ..Default::default()
+        },
+    }
+}
+
+pub struct ReloadConfigTest;
+
+impl TestOverrides for ReloadConfigTest {
+    fn modify_relayer_config(&self, config: &mut Config) {
+        config.mode = packets_mode();
+    }
+
+    fn should_spawn_supervisor(&self) -> bool {
+        false
+    }
+}
+
+impl BinaryChannelTest for ReloadConfigTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        channel: ConnectedChannel<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        let denom_a = chains.node_a.denom();
+
+        let wallet_a = chains.node_a.wallets().user1().cloned();
+        let wallet_b = chains.node_b.wallets().user1().cloned();
+
+        let amount = random_u128_range(1000, 5000);
+
+        let supervisor = relayer.spawn_supervisor()?;
+
+        // Transfer tokens so that the packet workers are spawned
+        chains.node_a.chain_driver().ibc_transfer_token(
+            &channel.port_a.as_ref(),
+            &channel.channel_id_a.as_ref(),
+            &wallet_a.as_ref(),
+            &wallet_b.address(),
+            &denom_a.with_amount(amount).as_ref(),
+        )?;
+
+        assert_eventually_succeed(
+            "packet workers are spawned",
+            50,
+            Duration::from_secs(1),
+            || {
+                let state = supervisor.dump_state()?;
+                let packet_workers = state.workers.get(&ObjectType::Packet).map_or(0, Vec::len);
+
+                if packet_workers == 2 {
+                    Ok(())
+                } else {
+                    Err(Error::generic(eyre!(
+                        "expected 2 packet workers, found {packet_workers}"
+                    )))
+                }
+            },
+        )?;
+
+        // Remove the channel from the configuration, by denying it on both chains
+        let mut new_config = relayer.config.clone();
+
+        for chain in &mut new_config.chains {
+            match chain {
+                ChainConfig::CosmosSdk(chain_config) | ChainConfig::Namada(chain_config) => {
+                    chain_config.packet_filter.channel_policy =
+                        ChannelPolicy::Deny(ChannelFilters::new(vec![(
+                            FilterPattern::Wildcard("*".parse().unwrap()),
+                            FilterPattern::Wildcard("*".parse().unwrap()),
+                        )]));
+                }
+                ChainConfig::Penumbra(_) => {
+                    panic!("running tests with Penumbra chain not supported")
+                }
+            }
+        }
+
+        let stopped = supervisor.reload_config(new_config)?;
+
+        let stopped_channels = stopped
+            .iter()
+            .filter_map(|object| match object {
+                Object::Packet(packet) => Some(packet.src_channel_id.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(stopped_channels.len(), 2);
+        assert!(stopped_channels.contains(channel.channel_id_a.value()));
+        assert!(stopped_channels.contains(channel.channel_id_b.value()));
+
+        // The workers and their state are dropped as soon as the configuration is reloaded
+        let state = supervisor.dump_state()?;
+        assert!(!state.workers.contains_key(&ObjectType::Packet));
+
+        Ok(())
+    }
+}
+
+pub struct ReloadAddedChainConfigTest;
+
+impl TestOverrides for ReloadAddedChainConfigTest {
+    fn modify_relayer_config(&self, config: &mut Config) {
+        config.mode = packets_mode();
+        config.mode.packets.clear_interval = 0;
+    }
+
+    fn should_spawn_supervisor(&self) -> bool {
+        false
+    }
+}
+
+impl BinaryChannelTest for ReloadAddedChainConfigTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        channel: ConnectedChannel<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        let denom_a = chains.node_a.denom();
+
+        let wallet_a = chains.node_a.wallets().user1().cloned();
+        let wallet_b = chains.node_b.wallets().user1().cloned();
+
+        let chain_id_b = (*chains.chain_id_b().value()).clone();
+
+        // Start without chain B
+        let mut relayer_without_b = relayer.clone();
+        relayer_without_b
+            .config
+            .chains
+            .retain(|chain_config| chain_config.id() != &chain_id_b);
+
+        let supervisor = relayer_without_b.spawn_supervisor()?;
+
+        supervisor.reload_config(relayer.config.clone())?;
+
+        let amount = random_u128_range(1000, 5000);
+
+        chains.node_a.chain_driver().ibc_transfer_token(
+            &channel.port_a.as_ref(),
+            &channel.channel_id_a.as_ref(),
+            &wallet_a.as_ref(),
+            &wallet_b.address(),
+            &denom_a.with_amount(amount).as_ref(),
+        )?;
+
+        assert_eventually_succeed(
+            "the transfer should be acknowledged on chain A",
+            60,
+            Duration::from_secs(1),
+            || {
+                let (commitments, _) =
+                    chains
+                        .handle_a()
+                        .query_packet_commitments(QueryPacketCommitmentsRequest {
+                            query_height: QueryHeight::Latest,
+                            port_id: channel.port_a.value().clone(),
+                            channel_id: channel.channel_id_a.value().clone(),
+                            pagination: Paginate::All,
+                        })?;
+
+                if commitments.is_empty() {
+                    Ok(())
+                } else {
+                    Err(Error::generic(eyre!(
+                        "packets {commitments:?} are not acknowledged yet"
+                    )))
+                }
+            },
+        )?;
+
+        Ok(())
+    }
+}
+
+pub struct ReloadUpdatedChainConfigTest;
+
+impl TestOverrides for ReloadUpdatedChainConfigTest {
+    fn modify_relayer_config(&self, config: &mut Config) {
+        config.mode = packets_mode();
+    }
+
+    fn should_spawn_supervisor(&self) -> bool {
+        false
+    }
+
+    fn topology(&self) -> Option<TopologyType> {
+        Some(TopologyType::Linear)
+    }
+}
+
+impl PortsOverride<3> for ReloadUpdatedChainConfigTest {}
+
+impl NaryChannelTest<3> for ReloadUpdatedChainConfigTest {
+    fn run<Handle: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        relayer: RelayerDriver,
+        chains: NaryConnectedChains<Handle, 3>,
+        channels: NaryConnectedChannels<Handle, 3>,
+    ) -> Result<(), Error> {
+        let node_a = chains.full_node_at::<0>()?;
+        let node_b = chains.full_node_at::<1>()?;
+        let node_c = chains.full_node_at::<2>()?;
+
+        let channel_a_to_b = channels.channel_at::<0, 1>()?;
+        let channel_b_to_c = channels.channel_at::<1, 2>()?;
+
+        let chain_id_c = (*node_c.chain_id().value()).clone();
+
+        let supervisor = relayer.spawn_supervisor()?;
+
+        let transfer_a_to_b = |amount: u64| -> Result<(), Error> {
+            let denom_a = node_a.denom();
+            let wallet_a = node_a.wallets().user1().cloned();
+            let wallet_b = node_b.wallets().user1().cloned();
+
+            let denom_a_to_b = derive_ibc_denom(
+                &node_b.chain_driver().value().chain_type,
+                &channel_a_to_b.port_b.as_ref(),
+                &channel_a_to_b.channel_id_b.as_ref(),
+                &denom_a,
+            )?;
+
+            let balance_b = node_b
+                .chain_driver()
+                .query_balance(&wallet_b.address(), &denom_a_to_b.as_ref())?;
+
+            node_a.chain_driver().ibc_transfer_token(
+                &channel_a_to_b.port_a.as_ref(),
+                &channel_a_to_b.channel_id_a.as_ref(),
+                &wallet_a.as_ref(),
+                &wallet_b.address(),
+                &denom_a.with_amount(amount).as_ref(),
+            )?;
+
+            node_b
+                .chain_driver()
+                .assert_eventual_wallet_amount(&wallet_b.address(), &(balance_b + amount).as_ref())
+        };
+
+        let transfer_b_to_c = |amount: u64| -> Result<(), Error> {
+            let denom_b = node_b.denom();
+            let wallet_b = node_b.wallets().user2().cloned();
+            let wallet_c = node_c.wallets().user1().cloned();
+
+            let denom_b_to_c = derive_ibc_denom(
+                &node_c.chain_driver().value().chain_type,
+                &channel_b_to_c.port_b.as_ref(),
+                &channel_b_to_c.channel_id_b.as_ref(),
+                &denom_b,
+            )?;
+
+            let balance_c = node_c
+                .chain_driver()
+                .query_balance(&wallet_c.address(), &denom_b_to_c.as_ref())?;
+
+            node_b.chain_driver().ibc_transfer_token(
+                &channel_b_to_c.port_a.as_ref(),
+                &channel_b_to_c.channel_id_a.as_ref(),
+                &wallet_b.as_ref(),
+                &wallet_c.address(),
+                &denom_b.with_amount(amount).as_ref(),
+            )?;
+
+            node_c
+                .chain_driver()
+                .assert_eventual_wallet_amount(&wallet_c.address(), &(balance_c + amount).as_ref())
+        };
+
+        // Relay over both channels so that their packet workers are spawned
+        transfer_a_to_b(1000)?;
+        transfer_b_to_c(1000)?;
+
+        let involves_chain_c = |object: &Object| {
+            object.src_chain_id() == &chain_id_c || object.dst_chain_id() == &chain_id_c
+        };
+
+        let state = supervisor.dump_state()?;
+        let packet_workers = state
+            .workers
+            .get(&ObjectType::Packet)
+            .cloned()
+            .unwrap_or_default();
+
+        let (workers_c, workers_a_b): (Vec<_>, Vec<_>) = packet_workers
+            .into_iter()
+            .partition(|desc| involves_chain_c(&desc.object));
+
+        assert!(!workers_c.is_empty());
+        assert!(!workers_a_b.is_empty());
+
+        // Only change the gas price of chain C
+        let mut new_config = relayer.config.clone();
+
+        match new_config
+            .find_chain_mut(&chain_id_c)
+            .expect("chain C is configured")
+        {
+            ChainConfig::CosmosSdk(chain_config) | ChainConfig::Namada(chain_config) => {
+                chain_config.gas_price = GasPrice::new(
+                    chain_config.gas_price.preferred().price * 2.0,
+                    chain_config.gas_price.preferred().denom.clone(),
+                )
+                .into();
+            }
+            ChainConfig::Penumbra(_) => {
+                panic!("running tests with Penumbra chain not supported")
+            }
+        }
+
+        let stopped = supervisor.reload_config(new_config)?;
+
+        // Only the workers relaying to or from chain C are stopped
+        assert!(!stopped.is_empty());
+        assert!(stopped.iter().all(involves_chain_c));
+
+        // The workers relaying between chains A and B keep running
+        let state = supervisor.dump_state()?;
+        let packet_workers = state
+            .workers
+            .get(&ObjectType::Packet)
+            .cloned()
+            .unwrap_or_default();
+
+        for desc in &workers_a_b {
+            assert!(
+                packet_workers.iter().any(|running| running.id == desc.id),
+                "worker for {} was restarted",
+                desc.object.short_name()
+            );
+        }
+
+        // Relays go on over both channels, with the restarted runtime of chain C
+        transfer_a_to_b(1000)?;
+        transfer_b_to_c(1000)?;
+
+        Ok(())
+    }
+}
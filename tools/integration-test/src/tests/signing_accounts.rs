@@ -0,0 +1,154 @@
+//! Tests the `signing_accounts` configuration, which allows signing the
+//! transactions relayed to a chain in turn with several keys, each with
+//! its own account sequence.
+//!
+//! The relayer is configured on chain B with the `user1` and `user2` keys as
+//! signing accounts. The test relays a transfer over each of two channels
+//! between chain A and chain B, and asserts that the `MsgRecvPacket` messages
+//! received by chain B were signed by both keys.
+
+use std::collections::BTreeSet;
+
+use ibc_relayer::config::ChainConfig;
+use ibc_test_framework::bootstrap::binary::channel::bootstrap_channel_with_connection;
+use ibc_test_framework::prelude::*;
+use ibc_test_framework::util::random::random_u128_range;
+
+const SIGNING_ACCOUNTS: [&str; 2] = ["user1", "user2"];
+
+#[test]
+fn test_signing_accounts() -> Result<(), Error> {
+    run_binary_connection_test(&SigningAccountsTest)
+}
+
+pub struct SigningAccountsTest;
+
+impl TestOverrides for SigningAccountsTest {
+    fn modify_test_config(&self, config: &mut TestConfig) {
+        // Ensure the wallets have predictable key names
+        config.bootstrap_with_random_ids = false;
+    }
+
+    fn modify_relayer_config(&self, config: &mut Config) {
+        match &mut config.chains[1] {
+            ChainConfig::CosmosSdk(chain_config) | ChainConfig::Namada(chain_config) => {
+                chain_config.signing_accounts =
+                    SIGNING_ACCOUNTS.iter().map(|key| key.to_string()).collect();
+            }
+            ChainConfig::Penumbra(_) => panic!("running tests with Penumbra chain not supported"),
+        }
+    }
+
+    fn should_spawn_supervisor(&self) -> bool {
+        false
+    }
+}
+
+impl BinaryConnectionTest for SigningAccountsTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        connection: ConnectedConnection<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        let port = PortId::transfer();
+
+        let channels = [
+            bootstrap_channel_with_connection(
+                &chains.handle_a,
+                &chains.handle_b,
+                connection.clone(),
+                &DualTagged::new(&port),
+                &DualTagged::new(&port),
+                Default::default(),
+            )?,
+            bootstrap_channel_with_connection(
+                &chains.handle_a,
+                &chains.handle_b,
+                connection,
+                &DualTagged::new(&port),
+                &DualTagged::new(&port),
+                Default::default(),
+            )?,
+        ];
+
+        let denom_a = chains.node_a.denom();
+        let wallet_a = chains.node_a.wallets().user1().cloned();
+        let wallet_b = chains.node_b.wallets().user1().cloned();
+
+        relayer.with_supervisor(|| {
+            for channel in &channels {
+                let amount = random_u128_range(1000, 5000);
+
+                info!(
+                    "sending {} {} from chain A to chain B over {}",
+                    amount, denom_a, channel.channel_id_a
+                );
+
+                chains.node_a.chain_driver().ibc_transfer_token(
+                    &channel.port_a.as_ref(),
+                    &channel.channel_id_a.as_ref(),
+                    &wallet_a.as_ref(),
+                    &wallet_b.address(),
+                    &denom_a.with_amount(amount).as_ref(),
+                )?;
+
+                let denom_b = derive_ibc_denom(
+                    &chains.node_b.chain_driver().value().chain_type,
+                    &channel.port_b.as_ref(),
+                    &channel.channel_id_b.as_ref(),
+                    &denom_a,
+                )?;
+
+                chains.node_b.chain_driver().assert_eventual_wallet_amount(
+                    &wallet_b.address(),
+                    &denom_b.with_amount(amount).as_ref(),
+                )?;
+            }
+
+            Ok(())
+        })?;
+
+        let signers = query_recv_packet_signers(&chains, &wallet_b.address())?;
+
+        let wallets_b = chains.node_b.wallets();
+        let expected: BTreeSet<String> = [
+            wallets_b.user1().address().value().to_string(),
+            wallets_b.user2().address().value().to_string(),
+        ]
+        .into();
+
+        assert_eq!(signers, expected);
+
+        Ok(())
+    }
+}
+
+/// Returns the signers of the `MsgRecvPacket` messages which transferred tokens to the given recipient.
+fn query_recv_packet_signers<ChainA: ChainHandle, ChainB: ChainHandle>(
+    chains: &ConnectedChains<ChainA, ChainB>,
+    recipient: &MonoTagged<ChainB, &WalletAddress>,
+) -> Result<BTreeSet<String>, Error> {
+    let tx_info = chains
+        .node_b
+        .chain_driver()
+        .query_recipient_transactions(recipient)?;
+
+    debug!("looking up MsgRecvPacket signers in {}", tx_info);
+
+    let txs = tx_info["txs"]
+        .as_array()
+        .ok_or_else(|| eyre!("expect txs array field to be present in JSON"))?;
+
+    let signers = txs
+        .iter()
+        .filter_map(|tx| tx["tx"]["body"]["messages"].as_array())
+        .flatten()
+        .filter(|msg| msg["@type"] == "/ibc.core.channel.v1.MsgRecvPacket")
+        .filter_map(|msg| msg["signer"].as_str())
+        .map(str::to_owned)
+        .collect();
+
+    Ok(signers)
+}
@@ -0,0 +1,106 @@
+//! Tests that Hermes recovers from a race on the account sequence number of its
+//! account, reproduced deterministically with a submit delay.
+//!
+//! The test delays the broadcast of the transactions submitted by the relayer to
+//! chain B, and relays a transfer from chain A. While the transaction of the relayer
+//! is held back, it submits a transaction from the relayer account, which consumes
+//! the sequence number the delayed transaction was signed with. Once the delay is
+//! cleared, the relayer must refresh its account sequence number, resubmit and
+//! relay the transfer.
+
+use ibc_test_framework::prelude::*;
+use ibc_test_framework::relayer::submit_delay::SubmitDelay;
+use ibc_test_framework::util::random::random_u128_range;
+
+#[test]
+fn test_submit_delay_sequence_race() -> Result<(), Error> {
+    run_binary_channel_test(&SubmitDelaySequenceRaceTest)
+}
+
+pub struct SubmitDelaySequenceRaceTest;
+
+impl TestOverrides for SubmitDelaySequenceRaceTest {
+    fn modify_relayer_config(&self, config: &mut Config) {
+        config.mode.clients.misbehaviour = false;
+        config.mode.clients.refresh = false;
+        config.mode.packets.enabled = true;
+    }
+
+    fn should_spawn_supervisor(&self) -> bool {
+        false
+    }
+}
+
+impl BinaryChannelTest for SubmitDelaySequenceRaceTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        config: &TestConfig,
+        relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        channel: ConnectedChannel<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        let fee_denom_b: MonoTagged<ChainB, Denom> =
+            MonoTagged::new(Denom::base(config.native_token(1), config.native_token(1)));
+        let denom_a = chains.node_a.denom();
+        let denom_b = chains.node_b.denom();
+
+        let wallet_a = chains.node_a.wallets().user1().cloned();
+        let wallet_b = chains.node_b.wallets().user1().cloned();
+
+        let relayer_wallet_b = chains.node_b.wallets().relayer().cloned();
+
+        let submit_delay = SubmitDelay::new(chains.handle_b().clone(), Duration::from_secs(10))?;
+
+        let _supervisor = relayer.spawn_supervisor()?;
+
+        let amount = random_u128_range(1000, 5000);
+
+        chains.node_a.chain_driver().ibc_transfer_token(
+            &channel.port_a.as_ref(),
+            &channel.channel_id_a.as_ref(),
+            &wallet_a.as_ref(),
+            &wallet_b.address(),
+            &denom_a.with_amount(amount).as_ref(),
+        )?;
+
+        assert_eventually_succeed(
+            "the relayer tx to chain B is delayed",
+            30,
+            Duration::from_secs(1),
+            || {
+                if submit_delay.delayed_submissions()? > 0 {
+                    Ok(())
+                } else {
+                    Err(Error::generic(eyre!(
+                        "no relayer tx to chain B was delayed yet"
+                    )))
+                }
+            },
+        )?;
+
+        // Consume the sequence number of the delayed tx of the relayer
+        chains.node_b.chain_driver().local_transfer_token(
+            &relayer_wallet_b.as_ref(),
+            &wallet_b.address(),
+            &denom_b.with_amount(100u64).as_ref(),
+            &fee_denom_b.with_amount(381000000u64).as_ref(),
+        )?;
+
+        // Resolve the race, by broadcasting the next txs without delay
+        drop(submit_delay);
+
+        let denom_a_to_b = derive_ibc_denom(
+            &chains.node_b.chain_driver().value().chain_type,
+            &channel.port_b.as_ref(),
+            &channel.channel_id_b.as_ref(),
+            &denom_a,
+        )?;
+
+        chains.node_b.chain_driver().assert_eventual_wallet_amount(
+            &wallet_b.address(),
+            &denom_a_to_b.with_amount(amount).as_ref(),
+        )?;
+
+        Ok(())
+    }
+}
@@ -34,8 +34,12 @@ impl TestOverrides for SupervisorTest {
                 enabled: true,
                 refresh: true,
                 misbehaviour: true,
+                smooth_refresh: false,
+            },
+            connections: config::Connections {
+                enabled: true,
+                ..Default::default()
             },
-            connections: config::Connections { enabled: true },
             channels: config::Channels { enabled: true },
             packets: config::Packets {
                 enabled: true,
@@ -179,8 +183,12 @@ impl TestOverrides for SupervisorScanTest {
                 enabled: false, // disable client workers, otherwise we have to scan
                 refresh: true,
                 misbehaviour: true,
+                smooth_refresh: false,
+            },
+            connections: config::Connections {
+                enabled: true,
+                ..Default::default()
             },
-            connections: config::Connections { enabled: true },
             channels: config::Channels { enabled: true },
             packets: config::Packets {
                 enabled: true,
@@ -0,0 +1,205 @@
+//! Tests the `retry_strategy` of the submission of transactions.
+//!
+//! The test injects transient failures by submitting transactions from the relayer
+//! account of chain B behind its back, which outdates the account sequence number
+//! cached by the relayer, then asserts that a transfer is still relayed to chain B
+//! once the relayer refreshed the sequence number and retried with an exponential backoff.
+//!
+//! A second test configures a single attempt, and asserts that a transaction submitted
+//! with an outdated account sequence number then fails without being retried, reporting
+//! the number of attempts and the error of the chain, whereas it used to be retried once.
+
+use core::num::NonZeroU32;
+
+use ibc_relayer::chain::tracking::TrackedMsgs;
+use ibc_relayer::config::retry_strategy::{Backoff, RetryStrategy};
+use ibc_relayer::config::ChainConfig;
+use ibc_relayer::error::ErrorDetail as RelayerErrorDetail;
+use ibc_test_framework::prelude::*;
+use ibc_test_framework::relayer::transfer::build_transfer_message;
+use ibc_test_framework::util::random::random_u128_range;
+
+#[test]
+fn test_tx_retry_strategy() -> Result<(), Error> {
+    run_binary_channel_test(&TxRetryStrategyTest)
+}
+
+#[test]
+fn test_tx_retries_exhausted() -> Result<(), Error> {
+    run_binary_channel_test(&TxRetriesExhaustedTest)
+}
+
+pub struct TxRetryStrategyTest;
+
+impl TestOverrides for TxRetryStrategyTest {
+    fn modify_relayer_config(&self, config: &mut Config) {
+        config.mode.packets.enabled = true;
+        config.mode.packets.clear_on_start = true;
+
+        match &mut config.chains[1] {
+            ChainConfig::CosmosSdk(chain_config) | ChainConfig::Namada(chain_config) => {
+                chain_config.retry_strategy = RetryStrategy {
+                    backoff: Backoff::Exponential,
+                    base_delay: Duration::from_millis(200),
+                    max_attempts: NonZeroU32::new(4).unwrap(),
+                };
+            }
+            ChainConfig::Penumbra(_) => panic!("running tests with Penumbra chain not supported"),
+        }
+    }
+
+    fn should_spawn_supervisor(&self) -> bool {
+        false
+    }
+}
+
+impl BinaryChannelTest for TxRetryStrategyTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        config: &TestConfig,
+        relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        channel: ConnectedChannel<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        let fee_denom_b: MonoTagged<ChainB, Denom> =
+            MonoTagged::new(Denom::base(config.native_token(1), config.native_token(1)));
+        let denom_a = chains.node_a.denom();
+        let denom_b = chains.node_b.denom();
+
+        let wallet_a = chains.node_a.wallets().user1().cloned();
+        let wallet_b = chains.node_b.wallets().user1().cloned();
+
+        let relayer_wallet_b = chains.node_b.wallets().relayer().cloned();
+
+        // Outdate the account sequence number cached by the chain handle of chain B
+        for _ in 0..2 {
+            chains.node_b.chain_driver().local_transfer_token(
+                &relayer_wallet_b.as_ref(),
+                &wallet_b.address(),
+                &denom_b.with_amount(100u64).as_ref(),
+                &fee_denom_b.with_amount(381000000u64).as_ref(),
+            )?;
+        }
+
+        let amount = random_u128_range(1000, 5000);
+
+        chains.node_a.chain_driver().ibc_transfer_token(
+            &channel.port_a.as_ref(),
+            &channel.channel_id_a.as_ref(),
+            &wallet_a.as_ref(),
+            &wallet_b.address(),
+            &denom_a.with_amount(amount).as_ref(),
+        )?;
+
+        let denom_a_to_b = derive_ibc_denom(
+            &chains.node_b.chain_driver().value().chain_type,
+            &channel.port_b.as_ref(),
+            &channel.channel_id_b.as_ref(),
+            &denom_a,
+        )?;
+
+        relayer.with_supervisor(|| {
+            chains.node_b.chain_driver().assert_eventual_wallet_amount(
+                &wallet_b.address(),
+                &denom_a_to_b.with_amount(amount).as_ref(),
+            )?;
+
+            Ok(())
+        })
+    }
+}
+
+pub struct TxRetriesExhaustedTest;
+
+impl TestOverrides for TxRetriesExhaustedTest {
+    fn modify_relayer_config(&self, config: &mut Config) {
+        match &mut config.chains[1] {
+            ChainConfig::CosmosSdk(chain_config) | ChainConfig::Namada(chain_config) => {
+                chain_config.retry_strategy = RetryStrategy {
+                    max_attempts: NonZeroU32::new(1).unwrap(),
+                    ..Default::default()
+                };
+            }
+            ChainConfig::Penumbra(_) => panic!("running tests with Penumbra chain not supported"),
+        }
+    }
+
+    fn should_spawn_supervisor(&self) -> bool {
+        false
+    }
+}
+
+impl BinaryChannelTest for TxRetriesExhaustedTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        config: &TestConfig,
+        _relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        channel: ConnectedChannel<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        let fee_denom_b: MonoTagged<ChainB, Denom> =
+            MonoTagged::new(Denom::base(config.native_token(1), config.native_token(1)));
+        let denom_b = chains.node_b.denom();
+
+        let wallet_a = chains.node_a.wallets().user1().cloned();
+        let wallet_b = chains.node_b.wallets().user1().cloned();
+        let relayer_wallet_b = chains.node_b.wallets().relayer().cloned();
+
+        let transfer = |memo: &str| {
+            let message = build_transfer_message(
+                &channel.port_b.as_ref(),
+                &channel.channel_id_b.as_ref(),
+                &relayer_wallet_b.as_ref(),
+                &wallet_a.address(),
+                &denom_b.with_amount(100u64).as_ref(),
+                Duration::from_secs(60),
+                Some(memo.to_owned()),
+            )?;
+
+            Ok::<_, Error>(TrackedMsgs::new_static(
+                vec![message],
+                "test_tx_retries_exhausted",
+            ))
+        };
+
+        // The chain handle of chain B caches the account sequence number of the relayer
+        chains
+            .handle_b()
+            .send_messages_and_wait_commit(transfer("first")?)?;
+
+        // Outdate the account sequence number cached by the chain handle of chain B
+        for _ in 0..2 {
+            chains.node_b.chain_driver().local_transfer_token(
+                &relayer_wallet_b.as_ref(),
+                &wallet_b.address(),
+                &denom_b.with_amount(100u64).as_ref(),
+                &fee_denom_b.with_amount(381000000u64).as_ref(),
+            )?;
+        }
+
+        let result = chains
+            .handle_b()
+            .send_messages_and_wait_commit(transfer("second")?);
+
+        match result {
+            Err(e) => match e.detail() {
+                RelayerErrorDetail::TxRetriesExhausted(e) => {
+                    assert_eq("the tx should be submitted once", &e.attempts, &1)?;
+                    assert!(
+                        e.last_error.contains("account sequence mismatch"),
+                        "the last error of the chain should be reported, got: {}",
+                        e.last_error
+                    );
+
+                    Ok(())
+                }
+                _ => Err(Error::generic(eyre!(
+                    "expected the retries of the tx to be exhausted, got: {e}"
+                ))),
+            },
+            Ok(events) => Err(Error::generic(eyre!(
+                "the tx with an outdated account sequence should not be retried, got: {events:?}"
+            ))),
+        }
+    }
+}
@@ -79,6 +79,7 @@ pub fn init_test() -> Result<TestConfig, Error> {
         native_tokens,
         ipv6_grpc,
         compat_modes,
+        extra_start_args: vec![],
     })
 }
 
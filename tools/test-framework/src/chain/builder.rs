@@ -47,6 +47,8 @@ pub struct ChainBuilder {
 
     pub ipv6_grpc: bool,
 
+    pub extra_start_args: Vec<String>,
+
     pub runtime: Arc<Runtime>,
 }
 
@@ -61,6 +63,7 @@ impl ChainBuilder {
         native_tokens: Vec<String>,
         compat_modes: Option<Vec<String>>,
         ipv6_grpc: bool,
+        extra_start_args: Vec<String>,
         runtime: Arc<Runtime>,
     ) -> Self {
         Self {
@@ -70,6 +73,7 @@ impl ChainBuilder {
             native_tokens,
             compat_modes,
             ipv6_grpc,
+            extra_start_args,
             runtime,
         }
     }
@@ -85,6 +89,7 @@ impl ChainBuilder {
             config.native_tokens.clone(),
             config.compat_modes.clone(),
             config.ipv6_grpc,
+            config.extra_start_args.clone(),
             runtime,
         )
     }
@@ -152,6 +157,7 @@ impl ChainBuilder {
             self.native_tokens[native_token_number].clone(),
             compat_mode,
             self.ipv6_grpc,
+            self.extra_start_args.clone(),
         )?;
 
         Ok(driver)
@@ -102,6 +102,12 @@ pub struct ChainDriver {
     pub compat_mode: Option<CompatMode>,
 
     pub ipv6_grpc: bool,
+
+    /**
+       Extra arguments given to the `start` command of the chain, after
+       the ones of its [`ChainType`].
+    */
+    pub extra_start_args: Vec<String>,
 }
 
 impl ExportEnv for ChainDriver {
@@ -130,6 +136,7 @@ impl ChainDriver {
         native_token: String,
         compat_mode: Option<CompatMode>,
         ipv6_grpc: bool,
+        extra_start_args: Vec<String>,
     ) -> Result<Self, Error> {
         let grpc_address = if ipv6_grpc {
             format!("http://[::1]:{grpc_port}")
@@ -160,6 +167,7 @@ impl ChainDriver {
             runtime,
             compat_mode,
             ipv6_grpc,
+            extra_start_args,
         })
     }
 
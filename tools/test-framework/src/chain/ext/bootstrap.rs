@@ -281,7 +281,8 @@ impl ChainBootstrapMethodsExt for ChainDriver {
     }
 
     fn start(&self) -> Result<ChildProcess, Error> {
-        let extra_start_args = self.chain_type.extra_start_args();
+        let mut extra_start_args = self.chain_type.extra_start_args();
+        extra_start_args.extend(self.extra_start_args.iter().cloned());
 
         start_chain(
             &self.command_path,
@@ -1,4 +1,5 @@
 use core::time::Duration;
+use eyre::eyre;
 
 use ibc_relayer_types::core::ics02_client::height::Height;
 use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, PortId};
@@ -11,7 +12,7 @@ use crate::error::Error;
 use crate::ibc::token::TaggedTokenRef;
 use crate::relayer::transfer::{
     batched_ibc_token_transfer, ibc_namada_token_transfer, ibc_token_transfer,
-    local_namada_token_transfer,
+    local_namada_token_transfer, query_latest_height,
 };
 use crate::types::id::{TaggedChannelIdRef, TaggedPortIdRef};
 use crate::types::tagged::*;
@@ -47,7 +48,14 @@ pub trait ChainTransferMethodsExt<Chain> {
         token: &TaggedTokenRef<Chain>,
     ) -> Result<(), Error>;
 
-    fn ibc_transfer_token_with_memo_and_timeout<Counterparty>(
+    /**
+       Submits an IBC token transfer transaction like
+       [`ibc_transfer_token`](ChainTransferMethodsExt::ibc_transfer_token),
+       with the given memo, which times out either after the given `timeout`,
+       or after the given number of blocks of the `Counterparty` chain, whose
+       latest height is queried from the given driver. Specifying both is an error.
+    */
+    fn ibc_transfer_token_with_memo_and_timeout<Counterparty: Send>(
         &self,
         port_id: &TaggedPortIdRef<Chain, Counterparty>,
         channel_id: &TaggedChannelIdRef<Chain, Counterparty>,
@@ -56,6 +64,7 @@ pub trait ChainTransferMethodsExt<Chain> {
         token: &TaggedTokenRef<Chain>,
         memo: Option<String>,
         timeout: Option<Duration>,
+        timeout_height_offset: Option<(u64, &MonoTagged<Counterparty, &ChainDriver>)>,
     ) -> Result<(), Error>;
 
     fn ibc_transfer_token_multiple<Counterparty>(
@@ -122,12 +131,13 @@ impl<Chain: Send> ChainTransferMethodsExt<Chain> for MonoTagged<Chain, &ChainDri
                     token,
                     None,
                     None,
+                    None,
                 ))
             }
         }
     }
 
-    fn ibc_transfer_token_with_memo_and_timeout<Counterparty>(
+    fn ibc_transfer_token_with_memo_and_timeout<Counterparty: Send>(
         &self,
         port_id: &TaggedPortIdRef<Chain, Counterparty>,
         channel_id: &TaggedChannelIdRef<Chain, Counterparty>,
@@ -136,8 +146,12 @@ impl<Chain: Send> ChainTransferMethodsExt<Chain> for MonoTagged<Chain, &ChainDri
         token: &TaggedTokenRef<Chain>,
         memo: Option<String>,
         timeout: Option<Duration>,
+        timeout_height_offset: Option<(u64, &MonoTagged<Counterparty, &ChainDriver>)>,
     ) -> Result<(), Error> {
         match self.value().chain_type {
+            ChainType::Namada if timeout_height_offset.is_some() => Err(Error::generic(eyre!(
+                "a timeout height offset is not supported for transfers from Namada"
+            ))),
             ChainType::Namada => {
                 let denom = token.value().denom.to_string();
                 let amount = token.value().amount.to_string();
@@ -155,6 +169,19 @@ impl<Chain: Send> ChainTransferMethodsExt<Chain> for MonoTagged<Chain, &ChainDri
                 )
             }
             _ => {
+                let timeout_height = match timeout_height_offset {
+                    Some((offset, counterparty)) => {
+                        let latest_height =
+                            counterparty.value().runtime.block_on(query_latest_height(
+                                counterparty.rpc_client()?.as_ref(),
+                                &counterparty.value().chain_id,
+                            ))?;
+
+                        Some(latest_height + offset)
+                    }
+                    None => None,
+                };
+
                 let rpc_client = self.rpc_client()?;
                 self.value().runtime.block_on(ibc_token_transfer(
                     rpc_client.as_ref(),
@@ -166,6 +193,7 @@ impl<Chain: Send> ChainTransferMethodsExt<Chain> for MonoTagged<Chain, &ChainDri
                     token,
                     memo,
                     timeout,
+                    timeout_height,
                 ))
             }
         }
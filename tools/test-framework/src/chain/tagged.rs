@@ -5,12 +5,13 @@ use eyre::eyre;
 use serde_json as json;
 
 use ibc_proto::google::protobuf::Any;
+use ibc_relayer::chain::cosmos::request_rate::PacedRpcClient;
 use ibc_relayer::chain::cosmos::tx::simple_send_tx;
 use ibc_relayer::chain::cosmos::types::config::TxConfig;
 use ibc_relayer::config::compat_mode::CompatMode;
 use ibc_relayer::event::IbcEventWithHeight;
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
-use tendermint_rpc::client::{Client, HttpClient};
+use tendermint_rpc::HttpClient;
 use tendermint_rpc::Url;
 use tracing::warn;
 
@@ -43,7 +44,7 @@ pub trait TaggedChainDriverExt<Chain> {
     ///
     /// The RPC server must be running and be able to respond on the
     /// `/status` endpoint.
-    fn rpc_client(&self) -> Result<MonoTagged<Chain, HttpClient>, Error>;
+    fn rpc_client(&self) -> Result<MonoTagged<Chain, PacedRpcClient>, Error>;
 
     fn send_tx(
         &self,
@@ -120,11 +121,14 @@ impl<Chain: Send> TaggedChainDriverExt<Chain> for MonoTagged<Chain, &ChainDriver
         self.map_ref(|val| &val.tx_config)
     }
 
-    fn rpc_client(&self) -> Result<MonoTagged<Chain, HttpClient>, Error> {
+    fn rpc_client(&self) -> Result<MonoTagged<Chain, PacedRpcClient>, Error> {
         let rpc_address = self.value().tx_config.rpc_address.clone();
         let rt = &self.value().runtime;
 
-        let mut client = HttpClient::new(rpc_address.clone()).map_err(handle_generic_error)?;
+        let mut client = PacedRpcClient::new(
+            HttpClient::new(rpc_address.clone()).map_err(handle_generic_error)?,
+            None,
+        );
 
         let compat_mode = rt.block_on(fetch_compat_mode(
             &client,
@@ -225,7 +229,7 @@ impl<Chain: Send> TaggedChainDriverExt<Chain> for MonoTagged<Chain, &ChainDriver
 }
 
 pub async fn fetch_compat_mode(
-    client: &HttpClient,
+    client: &PacedRpcClient,
     id: &ChainId,
     rpc_addr: &Url,
     configured_mode: &Option<CompatMode>,
@@ -32,8 +32,9 @@ use ibc_proto::ibc::apps::fee::v1::{
 };
 use ibc_relayer::account::Balance;
 use ibc_relayer::chain::client::ClientSettings;
-use ibc_relayer::chain::endpoint::{ChainStatus, HealthCheck};
+use ibc_relayer::chain::endpoint::ChainStatus;
 use ibc_relayer::chain::handle::{ChainHandle, ChainRequest, Subscription};
+use ibc_relayer::chain::health::HealthReport;
 use ibc_relayer::chain::requests::*;
 use ibc_relayer::chain::tracking::TrackedMsgs;
 use ibc_relayer::client_state::{AnyClientState, IdentifiedAnyClientState};
@@ -87,7 +88,7 @@ where
         self.value().shutdown()
     }
 
-    fn health_check(&self) -> Result<HealthCheck, Error> {
+    fn health_check(&self) -> Result<HealthReport, Error> {
         self.value().health_check()
     }
 
@@ -113,6 +114,14 @@ where
         self.value().get_signer()
     }
 
+    fn get_key_signer(&self, key_name: String) -> Result<Signer, Error> {
+        self.value().get_key_signer(key_name)
+    }
+
+    fn next_signing_account(&self) -> Result<Option<String>, Error> {
+        self.value().next_signing_account()
+    }
+
     fn config(&self) -> Result<ChainConfig, Error> {
         self.value().config()
     }
@@ -137,6 +146,10 @@ where
         self.value().query_latest_height()
     }
 
+    fn query_earliest_height(&self) -> Result<Height, Error> {
+        self.value().query_earliest_height()
+    }
+
     fn query_clients(
         &self,
         request: QueryClientStatesRequest,
@@ -461,4 +474,14 @@ where
     fn query_ccv_consumer_id(&self, client_id: &ClientId) -> Result<ConsumerId, Error> {
         self.value().query_ccv_consumer_id(client_id)
     }
+
+    #[cfg(feature = "submit-delay")]
+    fn set_submit_delay(&self, delay: Option<core::time::Duration>) -> Result<(), Error> {
+        self.value().set_submit_delay(delay)
+    }
+
+    #[cfg(feature = "submit-delay")]
+    fn query_delayed_submissions(&self) -> Result<u64, Error> {
+        self.value().query_delayed_submissions()
+    }
 }
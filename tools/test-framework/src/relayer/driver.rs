@@ -2,13 +2,18 @@
    Driver for spawning the relayer.
 */
 
-use ibc_relayer::chain::handle::CountingAndCachingChainHandle;
+use ibc_relayer::chain::handle::{ChainHandle, CountingAndCachingChainHandle};
 use ibc_relayer::config::Config;
+use ibc_relayer::link::{Link, LinkParameters};
 use ibc_relayer::registry::SharedRegistry;
 use ibc_relayer::supervisor::{spawn_supervisor, SupervisorHandle, SupervisorOptions};
 use std::path::PathBuf;
 
+use ibc_relayer_types::events::IbcEvent;
+
 use crate::error::Error;
+use crate::types::binary::chains::ConnectedChains;
+use crate::types::binary::channel::ConnectedChannel;
 use crate::types::env::{EnvWriter, ExportEnv};
 use crate::util::suspend::hang_on_error;
 
@@ -80,6 +85,55 @@ impl RelayerDriver {
 
         hang_on_error(self.hang_on_fail, cont)
     }
+
+    /**
+       Clears the packets pending on the given channel in both directions,
+       the same way as `hermes clear packets --stop-on-completion`, and returns
+       once all of them are relayed and acknowledged.
+    */
+    pub fn clear_packets_until_complete<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        chains: &ConnectedChains<ChainA, ChainB>,
+        channel: &ConnectedChannel<ChainA, ChainB>,
+    ) -> Result<Vec<IbcEvent>, Error> {
+        let packet_config = &self.config.mode.packets;
+
+        let link_parameters = |src_port_id, src_channel_id| LinkParameters {
+            src_port_id,
+            src_channel_id,
+            max_memo_size: packet_config.ics20_max_memo_size,
+            max_receiver_size: packet_config.ics20_max_receiver_size,
+            exclude_src_sequences: vec![],
+            max_clear_duration: None,
+            verification: packet_config.channel_verification(),
+            clear_order: packet_config.clear_order,
+        };
+
+        let link = Link::new_from_opts(
+            chains.handle_a().clone(),
+            chains.handle_b().clone(),
+            link_parameters(
+                channel.port_a.clone().into_value(),
+                channel.channel_id_a.clone().into_value(),
+            ),
+            false,
+            false,
+        )?;
+
+        let rev_link = Link::new_from_opts(
+            chains.handle_b().clone(),
+            chains.handle_a().clone(),
+            link_parameters(
+                channel.port_b.clone().into_value(),
+                channel.channel_id_b.clone().into_value(),
+            ),
+            false,
+            false,
+        )?;
+
+        link.clear_packets_until_complete(&rev_link)
+            .map_err(Error::link)
+    }
 }
 
 impl ExportEnv for RelayerDriver {
@@ -6,6 +6,7 @@ use ibc_relayer::chain::cosmos::query::fee::{
     query_counterparty_payee as raw_query_counterparty_payee,
     query_incentivized_packets as raw_query_incentivized_packets,
 };
+use ibc_relayer::chain::cosmos::request_rate::PacedRpcClient;
 use ibc_relayer::chain::cosmos::tx::simple_send_tx;
 use ibc_relayer::chain::cosmos::types::config::TxConfig;
 use ibc_relayer::event::IbcEventWithHeight;
@@ -16,7 +17,6 @@ use ibc_relayer_types::applications::ics29_fee::msgs::register_payee::{
 };
 use ibc_relayer_types::applications::ics29_fee::packet_fee::IdentifiedPacketFees;
 use ibc_relayer_types::core::ics04_channel::packet::Sequence;
-use tendermint_rpc::HttpClient;
 
 use crate::error::{handle_generic_error, Error};
 use crate::ibc::token::{TaggedTokenExt, TaggedTokenRef};
@@ -26,7 +26,7 @@ use crate::types::tagged::{DualTagged, MonoTagged};
 use crate::types::wallet::{Wallet, WalletAddress};
 
 pub async fn ibc_token_transfer_with_fee<SrcChain, DstChain>(
-    rpc_client: MonoTagged<SrcChain, &HttpClient>,
+    rpc_client: MonoTagged<SrcChain, &PacedRpcClient>,
     tx_config: &MonoTagged<SrcChain, &TxConfig>,
     port_id: &TaggedPortIdRef<'_, SrcChain, DstChain>,
     channel_id: &TaggedChannelIdRef<'_, SrcChain, DstChain>,
@@ -77,7 +77,7 @@ pub async fn ibc_token_transfer_with_fee<SrcChain, DstChain>(
 }
 
 pub async fn pay_packet_fee<Chain, Counterparty>(
-    rpc_client: MonoTagged<Chain, &HttpClient>,
+    rpc_client: MonoTagged<Chain, &PacedRpcClient>,
     tx_config: &MonoTagged<Chain, &TxConfig>,
     port_id: &TaggedPortIdRef<'_, Chain, Counterparty>,
     channel_id: &TaggedChannelIdRef<'_, Chain, Counterparty>,
@@ -117,7 +117,7 @@ pub async fn pay_packet_fee<Chain, Counterparty>(
 }
 
 pub async fn register_counterparty_payee<Chain, Counterparty>(
-    rpc_client: MonoTagged<Chain, &HttpClient>,
+    rpc_client: MonoTagged<Chain, &PacedRpcClient>,
     tx_config: &MonoTagged<Chain, &TxConfig>,
     wallet: &MonoTagged<Chain, &Wallet>,
     counterparty_payee: &MonoTagged<Counterparty, &WalletAddress>,
@@ -155,7 +155,7 @@ pub async fn register_counterparty_payee<Chain, Counterparty>(
 }
 
 pub async fn register_payee<Chain, Counterparty>(
-    rpc_client: MonoTagged<Chain, &HttpClient>,
+    rpc_client: MonoTagged<Chain, &PacedRpcClient>,
     tx_config: &MonoTagged<Chain, &TxConfig>,
     wallet: &MonoTagged<Chain, &Wallet>,
     payee: &MonoTagged<Chain, &WalletAddress>,
@@ -195,6 +195,8 @@ pub async fn query_counterparty_payee<Chain, Counterparty>(
 ) -> Result<Option<MonoTagged<Counterparty, WalletAddress>>, Error> {
     let counterparty_payee = raw_query_counterparty_payee(
         grpc_address,
+        None,
+        None,
         channel_id.value(),
         &address.value().0.parse().map_err(handle_generic_error)?,
     )
@@ -209,7 +211,13 @@ pub async fn query_incentivized_packets<Chain, Counterparty>(
     channel_id: &TaggedChannelIdRef<'_, Chain, Counterparty>,
     port_id: &TaggedPortIdRef<'_, Chain, Counterparty>,
 ) -> Result<Vec<IdentifiedPacketFees>, Error> {
-    raw_query_incentivized_packets(grpc_address, channel_id.value(), port_id.value())
-        .await
-        .map_err(handle_generic_error)
+    raw_query_incentivized_packets(
+        grpc_address,
+        None,
+        None,
+        channel_id.value(),
+        port_id.value(),
+    )
+    .await
+    .map_err(handle_generic_error)
 }
@@ -25,5 +25,7 @@ pub mod driver;
 pub mod fee;
 pub mod foreign_client;
 pub mod refresh;
+#[cfg(feature = "submit-delay")]
+pub mod submit_delay;
 pub mod transfer;
 pub mod tx;
@@ -0,0 +1,46 @@
+/*!
+   Delay the broadcast of the transactions submitted by the relayer to a chain,
+   in order to reproduce races between the relayer and other agents.
+
+   Only available with the `submit-delay` feature.
+*/
+
+use core::time::Duration;
+
+use ibc_relayer::chain::handle::ChainHandle;
+
+use crate::error::Error;
+
+/**
+   Delays the broadcast of the transactions submitted by the relayer
+   to a chain, until the value is dropped.
+*/
+pub struct SubmitDelay<Chain: ChainHandle> {
+    handle: Chain,
+}
+
+impl<Chain: ChainHandle> SubmitDelay<Chain> {
+    /**
+       Delay the broadcast of the transactions submitted by the relayer
+       to the chain of the given handle by the given amount.
+    */
+    pub fn new(handle: Chain, delay: Duration) -> Result<Self, Error> {
+        handle.set_submit_delay(Some(delay))?;
+
+        Ok(Self { handle })
+    }
+
+    /**
+       The number of broadcasts delayed so far, including the ones which
+       are still waiting for their delay to elapse.
+    */
+    pub fn delayed_submissions(&self) -> Result<u64, Error> {
+        Ok(self.handle.query_delayed_submissions()?)
+    }
+}
+
+impl<Chain: ChainHandle> Drop for SubmitDelay<Chain> {
+    fn drop(&mut self) {
+        let _ = self.handle.set_submit_delay(None);
+    }
+}
@@ -9,15 +9,17 @@ use eyre::eyre;
 use ibc_relayer_types::events::IbcEvent;
 
 use ibc_proto::google::protobuf::Any;
+use ibc_relayer::chain::cosmos::request_rate::PacedRpcClient;
 use ibc_relayer::chain::cosmos::tx::batched_send_tx;
 use ibc_relayer::chain::cosmos::tx::simple_send_tx;
 use ibc_relayer::chain::cosmos::types::config::TxConfig;
 use ibc_relayer::transfer::build_transfer_message as raw_build_transfer_message;
 use ibc_relayer::transfer::TransferError;
 use ibc_relayer_types::applications::transfer::error::Error as Ics20Error;
+use ibc_relayer_types::core::ics02_client::height::Height;
 use ibc_relayer_types::core::ics04_channel::timeout::TimeoutHeight;
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use ibc_relayer_types::timestamp::Timestamp;
-use tendermint_rpc::HttpClient;
 
 use crate::chain::exec::simple_exec;
 use crate::error::{handle_generic_error, Error};
@@ -39,6 +41,28 @@ pub fn build_transfer_message<SrcChain, DstChain>(
         .add(timeout)
         .map_err(handle_generic_error)?;
 
+    build_transfer_message_with_timeout(
+        port_id,
+        channel_id,
+        sender,
+        recipient,
+        token,
+        TimeoutHeight::no_timeout(),
+        timeout_timestamp,
+        memo,
+    )
+}
+
+fn build_transfer_message_with_timeout<SrcChain, DstChain>(
+    port_id: &TaggedPortIdRef<'_, SrcChain, DstChain>,
+    channel_id: &TaggedChannelIdRef<'_, SrcChain, DstChain>,
+    sender: &MonoTagged<SrcChain, &Wallet>,
+    recipient: &MonoTagged<DstChain, &WalletAddress>,
+    token: &TaggedTokenRef<'_, SrcChain>,
+    timeout_height: TimeoutHeight,
+    timeout_timestamp: Timestamp,
+    memo: Option<String>,
+) -> Result<Any, Error> {
     let sender = sender
         .value()
         .address
@@ -59,12 +83,30 @@ pub fn build_transfer_message<SrcChain, DstChain>(
         token.value().denom.to_string(),
         sender,
         receiver,
-        TimeoutHeight::no_timeout(),
+        timeout_height,
         timeout_timestamp,
         memo,
     ))
 }
 
+/// Queries the latest height of the chain with the given ID from its RPC endpoint.
+pub async fn query_latest_height<Chain>(
+    rpc_client: MonoTagged<Chain, &PacedRpcClient>,
+    chain_id: &ChainId,
+) -> Result<Height, Error> {
+    let status = rpc_client
+        .value()
+        .status()
+        .await
+        .map_err(handle_generic_error)?;
+
+    Height::new(
+        chain_id.version(),
+        status.sync_info.latest_block_height.value(),
+    )
+    .map_err(handle_generic_error)
+}
+
 /**
    Perform a simplified version of IBC token transfer for testing purpose.
 
@@ -81,9 +123,13 @@ pub fn build_transfer_message<SrcChain, DstChain>(
 
    If tests require explicit timeout, they should explicitly construct the
    transfer message and pass it to send_tx.
+
+   Alternatively, the transfer can time out at the given `timeout_height` of the
+   destination chain instead, with no timeout timestamp. Specifying both `timeout`
+   and `timeout_height` is an error.
 */
 pub async fn ibc_token_transfer<SrcChain, DstChain>(
-    rpc_client: MonoTagged<SrcChain, &HttpClient>,
+    rpc_client: MonoTagged<SrcChain, &PacedRpcClient>,
     tx_config: &MonoTagged<SrcChain, &TxConfig>,
     port_id: &TaggedPortIdRef<'_, SrcChain, DstChain>,
     channel_id: &TaggedChannelIdRef<'_, SrcChain, DstChain>,
@@ -92,16 +138,34 @@ pub async fn ibc_token_transfer<SrcChain, DstChain>(
     token: &TaggedTokenRef<'_, SrcChain>,
     memo: Option<String>,
     timeout: Option<Duration>,
+    timeout_height: Option<Height>,
 ) -> Result<(), Error> {
-    let message = build_transfer_message(
-        port_id,
-        channel_id,
-        sender,
-        recipient,
-        token,
-        timeout.unwrap_or(Duration::from_secs(60)),
-        memo.clone(),
-    )?;
+    let message = match (timeout, timeout_height) {
+        (Some(_), Some(_)) => {
+            return Err(Error::generic(eyre!(
+                "cannot specify both a timeout and a timeout height for a transfer"
+            )));
+        }
+        (None, Some(timeout_height)) => build_transfer_message_with_timeout(
+            port_id,
+            channel_id,
+            sender,
+            recipient,
+            token,
+            TimeoutHeight::At(timeout_height),
+            Timestamp::none(),
+            memo,
+        )?,
+        (timeout, None) => build_transfer_message(
+            port_id,
+            channel_id,
+            sender,
+            recipient,
+            token,
+            timeout.unwrap_or(Duration::from_secs(60)),
+            memo,
+        )?,
+    };
 
     let key = &sender
         .value()
@@ -217,7 +281,7 @@ pub fn ibc_namada_token_transfer(
 }
 
 pub async fn batched_ibc_token_transfer<SrcChain, DstChain>(
-    rpc_client: MonoTagged<SrcChain, &HttpClient>,
+    rpc_client: MonoTagged<SrcChain, &PacedRpcClient>,
     tx_config: &MonoTagged<SrcChain, &TxConfig>,
     port_id: &TaggedPortIdRef<'_, SrcChain, DstChain>,
     channel_id: &TaggedChannelIdRef<'_, SrcChain, DstChain>,
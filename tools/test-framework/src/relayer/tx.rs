@@ -1,14 +1,17 @@
 use core::str::FromStr;
 use core::time::Duration;
+use std::sync::Arc;
 
 use http::uri::Uri;
 
 use ibc_proto::cosmos::tx::v1beta1::Fee;
-use ibc_relayer::chain::cosmos::gas::calculate_fee;
+use ibc_relayer::chain::cosmos::fee_denom::FeeDenomCandidates;
+use ibc_relayer::chain::cosmos::gas::{calculate_fee, GasEstimateSampler};
 use ibc_relayer::chain::cosmos::types::config::TxConfig;
 use ibc_relayer::chain::cosmos::types::gas::GasConfig;
+use ibc_relayer::chain::cosmos::wait::TxConfirmation;
 use ibc_relayer::config::dynamic_gas::DynamicGasPrice;
-use ibc_relayer::config::{AddressType, GasPrice};
+use ibc_relayer::config::{default, AddressType, GasPrice};
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use tendermint_rpc::Url;
 
@@ -49,9 +52,16 @@ pub fn gas_config_for_test(native_token: String, chain_type: ChainType) -> GasCo
         max_gas,
         gas_multiplier,
         gas_price,
+        gas_price_decimals: None,
         max_fee,
         fee_granter,
         dynamic_gas_price,
+        gas_sampler: Arc::new(GasEstimateSampler::new(Default::default())),
+        tx_priority: Default::default(),
+        shadow_gas_strategy: None,
+        reference_gas_price: None,
+        txfees_gas_price: None,
+        base_fee_history: Default::default(),
     }
 }
 
@@ -67,19 +77,42 @@ pub fn new_tx_config_for_test(
     let grpc_address = Uri::from_str(&raw_grpc_address).map_err(handle_generic_error)?;
     let gas_config = gas_config_for_test(native_token, chain_type);
     let rpc_timeout = Duration::from_secs(30);
+    let max_concurrent_tx_confirmations = default::max_concurrent_tx_confirmations();
     let max_msg_num = Default::default();
     let max_tx_size = Default::default();
     let extension_options = Default::default();
+    let fee_denom_candidates = Arc::new(FeeDenomCandidates::new(
+        &gas_config,
+        [&gas_config.gas_price],
+        default::max_block_time(),
+    ));
 
     Ok(TxConfig {
-        chain_id,
+        chain_id: chain_id.clone(),
         gas_config,
         rpc_address,
         grpc_address,
+        grpc_session: None,
+        request_limiter: None,
         rpc_timeout,
+        max_concurrent_tx_confirmations,
         address_type,
+        account_query: None,
         max_msg_num,
         max_tx_size,
+        block_gas_usage: None,
+        min_tx_interval: None,
+        daily_fee_budget: None,
+        chain_id_drift: Default::default(),
+        fee_denom_balances: Default::default(),
+        fee_denom_candidates,
+        batch_failure_mode: Default::default(),
         extension_options,
+        multisig: None,
+        tx_confirmation: TxConfirmation::new(chain_id, Default::default()),
+        retry_strategy: Default::default(),
+        attribution_memo: None,
+        #[cfg(feature = "submit-delay")]
+        submit_delay: Default::default(),
     })
 }
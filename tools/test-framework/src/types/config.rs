@@ -36,6 +36,14 @@ pub struct TestConfig {
 
     pub ipv6_grpc: bool,
 
+    /**
+       Extra arguments given to the `start` command of the chains, after the
+       ones of their [`ChainType`](crate::chain::chain_type::ChainType).
+       Empty by default, can be set by the tests to eg. make the chains
+       prune their state.
+    */
+    pub extra_start_args: Vec<String>,
+
     /**
        The directory path for storing the chain and relayer files.
        Defaults to `"data"`. This can be overridden with the `$CHAIN_STORE_DIR`
@@ -175,6 +175,9 @@ impl FullNode {
                 event_source: config::EventSourceMode::Push {
                     url: WebSocketClientUrl::from_str(&self.chain_driver.websocket_address())?,
                     batch_delay: config::default::batch_delay(),
+                    queries: vec![],
+                    reconnect_backoff: config::default::reconnect_backoff(),
+                    max_reconnect_backoff: config::default::max_reconnect_backoff(),
                 },
                 rpc_timeout: config::default::rpc_timeout(),
                 trusted_node: false,
@@ -188,23 +191,40 @@ impl FullNode {
                 max_gas: Some(3000000),
                 gas_adjustment: None,
                 gas_multiplier: Some(GasMultiplier::unsafe_new(1.5)),
+                gas_price_decimals: None,
                 dynamic_gas_price,
                 fee_granter: None,
                 max_msg_num: Default::default(),
                 max_tx_size: Default::default(),
                 max_grpc_decoding_size: config::default::max_grpc_decoding_size(),
                 query_packets_chunk_size: config::default::query_packets_chunk_size(),
+                max_concurrent_tx_confirmations: config::default::max_concurrent_tx_confirmations(),
                 max_block_time: Duration::from_secs(30),
                 clock_drift: Duration::from_secs(5),
                 trusting_period: Some(Duration::from_secs(14 * 24 * 3600)),
                 client_refresh_rate: config::default::client_refresh_rate(),
+                trusting_period_source: Default::default(),
+                timeout_timestamp_unit: Default::default(),
+                grpc_height_header: Default::default(),
+                timeout_batch: None,
+                successor_chain_id: None,
+                max_concurrent_client_updates: None,
+                max_parallel_header_builds: None,
+                max_concurrent_client_state_queries:
+                    config::default::max_concurrent_client_state_queries(),
+                detect_misbehaviour: None,
+                multisig: None,
+                tx_confirmation_strategy: Default::default(),
                 ccv_consumer_chain: false,
                 trust_threshold: Default::default(),
-                gas_price,
+                gas_price: gas_price.into(),
                 packet_filter: Default::default(),
                 address_type: chain_type.address_type(),
+                signer_encoding: Default::default(),
                 memo_prefix: Default::default(),
                 memo_overwrite: None,
+                memo_overrides: BTreeMap::new(),
+                max_memo_bytes: None,
                 proof_specs: Default::default(),
                 extension_options: Default::default(),
                 sequential_batch_tx: false,
@@ -212,6 +232,35 @@ impl FullNode {
                 clear_interval: None,
                 excluded_sequences: ExcludedSequences::new(BTreeMap::new()),
                 allow_ccq: true,
+                gas_estimation_sampling: Default::default(),
+                tx_priority: Default::default(),
+                retry_strategy: Default::default(),
+                shadow_gas_strategy: None,
+                reference_gas_price: None,
+                txfees_gas_price: None,
+                block_gas_usage: None,
+                acquire_fee_denom_command: Vec::new(),
+                extra_fee_denoms: Vec::new(),
+                min_tx_interval: None,
+                receipt_recheck_delay: None,
+                daily_fee_budget: None,
+                sticky_session: None,
+                relayer_attribution_memo: None,
+                max_requests_per_second: None,
+                recv_signer_whitelist_query: None,
+                denom_key_names: Default::default(),
+                signing_accounts: Vec::new(),
+                skip_empty_memo_channels: Vec::new(),
+                pruned_height_handling: Default::default(),
+                account_query: None,
+                min_confirmation_blocks: None,
+                batch_failure_mode: Default::default(),
+                proof_height_strategy: Default::default(),
+                packet_commitment_source: Default::default(),
+                local_trust_threshold: None,
+                channel_overrides: Default::default(),
+                canaries: Default::default(),
+                packet_data_encodings: Default::default(),
             }),
             TestedChainType::Namada => config::ChainConfig::Namada(CosmosSdkConfig {
                 id: self.chain_driver.chain_id.clone(),
@@ -220,6 +269,9 @@ impl FullNode {
                 event_source: config::EventSourceMode::Push {
                     url: WebSocketClientUrl::from_str(&self.chain_driver.websocket_address())?,
                     batch_delay: config::default::batch_delay(),
+                    queries: vec![],
+                    reconnect_backoff: config::default::reconnect_backoff(),
+                    max_reconnect_backoff: config::default::max_reconnect_backoff(),
                 },
                 rpc_timeout: config::default::rpc_timeout(),
                 trusted_node: false,
@@ -233,23 +285,40 @@ impl FullNode {
                 max_gas: Some(4000000),
                 gas_adjustment: None,
                 gas_multiplier: Some(GasMultiplier::unsafe_new(1.2)),
+                gas_price_decimals: None,
                 dynamic_gas_price: DynamicGasPrice::default(),
                 fee_granter: None,
                 max_msg_num: Default::default(),
                 max_tx_size: Default::default(),
                 max_grpc_decoding_size: config::default::max_grpc_decoding_size(),
                 query_packets_chunk_size: config::default::query_packets_chunk_size(),
+                max_concurrent_tx_confirmations: config::default::max_concurrent_tx_confirmations(),
                 max_block_time: Duration::from_secs(30),
                 clock_drift: Duration::from_secs(5),
                 trusting_period: Some(Duration::from_secs(1999)),
                 client_refresh_rate: config::default::client_refresh_rate(),
+                trusting_period_source: Default::default(),
+                timeout_timestamp_unit: Default::default(),
+                grpc_height_header: Default::default(),
+                timeout_batch: None,
+                successor_chain_id: None,
+                max_concurrent_client_updates: None,
+                max_parallel_header_builds: None,
+                max_concurrent_client_state_queries:
+                    config::default::max_concurrent_client_state_queries(),
+                detect_misbehaviour: None,
+                multisig: None,
+                tx_confirmation_strategy: Default::default(),
                 ccv_consumer_chain: false,
                 trust_threshold: Default::default(),
-                gas_price,
+                gas_price: gas_price.into(),
                 packet_filter: Default::default(),
                 address_type: chain_type.address_type(),
+                signer_encoding: Default::default(),
                 memo_prefix: Default::default(),
                 memo_overwrite: None,
+                memo_overrides: BTreeMap::new(),
+                max_memo_bytes: None,
                 proof_specs: Default::default(),
                 extension_options: Default::default(),
                 sequential_batch_tx: false,
@@ -257,6 +326,35 @@ impl FullNode {
                 clear_interval: None,
                 excluded_sequences: ExcludedSequences::new(BTreeMap::new()),
                 allow_ccq: false,
+                gas_estimation_sampling: Default::default(),
+                tx_priority: Default::default(),
+                retry_strategy: Default::default(),
+                shadow_gas_strategy: None,
+                reference_gas_price: None,
+                txfees_gas_price: None,
+                block_gas_usage: None,
+                acquire_fee_denom_command: Vec::new(),
+                extra_fee_denoms: Vec::new(),
+                min_tx_interval: None,
+                receipt_recheck_delay: None,
+                daily_fee_budget: None,
+                sticky_session: None,
+                relayer_attribution_memo: None,
+                max_requests_per_second: None,
+                recv_signer_whitelist_query: None,
+                denom_key_names: Default::default(),
+                signing_accounts: Vec::new(),
+                skip_empty_memo_channels: Vec::new(),
+                pruned_height_handling: Default::default(),
+                account_query: None,
+                min_confirmation_blocks: None,
+                batch_failure_mode: Default::default(),
+                proof_height_strategy: Default::default(),
+                packet_commitment_source: Default::default(),
+                local_trust_threshold: None,
+                channel_overrides: Default::default(),
+                canaries: Default::default(),
+                packet_data_encodings: Default::default(),
             }),
         };
 